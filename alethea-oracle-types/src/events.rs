@@ -0,0 +1,252 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Oracle Registry events for cross-chain streaming
+//! 
+//! Shared by the registry, which emits them, and subscribers such as
+//! alethea-sdk, which decode them without depending on the registry.
+
+use linera_sdk::linera_base_types::{Account, AccountOwner, Amount, ChainId, Timestamp};
+use serde::{Deserialize, Serialize};
+
+/// Stream name for Oracle events - used for cross-chain event subscription
+pub const ORACLE_STREAM_NAME: &str = "oracle_events";
+
+/// Oracle events for real-time cross-chain notifications
+/// 
+/// These events are emitted using `runtime.emit()` and can be received by
+/// other chains that subscribe using `runtime.subscribe_to_events()`.
+/// 
+/// ## Usage
+/// 
+/// To subscribe to oracle events from another contract:
+/// ```ignore
+/// // In instantiate or any operation
+/// self.runtime.subscribe_to_events(
+///     oracle_chain_id,
+///     oracle_app_id,
+///     StreamName::from(ORACLE_STREAM_NAME),
+/// );
+/// ```
+/// 
+/// To process received events:
+/// ```ignore
+/// async fn process_streams(&mut self, updates: Vec<StreamUpdate>) {
+///     for update in updates {
+///         for index in update.previous_index..update.next_index {
+///             let event: OracleEvent = self.runtime.read_event(
+///                 update.chain_id,
+///                 update.stream_id.stream_name.clone(),
+///                 index,
+///             );
+///             // Handle event...
+///         }
+///     }
+/// }
+/// ```
+/// 
+/// `alethea_sdk::decode_oracle_events` wraps this loop and skips events that
+/// fail to decode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum OracleEvent {
+    /// Emitted when a new query is created
+    QueryCreated {
+        query_id: u64,
+        description: String,
+        outcomes: Vec<String>,
+        deadline: Timestamp,
+        creator: ChainId,
+        min_votes: usize,
+    },
+    
+    /// Emitted when a query's decision strategy is changed before voting
+    QueryStrategyUpdated {
+        query_id: u64,
+        old_strategy: String,
+        new_strategy: String,
+        updated_by: ChainId,
+    },
+    
+    /// Emitted when a query is cancelled before resolution
+    QueryCancelled {
+        query_id: u64,
+        cancelled_by: ChainId,
+        released_voters: usize,
+    },
+    
+    /// Emitted when UnlockQueryStakes releases another batch of a query's stakes
+    QueryStakesUnlocked {
+        query_id: u64,
+        released_voters: usize,
+        remaining: usize,
+    },
+    
+    /// Emitted when an admin manually advances a query's voting phase
+    QueryPhaseAdvanced {
+        query_id: u64,
+        from_phase: String,
+        to_phase: String,
+        advanced_by: ChainId,
+    },
+    
+    /// Emitted when a voter sets or clears the operator allowed to claim for them
+    ClaimOperatorSet {
+        voter_chain: ChainId,
+        operator: Option<ChainId>,
+    },
+    
+    /// Emitted when a timelocked parameter change is proposed
+    ParametersProposed {
+        min_stake: Amount,
+        min_votes_default: usize,
+        effective_at: Timestamp,
+        proposed_by: ChainId,
+    },
+    
+    /// Emitted when a resolution would slash more than the per-resolution
+    /// limit; the resolution is aborted and the protocol paused
+    SlashCircuitBreakerTripped {
+        query_id: u64,
+        projected_slash: Amount,
+        total_stake: Amount,
+        max_slash_per_resolution_bps: u32,
+    },
+    
+    /// Emitted when a voter forfeits their locked stake to exit immediately
+    VoterForfeitedAndExited {
+        voter_chain: ChainId,
+        forfeited: Amount,
+        stake_returned: Amount,
+        forfeited_queries: Vec<u64>,
+    },
+    
+    /// Emitted when treasury tokens are released to the treasury account
+    TreasurySwept {
+        amount: Amount,
+        treasury_account: Account,
+        remaining_treasury: Amount,
+    },
+    
+    /// Emitted when the admin transfers tokens into the reward pool
+    RewardPoolFunded {
+        amount: Amount,
+        funder: AccountOwner,
+        reward_pool: Amount,
+    },
+    
+    /// Emitted when the admin moves tokens into or out of the treasury
+    TreasuryAdjusted {
+        amount: Amount,
+        increase: bool,
+        owner: AccountOwner,
+        protocol_treasury: Amount,
+    },
+    
+    /// Emitted when expired pending rewards are returned to the reward pool
+    UnclaimedRewardsReclaimed {
+        voters: Vec<ChainId>,
+        amount: Amount,
+        reward_pool: Amount,
+    },
+    
+    /// Emitted when the admin resolves a query from external data
+    QueryResolvedExternally {
+        query_id: u64,
+        result: String,
+        source: String,
+        resolved_at: Timestamp,
+    },
+    
+    /// Emitted when a cross-chain message fails and is logged
+    MessageFailed {
+        kind: String,
+        sender: Option<ChainId>,
+        error: String,
+    },
+    
+    /// Emitted when a query is resolved
+    QueryResolved {
+        query_id: u64,
+        result: String,
+        resolved_at: Timestamp,
+        total_votes: usize,
+        correct_voters: usize,
+    },
+    
+    /// Emitted when a query expires without resolution
+    QueryExpired {
+        query_id: u64,
+        expired_at: Timestamp,
+        votes_received: usize,
+        min_votes_required: usize,
+    },
+    
+    /// Emitted when a voter registers
+    VoterRegistered {
+        voter_chain: ChainId,
+        stake: Amount,
+        name: Option<String>,
+    },
+    
+    /// Emitted when a voter deregisters
+    VoterDeregistered {
+        voter_chain: ChainId,
+        stake_returned: Amount,
+    },
+    
+    /// Emitted when a voter withdraws stake through the emergency path
+    EmergencyWithdrawal {
+        voter_chain: ChainId,
+        amount: Amount,
+        penalty: Amount,
+        withdrawn_queries: Vec<u64>,
+    },
+    
+    /// Emitted when a vote is committed (phase 1)
+    VoteCommitted {
+        query_id: u64,
+        voter_chain: ChainId,
+        commit_hash: String,
+    },
+    
+    /// Emitted when a vote is revealed (phase 2)
+    VoteRevealed {
+        query_id: u64,
+        voter_chain: ChainId,
+        value: String,
+    },
+    
+    /// Emitted when a direct vote is submitted (no commit/reveal)
+    VoteSubmitted {
+        query_id: u64,
+        voter_chain: ChainId,
+        value: String,
+    },
+    
+    /// Emitted when rewards are claimed
+    RewardsClaimed {
+        voter_chain: ChainId,
+        amount: Amount,
+    },
+    
+    /// Emitted when protocol parameters are updated
+    ParametersUpdated {
+        min_stake: Amount,
+        min_votes_default: usize,
+        updated_by: ChainId,
+    },
+    
+    /// Emitted when protocol is paused/unpaused
+    ProtocolStatusChanged {
+        is_paused: bool,
+        changed_by: ChainId,
+    },
+    
+    /// Emitted when stake is updated
+    StakeUpdated {
+        voter_chain: ChainId,
+        new_stake: Amount,
+        change: Amount,
+        is_increase: bool,
+    },
+}
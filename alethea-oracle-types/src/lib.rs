@@ -17,6 +17,7 @@
 pub mod registry;
 pub mod voter;
 pub mod constants;
+pub mod events;
 pub mod graphql_types;
 
 // Re-export new protocol types
@@ -4,11 +4,27 @@
 //! Alethea Client Implementation
 
 use linera_sdk::{
-    linera_base_types::{ApplicationId, Timestamp},
-    ContractRuntime, Contract,
+    linera_base_types::{AccountOwner, Amount, ApplicationId, ChainId, ServiceAbi, Timestamp},
+    ContractRuntime, Contract, Service, ServiceRuntime,
 };
 use alethea_oracle_types::RegistryMessage;
-use crate::{AletheaError, Result, ResolutionResult, MarketRegistration};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::{AletheaError, Network, Result, ResolutionResult, MarketRegistration, TotalPosition};
+
+/// GraphQL request sent to another application's service
+#[derive(Debug, Serialize, Deserialize)]
+struct GraphQlRequest {
+    query: String,
+}
+
+/// ABI for raw GraphQL queries to the token and registry services
+struct GraphQlAbi;
+
+impl ServiceAbi for GraphQlAbi {
+    type Query = GraphQlRequest;
+    type QueryResponse = Value;
+}
 
 /// Alethea Oracle Client
 /// 
@@ -16,7 +32,7 @@ use crate::{AletheaError, Result, ResolutionResult, MarketRegistration};
 /// 
 /// # Example
 /// 
-/// ```rust,no_run
+/// ```ignore
 /// use alethea_sdk::AletheaClient;
 /// 
 /// // Create client
@@ -32,16 +48,28 @@ use crate::{AletheaError, Result, ResolutionResult, MarketRegistration};
 /// ).await?;
 /// ```
 pub struct AletheaClient {
+    network: Network,
     registry_id: Option<ApplicationId>,
+    token_id: Option<ApplicationId>,
 }
 
 impl AletheaClient {
     /// Create new client with canonical registry
     /// 
-    /// Uses the well-known Alethea Oracle Registry ApplicationId.
+    /// Uses the Alethea Oracle Registry deployed on the default network (Testnet).
     pub fn new() -> Self {
+        Self::for_network(Network::default())
+    }
+    
+    /// Create client targeting the registry deployed on `network`
+    /// 
+    /// # Arguments
+    /// * `network` - Network whose registry deployment to use
+    pub fn for_network(network: Network) -> Self {
         Self {
-            registry_id: None, // Will use canonical ID
+            network,
+            registry_id: None, // Will use the network's registry ID
+            token_id: None,
         }
     }
     
@@ -51,19 +79,40 @@ impl AletheaClient {
     /// * `registry_id` - Custom registry ApplicationId
     pub fn with_registry(registry_id: ApplicationId) -> Self {
         Self {
+            network: Network::default(),
             registry_id: Some(registry_id),
+            token_id: None,
         }
     }
     
+    /// Set the ALTH token application (required for `total_position`)
+    /// 
+    /// # Arguments
+    /// * `token_id` - Token ApplicationId
+    pub fn with_token(mut self, token_id: ApplicationId) -> Self {
+        self.token_id = Some(token_id);
+        self
+    }
+    
     /// Get registry ApplicationId
     fn get_registry_id(&self) -> Result<ApplicationId> {
         if let Some(id) = self.registry_id {
             Ok(id)
         } else {
-            crate::canonical_registry_id()
+            self.network.registry_id()
         }
     }
     
+    /// Get the network this client targets
+    pub fn network(&self) -> Network {
+        self.network
+    }
+    
+    /// Get the chain hosting the registry, if known for the network
+    pub fn registry_chain(&self) -> Option<ChainId> {
+        self.network.registry_chain()
+    }
+    
     /// Request market resolution
     /// 
     /// Sends a market registration request to the Oracle Registry.
@@ -81,7 +130,7 @@ impl AletheaClient {
     /// 
     /// # Example
     /// 
-    /// ```rust,no_run
+    /// ```ignore
     /// let market_id = 123u64;
     /// client.request_resolution(
     ///     &runtime,
@@ -128,7 +177,7 @@ impl AletheaClient {
     /// 
     /// # Example
     /// 
-    /// ```rust,no_run
+    /// ```ignore
     /// client.request_binary_resolution(
     ///     &runtime,
     ///     "Will BTC reach $100k by EOY?".to_string(),
@@ -166,7 +215,7 @@ impl AletheaClient {
     /// 
     /// # Example
     /// 
-    /// ```rust,no_run
+    /// ```ignore
     /// async fn execute_message(&mut self, message: Message) {
     ///     if let Some(result) = client.handle_resolution(message) {
     ///         // Market resolved!
@@ -205,6 +254,97 @@ impl AletheaClient {
     pub fn extract_market_id(&self, result: &ResolutionResult) -> Option<u64> {
         result.market_id_from_callback()
     }
+    
+    /// Get an account's combined token position
+    /// 
+    /// Queries the token service for the liquid balance of `owner` and the
+    /// registry service for the stake, locked stake and pending rewards of
+    /// `voter_chain`. Service queries read application state on the current
+    /// chain, so call this from a service running on the chain that hosts
+    /// both applications.
+    /// 
+    /// # Arguments
+    /// * `runtime` - Service runtime
+    /// * `owner` - Token account owner
+    /// * `voter_chain` - Chain the owner is registered as voter with
+    /// 
+    /// # Example
+    /// 
+    /// ```ignore
+    /// let client = AletheaClient::with_registry(registry_id).with_token(token_id);
+    /// let position = client.total_position(&runtime, owner, voter_chain)?;
+    /// println!("Total: {}", position.total());
+    /// ```
+    pub fn total_position<S: Service>(
+        &self,
+        runtime: &ServiceRuntime<S>,
+        owner: AccountOwner,
+        voter_chain: ChainId,
+    ) -> Result<TotalPosition> {
+        let token_id = self.token_id.ok_or(AletheaError::TokenNotConfigured)?;
+        let registry_id = self.get_registry_id()?;
+        
+        let token_response = runtime.query_application(
+            token_id.with_abi::<GraphQlAbi>(),
+            &GraphQlRequest {
+                query: format!("query {{ balance(owner: \"{}\") }}", owner),
+            },
+        );
+        let registry_response = runtime.query_application(
+            registry_id.with_abi::<GraphQlAbi>(),
+            &GraphQlRequest {
+                query: format!(
+                    "query {{ voter(address: \"{}\") {{ stake lockedStake pendingRewards }} }}",
+                    voter_chain
+                ),
+            },
+        );
+        
+        aggregate_position(&token_response, &registry_response)
+    }
+}
+
+/// Combine token and registry GraphQL responses into a TotalPosition
+/// 
+/// An unregistered voter (`voter: null`) has nothing staked.
+fn aggregate_position(token_response: &Value, registry_response: &Value) -> Result<TotalPosition> {
+    let liquid = parse_amount(graphql_data(token_response)?.get("balance"))?;
+    
+    let voter = graphql_data(registry_response)?
+        .get("voter")
+        .filter(|voter| !voter.is_null());
+    let (staked, locked, pending_rewards) = match voter {
+        Some(voter) => (
+            parse_amount(voter.get("stake"))?,
+            parse_amount(voter.get("lockedStake"))?,
+            parse_amount(voter.get("pendingRewards"))?,
+        ),
+        None => (Amount::ZERO, Amount::ZERO, Amount::ZERO),
+    };
+    
+    Ok(TotalPosition {
+        liquid,
+        staked,
+        locked,
+        pending_rewards,
+    })
+}
+
+/// Extract `data` from a GraphQL response, surfacing the first error
+fn graphql_data(response: &Value) -> Result<&Value> {
+    if let Some(error) = response.get("errors").and_then(Value::as_array).and_then(|errors| errors.first()) {
+        let message = error.get("message").and_then(Value::as_str).unwrap_or("unknown error");
+        return Err(AletheaError::QueryFailed(message.to_string()));
+    }
+    response.get("data").ok_or(AletheaError::InvalidResponse)
+}
+
+/// Parse an amount returned as a string (e.g. "12.5")
+fn parse_amount(value: Option<&Value>) -> Result<Amount> {
+    value
+        .and_then(Value::as_str)
+        .and_then(|amount| amount.parse().ok())
+        .ok_or(AletheaError::InvalidResponse)
 }
 
 impl Default for AletheaClient {
@@ -217,16 +357,40 @@ impl Default for AletheaClient {
 mod tests {
     use super::*;
 
+    fn app_id(n: u8) -> ApplicationId {
+        ApplicationId::new(format!("{:064x}", n).parse().expect("valid hash"))
+    }
+
     #[test]
     fn test_client_creation() {
         let client = AletheaClient::new();
         assert!(client.registry_id.is_none());
         
-        let custom_id = ApplicationId::from([1u8; 64]);
+        let custom_id = app_id(1);
         let custom_client = AletheaClient::with_registry(custom_id);
         assert_eq!(custom_client.registry_id, Some(custom_id));
     }
 
+    #[test]
+    fn test_client_for_network() {
+        let client = AletheaClient::new();
+        assert_eq!(client.network(), Network::Testnet);
+        assert_eq!(client.get_registry_id().unwrap(), Network::Testnet.registry_id().unwrap());
+        
+        let mainnet = AletheaClient::for_network(Network::Mainnet);
+        assert_eq!(mainnet.network(), Network::Mainnet);
+        assert!(matches!(
+            mainnet.get_registry_id(),
+            Err(AletheaError::NetworkNotDeployed(Network::Mainnet))
+        ));
+        assert_eq!(mainnet.registry_chain(), None);
+        
+        // A custom registry overrides the network's deployment
+        let custom_id = app_id(1);
+        let custom = AletheaClient::with_registry(custom_id);
+        assert_eq!(custom.get_registry_id().unwrap(), custom_id);
+    }
+
     #[test]
     fn test_handle_resolution() {
         let client = AletheaClient::new();
@@ -251,13 +415,13 @@ mod tests {
     fn test_handle_non_resolution_message() {
         let client = AletheaClient::new();
         
-        let message = RegistryMessage::VoteRequest {
+        let message = RegistryMessage::CreateQueryFromMarket {
             market_id: 123,
             question: "Test?".to_string(),
             outcomes: vec!["Yes".to_string(), "No".to_string()],
             deadline: Timestamp::from(0),
-            commit_deadline: Timestamp::from(0),
-            reveal_deadline: Timestamp::from(0),
+            callback_chain: format!("{:064x}", 1).parse().expect("valid chain ID"),
+            callback_data: vec![],
         };
         
         let result = client.handle_resolution(message);
@@ -278,4 +442,66 @@ mod tests {
         
         assert_eq!(client.extract_market_id(&result), Some(456));
     }
+
+    #[test]
+    fn test_aggregate_position() {
+        let token_response = serde_json::json!({ "data": { "balance": "250." } });
+        let registry_response = serde_json::json!({
+            "data": {
+                "voter": {
+                    "stake": "1000.",
+                    "lockedStake": "100.",
+                    "pendingRewards": "12.5"
+                }
+            }
+        });
+        
+        let position = aggregate_position(&token_response, &registry_response).unwrap();
+        assert_eq!(position.liquid, Amount::from_tokens(250));
+        assert_eq!(position.staked, Amount::from_tokens(1000));
+        assert_eq!(position.locked, Amount::from_tokens(100));
+        assert_eq!(position.pending_rewards, "12.5".parse::<Amount>().unwrap());
+        assert_eq!(position.total(), "1262.5".parse::<Amount>().unwrap());
+    }
+
+    #[test]
+    fn test_aggregate_position_unregistered_voter() {
+        let token_response = serde_json::json!({ "data": { "balance": "40." } });
+        let registry_response = serde_json::json!({ "data": { "voter": null } });
+        
+        let position = aggregate_position(&token_response, &registry_response).unwrap();
+        assert_eq!(position.liquid, Amount::from_tokens(40));
+        assert_eq!(position.staked, Amount::ZERO);
+        assert_eq!(position.total(), Amount::from_tokens(40));
+    }
+
+    #[test]
+    fn test_aggregate_position_errors() {
+        let token_response = serde_json::json!({ "data": { "balance": "40." } });
+        let registry_error = serde_json::json!({
+            "data": null,
+            "errors": [{ "message": "Invalid chain ID format" }]
+        });
+        assert!(matches!(
+            aggregate_position(&token_response, &registry_error),
+            Err(AletheaError::QueryFailed(message)) if message == "Invalid chain ID format"
+        ));
+        
+        let malformed = serde_json::json!({ "data": { "balance": 40 } });
+        let registry_response = serde_json::json!({ "data": { "voter": null } });
+        assert!(matches!(
+            aggregate_position(&malformed, &registry_response),
+            Err(AletheaError::InvalidResponse)
+        ));
+    }
+    
+    #[test]
+    fn test_total_position_requires_token() {
+        let client = AletheaClient::with_registry(app_id(1));
+        assert!(client.token_id.is_none());
+        
+        let token_id = app_id(2);
+        let client = client.with_token(token_id);
+        assert_eq!(client.token_id, Some(token_id));
+    }
 }
@@ -0,0 +1,147 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Decoding Oracle Registry events from subscribed streams
+
+use linera_sdk::{
+    linera_base_types::{ChainId, StreamName, StreamUpdate},
+    Contract, ContractRuntime,
+};
+pub use alethea_oracle_types::events::{OracleEvent, ORACLE_STREAM_NAME};
+
+/// Source of raw stream events, as read in `process_streams`
+/// 
+/// Implemented for `ContractRuntime`; tests can supply events directly.
+pub trait OracleEventSource {
+    /// BCS bytes of event `index` on `chain_id`'s `stream_name` stream
+    fn read_event_bytes(&mut self, chain_id: ChainId, stream_name: StreamName, index: u32) -> Vec<u8>;
+}
+
+impl<C: Contract> OracleEventSource for ContractRuntime<C> {
+    fn read_event_bytes(&mut self, chain_id: ChainId, stream_name: StreamName, index: u32) -> Vec<u8> {
+        let event = self.read_event(chain_id, stream_name, index);
+        bcs::to_bytes(&event).unwrap_or_default()
+    }
+}
+
+/// Decode the Oracle Registry events announced by `updates`
+/// 
+/// Reads events `previous_index..next_index` of every update on the oracle
+/// stream, in order. Updates for other streams and events that do not decode
+/// as an `OracleEvent` are skipped.
+/// 
+/// # Example
+/// 
+/// ```ignore
+/// async fn process_streams(&mut self, updates: Vec<StreamUpdate>) {
+///     for event in alethea_sdk::decode_oracle_events(updates, &mut self.runtime) {
+///         // Handle event...
+///     }
+/// }
+/// ```
+pub fn decode_oracle_events(
+    updates: Vec<StreamUpdate>,
+    runtime: &mut impl OracleEventSource,
+) -> Vec<OracleEvent> {
+    let mut events = Vec::new();
+    for update in updates {
+        if update.stream_id.stream_name != StreamName::from(ORACLE_STREAM_NAME) {
+            continue;
+        }
+        for index in update.previous_index..update.next_index {
+            let bytes = runtime.read_event_bytes(update.chain_id, update.stream_id.stream_name.clone(), index);
+            if let Ok(event) = bcs::from_bytes::<OracleEvent>(&bytes) {
+                events.push(event);
+            }
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use linera_sdk::linera_base_types::{ApplicationId, GenericApplicationId, StreamId};
+    use std::collections::BTreeMap;
+
+    /// Events keyed by (chain, stream name, index)
+    #[derive(Default)]
+    struct MockEvents(BTreeMap<(ChainId, StreamName, u32), Vec<u8>>);
+
+    impl OracleEventSource for MockEvents {
+        fn read_event_bytes(&mut self, chain_id: ChainId, stream_name: StreamName, index: u32) -> Vec<u8> {
+            self.0.get(&(chain_id, stream_name, index)).cloned().unwrap_or_default()
+        }
+    }
+
+    fn chain(id: u8) -> ChainId {
+        format!("{:064x}", id).parse().unwrap()
+    }
+
+    fn update(chain_id: ChainId, stream_name: &str, previous_index: u32, next_index: u32) -> StreamUpdate {
+        StreamUpdate {
+            chain_id,
+            stream_id: StreamId {
+                application_id: GenericApplicationId::User(ApplicationId::new(format!("{:064x}", 1).parse().unwrap())),
+                stream_name: StreamName::from(stream_name),
+            },
+            previous_index,
+            next_index,
+        }
+    }
+
+    fn cancelled(query_id: u64) -> OracleEvent {
+        OracleEvent::QueryCancelled {
+            query_id,
+            cancelled_by: chain(9),
+            released_voters: 0,
+        }
+    }
+
+    fn cancelled_id(event: &OracleEvent) -> u64 {
+        match event {
+            OracleEvent::QueryCancelled { query_id, .. } => *query_id,
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decodes_update_range_in_order() {
+        let mut source = MockEvents::default();
+        let stream = StreamName::from(ORACLE_STREAM_NAME);
+        for index in 0..4u32 {
+            let bytes = bcs::to_bytes(&cancelled(index as u64)).unwrap();
+            source.0.insert((chain(1), stream.clone(), index), bytes);
+        }
+
+        // Events before previous_index were already processed
+        let events = decode_oracle_events(vec![update(chain(1), ORACLE_STREAM_NAME, 1, 4)], &mut source);
+        assert_eq!(events.iter().map(cancelled_id).collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_skips_malformed_events_and_other_streams() {
+        let mut source = MockEvents::default();
+        let stream = StreamName::from(ORACLE_STREAM_NAME);
+        source.0.insert((chain(1), stream.clone(), 0), bcs::to_bytes(&cancelled(5)).unwrap());
+        source.0.insert((chain(1), stream.clone(), 1), vec![0xff, 0xff, 0xff]);
+        source.0.insert((chain(1), stream.clone(), 2), bcs::to_bytes(&cancelled(6)).unwrap());
+        source.0.insert((chain(2), StreamName::from("other"), 0), bcs::to_bytes(&cancelled(7)).unwrap());
+
+        let events = decode_oracle_events(
+            vec![
+                update(chain(1), ORACLE_STREAM_NAME, 0, 3),
+                update(chain(2), "other", 0, 1),
+            ],
+            &mut source,
+        );
+        assert_eq!(events.iter().map(cancelled_id).collect::<Vec<_>>(), vec![5, 6]);
+    }
+
+    #[test]
+    fn test_empty_update_decodes_nothing() {
+        let mut source = MockEvents::default();
+        assert!(decode_oracle_events(vec![update(chain(1), ORACLE_STREAM_NAME, 3, 3)], &mut source).is_empty());
+        assert!(decode_oracle_events(Vec::new(), &mut source).is_empty());
+    }
+}
@@ -7,7 +7,7 @@
 //! 
 //! ## Quick Start
 //! 
-//! ```rust,no_run
+//! ```ignore
 //! use alethea_sdk::AletheaClient;
 //! use linera_sdk::linera_base_types::Timestamp;
 //! 
@@ -33,13 +33,17 @@ use linera_sdk::{
     linera_base_types::{ApplicationId, Timestamp},
     ContractRuntime, Contract,
 };
-use alethea_oracle_types::{RegistryMessage, CANONICAL_REGISTRY_ID_PLACEHOLDER};
+use alethea_oracle_types::RegistryMessage;
 use thiserror::Error;
 
 pub mod client;
+pub mod events;
+pub mod network;
 pub mod types;
 
 pub use client::AletheaClient;
+pub use events::{decode_oracle_events, OracleEvent, OracleEventSource};
+pub use network::Network;
 pub use types::*;
 
 /// Alethea SDK errors
@@ -48,6 +52,9 @@ pub enum AletheaError {
     #[error("Registry not configured")]
     RegistryNotConfigured,
     
+    #[error("No registry deployed on {0:?}")]
+    NetworkNotDeployed(Network),
+    
     #[error("Invalid market parameters: {0}")]
     InvalidParameters(String),
     
@@ -59,6 +66,12 @@ pub enum AletheaError {
     
     #[error("Market not found: {0}")]
     MarketNotFound(u64),
+    
+    #[error("Token application not configured")]
+    TokenNotConfigured,
+    
+    #[error("Application query failed: {0}")]
+    QueryFailed(String),
 }
 
 /// Result type for Alethea SDK operations
@@ -66,16 +79,8 @@ pub type Result<T> = std::result::Result<T, AletheaError>;
 
 /// Get canonical registry ApplicationId
 /// 
-/// This returns the well-known ApplicationId of the Alethea Oracle Registry.
-/// All dApps use this same registry for oracle services.
+/// This returns the well-known ApplicationId of the Alethea Oracle Registry
+/// on the default network. Use `Network::registry_id` for other networks.
 pub fn canonical_registry_id() -> Result<ApplicationId> {
-    // TODO: Replace with actual deployed registry ID
-    // For now, return error if not configured
-    if CANONICAL_REGISTRY_ID_PLACEHOLDER == "REGISTRY_NOT_DEPLOYED" {
-        return Err(AletheaError::RegistryNotConfigured);
-    }
-    
-    CANONICAL_REGISTRY_ID_PLACEHOLDER
-        .parse()
-        .map_err(|_| AletheaError::RegistryNotConfigured)
+    Network::default().registry_id()
 }
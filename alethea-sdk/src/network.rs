@@ -0,0 +1,110 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Known Oracle Registry deployments
+
+use linera_sdk::linera_base_types::{ApplicationId, ChainId};
+use alethea_oracle_types::CANONICAL_REGISTRY_ID_PLACEHOLDER;
+use crate::{AletheaError, Result};
+
+/// Marker used in place of an ID for networks without a deployment
+const NOT_DEPLOYED: &str = "REGISTRY_NOT_DEPLOYED";
+
+/// Network whose Oracle Registry deployment a client targets
+/// 
+/// Local deployments differ per developer, so their IDs are read at build
+/// time from `ALETHEA_LOCAL_REGISTRY_ID` and `ALETHEA_LOCAL_REGISTRY_CHAIN`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Network {
+    /// Local `linera net up` deployment
+    Local,
+    
+    /// Public testnet (default)
+    #[default]
+    Testnet,
+    
+    /// Mainnet
+    Mainnet,
+}
+
+impl Network {
+    /// Registry ApplicationId as configured for this network
+    fn registry_id_str(self) -> &'static str {
+        match self {
+            Network::Local => option_env!("ALETHEA_LOCAL_REGISTRY_ID").unwrap_or(NOT_DEPLOYED),
+            Network::Testnet => CANONICAL_REGISTRY_ID_PLACEHOLDER,
+            Network::Mainnet => NOT_DEPLOYED,
+        }
+    }
+    
+    /// Registry chain ID as configured for this network
+    fn registry_chain_str(self) -> &'static str {
+        match self {
+            Network::Local => option_env!("ALETHEA_LOCAL_REGISTRY_CHAIN").unwrap_or(NOT_DEPLOYED),
+            Network::Testnet => NOT_DEPLOYED,
+            Network::Mainnet => NOT_DEPLOYED,
+        }
+    }
+    
+    /// Get the registry ApplicationId deployed on this network
+    /// 
+    /// # Returns
+    /// Err(NetworkNotDeployed) if the network has no known registry
+    pub fn registry_id(self) -> Result<ApplicationId> {
+        let id = self.registry_id_str();
+        if id == NOT_DEPLOYED {
+            return Err(AletheaError::NetworkNotDeployed(self));
+        }
+        
+        id.parse().map_err(|_| AletheaError::RegistryNotConfigured)
+    }
+    
+    /// Get the chain hosting the registry on this network, if known
+    pub fn registry_chain(self) -> Option<ChainId> {
+        let chain = self.registry_chain_str();
+        if chain == NOT_DEPLOYED {
+            return None;
+        }
+        
+        chain.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_network_is_testnet() {
+        assert_eq!(Network::default(), Network::Testnet);
+    }
+
+    #[test]
+    fn test_testnet_resolves_canonical_registry() {
+        let expected: ApplicationId = CANONICAL_REGISTRY_ID_PLACEHOLDER.parse().unwrap();
+        assert_eq!(Network::Testnet.registry_id().unwrap(), expected);
+        assert_eq!(Network::Testnet.registry_chain(), None);
+    }
+
+    #[test]
+    fn test_mainnet_not_deployed() {
+        assert!(matches!(
+            Network::Mainnet.registry_id(),
+            Err(AletheaError::NetworkNotDeployed(Network::Mainnet))
+        ));
+        assert_eq!(Network::Mainnet.registry_chain(), None);
+    }
+
+    #[test]
+    fn test_local_requires_build_config() {
+        if option_env!("ALETHEA_LOCAL_REGISTRY_ID").is_none() {
+            assert!(matches!(
+                Network::Local.registry_id(),
+                Err(AletheaError::NetworkNotDeployed(Network::Local))
+            ));
+        }
+        if option_env!("ALETHEA_LOCAL_REGISTRY_CHAIN").is_none() {
+            assert_eq!(Network::Local.registry_chain(), None);
+        }
+    }
+}
@@ -3,6 +3,7 @@
 
 //! Types for Alethea SDK
 
+use linera_sdk::linera_base_types::Amount;
 use serde::{Deserialize, Serialize};
 
 /// Resolution result from oracle
@@ -93,6 +94,31 @@ impl MarketRegistration {
     }
 }
 
+/// Combined token position of an account across the token and registry apps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TotalPosition {
+    /// Token balance held by the account
+    pub liquid: Amount,
+    
+    /// Stake registered with the Oracle Registry (includes `locked`)
+    pub staked: Amount,
+    
+    /// Part of `staked` locked in active votes
+    pub locked: Amount,
+    
+    /// Rewards waiting to be claimed from the registry
+    pub pending_rewards: Amount,
+}
+
+impl TotalPosition {
+    /// Total tokens owned: liquid + staked + pending rewards
+    pub fn total(&self) -> Amount {
+        self.liquid
+            .saturating_add(self.staked)
+            .saturating_add(self.pending_rewards)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -336,6 +336,54 @@ impl Contract for AletheaTokenContract {
                 OperationResponse::success("Registry app ID updated")
             }
 
+            Operation::ReleaseFromApplication {
+                target_account,
+                amount,
+            } => {
+                // Only the holding application itself may release its tokens
+                let application = match self.runtime.authenticated_caller_id() {
+                    Some(application) => application,
+                    None => return OperationResponse::error("Only applications can release held tokens"),
+                };
+
+                let held = self
+                    .state
+                    .application_holdings
+                    .get(&application)
+                    .await
+                    .ok()
+                    .flatten()
+                    .unwrap_or(Amount::ZERO);
+
+                if held < amount {
+                    return OperationResponse::error("Insufficient application holdings");
+                }
+
+                self.state
+                    .application_holdings
+                    .insert(&application, held.saturating_sub(amount))
+                    .expect("Failed to update application holdings");
+
+                // Credit the target account via WithdrawToAccount
+                let message = Message::WithdrawToAccount {
+                    target_chain: target_account.chain_id,
+                    target: target_account.owner,
+                    amount,
+                };
+
+                let token_chain = self.runtime.chain_id();
+                self.runtime
+                    .prepare_message(message)
+                    .with_authentication()
+                    .with_tracking()
+                    .send_to(token_chain);
+
+                OperationResponse::success(format!(
+                    "Released {} tokens to {:?}",
+                    amount, target_account
+                ))
+            }
+
             Operation::SendTransferMessage {
                 token_chain,
                 amount,
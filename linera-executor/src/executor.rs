@@ -6,11 +6,33 @@ use linera_base::data_types::Amount;
 use oracle_registry_v2::{Operation, Message, state::DecisionStrategy};
 use tracing::{info, warn};
 
+use crate::replay::{self, ReplayParams, ReplayQuery, ReplayVerdict};
+
 pub struct LineraExecutor {
     chain_id: String,
     app_id: String,
     wallet_path: String,
     storage_path: String,
+    verbose: bool,
+}
+
+/// Variant name of a JSON-serialized operation or message
+/// 
+/// Unit variants serialize as a string, others as a single-key object.
+pub fn payload_kind(payload: &serde_json::Value) -> String {
+    match payload {
+        serde_json::Value::String(kind) => kind.clone(),
+        serde_json::Value::Object(map) => map.keys().next().cloned().unwrap_or_else(|| "Unknown".to_string()),
+        _ => "Unknown".to_string(),
+    }
+}
+
+/// Describe a submission for error context: what was sent, where, and the payload
+pub fn submission_context(kind: &str, chain_id: &str, app_id: &str, payload: &str) -> String {
+    format!(
+        "{} on chain {} (application {}) failed; payload: {}",
+        kind, chain_id, app_id, payload
+    )
 }
 
 impl LineraExecutor {
@@ -19,15 +41,22 @@ impl LineraExecutor {
         app_id: String,
         wallet_path: String,
         storage_path: String,
+        verbose: bool,
     ) -> Result<Self> {
         Ok(Self {
             chain_id,
             app_id,
             wallet_path,
             storage_path,
+            verbose,
         })
     }
 
+    /// Error context for a submission of `payload` to this executor's chain
+    fn context_for(&self, payload: &serde_json::Value) -> String {
+        submission_context(&payload_kind(payload), &self.chain_id, &self.app_id, &payload.to_string())
+    }
+
     /// Register voter
     pub async fn register_voter(
         &self,
@@ -38,12 +67,16 @@ impl LineraExecutor {
         info!("Creating RegisterVoter operation...");
 
         // Parse stake as tokens (not attos)
-        let stake_amount = Amount::from_tokens(stake.parse()?);
+        let stake_amount = Amount::from_tokens(
+            stake.parse().with_context(|| format!("Invalid stake amount '{}'", stake))?,
+        );
 
         let operation = Operation::RegisterVoter {
             stake: stake_amount,
             name,
             metadata_url,
+            referral: None,
+            encrypted_metadata: None,
         };
 
         self.execute_operation(operation).await
@@ -94,7 +127,9 @@ impl LineraExecutor {
             outcomes,
             strategy,
             min_votes: min_votes_usize,
-            reward_amount: Amount::from_tokens(reward_amount.parse()?),
+            reward_amount: Amount::from_tokens(
+                reward_amount.parse().with_context(|| format!("Invalid reward amount '{}'", reward_amount))?,
+            ),
             deadline: None,
         };
 
@@ -106,7 +141,9 @@ impl LineraExecutor {
         info!("Creating UpdateStake operation...");
 
         let operation = Operation::UpdateStake {
-            additional_stake: Amount::from_tokens(additional_stake.parse()?),
+            additional_stake: Amount::from_tokens(
+                additional_stake.parse().with_context(|| format!("Invalid stake amount '{}'", additional_stake))?,
+            ),
         };
 
         self.execute_operation(operation).await
@@ -117,7 +154,9 @@ impl LineraExecutor {
         info!("Creating WithdrawStake operation...");
 
         let operation = Operation::WithdrawStake {
-            amount: Amount::from_tokens(amount.parse()?),
+            amount: Amount::from_tokens(
+                amount.parse().with_context(|| format!("Invalid withdrawal amount '{}'", amount))?,
+            ),
         };
 
         self.execute_operation(operation).await
@@ -137,16 +176,27 @@ impl LineraExecutor {
         info!("Executing operation: {:?}", operation);
 
         // Serialize operation to JSON
-        let operation_json = serde_json::to_string_pretty(&operation)?;
+        let payload = serde_json::to_value(&operation)
+            .context("Failed to serialize operation")?;
+        let operation_json = serde_json::to_string_pretty(&payload)?;
         info!("Operation JSON:\n{}", operation_json);
 
         // Write to temp file
         let temp_file = format!("/tmp/linera_op_{}.json", std::process::id());
         std::fs::write(&temp_file, &operation_json)
-            .context("Failed to write operation file")?;
+            .with_context(|| format!("Failed to write operation file {}", temp_file))
+            .with_context(|| self.context_for(&payload))?;
 
         info!("Operation file: {}", temp_file);
 
+        if self.verbose {
+            info!(
+                "Invocation: linera-executor --chain-id {} --app-id {} execute-file --file {}",
+                self.chain_id, self.app_id, temp_file
+            );
+            info!("Wallet: {}, storage: {}", self.wallet_path, self.storage_path);
+        }
+
         // Method 1: Try using linera CLI to execute
         // Note: This might not work directly as Linera doesn't have execute-operation command
         // We'll use a workaround by creating a block proposal
@@ -178,13 +228,22 @@ impl LineraExecutor {
         info!("Sending message to chain: {}", target_chain);
 
         // Serialize message
-        let message_json = serde_json::to_string_pretty(&message)?;
+        let payload = serde_json::to_value(&message)
+            .context("Failed to serialize message")?;
+        let message_json = serde_json::to_string_pretty(&payload)?;
         info!("Message JSON:\n{}", message_json);
 
         // Write to temp file
         let temp_file = format!("/tmp/linera_msg_{}.json", std::process::id());
         std::fs::write(&temp_file, &message_json)
-            .context("Failed to write message file")?;
+            .with_context(|| format!("Failed to write message file {}", temp_file))
+            .with_context(|| {
+                submission_context(&payload_kind(&payload), &target_chain, &self.app_id, &payload.to_string())
+            })?;
+
+        if self.verbose {
+            info!("Target chain: {}, message file: {}", target_chain, temp_file);
+        }
 
         // Try to send message using linera CLI
         // Note: This requires proper message sending implementation
@@ -200,6 +259,74 @@ impl LineraExecutor {
         ))
     }
 
+    /// Replay a query's resolution locally and compare it with the on-chain result
+    pub async fn replay(&self, query_id: u64) -> Result<String> {
+        info!("Fetching query {} and resolution parameters...", query_id);
+
+        let url = format!(
+            "http://localhost:8080/chains/{}/applications/{}",
+            self.chain_id, self.app_id
+        );
+        let body = serde_json::json!({ "query": replay::replay_query(query_id) });
+
+        if self.verbose {
+            info!(
+                "Invocation: curl -X POST '{}' -H 'Content-Type: application/json' -d '{}'",
+                url, body
+            );
+        }
+
+        let response: serde_json::Value = reqwest::Client::new()
+            .post(&url)
+            .json(&body)
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to Linera service at {}", url))?
+            .json()
+            .await
+            .with_context(|| format!("Invalid response from {}", url))?;
+
+        if let Some(errors) = response.get("errors") {
+            bail!("GraphQL errors: {}", errors);
+        }
+        let data = response.get("data").context("GraphQL response has no data")?;
+        if data["queryWithVotes"].is_null() {
+            bail!("Query {} not found", query_id);
+        }
+        let query: ReplayQuery = serde_json::from_value(data["queryWithVotes"].clone())
+            .context("Failed to parse query")?;
+        let params: ReplayParams = serde_json::from_value(data["resolutionParameters"].clone())
+            .context("Failed to parse resolution parameters")?;
+
+        let expected = replay::replay(&query, &params)?;
+
+        let mut report = format!(
+            "Query {} ({}, tie-break {}, status {})\n\
+            Late reveal weight: {} bps, multi-winner threshold: {} bps, max effective stake: {}\n\
+            Votes:\n",
+            query.id, query.strategy, query.tie_break, query.status,
+            params.late_reveal_weight_bps, params.multi_winner_threshold_bps,
+            params.max_effective_stake.map_or("none".to_string(), |cap| cap.to_string()),
+        );
+        for vote in query.votes.iter().flatten() {
+            report.push_str(&format!(
+                "  {} -> {} (stake {}, reputation {}, confidence {:?}, at {})\n",
+                vote.voter, vote.value, vote.stake_at_vote, vote.reputation_at_vote, vote.confidence, vote.timestamp
+            ));
+        }
+        report.push_str(&format!("Replayed result: {}\n", expected));
+
+        match replay::compare(&expected, &query) {
+            ReplayVerdict::Match => report.push_str("✅ Matches the on-chain result"),
+            ReplayVerdict::Unresolved => report.push_str("ℹ️  Query has no on-chain result yet"),
+            ReplayVerdict::Mismatch { on_chain } => {
+                warn!("Replay differs from the on-chain result");
+                report.push_str(&format!("❌ On-chain result differs: {}", on_chain));
+            }
+        }
+        Ok(report)
+    }
+
     /// Test connection
     pub async fn test_connection(&self) -> Result<String> {
         info!("Testing connection to Linera service...");
@@ -212,18 +339,29 @@ impl LineraExecutor {
 
         info!("GraphQL endpoint: {}", url);
 
+        let body = serde_json::json!({
+            "query": "{ voters { address } }"
+        });
+
+        if self.verbose {
+            info!(
+                "Invocation: curl -X POST '{}' -H 'Content-Type: application/json' -d '{}'",
+                url, body
+            );
+        }
+
         let client = reqwest::Client::new();
         let response = client
             .post(&url)
-            .json(&serde_json::json!({
-                "query": "{ voters { address } }"
-            }))
+            .json(&body)
             .send()
             .await
-            .context("Failed to connect to Linera service")?;
+            .with_context(|| format!("Failed to connect to Linera service at {}", url))
+            .with_context(|| submission_context("GraphQL query", &self.chain_id, &self.app_id, &body.to_string()))?;
 
         let status = response.status();
-        let text = response.text().await?;
+        let text = response.text().await
+            .with_context(|| format!("Failed to read response from {}", url))?;
 
         if status.is_success() {
             Ok(format!(
@@ -233,7 +371,48 @@ impl LineraExecutor {
                 status, text
             ))
         } else {
-            bail!("Connection failed: {} - {}", status, text)
+            bail!(
+                "Connection failed: {} - {}\n{}",
+                status,
+                text,
+                submission_context("GraphQL query", &self.chain_id, &self.app_id, &body.to_string())
+            )
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn test_payload_kind() {
+        let unit = serde_json::to_value(&Operation::ClaimRewards).unwrap();
+        assert_eq!(payload_kind(&unit), "ClaimRewards");
+
+        let payload = serde_json::json!({ "WithdrawStake": { "amount": "5." } });
+        assert_eq!(payload_kind(&payload), "WithdrawStake");
+        assert_eq!(payload_kind(&serde_json::json!(42)), "Unknown");
+    }
+
+    #[test]
+    fn test_error_wrapped_with_submission_context() {
+        let executor = LineraExecutor::new(
+            "e476".to_string(),
+            "a1b2".to_string(),
+            "wallet.json".to_string(),
+            "rocksdb:wallet.db".to_string(),
+            false,
+        ).unwrap();
+        let payload = serde_json::json!({ "WithdrawStake": { "amount": "5." } });
+
+        let result: Result<()> = Err(anyhow!("node rejected block"));
+        let err = result.with_context(|| executor.context_for(&payload)).unwrap_err();
+        let message = format!("{:#}", err);
+
+        assert!(message.starts_with("WithdrawStake on chain e476 (application a1b2) failed"));
+        assert!(message.contains(r#"payload: {"WithdrawStake":{"amount":"5."}}"#));
+        assert!(message.ends_with("node rejected block"));
+    }
+}
@@ -8,6 +8,7 @@ use std::path::PathBuf;
 use tracing::info;
 
 mod executor;
+mod replay;
 use executor::LineraExecutor;
 
 #[derive(Parser)]
@@ -30,6 +31,10 @@ struct Cli {
     #[arg(long, env = "STORAGE_PATH", default_value = "rocksdb:~/.config/linera/wallet.db")]
     storage_path: String,
 
+    /// Log the exact GraphQL/CLI invocation for each submission
+    #[arg(long, global = true)]
+    verbose: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -124,6 +129,13 @@ enum Commands {
         message: String,
     },
 
+    /// Re-run a query's resolution locally and compare with the on-chain result
+    Replay {
+        /// Query ID
+        #[arg(long)]
+        query_id: u64,
+    },
+
     /// Test connection
     Test,
 }
@@ -166,6 +178,7 @@ async fn main() -> Result<()> {
         app_id.clone(),
         wallet_path,
         storage_path,
+        cli.verbose,
     )?;
 
     // Execute command
@@ -286,6 +299,16 @@ async fn main() -> Result<()> {
             info!("{}", result);
         }
 
+        Commands::Replay { query_id } => {
+            info!("🔁 Replaying resolution...");
+            info!("  Query ID: {}", query_id);
+            info!("");
+
+            let result = executor.replay(query_id).await?;
+            
+            info!("{}", result);
+        }
+
         Commands::Test => {
             info!("🧪 Testing connection...");
             info!("");
@@ -0,0 +1,302 @@
+// Replay - menghitung ulang hasil resolusi query secara lokal
+// Local re-run of a query's resolution math, to compare with the on-chain result
+
+use anyhow::{bail, Context, Result};
+use linera_base::data_types::Amount;
+use oracle_registry_v2::state::{reputation_weight, ResolutionResult, TieBreak, ABSTAIN_VOTE};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// GraphQL query fetching a query's votes and the resolution parameters
+pub fn replay_query(query_id: u64) -> String {
+    format!(
+        "{{ queryWithVotes(id: {}) {{ id strategy outcomes allowAbstain multiWinner tieBreak revealEnd status resultDisplay \
+        votes {{ voter value timestamp confidence stakeAtVote reputationAtVote }} }} \
+        resolutionParameters {{ lateRevealWeightBps multiWinnerThresholdBps maxEffectiveStake }} }}",
+        query_id
+    )
+}
+
+/// A query as returned by `queryWithVotes`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayQuery {
+    pub id: u64,
+    pub strategy: String,
+    pub outcomes: Vec<String>,
+    pub allow_abstain: bool,
+    pub multi_winner: bool,
+    pub tie_break: String,
+    /// Microseconds as string
+    pub reveal_end: String,
+    pub status: String,
+    pub result_display: Option<String>,
+    #[serde(default)]
+    pub votes: Option<Vec<ReplayVote>>,
+}
+
+/// One vote of `queryWithVotes`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayVote {
+    pub voter: String,
+    pub value: String,
+    /// Microseconds as string
+    pub timestamp: String,
+    pub confidence: Option<u8>,
+    /// Amount in tokens, e.g. "100."
+    pub stake_at_vote: String,
+    pub reputation_at_vote: u32,
+}
+
+/// The protocol parameters that affect a resolution, as returned by `resolutionParameters`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayParams {
+    pub late_reveal_weight_bps: u32,
+    pub multi_winner_threshold_bps: u32,
+    /// Amount in tokens, e.g. "150."
+    pub max_effective_stake: Option<Amount>,
+}
+
+/// Parse an amount in tokens ("100.")
+fn parse_amount(value: &str) -> Result<Amount> {
+    Amount::from_str(value).map_err(|e| anyhow::anyhow!("Invalid amount '{}': {}", value, e))
+}
+
+fn parse_micros(value: &str) -> Result<u64> {
+    value.parse().with_context(|| format!("Invalid timestamp '{}'", value))
+}
+
+/// Recompute the result the registry should have reached for `query`
+///
+/// Mirrors the contract: abstentions are ignored, reveals after the reveal
+/// phase count at the late reveal weight, stake is capped at the maximum
+/// effective stake, and Majority ties follow the query's tie-break mode.
+/// Median votes are parsed as numbers (custom numeric outcome labels are not
+/// reported by the service).
+pub fn replay(query: &ReplayQuery, params: &ReplayParams) -> Result<ResolutionResult> {
+    let reveal_end = parse_micros(&query.reveal_end)?;
+    let late_weight_bps = params.late_reveal_weight_bps.min(10000);
+
+    // Tallied votes with their weight (basis points) and capped stake
+    let mut weighted = Vec::new();
+    for vote in query.votes.iter().flatten() {
+        if query.allow_abstain && vote.value == ABSTAIN_VOTE {
+            continue;
+        }
+        let weight_bps = if parse_micros(&vote.timestamp)? > reveal_end { late_weight_bps } else { 10000 };
+        if weight_bps == 0 {
+            continue;
+        }
+        let mut stake = parse_amount(&vote.stake_at_vote)?;
+        if let Some(cap) = params.max_effective_stake {
+            stake = stake.min(cap);
+        }
+        weighted.push((vote, weight_bps, u128::from(stake)));
+    }
+
+    let multi_winner_bps = query.multi_winner.then_some(params.multi_winner_threshold_bps);
+    let pick = |tally: BTreeMap<String, f64>| match multi_winner_bps {
+        Some(threshold_bps) => ResolutionResult::from_tally_above_share(tally, threshold_bps),
+        None => ResolutionResult::from_tally(tally),
+    };
+
+    let result = match query.strategy.as_str() {
+        "Majority" => {
+            let mut tally: BTreeMap<String, u64> = BTreeMap::new();
+            for (vote, weight_bps, _) in &weighted {
+                *tally.entry(vote.value.clone()).or_insert(0) += *weight_bps as u64;
+            }
+            if multi_winner_bps.is_some() {
+                return Ok(pick(tally.into_iter().map(|(value, weight)| (value, weight as f64)).collect()));
+            }
+
+            let tie_break = TieBreak::parse(&query.tie_break).map_err(anyhow::Error::msg)?;
+            let mut backing: BTreeMap<String, u128> = BTreeMap::new();
+            for (vote, _, stake) in &weighted {
+                let weight = match tie_break {
+                    TieBreak::HigherStake => *stake,
+                    _ => vote.reputation_at_vote as u128,
+                };
+                *backing.entry(vote.value.clone()).or_insert(0) += weight;
+            }
+            ResolutionResult::from_tally_with_tie_break(&tally, &query.outcomes, tie_break, &backing)
+        }
+        "WeightedByStake" => {
+            let mut tally: BTreeMap<String, u128> = BTreeMap::new();
+            for (vote, weight_bps, stake) in &weighted {
+                *tally.entry(vote.value.clone()).or_insert(0) += stake.saturating_mul(*weight_bps as u128) / 10000;
+            }
+            pick(tally.into_iter().map(|(value, weight)| (value, weight as f64)).collect())
+        }
+        "WeightedByReputation" => {
+            let mut tally: BTreeMap<String, f64> = BTreeMap::new();
+            for (vote, weight_bps, _) in &weighted {
+                *tally.entry(vote.value.clone()).or_insert(0.0) +=
+                    reputation_weight(vote.reputation_at_vote) * *weight_bps as f64 / 10000.0;
+            }
+            pick(tally)
+        }
+        "Median" | "ConfidenceWeightedMedian" => {
+            let confidence_weighted = query.strategy == "ConfidenceWeightedMedian";
+            ResolutionResult::from_weighted_median(weighted.iter().filter_map(|(vote, weight_bps, _)| {
+                let value = vote.value.parse::<f64>().ok()?;
+                let confidence = if confidence_weighted { vote.confidence.map_or(100, |c| c.min(100)) as u64 } else { 1 };
+                Some((value, confidence * *weight_bps as u64))
+            }))
+        }
+        other => bail!("Unknown strategy '{}'", other),
+    };
+    Ok(result)
+}
+
+/// Outcome of comparing a replay with the on-chain result
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayVerdict {
+    /// Replay and chain agree
+    Match,
+    /// The chain recorded a different result
+    Mismatch { on_chain: String },
+    /// The query has no result on chain yet
+    Unresolved,
+}
+
+/// Compare the replayed result with the query's on-chain `resultDisplay`
+pub fn compare(expected: &ResolutionResult, query: &ReplayQuery) -> ReplayVerdict {
+    match query.result_display.as_deref() {
+        None => ReplayVerdict::Unresolved,
+        Some(on_chain) if on_chain == expected.to_string() => ReplayVerdict::Match,
+        Some(on_chain) => ReplayVerdict::Mismatch { on_chain: on_chain.to_string() },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(value: &str, stake: u128, reputation: u32) -> ReplayVote {
+        ReplayVote {
+            voter: format!("{}-{}", value, stake),
+            value: value.to_string(),
+            timestamp: "50".to_string(),
+            confidence: None,
+            stake_at_vote: Amount::from_tokens(stake).to_string(),
+            reputation_at_vote: reputation,
+        }
+    }
+
+    fn query(strategy: &str, outcomes: &[&str], votes: Vec<ReplayVote>) -> ReplayQuery {
+        ReplayQuery {
+            id: 7,
+            strategy: strategy.to_string(),
+            outcomes: outcomes.iter().map(|outcome| outcome.to_string()).collect(),
+            allow_abstain: false,
+            multi_winner: false,
+            tie_break: "OutcomeOrder".to_string(),
+            reveal_end: "100".to_string(),
+            status: "Resolved".to_string(),
+            result_display: None,
+            votes: Some(votes),
+        }
+    }
+
+    fn params() -> ReplayParams {
+        ReplayParams { late_reveal_weight_bps: 5000, multi_winner_threshold_bps: 3000, max_effective_stake: None }
+    }
+
+    fn outcome(value: &str) -> ResolutionResult {
+        ResolutionResult::Outcome(value.to_string())
+    }
+
+    #[test]
+    fn test_majority_and_tie_breaks() {
+        let votes = vec![vote("Yes", 400, 50), vote("Yes", 100, 50), vote("No", 100, 90), vote("No", 100, 50)];
+        let mut q = query("Majority", &["No", "Yes"], votes);
+        assert_eq!(replay(&q, &params()).unwrap(), outcome("No"));
+
+        q.tie_break = "HigherStake".to_string();
+        assert_eq!(replay(&q, &params()).unwrap(), outcome("Yes"));
+        q.tie_break = "HigherReputation".to_string();
+        assert_eq!(replay(&q, &params()).unwrap(), outcome("No"));
+        q.tie_break = "NoConsensus".to_string();
+        assert_eq!(replay(&q, &params()).unwrap(), ResolutionResult::NoConsensus);
+
+        // A late reveal at half weight breaks the tie the other way
+        q.tie_break = "OutcomeOrder".to_string();
+        q.votes.as_mut().unwrap()[3].timestamp = "150".to_string();
+        assert_eq!(replay(&q, &params()).unwrap(), outcome("Yes"));
+    }
+
+    #[test]
+    fn test_weighted_strategies() {
+        let votes = vec![vote("Yes", 1000, 10), vote("No", 100, 90), vote("No", 100, 90)];
+        let q = query("WeightedByStake", &["Yes", "No"], votes.clone());
+        assert_eq!(replay(&q, &params()).unwrap(), outcome("Yes"));
+
+        // Capping stake at 150 leaves Yes with 150 against No's 200
+        let capped = ReplayParams { max_effective_stake: Some(Amount::from_tokens(150)), ..params() };
+        assert_eq!(replay(&q, &capped).unwrap(), outcome("No"));
+
+        let q = query("WeightedByReputation", &["Yes", "No"], votes);
+        assert_eq!(replay(&q, &params()).unwrap(), outcome("No"));
+    }
+
+    #[test]
+    fn test_median_strategies() {
+        let mut votes = vec![vote("10", 100, 50), vote("20", 100, 50), vote("60", 100, 50)];
+        let q = query("Median", &["10", "20", "60"], votes.clone());
+        assert_eq!(replay(&q, &params()).unwrap(), ResolutionResult::NumericMedian(20.0));
+
+        votes[2].confidence = Some(100);
+        votes[0].confidence = Some(10);
+        votes[1].confidence = Some(10);
+        let q = query("ConfidenceWeightedMedian", &["10", "20", "60"], votes);
+        assert_eq!(replay(&q, &params()).unwrap(), ResolutionResult::NumericMedian(60.0));
+    }
+
+    #[test]
+    fn test_abstentions_and_multi_winner() {
+        let votes = vec![
+            vote("Rain", 100, 50), vote("Rain", 100, 50), vote("Wind", 100, 50),
+            vote("Wind", 100, 50), vote("Snow", 100, 50), vote(ABSTAIN_VOTE, 100, 50),
+        ];
+        let mut q = query("Majority", &["Rain", "Wind", "Snow"], votes);
+        q.allow_abstain = true;
+        q.multi_winner = true;
+        assert_eq!(
+            replay(&q, &params()).unwrap(),
+            ResolutionResult::Outcomes(vec!["Rain".to_string(), "Wind".to_string()])
+        );
+
+        q.result_display = Some("Rain, Wind".to_string());
+        assert_eq!(compare(&replay(&q, &params()).unwrap(), &q), ReplayVerdict::Match);
+        q.result_display = Some("Rain".to_string());
+        assert_eq!(
+            compare(&replay(&q, &params()).unwrap(), &q),
+            ReplayVerdict::Mismatch { on_chain: "Rain".to_string() }
+        );
+    }
+
+    #[test]
+    fn test_params_deserialize_from_graphql() {
+        let params: ReplayParams = serde_json::from_str(
+            r#"{"lateRevealWeightBps":5000,"multiWinnerThresholdBps":3000,"maxEffectiveStake":"150."}"#,
+        ).unwrap();
+        assert_eq!(
+            params,
+            ReplayParams {
+                late_reveal_weight_bps: 5000,
+                multi_winner_threshold_bps: 3000,
+                max_effective_stake: Some(Amount::from_tokens(150)),
+            }
+        );
+
+        let without_cap: ReplayParams = serde_json::from_str(
+            r#"{"lateRevealWeightBps":5000,"multiWinnerThresholdBps":3000,"maxEffectiveStake":null}"#,
+        ).unwrap();
+        assert_eq!(without_cap.max_effective_stake, None);
+        assert!(serde_json::from_str::<ReplayParams>(r#"{"lateRevealWeightBps":5000}"#).is_err());
+    }
+}
@@ -87,6 +87,13 @@ impl QueryRoot {
         result
     }
     
+    /// Get per-outcome prices and implied probabilities for a market
+    async fn market_prices(&self, market_id: u64) -> GraphQLResult<Vec<state::OutcomePrice>> {
+        let market = self.state.get_market(market_id).await
+            .ok_or_else(|| async_graphql::Error::new(format!("Market {} not found", market_id)))?;
+        Ok(market.outcome_prices())
+    }
+    
     /// Get position for a market and owner
     async fn position(&self, market_id: u64, owner: AccountOwner) -> Option<state::Position> {
         self.state.get_position(market_id, &owner).await
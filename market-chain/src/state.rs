@@ -50,6 +50,66 @@ pub struct Position {
     pub average_price: Amount,
 }
 
+/// Current price and implied probability of a single market outcome
+#[derive(Debug, Clone, Serialize, Deserialize, async_graphql::SimpleObject)]
+pub struct OutcomePrice {
+    pub outcome: String,
+    /// Cost of one share paying out one token, from the outcome's pool share
+    pub price: Amount,
+    /// Price normalised so that all outcomes of the market sum to 1.0
+    pub implied_probability: f64,
+}
+
+impl Market {
+    /// Price each outcome by its share of the combined outcome pools.
+    /// Markets with empty pools are priced uniformly.
+    pub fn outcome_prices(&self) -> Vec<OutcomePrice> {
+        let count = self.outcomes.len();
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let pools: Vec<u128> = (0..count)
+            .map(|i| self.outcome_pools.get(i).copied().unwrap_or(Amount::ZERO).into())
+            .collect();
+        let total = pools.iter().fold(0u128, |acc, pool| acc.saturating_add(*pool));
+        let one: u128 = Amount::ONE.into();
+
+        let prices: Vec<u128> = pools
+            .iter()
+            .map(|pool| {
+                if total == 0 {
+                    one / count as u128
+                } else {
+                    // Split to avoid overflowing pool * 10^18 on large pools
+                    let whole = pool / total;
+                    let rest = pool % total;
+                    whole.saturating_mul(one).saturating_add(
+                        rest.checked_mul(one)
+                            .map(|r| r / total)
+                            .unwrap_or_else(|| ((rest as f64 / total as f64) * one as f64) as u128),
+                    )
+                }
+            })
+            .collect();
+        let price_sum: f64 = prices.iter().map(|price| *price as f64).sum();
+
+        self.outcomes
+            .iter()
+            .zip(prices)
+            .map(|(outcome, price)| OutcomePrice {
+                outcome: outcome.clone(),
+                price: Amount::from_attos(price),
+                implied_probability: if price_sum > 0.0 {
+                    price as f64 / price_sum
+                } else {
+                    1.0 / count as f64
+                },
+            })
+            .collect()
+    }
+}
+
 impl MarketState {
     /// Get balance for a position - WASM safe
     pub async fn get_position(&self, market_id: u64, owner: &AccountOwner) -> Option<Position> {
@@ -84,3 +144,43 @@ impl MarketState {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binary_market(yes: u128, no: u128) -> Market {
+        Market {
+            id: 0,
+            question: "Will BTC hit 100k?".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            creator: None,
+            total_liquidity: Amount::from_tokens(yes + no),
+            outcome_pools: vec![Amount::from_tokens(yes), Amount::from_tokens(no)],
+            resolution_deadline: Timestamp::from(1000000),
+            status: MarketStatus::Open,
+            final_outcome: None,
+        }
+    }
+
+    #[test]
+    fn test_binary_implied_probabilities_sum_to_one() {
+        let prices = binary_market(700, 300).outcome_prices();
+
+        assert_eq!(prices.len(), 2);
+        assert_eq!(prices[0].outcome, "Yes");
+        assert_eq!(prices[0].price, Amount::from_millis(700));
+        assert_eq!(prices[1].price, Amount::from_millis(300));
+        let sum: f64 = prices.iter().map(|p| p.implied_probability).sum();
+        assert!((sum - 1.0).abs() < 1e-9);
+        assert!((prices[0].implied_probability - 0.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_pools_priced_uniformly() {
+        let prices = binary_market(0, 0).outcome_prices();
+
+        assert_eq!(prices[0].price, Amount::from_millis(500));
+        assert!((prices[1].implied_probability - 0.5).abs() < 1e-9);
+    }
+}
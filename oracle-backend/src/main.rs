@@ -2,6 +2,8 @@ use actix_web::{web, App, HttpResponse, HttpServer};
 use serde::{Deserialize, Serialize};
 use std::process::Command;
 
+mod metrics;
+
 #[derive(Deserialize)]
 struct RegisterVoterRequest {
     voter_address: String,
@@ -35,9 +37,18 @@ async fn register_voter(req: web::Json<RegisterVoterRequest>) -> HttpResponse {
 async fn main() -> std::io::Result<()> {
     println!("Starting Oracle Backend on http://localhost:3001");
     
-    HttpServer::new(|| {
+    // Registry service GraphQL URL, e.g. http://localhost:8080/chains/<chain>/applications/<app>
+    let graphql_url = std::env::var("ORACLE_GRAPHQL_URL").ok();
+    if graphql_url.is_none() {
+        println!("ORACLE_GRAPHQL_URL not set, /metrics will be unavailable");
+    }
+    let metrics_cache = web::Data::new(metrics::MetricsCache::new(graphql_url, metrics::DEFAULT_CACHE_TTL));
+    
+    HttpServer::new(move || {
         App::new()
+            .app_data(metrics_cache.clone())
             .route("/api/register-voter", web::post().to(register_voter))
+            .route("/metrics", web::get().to(metrics::metrics))
     })
     .bind(("127.0.0.1", 3001))?
     .run()
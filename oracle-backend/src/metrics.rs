@@ -0,0 +1,230 @@
+//! Prometheus metrics scraped from the registry's `statistics` query
+
+use actix_web::{web, HttpResponse};
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// How long a rendered scrape is served before the node is queried again
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(5);
+
+const STATISTICS_QUERY: &str =
+    "query { statistics { totalVoters activeQueriesCount totalStake resolutionRate protocolTreasury } }";
+
+/// The fields of the registry's `statistics` query exported as metrics
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Statistics {
+    pub total_voters: u64,
+    pub active_queries_count: u64,
+    /// Amount in tokens, e.g. "1500." or "12.5"
+    pub total_stake: String,
+    pub resolution_rate: f64,
+    /// Amount in tokens
+    pub protocol_treasury: String,
+}
+
+#[derive(Deserialize)]
+struct GraphQlResponse {
+    data: Option<StatisticsData>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Deserialize)]
+struct StatisticsData {
+    statistics: Statistics,
+}
+
+#[derive(Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+/// Parse a token amount as printed by the registry ("100." or "12.5")
+fn parse_tokens(amount: &str) -> Result<f64> {
+    amount.trim().parse::<f64>().with_context(|| format!("Invalid token amount '{}'", amount))
+}
+
+/// Render statistics in the Prometheus text exposition format
+pub fn render(stats: &Statistics) -> Result<String> {
+    let metrics = [
+        ("alethea_voter_count", "Registered voters", stats.total_voters as f64),
+        ("alethea_active_queries", "Queries currently open for voting", stats.active_queries_count as f64),
+        ("alethea_total_stake", "Stake held by all voters, in tokens", parse_tokens(&stats.total_stake)?),
+        ("alethea_resolution_rate", "Resolved queries as a share of created queries", stats.resolution_rate),
+        ("alethea_treasury_balance", "Protocol treasury balance, in tokens", parse_tokens(&stats.protocol_treasury)?),
+    ];
+
+    let mut output = String::new();
+    for (name, help, value) in metrics {
+        writeln!(output, "# HELP {} {}", name, help)?;
+        writeln!(output, "# TYPE {} gauge", name)?;
+        writeln!(output, "{} {}", name, value)?;
+    }
+    Ok(output)
+}
+
+/// Fetches statistics from the registry's GraphQL endpoint, serving the
+/// last rendered scrape until it is older than the TTL
+pub struct MetricsCache {
+    graphql_url: Option<String>,
+    ttl: Duration,
+    client: reqwest::Client,
+    cached: Mutex<Option<(Instant, String)>>,
+}
+
+impl MetricsCache {
+    pub fn new(graphql_url: Option<String>, ttl: Duration) -> Self {
+        Self {
+            graphql_url,
+            ttl,
+            client: reqwest::Client::new(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Rendered metrics, queried from the node at most once per TTL
+    pub async fn get(&self) -> Result<String> {
+        // Holding the lock across the fetch keeps concurrent scrapes to one request
+        let mut cached = self.cached.lock().await;
+        if let Some((fetched_at, body)) = cached.as_ref() {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(body.clone());
+            }
+        }
+
+        let body = render(&self.fetch().await?)?;
+        *cached = Some((Instant::now(), body.clone()));
+        Ok(body)
+    }
+
+    async fn fetch(&self) -> Result<Statistics> {
+        let url = self.graphql_url.as_deref()
+            .ok_or_else(|| anyhow!("ORACLE_GRAPHQL_URL is not set"))?;
+
+        let response: GraphQlResponse = self.client
+            .post(url)
+            .json(&serde_json::json!({ "query": STATISTICS_QUERY }))
+            .send()
+            .await
+            .context("Failed to reach the registry GraphQL endpoint")?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Invalid GraphQL response")?;
+
+        if let Some(error) = response.errors.first() {
+            return Err(anyhow!("GraphQL error: {}", error.message));
+        }
+        response.data
+            .map(|data| data.statistics)
+            .ok_or_else(|| anyhow!("GraphQL response has no data"))
+    }
+}
+
+pub async fn metrics(cache: web::Data<MetricsCache>) -> HttpResponse {
+    match cache.get().await {
+        Ok(body) => HttpResponse::Ok()
+            .content_type("text/plain; version=0.0.4")
+            .body(body),
+        Err(e) => {
+            println!("Metrics scrape failed: {:#}", e);
+            HttpResponse::ServiceUnavailable().body(format!("{:#}", e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn is_metric_name(name: &str) -> bool {
+        let mut chars = name.chars();
+        matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == ':')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+    }
+
+    /// Check the text exposition format: HELP/TYPE comments precede each
+    /// metric's samples, names are valid and values parse as floats
+    fn parse_exposition(text: &str) -> Result<HashMap<String, f64>, String> {
+        let mut types: HashMap<String, String> = HashMap::new();
+        let mut samples = HashMap::new();
+
+        for line in text.lines().filter(|line| !line.is_empty()) {
+            if let Some(comment) = line.strip_prefix("# ") {
+                let mut parts = comment.splitn(3, ' ');
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some("HELP"), Some(name), Some(_)) if is_metric_name(name) => {}
+                    (Some("TYPE"), Some(name), Some(kind)) if is_metric_name(name) => {
+                        if !["counter", "gauge", "histogram", "summary", "untyped"].contains(&kind) {
+                            return Err(format!("unknown metric type in '{}'", line));
+                        }
+                        types.insert(name.to_string(), kind.to_string());
+                    }
+                    _ => return Err(format!("malformed comment '{}'", line)),
+                }
+                continue;
+            }
+
+            let (name, value) = line.split_once(' ').ok_or(format!("malformed sample '{}'", line))?;
+            if !is_metric_name(name) || !types.contains_key(name) {
+                return Err(format!("sample '{}' has no valid TYPE", line));
+            }
+            let value: f64 = value.parse().map_err(|_| format!("invalid value in '{}'", line))?;
+            if samples.insert(name.to_string(), value).is_some() {
+                return Err(format!("duplicate sample '{}'", name));
+            }
+        }
+        Ok(samples)
+    }
+
+    fn statistics() -> Statistics {
+        Statistics {
+            total_voters: 12,
+            active_queries_count: 3,
+            total_stake: "1500.".to_string(),
+            resolution_rate: 0.75,
+            protocol_treasury: "42.5".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_output_is_valid_prometheus_exposition() {
+        let output = render(&statistics()).unwrap();
+        let samples = parse_exposition(&output).unwrap();
+
+        assert_eq!(samples.len(), 5);
+        assert_eq!(samples["alethea_voter_count"], 12.0);
+        assert_eq!(samples["alethea_active_queries"], 3.0);
+        assert_eq!(samples["alethea_total_stake"], 1500.0);
+        assert_eq!(samples["alethea_resolution_rate"], 0.75);
+        assert_eq!(samples["alethea_treasury_balance"], 42.5);
+    }
+
+    #[test]
+    fn test_statistics_deserialize_from_graphql() {
+        let response: GraphQlResponse = serde_json::from_str(
+            r#"{"data":{"statistics":{"totalVoters":2,"activeQueriesCount":1,"totalStake":"200.","resolutionRate":0.5,"protocolTreasury":"0."}}}"#,
+        ).unwrap();
+        let stats = response.data.unwrap().statistics;
+        assert_eq!(stats.total_voters, 2);
+        assert_eq!(render(&stats).unwrap().lines().count(), 15);
+    }
+
+    #[test]
+    fn test_invalid_amount_is_an_error() {
+        let stats = Statistics { total_stake: "lots".to_string(), ..statistics() };
+        assert!(render(&stats).is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_missing_endpoint_is_reported() {
+        let cache = MetricsCache::new(None, DEFAULT_CACHE_TTL);
+        let error = cache.get().await.unwrap_err();
+        assert!(error.to_string().contains("ORACLE_GRAPHQL_URL"));
+    }
+}
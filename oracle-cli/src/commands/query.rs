@@ -92,6 +92,30 @@ pub async fn handle_stats(
     Ok(())
 }
 
+pub async fn handle_verify_commit(
+    config: &Config,
+    query_id: u64,
+    address: &str,
+    value: &str,
+    salt: &str,
+) -> Result<()> {
+    println!("{}", format!("Verifying commit on query {}...", query_id).bright_blue());
+    println!();
+    
+    let query = operations::build_verify_commit_query(query_id, address, value, salt);
+    let result = execute_graphql_query(config, &query).await?;
+    
+    if result["verifyCommit"].as_bool() == Some(true) {
+        println!("{}", "✅ Value and salt match your commit".bright_green());
+    } else {
+        println!("{}", "❌ Value and salt do NOT match your commit".bright_red());
+        println!("   Revealing with them would fail; check the value and salt you committed with");
+    }
+    println!();
+    
+    Ok(())
+}
+
 async fn execute_graphql_query(config: &Config, query: &str) -> Result<Value> {
     let client = reqwest::Client::new();
     
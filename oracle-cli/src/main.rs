@@ -117,6 +117,25 @@ enum Commands {
         confidence: Option<u8>,
     },
     
+    /// Check a value and salt reproduce your commit, without revealing
+    VerifyCommit {
+        /// Query ID
+        #[arg(long)]
+        query_id: u64,
+        
+        /// Voter address (chain ID that committed)
+        #[arg(long)]
+        address: String,
+        
+        /// Vote value that was committed
+        #[arg(long)]
+        value: String,
+        
+        /// Salt used for the commit
+        #[arg(long)]
+        salt: String,
+    },
+    
     /// Resolve a query
     Resolve {
         /// Query ID
@@ -241,6 +260,9 @@ async fn main() -> Result<()> {
         Commands::Vote { query_id, value, confidence } => {
             commands::vote::handle_vote(&config, query_id, value, confidence).await
         }
+        Commands::VerifyCommit { query_id, address, value, salt } => {
+            commands::query::handle_verify_commit(&config, query_id, &address, &value, &salt).await
+        }
         Commands::Resolve { query_id } => {
             commands::resolve::handle_resolve(&config, query_id).await
         }
@@ -204,6 +204,19 @@ pub fn build_query_query(query_id: u64) -> String {
     )
 }
 
+/// Build GraphQL query checking a value and salt against a stored commit
+pub fn build_verify_commit_query(query_id: u64, address: &str, value: &str, salt: &str) -> String {
+    format!(
+        r#"{{
+            verifyCommit(queryId: {}, address: {}, value: {}, salt: {})
+        }}"#,
+        query_id,
+        json!(address),
+        json!(value),
+        json!(salt)
+    )
+}
+
 /// Build GraphQL query for statistics
 pub fn build_stats_query() -> String {
     r#"{
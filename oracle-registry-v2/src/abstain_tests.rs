@@ -0,0 +1,76 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for abstain votes: counted as participation, left out of the result
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, chain, query};
+    use crate::state::{validate_abstain_outcomes, ResolutionResult, ABSTAIN_VOTE};
+
+    #[test]
+    fn test_abstain_only_valid_when_allowed() {
+        let mut q = query(&["Yes", "No"]);
+        let err = q.check_vote_value(ABSTAIN_VOTE).unwrap_err();
+        assert!(err.contains("Valid outcomes: Yes, No"));
+
+        q.allow_abstain = true;
+        assert!(q.check_vote_value(ABSTAIN_VOTE).is_ok());
+        assert!(q.check_vote_value("Yes").is_ok());
+        assert!(q.check_vote_value("Maybe").unwrap_err().contains("Yes, No, Abstain"));
+    }
+
+    #[test]
+    fn test_abstentions_do_not_affect_winner() {
+        let mut q = query(&["Yes", "No"]);
+        q.allow_abstain = true;
+        q.min_votes = 5;
+        add_vote(&mut q, 2, "No");
+        add_vote(&mut q, 3, "Yes");
+        add_vote(&mut q, 4, "Yes");
+        add_vote(&mut q, 5, ABSTAIN_VOTE);
+        add_vote(&mut q, 6, ABSTAIN_VOTE);
+
+        // All five votes count towards participation
+        assert!(q.votes.len() >= q.min_votes);
+
+        let result = ResolutionResult::from_majority(q.tallied_votes().map(|(_, vote)| vote));
+        assert_eq!(result, ResolutionResult::Outcome("Yes".to_string()));
+        assert_eq!(q.distinct_voted_outcomes(), 2);
+    }
+
+    #[test]
+    fn test_abstainers_not_slashed_for_correctness() {
+        let mut q = query(&["Yes", "No"]);
+        q.allow_abstain = true;
+        add_vote(&mut q, 2, "Yes");
+        add_vote(&mut q, 3, "No");
+        add_vote(&mut q, 4, ABSTAIN_VOTE);
+
+        let result = ResolutionResult::Outcome("Yes".to_string());
+        let incorrect: Vec<_> = q.tallied_votes()
+            .filter(|(_, vote)| !result.matches_vote(&vote.value))
+            .map(|(voter, _)| *voter)
+            .collect();
+
+        assert_eq!(incorrect, vec![chain(3)]);
+        assert!(q.tallied_votes().all(|(voter, _)| *voter != chain(4)));
+    }
+
+    #[test]
+    fn test_abstain_is_a_regular_vote_without_flag() {
+        let mut q = query(&["Yes", "Abstain"]);
+        add_vote(&mut q, 2, "Abstain");
+
+        assert!(!q.is_abstention("Abstain"));
+        assert_eq!(q.tallied_votes().count(), 1);
+    }
+
+    #[test]
+    fn test_abstain_outcome_reserved_when_allowed() {
+        let outcomes = vec!["Yes".to_string(), ABSTAIN_VOTE.to_string()];
+
+        assert!(validate_abstain_outcomes(false, &outcomes).is_ok());
+        assert!(validate_abstain_outcomes(true, &outcomes).unwrap_err().contains("reserved"));
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for listing queries a keeper can resolve or expire
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, query};
+    use crate::state::{actionable_queries, Query, QueryStatus};
+    use linera_sdk::linera_base_types::Timestamp;
+
+    const GRACE_SECS: u64 = 30;
+
+    fn secs(s: u64) -> Timestamp {
+        Timestamp::from(s * 1_000_000)
+    }
+
+    /// Fixture query (deadline 3s, reveal end 2s, min 1 vote) with `votes` votes
+    fn with_votes(id: u64, votes: u8) -> Query {
+        let mut q = query(&["Yes", "No"]);
+        q.id = id;
+        for voter in 0..votes {
+            add_vote(&mut q, voter + 2, "Yes");
+        }
+        q
+    }
+
+    #[test]
+    fn test_mixed_queries_are_categorized() {
+        let resolvable = with_votes(1, 2);
+        let expirable = with_votes(2, 0);
+
+        let mut not_due = with_votes(3, 2);
+        not_due.deadline = secs(100);
+
+        let mut already_resolved = with_votes(4, 2);
+        already_resolved.status = QueryStatus::Resolved;
+
+        // Past the deadline with votes, but reveals may still land
+        let mut in_grace = with_votes(5, 1);
+        in_grace.reveal_phase_end = secs(35);
+        in_grace.deadline = secs(36);
+
+        let mut short_of_min = with_votes(6, 1);
+        short_of_min.min_votes = 3;
+
+        let queries = vec![resolvable, expirable, not_due, already_resolved, in_grace, short_of_min];
+        let actionable = actionable_queries(&queries, secs(40), GRACE_SECS);
+
+        assert_eq!(actionable.resolvable, vec![1]);
+        assert_eq!(actionable.expirable, vec![2, 6]);
+    }
+
+    #[test]
+    fn test_resolvable_once_grace_window_ends() {
+        let queries = vec![with_votes(1, 1)];
+
+        // Reveal phase ends at 2s, so resolution opens at 32s
+        assert!(actionable_queries(&queries, secs(31), GRACE_SECS).resolvable.is_empty());
+        assert_eq!(actionable_queries(&queries, secs(32), GRACE_SECS).resolvable, vec![1]);
+    }
+
+    #[test]
+    fn test_expirable_as_soon_as_deadline_passes() {
+        let queries = vec![with_votes(1, 0)];
+
+        assert!(actionable_queries(&queries, secs(2), GRACE_SECS).expirable.is_empty());
+        assert_eq!(actionable_queries(&queries, secs(3), GRACE_SECS).expirable, vec![1]);
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the cap on concurrently active queries
+
+#[cfg(test)]
+mod tests {
+    use crate::state::ProtocolParameters;
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::{Message, Operation, OperationResponse};
+
+    fn capped(max_active_queries: u64) -> TestHarness {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            max_active_queries,
+            ..ProtocolParameters::default()
+        });
+        assert!(harness.register(chain(1), 100).success);
+        harness
+    }
+
+    fn create(harness: &mut TestHarness) -> OperationResponse {
+        harness.create_query(&["Yes", "No"], 1, 120)
+    }
+
+    fn query_id(response: OperationResponse) -> u64 {
+        assert!(response.success, "{}", response.message);
+        response.data.and_then(|data| data.query_id).expect("query id")
+    }
+
+    #[test]
+    fn test_creation_is_rejected_at_the_cap_until_a_query_resolves() {
+        let mut harness = capped(2);
+        let first = query_id(create(&mut harness));
+        query_id(create(&mut harness));
+
+        let response = create(&mut harness);
+        assert!(!response.success);
+        assert!(response.message.contains("Too many active queries (2 of max 2)"));
+
+        assert!(harness.submit_vote(chain(1), first, "Yes").success);
+        harness.advance_past_deadline(first);
+        let response = harness.resolve(first);
+        assert!(response.success, "{}", response.message);
+
+        query_id(create(&mut harness));
+    }
+
+    #[test]
+    fn test_admin_can_raise_the_cap() {
+        let mut harness = capped(1);
+        query_id(create(&mut harness));
+        assert!(!create(&mut harness).success);
+
+        let admin = harness.admin;
+        let params = oracle_registry_v2::state::ProtocolParameters { max_active_queries: 2, ..Default::default() };
+        let response = harness.execute_as(admin, Operation::UpdateParameters { params });
+        assert!(response.success, "{}", response.message);
+
+        query_id(create(&mut harness));
+    }
+
+    #[test]
+    fn test_cross_chain_creation_is_rejected_at_the_cap() {
+        let mut harness = capped(1);
+        query_id(create(&mut harness));
+
+        harness.deliver_message(chain(5), Message::CreateQuery {
+            sender_chain: chain(5),
+            description: "Will it rain?".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            strategy: String::new(),
+            min_votes: Some(1),
+            reward_amount: Amount::from_tokens(10),
+            duration_secs: Some(120),
+        });
+
+        assert_eq!(harness.state().get_active_queries().blocking_wait().len(), 1);
+        let failed = harness.state().get_failed_messages(10).blocking_wait();
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].error.contains("Too many active queries"));
+    }
+
+    #[test]
+    fn test_zero_cap_is_rejected() {
+        let mut harness = capped(1);
+        let admin = harness.admin;
+        let params = oracle_registry_v2::state::ProtocolParameters { max_active_queries: 0, ..Default::default() };
+        let response = harness.execute_as(admin, Operation::UpdateParameters { params });
+        assert!(!response.success);
+        assert!(response.message.contains("Max active queries must be at least 1"));
+    }
+
+    #[test]
+    fn test_check_active_query_capacity() {
+        let params = ProtocolParameters { max_active_queries: 3, ..ProtocolParameters::default() };
+        assert!(params.check_active_query_capacity(0).is_ok());
+        assert!(params.check_active_query_capacity(2).is_ok());
+        assert!(params.check_active_query_capacity(3).is_err());
+        assert_eq!(ProtocolParameters::default().max_active_queries, 1000);
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the bounded activity log behind the activity feed
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{ActivityEntry, ActivityKind, MAX_ACTIVITY_LOG};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+
+    fn feed(harness: &TestHarness, limit: usize) -> Vec<ActivityEntry> {
+        harness.state().get_activity_feed(limit).blocking_wait()
+    }
+
+    #[test]
+    fn test_feed_lists_most_recent_activities_in_order() {
+        let mut harness = TestHarness::new();
+        assert!(harness.register(chain(1), 100).success);
+        assert!(harness.register(chain(2), 100).success);
+        let created = harness.create_query(&["Yes", "No"], 2, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        assert!(harness.submit_vote(chain(1), query_id, "Yes").success);
+        assert!(harness.submit_vote(chain(2), query_id, "Yes").success);
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+
+        // The last four activities, most recent first
+        let recent = feed(&harness, 4);
+        let kinds: Vec<_> = recent.iter().map(|entry| entry.kind).collect();
+        assert_eq!(kinds, vec![
+            ActivityKind::QueryResolved,
+            ActivityKind::VoteCast,
+            ActivityKind::VoteCast,
+            ActivityKind::QueryCreated,
+        ]);
+        assert_eq!(recent[0].query_id, Some(query_id));
+        assert_eq!(recent[0].detail.as_deref(), Some("Yes"));
+        assert_eq!(recent[1].chain_id, Some(chain(2)));
+        assert_eq!(recent[2].chain_id, Some(chain(1)));
+        assert!(recent.windows(2).all(|pair| pair[0].id > pair[1].id));
+        assert!(recent.windows(2).all(|pair| pair[0].timestamp >= pair[1].timestamp));
+
+        // The full feed ends with the two registrations
+        let all = feed(&harness, 100);
+        assert_eq!(all.len(), 6);
+        assert_eq!(all[4].kind, ActivityKind::VoterRegistered);
+        assert_eq!(all[4].chain_id, Some(chain(2)));
+        assert_eq!(all[5].chain_id, Some(chain(1)));
+    }
+
+    #[test]
+    fn test_activity_log_is_capped() {
+        let mut harness = TestHarness::new();
+        let now = harness.now();
+        let total = MAX_ACTIVITY_LOG + 5;
+        for query_id in 0..total {
+            harness.contract.state
+                .record_activity(ActivityKind::QueryCreated, None, Some(query_id), None, now)
+                .unwrap();
+        }
+
+        let all = feed(&harness, usize::MAX);
+        assert_eq!(all.len() as u64, MAX_ACTIVITY_LOG);
+        assert_eq!(all.first().unwrap().query_id, Some(total - 1));
+        assert_eq!(all.last().unwrap().query_id, Some(5));
+
+        // The oldest entries are evicted from storage, not just hidden
+        let evicted = harness.state().activity_log.get(&4).blocking_wait().unwrap();
+        assert!(evicted.is_none());
+    }
+}
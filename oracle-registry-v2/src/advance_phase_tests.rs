@@ -0,0 +1,49 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for manually advancing a stuck query's voting phase
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::query;
+    use crate::state::{QueryStatus, VotingPhase};
+    use linera_sdk::linera_base_types::Timestamp;
+
+    #[test]
+    fn test_commit_advances_to_reveal_after_commit_end() {
+        let q = query(&["Yes", "No"]);
+
+        let next = q.next_phase_at(Timestamp::from(1_500_000), 0).unwrap();
+        assert_eq!(next, VotingPhase::Reveal);
+    }
+
+    #[test]
+    fn test_commit_not_advanced_before_commit_end() {
+        let q = query(&["Yes", "No"]);
+
+        let err = q.next_phase_at(Timestamp::from(500_000), 0).unwrap_err();
+        assert!(err.contains("commit phase has not ended"));
+    }
+
+    #[test]
+    fn test_reveal_advances_to_completed_after_grace_window() {
+        let mut q = query(&["Yes", "No"]);
+        q.phase = VotingPhase::Reveal;
+
+        assert!(q.next_phase_at(Timestamp::from(2_500_000), 1).is_err());
+        let next = q.next_phase_at(Timestamp::from(3_000_000), 1).unwrap();
+        assert_eq!(next, VotingPhase::Completed);
+    }
+
+    #[test]
+    fn test_completed_or_inactive_not_advanced() {
+        let mut q = query(&["Yes", "No"]);
+        q.phase = VotingPhase::Completed;
+        assert!(q.next_phase_at(Timestamp::from(5_000_000), 0).is_err());
+
+        let mut q = query(&["Yes", "No"]);
+        q.status = QueryStatus::Cancelled;
+        let err = q.next_phase_at(Timestamp::from(5_000_000), 0).unwrap_err();
+        assert!(err.contains("not active"));
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for archiving old resolved queries
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, query};
+    use crate::state::{Query, QueryStatus, ResolutionResult};
+    use linera_sdk::linera_base_types::Timestamp;
+
+    /// Helper to create a query closed at `closed_at` with the given status
+    fn closed_query(id: u64, status: QueryStatus, closed_at: u64) -> Query {
+        let mut q = query(&["Yes", "No"]);
+        q.id = id;
+        q.status = status;
+        q.resolved_at = Some(Timestamp::from(closed_at));
+        q
+    }
+
+    #[test]
+    fn test_only_old_closed_queries_are_archived() {
+        let cutoff = Timestamp::from(10_000_000);
+        let queries = vec![
+            closed_query(1, QueryStatus::Resolved, 5_000_000),
+            closed_query(2, QueryStatus::Expired, 9_000_000),
+            closed_query(3, QueryStatus::Resolved, 12_000_000),
+            query(&["Yes", "No"]),
+        ];
+
+        let (archived, kept): (Vec<_>, Vec<_>) =
+            queries.into_iter().partition(|q| q.is_archivable(cutoff));
+
+        assert_eq!(archived.iter().map(|q| q.id).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(kept.len(), 2);
+        assert!(kept.iter().all(|q| !q.is_archivable(cutoff)));
+    }
+
+    #[test]
+    fn test_summary_keeps_result_and_drops_votes() {
+        let mut q = closed_query(4, QueryStatus::Resolved, 5_000_000);
+        add_vote(&mut q, 2, "Yes");
+        add_vote(&mut q, 3, "Yes");
+        add_vote(&mut q, 4, "No");
+        let result = ResolutionResult::from_majority(q.votes.values());
+        q.result_display = Some(result.to_string());
+        q.result = Some(result);
+
+        let summary = q.to_archived();
+        assert_eq!(summary.id, 4);
+        assert_eq!(summary.status, QueryStatus::Resolved);
+        assert_eq!(summary.result, Some(ResolutionResult::Outcome("Yes".to_string())));
+        assert_eq!(summary.result_display.as_deref(), Some("Yes"));
+        assert_eq!(summary.vote_count, 3);
+        assert_eq!(summary.outcomes, q.outcomes);
+    }
+}
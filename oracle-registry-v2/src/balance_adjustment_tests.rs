@@ -0,0 +1,110 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for funding the reward pool and adjusting the treasury with token transfers
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::{Account, AccountOwner, Amount};
+    use oracle_registry_v2::Operation;
+
+    fn admin_owner() -> AccountOwner {
+        AccountOwner::Address20([7; 20])
+    }
+
+    fn funded_harness() -> TestHarness {
+        let mut harness = TestHarness::new();
+        harness.stub_token_app();
+        harness.sign_as(admin_owner());
+        harness
+    }
+
+    #[test]
+    fn test_fund_reward_pool_transfers_tokens_from_signer() {
+        let mut harness = funded_harness();
+        let admin = harness.admin;
+        let pool_before = *harness.state().reward_pool.get();
+        let held_before = *harness.state().total_tokens_held.get();
+
+        let amount = Amount::from_tokens(500);
+        let response = harness.execute_as(admin, Operation::FundRewardPool { amount });
+        assert!(response.success, "{}", response.message);
+
+        assert_eq!(harness.token_deposits(), vec![(admin_owner(), amount)]);
+        assert_eq!(*harness.state().reward_pool.get(), pool_before.saturating_add(amount));
+        assert_eq!(*harness.state().total_tokens_held.get(), held_before.saturating_add(amount));
+    }
+
+    #[test]
+    fn test_fund_reward_pool_rejects_non_admin_and_zero_amount() {
+        let mut harness = funded_harness();
+        let pool_before = *harness.state().reward_pool.get();
+
+        let response = harness.execute_as(chain(5), Operation::FundRewardPool { amount: Amount::from_tokens(1) });
+        assert!(!response.success);
+        assert!(response.message.contains("Unauthorized"));
+
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::FundRewardPool { amount: Amount::ZERO });
+        assert!(!response.success);
+
+        assert!(harness.token_deposits().is_empty());
+        assert_eq!(*harness.state().reward_pool.get(), pool_before);
+    }
+
+    #[test]
+    fn test_fund_reward_pool_requires_token_app() {
+        let mut harness = TestHarness::new();
+        let admin = harness.admin;
+        let pool_before = *harness.state().reward_pool.get();
+
+        let response = harness.execute_as(admin, Operation::FundRewardPool { amount: Amount::from_tokens(1) });
+        assert!(!response.success);
+        assert!(response.message.contains("Token application not configured"), "{}", response.message);
+        assert_eq!(*harness.state().reward_pool.get(), pool_before);
+    }
+
+    #[test]
+    fn test_adjust_treasury_increase_then_decrease() {
+        let mut harness = funded_harness();
+        let admin = harness.admin;
+        let treasury_before = *harness.state().protocol_treasury.get();
+        let held_before = *harness.state().total_tokens_held.get();
+
+        let response = harness.execute_as(admin, Operation::AdjustTreasury {
+            amount: Amount::from_tokens(80),
+            increase: true,
+        });
+        assert!(response.success, "{}", response.message);
+        assert_eq!(harness.token_deposits(), vec![(admin_owner(), Amount::from_tokens(80))]);
+        assert_eq!(*harness.state().protocol_treasury.get(), treasury_before.saturating_add(Amount::from_tokens(80)));
+
+        let response = harness.execute_as(admin, Operation::AdjustTreasury {
+            amount: Amount::from_tokens(30),
+            increase: false,
+        });
+        assert!(response.success, "{}", response.message);
+        let released_to = Account { chain_id: admin, owner: admin_owner() };
+        assert_eq!(harness.token_releases(), vec![(released_to, Amount::from_tokens(30))]);
+        assert_eq!(*harness.state().protocol_treasury.get(), treasury_before.saturating_add(Amount::from_tokens(50)));
+        assert_eq!(*harness.state().total_tokens_held.get(), held_before.saturating_add(Amount::from_tokens(50)));
+    }
+
+    #[test]
+    fn test_adjust_treasury_decrease_limited_to_held_tokens() {
+        let mut harness = funded_harness();
+        let admin = harness.admin;
+        // Treasury credited without any tokens behind it
+        harness.contract.state.protocol_treasury.set(Amount::from_tokens(100));
+
+        let response = harness.execute_as(admin, Operation::AdjustTreasury {
+            amount: Amount::from_tokens(10),
+            increase: false,
+        });
+        assert!(!response.success);
+        assert!(response.message.contains("backed by held tokens"), "{}", response.message);
+        assert!(harness.token_releases().is_empty());
+        assert_eq!(*harness.state().protocol_treasury.get(), Amount::from_tokens(100));
+    }
+}
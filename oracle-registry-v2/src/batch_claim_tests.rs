@@ -0,0 +1,117 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for claim operators and BatchClaimFor
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{check_claim_operator, validate_batch_claim, MAX_BATCH_CLAIM};
+    use crate::test_support::{chain, TestHarness};
+    use futures::FutureExt;
+    use linera_sdk::linera_base_types::{Amount, ChainId};
+    use oracle_registry_v2::Operation;
+
+    const OPERATOR: u8 = 50;
+
+    fn set_operator(harness: &mut TestHarness, voter: ChainId, operator: Option<ChainId>) {
+        let response = harness.execute_as(voter, Operation::SetClaimOperator { operator });
+        assert!(response.success, "{}", response.message);
+    }
+
+    /// Three registered voters with pending rewards, all delegated to OPERATOR
+    fn delegated_harness() -> TestHarness {
+        let mut harness = TestHarness::new();
+        harness.stub_token_app();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+            set_operator(&mut harness, chain(id), Some(chain(OPERATOR)));
+            harness.credit_rewards(chain(id), Amount::from_tokens(id as u128));
+        }
+        harness
+    }
+
+    #[test]
+    fn test_operator_claims_for_three_voters() {
+        let mut harness = delegated_harness();
+
+        let response = harness.execute_as(chain(OPERATOR), Operation::BatchClaimFor {
+            voters: vec![chain(1), chain(2), chain(3)],
+        });
+        assert!(response.success, "{}", response.message);
+
+        let data = response.data.expect("response data");
+        assert_eq!(data.rewards_claimed, Some(Amount::from_tokens(6).to_string()));
+        let results = data.batch_claims.expect("per-voter results");
+        assert_eq!(results.len(), 3);
+        for (result, id) in results.iter().zip(1..=3u8) {
+            assert_eq!(result.voter_address, chain(id).to_string());
+            assert_eq!(result.claimed, Some(Amount::from_tokens(id as u128).to_string()));
+            assert_eq!(result.error, None);
+        }
+
+        for id in 1..=3 {
+            let pending = harness.state().get_pending_rewards(&chain(id)).now_or_never().unwrap();
+            assert_eq!(pending, Amount::ZERO);
+        }
+        assert_eq!(*harness.state().total_rewards_distributed.get(), Amount::from_tokens(6));
+    }
+
+    #[test]
+    fn test_unauthorized_voter_is_reported_without_failing_batch() {
+        let mut harness = delegated_harness();
+        set_operator(&mut harness, chain(2), None);
+
+        let response = harness.execute_as(chain(OPERATOR), Operation::BatchClaimFor {
+            voters: vec![chain(1), chain(2), chain(3)],
+        });
+        assert!(response.success, "{}", response.message);
+
+        let results = response.data.unwrap().batch_claims.unwrap();
+        assert!(results[0].error.is_none());
+        assert!(results[1].error.as_deref().unwrap().contains("is not authorized to claim"));
+        assert!(results[2].error.is_none());
+
+        let pending = harness.state().get_pending_rewards(&chain(2)).now_or_never().unwrap();
+        assert_eq!(pending, Amount::from_tokens(2));
+    }
+
+    #[test]
+    fn test_admin_may_claim_without_operator() {
+        let mut harness = delegated_harness();
+        set_operator(&mut harness, chain(1), None);
+        let admin = harness.admin;
+
+        let response = harness.execute_as(admin, Operation::BatchClaimFor { voters: vec![chain(1)] });
+        let results = response.data.unwrap().batch_claims.unwrap();
+        assert!(results[0].error.is_none());
+    }
+
+    #[test]
+    fn test_voter_cannot_be_own_operator() {
+        let mut harness = delegated_harness();
+
+        let response = harness.execute_as(chain(1), Operation::SetClaimOperator { operator: Some(chain(1)) });
+        assert!(!response.success);
+    }
+
+    #[test]
+    fn test_check_claim_operator() {
+        let (voter, operator, other) = (chain(1), chain(2), chain(3));
+
+        assert!(check_claim_operator(&voter, &voter, false, None).is_ok());
+        assert!(check_claim_operator(&operator, &voter, false, Some(operator)).is_ok());
+        assert!(check_claim_operator(&other, &voter, true, None).is_ok());
+        assert!(check_claim_operator(&other, &voter, false, Some(operator)).is_err());
+        assert!(check_claim_operator(&operator, &voter, false, None).is_err());
+    }
+
+    #[test]
+    fn test_validate_batch_claim() {
+        assert!(validate_batch_claim(&[]).is_err());
+        assert!(validate_batch_claim(&[chain(1), chain(2)]).is_ok());
+        assert!(validate_batch_claim(&[chain(1), chain(1)]).unwrap_err().contains("Duplicate"));
+
+        let too_many: Vec<ChainId> = (0..=MAX_BATCH_CLAIM as u8).map(chain).collect();
+        assert!(validate_batch_claim(&too_many).unwrap_err().contains("Too many voters"));
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for registering the admin chain as a bootstrap voter at instantiation
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::TestHarness;
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::InstantiationArgument;
+
+    fn bootstrap(harness: &mut TestHarness, argument: InstantiationArgument) {
+        harness.contract.bootstrap_admin_voter(argument).blocking_wait();
+    }
+
+    #[test]
+    fn test_bootstrapped_registry_has_admin_voter() {
+        let mut harness = TestHarness::new();
+        bootstrap(&mut harness, InstantiationArgument {
+            bootstrap_admin_as_voter: true,
+            bootstrap_stake: None,
+        });
+
+        assert_eq!(*harness.state().voter_count.get(), 1);
+        let voter = harness.voter(&harness.admin).expect("admin registered");
+        assert!(voter.is_active);
+        assert_eq!(voter.stake, harness.params().min_stake);
+        assert_eq!(*harness.state().total_stake.get(), harness.params().min_stake);
+    }
+
+    #[test]
+    fn test_bootstrap_uses_configured_stake() {
+        let mut harness = TestHarness::new();
+        bootstrap(&mut harness, InstantiationArgument {
+            bootstrap_admin_as_voter: true,
+            bootstrap_stake: Some(Amount::from_tokens(1000)),
+        });
+
+        assert_eq!(harness.voter(&harness.admin).unwrap().stake, Amount::from_tokens(1000));
+    }
+
+    #[test]
+    fn test_default_argument_registers_no_voter() {
+        let mut harness = TestHarness::new();
+        bootstrap(&mut harness, InstantiationArgument::default());
+
+        assert_eq!(*harness.state().voter_count.get(), 0);
+        assert!(harness.voter(&harness.admin).is_none());
+    }
+}
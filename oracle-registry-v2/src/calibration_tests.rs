@@ -0,0 +1,65 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for voter confidence calibration scores
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::voter_info;
+    use crate::state::{brier_score_bps, VoterInfo};
+
+    fn scored(votes: &[(u8, bool)]) -> VoterInfo {
+        let mut info = voter_info(1, 100, 50);
+        for &(confidence, was_correct) in votes {
+            info.record_calibration(confidence, was_correct);
+        }
+        info
+    }
+
+    #[test]
+    fn test_brier_score_bps() {
+        assert_eq!(brier_score_bps(100, true), 0);
+        assert_eq!(brier_score_bps(100, false), 10000);
+        assert_eq!(brier_score_bps(0, false), 0);
+        assert_eq!(brier_score_bps(50, true), 2500);
+        assert_eq!(brier_score_bps(75, false), 5625);
+    }
+
+    #[test]
+    fn test_new_voter_has_no_score() {
+        let info = voter_info(1, 100, 50);
+        assert_eq!(info.calibration_score, None);
+        assert_eq!(info.calibrated_votes, 0);
+    }
+
+    #[test]
+    fn test_overconfident_wrong_voter_scores_worse() {
+        let overconfident = scored(&[(100, false)]);
+        let calibrated = scored(&[(60, false)]);
+
+        assert_eq!(overconfident.calibration_score, Some(10000));
+        assert_eq!(calibrated.calibration_score, Some(3600));
+    }
+
+    #[test]
+    fn test_calibrated_voter_beats_overconfident_with_same_accuracy() {
+        // Both are right three times out of four
+        let record = [true, true, false, true];
+        let overconfident = scored(&record.map(|correct| (100, correct)));
+        let calibrated = scored(&record.map(|correct| (75, correct)));
+
+        assert_eq!(overconfident.calibration_score, Some(2500));
+        assert_eq!(calibrated.calibration_score, Some(1875));
+        assert_eq!(calibrated.calibrated_votes, 4);
+    }
+
+    #[test]
+    fn test_running_mean_rounds_to_nearest() {
+        let info = scored(&[(100, true), (100, true), (99, false)]);
+        // (0 + 0 + 9801) / 3 = 3267
+        assert_eq!(info.calibration_score, Some(3267));
+
+        let info = scored(&[(100, true), (50, true)]);
+        assert_eq!(info.calibration_score, Some(1250));
+    }
+}
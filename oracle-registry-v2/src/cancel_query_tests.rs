@@ -0,0 +1,79 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for cancelling a query and releasing committed voters' stake
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{chain, query, vote, voter_info};
+    use crate::state::{QueryStatus, VoteCommit};
+    use linera_sdk::linera_base_types::{Amount, ChainId, Timestamp};
+
+    fn commit(voter: ChainId) -> VoteCommit {
+        VoteCommit {
+            voter,
+            commit_hash: "abc".to_string(),
+            committed_at: Timestamp::from(100_000),
+            revealed: false,
+        }
+    }
+
+    #[test]
+    fn test_committed_stake_released_on_cancel() {
+        let mut q = query(&["Yes", "No"]);
+        q.commits.insert(chain(2), commit(chain(2)));
+        let mut voter = voter_info(2, 1000, 50);
+
+        let locked = Amount::from_tokens(100);
+        voter.lock_stake(locked).unwrap();
+        assert_eq!(voter.locked_stake, locked);
+
+        assert!(q.check_cancellable(&chain(1), false).is_ok());
+        assert_eq!(q.stake_lockers(), vec![chain(2)]);
+        voter.unlock_stake(locked).unwrap();
+        assert_eq!(voter.locked_stake, Amount::ZERO);
+    }
+
+    #[test]
+    fn test_stake_lockers_deduplicates_commits_and_votes() {
+        let mut q = query(&["Yes", "No"]);
+        q.commits.insert(chain(2), commit(chain(2)));
+        q.commits.insert(chain(3), commit(chain(3)));
+        q.votes.insert(chain(3), vote(3, "Yes"));
+        q.votes.insert(chain(4), vote(4, "No"));
+
+        assert_eq!(q.stake_lockers(), vec![chain(2), chain(3), chain(4)]);
+    }
+
+    #[test]
+    fn test_lock_rejects_more_than_available() {
+        let mut voter = voter_info(2, 100, 50);
+        voter.lock_stake(Amount::from_tokens(60)).unwrap();
+
+        let err = voter.lock_stake(Amount::from_tokens(60)).unwrap_err();
+        assert!(err.contains("Insufficient available stake"));
+        let err = voter.unlock_stake(Amount::from_tokens(61)).unwrap_err();
+        assert!(err.contains("Cannot unlock more than locked"));
+    }
+
+    #[test]
+    fn test_admin_can_cancel_but_others_cannot() {
+        let q = query(&["Yes", "No"]);
+
+        assert!(q.check_cancellable(&chain(9), true).is_ok());
+        let err = q.check_cancellable(&chain(9), false).unwrap_err();
+        assert!(err.contains("Unauthorized"));
+    }
+
+    #[test]
+    fn test_cancel_rejected_after_reveal_or_when_inactive() {
+        let mut q = query(&["Yes", "No"]);
+        q.votes.insert(chain(2), vote(2, "Yes"));
+        let err = q.check_cancellable(&chain(1), false).unwrap_err();
+        assert!(err.contains("already has revealed votes"));
+
+        let mut q = query(&["Yes", "No"]);
+        q.status = QueryStatus::Resolved;
+        assert!(q.check_cancellable(&chain(1), false).is_err());
+    }
+}
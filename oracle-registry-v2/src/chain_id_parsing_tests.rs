@@ -0,0 +1,37 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for parsing chain IDs from user input
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::chain;
+    use crate::state::parse_chain_id;
+
+    #[test]
+    fn test_prefixed_and_unprefixed_parse_the_same() {
+        let hex = chain(7).to_string();
+
+        assert_eq!(parse_chain_id(&hex).unwrap(), chain(7));
+        assert_eq!(parse_chain_id(&format!("0x{}", hex)).unwrap(), chain(7));
+        assert_eq!(parse_chain_id(&format!("0X{}", hex)).unwrap(), chain(7));
+        assert_eq!(parse_chain_id(&format!("  {}\n", hex)).unwrap(), chain(7));
+    }
+
+    #[test]
+    fn test_too_short_reports_expected_length() {
+        let err = parse_chain_id("0xabc123").unwrap_err();
+
+        assert!(err.contains("expected 64 hex characters"));
+        assert!(err.contains("got 6"));
+    }
+
+    #[test]
+    fn test_non_hex_reports_position() {
+        let mut hex = chain(7).to_string();
+        hex.replace_range(10..11, "g");
+
+        let err = parse_chain_id(&hex).unwrap_err();
+        assert!(err.contains("'g' at position 10 is not a hex digit"));
+    }
+}
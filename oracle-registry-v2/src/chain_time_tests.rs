@@ -0,0 +1,27 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for formatting chain time returned by the `now` resolvers
+
+#[cfg(test)]
+mod tests {
+    use crate::state::timestamp_to_iso8601;
+    use linera_sdk::linera_base_types::Timestamp;
+
+    #[test]
+    fn test_runtime_time_formats_with_micros() {
+        let now = Timestamp::from(1_700_000_000_123_456);
+
+        assert_eq!(now.micros().to_string(), "1700000000123456");
+        assert_eq!(timestamp_to_iso8601(now), "2023-11-14T22:13:20.123456Z");
+    }
+
+    #[test]
+    fn test_epoch_and_leap_day() {
+        assert_eq!(timestamp_to_iso8601(Timestamp::from(0)), "1970-01-01T00:00:00.000000Z");
+        assert_eq!(
+            timestamp_to_iso8601(Timestamp::from(1_709_164_800_000_000)),
+            "2024-02-29T00:00:00.000000Z"
+        );
+    }
+}
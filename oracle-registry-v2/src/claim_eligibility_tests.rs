@@ -0,0 +1,40 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the per-voter reward claimability flag shown in GraphQL
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::voter_info;
+    use linera_sdk::linera_base_types::Amount;
+
+    #[test]
+    fn test_claim_allowed_in_normal_state() {
+        let voter = voter_info(2, 100, 50);
+
+        assert_eq!(voter.claim_blocked_reason(false, Amount::from_tokens(5)), None);
+    }
+
+    #[test]
+    fn test_paused_protocol_blocks_claim() {
+        let voter = voter_info(2, 100, 50);
+
+        let reason = voter.claim_blocked_reason(true, Amount::from_tokens(5));
+        assert_eq!(reason.as_deref(), Some("Protocol is paused"));
+    }
+
+    #[test]
+    fn test_inactive_voter_or_nothing_pending_blocks_claim() {
+        let mut voter = voter_info(2, 100, 50);
+        assert_eq!(
+            voter.claim_blocked_reason(false, Amount::ZERO).as_deref(),
+            Some("No pending rewards to claim")
+        );
+
+        voter.is_active = false;
+        assert_eq!(
+            voter.claim_blocked_reason(false, Amount::from_tokens(5)).as_deref(),
+            Some("Voter is not active")
+        );
+    }
+}
@@ -0,0 +1,35 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for claiming pending rewards to another chain
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::chain;
+    use crate::state::prepare_reward_claim;
+    use linera_sdk::linera_base_types::Amount;
+
+    #[test]
+    fn test_claim_to_different_chain() {
+        let claim = prepare_reward_claim(chain(2), chain(9), Amount::from_tokens(25)).unwrap();
+
+        assert_eq!(claim.voter, chain(2));
+        assert_eq!(claim.target_chain, chain(9));
+        assert_eq!(claim.amount, Amount::from_tokens(25));
+        assert!(claim.is_redirected());
+    }
+
+    #[test]
+    fn test_claim_to_own_chain_not_redirected() {
+        let claim = prepare_reward_claim(chain(2), chain(2), Amount::from_tokens(25)).unwrap();
+
+        assert!(!claim.is_redirected());
+    }
+
+    #[test]
+    fn test_rejected_without_pending_rewards() {
+        let err = prepare_reward_claim(chain(2), chain(9), Amount::ZERO).unwrap_err();
+
+        assert!(err.contains("No pending rewards"));
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the configurable commit-hash algorithm used on reveal
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{CommitHashAlgo, ProtocolParameters};
+
+    #[test]
+    fn test_default_is_sha256() {
+        assert_eq!(ProtocolParameters::default().commit_hash_algo, CommitHashAlgo::Sha256);
+        assert_eq!(
+            CommitHashAlgo::Sha256.compute("", ""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_keccak256_is_not_nist_sha3() {
+        assert_eq!(
+            CommitHashAlgo::Keccak256.compute("", ""),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        );
+    }
+
+    #[test]
+    fn test_reveal_with_matching_algo_succeeds() {
+        for algo in [CommitHashAlgo::Sha256, CommitHashAlgo::Keccak256] {
+            let commit = algo.compute("Yes", "salt123");
+            assert!(algo.verify("Yes", "salt123", &commit));
+            assert!(!algo.verify("No", "salt123", &commit));
+        }
+    }
+
+    #[test]
+    fn test_reveal_with_mismatched_algo_fails() {
+        let sha_commit = CommitHashAlgo::Sha256.compute("Yes", "salt123");
+        let keccak_commit = CommitHashAlgo::Keccak256.compute("Yes", "salt123");
+
+        assert!(!CommitHashAlgo::Keccak256.verify("Yes", "salt123", &sha_commit));
+        assert!(!CommitHashAlgo::Sha256.verify("Yes", "salt123", &keccak_commit));
+    }
+
+    #[test]
+    fn test_parse_rejects_unsupported_algo() {
+        assert_eq!(CommitHashAlgo::parse("Keccak256"), Ok(CommitHashAlgo::Keccak256));
+        let err = CommitHashAlgo::parse("Blake3").unwrap_err();
+        assert!(err.contains("Unsupported commit hash algorithm"));
+    }
+}
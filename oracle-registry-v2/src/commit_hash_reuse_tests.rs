@@ -0,0 +1,68 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for rejecting reused commit hashes
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{check_commit_hash_reuse, CommitHashUse};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::Timestamp;
+
+    const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    fn query_id(response: oracle_registry_v2::OperationResponse) -> u64 {
+        assert!(response.success, "{}", response.message);
+        response.data.and_then(|data| data.query_id).expect("query id")
+    }
+
+    #[test]
+    fn test_duplicate_commit_on_same_query_is_rejected() {
+        let mut harness = TestHarness::new();
+        assert!(harness.register(chain(1), 100).success);
+        let query = query_id(harness.create_query(&["Yes", "No"], 1, 120));
+
+        assert!(harness.commit_vote(chain(1), query, HASH).success);
+        let response = harness.commit_vote(chain(1), query, HASH);
+        assert!(!response.success);
+        assert!(response.message.contains("already committed"));
+    }
+
+    #[test]
+    fn test_hash_reuse_on_open_query_is_rejected_until_deadline() {
+        let mut harness = TestHarness::new();
+        assert!(harness.register(chain(1), 1000).success);
+        let first = query_id(harness.create_query(&["Yes", "No"], 1, 120));
+        let second = query_id(harness.create_query(&["Yes", "No"], 1, 1200));
+
+        assert!(harness.commit_vote(chain(1), first, HASH).success);
+        let response = harness.commit_vote(chain(1), second, &HASH.to_uppercase());
+        assert!(!response.success);
+        assert!(response.message.contains("Commit hash already used on query"));
+
+        // Once the first query's deadline has passed its reveal is final
+        harness.advance_secs(121);
+        assert!(harness.commit_vote(chain(1), second, HASH).success);
+    }
+
+    #[test]
+    fn test_other_voters_may_commit_same_hash() {
+        let mut harness = TestHarness::new();
+        assert!(harness.register(chain(1), 100).success);
+        assert!(harness.register(chain(2), 100).success);
+        let query = query_id(harness.create_query(&["Yes", "No"], 1, 120));
+
+        assert!(harness.commit_vote(chain(1), query, HASH).success);
+        assert!(harness.commit_vote(chain(2), query, HASH).success);
+    }
+
+    #[test]
+    fn test_check_commit_hash_reuse() {
+        let previous = CommitHashUse { query_id: 1, deadline: Timestamp::from(10_000_000) };
+
+        assert!(check_commit_hash_reuse(None, 1, Timestamp::from(0)).is_ok());
+        assert!(check_commit_hash_reuse(Some(&previous), 1, Timestamp::from(20_000_000)).is_err());
+        assert!(check_commit_hash_reuse(Some(&previous), 2, Timestamp::from(10_000_000)).is_err());
+        assert!(check_commit_hash_reuse(Some(&previous), 2, Timestamp::from(10_000_001)).is_ok());
+    }
+}
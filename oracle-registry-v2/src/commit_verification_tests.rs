@@ -0,0 +1,48 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for checking a commit before revealing it
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{chain, query};
+    use crate::state::{CommitHashAlgo, Query, VoteCommit};
+    use linera_sdk::linera_base_types::Timestamp;
+
+    /// Query where `chain(2)` committed to "Yes" with salt "salt123"
+    fn committed(algo: CommitHashAlgo) -> Query {
+        let mut q = query(&["Yes", "No"]);
+        q.commits.insert(chain(2), VoteCommit {
+            voter: chain(2),
+            commit_hash: algo.compute("Yes", "salt123"),
+            committed_at: Timestamp::from(500_000),
+            revealed: false,
+        });
+        q
+    }
+
+    #[test]
+    fn test_matching_value_and_salt_verify() {
+        for algo in [CommitHashAlgo::Sha256, CommitHashAlgo::Keccak256] {
+            let q = committed(algo);
+            assert_eq!(q.verify_commit(&chain(2), "Yes", "salt123", algo), Ok(true));
+        }
+    }
+
+    #[test]
+    fn test_wrong_value_or_salt_does_not_verify() {
+        let q = committed(CommitHashAlgo::Sha256);
+
+        assert_eq!(q.verify_commit(&chain(2), "No", "salt123", CommitHashAlgo::Sha256), Ok(false));
+        assert_eq!(q.verify_commit(&chain(2), "Yes", "salt124", CommitHashAlgo::Sha256), Ok(false));
+        assert_eq!(q.verify_commit(&chain(2), "Yes", "salt123", CommitHashAlgo::Keccak256), Ok(false));
+    }
+
+    #[test]
+    fn test_voter_without_commit_is_an_error() {
+        let q = committed(CommitHashAlgo::Sha256);
+
+        let err = q.verify_commit(&chain(3), "Yes", "salt123", CommitHashAlgo::Sha256).unwrap_err();
+        assert!(err.contains("No commit"));
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the confidence-weighted median strategy
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::vote;
+    use crate::state::{DecisionStrategy, ResolutionResult, Vote};
+
+    /// Helper to create a numeric vote with a confidence score
+    fn confident_vote(id: u8, value: &str, confidence: Option<u8>) -> Vote {
+        Vote {
+            confidence,
+            ..vote(id, value)
+        }
+    }
+
+    #[test]
+    fn test_low_confidence_votes_pull_median_less() {
+        let votes = vec![
+            confident_vote(1, "10", Some(90)),
+            confident_vote(2, "50", Some(10)),
+            confident_vote(3, "60", Some(10)),
+        ];
+
+        assert_eq!(ResolutionResult::from_median(&votes), ResolutionResult::NumericMedian(50.0));
+        assert_eq!(
+            ResolutionResult::from_confidence_weighted_median(&votes),
+            ResolutionResult::NumericMedian(10.0)
+        );
+    }
+
+    #[test]
+    fn test_equal_confidence_matches_plain_median() {
+        let votes = vec![
+            confident_vote(1, "4", None),
+            confident_vote(2, "1", None),
+            confident_vote(3, "3", None),
+            confident_vote(4, "2", None),
+        ];
+
+        assert_eq!(ResolutionResult::from_median(&votes), ResolutionResult::NumericMedian(2.5));
+        assert_eq!(
+            ResolutionResult::from_confidence_weighted_median(&votes),
+            ResolutionResult::NumericMedian(2.5)
+        );
+    }
+
+    #[test]
+    fn test_result_is_independent_of_vote_order() {
+        let votes = vec![
+            confident_vote(1, "7.5", Some(40)),
+            confident_vote(2, "-3", Some(70)),
+            confident_vote(3, "12", Some(25)),
+            confident_vote(4, "7.5", Some(5)),
+        ];
+        let mut reversed = votes.clone();
+        reversed.reverse();
+
+        assert_eq!(
+            ResolutionResult::from_confidence_weighted_median(&votes),
+            ResolutionResult::from_confidence_weighted_median(&reversed)
+        );
+    }
+
+    #[test]
+    fn test_zero_confidence_and_non_numeric_votes_ignored() {
+        let votes = vec![
+            confident_vote(1, "abc", Some(100)),
+            confident_vote(2, "100", Some(0)),
+        ];
+        assert_eq!(ResolutionResult::from_confidence_weighted_median(&votes), ResolutionResult::NoConsensus);
+
+        let outcomes = vec!["Yes".to_string()];
+        assert!(DecisionStrategy::ConfidenceWeightedMedian.validate_outcomes(&outcomes).is_err());
+    }
+}
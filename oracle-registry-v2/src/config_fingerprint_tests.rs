@@ -0,0 +1,59 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the configuration fingerprint
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::Operation;
+
+    fn update_min_stake(harness: &mut TestHarness, tokens: u128) {
+        let params = oracle_registry_v2::state::ProtocolParameters {
+            min_stake: Amount::from_tokens(tokens),
+            ..Default::default()
+        };
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::UpdateParameters { params });
+        assert!(response.success, "{}", response.message);
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable_without_config_changes() {
+        let mut harness = TestHarness::new();
+        let fingerprint = harness.state().config_fingerprint();
+        assert_eq!(fingerprint.len(), 64);
+        assert_eq!(harness.state().config_fingerprint(), fingerprint);
+
+        // Voter and query activity leave the configuration alone
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        assert!(harness.create_query(&["Yes", "No"], 3, 120).success);
+        assert_eq!(harness.state().config_fingerprint(), fingerprint);
+    }
+
+    #[test]
+    fn test_fingerprint_changes_after_update_parameters() {
+        let mut harness = TestHarness::new();
+        let before = harness.state().config_fingerprint();
+
+        update_min_stake(&mut harness, 250);
+        let after = harness.state().config_fingerprint();
+        assert_ne!(after, before);
+
+        // The same parameters hash the same way
+        update_min_stake(&mut harness, 250);
+        assert_eq!(harness.state().config_fingerprint(), after);
+    }
+
+    #[test]
+    fn test_fingerprint_covers_admin() {
+        let mut harness = TestHarness::new();
+        let before = harness.state().config_fingerprint();
+
+        harness.contract.state.admin.set(Some(chain(42)));
+        assert_ne!(harness.state().config_fingerprint(), before);
+    }
+}
@@ -5,6 +5,129 @@
 
 mod state;
 
+#[cfg(test)]
+mod active_query_cap_tests;
+
+#[cfg(test)]
+mod activity_feed_tests;
+
+#[cfg(test)]
+mod balance_adjustment_tests;
+
+#[cfg(test)]
+mod batch_claim_tests;
+
+#[cfg(test)]
+mod bootstrap_voter_tests;
+
+#[cfg(test)]
+mod commit_hash_reuse_tests;
+
+#[cfg(test)]
+mod config_fingerprint_tests;
+
+#[cfg(test)]
+mod creation_deposit_tests;
+
+#[cfg(test)]
+mod creator_rebate_tests;
+
+#[cfg(test)]
+mod emergency_unstake_tests;
+
+#[cfg(test)]
+mod encrypted_metadata_tests;
+
+#[cfg(test)]
+mod expire_queries_tests;
+
+#[cfg(test)]
+mod external_resolution_tests;
+
+#[cfg(test)]
+mod forfeit_and_exit_tests;
+
+#[cfg(test)]
+mod late_reveal_tests;
+
+#[cfg(test)]
+mod lock_preview_tests;
+
+#[cfg(test)]
+mod max_effective_stake_tests;
+
+#[cfg(test)]
+mod multi_winner_tests;
+
+#[cfg(test)]
+mod no_consensus_tests;
+
+#[cfg(test)]
+mod no_tallied_votes_tests;
+
+#[cfg(test)]
+mod numeric_outcomes_tests;
+
+#[cfg(test)]
+mod outcome_aliases_tests;
+
+#[cfg(test)]
+mod parameter_history_tests;
+
+#[cfg(test)]
+mod parameter_timelock_tests;
+
+#[cfg(test)]
+mod power_distribution_tests;
+
+#[cfg(test)]
+mod request_query_result_tests;
+
+#[cfg(test)]
+mod resend_callback_tests;
+
+#[cfg(test)]
+mod resolution_distribution_tests;
+
+#[cfg(test)]
+mod reward_expiry_tests;
+
+#[cfg(test)]
+mod selection_stats_tests;
+
+#[cfg(test)]
+mod slash_circuit_breaker_tests;
+
+#[cfg(test)]
+mod stake_history_tests;
+
+#[cfg(test)]
+mod strategy_min_votes_tests;
+
+#[cfg(test)]
+mod test_support;
+
+#[cfg(test)]
+mod tie_break_tests;
+
+#[cfg(test)]
+mod token_withdrawal_tests;
+
+#[cfg(test)]
+mod treasury_sweep_tests;
+
+#[cfg(test)]
+mod vote_count_drift_tests;
+
+#[cfg(test)]
+mod voter_diversity_tests;
+
+#[cfg(test)]
+mod voter_registration_tests;
+
+#[cfg(test)]
+mod voter_stake_floor_tests;
+
 use linera_sdk::{
     linera_base_types::{Amount, WithContractAbi, StreamName, StreamUpdate},
     views::{View, RootView},
@@ -13,6 +136,59 @@ use linera_sdk::{
 use state::{OracleRegistryV2, ProtocolParameters};
 use oracle_registry_v2::{OracleEvent, ORACLE_STREAM_NAME};
 
+// Operations carry the lib's copies of the state enums; these map them onto
+// the contract's own `state` module, which is compiled separately.
+
+impl From<oracle_registry_v2::state::DecisionStrategy> for state::DecisionStrategy {
+    fn from(strategy: oracle_registry_v2::state::DecisionStrategy) -> Self {
+        match strategy {
+            oracle_registry_v2::state::DecisionStrategy::Majority => state::DecisionStrategy::Majority,
+            oracle_registry_v2::state::DecisionStrategy::Median => state::DecisionStrategy::Median,
+            oracle_registry_v2::state::DecisionStrategy::WeightedByStake => state::DecisionStrategy::WeightedByStake,
+            oracle_registry_v2::state::DecisionStrategy::WeightedByReputation => state::DecisionStrategy::WeightedByReputation,
+            oracle_registry_v2::state::DecisionStrategy::ConfidenceWeightedMedian => state::DecisionStrategy::ConfidenceWeightedMedian,
+        }
+    }
+}
+
+impl From<oracle_registry_v2::state::RewardBasis> for state::RewardBasis {
+    fn from(basis: oracle_registry_v2::state::RewardBasis) -> Self {
+        match basis {
+            oracle_registry_v2::state::RewardBasis::Equal => state::RewardBasis::Equal,
+            oracle_registry_v2::state::RewardBasis::Stake => state::RewardBasis::Stake,
+            oracle_registry_v2::state::RewardBasis::Reputation => state::RewardBasis::Reputation,
+            oracle_registry_v2::state::RewardBasis::Power => state::RewardBasis::Power,
+            oracle_registry_v2::state::RewardBasis::Confidence => state::RewardBasis::Confidence,
+        }
+    }
+}
+
+impl From<oracle_registry_v2::state::TieBreak> for state::TieBreak {
+    fn from(tie_break: oracle_registry_v2::state::TieBreak) -> Self {
+        match tie_break {
+            oracle_registry_v2::state::TieBreak::OutcomeOrder => state::TieBreak::OutcomeOrder,
+            oracle_registry_v2::state::TieBreak::HigherStake => state::TieBreak::HigherStake,
+            oracle_registry_v2::state::TieBreak::HigherReputation => state::TieBreak::HigherReputation,
+            oracle_registry_v2::state::TieBreak::NoConsensus => state::TieBreak::NoConsensus,
+        }
+    }
+}
+
+impl From<oracle_registry_v2::state::CommitHashAlgo> for state::CommitHashAlgo {
+    fn from(algo: oracle_registry_v2::state::CommitHashAlgo) -> Self {
+        match algo {
+            oracle_registry_v2::state::CommitHashAlgo::Sha256 => state::CommitHashAlgo::Sha256,
+            oracle_registry_v2::state::CommitHashAlgo::Keccak256 => state::CommitHashAlgo::Keccak256,
+        }
+    }
+}
+
+impl From<oracle_registry_v2::state::StakeLockRule> for state::StakeLockRule {
+    fn from(rule: oracle_registry_v2::state::StakeLockRule) -> Self {
+        state::StakeLockRule { lock_bps: rule.lock_bps, min_lock: rule.min_lock }
+    }
+}
+
 pub struct OracleRegistryV2Contract {
     state: OracleRegistryV2,
     runtime: ContractRuntime<Self>,
@@ -26,7 +202,7 @@ impl WithContractAbi for OracleRegistryV2Contract {
 
 impl Contract for OracleRegistryV2Contract {
     type Message = oracle_registry_v2::Message;
-    type InstantiationArgument = ();
+    type InstantiationArgument = Option<oracle_registry_v2::InstantiationArgument>;
     type Parameters = ();
     type EventValue = OracleEvent;  // ← Enable event streaming!
 
@@ -38,17 +214,23 @@ impl Contract for OracleRegistryV2Contract {
         OracleRegistryV2Contract { state, runtime }
     }
 
-    async fn instantiate(&mut self, _argument: ()) {
+    async fn instantiate(&mut self, argument: Option<oracle_registry_v2::InstantiationArgument>) {
         // The chain that instantiates the contract becomes the admin
         let admin_chain = self.runtime.chain_id();
         
         // Use default parameters
         let params = ProtocolParameters::default();
-        self.state.initialize(params, admin_chain).await;
+        self.state.initialize(params.clone(), admin_chain).await;
+        if let Err(e) = self.state.record_parameter_change(admin_chain, params, self.runtime.system_time()) {
+            eprintln!("Warning: {}", e);
+        }
         
         // Initialize test voters for development/testing
         OracleRegistryV2Contract::initialize_test_voters_internal(&mut self.state).await;
         
+        // Optionally make the admin chain the first voter (test deployments)
+        self.bootstrap_admin_voter(argument.unwrap_or_default()).await;
+        
         // Subscribe to own events for cross-chain propagation
         // This allows the contract to process its own events if needed
         let chain_id = self.runtime.chain_id();
@@ -103,18 +285,19 @@ impl Contract for OracleRegistryV2Contract {
         // Check if paused (except for admin operations)
         if self.state.is_paused().await {
             match operation {
-                Operation::UnpauseProtocol => {},
+                // Reads stay available so integrations can still settle on results
+                Operation::UnpauseProtocol | Operation::GetQueryResult { .. } => {},
                 _ => return OperationResponse::error("Protocol is paused"),
             }
         }
         
         match operation {
-            Operation::RegisterVoter { stake, name, metadata_url } => {
-                self.register_voter_chainid(stake, name, metadata_url).await
+            Operation::RegisterVoter { stake, name, metadata_url, referral, encrypted_metadata } => {
+                self.register_voter_chainid(stake, name, metadata_url, referral, encrypted_metadata).await
             }
             
-            Operation::RegisterVoterFor { voter_address, stake, name, metadata_url } => {
-                self.register_voter_for(voter_address, stake, name, metadata_url).await
+            Operation::RegisterVoterFor { voter_address, stake, name, metadata_url, referral } => {
+                self.register_voter_for(voter_address, stake, name, metadata_url, referral).await
             }
             
             Operation::UpdateStake { additional_stake } => {
@@ -125,19 +308,56 @@ impl Contract for OracleRegistryV2Contract {
                 self.withdraw_stake(amount).await
             }
             
+            Operation::EmergencyWithdraw { amount } => {
+                self.emergency_withdraw(amount).await
+            }
+            
+            Operation::ForfeitAndExit => {
+                self.forfeit_and_exit().await
+            }
+            
             Operation::DeregisterVoter => {
                 self.deregister_voter().await
             }
             
-            Operation::CreateQuery { description, outcomes, strategy, min_votes, reward_amount, deadline, duration_secs } => {
-                // Convert from lib DecisionStrategy to state DecisionStrategy
-                let state_strategy = match strategy {
-                    oracle_registry_v2::state::DecisionStrategy::Majority => state::DecisionStrategy::Majority,
-                    oracle_registry_v2::state::DecisionStrategy::Median => state::DecisionStrategy::Median,
-                    oracle_registry_v2::state::DecisionStrategy::WeightedByStake => state::DecisionStrategy::WeightedByStake,
-                    oracle_registry_v2::state::DecisionStrategy::WeightedByReputation => state::DecisionStrategy::WeightedByReputation,
-                };
-                self.create_query(description, outcomes, state_strategy, min_votes, reward_amount, deadline, duration_secs).await
+            Operation::SetEncryptedMetadata { encrypted_metadata } => {
+                self.set_encrypted_metadata(encrypted_metadata).await
+            }
+            
+            Operation::CreateQuery { description, outcomes, strategy, min_votes, reward_amount, deadline, duration_secs, min_distinct_voted_outcomes, min_votes_for_winner, min_distinct_voters, max_group_power_bps, min_voter_stake, allow_abstain, multi_winner, category, tags, reward_basis, outcome_aliases, tie_break } => {
+                self.create_query(description, outcomes, strategy.into(), min_votes, reward_amount, deadline, duration_secs, min_distinct_voted_outcomes, min_votes_for_winner, min_distinct_voters, max_group_power_bps, min_voter_stake, allow_abstain, multi_winner, category, tags, reward_basis.map(Into::into), outcome_aliases, tie_break.into()).await
+            }
+            
+            Operation::UpdateQueryStrategy { query_id, strategy } => {
+                self.update_query_strategy(query_id, strategy.into()).await
+            }
+            
+            Operation::GetQueryResult { query_id } => {
+                self.get_query_result(query_id).await
+            }
+            
+            Operation::CancelQuery { query_id } => {
+                self.cancel_query(query_id).await
+            }
+            
+            Operation::AdvancePhase { query_id } => {
+                self.advance_phase(query_id).await
+            }
+            
+            Operation::UnlockQueryStakes { query_id, max } => {
+                self.unlock_query_stakes_operation(query_id, max).await
+            }
+            
+            Operation::SetQueryMetadata { query_id, category, tags } => {
+                self.set_query_metadata(query_id, category, tags).await
+            }
+            
+            Operation::SetExternalResolution { query_id, allowed } => {
+                self.set_external_resolution(query_id, allowed).await
+            }
+            
+            Operation::ResolveWithExternalData { query_id, outcome, source } => {
+                self.resolve_with_external_data(query_id, outcome, source).await
             }
             
             Operation::SubmitVote { query_id, value, confidence } => {
@@ -160,21 +380,36 @@ impl Contract for OracleRegistryV2Contract {
                 self.claim_rewards().await
             }
             
+            Operation::ClaimRewardsTo { target_chain } => {
+                self.claim_rewards_to(target_chain).await
+            }
+            
+            Operation::SetClaimOperator { operator } => {
+                self.set_claim_operator(operator).await
+            }
+            
+            Operation::BatchClaimFor { voters } => {
+                self.batch_claim_for(voters).await
+            }
+            
             Operation::UpdateParameters { params } => {
-                // Convert params to state::ProtocolParameters
-                // They are the same struct, just different namespace
-                let state_params = state::ProtocolParameters {
-                    min_stake: params.min_stake,
-                    min_votes_default: params.min_votes_default,
-                    default_query_duration: params.default_query_duration,
-                    reward_percentage: params.reward_percentage,
-                    slash_percentage: params.slash_percentage,
-                    protocol_fee: params.protocol_fee,
-                    token_app_id: None, // Token app ID is set separately
-                };
+                let state_params = self.convert_parameters(params).await;
                 self.update_parameters(state_params).await
             }
             
+            Operation::ProposeParameters { params } => {
+                let state_params = self.convert_parameters(params).await;
+                self.propose_parameters(state_params).await
+            }
+            
+            Operation::ApplyPendingParameters => {
+                self.apply_pending_parameters().await
+            }
+            
+            Operation::SetTokenApplication { token_app_id } => {
+                self.set_token_application(token_app_id).await
+            }
+            
             Operation::PauseProtocol => {
                 self.pause_protocol().await
             }
@@ -191,10 +426,46 @@ impl Contract for OracleRegistryV2Contract {
                 self.expire_query_operation(query_id).await
             }
             
+            Operation::ExpireQueries { query_ids } => {
+                self.expire_queries_operation(query_ids).await
+            }
+            
             Operation::AutoResolveQueries => {
                 self.auto_resolve_queries_operation().await
             }
             
+            Operation::RefreshStatistics => {
+                self.refresh_statistics_operation().await
+            }
+            
+            Operation::ReclaimUnclaimedRewards { max } => {
+                self.reclaim_unclaimed_rewards(max).await
+            }
+            
+            Operation::SweepTreasury => {
+                self.sweep_treasury_operation().await
+            }
+            
+            Operation::FundRewardPool { amount } => {
+                self.fund_reward_pool(amount).await
+            }
+            
+            Operation::AdjustTreasury { amount, increase } => {
+                self.adjust_treasury(amount, increase).await
+            }
+            
+            Operation::ResendCallback { query_id } => {
+                self.resend_callback(query_id).await
+            }
+            
+            Operation::SettleQueries { max } => {
+                self.settle_queries_operation(max).await
+            }
+            
+            Operation::ArchiveQueries { before } => {
+                self.archive_queries_operation(before).await
+            }
+            
             Operation::SendRegisterVoterMessage { target_chain, stake, name, metadata_url } => {
                 self.send_register_voter_message(target_chain, stake, name, metadata_url).await
             }
@@ -226,6 +497,10 @@ impl Contract for OracleRegistryV2Contract {
                 self.send_update_stake_message(target_chain, additional_stake).await
             }
             
+            Operation::SendRequestQueryResultMessage { target_chain, query_id } => {
+                self.send_request_query_result_message(target_chain, query_id).await
+            }
+            
             Operation::CreateQueryWithCallback {
                 description,
                 outcomes,
@@ -237,17 +512,10 @@ impl Contract for OracleRegistryV2Contract {
                 callback_app,
                 callback_data,
             } => {
-                // Convert from lib DecisionStrategy to state DecisionStrategy
-                let state_strategy = match strategy {
-                    oracle_registry_v2::state::DecisionStrategy::Majority => state::DecisionStrategy::Majority,
-                    oracle_registry_v2::state::DecisionStrategy::Median => state::DecisionStrategy::Median,
-                    oracle_registry_v2::state::DecisionStrategy::WeightedByStake => state::DecisionStrategy::WeightedByStake,
-                    oracle_registry_v2::state::DecisionStrategy::WeightedByReputation => state::DecisionStrategy::WeightedByReputation,
-                };
                 self.create_query_with_callback(
                     description,
                     outcomes,
-                    state_strategy,
+                    strategy.into(),
                     min_votes,
                     reward_amount,
                     deadline,
@@ -262,6 +530,10 @@ impl Contract for OracleRegistryV2Contract {
     async fn execute_message(&mut self, message: Self::Message) {
         use oracle_registry_v2::Message;
         
+        // Capture kind and sender up front for the failed message log
+        let kind = message.kind();
+        let sender = message.sender_chain().or_else(|| self.runtime.message_origin_chain_id());
+        
         // Handle cross-chain messages for account-based voting
         // Authentication is automatic - Linera verifies the message sender
         let response = match message {
@@ -347,11 +619,22 @@ impl Contract for OracleRegistryV2Contract {
                 // Request to withdraw tokens back to user
                 self.handle_withdraw_tokens(amount, target_chain).await
             }
+            
+            // Re-send a query's result to a chain that asks for it
+            Message::RequestQueryResult { query_id, reply_to } => {
+                self.handle_request_query_result(query_id, reply_to).await
+            }
+            
+            Message::QueryResultUnavailable { .. } => {
+                // This message is sent FROM Registry TO the requester, not received by Registry
+                oracle_registry_v2::OperationResponse::error("Registry does not handle QueryResultUnavailable")
+            }
         };
         
-        // Response is handled internally by the contract
-        // No need to return or log explicitly
-        let _ = response;
+        // Messages have no caller to return an error to, so log failures
+        if !response.success {
+            self.record_failed_message(kind, sender, response.message);
+        }
     }
 }
 
@@ -525,6 +808,26 @@ impl OracleRegistryV2Contract {
             return Err("Minimum votes default too high (max 1000)".to_string());
         }
         
+        // Validate per-strategy stake locks are a share of the available stake
+        for (strategy, rule) in &params.strategy_stake_locks {
+            if rule.lock_bps == 0 || rule.lock_bps > 10000 {
+                return Err(format!(
+                    "Stake lock for {} must be between 1 and 10000 basis points",
+                    strategy.as_str()
+                ));
+            }
+        }
+        
+        // Validate per-strategy minimum votes like min_votes_default
+        for (strategy, min_votes) in &params.strategy_min_votes {
+            if *min_votes == 0 || *min_votes > 1000 {
+                return Err(format!(
+                    "Minimum votes for {} must be between 1 and 1000",
+                    strategy.as_str()
+                ));
+            }
+        }
+        
         // Validate default_query_duration is reasonable
         if params.default_query_duration == 0 {
             return Err("Default query duration must be greater than zero".to_string());
@@ -555,6 +858,84 @@ impl OracleRegistryV2Contract {
             return Err("Protocol fee too high (max 1000 basis points = 10%)".to_string());
         }
         
+        // Validate resolution grace window is reasonable (max 1 hour)
+        if params.resolution_grace_secs > 3600 {
+            return Err("Resolution grace window too long (max 3600 seconds)".to_string());
+        }
+        
+        // Validate minimum vote interval is reasonable (max 1 day)
+        if params.min_vote_interval_secs > 86400 {
+            return Err("Minimum vote interval too long (max 86400 seconds)".to_string());
+        }
+        
+        // Validate emergency unstake penalty is reasonable (0-50%)
+        if params.emergency_unstake_penalty_bps > 5000 {
+            return Err("Emergency unstake penalty too high (max 5000 basis points = 50%)".to_string());
+        }
+        
+        // Validate parameter timelock is reasonable (max 30 days)
+        if params.timelock_secs > state::MAX_PARAMETER_TIMELOCK_SECS {
+            return Err(format!(
+                "Parameter timelock too long (max {} seconds)",
+                state::MAX_PARAMETER_TIMELOCK_SECS
+            ));
+        }
+        
+        // Validate per-resolution slashing limit is a share of total stake
+        if params.max_slash_per_resolution_bps > 10000 {
+            return Err("Max slash per resolution too high (max 10000 basis points = 100%)".to_string());
+        }
+        
+        // Validate the active query cap leaves room for at least one query
+        if params.max_active_queries == 0 {
+            return Err("Max active queries must be at least 1".to_string());
+        }
+        
+        // Validate multi-winner threshold is a non-zero share of the tally
+        if params.multi_winner_threshold_bps == 0 || params.multi_winner_threshold_bps > 10000 {
+            return Err("Multi-winner threshold must be between 1 and 10000 basis points".to_string());
+        }
+        
+        // Validate the address-prefix group length fits in a chain ID (64 hex characters)
+        if params.voter_group_prefix_len == 0 || params.voter_group_prefix_len > 64 {
+            return Err("Voter group prefix length must be between 1 and 64 hex characters".to_string());
+        }
+        
+        // Validate the effective stake cap still counts a minimum stake in full
+        if let Some(max_effective_stake) = params.max_effective_stake {
+            if max_effective_stake < params.min_stake {
+                return Err("Max effective stake cannot be below the minimum stake".to_string());
+            }
+        }
+        
+        // Validate creator rebate is a share of the protocol fee
+        if params.creator_rebate_bps > 10000 {
+            return Err("Creator rebate too high (max 10000 basis points = 100%)".to_string());
+        }
+        
+        // Validate late reveal weight does not exceed an on-time reveal's
+        if params.late_reveal_weight_bps > 10000 {
+            return Err("Late reveal weight too high (max 10000 basis points = 100%)".to_string());
+        }
+        
+        // Validate selection cooldown is reasonable
+        if params.selection_cooldown_queries > 1000 {
+            return Err("Selection cooldown too long (max 1000 queries)".to_string());
+        }
+        
+        // Validate high-stake reputation bonus is bounded
+        if params.high_stake_reputation_bonus > state::MAX_HIGH_STAKE_REPUTATION_BONUS {
+            return Err(format!(
+                "High-stake reputation bonus too high (max {})",
+                state::MAX_HIGH_STAKE_REPUTATION_BONUS
+            ));
+        }
+        
+        // Validate maximum query reward, if set, allows some reward
+        if params.max_query_reward == Some(Amount::ZERO) {
+            return Err("Maximum query reward must be greater than zero".to_string());
+        }
+        
         // Validate that reward + slash + fee doesn't exceed 100%
         let total_percentage = params.reward_percentage + params.slash_percentage + params.protocol_fee;
         if total_percentage > 10000 {
@@ -576,22 +957,16 @@ impl OracleRegistryV2Contract {
         stake: Amount,
         name: Option<String>,
         metadata_url: Option<String>,
+        referral: Option<String>,
     ) -> oracle_registry_v2::OperationResponse {
         use oracle_registry_v2::{OperationResponse, ResponseData};
         use state::VoterInfo;
         use linera_sdk::linera_base_types::ChainId;
         
-        // Strip "0x" prefix if present before parsing
-        let address_hex = if voter_address.starts_with("0x") || voter_address.starts_with("0X") {
-            &voter_address[2..]
-        } else {
-            &voter_address
-        };
-        
-        // Parse voter chain ID from hex string
-        let voter_chain = match address_hex.parse::<ChainId>() {
+        // Parse voter chain ID from hex string ("0x" prefix optional)
+        let voter_chain: ChainId = match state::parse_chain_id(&voter_address) {
             Ok(chain) => chain,
-            Err(_) => return OperationResponse::error("Invalid chain ID format: failed to parse hex string"),
+            Err(e) => return OperationResponse::error(format!("Invalid chain ID: {}", e)),
         };
         
         // Validate registration parameters
@@ -599,6 +974,12 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(e);
         }
         
+        // Validate and normalize referral code
+        let referral = match referral.as_deref().map(state::normalize_referral_code).transpose() {
+            Ok(code) => code,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
         // Check if already registered
         if let Err(e) = self.validate_voter_not_registered(&voter_chain).await {
             return OperationResponse::error(e);
@@ -615,17 +996,28 @@ impl OracleRegistryV2Contract {
             chain_id: voter_chain,
             stake,
             locked_stake: Amount::ZERO,
-            reputation: 50,
+            reputation: params.initial_reputation(stake), // Default reputation plus any high-stake bonus
             total_votes: 0,
             correct_votes: 0,
             registered_at: self.runtime.system_time(),
+            last_active_at: self.runtime.system_time(),
+            last_vote_at: None,
             is_active: true,
             name: name.clone(),
             metadata_url: metadata_url.clone(),
+            referral,
+            calibration_score: None,
+            calibrated_votes: 0,
+            encrypted_metadata: None,
         };
         
+        // Record the opening stake
+        if let Err(e) = self.state.record_stake_snapshot(&voter_chain, &voter_info, self.runtime.system_time()).await {
+            eprintln!("Warning: {}", e);
+        }
+        
         // Store voter
-        self.state.voters.insert(&voter_chain, voter_info).expect("Failed to insert voter");
+        self.state.save_voter(&voter_chain, voter_info).await.expect("Failed to insert voter");
         
         // Update totals - use saturating_add to avoid overflow
         let current_stake = *self.state.total_stake.get();
@@ -633,7 +1025,7 @@ impl OracleRegistryV2Contract {
         self.state.total_stake.set(new_total);
         
         let current_count = *self.state.voter_count.get();
-        self.state.voter_count.set(current_count + 1);
+        self.state.voter_count.set(state::increment_counter(current_count, "voter_count"));
         
         OperationResponse::success_with_data(
             "Voter registered successfully (admin operation)",
@@ -642,6 +1034,11 @@ impl OracleRegistryV2Contract {
                 query_id: None,
                 vote_count: None,
                 rewards_claimed: None,
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
             }
         )
     }
@@ -653,6 +1050,8 @@ impl OracleRegistryV2Contract {
         stake: Amount,
         name: Option<String>,
         metadata_url: Option<String>,
+        referral: Option<String>,
+        encrypted_metadata: Option<Vec<u8>>,
     ) -> oracle_registry_v2::OperationResponse {
         use oracle_registry_v2::{OperationResponse, ResponseData};
         use state::VoterInfo;
@@ -665,6 +1064,17 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(e);
         }
         
+        // Validate encrypted metadata size
+        if let Some(Err(e)) = encrypted_metadata.as_deref().map(state::validate_encrypted_metadata) {
+            return OperationResponse::error(e);
+        }
+        
+        // Validate and normalize referral code
+        let referral = match referral.as_deref().map(state::normalize_referral_code).transpose() {
+            Ok(code) => code,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
         // Check if already registered
         if let Ok(Some(_)) = self.state.voters.get(&voter_chain).await {
             return OperationResponse::error("Chain already registered as voter");
@@ -681,20 +1091,31 @@ impl OracleRegistryV2Contract {
             chain_id: voter_chain,  // ← Use chain ID as identifier!
             stake,
             locked_stake: Amount::ZERO,
-            reputation: 50, // Default reputation for new voters
+            reputation: params.initial_reputation(stake), // Default reputation plus any high-stake bonus
             total_votes: 0,
             correct_votes: 0,
             registered_at: self.runtime.system_time(),
+            last_active_at: self.runtime.system_time(),
+            last_vote_at: None,
             is_active: true,
             name,
             metadata_url,
+            referral,
+            calibration_score: None,
+            calibrated_votes: 0,
+            encrypted_metadata,
         };
         
         // Clone name for event before moving into voter_info
         let name_for_event = voter_info.name.clone();
         
+        // Record the opening stake
+        if let Err(e) = self.state.record_stake_snapshot(&voter_chain, &voter_info, self.runtime.system_time()).await {
+            eprintln!("Warning: {}", e);
+        }
+        
         // Store voter by chain ID
-        self.state.voters.insert(&voter_chain, voter_info).expect("Failed to insert voter");
+        self.state.save_voter(&voter_chain, voter_info).await.expect("Failed to insert voter");
         
         // Update totals - use saturating_add to avoid overflow
         let current_stake = *self.state.total_stake.get();
@@ -702,7 +1123,7 @@ impl OracleRegistryV2Contract {
         self.state.total_stake.set(new_total);
         
         let current_count = *self.state.voter_count.get();
-        self.state.voter_count.set(current_count + 1);
+        self.state.voter_count.set(state::increment_counter(current_count, "voter_count"));
         
         // Emit VoterRegistered event for cross-chain subscribers
         self.emit_oracle_event(OracleEvent::VoterRegistered {
@@ -718,6 +1139,11 @@ impl OracleRegistryV2Contract {
                 query_id: None,
                 vote_count: None,
                 rewards_claimed: None,
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
             },
         )
     }
@@ -734,9 +1160,9 @@ impl OracleRegistryV2Contract {
         use state::VoterInfo;
         
         // Parse voter chain ID from string
-        let voter_chain = match voter_address.parse::<linera_sdk::linera_base_types::ChainId>() {
+        let voter_chain = match state::parse_chain_id(&voter_address) {
             Ok(chain) => chain,
-            Err(_) => return OperationResponse::error("Invalid chain ID format"),
+            Err(e) => return OperationResponse::error(format!("Invalid chain ID: {}", e)),
         };
         
         // Validate registration parameters
@@ -760,17 +1186,28 @@ impl OracleRegistryV2Contract {
             chain_id: voter_chain,
             stake,
             locked_stake: Amount::ZERO,
-            reputation: 50, // Default reputation for new voters
+            reputation: params.initial_reputation(stake), // Default reputation plus any high-stake bonus
             total_votes: 0,
             correct_votes: 0,
             registered_at: self.runtime.system_time(),
+            last_active_at: self.runtime.system_time(),
+            last_vote_at: None,
             is_active: true,
             name,
             metadata_url,
+            referral: None,
+            calibration_score: None,
+            calibrated_votes: 0,
+            encrypted_metadata: None,
         };
         
+        // Record the opening stake
+        if let Err(e) = self.state.record_stake_snapshot(&voter_chain, &voter_info, self.runtime.system_time()).await {
+            eprintln!("Warning: {}", e);
+        }
+        
         // Store voter
-        self.state.voters.insert(&voter_chain, voter_info).expect("Failed to insert voter");
+        self.state.save_voter(&voter_chain, voter_info).await.expect("Failed to insert voter");
         
         // Update totals - use saturating_add to avoid overflow
         let current_stake = *self.state.total_stake.get();
@@ -778,7 +1215,7 @@ impl OracleRegistryV2Contract {
         self.state.total_stake.set(new_total);
         
         let current_count = *self.state.voter_count.get();
-        self.state.voter_count.set(current_count + 1);
+        self.state.voter_count.set(state::increment_counter(current_count, "voter_count"));
         
         OperationResponse::success_with_data(
             "Voter registered successfully",
@@ -787,6 +1224,11 @@ impl OracleRegistryV2Contract {
                 query_id: None,
                 vote_count: None,
                 rewards_claimed: None,
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
             },
         )
     }
@@ -828,20 +1270,31 @@ impl OracleRegistryV2Contract {
             chain_id: voter_chain,
             stake,
             locked_stake: Amount::ZERO,
-            reputation: 50, // Default reputation for new voters (neutral starting point)
+            reputation: params.initial_reputation(stake), // Default reputation plus any high-stake bonus
             total_votes: 0,
             correct_votes: 0,
             registered_at: self.runtime.system_time(),
+            last_active_at: self.runtime.system_time(),
+            last_vote_at: None,
             is_active: true,
             name,
             metadata_url,
+            referral: None,
+            calibration_score: None,
+            calibrated_votes: 0,
+            encrypted_metadata: None,
         };
         
-        // Calculate initial reputation (should be 50 for new voters)
-        let initial_reputation = self.state.calculate_reputation(&voter_info);
+        // Initial reputation (50, plus the high-stake bonus if configured)
+        let initial_reputation = voter_info.reputation;
+        
+        // Record the opening stake
+        if let Err(e) = self.state.record_stake_snapshot(&voter_chain, &voter_info, self.runtime.system_time()).await {
+            eprintln!("Warning: {}", e);
+        }
         
         // Store voter
-        self.state.voters.insert(&voter_chain, voter_info).expect("Failed to insert voter");
+        self.state.save_voter(&voter_chain, voter_info).await.expect("Failed to insert voter");
         
         // Update totals - use saturating_add to avoid overflow
         let current_stake = *self.state.total_stake.get();
@@ -849,7 +1302,7 @@ impl OracleRegistryV2Contract {
         self.state.total_stake.set(new_total);
         
         let current_count = *self.state.voter_count.get();
-        self.state.voter_count.set(current_count + 1);
+        self.state.voter_count.set(state::increment_counter(current_count, "voter_count"));
         
         OperationResponse::success_with_data(
             "Voter registered successfully",
@@ -858,6 +1311,11 @@ impl OracleRegistryV2Contract {
                 query_id: None,
                 vote_count: None,
                 rewards_claimed: None,
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
             }
         )
     }
@@ -879,13 +1337,36 @@ impl OracleRegistryV2Contract {
         // Use sender_chain as voter identifier (from cross-chain message)
         let voter_chain = sender_chain;
         
-        // Validate registration parameters
-        if let Err(e) = self.validate_registration_params(stake, &name, &metadata_url) {
-            return OperationResponse::error(e);
+        // Duplicate or retried delivery: registration is idempotent
+        // Messages don't return to the sender, so this is a no-op rather than an error.
+        // RegisterVoter carries no tokens (stake tokens arrive via ReceiveTokensForStake),
+        // so the duplicate's stake is simply not applied and nothing is lost.
+        let existing = self.state.get_voter(&voter_chain).await;
+        if let state::RegisterMessageOutcome::Duplicate { unapplied_stake } =
+            state::classify_register_message(existing.as_ref(), stake)
+        {
+            eprintln!(
+                "⚠️ Duplicate RegisterVoter message from {} ignored (stake {} not applied)",
+                voter_chain, unapplied_stake
+            );
+            return OperationResponse::success_with_data(
+                format!("Voter already registered; duplicate message ignored (stake {} not applied)", unapplied_stake),
+                ResponseData {
+                    voter_address: Some(voter_chain.to_string()),
+                    query_id: None,
+                    vote_count: None,
+                    rewards_claimed: None,
+                    query_result: None,
+                    batch_claims: None,
+                    expired_queries: None,
+                    reward_distribution: None,
+                    slash_distribution: None,
+                }
+            );
         }
         
-        // Check if already registered
-        if let Err(e) = self.validate_voter_not_registered(&voter_chain).await {
+        // Validate registration parameters
+        if let Err(e) = self.validate_registration_params(stake, &name, &metadata_url) {
             return OperationResponse::error(e);
         }
         
@@ -900,17 +1381,28 @@ impl OracleRegistryV2Contract {
             chain_id: voter_chain,
             stake,
             locked_stake: Amount::ZERO,
-            reputation: 50,
+            reputation: params.initial_reputation(stake), // Default reputation plus any high-stake bonus
             total_votes: 0,
             correct_votes: 0,
             registered_at: self.runtime.system_time(),
+            last_active_at: self.runtime.system_time(),
+            last_vote_at: None,
             is_active: true,
             name: name.clone(),
             metadata_url,
+            referral: None,
+            calibration_score: None,
+            calibrated_votes: 0,
+            encrypted_metadata: None,
         };
         
+        // Record the opening stake
+        if let Err(e) = self.state.record_stake_snapshot(&voter_chain, &voter_info, self.runtime.system_time()).await {
+            eprintln!("Warning: {}", e);
+        }
+        
         // Store voter
-        self.state.voters.insert(&voter_chain, voter_info).expect("Failed to insert voter");
+        self.state.save_voter(&voter_chain, voter_info).await.expect("Failed to insert voter");
         
         // Update totals - use saturating_add to avoid overflow
         let current_stake = *self.state.total_stake.get();
@@ -918,7 +1410,7 @@ impl OracleRegistryV2Contract {
         self.state.total_stake.set(new_total);
         
         let current_count = *self.state.voter_count.get();
-        self.state.voter_count.set(current_count + 1);
+        self.state.voter_count.set(state::increment_counter(current_count, "voter_count"));
         
         eprintln!("✅ Voter {} registered successfully on application chain", voter_chain);
         eprintln!("   Total stake now: {}", new_total);
@@ -930,6 +1422,11 @@ impl OracleRegistryV2Contract {
                 query_id: None,
                 vote_count: None,
                 rewards_claimed: None,
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
             }
         )
     }
@@ -956,7 +1453,11 @@ impl OracleRegistryV2Contract {
         
         // Update stake - use saturating_add to avoid overflow
         voter_info.stake = voter_info.stake.saturating_add(additional_stake);
-        self.state.voters.insert(&voter_chain, voter_info).expect("Failed to update voter");
+        voter_info.last_active_at = self.runtime.system_time();
+        if let Err(e) = self.state.record_stake_snapshot(&voter_chain, &voter_info, self.runtime.system_time()).await {
+            eprintln!("Warning: {}", e);
+        }
+        self.state.save_voter(&voter_chain, voter_info).await.expect("Failed to update voter");
         
         // Update total - use saturating_add to avoid overflow
         let current_stake = *self.state.total_stake.get();
@@ -999,67 +1500,328 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(e);
         }
         
-        // Update stake - use saturating_sub to avoid underflow
-        voter_info.stake = voter_info.stake.saturating_sub(amount);
-        self.state.voters.insert(&voter_chain, voter_info).expect("Failed to update voter");
-        
-        // Update total - use saturating_sub to avoid underflow
-        let current_stake = *self.state.total_stake.get();
-        let new_total = current_stake.saturating_sub(amount);
-        self.state.total_stake.set(new_total);
+        // Record voter activity
+        voter_info.last_active_at = self.runtime.system_time();
+        self.state.save_voter(&voter_chain, voter_info).await.expect("Failed to update voter");
         
-        // Transfer stake back
-        // Note: Implement proper token transfer
+        // Stake is deducted by the token chain once the tokens are released
+        self.send_withdraw_tokens_message(amount, voter_chain);
         
-        OperationResponse::success("Stake withdrawn successfully")
+        OperationResponse::success(format!("Withdrawal of {} staked tokens requested", amount))
     }
     
-    /// Deregister voter
-    async fn deregister_voter(&mut self) -> oracle_registry_v2::OperationResponse {
+    /// Withdraw stake immediately, including stake locked on active queries
+    /// 
+    /// The voter leaves every active query they are involved in and their
+    /// locks are released. Stake still locked on finalized queries awaiting
+    /// UnlockQueryStakes cannot be withdrawn. `emergency_unstake_penalty_bps`
+    /// of the amount goes to the protocol treasury; the rest is returned
+    /// through the token contract.
+    async fn emergency_withdraw(&mut self, amount: Amount) -> oracle_registry_v2::OperationResponse {
         use oracle_registry_v2::OperationResponse;
         
         let voter_chain = self.runtime.chain_id();
         
+        // Validate withdrawal amount is positive
+        if amount == Amount::ZERO {
+            return OperationResponse::error("Withdrawal amount must be greater than zero");
+        }
+        
         // Validate voter is registered and active
-        let voter_info = match self.validate_voter_registered(&voter_chain).await {
+        let mut voter_info = match self.validate_voter_registered(&voter_chain).await {
             Ok(info) => info,
             Err(e) => return OperationResponse::error(e),
         };
         
-        // Validate no pending rewards
-        if let Err(e) = self.validate_no_pending_rewards(&voter_chain).await {
-            return OperationResponse::error(e);
+        // Leaving active queries releases their locks; locks held on finalized
+        // queries stay until UnlockQueryStakes releases them
+        let params = self.state.get_parameters().await;
+        let mut releases = Vec::new();
+        let mut released_stake = Amount::ZERO;
+        for query_id in self.state.get_active_queries().await {
+            let mut query = match self.state.get_query(query_id).await {
+                Some(q) => q,
+                None => continue,
+            };
+            
+            let held_lock = query.stake_lockers().contains(&voter_chain);
+            if !query.withdraw_voter(&voter_chain) {
+                continue;
+            }
+            
+            let locked_amount = if held_lock {
+                match self.state.stake_locks.get(&(query_id, voter_chain)).await.ok().flatten() {
+                    Some(locked) => locked,
+                    None => self.calculate_stake_to_lock(&voter_info, &query, &params),
+                }
+            } else {
+                Amount::ZERO
+            };
+            released_stake = released_stake.saturating_add(locked_amount);
+            releases.push((query_id, query, held_lock, locked_amount));
         }
         
-        // Validate no active votes
-        if let Err(e) = self.validate_no_active_votes(&voter_chain).await {
+        if let Err(e) = self.validate_sufficient_stake(
+            voter_info.stake,
+            voter_info.locked_stake.saturating_sub(released_stake),
+            amount,
+            params.min_stake
+        ) {
             return OperationResponse::error(e);
         }
         
-        // Return stake
-        let stake = voter_info.stake;
+        // Return the tokens first; nothing is changed if the release fails
+        let penalty = params.emergency_unstake_penalty(amount);
+        let payout = amount.saturating_sub(penalty);
+        if payout > Amount::ZERO {
+            if let Err(e) = self.release_tokens(payout, voter_chain).await {
+                return OperationResponse::error(format!("Failed to return tokens: {}", e));
+            }
+        }
         
-        // Remove voter
-        self.state.voters.remove(&voter_chain).expect("Failed to remove voter");
+        // Leave every active query the voter is involved in
+        let mut withdrawn_queries = Vec::new();
+        for (query_id, query, held_lock, locked_amount) in releases {
+            if held_lock {
+                self.state.take_stake_lock(query_id, &voter_chain).await;
+            }
+            
+            if let Err(e) = voter_info.unlock_stake(locked_amount) {
+                eprintln!("Warning: Failed to unlock stake for voter {} on query {}: {}",
+                          voter_chain, query_id, e);
+            }
+            
+            self.state.queries.insert(&query_id, query).expect("Failed to update query");
+            if let Err(e) = self.state.reconcile_vote_counts(query_id).await {
+                eprintln!("Warning: Failed to reconcile vote count for query {}: {}", query_id, e);
+            }
+            withdrawn_queries.push(query_id);
+        }
         
-        // Update totals - use saturating_sub to avoid underflow
-        let current_stake = *self.state.total_stake.get();
-        let new_total = current_stake.saturating_sub(stake);
-        self.state.total_stake.set(new_total);
+        // Deduct the full amount from the voter's stake
+        voter_info.stake = voter_info.stake.saturating_sub(amount);
+        voter_info.last_active_at = self.runtime.system_time();
+        let remaining_stake = voter_info.stake;
+        if let Err(e) = self.state.record_stake_snapshot(&voter_chain, &voter_info, self.runtime.system_time()).await {
+            eprintln!("Warning: {}", e);
+        }
+        self.state.save_voter(&voter_chain, voter_info).await.expect("Failed to update voter");
         
-        let current_count = *self.state.voter_count.get();
-        self.state.voter_count.set(current_count - 1);
+        let total = *self.state.total_stake.get();
+        self.state.total_stake.set(total.saturating_sub(amount));
         
-        // Emit VoterDeregistered event for cross-chain subscribers
-        self.emit_oracle_event(OracleEvent::VoterDeregistered {
+        // The penalty stays with the registry as treasury funds
+        let current_treasury = *self.state.protocol_treasury.get();
+        self.state.protocol_treasury.set(current_treasury.saturating_add(penalty));
+        
+        let current_holdings = self.state.token_holdings.get(&voter_chain).await.ok().flatten().unwrap_or(Amount::ZERO);
+        self.state.token_holdings.insert(&voter_chain, current_holdings.saturating_sub(amount)).expect("Failed to update holdings");
+        
+        let total_held = *self.state.total_tokens_held.get();
+        self.state.total_tokens_held.set(total_held.saturating_sub(payout));
+        
+        eprintln!("🚨 Emergency withdrawal by {}: {} returned, {} penalty, left {} active query(ies)",
+                  voter_chain, payout, penalty, withdrawn_queries.len());
+        
+        self.emit_oracle_event(OracleEvent::EmergencyWithdrawal {
             voter_chain,
-            stake_returned: stake,
+            amount,
+            penalty,
+            withdrawn_queries: withdrawn_queries.clone(),
         });
         
-        // Transfer stake back
-        // Note: Implement proper token transfer
+        OperationResponse::success(format!(
+            "Emergency withdrawal of {} tokens ({} penalty). Left {} active query(ies). Remaining stake: {}",
+            amount, penalty, withdrawn_queries.len(), remaining_stake
+        ))
+    }
+    
+    /// Forfeit the stake locked on active queries and deregister immediately
+    /// 
+    /// The voter leaves every active query they are involved in, so their
+    /// commits and votes count as non-participation. The stake locked on
+    /// those queries goes to the protocol treasury. Locks on finalized queries
+    /// awaiting UnlockQueryStakes are released, and the rest of the stake is
+    /// returned through the token contract before the voter is removed.
+    async fn forfeit_and_exit(&mut self) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let voter_chain = self.runtime.chain_id();
+        
+        // Validate voter is registered and active
+        let voter_info = match self.validate_voter_registered(&voter_chain).await {
+            Ok(info) => info,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Validate no pending rewards
+        if let Err(e) = self.validate_no_pending_rewards(&voter_chain).await {
+            return OperationResponse::error(e);
+        }
+        
+        // Only the locks of the active queries the voter leaves are forfeited
+        let params = self.state.get_parameters().await;
+        let mut withdrawals = Vec::new();
+        let mut forfeited = Amount::ZERO;
+        for query_id in self.state.get_active_queries().await {
+            let mut query = match self.state.get_query(query_id).await {
+                Some(q) => q,
+                None => continue,
+            };
+            
+            let held_lock = query.stake_lockers().contains(&voter_chain);
+            if !query.withdraw_voter(&voter_chain) {
+                continue;
+            }
+            
+            if held_lock {
+                let locked_amount = match self.state.stake_locks.get(&(query_id, voter_chain)).await.ok().flatten() {
+                    Some(locked) => locked,
+                    None => self.calculate_stake_to_lock(&voter_info, &query, &params),
+                };
+                forfeited = forfeited.saturating_add(locked_amount);
+            }
+            withdrawals.push((query_id, query));
+        }
+        
+        // Return the rest of the stake first; nothing is changed if the release fails
+        let forfeited = forfeited.min(voter_info.stake);
+        let stake_returned = voter_info.stake.saturating_sub(forfeited);
+        if stake_returned > Amount::ZERO {
+            if let Err(e) = self.release_tokens(stake_returned, voter_chain).await {
+                return OperationResponse::error(format!("Failed to return tokens: {}", e));
+            }
+        }
+        
+        // Leave every active query the voter is involved in
+        let mut forfeited_queries = Vec::new();
+        for (query_id, query) in withdrawals {
+            // The lock itself is forfeited with the rest of the locked stake
+            self.state.take_stake_lock(query_id, &voter_chain).await;
+            
+            self.state.queries.insert(&query_id, query).expect("Failed to update query");
+            if let Err(e) = self.state.reconcile_vote_counts(query_id).await {
+                eprintln!("Warning: Failed to reconcile vote count for query {}: {}", query_id, e);
+            }
+            forfeited_queries.push(query_id);
+        }
+        
+        // Locks on finalized queries were returned with the rest of the stake
+        let released = self.state.take_pending_unlock_locks(&voter_chain).await;
+        
+        // Remove the voter and their stake
+        self.state.remove_voter(&voter_chain).await.expect("Failed to remove voter");
+        let exited = state::VoterInfo { stake: Amount::ZERO, locked_stake: Amount::ZERO, ..voter_info.clone() };
+        if let Err(e) = self.state.record_stake_snapshot(&voter_chain, &exited, self.runtime.system_time()).await {
+            eprintln!("Warning: {}", e);
+        }
+        
+        let total = *self.state.total_stake.get();
+        self.state.total_stake.set(total.saturating_sub(voter_info.stake));
+        
+        // The forfeited stake stays with the registry as treasury funds
+        let current_treasury = *self.state.protocol_treasury.get();
+        self.state.protocol_treasury.set(current_treasury.saturating_add(forfeited));
+        
+        let current_holdings = self.state.token_holdings.get(&voter_chain).await.ok().flatten().unwrap_or(Amount::ZERO);
+        self.state.token_holdings.insert(&voter_chain, current_holdings.saturating_sub(voter_info.stake)).expect("Failed to update holdings");
+        
+        let total_held = *self.state.total_tokens_held.get();
+        self.state.total_tokens_held.set(total_held.saturating_sub(stake_returned));
+        
+        eprintln!("🚪 Forfeit and exit by {}: {} forfeited, {} returned ({} released from finalized queries), left {} active query(ies)",
+                  voter_chain, forfeited, stake_returned, released, forfeited_queries.len());
+        
+        self.emit_oracle_event(OracleEvent::VoterForfeitedAndExited {
+            voter_chain,
+            forfeited,
+            stake_returned,
+            forfeited_queries: forfeited_queries.clone(),
+        });
+        self.finalize_deregistration(voter_chain, stake_returned);
+        
+        OperationResponse::success(format!(
+            "Voter deregistered: {} locked stake forfeited, {} returned. Left {} active query(ies)",
+            forfeited, stake_returned, forfeited_queries.len()
+        ))
+    }
+    
+    /// Send WithdrawTokens to the token chain (the registry's creator chain)
+    fn send_withdraw_tokens_message(
+        &mut self,
+        amount: Amount,
+        target_chain: linera_sdk::linera_base_types::ChainId,
+    ) {
+        use oracle_registry_v2::Message;
+        
+        let token_chain = self.runtime.application_creator_chain_id();
+        eprintln!("📤 Sending WithdrawTokens ({} tokens for {}) to {}", amount, target_chain, token_chain);
+        
+        self.runtime.prepare_message(Message::WithdrawTokens { amount, target_chain })
+            .with_authentication()
+            .with_tracking()
+            .send_to(token_chain);
+    }
+    
+    /// Deregister voter
+    async fn deregister_voter(&mut self) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let voter_chain = self.runtime.chain_id();
+        
+        // Validate voter is registered and active
+        let mut voter_info = match self.validate_voter_registered(&voter_chain).await {
+            Ok(info) => info,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Validate no pending rewards
+        if let Err(e) = self.validate_no_pending_rewards(&voter_chain).await {
+            return OperationResponse::error(e);
+        }
+        
+        // Validate no active votes
+        if let Err(e) = self.validate_no_active_votes(&voter_chain).await {
+            return OperationResponse::error(e);
+        }
+        
+        // Nothing staked: remove the voter right away
+        let stake = voter_info.stake;
+        if stake == Amount::ZERO {
+            self.state.remove_voter(&voter_chain).await.expect("Failed to remove voter");
+            self.finalize_deregistration(voter_chain, stake);
+            return OperationResponse::success("Voter deregistered successfully");
+        }
+        
+        // Deactivate; the voter is removed once the token chain has
+        // returned their stake (see handle_withdraw_tokens)
+        voter_info.is_active = false;
+        voter_info.last_active_at = self.runtime.system_time();
+        self.state.save_voter(&voter_chain, voter_info).await.expect("Failed to update voter");
+        
+        // Request stake return from the token chain
+        self.send_withdraw_tokens_message(stake, voter_chain);
+        
+        OperationResponse::success(format!(
+            "Voter deregistration requested; {} staked tokens will be returned",
+            stake
+        ))
+    }
+    
+    /// Update totals and emit VoterDeregistered once a voter has been removed
+    fn finalize_deregistration(
+        &mut self,
+        voter_chain: linera_sdk::linera_base_types::ChainId,
+        stake_returned: Amount,
+    ) {
+        let current_count = *self.state.voter_count.get();
+        self.state.voter_count.set(state::decrement_counter(current_count, "voter_count"));
         
-        OperationResponse::success("Voter deregistered successfully")
+        // Emit VoterDeregistered event for cross-chain subscribers
+        self.emit_oracle_event(OracleEvent::VoterDeregistered {
+            voter_chain,
+            stake_returned,
+        });
     }
 }
 
@@ -1224,13 +1986,7 @@ impl OracleRegistryV2Contract {
         eprintln!("📤 Sending CreateQuery message from {} to {}", sender_chain, target_chain);
         eprintln!("   Description: {}", description);
         
-        // Convert strategy to string
-        let strategy_str = match strategy {
-            oracle_registry_v2::state::DecisionStrategy::Majority => "Majority",
-            oracle_registry_v2::state::DecisionStrategy::Median => "Median",
-            oracle_registry_v2::state::DecisionStrategy::WeightedByStake => "WeightedByStake",
-            oracle_registry_v2::state::DecisionStrategy::WeightedByReputation => "WeightedByReputation",
-        }.to_string();
+        let strategy_str = strategy.as_str().to_string();
         
         // Create the message
         let message = Message::CreateQuery {
@@ -1289,6 +2045,35 @@ impl OracleRegistryV2Contract {
         ))
     }
     
+    /// Send RequestQueryResult message to target chain (cross-chain result lookup)
+    async fn send_request_query_result_message(
+        &mut self,
+        target_chain: linera_sdk::linera_base_types::ChainId,
+        query_id: u64,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::{Message, OperationResponse};
+        
+        let reply_to = self.runtime.chain_id();
+        eprintln!("📤 Sending RequestQueryResult message from {} to {}", reply_to, target_chain);
+        eprintln!("   Query ID: {}", query_id);
+        
+        // Ask for the result to be delivered back to this chain
+        let message = Message::RequestQueryResult {
+            query_id,
+            reply_to,
+        };
+        
+        self.runtime.prepare_message(message)
+            .with_authentication()
+            .with_tracking()
+            .send_to(target_chain);
+        
+        OperationResponse::success(format!(
+            "RequestQueryResult message sent to chain {}. Result for query {} will be sent to {}",
+            target_chain, query_id, reply_to
+        ))
+    }
+    
     /// Update stake from cross-chain message
     async fn update_stake_from_message(
         &mut self,
@@ -1317,10 +2102,14 @@ impl OracleRegistryV2Contract {
         // Update voter stake
         let mut updated_info = voter_info.clone();
         updated_info.stake = new_stake;
+        updated_info.last_active_at = self.runtime.system_time();
         
-        if let Err(e) = self.state.voters.insert(&sender_chain, updated_info) {
+        if let Err(e) = self.state.save_voter(&sender_chain, updated_info.clone()).await {
             return OperationResponse::error(format!("Failed to update stake: {}", e));
         }
+        if let Err(e) = self.state.record_stake_snapshot(&sender_chain, &updated_info, self.runtime.system_time()).await {
+            eprintln!("Warning: {}", e);
+        }
         
         // Update total stake using saturating_add
         let total_stake = *self.state.total_stake.get();
@@ -1359,12 +2148,14 @@ impl OracleRegistryV2Contract {
         eprintln!("📥 Received CreateQuery message from chain: {}", sender_chain);
         eprintln!("   Description: {}", description);
         
-        // Parse strategy string
+        // Parse strategy string (empty = registry default)
         let state_strategy = match strategy.as_str() {
+            "" => self.state.get_parameters().await.strategy_or_default(None),
             "Majority" => DecisionStrategy::Majority,
             "Median" => DecisionStrategy::Median,
             "WeightedByStake" => DecisionStrategy::WeightedByStake,
             "WeightedByReputation" => DecisionStrategy::WeightedByReputation,
+            "ConfidenceWeightedMedian" => DecisionStrategy::ConfidenceWeightedMedian,
             _ => return OperationResponse::error(format!("Invalid strategy: {}", strategy)),
         };
         
@@ -1373,11 +2164,29 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(e);
         }
         
+        // Explicit median strategies need numeric outcomes; the registry
+        // default falls back to Majority at resolution when they do not fit
+        let numeric_outcomes = match self.validate_strategy_compatibility(&state_strategy, &outcomes) {
+            Ok(numeric) => numeric,
+            Err(_) if strategy.is_empty() => None,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Enforce the governance cap on query rewards
+        if let Err(e) = self.state.get_parameters().await.check_query_reward(reward_amount) {
+            return OperationResponse::error(e);
+        }
+        
+        // Keep the number of active queries under the governance cap
+        if let Err(e) = self.validate_active_query_capacity().await {
+            return OperationResponse::error(e);
+        }
+        
         // Get protocol parameters
         let params = self.state.get_parameters().await;
         
-        // Determine min_votes (use provided or default)
-        let min_votes_required = min_votes.unwrap_or(params.min_votes_default);
+        // Determine min_votes (use provided or the strategy's default)
+        let min_votes_required = min_votes.unwrap_or_else(|| params.min_votes_for(&state_strategy));
         
         // Calculate commit/reveal phases
         let current_time = self.runtime.system_time();
@@ -1395,10 +2204,6 @@ impl OracleRegistryV2Contract {
         
         let query_deadline = reveal_phase_end;
         
-        // Get next query ID
-        let query_id = *self.state.next_query_id.get();
-        self.state.next_query_id.set(query_id + 1);
-        
         // Determine max_voters
         let max_voters = min_votes_required * 2;
         
@@ -1411,13 +2216,41 @@ impl OracleRegistryV2Contract {
             Err(e) => return OperationResponse::error(format!("Failed to select voters: {}", e)),
         };
         
+        // Collect the deposit last, so a rejected query never keeps it
+        let deposit = match self.collect_creation_deposit().await {
+            Ok(deposit) => deposit,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Allocate query ID only once voter selection has succeeded
+        let query_id = *self.state.next_query_id.get();
+        self.state.next_query_id.set(query_id + 1);
+        if let Err(e) = self.state.record_voter_selections(query_id, &selected_voters).await {
+            eprintln!("Warning: {}", e);
+        }
+        self.hold_creation_deposit(query_id, deposit);
+        
         // Create query
         let query = Query {
             id: query_id,
             description: description.clone(),
             outcomes: outcomes.clone(),
+            numeric_outcomes,
+            outcome_aliases: std::collections::BTreeMap::new(),
             strategy: state_strategy,
             min_votes: min_votes_required,
+            reward_basis: None,
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            allow_external_resolution: false,
+            tie_break: state::TieBreak::OutcomeOrder,
+            category: None,
+            tags: Vec::new(),
             reward_amount,
             creator: sender_chain,
             created_at: current_time,
@@ -1427,7 +2260,9 @@ impl OracleRegistryV2Contract {
             phase: state::VotingPhase::Commit,
             status: QueryStatus::Active,
             result: None,
+            result_display: None,
             resolved_at: None,
+            resolution_source: None,
             commits: std::collections::BTreeMap::new(),
             votes: std::collections::BTreeMap::new(),
             selected_voters,
@@ -1449,7 +2284,7 @@ impl OracleRegistryV2Contract {
         
         // Update statistics
         let total_created = *self.state.total_queries_created.get();
-        self.state.total_queries_created.set(total_created + 1);
+        self.state.total_queries_created.set(state::increment_counter(total_created, "total_queries_created"));
         
         eprintln!("✅ Query {} created successfully from chain {}", query_id, sender_chain);
         
@@ -1460,6 +2295,11 @@ impl OracleRegistryV2Contract {
                 query_id: Some(query_id),
                 vote_count: None,
                 rewards_claimed: None,
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
             }
         )
     }
@@ -1492,8 +2332,12 @@ impl OracleRegistryV2Contract {
         let new_stake = voter.stake.saturating_add(amount);
         let mut updated_voter = voter.clone();
         updated_voter.stake = new_stake;
+        updated_voter.last_active_at = self.runtime.system_time();
         
-        self.state.voters.insert(&sender_chain, updated_voter).expect("Failed to update voter");
+        if let Err(e) = self.state.record_stake_snapshot(&sender_chain, &updated_voter, self.runtime.system_time()).await {
+            eprintln!("Warning: {}", e);
+        }
+        self.state.save_voter(&sender_chain, updated_voter).await.expect("Failed to update voter");
         
         // Update total stake
         let total = *self.state.total_stake.get();
@@ -1519,6 +2363,11 @@ impl OracleRegistryV2Contract {
         
         eprintln!("💸 Withdraw request: {} tokens to chain {}", amount, target_chain);
         
+        // Only the voter's own chain may request its stake back
+        if self.runtime.message_origin_chain_id() != Some(target_chain) {
+            return OperationResponse::error("WithdrawTokens must be sent from the voter's chain");
+        }
+        
         // Check if voter has enough available stake
         let voter = match self.state.get_voter(&target_chain).await {
             Some(v) => v,
@@ -1535,12 +2384,29 @@ impl OracleRegistryV2Contract {
             ));
         }
         
+        // Release the tokens first; tracked stake is only deducted once the
+        // token contract has accepted the release
+        if let Err(e) = self.release_tokens(amount, target_chain).await {
+            return OperationResponse::error(format!("Failed to return tokens: {}", e));
+        }
+        
         // Deduct from voter's stake
         let new_stake = voter.stake.saturating_sub(amount);
         let mut updated_voter = voter.clone();
         updated_voter.stake = new_stake;
+        updated_voter.last_active_at = self.runtime.system_time();
+        
+        if let Err(e) = self.state.record_stake_snapshot(&target_chain, &updated_voter, self.runtime.system_time()).await {
+            eprintln!("Warning: {}", e);
+        }
         
-        self.state.voters.insert(&target_chain, updated_voter).expect("Failed to update voter");
+        // Complete a pending deregistration once all stake is returned
+        let deregistered = !updated_voter.is_active && new_stake == Amount::ZERO;
+        if deregistered {
+            self.state.remove_voter(&target_chain).await.expect("Failed to remove voter");
+        } else {
+            self.state.save_voter(&target_chain, updated_voter).await.expect("Failed to update voter");
+        }
         
         // Update total stake
         let total = *self.state.total_stake.get();
@@ -1553,99 +2419,402 @@ impl OracleRegistryV2Contract {
         let total_held = *self.state.total_tokens_held.get();
         self.state.total_tokens_held.set(total_held.saturating_sub(amount));
         
-        // TODO: Send WithdrawToAccount message to token contract to return tokens
-        // This requires knowing the token contract's application ID
+        if deregistered {
+            self.finalize_deregistration(target_chain, voter.stake);
+            return OperationResponse::success(format!("Voter deregistered; returned {} tokens", amount));
+        }
         
         OperationResponse::success(format!("Withdrawn {} tokens. Remaining stake: {}", amount, new_stake))
     }
-}
-
-impl OracleRegistryV2Contract {
-    /// Initialize test voters for development and testing
-    /// This adds three pre-configured voters (Alice, Bob, Charlie) to the registry
+    
+    /// Release `amount` tokens (stake or rewards) to `target_chain` via the token contract
     /// 
-    /// NOTE: This is for testing only. In production, remove this function.
-    async fn initialize_test_voters_internal(_state: &mut OracleRegistryV2) {
-        // Disabled: ChainId parsing doesn't work reliably in WASM initialization context
-        // Test voters should be registered through normal registration flow after deployment
-        // 
-        // To register a test voter after deployment, use:
-        // curl -X POST http://localhost:8080/chains/{CHAIN}/applications/{APP} \
-        //   -H "Content-Type: application/json" \
-        //   -d '{"query": "mutation { registerVoter(stake: \"1000\", name: \"TestVoter\") }"}'
-        //
-        // Or use the admin RegisterVoterFor operation from the contract owner chain
+    /// The token contract debits the registry's holdings and credits the
+    /// chain's account through its WithdrawToAccount message.
+    async fn release_tokens(
+        &mut self,
+        amount: Amount,
+        target_chain: linera_sdk::linera_base_types::ChainId,
+    ) -> Result<(), String> {
+        use linera_sdk::linera_base_types::{Account, AccountOwner};
+        
+        self.release_tokens_to_account(amount, Account {
+            chain_id: target_chain,
+            owner: AccountOwner::CHAIN,
+        }).await
     }
-}
-
-// ==================== EVENT STREAMING HELPERS ====================
-
-impl OracleRegistryV2Contract {
-    /// Emit an oracle event to the event stream
-    /// 
-    /// This broadcasts the event to all chains that have subscribed to
-    /// this contract's oracle events using `subscribe_to_events()`.
-    /// 
-    /// ## Example
-    /// ```rust
-    /// self.emit_oracle_event(OracleEvent::QueryCreated {
-    ///     query_id: 1,
-    ///     description: "Will BTC reach $100k?".to_string(),
-    ///     outcomes: vec!["Yes".to_string(), "No".to_string()],
-    ///     deadline: timestamp,
-    ///     creator: chain_id,
-    ///     min_votes: 3,
-    /// });
-    /// ```
-    fn emit_oracle_event(&mut self, event: OracleEvent) {
-        eprintln!("📤 Emitting OracleEvent: {:?}", event);
-        self.runtime.emit(
-            StreamName::from(ORACLE_STREAM_NAME),
-            &event,
+    
+    /// Release `amount` tokens to `target_account` via the token contract
+    async fn release_tokens_to_account(
+        &mut self,
+        amount: Amount,
+        target_account: linera_sdk::linera_base_types::Account,
+    ) -> Result<(), String> {
+        let token_app_id = self.state.get_parameters().await.token_app_id
+            .ok_or_else(|| "Token application not configured".to_string())?;
+        
+        let response = self.runtime.call_application(
+            true,
+            token_app_id.with_abi::<alethea_token::AletheaTokenAbi>(),
+            &alethea_token::Operation::ReleaseFromApplication {
+                target_account,
+                amount,
+            },
         );
+        
+        if response.success {
+            Ok(())
+        } else {
+            Err(response.message)
+        }
     }
     
-    /// Handle incoming oracle events from other chains
+    /// Sweep the treasury's held tokens to the treasury account (anyone)
     /// 
-    /// This is called by `process_streams()` when events are received
-    /// from subscribed chains. Override this to add custom event handling.
-    async fn handle_oracle_event(
-        &mut self,
-        event: OracleEvent,
-        source_chain: linera_sdk::linera_base_types::ChainId,
-    ) {
-        // Log the event for monitoring
-        match &event {
-            OracleEvent::QueryCreated { query_id, description, .. } => {
-                eprintln!(
-                    "📥 [{}] Query {} created: {}",
-                    source_chain, query_id, description
-                );
-            }
-            OracleEvent::QueryResolved { query_id, result, .. } => {
-                eprintln!(
-                    "📥 [{}] Query {} resolved: {}",
-                    source_chain, query_id, result
-                );
-            }
-            OracleEvent::VoterRegistered { voter_chain, stake, name } => {
-                eprintln!(
-                    "📥 [{}] Voter {} registered with stake {} (name: {:?})",
-                    source_chain, voter_chain, stake, name
-                );
-            }
-            OracleEvent::VoteSubmitted { query_id, voter_chain, value } => {
-                eprintln!(
-                    "📥 [{}] Vote on query {} by {}: {}",
-                    source_chain, query_id, voter_chain, value
-                );
-            }
-            OracleEvent::RewardsClaimed { voter_chain, amount } => {
-                eprintln!(
-                    "📥 [{}] Rewards claimed by {}: {}",
-                    source_chain, voter_chain, amount
-                );
-            }
+    /// Tokens always go to the account set in the parameters, so any chain
+    /// may trigger the sweep.
+    async fn sweep_treasury_operation(&mut self) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        match self.sweep_treasury().await {
+            Ok(amount) => OperationResponse::success(format!("Swept {} treasury tokens", amount)),
+            Err(e) => OperationResponse::error(e),
+        }
+    }
+    
+    /// Release the sweepable treasury to the treasury account, returning the amount swept
+    /// 
+    /// `protocol_treasury` and the registry's token holdings are reduced by the
+    /// amount released, so the counter keeps matching the tokens still held.
+    async fn sweep_treasury(&mut self) -> Result<Amount, String> {
+        let params = self.state.get_parameters().await;
+        let treasury_account = params.treasury_account
+            .ok_or_else(|| "Treasury account not configured".to_string())?;
+        
+        // Only tokens held beyond voter stakes can leave the registry
+        let amount = self.state.sweepable_treasury();
+        if amount == Amount::ZERO {
+            return Err(format!(
+                "Nothing to sweep: treasury {} is not backed by tokens held beyond stakes",
+                self.state.protocol_treasury.get()
+            ));
+        }
+        
+        self.release_tokens_to_account(amount, treasury_account).await
+            .map_err(|e| format!("Failed to release treasury tokens: {}", e))?;
+        
+        let remaining_treasury = self.state.protocol_treasury.get().saturating_sub(amount);
+        self.state.protocol_treasury.set(remaining_treasury);
+        
+        let total_held = *self.state.total_tokens_held.get();
+        self.state.total_tokens_held.set(total_held.saturating_sub(amount));
+        
+        self.state.last_treasury_sweep.set(self.runtime.system_time());
+        
+        self.emit_oracle_event(OracleEvent::TreasurySwept {
+            amount,
+            treasury_account,
+            remaining_treasury,
+        });
+        
+        Ok(amount)
+    }
+    
+    /// Transfer `amount` tokens from the signer's token account into the reward pool (admin only)
+    async fn fund_reward_pool(&mut self, amount: Amount) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let caller_chain = self.runtime.chain_id();
+        if !self.state.is_admin(&caller_chain).await {
+            return OperationResponse::error("Unauthorized: only admin can fund the reward pool");
+        }
+        if amount == Amount::ZERO {
+            return OperationResponse::error("Amount must be greater than zero");
+        }
+        
+        // The pool only grows by tokens that actually reached the registry
+        let funder = match self.receive_tokens_from_signer(amount).await {
+            Ok(owner) => owner,
+            Err(e) => return OperationResponse::error(format!("Failed to transfer tokens: {}", e)),
+        };
+        
+        let reward_pool = self.state.reward_pool.get().saturating_add(amount);
+        self.state.reward_pool.set(reward_pool);
+        
+        self.emit_oracle_event(OracleEvent::RewardPoolFunded {
+            amount,
+            funder,
+            reward_pool,
+        });
+        
+        OperationResponse::success(format!("Funded reward pool with {} tokens. Reward pool: {}", amount, reward_pool))
+    }
+    
+    /// Move `amount` tokens into or out of the protocol treasury (admin only)
+    /// 
+    /// An increase transfers tokens from the signer's token account. A decrease
+    /// releases tokens back to it and is limited to the treasury backed by
+    /// held tokens, like a sweep.
+    async fn adjust_treasury(&mut self, amount: Amount, increase: bool) -> oracle_registry_v2::OperationResponse {
+        use linera_sdk::linera_base_types::Account;
+        use oracle_registry_v2::OperationResponse;
+        
+        let caller_chain = self.runtime.chain_id();
+        if !self.state.is_admin(&caller_chain).await {
+            return OperationResponse::error("Unauthorized: only admin can adjust the treasury");
+        }
+        if amount == Amount::ZERO {
+            return OperationResponse::error("Amount must be greater than zero");
+        }
+        
+        let owner = if increase {
+            match self.receive_tokens_from_signer(amount).await {
+                Ok(owner) => owner,
+                Err(e) => return OperationResponse::error(format!("Failed to transfer tokens: {}", e)),
+            }
+        } else {
+            let Some(owner) = self.runtime.authenticated_signer() else {
+                return OperationResponse::error("Treasury adjustments must be signed by the admin's owner");
+            };
+            let sweepable = self.state.sweepable_treasury();
+            if amount > sweepable {
+                return OperationResponse::error(format!(
+                    "Cannot decrease treasury by {}: only {} is backed by held tokens",
+                    amount, sweepable
+                ));
+            }
+            let target_account = Account { chain_id: caller_chain, owner };
+            if let Err(e) = self.release_tokens_to_account(amount, target_account).await {
+                return OperationResponse::error(format!("Failed to release treasury tokens: {}", e));
+            }
+            let total_held = *self.state.total_tokens_held.get();
+            self.state.total_tokens_held.set(total_held.saturating_sub(amount));
+            owner
+        };
+        
+        let treasury = *self.state.protocol_treasury.get();
+        let protocol_treasury = if increase {
+            treasury.saturating_add(amount)
+        } else {
+            treasury.saturating_sub(amount)
+        };
+        self.state.protocol_treasury.set(protocol_treasury);
+        
+        self.emit_oracle_event(OracleEvent::TreasuryAdjusted {
+            amount,
+            increase,
+            owner,
+            protocol_treasury,
+        });
+        
+        OperationResponse::success(format!(
+            "Treasury {} by {} tokens. Treasury: {}",
+            if increase { "increased" } else { "decreased" },
+            amount,
+            protocol_treasury
+        ))
+    }
+    
+    /// Transfer `amount` tokens from the signer's token account to the registry
+    /// 
+    /// Returns the signer. The registry's token holdings grow by `amount` once
+    /// the token contract accepts the transfer.
+    async fn receive_tokens_from_signer(
+        &mut self,
+        amount: Amount,
+    ) -> Result<linera_sdk::linera_base_types::AccountOwner, String> {
+        let token_app_id = self.state.get_parameters().await.token_app_id
+            .ok_or_else(|| "Token application not configured".to_string())?;
+        let owner = self.runtime.authenticated_signer()
+            .ok_or_else(|| "Operation must be signed by the owner of the tokens".to_string())?;
+        let target_application = self.runtime.application_id().forget_abi();
+        let target_chain = self.runtime.chain_id();
+        
+        let response = self.runtime.call_application(
+            true,
+            token_app_id.with_abi::<alethea_token::AletheaTokenAbi>(),
+            &alethea_token::Operation::TransferToApplication {
+                owner,
+                amount,
+                target_application,
+                target_chain,
+            },
+        );
+        if !response.success {
+            return Err(response.message);
+        }
+        
+        let total_held = *self.state.total_tokens_held.get();
+        self.state.total_tokens_held.set(total_held.saturating_add(amount));
+        
+        Ok(owner)
+    }
+}
+
+impl OracleRegistryV2Contract {
+    /// Initialize test voters for development and testing
+    /// This adds three pre-configured voters (Alice, Bob, Charlie) to the registry
+    /// 
+    /// NOTE: This is for testing only. In production, remove this function.
+    async fn initialize_test_voters_internal(_state: &mut OracleRegistryV2) {
+        // Disabled: ChainId parsing doesn't work reliably in WASM initialization context
+        // Test voters should be registered through normal registration flow after deployment
+        // 
+        // To register a test voter after deployment, use:
+        // curl -X POST http://localhost:8080/chains/{CHAIN}/applications/{APP} \
+        //   -H "Content-Type: application/json" \
+        //   -d '{"query": "mutation { registerVoter(stake: \"1000\", name: \"TestVoter\") }"}'
+        //
+        // Or use the admin RegisterVoterFor operation from the contract owner chain
+    }
+    
+    /// Register the admin chain as a voter if the instantiation argument asks for it
+    /// 
+    /// Uses `bootstrap_stake`, or the minimum stake when unset. Failures are
+    /// logged so instantiation itself always succeeds.
+    async fn bootstrap_admin_voter(&mut self, argument: oracle_registry_v2::InstantiationArgument) {
+        if !argument.bootstrap_admin_as_voter {
+            return;
+        }
+        
+        let stake = match argument.bootstrap_stake {
+            Some(stake) => stake,
+            None => self.state.get_parameters().await.min_stake,
+        };
+        
+        let response = self.register_voter(stake, Some("Bootstrap voter".to_string()), None).await;
+        if response.success {
+            eprintln!("🗳️ Admin chain {} registered as bootstrap voter with stake {}", self.runtime.chain_id(), stake);
+        } else {
+            eprintln!("Warning: Failed to register bootstrap voter: {}", response.message);
+        }
+    }
+}
+
+// ==================== EVENT STREAMING HELPERS ====================
+
+impl OracleRegistryV2Contract {
+    /// Emit an oracle event to the event stream
+    /// 
+    /// This broadcasts the event to all chains that have subscribed to
+    /// this contract's oracle events using `subscribe_to_events()`.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// self.emit_oracle_event(OracleEvent::QueryCreated {
+    ///     query_id: 1,
+    ///     description: "Will BTC reach $100k?".to_string(),
+    ///     outcomes: vec!["Yes".to_string(), "No".to_string()],
+    ///     deadline: timestamp,
+    ///     creator: chain_id,
+    ///     min_votes: 3,
+    /// });
+    /// ```
+    fn emit_oracle_event(&mut self, event: OracleEvent) {
+        eprintln!("📤 Emitting OracleEvent: {:?}", event);
+        self.record_activity(&event);
+        self.runtime.emit(
+            StreamName::from(ORACLE_STREAM_NAME),
+            &event,
+        );
+    }
+    
+    /// Add `event` to the activity feed if it is a registration, query
+    /// creation, vote or resolution
+    fn record_activity(&mut self, event: &OracleEvent) {
+        use state::ActivityKind;
+        
+        let (kind, chain_id, query_id, detail) = match event {
+            OracleEvent::VoterRegistered { voter_chain, .. } => {
+                (ActivityKind::VoterRegistered, Some(*voter_chain), None, None)
+            }
+            OracleEvent::QueryCreated { query_id, creator, .. } => {
+                (ActivityKind::QueryCreated, Some(*creator), Some(*query_id), None)
+            }
+            OracleEvent::VoteCommitted { query_id, voter_chain, .. } => {
+                (ActivityKind::VoteCommitted, Some(*voter_chain), Some(*query_id), None)
+            }
+            OracleEvent::VoteSubmitted { query_id, voter_chain, value }
+            | OracleEvent::VoteRevealed { query_id, voter_chain, value } => {
+                (ActivityKind::VoteCast, Some(*voter_chain), Some(*query_id), Some(value.clone()))
+            }
+            OracleEvent::QueryResolved { query_id, result, .. }
+            | OracleEvent::QueryResolvedExternally { query_id, result, .. } => {
+                (ActivityKind::QueryResolved, None, Some(*query_id), Some(result.clone()))
+            }
+            _ => return,
+        };
+        
+        let now = self.runtime.system_time();
+        if let Err(e) = self.state.record_activity(kind, chain_id, query_id, detail, now) {
+            eprintln!("Warning: {}", e);
+        }
+    }
+    
+    /// Log a failed cross-chain message and emit a MessageFailed event
+    /// 
+    /// The log is bounded; the oldest entry is dropped once it is full.
+    fn record_failed_message(
+        &mut self,
+        kind: &str,
+        sender: Option<linera_sdk::linera_base_types::ChainId>,
+        error: String,
+    ) {
+        eprintln!("❌ [CONTRACT] {} message from {:?} failed: {}", kind, sender, error);
+        
+        let failed_at = self.runtime.system_time();
+        if let Err(e) = self.state.record_failed_message(kind.to_string(), sender, error.clone(), failed_at) {
+            eprintln!("Warning: {}", e);
+        }
+        
+        self.emit_oracle_event(OracleEvent::MessageFailed {
+            kind: kind.to_string(),
+            sender,
+            error,
+        });
+    }
+    
+    /// Handle incoming oracle events from other chains
+    /// 
+    /// This is called by `process_streams()` when events are received
+    /// from subscribed chains. Override this to add custom event handling.
+    async fn handle_oracle_event(
+        &mut self,
+        event: OracleEvent,
+        source_chain: linera_sdk::linera_base_types::ChainId,
+    ) {
+        // Log the event for monitoring
+        match &event {
+            OracleEvent::QueryCreated { query_id, description, .. } => {
+                eprintln!(
+                    "📥 [{}] Query {} created: {}",
+                    source_chain, query_id, description
+                );
+            }
+            OracleEvent::QueryResolved { query_id, result, .. } => {
+                eprintln!(
+                    "📥 [{}] Query {} resolved: {}",
+                    source_chain, query_id, result
+                );
+            }
+            OracleEvent::VoterRegistered { voter_chain, stake, name } => {
+                eprintln!(
+                    "📥 [{}] Voter {} registered with stake {} (name: {:?})",
+                    source_chain, voter_chain, stake, name
+                );
+            }
+            OracleEvent::VoteSubmitted { query_id, voter_chain, value } => {
+                eprintln!(
+                    "📥 [{}] Vote on query {} by {}: {}",
+                    source_chain, query_id, voter_chain, value
+                );
+            }
+            OracleEvent::RewardsClaimed { voter_chain, amount } => {
+                eprintln!(
+                    "📥 [{}] Rewards claimed by {}: {}",
+                    source_chain, voter_chain, amount
+                );
+            }
             _ => {
                 eprintln!("📥 [{}] Event: {:?}", source_chain, event);
             }
@@ -1669,6 +2838,18 @@ impl OracleRegistryV2Contract {
         reward_amount: Amount,
         deadline: Option<linera_sdk::linera_base_types::Timestamp>,
         duration_secs: Option<u64>,
+        min_distinct_voted_outcomes: Option<usize>,
+        min_votes_for_winner: Option<usize>,
+        min_distinct_voters: Option<usize>,
+        max_group_power_bps: Option<u32>,
+        min_voter_stake: Option<Amount>,
+        allow_abstain: bool,
+        multi_winner: bool,
+        category: Option<String>,
+        tags: Vec<String>,
+        reward_basis: Option<state::RewardBasis>,
+        outcome_aliases: std::collections::BTreeMap<String, String>,
+        tie_break: state::TieBreak,
     ) -> oracle_registry_v2::OperationResponse {
         use oracle_registry_v2::{OperationResponse, ResponseData};
         use state::{Query, QueryStatus};
@@ -1680,11 +2861,87 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(e);
         }
         
+        // Enforce the governance cap on query rewards
+        if let Err(e) = self.state.get_parameters().await.check_query_reward(reward_amount) {
+            return OperationResponse::error(e);
+        }
+        
+        // Keep the number of active queries under the governance cap
+        if let Err(e) = self.validate_active_query_capacity().await {
+            return OperationResponse::error(e);
+        }
+        
+        // Validate distinct voted outcome requirement
+        if let Some(min_distinct) = min_distinct_voted_outcomes {
+            if let Err(e) = self.validate_min_distinct_param(min_distinct, outcomes.len()) {
+                return OperationResponse::error(e);
+            }
+        }
+        
+        // Validate distinct voter and group power requirements
+        if let Err(e) = state::validate_voter_diversity(min_distinct_voters, max_group_power_bps) {
+            return OperationResponse::error(e);
+        }
+        
+        // Keep the abstain vote value distinct from real outcomes
+        if let Err(e) = state::validate_abstain_outcomes(allow_abstain, &outcomes) {
+            return OperationResponse::error(e);
+        }
+        
+        // Multiple winners need a strategy that tallies outcomes
+        if let Err(e) = state::validate_multi_winner(multi_winner, &strategy) {
+            return OperationResponse::error(e);
+        }
+        
+        // Only Majority queries break ties by mode
+        if let Err(e) = state::validate_tie_break(tie_break, &strategy) {
+            return OperationResponse::error(e);
+        }
+        
+        // Validate category and tags
+        if let Err(e) = state::validate_query_labels(&category, &tags) {
+            return OperationResponse::error(e);
+        }
+        
+        // Every alias must lead to a real outcome
+        if let Err(e) = state::validate_outcome_aliases(&outcome_aliases, &outcomes) {
+            return OperationResponse::error(e);
+        }
+        
         // Get protocol parameters
         let params = self.state.get_parameters().await;
         
-        // Determine min_votes (use provided or default)
-        let min_votes_required = min_votes.unwrap_or(params.min_votes_default);
+        // Determine min_votes (use provided or the strategy's default)
+        let min_votes_required = min_votes.unwrap_or_else(|| params.min_votes_for(&strategy));
+        
+        // Determine max_voters (2x min_votes to allow for non-participation)
+        let max_voters = min_votes_required * 2;
+        
+        // Validate the winner's vote requirement against the panel size
+        if let Some(min_winner) = min_votes_for_winner {
+            if let Err(e) = self.validate_min_votes_for_winner_param(min_winner, max_voters) {
+                return OperationResponse::error(e);
+            }
+        }
+        
+        // Validate the voting stake floor against the registration floor
+        if let Some(min_voter_stake) = min_voter_stake {
+            if let Err(e) = self.validate_min_voter_stake_param(min_voter_stake, params.min_stake) {
+                return OperationResponse::error(e);
+            }
+        }
+        
+        // SELECT VOTERS BY POWER
+        // Done before any state change so a failure leaves nothing behind
+        let selected_voters = match self.state
+            .select_voters_for_query(min_votes_required, max_voters)
+            .await
+        {
+            Ok(voters) => voters,
+            Err(e) => return OperationResponse::error(format!(
+                "Failed to select voters: {}", e
+            )),
+        };
         
         // Validate min_votes is reasonable
         let voter_count = *self.state.voter_count.get();
@@ -1693,9 +2950,10 @@ impl OracleRegistryV2Contract {
         }
         
         // Validate strategy is compatible with outcomes
-        if let Err(e) = self.validate_strategy_compatibility(&strategy, &outcomes) {
-            return OperationResponse::error(e);
-        }
+        let numeric_outcomes = match self.validate_strategy_compatibility(&strategy, &outcomes) {
+            Ok(numeric) => numeric,
+            Err(e) => return OperationResponse::error(e),
+        };
         
         // Calculate commit/reveal phases
         // Use custom duration if provided, otherwise use default
@@ -1721,14 +2979,161 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error("Deadline must be in the future");
         }
         
-        // Get next query ID
+        // Collect the deposit last, so a rejected query never keeps it
+        let deposit = match self.collect_creation_deposit().await {
+            Ok(deposit) => deposit,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Allocate query ID only once voter selection has succeeded
         let query_id = *self.state.next_query_id.get();
         self.state.next_query_id.set(query_id + 1);
+        if let Err(e) = self.state.record_voter_selections(query_id, &selected_voters).await {
+            eprintln!("Warning: {}", e);
+        }
+        self.hold_creation_deposit(query_id, deposit);
         
-        // Determine max_voters (2x min_votes to allow for non-participation)
-        let max_voters = min_votes_required * 2;
-        
-        // SELECT VOTERS BY POWER
+        // Create query with selected voters and commit/reveal phases
+        // Manual queries don't have callback info (only market-created queries do)
+        let query = Query {
+            id: query_id,
+            description,
+            outcomes,
+            numeric_outcomes,
+            outcome_aliases,
+            strategy,
+            min_votes: min_votes_required,
+            reward_basis,
+            min_distinct_voted_outcomes,
+            min_votes_for_winner,
+            min_distinct_voters,
+            max_group_power_bps,
+            min_voter_stake,
+            allow_abstain,
+            multi_winner,
+            allow_external_resolution: false,
+            tie_break,
+            category,
+            tags,
+            reward_amount,
+            creator,
+            created_at: current_time,
+            deadline: query_deadline,
+            commit_phase_end,
+            reveal_phase_end,
+            phase: state::VotingPhase::Commit,
+            status: QueryStatus::Active,
+            result: None,
+            result_display: None,
+            resolved_at: None,
+            resolution_source: None,
+            commits: std::collections::BTreeMap::new(),
+            votes: std::collections::BTreeMap::new(),
+            selected_voters,
+            max_voters,
+            callback_chain: None,  // No callback for manual queries
+            callback_data: None,   // No callback for manual queries
+        };
+        
+        // Clone data for event before moving into state
+        let description_for_event = query.description.clone();
+        let outcomes_for_event = query.outcomes.clone();
+        
+        // Store query
+        self.state.queries.insert(&query_id, query).expect("Failed to insert query");
+        
+        // Add to active queries
+        let mut active = self.state.get_active_queries().await;
+        active.push(query_id);
+        self.state.active_queries.set(active);
+        
+        // Initialize vote count
+        self.state.vote_counts.insert(&query_id, 0).expect("Failed to initialize vote count");
+        
+        // Update statistics
+        let total_created = *self.state.total_queries_created.get();
+        self.state.total_queries_created.set(state::increment_counter(total_created, "total_queries_created"));
+        
+        // Emit QueryCreated event for cross-chain subscribers
+        self.emit_oracle_event(OracleEvent::QueryCreated {
+            query_id,
+            description: description_for_event,
+            outcomes: outcomes_for_event,
+            deadline: query_deadline,
+            creator,
+            min_votes: min_votes_required,
+        });
+        
+        // Transfer reward amount to contract
+        // Note: In production, implement proper token transfer from creator
+        
+        OperationResponse::success_with_data(
+            format!("Query {} created successfully", query_id),
+            ResponseData {
+                voter_address: None,
+                query_id: Some(query_id),
+                vote_count: None,
+                rewards_claimed: None,
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
+            }
+        )
+    }
+    
+    /// Create a new query with callback information (for cross-application calls)
+    /// This allows other applications (like Simple Market) to create queries
+    /// and receive callbacks when the query is resolved
+    async fn create_query_with_callback(
+        &mut self,
+        description: String,
+        outcomes: Vec<String>,
+        strategy: state::DecisionStrategy,
+        min_votes: Option<usize>,
+        reward_amount: Amount,
+        deadline: Option<linera_sdk::linera_base_types::Timestamp>,
+        callback_chain: linera_sdk::linera_base_types::ChainId,
+        callback_app: linera_sdk::linera_base_types::ApplicationId,
+        callback_data: Vec<u8>,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::{OperationResponse, ResponseData};
+        use state::{Query, QueryStatus};
+        
+        eprintln!(
+            "📥 CreateQueryWithCallback: description={}, callback_chain={}, callback_app={}",
+            description, callback_chain, callback_app
+        );
+        
+        let creator = self.runtime.chain_id();
+        
+        // Validate query parameters
+        if let Err(e) = self.validate_query_params(&description, &outcomes, &reward_amount, &deadline) {
+            return OperationResponse::error(e);
+        }
+        
+        // Enforce the governance cap on query rewards
+        if let Err(e) = self.state.get_parameters().await.check_query_reward(reward_amount) {
+            return OperationResponse::error(e);
+        }
+        
+        // Keep the number of active queries under the governance cap
+        if let Err(e) = self.validate_active_query_capacity().await {
+            return OperationResponse::error(e);
+        }
+        
+        // Get protocol parameters
+        let params = self.state.get_parameters().await;
+        
+        // Determine min_votes (use provided or the strategy's default)
+        let min_votes_required = min_votes.unwrap_or_else(|| params.min_votes_for(&strategy));
+        
+        // Determine max_voters (2x min_votes to allow for non-participation)
+        let max_voters = min_votes_required * 2;
+        
+        // SELECT VOTERS BY POWER
+        // Done before any state change so a failure leaves nothing behind
         let selected_voters = match self.state
             .select_voters_for_query(min_votes_required, max_voters)
             .await
@@ -1739,14 +3144,64 @@ impl OracleRegistryV2Contract {
             )),
         };
         
-        // Create query with selected voters and commit/reveal phases
-        // Manual queries don't have callback info (only market-created queries do)
+        // Validate min_votes is reasonable
+        let voter_count = *self.state.voter_count.get();
+        if let Err(e) = self.validate_min_votes_param(min_votes_required, voter_count) {
+            return OperationResponse::error(e);
+        }
+        
+        // Validate strategy is compatible with outcomes
+        let numeric_outcomes = match self.validate_strategy_compatibility(&strategy, &outcomes) {
+            Ok(numeric) => numeric,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Calculate commit/reveal phases (1 hour each for cross-app queries)
+        let current_time = self.runtime.system_time();
+        let commit_duration = linera_sdk::linera_base_types::TimeDelta::from_micros(1 * 60 * 60 * 1_000_000u64);
+        let reveal_duration = linera_sdk::linera_base_types::TimeDelta::from_micros(1 * 60 * 60 * 1_000_000u64);
+        
+        let commit_phase_end = current_time.saturating_add(commit_duration);
+        let reveal_phase_end = commit_phase_end.saturating_add(reveal_duration);
+        
+        // Determine final deadline (use provided or calculated reveal_phase_end)
+        let query_deadline = deadline.unwrap_or(reveal_phase_end);
+        
+        // Collect the deposit last, so a rejected query never keeps it
+        let deposit = match self.collect_creation_deposit().await {
+            Ok(deposit) => deposit,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Allocate query ID only once voter selection has succeeded
+        let query_id = *self.state.next_query_id.get();
+        self.state.next_query_id.set(query_id + 1);
+        if let Err(e) = self.state.record_voter_selections(query_id, &selected_voters).await {
+            eprintln!("Warning: {}", e);
+        }
+        self.hold_creation_deposit(query_id, deposit);
+        
+        // Create query with callback information
         let query = Query {
             id: query_id,
             description,
             outcomes,
+            numeric_outcomes,
+            outcome_aliases: std::collections::BTreeMap::new(),
             strategy,
             min_votes: min_votes_required,
+            reward_basis: None,
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            allow_external_resolution: false,
+            tie_break: state::TieBreak::OutcomeOrder,
+            category: None,
+            tags: Vec::new(),
             reward_amount,
             creator,
             created_at: current_time,
@@ -1756,187 +3211,409 @@ impl OracleRegistryV2Contract {
             phase: state::VotingPhase::Commit,
             status: QueryStatus::Active,
             result: None,
+            result_display: None,
             resolved_at: None,
+            resolution_source: None,
             commits: std::collections::BTreeMap::new(),
             votes: std::collections::BTreeMap::new(),
             selected_voters,
             max_voters,
-            callback_chain: None,  // No callback for manual queries
-            callback_data: None,   // No callback for manual queries
+            callback_chain: Some(callback_chain),
+            callback_data: Some(callback_data),
         };
         
-        // Clone data for event before moving into state
-        let description_for_event = query.description.clone();
-        let outcomes_for_event = query.outcomes.clone();
-        
         // Store query
         self.state.queries.insert(&query_id, query).expect("Failed to insert query");
         
-        // Add to active queries
-        let mut active = self.state.get_active_queries().await;
-        active.push(query_id);
-        self.state.active_queries.set(active);
+        // Add to active queries
+        let mut active = self.state.get_active_queries().await;
+        active.push(query_id);
+        self.state.active_queries.set(active);
+        
+        // Initialize vote count
+        self.state.vote_counts.insert(&query_id, 0).expect("Failed to initialize vote count");
+        
+        // Update statistics
+        let total_created = *self.state.total_queries_created.get();
+        self.state.total_queries_created.set(state::increment_counter(total_created, "total_queries_created"));
+        
+        eprintln!("✅ Query {} created with callback to chain {} app {}", query_id, callback_chain, callback_app);
+        
+        OperationResponse::success_with_data(
+            format!("Query {} created with callback", query_id),
+            ResponseData {
+                voter_address: None,
+                query_id: Some(query_id),
+                vote_count: None,
+                rewards_claimed: None,
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
+            }
+        )
+    }
+    
+    /// Change the decision strategy of a query before voting starts (creator only)
+    async fn update_query_strategy(
+        &mut self,
+        query_id: u64,
+        strategy: state::DecisionStrategy,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let caller_chain = self.runtime.chain_id();
+        
+        // Validate query exists
+        let mut query = match self.validate_query_exists(query_id).await {
+            Ok(q) => q,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Only the creator, during commit phase, with no votes yet
+        let current_time = self.runtime.system_time();
+        if let Err(e) = query.check_strategy_update(&caller_chain, current_time) {
+            return OperationResponse::error(e);
+        }
+        
+        // Validate new strategy is compatible with outcomes
+        let numeric_outcomes = match self.validate_strategy_compatibility(&strategy, &query.outcomes) {
+            Ok(numeric) => numeric,
+            Err(e) => return OperationResponse::error(e),
+        };
+        if let Err(e) = state::validate_multi_winner(query.multi_winner, &strategy) {
+            return OperationResponse::error(e);
+        }
+        if let Err(e) = state::validate_tie_break(query.tie_break, &strategy) {
+            return OperationResponse::error(e);
+        }
+        
+        let old_strategy = query.strategy.as_str().to_string();
+        let new_strategy = strategy.as_str().to_string();
+        
+        query.strategy = strategy;
+        query.numeric_outcomes = numeric_outcomes;
+        self.state.queries.insert(&query_id, query).expect("Failed to update query");
+        
+        // Emit QueryStrategyUpdated event for cross-chain subscribers
+        self.emit_oracle_event(OracleEvent::QueryStrategyUpdated {
+            query_id,
+            old_strategy: old_strategy.clone(),
+            new_strategy: new_strategy.clone(),
+            updated_by: caller_chain,
+        });
+        
+        OperationResponse::success(format!(
+            "Query {} strategy changed from {} to {}",
+            query_id, old_strategy, new_strategy
+        ))
+    }
+    
+    /// Read a query's result for a synchronous cross-application call
+    /// 
+    /// Read-only; archived queries are served from their summary.
+    async fn get_query_result(&mut self, query_id: u64) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::{OperationResponse, QueryResultData, ResponseData};
+        
+        let query_result = if let Some(query) = self.state.get_query(query_id).await {
+            QueryResultData {
+                status: query.status.as_str().to_string(),
+                resolved: query.final_result().is_some(),
+                result: query.final_result().map(str::to_string),
+                resolved_at: query.resolved_at,
+            }
+        } else if let Ok(Some(archived)) = self.state.archived_queries.get(&query_id).await {
+            QueryResultData {
+                status: archived.status.as_str().to_string(),
+                resolved: archived.final_result().is_some(),
+                result: archived.final_result().map(str::to_string),
+                resolved_at: archived.resolved_at,
+            }
+        } else {
+            return OperationResponse::error(format!("Query {} not found", query_id));
+        };
+        
+        OperationResponse::success_with_data(
+            format!("Query {} is {}", query_id, query_result.status),
+            ResponseData {
+                voter_address: None,
+                query_id: Some(query_id),
+                vote_count: None,
+                rewards_claimed: None,
+                query_result: Some(query_result),
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
+            }
+        )
+    }
+    
+    /// Cancel an active query before any vote is revealed (creator or admin)
+    /// 
+    /// Committed voters get their locked stake back; no rewards or slashing.
+    async fn cancel_query(&mut self, query_id: u64) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let caller_chain = self.runtime.chain_id();
+        
+        // Validate query exists
+        let mut query = match self.validate_query_exists(query_id).await {
+            Ok(q) => q,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Only the creator or admin, while active and before any reveal
+        let caller_is_admin = self.state.is_admin(&caller_chain).await;
+        if let Err(e) = query.check_cancellable(&caller_chain, caller_is_admin) {
+            return OperationResponse::error(e);
+        }
+        
+        query.status = state::QueryStatus::Cancelled;
+        query.resolved_at = Some(self.runtime.system_time());
+        self.state.queries.insert(&query_id, query.clone()).expect("Failed to update query");
+        
+        // Release stake locked by committed voters (first batch; the rest via UnlockQueryStakes)
+        let (released_voters, _remaining) = self.unlock_query_stakes(&query, state::UNLOCK_BATCH_SIZE).await;
+        self.refund_creation_deposit(&query).await;
+        
+        // Remove from active queries
+        let mut active = self.state.get_active_queries().await;
+        active.retain(|&id| id != query_id);
+        self.state.active_queries.set(active);
+        
+        eprintln!("🚫 Query {} cancelled by {}, released stake for {} voter(s)", query_id, caller_chain, released_voters);
+        
+        self.emit_oracle_event(OracleEvent::QueryCancelled {
+            query_id,
+            cancelled_by: caller_chain,
+            released_voters,
+        });
+        
+        OperationResponse::success(format!(
+            "Query {} cancelled, released stake for {} voter(s)",
+            query_id, released_voters
+        ))
+    }
+    
+    /// Move a stuck query to its next voting phase (admin only)
+    /// 
+    /// Only transitions the phase; resolution is left to ResolveQuery or
+    /// maintenance.
+    async fn advance_phase(&mut self, query_id: u64) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let caller_chain = self.runtime.chain_id();
+        
+        // Verify caller is admin
+        if !self.state.is_admin(&caller_chain).await {
+            return OperationResponse::error("Unauthorized: only admin can advance a query's phase");
+        }
+        
+        // Validate query exists
+        let mut query = match self.validate_query_exists(query_id).await {
+            Ok(q) => q,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Validate the current time warrants the transition
+        let current_time = self.runtime.system_time();
+        let grace_secs = self.state.get_parameters().await.resolution_grace_secs;
+        let next_phase = match query.next_phase_at(current_time, grace_secs) {
+            Ok(phase) => phase,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        let from_phase = format!("{:?}", query.phase);
+        let to_phase = format!("{:?}", next_phase);
         
-        // Initialize vote count
-        self.state.vote_counts.insert(&query_id, 0).expect("Failed to initialize vote count");
+        query.phase = next_phase;
+        self.state.queries.insert(&query_id, query).expect("Failed to update query");
         
-        // Update statistics
-        let total_created = *self.state.total_queries_created.get();
-        self.state.total_queries_created.set(total_created + 1);
+        eprintln!("⏩ Query {} advanced from {} to {} by {}", query_id, from_phase, to_phase, caller_chain);
         
-        // Emit QueryCreated event for cross-chain subscribers
-        self.emit_oracle_event(OracleEvent::QueryCreated {
+        self.emit_oracle_event(OracleEvent::QueryPhaseAdvanced {
             query_id,
-            description: description_for_event,
-            outcomes: outcomes_for_event,
-            deadline: query_deadline,
-            creator,
-            min_votes: min_votes_required,
+            from_phase: from_phase.clone(),
+            to_phase: to_phase.clone(),
+            advanced_by: caller_chain,
         });
         
-        // Transfer reward amount to contract
-        // Note: In production, implement proper token transfer from creator
-        
-        OperationResponse::success_with_data(
-            format!("Query {} created successfully", query_id),
-            ResponseData {
-                voter_address: None,
-                query_id: Some(query_id),
-                vote_count: None,
-                rewards_claimed: None,
-            }
-        )
+        OperationResponse::success(format!(
+            "Query {} advanced from {} to {}",
+            query_id, from_phase, to_phase
+        ))
     }
     
-    /// Create a new query with callback information (for cross-application calls)
-    /// This allows other applications (like Simple Market) to create queries
-    /// and receive callbacks when the query is resolved
-    async fn create_query_with_callback(
+    /// Set a query's category and tags (creator only)
+    async fn set_query_metadata(
         &mut self,
-        description: String,
-        outcomes: Vec<String>,
-        strategy: state::DecisionStrategy,
-        min_votes: Option<usize>,
-        reward_amount: Amount,
-        deadline: Option<linera_sdk::linera_base_types::Timestamp>,
-        callback_chain: linera_sdk::linera_base_types::ChainId,
-        callback_app: linera_sdk::linera_base_types::ApplicationId,
-        callback_data: Vec<u8>,
+        query_id: u64,
+        category: Option<String>,
+        tags: Vec<String>,
     ) -> oracle_registry_v2::OperationResponse {
-        use oracle_registry_v2::{OperationResponse, ResponseData};
-        use state::{Query, QueryStatus};
+        use oracle_registry_v2::OperationResponse;
         
-        eprintln!(
-            "📥 CreateQueryWithCallback: description={}, callback_chain={}, callback_app={}",
-            description, callback_chain, callback_app
-        );
+        let caller_chain = self.runtime.chain_id();
         
-        let creator = self.runtime.chain_id();
+        // Validate query exists
+        let mut query = match self.validate_query_exists(query_id).await {
+            Ok(q) => q,
+            Err(e) => return OperationResponse::error(e),
+        };
         
-        // Validate query parameters
-        if let Err(e) = self.validate_query_params(&description, &outcomes, &reward_amount, &deadline) {
+        // Only the creator can change metadata
+        if query.creator != caller_chain {
+            return OperationResponse::error("Unauthorized: only the query creator can set its metadata");
+        }
+        
+        // Validate category and tags
+        if let Err(e) = state::validate_query_labels(&category, &tags) {
             return OperationResponse::error(e);
         }
         
-        // Get protocol parameters
-        let params = self.state.get_parameters().await;
+        query.category = category;
+        query.tags = tags;
+        self.state.queries.insert(&query_id, query).expect("Failed to update query");
         
-        // Determine min_votes (use provided or default)
-        let min_votes_required = min_votes.unwrap_or(params.min_votes_default);
+        OperationResponse::success(format!("Query {} metadata updated", query_id))
+    }
+    
+    /// Allow or forbid resolving an active query from external data (admin only)
+    async fn set_external_resolution(&mut self, query_id: u64, allowed: bool) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
         
-        // Validate min_votes is reasonable
-        let voter_count = *self.state.voter_count.get();
-        if let Err(e) = self.validate_min_votes_param(min_votes_required, voter_count) {
-            return OperationResponse::error(e);
+        let caller_chain = self.runtime.chain_id();
+        
+        // Verify caller is admin
+        if !self.state.is_admin(&caller_chain).await {
+            return OperationResponse::error("Unauthorized: only admin can designate queries for external resolution");
         }
         
-        // Validate strategy is compatible with outcomes
-        if let Err(e) = self.validate_strategy_compatibility(&strategy, &outcomes) {
-            return OperationResponse::error(e);
+        // Validate query exists and is still open
+        let mut query = match self.validate_query_exists(query_id).await {
+            Ok(q) => q,
+            Err(e) => return OperationResponse::error(e),
+        };
+        if query.status != state::QueryStatus::Active {
+            return OperationResponse::error(format!("Query {} is not active (status: {:?})", query_id, query.status));
         }
         
-        // Calculate commit/reveal phases (1 hour each for cross-app queries)
-        let current_time = self.runtime.system_time();
-        let commit_duration = linera_sdk::linera_base_types::TimeDelta::from_micros(1 * 60 * 60 * 1_000_000u64);
-        let reveal_duration = linera_sdk::linera_base_types::TimeDelta::from_micros(1 * 60 * 60 * 1_000_000u64);
+        query.allow_external_resolution = allowed;
+        self.state.queries.insert(&query_id, query).expect("Failed to update query");
         
-        let commit_phase_end = current_time.saturating_add(commit_duration);
-        let reveal_phase_end = commit_phase_end.saturating_add(reveal_duration);
+        OperationResponse::success(if allowed {
+            format!("Query {} may be resolved from external data", query_id)
+        } else {
+            format!("Query {} is voting-only", query_id)
+        })
+    }
+    
+    /// Resolve a query from external data, bypassing voting (admin only)
+    /// 
+    /// The query must have been designated with SetExternalResolution. Voters
+    /// who committed or voted get their locked stake back without rewards or
+    /// slashing, and the creator's deposit is refunded.
+    async fn resolve_with_external_data(
+        &mut self,
+        query_id: u64,
+        outcome: String,
+        source: String,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::{OperationResponse, ResponseData};
         
-        // Determine final deadline (use provided or calculated reveal_phase_end)
-        let query_deadline = deadline.unwrap_or(reveal_phase_end);
+        let caller_chain = self.runtime.chain_id();
         
-        // Get next query ID
-        let query_id = *self.state.next_query_id.get();
-        self.state.next_query_id.set(query_id + 1);
+        // Verify caller is admin
+        if !self.state.is_admin(&caller_chain).await {
+            return OperationResponse::error("Unauthorized: only admin can resolve queries from external data");
+        }
         
-        // Determine max_voters (2x min_votes to allow for non-participation)
-        let max_voters = min_votes_required * 2;
+        // Validate the source reference
+        if let Err(e) = state::validate_resolution_source(&source) {
+            return OperationResponse::error(e);
+        }
         
-        // SELECT VOTERS BY POWER
-        let selected_voters = match self.state
-            .select_voters_for_query(min_votes_required, max_voters)
-            .await
-        {
-            Ok(voters) => voters,
-            Err(e) => return OperationResponse::error(format!(
-                "Failed to select voters: {}", e
-            )),
+        // Validate query exists, is designated and the outcome is valid
+        let mut query = match self.validate_query_exists(query_id).await {
+            Ok(q) => q,
+            Err(e) => return OperationResponse::error(e),
         };
+        if let Err(e) = query.check_external_resolution(&outcome) {
+            return OperationResponse::error(e);
+        }
         
-        // Create query with callback information
-        let query = Query {
-            id: query_id,
-            description,
-            outcomes,
-            strategy,
-            min_votes: min_votes_required,
-            reward_amount,
-            creator,
-            created_at: current_time,
-            deadline: query_deadline,
-            commit_phase_end,
-            reveal_phase_end,
-            phase: state::VotingPhase::Commit,
-            status: QueryStatus::Active,
-            result: None,
-            resolved_at: None,
-            commits: std::collections::BTreeMap::new(),
-            votes: std::collections::BTreeMap::new(),
-            selected_voters,
-            max_voters,
-            callback_chain: Some(callback_chain),
-            callback_data: Some(callback_data),
-        };
+        let current_time = self.runtime.system_time();
+        let result = state::ResolutionResult::Outcome(query.canonical_outcome(&outcome));
+        let result_display = result.to_string();
         
-        // Store query
-        self.state.queries.insert(&query_id, query).expect("Failed to insert query");
+        // Update query status
+        query.status = state::QueryStatus::Resolved;
+        query.result = Some(result);
+        query.result_display = Some(result_display.clone());
+        query.resolved_at = Some(current_time);
+        query.resolution_source = Some(source.clone());
+        query.phase = state::VotingPhase::Completed;
+        self.state.queries.insert(&query_id, query.clone()).expect("Failed to update query");
         
-        // Add to active queries
+        // Reconcile stored vote count with the query's votes
+        if let Err(e) = self.state.reconcile_vote_counts(query_id).await {
+            eprintln!("Warning: Failed to reconcile vote count for query {}: {}", query_id, e);
+        }
+        
+        // Release stake locked by voters (first batch; the rest via UnlockQueryStakes)
+        let (released_voters, _remaining) = self.unlock_query_stakes(&query, state::UNLOCK_BATCH_SIZE).await;
+        self.refund_creation_deposit(&query).await;
+        
+        // Remove from active queries
         let mut active = self.state.get_active_queries().await;
-        active.push(query_id);
+        active.retain(|&id| id != query_id);
         self.state.active_queries.set(active);
         
-        // Initialize vote count
-        self.state.vote_counts.insert(&query_id, 0).expect("Failed to initialize vote count");
-        
         // Update statistics
-        let total_created = *self.state.total_queries_created.get();
-        self.state.total_queries_created.set(total_created + 1);
+        let total_resolved = *self.state.total_queries_resolved.get();
+        self.state.total_queries_resolved.set(state::increment_counter(total_resolved, "total_queries_resolved"));
         
-        eprintln!("✅ Query {} created with callback to chain {} app {}", query_id, callback_chain, callback_app);
+        // Send callback to requesting chain if callback info exists
+        if query.callback_chain.is_some() {
+            if let Err(e) = self.send_resolution_callback(&query) {
+                eprintln!("Warning: {}", e);
+            }
+        }
+        
+        self.emit_oracle_event(OracleEvent::QueryResolvedExternally {
+            query_id,
+            result: result_display.clone(),
+            source: source.clone(),
+            resolved_at: current_time,
+        });
         
         OperationResponse::success_with_data(
-            format!("Query {} created with callback", query_id),
+            format!(
+                "Query {} resolved from external data with result: {} (source: {}), released stake for {} voter(s)",
+                query_id, result_display, source, released_voters
+            ),
             ResponseData {
                 voter_address: None,
                 query_id: Some(query_id),
-                vote_count: None,
+                vote_count: Some(query.votes.len()),
                 rewards_claimed: None,
-            }
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
+            },
         )
     }
     
+    /// Validate there is room under the active query cap for a new query
+    async fn validate_active_query_capacity(&self) -> Result<(), String> {
+        let active_count = self.state.get_active_queries().await.len();
+        self.state.get_parameters().await.check_active_query_capacity(active_count)
+    }
+    
     /// Validate query creation parameters
     fn validate_query_params(
         &mut self,
@@ -2044,25 +3721,48 @@ impl OracleRegistryV2Contract {
     }
     
     /// Validate voter has not already voted on query
-    fn validate_voter_not_voted(&self, query: &state::Query, voter_chain: &linera_sdk::linera_base_types::ChainId) -> Result<(), String> {
-        if query.votes.contains_key(voter_chain) {
-            return Err(format!(
-                "Voter {} has already voted on query {}",
-                voter_chain, query.id
-            ));
+    /// 
+    /// With `allow_vote_change_until_deadline` a direct vote may be replaced;
+    /// returns whether this vote is such a change.
+    fn validate_voter_not_voted(&self, query: &state::Query, voter_chain: &linera_sdk::linera_base_types::ChainId) -> Result<bool, String> {
+        let allow_vote_change = self.state.parameters.get().allow_vote_change_until_deadline;
+        query.check_direct_vote(voter_chain, allow_vote_change)
+    }
+    
+    /// Lock stake for a direct vote, replacing the lock of a changed vote
+    /// 
+    /// The previous lock is released first so the stake is not locked twice,
+    /// and restored if the new lock fails.
+    async fn lock_stake_for_vote(
+        &mut self,
+        query_id: u64,
+        voter_chain: &linera_sdk::linera_base_types::ChainId,
+        stake_to_lock: Amount,
+        is_change: bool,
+    ) -> Result<(), String> {
+        let previous_lock = if is_change {
+            self.state.take_stake_lock(query_id, voter_chain).await
+        } else {
+            None
+        };
+        if let Some(previous) = previous_lock {
+            self.state.unlock_stake(voter_chain, previous).await?;
         }
-        Ok(())
+        
+        if let Err(e) = self.state.lock_stake(voter_chain, stake_to_lock).await {
+            if let Some(previous) = previous_lock {
+                if self.state.lock_stake(voter_chain, previous).await.is_ok() {
+                    let _ = self.state.record_stake_lock(query_id, voter_chain, previous);
+                }
+            }
+            return Err(format!("Failed to lock stake: {}", e));
+        }
+        self.state.record_stake_lock(query_id, voter_chain, stake_to_lock)
     }
     
     /// Validate vote value is a valid outcome
     fn validate_vote_value(&self, query: &state::Query, value: &str) -> Result<(), String> {
-        if !query.outcomes.contains(&value.to_string()) {
-            return Err(format!(
-                "Invalid vote value '{}' for query {}. Valid outcomes: {}",
-                value, query.id, query.outcomes.join(", ")
-            ));
-        }
-        Ok(())
+        query.check_vote_value(value)
     }
     
     /// Validate confidence score is within valid range
@@ -2095,12 +3795,60 @@ impl OracleRegistryV2Contract {
         // Check deadline has passed
         self.validate_query_deadline_passed(query)?;
         
+        // Check reveal grace window has passed
+        let grace_secs = self.state.get_parameters().await.resolution_grace_secs;
+        query.check_resolution_window(self.runtime.system_time(), grace_secs)?;
+        
         // Check minimum votes met
         self.validate_minimum_votes_met(query)?;
         
         Ok(())
     }
     
+    /// Validate min_distinct_voted_outcomes parameter
+    fn validate_min_distinct_param(&self, min_distinct: usize, outcome_count: usize) -> Result<(), String> {
+        if min_distinct == 0 {
+            return Err("Minimum distinct voted outcomes must be at least 1".to_string());
+        }
+        
+        if min_distinct > outcome_count {
+            return Err(format!(
+                "Minimum distinct voted outcomes ({}) exceeds number of outcomes ({})",
+                min_distinct, outcome_count
+            ));
+        }
+        
+        Ok(())
+    }
+    
+    /// Validate min_voter_stake parameter
+    fn validate_min_voter_stake_param(&self, min_voter_stake: Amount, min_stake: Amount) -> Result<(), String> {
+        if min_voter_stake < min_stake {
+            return Err(format!(
+                "Minimum voter stake ({}) is below the registration minimum stake ({})",
+                min_voter_stake, min_stake
+            ));
+        }
+        
+        Ok(())
+    }
+    
+    /// Validate min_votes_for_winner parameter
+    fn validate_min_votes_for_winner_param(&self, min_winner: usize, max_voters: usize) -> Result<(), String> {
+        if min_winner == 0 {
+            return Err("Minimum votes for winner must be at least 1".to_string());
+        }
+        
+        if min_winner > max_voters {
+            return Err(format!(
+                "Minimum votes for winner ({}) exceeds number of selected voters ({})",
+                min_winner, max_voters
+            ));
+        }
+        
+        Ok(())
+    }
+    
     /// Validate min_votes parameter is reasonable
     fn validate_min_votes_param(&self, min_votes: usize, voter_count: u64) -> Result<(), String> {
         if min_votes == 0 {
@@ -2127,24 +3875,10 @@ impl OracleRegistryV2Contract {
     }
     
     /// Validate decision strategy is compatible with outcomes
-    fn validate_strategy_compatibility(&self, strategy: &state::DecisionStrategy, outcomes: &[String]) -> Result<(), String> {
-        match strategy {
-            state::DecisionStrategy::Median => {
-                // Median strategy requires numeric outcomes
-                for outcome in outcomes {
-                    if outcome.parse::<f64>().is_err() {
-                        return Err(format!(
-                            "Median strategy requires numeric outcomes, but '{}' is not numeric",
-                            outcome
-                        ));
-                    }
-                }
-            },
-            _ => {
-                // Other strategies work with any outcomes
-            }
-        }
-        Ok(())
+    /// 
+    /// Returns the outcomes parsed as numbers for median strategies.
+    fn validate_strategy_compatibility(&self, strategy: &state::DecisionStrategy, outcomes: &[String]) -> Result<Option<Vec<f64>>, String> {
+        strategy.parse_numeric_outcomes(outcomes)
     }
     
     /// Submit a vote for a query
@@ -2165,6 +3899,12 @@ impl OracleRegistryV2Contract {
             Err(e) => return OperationResponse::error(e),
         };
         
+        // Enforce the minimum gap since the voter's last vote
+        let min_vote_interval_secs = self.state.get_parameters().await.min_vote_interval_secs;
+        if let Err(e) = voter_info.check_vote_interval(self.runtime.system_time(), min_vote_interval_secs) {
+            return OperationResponse::error(e);
+        }
+        
         // Validate query exists
         let mut query = match self.validate_query_exists(query_id).await {
             Ok(q) => q,
@@ -2176,6 +3916,11 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(e);
         }
         
+        // Validate voter meets the query's stake floor
+        if let Err(e) = query.check_voter_stake(voter_info.stake) {
+            return OperationResponse::error(e);
+        }
+        
         // CHECK IF VOTER IS SELECTED FOR THIS QUERY
         // TEMPORARY: Disabled - all registered voters can vote
         // if !query.selected_voters.contains(&voter_chain) {
@@ -2188,7 +3933,7 @@ impl OracleRegistryV2Contract {
         // }
         
         // Check if query has expired (deadline passed)
-        if self.is_query_expired(&query) {
+        if self.is_query_expired(&query).await {
             // Automatically mark as expired
             if let Err(e) = self.mark_query_expired(query_id).await {
                 eprintln!("Warning: Failed to auto-expire query {}: {}", query_id, e);
@@ -2204,10 +3949,14 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(e);
         }
         
-        // Validate voter hasn't already voted
-        if let Err(e) = self.validate_voter_not_voted(&query, &voter_chain) {
-            return OperationResponse::error(e);
-        }
+        // Validate voter hasn't already voted (or may change their vote)
+        let is_change = match self.validate_voter_not_voted(&query, &voter_chain) {
+            Ok(is_change) => is_change,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Accept the creator's synonyms for outcomes
+        let value = query.canonical_outcome(&value);
         
         // Validate vote value is valid
         if let Err(e) = self.validate_vote_value(&query, &value) {
@@ -2223,9 +3972,9 @@ impl OracleRegistryV2Contract {
         let params = self.state.get_parameters().await;
         let stake_to_lock = self.calculate_stake_to_lock(&voter_info, &query, &params);
         
-        // Lock stake for this vote
-        if let Err(e) = self.state.lock_stake(&voter_chain, stake_to_lock).await {
-            return OperationResponse::error(format!("Failed to lock stake: {}", e));
+        // Lock stake for this vote (a changed vote replaces its previous lock)
+        if let Err(e) = self.lock_stake_for_vote(query_id, &voter_chain, stake_to_lock, is_change).await {
+            return OperationResponse::error(e);
         }
         
         // Create vote
@@ -2237,23 +3986,29 @@ impl OracleRegistryV2Contract {
             confidence,
         };
         
-        // Store vote
+        // Store vote (overwrites a changed vote)
         query.votes.insert(voter_chain, vote.clone());
         self.state.queries.insert(&query_id, query).expect("Failed to update query");
         self.state.votes.insert(&(query_id, voter_chain), vote).expect("Failed to store vote");
         
-        // Update vote count
-        let current_count = self.state.vote_counts.get(&query_id).await.ok().flatten().unwrap_or(0);
-        self.state.vote_counts.insert(&query_id, current_count + 1).expect("Failed to update vote count");
-        
-        // Update voter stats
         let mut updated_voter_info = self.state.get_voter(&voter_chain).await.expect("Voter should exist");
-        updated_voter_info.total_votes += 1;
-        self.state.voters.insert(&voter_chain, updated_voter_info).expect("Failed to update voter");
-        
-        // Update total votes submitted
-        let total_votes = *self.state.total_votes_submitted.get();
-        self.state.total_votes_submitted.set(total_votes + 1);
+        if is_change {
+            // A changed vote is not counted again
+            updated_voter_info.record_vote_change(self.runtime.system_time());
+            self.state.save_voter(&voter_chain, updated_voter_info).await.expect("Failed to update voter");
+        } else {
+            // Update vote count
+            let current_count = self.state.vote_counts.get(&query_id).await.ok().flatten().unwrap_or(0);
+            self.state.vote_counts.insert(&query_id, current_count.saturating_add(1)).expect("Failed to update vote count");
+            
+            // Update voter stats
+            updated_voter_info.record_vote(self.runtime.system_time());
+            self.state.save_voter(&voter_chain, updated_voter_info).await.expect("Failed to update voter");
+            
+            // Update total votes submitted
+            let total_votes = *self.state.total_votes_submitted.get();
+            self.state.total_votes_submitted.set(state::increment_counter(total_votes, "total_votes_submitted"));
+        }
         
         // Emit VoteSubmitted event for cross-chain subscribers
         self.emit_oracle_event(OracleEvent::VoteSubmitted {
@@ -2262,7 +4017,8 @@ impl OracleRegistryV2Contract {
             value,
         });
         
-        OperationResponse::success(format!("Vote submitted successfully, {} stake locked", stake_to_lock))
+        let action = if is_change { "changed" } else { "submitted" };
+        OperationResponse::success(format!("Vote {} successfully, {} stake locked", action, stake_to_lock))
     }
     
     /// Submit a vote from cross-chain message (uses sender_chain as voter ID)
@@ -2288,6 +4044,12 @@ impl OracleRegistryV2Contract {
             Err(e) => return OperationResponse::error(e),
         };
         
+        // Enforce the minimum gap since the voter's last vote
+        let min_vote_interval_secs = self.state.get_parameters().await.min_vote_interval_secs;
+        if let Err(e) = voter_info.check_vote_interval(self.runtime.system_time(), min_vote_interval_secs) {
+            return OperationResponse::error(e);
+        }
+        
         // Validate query exists
         let mut query = match self.validate_query_exists(query_id).await {
             Ok(q) => q,
@@ -2299,6 +4061,11 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(e);
         }
         
+        // Validate voter meets the query's stake floor
+        if let Err(e) = query.check_voter_stake(voter_info.stake) {
+            return OperationResponse::error(e);
+        }
+        
         // CHECK IF VOTER IS SELECTED FOR THIS QUERY
         // TEMPORARY: Disabled - all registered voters can vote
         // if !query.selected_voters.contains(&voter_chain) {
@@ -2310,7 +4077,7 @@ impl OracleRegistryV2Contract {
         // }
         
         // Check if query has expired (deadline passed)
-        if self.is_query_expired(&query) {
+        if self.is_query_expired(&query).await {
             return OperationResponse::error(format!(
                 "Query {} has expired (deadline passed: {:?})",
                 query_id, query.deadline
@@ -2322,10 +4089,14 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(e);
         }
         
-        // Validate voter hasn't already voted
-        if let Err(e) = self.validate_voter_not_voted(&query, &voter_chain) {
-            return OperationResponse::error(e);
-        }
+        // Validate voter hasn't already voted (or may change their vote)
+        let is_change = match self.validate_voter_not_voted(&query, &voter_chain) {
+            Ok(is_change) => is_change,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Accept the creator's synonyms for outcomes
+        let value = query.canonical_outcome(&value);
         
         // Validate vote value is valid
         if let Err(e) = self.validate_vote_value(&query, &value) {
@@ -2341,9 +4112,9 @@ impl OracleRegistryV2Contract {
         let params = self.state.get_parameters().await;
         let stake_to_lock = self.calculate_stake_to_lock(&voter_info, &query, &params);
         
-        // Lock stake for this vote
-        if let Err(e) = self.state.lock_stake(&voter_chain, stake_to_lock).await {
-            return OperationResponse::error(format!("Failed to lock stake: {}", e));
+        // Lock stake for this vote (a changed vote replaces its previous lock)
+        if let Err(e) = self.lock_stake_for_vote(query_id, &voter_chain, stake_to_lock, is_change).await {
+            return OperationResponse::error(e);
         }
         
         // Create vote
@@ -2355,23 +4126,29 @@ impl OracleRegistryV2Contract {
             confidence,
         };
         
-        // Store vote
+        // Store vote (overwrites a changed vote)
         query.votes.insert(voter_chain, vote.clone());
         self.state.queries.insert(&query_id, query).expect("Failed to update query");
         self.state.votes.insert(&(query_id, voter_chain), vote).expect("Failed to store vote");
         
-        // Update vote count
-        let current_count = self.state.vote_counts.get(&query_id).await.ok().flatten().unwrap_or(0);
-        self.state.vote_counts.insert(&query_id, current_count + 1).expect("Failed to update vote count");
-        
-        // Update voter stats
         let mut updated_voter_info = self.state.get_voter(&voter_chain).await.expect("Voter should exist");
-        updated_voter_info.total_votes += 1;
-        self.state.voters.insert(&voter_chain, updated_voter_info).expect("Failed to update voter");
-        
-        // Update total votes submitted
-        let total_votes = *self.state.total_votes_submitted.get();
-        self.state.total_votes_submitted.set(total_votes + 1);
+        if is_change {
+            // A changed vote is not counted again
+            updated_voter_info.record_vote_change(self.runtime.system_time());
+            self.state.save_voter(&voter_chain, updated_voter_info).await.expect("Failed to update voter");
+        } else {
+            // Update vote count
+            let current_count = self.state.vote_counts.get(&query_id).await.ok().flatten().unwrap_or(0);
+            self.state.vote_counts.insert(&query_id, current_count.saturating_add(1)).expect("Failed to update vote count");
+            
+            // Update voter stats
+            updated_voter_info.record_vote(self.runtime.system_time());
+            self.state.save_voter(&voter_chain, updated_voter_info).await.expect("Failed to update voter");
+            
+            // Update total votes submitted
+            let total_votes = *self.state.total_votes_submitted.get();
+            self.state.total_votes_submitted.set(state::increment_counter(total_votes, "total_votes_submitted"));
+        }
         
         // Emit VoteSubmitted event for cross-chain subscribers
         self.emit_oracle_event(OracleEvent::VoteSubmitted {
@@ -2382,9 +4159,10 @@ impl OracleRegistryV2Contract {
         
         eprintln!("✅ Vote from {} on query {} recorded successfully", voter_chain, query_id);
         
+        let action = if is_change { "changed" } else { "submitted" };
         OperationResponse::success(format!(
-            "Vote submitted successfully via cross-chain message, {} stake locked",
-            stake_to_lock
+            "Vote {} successfully via cross-chain message, {} stake locked",
+            action, stake_to_lock
         ))
     }
     
@@ -2409,6 +4187,12 @@ impl OracleRegistryV2Contract {
             Err(e) => return OperationResponse::error(e),
         };
         
+        // Enforce the minimum gap since the voter's last vote
+        let min_vote_interval_secs = self.state.get_parameters().await.min_vote_interval_secs;
+        if let Err(e) = voter_info.check_vote_interval(self.runtime.system_time(), min_vote_interval_secs) {
+            return OperationResponse::error(e);
+        }
+        
         // Validate query exists
         let mut query = match self.validate_query_exists(query_id).await {
             Ok(q) => q,
@@ -2420,6 +4204,11 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(e);
         }
         
+        // Validate voter meets the query's stake floor
+        if let Err(e) = query.check_voter_stake(voter_info.stake) {
+            return OperationResponse::error(e);
+        }
+        
         // Check if voter is selected for this query
         // TEMPORARY: Disabled - all registered voters can vote
         // if !query.selected_voters.contains(&voter_chain) {
@@ -2455,6 +4244,11 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error("Invalid commit hash format");
         }
         
+        // Reject a hash this voter already used on a still-open query
+        if let Err(e) = self.state.check_commit_hash_unused(&voter_chain, query_id, &commit_hash, current_time).await {
+            return OperationResponse::error(e);
+        }
+        
         // Calculate stake to lock
         let params = self.state.get_parameters().await;
         let stake_to_lock = self.calculate_stake_to_lock(&voter_info, &query, &params);
@@ -2463,6 +4257,9 @@ impl OracleRegistryV2Contract {
         if let Err(e) = self.state.lock_stake(&voter_chain, stake_to_lock).await {
             return OperationResponse::error(format!("Failed to lock stake: {}", e));
         }
+        if let Err(e) = self.state.record_stake_lock(query_id, &voter_chain, stake_to_lock) {
+            return OperationResponse::error(e);
+        }
         
         // Create commit
         let commit = VoteCommit {
@@ -2473,13 +4270,17 @@ impl OracleRegistryV2Contract {
         };
         
         let commit_phase_end = query.commit_phase_end;
+        let commit_use = state::CommitHashUse { query_id, deadline: query.deadline };
         query.commits.insert(voter_chain, commit);
         self.state.queries.insert(&query_id, query).expect("Failed to update query");
+        if let Err(e) = self.state.record_commit_hash(&voter_chain, &commit_hash, commit_use) {
+            eprintln!("Warning: {}", e);
+        }
         
         // Update voter stats
         let mut updated_voter_info = self.state.get_voter(&voter_chain).await.expect("Voter should exist");
-        updated_voter_info.total_votes += 1;
-        self.state.voters.insert(&voter_chain, updated_voter_info).expect("Failed to update voter");
+        updated_voter_info.record_vote(self.runtime.system_time());
+        self.state.save_voter(&voter_chain, updated_voter_info).await.expect("Failed to update voter");
         
         // Emit VoteCommitted event
         self.emit_oracle_event(OracleEvent::VoteCommitted {
@@ -2544,8 +4345,9 @@ impl OracleRegistryV2Contract {
             }
         }
         
-        // Check if reveal phase has ended
-        if current_time > query.reveal_phase_end {
+        // Check if reveal phase (including grace window) has ended
+        let grace_secs = self.state.get_parameters().await.resolution_grace_secs;
+        if !query.accepts_reveal_at(current_time, grace_secs) {
             query.phase = state::VotingPhase::Completed;
             self.state.queries.insert(&query_id, query.clone()).expect("Failed to update query");
             return OperationResponse::error("Reveal phase has ended");
@@ -2562,8 +4364,11 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error("Voter has already revealed their vote");
         }
         
+        // Accept the creator's synonyms for outcomes (the commit hash covers the value as sent)
+        let canonical = query.canonical_outcome(&value);
+        
         // Validate vote value is valid
-        if let Err(e) = self.validate_vote_value(&query, &value) {
+        if let Err(e) = self.validate_vote_value(&query, &canonical) {
             return OperationResponse::error(e);
         }
         
@@ -2583,12 +4388,19 @@ impl OracleRegistryV2Contract {
         // Create vote
         let vote = Vote {
             voter: voter_chain,
-            value: value.clone(),
+            value: canonical.clone(),
             timestamp: current_time,
             salt: Some(salt),
             confidence,
         };
         
+        // Reveals landing in the grace window count at reduced weight
+        let late_weight_bps = if query.is_late_reveal(&vote) {
+            Some(self.state.get_parameters().await.late_reveal_weight_bps)
+        } else {
+            None
+        };
+        
         // Store vote
         query.votes.insert(voter_chain, vote.clone());
         
@@ -2602,22 +4414,32 @@ impl OracleRegistryV2Contract {
         
         // Update vote count
         let current_count = self.state.vote_counts.get(&query_id).await.ok().flatten().unwrap_or(0);
-        self.state.vote_counts.insert(&query_id, current_count + 1).expect("Failed to update vote count");
+        self.state.vote_counts.insert(&query_id, current_count.saturating_add(1)).expect("Failed to update vote count");
         
         // Update total votes submitted
         let total_votes = *self.state.total_votes_submitted.get();
-        self.state.total_votes_submitted.set(total_votes + 1);
+        self.state.total_votes_submitted.set(state::increment_counter(total_votes, "total_votes_submitted"));
+        
+        // Record voter activity
+        let now = self.runtime.system_time();
+        self.state.mark_voter_active(&voter_chain, now).await;
         
         // Emit VoteRevealed event
         self.emit_oracle_event(OracleEvent::VoteRevealed {
             query_id,
             voter_chain,
-            value: value.clone(),
+            value: canonical.clone(),
         });
         
-        eprintln!("✅ Vote revealed from {} on query {}: {}", voter_chain, query_id, value);
+        eprintln!("✅ Vote revealed from {} on query {}: {}", voter_chain, query_id, canonical);
         
-        OperationResponse::success("Vote revealed successfully via cross-chain message")
+        match late_weight_bps {
+            Some(weight_bps) => OperationResponse::success(format!(
+                "Vote revealed late via cross-chain message; it counts at {} basis points of weight",
+                weight_bps
+            )),
+            None => OperationResponse::success("Vote revealed successfully via cross-chain message"),
+        }
     }
     
     /// Commit a vote (phase 1 of commit/reveal)
@@ -2637,6 +4459,12 @@ impl OracleRegistryV2Contract {
             Err(e) => return OperationResponse::error(e),
         };
         
+        // Enforce the minimum gap since the voter's last vote
+        let min_vote_interval_secs = self.state.get_parameters().await.min_vote_interval_secs;
+        if let Err(e) = voter_info.check_vote_interval(self.runtime.system_time(), min_vote_interval_secs) {
+            return OperationResponse::error(e);
+        }
+        
         // Validate query exists
         let mut query = match self.validate_query_exists(query_id).await {
             Ok(q) => q,
@@ -2648,6 +4476,11 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(e);
         }
         
+        // Validate voter meets the query's stake floor
+        if let Err(e) = query.check_voter_stake(voter_info.stake) {
+            return OperationResponse::error(e);
+        }
+        
         // Check if voter is selected for this query
         // TEMPORARY: Disabled - all registered voters can vote
         // if !query.selected_voters.contains(&voter_chain) {
@@ -2683,6 +4516,11 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error("Invalid commit hash format");
         }
         
+        // Reject a hash this voter already used on a still-open query
+        if let Err(e) = self.state.check_commit_hash_unused(&voter_chain, query_id, &commit_hash, current_time).await {
+            return OperationResponse::error(e);
+        }
+        
         // Calculate stake to lock
         let params = self.state.get_parameters().await;
         let stake_to_lock = self.calculate_stake_to_lock(&voter_info, &query, &params);
@@ -2691,6 +4529,9 @@ impl OracleRegistryV2Contract {
         if let Err(e) = self.state.lock_stake(&voter_chain, stake_to_lock).await {
             return OperationResponse::error(format!("Failed to lock stake: {}", e));
         }
+        if let Err(e) = self.state.record_stake_lock(query_id, &voter_chain, stake_to_lock) {
+            return OperationResponse::error(e);
+        }
         
         // Create commit
         let commit = VoteCommit {
@@ -2702,13 +4543,17 @@ impl OracleRegistryV2Contract {
         
         // Store commit and get commit_phase_end before moving query
         let commit_phase_end = query.commit_phase_end;
+        let commit_use = state::CommitHashUse { query_id, deadline: query.deadline };
         query.commits.insert(voter_chain, commit);
         self.state.queries.insert(&query_id, query).expect("Failed to update query");
+        if let Err(e) = self.state.record_commit_hash(&voter_chain, &commit_hash, commit_use) {
+            eprintln!("Warning: {}", e);
+        }
         
         // Update voter stats - increment total_votes on commit
         let mut updated_voter_info = self.state.get_voter(&voter_chain).await.expect("Voter should exist");
-        updated_voter_info.total_votes += 1;
-        self.state.voters.insert(&voter_chain, updated_voter_info).expect("Failed to update voter");
+        updated_voter_info.record_vote(self.runtime.system_time());
+        self.state.save_voter(&voter_chain, updated_voter_info).await.expect("Failed to update voter");
         
         // Emit VoteCommitted event for cross-chain subscribers
         self.emit_oracle_event(OracleEvent::VoteCommitted {
@@ -2768,8 +4613,9 @@ impl OracleRegistryV2Contract {
             }
         }
         
-        // Check if reveal phase has ended
-        if current_time > query.reveal_phase_end {
+        // Check if reveal phase (including grace window) has ended
+        let grace_secs = self.state.get_parameters().await.resolution_grace_secs;
+        if !query.accepts_reveal_at(current_time, grace_secs) {
             // Auto-transition to completed
             query.phase = state::VotingPhase::Completed;
             self.state.queries.insert(&query_id, query.clone()).expect("Failed to update query");
@@ -2787,8 +4633,11 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error("You have already revealed your vote");
         }
         
+        // Accept the creator's synonyms for outcomes (the commit hash covers the value as sent)
+        let canonical = query.canonical_outcome(&value);
+        
         // Validate vote value is valid
-        if let Err(e) = self.validate_vote_value(&query, &value) {
+        if let Err(e) = self.validate_vote_value(&query, &canonical) {
             return OperationResponse::error(e);
         }
         
@@ -2808,12 +4657,19 @@ impl OracleRegistryV2Contract {
         // Create vote
         let vote = Vote {
             voter: voter_chain,
-            value: value.clone(),
+            value: canonical.clone(),
             timestamp: current_time,
             salt: Some(salt),
             confidence,
         };
         
+        // Reveals landing in the grace window count at reduced weight
+        let late_weight_bps = if query.is_late_reveal(&vote) {
+            Some(self.state.get_parameters().await.late_reveal_weight_bps)
+        } else {
+            None
+        };
+        
         // Store vote
         query.votes.insert(voter_chain, vote.clone());
         
@@ -2827,61 +4683,50 @@ impl OracleRegistryV2Contract {
         
         // Update vote count
         let current_count = self.state.vote_counts.get(&query_id).await.ok().flatten().unwrap_or(0);
-        self.state.vote_counts.insert(&query_id, current_count + 1).expect("Failed to update vote count");
+        self.state.vote_counts.insert(&query_id, current_count.saturating_add(1)).expect("Failed to update vote count");
         
         // Note: total_votes is already incremented in commit_vote, no need to increment again here
         
         // Update total votes submitted
         let total_votes = *self.state.total_votes_submitted.get();
-        self.state.total_votes_submitted.set(total_votes + 1);
+        self.state.total_votes_submitted.set(state::increment_counter(total_votes, "total_votes_submitted"));
+        
+        // Record voter activity
+        let now = self.runtime.system_time();
+        self.state.mark_voter_active(&voter_chain, now).await;
         
         // Emit VoteRevealed event for cross-chain subscribers
         self.emit_oracle_event(OracleEvent::VoteRevealed {
             query_id,
             voter_chain,
-            value,
+            value: canonical,
         });
         
-        OperationResponse::success("Vote revealed successfully")
+        match late_weight_bps {
+            Some(weight_bps) => OperationResponse::success(format!(
+                "Vote revealed after the reveal phase; it counts at {} basis points of weight",
+                weight_bps
+            )),
+            None => OperationResponse::success("Vote revealed successfully"),
+        }
     }
     
-    /// Compute commit hash from value and salt
+    /// Compute commit hash from value and salt with the configured algorithm
     fn compute_commit_hash(&self, value: &str, salt: &str) -> String {
-        use sha2::{Sha256, Digest};
-        
-        let mut hasher = Sha256::new();
-        hasher.update(value.as_bytes());
-        hasher.update(salt.as_bytes());
-        let result = hasher.finalize();
-        
-        // Convert to hex string
-        format!("{:x}", result)
+        self.state.parameters.get().commit_hash_algo.compute(value, salt)
     }
     
     /// Calculate how much stake to lock for a vote
+    /// 
+    /// Uses the stake lock rule configured for the query's strategy (10% of
+    /// available stake, minimum 1 token, unless overridden).
     fn calculate_stake_to_lock(
         &self,
         voter_info: &state::VoterInfo,
-        _query: &state::Query,
-        _params: &state::ProtocolParameters,
+        query: &state::Query,
+        params: &state::ProtocolParameters,
     ) -> Amount {
-        // Lock 10% of voter's available stake for each vote
-        // This allows voters to participate in multiple queries simultaneously
-        // while still having skin in the game
-        
-        // Use Amount arithmetic directly - no conversion needed!
-        // saturating_sub and saturating_div work on Amount directly
-        let available_stake = voter_info.stake.saturating_sub(voter_info.locked_stake);
-        
-        // Lock 10% of available stake
-        // If 10% is zero (stake < 10), lock 1 token minimum
-        let ten_percent = available_stake.saturating_div(10);
-        
-        if ten_percent == Amount::ZERO {
-            Amount::from_tokens(1)  // Minimum 1 token
-        } else {
-            ten_percent  // Already an Amount, no conversion needed
-        }
+        params.stake_lock_rule(&query.strategy).stake_to_lock(voter_info)
     }
     
     /// Get voter reputation information
@@ -2911,20 +4756,191 @@ impl OracleRegistryV2Contract {
     
     // ==================== DEADLINE CHECKING ====================
     
-    /// Check if a query has expired (deadline passed but not resolved)
-    fn is_query_expired(&mut self, query: &state::Query) -> bool {
-        let current_time = self.runtime.system_time();
-        query.status == state::QueryStatus::Active && current_time >= query.deadline
-    }
-    
-    /// Check if a query deadline has passed
-    fn has_deadline_passed(&mut self, query: &state::Query) -> bool {
+    /// Check if a query has expired (deadline and reveal grace window passed but not resolved)
+    async fn is_query_expired(&mut self, query: &state::Query) -> bool {
         let current_time = self.runtime.system_time();
-        current_time >= query.deadline
+        let grace_secs = self.state.get_parameters().await.resolution_grace_secs;
+        query.status == state::QueryStatus::Active && query.may_expire_at(current_time, grace_secs)
     }
     
     /// Mark a query as expired and unlock stakes
     async fn mark_query_expired(&mut self, query_id: u64) -> Result<(), String> {
+        self.close_query_unresolved(query_id, state::ResolutionResult::Expired).await?;
+        
+        // Too few voters showed up: the creation deposit goes to the treasury
+        self.forfeit_creation_deposit(query_id).await;
+        Ok(())
+    }
+    
+    /// Transfer the creation deposit for a new query from the signer
+    /// 
+    /// Returns the deposit with the account that paid it, `None` when no
+    /// deposit is configured. Fails when the operation is unsigned or the
+    /// token transfer fails.
+    async fn collect_creation_deposit(&mut self) -> Result<Option<state::CreationDeposit>, String> {
+        let amount = self.state.get_parameters().await.query_creation_deposit;
+        if amount == Amount::ZERO {
+            return Ok(None);
+        }
+        
+        let owner = self.receive_tokens_from_signer(amount).await
+            .map_err(|e| format!("Failed to collect creation deposit: {}", e))?;
+        let payer = linera_sdk::linera_base_types::Account {
+            chain_id: self.runtime.chain_id(),
+            owner,
+        };
+        Ok(Some(state::CreationDeposit { payer, amount }))
+    }
+    
+    /// Hold a collected creation deposit until the query is finalized
+    fn hold_creation_deposit(&mut self, query_id: u64, deposit: Option<state::CreationDeposit>) {
+        let Some(deposit) = deposit else {
+            return;
+        };
+        
+        if let Err(e) = self.state.creation_deposits.insert(&query_id, deposit) {
+            eprintln!("Warning: Failed to record creation deposit for query {}: {}", query_id, e);
+        }
+    }
+    
+    /// Return a query's creation deposit to the account that paid it
+    /// 
+    /// Falls back to crediting the creator's pending rewards if the token
+    /// release fails, so the deposit is not lost.
+    async fn refund_creation_deposit(&mut self, query: &state::Query) {
+        let Some(deposit) = self.state.take_creation_deposit(query.id).await else {
+            return;
+        };
+        
+        let Err(e) = self.release_tokens_to_account(deposit.amount, deposit.payer).await else {
+            let total_held = *self.state.total_tokens_held.get();
+            self.state.total_tokens_held.set(total_held.saturating_sub(deposit.amount));
+            return;
+        };
+        eprintln!("Warning: Failed to refund creation deposit of query {} to {}: {}", query.id, deposit.payer, e);
+        let now = self.runtime.system_time();
+        if let Err(e) = self.state.credit_pending_rewards(&query.creator, deposit.amount, now).await {
+            eprintln!("Warning: Failed to credit creation deposit of query {} to {}: {}", query.id, query.creator, e);
+        }
+    }
+    
+    /// Move a query's creation deposit to the protocol treasury
+    async fn forfeit_creation_deposit(&mut self, query_id: u64) {
+        let Some(deposit) = self.state.take_creation_deposit(query_id).await else {
+            return;
+        };
+        
+        let current_treasury = *self.state.protocol_treasury.get();
+        self.state.protocol_treasury.set(current_treasury.saturating_add(deposit.amount));
+    }
+    
+    /// Unlock the stake committers and voters locked on a finalized query
+    /// 
+    /// Processes at most `max` lockers, continuing from the query's unlock
+    /// cursor. While lockers remain the cursor is kept in `pending_unlocks`
+    /// (unlocking in progress); it is cleared once all are done.
+    /// 
+    /// Uses the amount recorded at lock time, falling back to recomputing it
+    /// for locks taken before amounts were recorded. Returns the number of
+    /// voters whose stake was released and the number of lockers remaining.
+    async fn unlock_query_stakes(&mut self, query: &state::Query, max: usize) -> (usize, usize) {
+        let params = self.state.get_parameters().await;
+        let lockers = query.stake_lockers();
+        let cursor = self.state.get_unlock_cursor(query.id).await.unwrap_or(0);
+        let (batch, next_cursor) = state::next_unlock_batch(&lockers, cursor, max);
+        let mut released = 0;
+        
+        for voter in batch {
+            let locked_amount = match self.state.take_stake_lock(query.id, voter).await {
+                Some(amount) => amount,
+                None => match self.state.get_voter(voter).await {
+                    Some(voter_info) => self.calculate_stake_to_lock(&voter_info, query, &params),
+                    None => continue,
+                },
+            };
+            
+            // Log error but continue - don't fail the caller
+            match self.state.unlock_stake(voter, locked_amount).await {
+                Ok(()) => released += 1,
+                Err(e) => eprintln!("Warning: Failed to unlock stake for voter {} on query {}: {}",
+                                    voter, query.id, e),
+            }
+        }
+        
+        // Record progress so UnlockQueryStakes can pick up where this left off
+        let remaining = match next_cursor {
+            Some(next) => {
+                if let Err(e) = self.state.pending_unlocks.insert(&query.id, next) {
+                    eprintln!("Warning: Failed to record unlock progress for query {}: {}", query.id, e);
+                }
+                lockers.len() - next
+            }
+            None => {
+                if let Err(e) = self.state.pending_unlocks.remove(&query.id) {
+                    eprintln!("Warning: Failed to clear unlock progress for query {}: {}", query.id, e);
+                }
+                0
+            }
+        };
+        
+        (released, remaining)
+    }
+    
+    /// Continue unlocking stakes of a finalized query (anyone)
+    /// 
+    /// Unlocks up to `max` of the lockers left after the query was resolved,
+    /// expired or cancelled.
+    async fn unlock_query_stakes_operation(
+        &mut self,
+        query_id: u64,
+        max: u32,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        if max == 0 {
+            return OperationResponse::error("max must be at least 1");
+        }
+        
+        let query = match self.validate_query_exists(query_id).await {
+            Ok(q) => q,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        if query.status == state::QueryStatus::Active {
+            return OperationResponse::error(format!("Query {} is still active", query_id));
+        }
+        
+        // Without a cursor every lock was already released; don't unlock twice
+        if self.state.get_unlock_cursor(query_id).await.is_none() {
+            return OperationResponse::error(format!("No stake unlocking pending for query {}", query_id));
+        }
+        
+        let (released_voters, remaining) = self.unlock_query_stakes(&query, max as usize).await;
+        
+        eprintln!("🔓 Unlocked stake for {} voter(s) on query {}, {} remaining", released_voters, query_id, remaining);
+        
+        self.emit_oracle_event(OracleEvent::QueryStakesUnlocked {
+            query_id,
+            released_voters,
+            remaining,
+        });
+        
+        OperationResponse::success(format!(
+            "Unlocked stake for {} voter(s) on query {}, {} remaining",
+            released_voters, query_id, remaining
+        ))
+    }
+    
+    /// Close an active query past its deadline without resolving it
+    /// 
+    /// Sets status to Expired or NoConsensus to match the given result, and
+    /// unlocks voter stakes without rewards or slashing. A query closed
+    /// without consensus reports NO_CONSENSUS_OUTCOME to its callback chain.
+    async fn close_query_unresolved(
+        &mut self,
+        query_id: u64,
+        result: state::ResolutionResult,
+    ) -> Result<(), String> {
         let mut query = self.state.get_query(query_id).await
             .ok_or_else(|| format!("Query {} not found", query_id))?;
         
@@ -2933,29 +4949,42 @@ impl OracleRegistryV2Contract {
             return Err(format!("Query {} is not active (status: {:?})", query_id, query.status));
         }
         
-        // Check if deadline has actually passed
-        if !self.has_deadline_passed(&query) {
-            return Err(format!("Query {} deadline has not passed yet", query_id));
+        // Check that the deadline and the reveal grace window have passed
+        let grace_secs = self.state.get_parameters().await.resolution_grace_secs;
+        if !query.may_expire_at(self.runtime.system_time(), grace_secs) {
+            return Err(format!(
+                "Query {} deadline and reveal grace window have not passed yet", query_id
+            ));
         }
         
         // Update query status
-        query.status = state::QueryStatus::Expired;
+        let no_consensus = result == state::ResolutionResult::NoConsensus;
+        query.status = if no_consensus {
+            state::QueryStatus::NoConsensus
+        } else {
+            state::QueryStatus::Expired
+        };
+        query.result_display = Some(result.to_string());
+        query.result = Some(result);
         query.resolved_at = Some(self.runtime.system_time());
         self.state.queries.insert(&query_id, query.clone())
             .map_err(|e| format!("Failed to update query: {}", e))?;
         
-        // Unlock stake for all voters who participated
-        for (voter, _vote) in &query.votes {
-            if let Some(voter_info) = self.state.get_voter(voter).await {
-                let params = self.state.get_parameters().await;
-                let locked_amount = self.calculate_stake_to_lock(&voter_info, &query, &params);
-                
-                // Unlock the stake
-                if let Err(e) = self.state.unlock_stake(voter, locked_amount).await {
-                    eprintln!("Warning: Failed to unlock stake for voter {} on expired query {}: {}", 
-                             voter, query_id, e);
-                }
-            }
+        // Reconcile stored vote count with the query's votes
+        self.state.reconcile_vote_counts(query_id).await?;
+        
+        // Record which panel members took part
+        if let Err(e) = self.state.record_panel_participation(&query).await {
+            eprintln!("Warning: {}", e);
+        }
+        
+        // Unlock stake for voters who committed or voted (first batch; the rest via UnlockQueryStakes)
+        self.unlock_query_stakes(&query, state::UNLOCK_BATCH_SIZE).await;
+        
+        // Voters took part even without consensus, so the creator is refunded
+        // (expiry forfeits the deposit instead, see mark_query_expired)
+        if no_consensus {
+            self.refund_creation_deposit(&query).await;
         }
         
         // Remove from active queries
@@ -2963,17 +4992,55 @@ impl OracleRegistryV2Contract {
         active.retain(|&id| id != query_id);
         self.state.active_queries.set(active);
         
+        if no_consensus {
+            // Update statistics
+            let total_no_consensus = *self.state.total_queries_no_consensus.get();
+            self.state.total_queries_no_consensus.set(state::increment_counter(total_no_consensus, "total_queries_no_consensus"));
+            
+            // Let the requesting market tell disagreement apart from a missing result
+            if query.callback_chain.is_some() {
+                if let Err(e) = self.send_resolution_callback(&query) {
+                    eprintln!("Warning: {}", e);
+                }
+            }
+        }
+        
         Ok(())
     }
     
+    /// Whether a failed resolution closed the query without consensus
+    /// (rather than leaving it active)
+    async fn is_closed_without_consensus(&self, query_id: u64) -> bool {
+        self.state.get_query(query_id).await
+            .is_some_and(|query| query.status == state::QueryStatus::NoConsensus)
+    }
+    
+    /// Load a query listed in `active_queries` for a maintenance loop
+    /// 
+    /// Entries whose query is no longer Active (e.g. Cancelled) indicate a
+    /// missed removal: they are logged, pruned from the list and skipped.
+    async fn load_maintainable_query(&mut self, query_id: u64) -> Option<state::Query> {
+        let query = self.state.get_query(query_id).await?;
+        
+        if let Err(e) = query.check_listed_active() {
+            eprintln!("⚠️ {}; removing it", e);
+            let mut active = self.state.get_active_queries().await;
+            active.retain(|&id| id != query_id);
+            self.state.active_queries.set(active);
+            return None;
+        }
+        
+        Some(query)
+    }
+    
     /// Check and expire all queries that have passed their deadline
     async fn check_and_expire_queries(&mut self) -> Vec<u64> {
         let mut expired_query_ids = Vec::new();
         let active_queries = self.state.get_active_queries().await;
         
         for query_id in active_queries {
-            if let Some(query) = self.state.get_query(query_id).await {
-                if self.is_query_expired(&query) {
+            if let Some(query) = self.load_maintainable_query(query_id).await {
+                if self.is_query_expired(&query).await {
                     // Check if it has minimum votes - if yes, it should be resolved, not expired
                     if query.votes.len() >= query.min_votes {
                         // This query should be resolved, not expired
@@ -2999,8 +5066,8 @@ impl OracleRegistryV2Contract {
         let active_queries = self.state.get_active_queries().await;
         
         for query_id in active_queries {
-            if let Some(query) = self.state.get_query(query_id).await {
-                if self.is_query_expired(&query) {
+            if let Some(query) = self.load_maintainable_query(query_id).await {
+                if self.is_query_expired(&query).await {
                     expired_ids.push(query_id);
                 }
             }
@@ -3016,10 +5083,9 @@ impl OracleRegistryV2Contract {
         
         // Query should be expired if:
         // 1. It's currently active
-        // 2. Deadline has passed
+        // 2. Deadline and reveal grace window have passed
         // 3. It doesn't have enough votes to be resolved
-        Ok(query.status == state::QueryStatus::Active 
-           && self.has_deadline_passed(&query)
+        Ok(self.is_query_expired(&query).await
            && query.votes.len() < query.min_votes)
     }
     
@@ -3057,10 +5123,15 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(format!("Invalid query parameters: {}", e));
         }
         
+        // Keep the number of active queries under the governance cap
+        if let Err(e) = self.validate_active_query_capacity().await {
+            return OperationResponse::error(e);
+        }
+        
         // Get parameters
         let params = self.state.get_parameters().await;
         let current_time = self.runtime.system_time();
-        let min_votes_required = params.min_votes_default;
+        let min_votes_required = params.min_votes_for(&params.strategy_or_default(None));
         
         // Calculate commit/reveal phases (1 hour each for testing)
         let commit_duration = TimeDelta::from_micros(1 * 60 * 60 * 1_000_000u64);
@@ -3069,10 +5140,6 @@ impl OracleRegistryV2Contract {
         let commit_phase_end = current_time.saturating_add(commit_duration);
         let reveal_phase_end = commit_phase_end.saturating_add(reveal_duration);
         
-        // Get next query ID
-        let query_id = *self.state.next_query_id.get();
-        self.state.next_query_id.set(query_id + 1);
-        
         // Select voters for this query
         let max_voters = min_votes_required * 2;
         let selected_voters = match self.state
@@ -3085,18 +5152,46 @@ impl OracleRegistryV2Contract {
             )),
         };
         
+        // Collect the deposit last, so a rejected query never keeps it
+        let deposit = match self.collect_creation_deposit().await {
+            Ok(deposit) => deposit,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        // Allocate query ID only once voter selection has succeeded
+        let query_id = *self.state.next_query_id.get();
+        self.state.next_query_id.set(query_id + 1);
+        if let Err(e) = self.state.record_voter_selections(query_id, &selected_voters).await {
+            eprintln!("Warning: {}", e);
+        }
+        self.hold_creation_deposit(query_id, deposit);
+        
         // Create query with callback information
         let query = state::Query {
             id: query_id,
             description: format!("Market #{}: {}", market_id, question),
             outcomes: outcomes.clone(),
+            numeric_outcomes: params.strategy_or_default(None).parse_numeric_outcomes(&outcomes).ok().flatten(),
+            outcome_aliases: std::collections::BTreeMap::new(),
             commit_phase_end,
             reveal_phase_end,
             deadline: reveal_phase_end,
             phase: state::VotingPhase::Commit,
             status: state::QueryStatus::Active,
-            strategy: state::DecisionStrategy::Majority,
+            strategy: params.strategy_or_default(None),
             min_votes: min_votes_required,
+            reward_basis: None,
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            allow_external_resolution: false,
+            tie_break: state::TieBreak::OutcomeOrder,
+            category: None,
+            tags: Vec::new(),
             max_voters,
             reward_amount: Amount::ZERO,
             creator: callback_chain,
@@ -3105,7 +5200,9 @@ impl OracleRegistryV2Contract {
             votes: std::collections::BTreeMap::new(),
             selected_voters,
             result: None,
+            result_display: None,
             resolved_at: None,
+            resolution_source: None,
             callback_chain: Some(callback_chain),
             callback_data: Some(callback_data),
         };
@@ -3125,19 +5222,131 @@ impl OracleRegistryV2Contract {
         
         // Update statistics
         let total = *self.state.total_queries_created.get();
-        self.state.total_queries_created.set(total + 1);
+        self.state.total_queries_created.set(state::increment_counter(total, "total_queries_created"));
+        
+        eprintln!("✅ Query {} created from market {}", query_id, market_id);
+        
+        OperationResponse::success_with_data(
+            format!("Query {} created for market {}. Voters can now commit and reveal votes.", query_id, market_id),
+            ResponseData {
+                voter_address: None,
+                query_id: Some(query_id),
+                vote_count: None,
+                rewards_claimed: None,
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
+            }
+        )
+    }
+    
+    /// Handle cross-chain request to re-send a query's result
+    /// 
+    /// Replies to `reply_to` with a QueryResolutionCallback when the query is
+    /// resolved, and with QueryResultUnavailable otherwise.
+    async fn handle_request_query_result(
+        &mut self,
+        query_id: u64,
+        reply_to: linera_sdk::linera_base_types::ChainId,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::{Message, OperationResponse};
+        
+        eprintln!("📥 Received RequestQueryResult: query_id={}, reply_to={}", query_id, reply_to);
+        
+        // Look up the resolved outcome
+        let resolved = match self.state.get_query(query_id).await {
+            Some(query) => query.resolved_outcome().map(|(outcome, resolved_at)| {
+                (outcome, resolved_at, query.callback_data.unwrap_or_default())
+            }),
+            None => Err(format!("Query {} not found", query_id)),
+        };
+        
+        // Reply with the result, or with the reason there is none
+        let (reply, response) = match resolved {
+            Ok((resolved_outcome, resolved_at, callback_data)) => {
+                let response = OperationResponse::success(format!(
+                    "Result for query {} sent to chain {}", query_id, reply_to
+                ));
+                (Message::QueryResolutionCallback { query_id, resolved_outcome, resolved_at, callback_data }, response)
+            }
+            Err(reason) => {
+                let response = OperationResponse::error(reason.clone());
+                (Message::QueryResultUnavailable { query_id, reason }, response)
+            }
+        };
+        
+        self.runtime.prepare_message(reply)
+            .with_authentication()
+            .with_tracking()
+            .send_to(reply_to);
+        
+        response
+    }
+    
+    /// Send `query`'s QueryResolutionCallback to the chain that requested it
+    /// 
+    /// The payload comes from `Query::resolved_outcome`, so a resend repeats
+    /// exactly what was sent at resolution. Returns the callback chain.
+    fn send_resolution_callback(
+        &mut self,
+        query: &state::Query,
+    ) -> Result<linera_sdk::linera_base_types::ChainId, String> {
+        let (callback_chain, callback_data) = match (query.callback_chain, query.callback_data.clone()) {
+            (Some(chain), Some(data)) => (chain, data),
+            _ => return Err(format!("Query {} has no callback configured", query.id)),
+        };
+        let (resolved_outcome, resolved_at) = query.resolved_outcome()?;
+        
+        eprintln!("📤 Sending QueryResolutionCallback to chain {}: query_id={}, result={}",
+                 callback_chain, query.id, resolved_outcome);
+        let callback_message = oracle_registry_v2::Message::QueryResolutionCallback {
+            query_id: query.id,
+            resolved_outcome,
+            resolved_at,
+            callback_data,
+        };
+        
+        self.runtime.prepare_message(callback_message)
+            .with_authentication()
+            .with_tracking()
+            .send_to(callback_chain);
+        
+        Ok(callback_chain)
+    }
+    
+    /// Re-send a closed query's resolution callback (admin only)
+    /// 
+    /// Recovers markets that never processed the original callback. The payload
+    /// matches the one sent at resolution (NO_CONSENSUS_OUTCOME for queries
+    /// closed without consensus). Callbacks are not acknowledged, so the
+    /// registry cannot tell whether the market already has the result; markets
+    /// must ignore duplicates.
+    async fn resend_callback(&mut self, query_id: u64) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let caller_chain = self.runtime.chain_id();
+        
+        // Verify caller is admin
+        if !self.state.is_admin(&caller_chain).await {
+            return OperationResponse::error("Unauthorized: only admin can resend callbacks");
+        }
         
-        eprintln!("✅ Query {} created from market {}", query_id, market_id);
+        let query = match self.validate_query_exists(query_id).await {
+            Ok(q) => q,
+            Err(e) => return OperationResponse::error(e),
+        };
         
-        OperationResponse::success_with_data(
-            format!("Query {} created for market {}. Voters can now commit and reveal votes.", query_id, market_id),
-            ResponseData {
-                voter_address: None,
-                query_id: Some(query_id),
-                vote_count: None,
-                rewards_claimed: None,
-            }
-        )
+        // Only results that were (or would have been) sent can be resent
+        let callback_chain = match self.send_resolution_callback(&query) {
+            Ok(chain) => chain,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        OperationResponse::success(format!(
+            "Callback for query {} resent to chain {}", query_id, callback_chain
+        ))
     }
     
     /// Resolve a query
@@ -3145,7 +5354,12 @@ impl OracleRegistryV2Contract {
         &mut self,
         query_id: u64,
     ) -> oracle_registry_v2::OperationResponse {
-        use oracle_registry_v2::OperationResponse;
+        use oracle_registry_v2::{OperationResponse, ResponseData, VoterAmount};
+        
+        // A tripped circuit breaker also halts the rest of a settlement batch
+        if self.state.is_paused().await {
+            return OperationResponse::error("Protocol is paused");
+        }
         
         // Validate query exists
         let mut query = match self.validate_query_exists(query_id).await {
@@ -3175,6 +5389,34 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error(e);
         }
         
+        // Require enough distinct voted outcomes, otherwise close as no consensus
+        if let Err(reason) = query.check_distinct_voted_outcomes() {
+            if let Err(e) = self.close_query_unresolved(query_id, state::ResolutionResult::NoConsensus).await {
+                return OperationResponse::error(format!(
+                    "Query {} lacks distinct outcomes but failed to close: {}",
+                    query_id, e
+                ));
+            }
+            return OperationResponse::error(format!(
+                "Query {} closed without consensus: {}",
+                query_id, reason
+            ));
+        }
+        
+        // Require enough distinct voter chains, none of which dominate as a group
+        if let Err(reason) = self.check_voter_diversity(&query).await {
+            if let Err(e) = self.close_query_unresolved(query_id, state::ResolutionResult::NoConsensus).await {
+                return OperationResponse::error(format!(
+                    "Query {} lacks voter diversity but failed to close: {}",
+                    query_id, e
+                ));
+            }
+            return OperationResponse::error(format!(
+                "Query {} closed without consensus: {}",
+                query_id, reason
+            ));
+        }
+        
         // Get current time for resolution timestamp
         let current_time = self.runtime.system_time();
         
@@ -3187,30 +5429,83 @@ impl OracleRegistryV2Contract {
             ));
         }
         
-        // TODO: Implement proper resolution logic based on strategy
-        // For now, just use simple majority
+        // Default strategies are only checked against the outcomes here
+        let strategy = query.strategy_for_resolution();
+        if strategy != query.strategy {
+            eprintln!("⚠️ Query {} strategy {} does not fit its outcomes, resolving with {}",
+                     query_id, query.strategy.as_str(), strategy.as_str());
+            query.strategy = strategy;
+        }
+        
+        // Calculate result based on the query's decision strategy
         let result = self.calculate_result(&query).await;
         
+        // A winner short of its required votes closes as no consensus
+        if let Err(reason) = query.check_winner_votes(&result) {
+            if let Err(e) = self.close_query_unresolved(query_id, state::ResolutionResult::NoConsensus).await {
+                return OperationResponse::error(format!(
+                    "Query {} winner lacks votes but failed to close: {}",
+                    query_id, e
+                ));
+            }
+            return OperationResponse::error(format!(
+                "Query {} closed without consensus: {}",
+                query_id, reason
+            ));
+        }
+        
+        // Nothing could be tallied (e.g. a Median query without a numeric vote):
+        // close as no consensus so voters are refunded rather than slashed
+        if result == state::ResolutionResult::NoConsensus {
+            if let Err(e) = self.close_query_unresolved(query_id, state::ResolutionResult::NoConsensus).await {
+                return OperationResponse::error(format!(
+                    "Query {} has no tallied votes but failed to close: {}",
+                    query_id, e
+                ));
+            }
+            return OperationResponse::error(format!(
+                "Query {} closed without consensus: no vote could be tallied with the {} strategy",
+                query_id, query.strategy.as_str()
+            ));
+        }
+        
+        // Circuit breaker: abort and pause if this resolution would slash too much
+        let params = self.state.get_parameters().await;
+        let projected_slash = self.projected_slash(&query, &result, &params).await;
+        let total_stake = *self.state.total_stake.get();
+        if let Err(reason) = params.check_resolution_slash(projected_slash, total_stake) {
+            self.trip_slash_circuit_breaker(query_id, projected_slash, total_stake, &params);
+            return OperationResponse::error(format!(
+                "Query {} resolution aborted and protocol paused: {}",
+                query_id, reason
+            ));
+        }
+        
+        let result_display = result.to_string();
+        
         // Update query status
         query.status = state::QueryStatus::Resolved;
         query.result = Some(result.clone());
+        query.result_display = Some(result_display.clone());
         query.resolved_at = Some(self.runtime.system_time());
         self.state.queries.insert(&query_id, query.clone()).expect("Failed to update query");
         
-        // Unlock stake for all voters who participated
-        for (voter, _vote) in &query.votes {
-            // Calculate how much was locked for this vote
-            if let Some(voter_info) = self.state.get_voter(voter).await {
-                let params = self.state.get_parameters().await;
-                let locked_amount = self.calculate_stake_to_lock(&voter_info, &query, &params);
-                
-                if let Err(e) = self.state.unlock_stake(voter, locked_amount).await {
-                    // Log error but continue - don't fail the entire resolution
-                    eprintln!("Warning: Failed to unlock stake for voter {}: {}", voter, e);
-                }
-            }
+        // Reconcile stored vote count with the query's votes
+        if let Err(e) = self.state.reconcile_vote_counts(query_id).await {
+            eprintln!("Warning: Failed to reconcile vote count for query {}: {}", query_id, e);
+        }
+        
+        // Record which panel members took part
+        if let Err(e) = self.state.record_panel_participation(&query).await {
+            eprintln!("Warning: {}", e);
         }
         
+        // Unlock stake for voters who committed or voted (first batch; the rest via UnlockQueryStakes)
+        self.unlock_query_stakes(&query, state::UNLOCK_BATCH_SIZE).await;
+        
+        // Return the creator's deposit now that the query has resolved
+        self.refund_creation_deposit(&query).await;
+        
         // Remove from active queries
         let mut active = self.state.get_active_queries().await;
         active.retain(|&id| id != query_id);
@@ -3218,14 +5513,20 @@ impl OracleRegistryV2Contract {
         
         // Update statistics
         let total_resolved = *self.state.total_queries_resolved.get();
-        self.state.total_queries_resolved.set(total_resolved + 1);
+        self.state.total_queries_resolved.set(state::increment_counter(total_resolved, "total_queries_resolved"));
         
-        // Update voter reputations based on correctness
+        // Update voter reputations based on correctness (abstentions are neither)
         let mut correct_voters = 0;
         let mut incorrect_voters = 0;
         
-        for (voter, vote) in &query.votes {
-            let was_correct = vote.value == result;
+        for (voter, vote) in query.tallied_votes() {
+            let was_correct = result.matches_vote(&vote.value);
+            
+            if let Some(confidence) = vote.confidence {
+                if let Err(e) = self.state.update_voter_calibration(voter, confidence, was_correct).await {
+                    eprintln!("Warning: Failed to update calibration for voter {}: {}", voter, e);
+                }
+            }
             
             if let Err(e) = self.state.update_voter_reputation(voter, was_correct).await {
                 eprintln!("Warning: Failed to update reputation for voter {}: {}", voter, e);
@@ -3239,85 +5540,85 @@ impl OracleRegistryV2Contract {
         }
         
         // Calculate and distribute rewards to correct voters
+        let mut reward_distribution = Vec::new();
         if correct_voters > 0 {
-            let params = self.state.get_parameters().await;
             let reward_amount = query.reward_amount;
             
-            // Collect correct voters with their info
+            // Collect correct voters with their info (in chain ID order)
             let mut correct_voter_infos = Vec::new();
-            for (voter, vote) in &query.votes {
-                if vote.value == result {
+            for (voter, vote) in query.tallied_votes() {
+                if result.matches_vote(&vote.value) {
                     if let Some(voter_info) = self.state.get_voter(voter).await {
                         correct_voter_infos.push((*voter, voter_info));
                     }
                 }
             }
             
-            // Calculate rewards based on query strategy
-            let reward_distribution = match query.strategy {
-                state::DecisionStrategy::WeightedByStake => {
-                    self.state.calculate_stake_weighted_rewards(
-                        reward_amount,
-                        &correct_voter_infos,
-                        &params,
-                    )
-                },
-                state::DecisionStrategy::WeightedByReputation => {
-                    self.state.calculate_reputation_weighted_rewards(
-                        reward_amount,
-                        &correct_voter_infos,
-                        &params,
-                    )
-                },
-                _ => {
-                    // For Majority and Median strategies, use equal distribution
-                    self.state.calculate_equal_rewards(
-                        reward_amount,
-                        &correct_voter_infos,
-                        &params,
-                    )
-                }
-            };
+            // Deduct protocol fee, then split the rest by the query's reward basis
+            // (or its strategy when no basis was set)
+            let reward_split = state::split_reward_for_query(
+                &query,
+                &correct_voter_infos,
+                &params,
+            );
             
             // Distribute rewards to correct voters
-            let mut total_distributed = 0u128;
-            for (voter, reward) in &reward_distribution {
-                let reward_value: u128 = (*reward).into();
-                total_distributed += reward_value;
-                
+            for (voter, reward) in &reward_split.rewards {
                 // Add to pending rewards
-                let current_pending = self.state.get_pending_rewards(voter).await;
-                let current_value: u128 = current_pending.into();
-                let new_pending = Amount::from_tokens(current_value + reward_value);
-                
-                if let Err(e) = self.state.pending_rewards.insert(voter, new_pending) {
+                if let Err(e) = self.state.credit_pending_rewards(voter, *reward, current_time).await {
                     eprintln!("Warning: Failed to add pending rewards for voter {}: {}", voter, e);
                 }
+                if let Err(e) = self.state.record_voter_reward(voter, *reward).await {
+                    eprintln!("Warning: {}", e);
+                }
+                reward_distribution.push(VoterAmount { voter_address: voter.to_string(), amount: reward.to_string() });
             }
             
-            // Calculate protocol fee from total reward
-            let protocol_fee = self.state.calculate_protocol_fee(reward_amount, &params);
-            let fee_value: u128 = protocol_fee.into();
+            // Rebate part of the protocol fee to the query creator
+            let creator_rebate = params.creator_rebate(reward_split.fee);
+            if creator_rebate > Amount::ZERO {
+                if let Err(e) = self.state.credit_pending_rewards(&query.creator, creator_rebate, current_time).await {
+                    eprintln!("Warning: Failed to add creator rebate for {}: {}", query.creator, e);
+                }
+            }
             
-            // Update protocol treasury with collected fees
+            // Route the rest of the protocol fee (and rounding dust) to the treasury
             let current_treasury = *self.state.protocol_treasury.get();
-            self.state.protocol_treasury.set(current_treasury.saturating_add(protocol_fee));
+            self.state.protocol_treasury.set(
+                current_treasury
+                    .saturating_add(reward_split.fee.saturating_sub(creator_rebate))
+                    .saturating_add(reward_split.dust)
+            );
             
-            // Update reward pool (add query reward, subtract distributed rewards)
+            // Update reward pool (add query reward, subtract everything paid out)
+            let distributed_amount = reward_split.distributed();
             let current_pool = *self.state.reward_pool.get();
-            let distributed_amount = Amount::from_attos(total_distributed);
-            let new_pool = current_pool.saturating_add(reward_amount).saturating_sub(distributed_amount);
+            let new_pool = current_pool
+                .saturating_add(reward_amount)
+                .saturating_sub(distributed_amount)
+                .saturating_sub(reward_split.fee)
+                .saturating_sub(reward_split.dust);
             self.state.reward_pool.set(new_pool);
+            
+            // Accounting invariant: distributed + fee + dust == reward_amount
+            // (the creator rebate is paid out of the fee)
+            reward_split.check_invariant(reward_amount)
+                .expect("Reward accounting invariant violated");
+            
+            if distributed_amount > Amount::ZERO {
+                if let Err(e) = self.state.record_reward_flow(current_time, query_id, distributed_amount) {
+                    eprintln!("Warning: {}", e);
+                }
+            }
         }
         
         // Apply slashing to incorrect voters
         let mut total_slashed = 0u128;
+        let mut slash_distribution = Vec::new();
         let mut voters_deactivated = 0;
         if incorrect_voters > 0 {
-            let params = self.state.get_parameters().await;
-            
-            for (voter, vote) in &query.votes {
-                if vote.value != result {
+            for (voter, vote) in query.tallied_votes() {
+                if !result.matches_vote(&vote.value) {
                     if let Some(voter_info) = self.state.get_voter(voter).await {
                         // Calculate slash amount based on protocol parameters
                         let slash_amount = self.state.calculate_slash_amount(&voter_info, &params);
@@ -3354,14 +5655,23 @@ impl OracleRegistryV2Contract {
                         }
                         
                         // Update voter info with reduced stake (and possibly deactivated status)
-                        if let Err(e) = self.state.voters.insert(voter, updated_info) {
+                        if let Err(e) = self.state.save_voter(voter, updated_info.clone()).await {
                             eprintln!("Warning: Failed to apply slash for voter {}: {}", voter, e);
                             continue;
                         }
                         
+                        if let Err(e) = self.state.record_stake_snapshot(voter, &updated_info, current_time).await {
+                            eprintln!("Warning: {}", e);
+                        }
+                        
+                        if let Err(e) = self.state.record_voter_slash(voter, actual_slash_amount).await {
+                            eprintln!("Warning: {}", e);
+                        }
+                        
                         // Track total slashed amount
                         let actual_slash_value: u128 = actual_slash_amount.into();
                         total_slashed += actual_slash_value;
+                        slash_distribution.push(VoterAmount { voter_address: voter.to_string(), amount: actual_slash_amount.to_string() });
                         
                         // Add slashed amount to protocol treasury - use saturating_add
                         let current_treasury = *self.state.protocol_treasury.get();
@@ -3386,32 +5696,19 @@ impl OracleRegistryV2Contract {
             }
         }
         
+        if total_slashed > 0 {
+            let slashed = Amount::from_attos(total_slashed);
+            if let Err(e) = self.state.record_slash_flow(current_time, query_id, slashed) {
+                eprintln!("Warning: {}", e);
+            }
+        }
+        
         // Send callback to requesting chain if callback info exists
-        if let (Some(callback_chain), Some(callback_data)) = 
-            (query.callback_chain, query.callback_data.clone()) 
-        {
-            eprintln!(
-                "📤 Sending QueryResolutionCallback to chain {}: query_id={}, result={}",
-                callback_chain, query_id, result
-            );
-            
-            // Create callback message with resolution result
-            let callback_message = oracle_registry_v2::Message::QueryResolutionCallback {
-                query_id,
-                resolved_outcome: result.clone(),
-                resolved_at: current_time,
-                callback_data,
-            };
-            
-            // Send callback to Market Chain with authentication
-            self.runtime.prepare_message(callback_message)
-                .with_authentication()
-                .with_tracking()
-                .send_to(callback_chain);
-            
-            eprintln!("✅ Callback sent successfully to {}", callback_chain);
-            // Note: send_to() doesn't return Result, it panics on error
-            // The query is still resolved, callback delivery is guaranteed by Linera
+        if query.callback_chain.is_some() {
+            match self.send_resolution_callback(&query) {
+                Ok(callback_chain) => eprintln!("✅ Callback sent successfully to {}", callback_chain),
+                Err(e) => eprintln!("Warning: {}", e),
+            }
         } else {
             eprintln!("ℹ️ No callback configured for query {}", query_id);
         }
@@ -3419,16 +5716,25 @@ impl OracleRegistryV2Contract {
         // Emit QueryResolved event for cross-chain subscribers
         self.emit_oracle_event(OracleEvent::QueryResolved {
             query_id,
-            result: result.clone(),
+            result: result_display.clone(),
             resolved_at: current_time,
             total_votes: correct_voters + incorrect_voters,
             correct_voters,
         });
         
+        // Sweep the treasury to the treasury account once the interval has passed
+        if params.treasury_sweep_due(*self.state.last_treasury_sweep.get(), current_time)
+            && self.state.sweepable_treasury() > Amount::ZERO
+        {
+            if let Err(e) = self.sweep_treasury().await {
+                eprintln!("Warning: {}", e);
+            }
+        }
+        
         // Build detailed response message
         let mut response_msg = format!(
             "Query resolved with result: {} ({} correct, {} incorrect)",
-            result, correct_voters, incorrect_voters
+            result_display, correct_voters, incorrect_voters
         );
         
         if total_slashed > 0 {
@@ -3446,40 +5752,160 @@ impl OracleRegistryV2Contract {
             }
         }
         
-        OperationResponse::success(response_msg)
+        OperationResponse::success_with_data(response_msg, ResponseData {
+            voter_address: None,
+            query_id: Some(query_id),
+            vote_count: Some(vote_count),
+            rewards_claimed: None,
+            query_result: None,
+            batch_claims: None,
+            expired_queries: None,
+            reward_distribution: Some(reward_distribution),
+            slash_distribution: Some(slash_distribution),
+        })
+    }
+    
+    /// Check `query`'s distinct voter and address-prefix group power requirements
+    async fn check_voter_diversity(&self, query: &state::Query) -> Result<(), String> {
+        query.check_distinct_voters()?;
+        
+        let Some(max_bps) = query.max_group_power_bps else {
+            return Ok(());
+        };
+        let mut powers = Vec::new();
+        for (voter, _) in query.tallied_votes() {
+            let power = self.state.get_voter(voter).await.map(|info| self.state.calculate_voter_power(&info)).unwrap_or(0);
+            powers.push((*voter, power));
+        }
+        let prefix_len = self.state.get_parameters().await.voter_group_prefix_len;
+        state::check_group_power(&powers, prefix_len, max_bps)
+    }
+    
+    /// Total that resolving `query` with `result` would slash from incorrect voters
+    /// 
+    /// Mirrors the slashing loop in `resolve_query`.
+    async fn projected_slash(
+        &self,
+        query: &state::Query,
+        result: &state::ResolutionResult,
+        params: &ProtocolParameters,
+    ) -> Amount {
+        let mut projected = Amount::ZERO;
+        for (voter, vote) in query.tallied_votes() {
+            if result.matches_vote(&vote.value) {
+                continue;
+            }
+            if let Some(voter_info) = self.state.get_voter(voter).await {
+                let slash_amount = self.state.calculate_slash_amount(&voter_info, params);
+                projected = projected.saturating_add(slash_amount.min(voter_info.stake));
+            }
+        }
+        projected
+    }
+    
+    /// Pause the protocol after a resolution exceeded the slashing limit
+    fn trip_slash_circuit_breaker(
+        &mut self,
+        query_id: u64,
+        projected_slash: Amount,
+        total_stake: Amount,
+        params: &ProtocolParameters,
+    ) {
+        eprintln!(
+            "🚨 Circuit breaker: query {} would slash {} of {} total stake, pausing protocol",
+            query_id, projected_slash, total_stake
+        );
+        self.state.is_paused.set(true);
+        
+        self.emit_oracle_event(OracleEvent::SlashCircuitBreakerTripped {
+            query_id,
+            projected_slash,
+            total_stake,
+            max_slash_per_resolution_bps: params.max_slash_per_resolution_bps,
+        });
+        let changed_by = self.runtime.chain_id();
+        self.emit_oracle_event(OracleEvent::ProtocolStatusChanged {
+            is_paused: true,
+            changed_by,
+        });
     }
     
     /// Calculate result based on votes and decision strategy
-    async fn calculate_result(&self, query: &state::Query) -> String {
+    /// 
+    /// Reveals landing in the grace window count at the late reveal weight.
+    async fn calculate_result(&self, query: &state::Query) -> state::ResolutionResult {
+        let params = self.state.get_parameters().await;
+        let late_weight_bps = params.late_reveal_weight_bps;
+        let multi_winner_bps = query.multi_winner.then_some(params.multi_winner_threshold_bps);
         match query.strategy {
-            state::DecisionStrategy::Majority => self.calculate_majority_result(query),
-            state::DecisionStrategy::WeightedByReputation => self.calculate_reputation_weighted_result(query).await,
-            state::DecisionStrategy::WeightedByStake => self.calculate_stake_weighted_result(query).await,
-            state::DecisionStrategy::Median => self.calculate_median_result(query),
+            state::DecisionStrategy::Majority => self.calculate_majority_result(query, late_weight_bps, multi_winner_bps).await,
+            state::DecisionStrategy::WeightedByReputation => self.calculate_reputation_weighted_result(query, late_weight_bps, multi_winner_bps).await,
+            state::DecisionStrategy::WeightedByStake => self.calculate_stake_weighted_result(query, late_weight_bps, multi_winner_bps).await,
+            state::DecisionStrategy::Median => self.calculate_median_result(query, late_weight_bps),
+            state::DecisionStrategy::ConfidenceWeightedMedian => self.calculate_confidence_weighted_median_result(query, late_weight_bps),
+        }
+    }
+    
+    /// Pick the winner of `tally`, or on multi-winner queries every outcome
+    /// holding at least `multi_winner_bps` of it
+    fn result_from_tally<W: PartialOrd + Copy>(
+        tally: std::collections::BTreeMap<String, W>,
+        multi_winner_bps: Option<u32>,
+        as_f64: fn(W) -> f64,
+    ) -> state::ResolutionResult {
+        match multi_winner_bps {
+            Some(threshold_bps) => state::ResolutionResult::from_tally_above_share(
+                tally.into_iter().map(|(value, weight)| (value, as_f64(weight))),
+                threshold_bps,
+            ),
+            None => state::ResolutionResult::from_tally(tally),
         }
     }
     
     /// Calculate result using simple majority
-    fn calculate_majority_result(&self, query: &state::Query) -> String {
-        let mut vote_counts: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
-        
-        for vote in query.votes.values() {
-            *vote_counts.entry(vote.value.clone()).or_insert(0) += 1;
+    /// 
+    /// A tie for the most votes is settled by the query's tie-break mode.
+    async fn calculate_majority_result(&self, query: &state::Query, late_weight_bps: u32, multi_winner_bps: Option<u32>) -> state::ResolutionResult {
+        let mut tally: std::collections::BTreeMap<String, u64> = std::collections::BTreeMap::new();
+        for (vote, vote_weight_bps) in query.weighted_votes(late_weight_bps) {
+            *tally.entry(vote.value.clone()).or_insert(0) += vote_weight_bps as u64;
+        }
+        if multi_winner_bps.is_some() {
+            return Self::result_from_tally(tally, multi_winner_bps, |weight| weight as f64);
         }
         
-        // Find the value with most votes
-        vote_counts
-            .into_iter()
-            .max_by_key(|(_, count)| *count)
-            .map(|(value, _)| value)
-            .unwrap_or_else(|| "No consensus".to_string())
+        let backing = if query.tie_break.uses_backing() {
+            self.outcome_backing(query, query.tie_break).await
+        } else {
+            std::collections::BTreeMap::new()
+        };
+        state::ResolutionResult::from_tally_with_tie_break(&tally, &query.outcomes, query.tie_break, &backing)
+    }
+    
+    /// Aggregate stake (capped at the effective maximum) or reputation of each
+    /// outcome's tallied voters, for breaking ties
+    async fn outcome_backing(&self, query: &state::Query, tie_break: state::TieBreak) -> std::collections::BTreeMap<String, u128> {
+        let max_effective_stake = self.state.get_parameters().await.max_effective_stake;
+        let mut backing: std::collections::BTreeMap<String, u128> = std::collections::BTreeMap::new();
+        
+        for (voter, vote) in query.tallied_votes() {
+            let weight = match self.state.get_voter(voter).await {
+                Some(voter_info) if tie_break == state::TieBreak::HigherStake => {
+                    u128::from(voter_info.effective_stake(max_effective_stake))
+                }
+                Some(voter_info) => voter_info.reputation as u128,
+                None => 0,
+            };
+            *backing.entry(vote.value.clone()).or_insert(0) += weight;
+        }
+        backing
     }
     
     /// Calculate result weighted by voter reputation
-    async fn calculate_reputation_weighted_result(&self, query: &state::Query) -> String {
-        let mut weighted_votes: std::collections::HashMap<String, f64> = std::collections::HashMap::new();
+    async fn calculate_reputation_weighted_result(&self, query: &state::Query, late_weight_bps: u32, multi_winner_bps: Option<u32>) -> state::ResolutionResult {
+        let mut weighted_votes: std::collections::BTreeMap<String, f64> = std::collections::BTreeMap::new();
         
-        for vote in query.votes.values() {
+        for (vote, vote_weight_bps) in query.weighted_votes(late_weight_bps) {
             // Get voter reputation (default to 50 if not found)
             let reputation = if let Some(voter_info) = self.state.get_voter(&vote.voter).await {
                 voter_info.reputation
@@ -3487,116 +5913,348 @@ impl OracleRegistryV2Contract {
                 50
             };
             
-            let weight = self.state.calculate_reputation_weight(reputation);
+            let weight = self.state.calculate_reputation_weight(reputation) * vote_weight_bps as f64 / 10000.0;
             *weighted_votes.entry(vote.value.clone()).or_insert(0.0) += weight;
         }
         
-        // Find the value with highest weighted votes
-        weighted_votes
-            .into_iter()
-            .max_by(|(_, weight_a), (_, weight_b)| {
-                weight_a.partial_cmp(weight_b).unwrap_or(std::cmp::Ordering::Equal)
-            })
-            .map(|(value, _)| value)
-            .unwrap_or_else(|| "No consensus".to_string())
+        // Find the value(s) with highest weighted votes
+        Self::result_from_tally(weighted_votes, multi_winner_bps, |weight| weight)
     }
     
     /// Calculate result weighted by voter stake
-    async fn calculate_stake_weighted_result(&self, query: &state::Query) -> String {
-        let mut weighted_votes: std::collections::HashMap<String, u128> = std::collections::HashMap::new();
+    async fn calculate_stake_weighted_result(&self, query: &state::Query, late_weight_bps: u32, multi_winner_bps: Option<u32>) -> state::ResolutionResult {
+        let mut weighted_votes: std::collections::BTreeMap<String, u128> = std::collections::BTreeMap::new();
+        let max_effective_stake = self.state.get_parameters().await.max_effective_stake;
         
-        for vote in query.votes.values() {
-            // Get voter stake (default to 0 if not found)
+        for (vote, vote_weight_bps) in query.weighted_votes(late_weight_bps) {
+            // Get voter stake, capped at the effective maximum (default to 0 if not found)
             let stake = if let Some(voter_info) = self.state.get_voter(&vote.voter).await {
-                u128::from(voter_info.stake)
+                u128::from(voter_info.effective_stake(max_effective_stake))
             } else {
                 0
             };
             
-            *weighted_votes.entry(vote.value.clone()).or_insert(0) += stake;
+            *weighted_votes.entry(vote.value.clone()).or_insert(0) += stake.saturating_mul(vote_weight_bps as u128) / 10000;
+        }
+        
+        // Find the value(s) with highest weighted votes
+        Self::result_from_tally(weighted_votes, multi_winner_bps, |weight| weight as f64)
+    }
+    
+    /// Calculate median result (for numeric values)
+    fn calculate_median_result(&self, query: &state::Query, late_weight_bps: u32) -> state::ResolutionResult {
+        state::ResolutionResult::from_weighted_median(
+            query.numeric_votes(late_weight_bps).map(|(_, value, weight)| (value, weight as u64))
+        )
+    }
+    
+    /// Calculate result using median with votes weighted by confidence
+    fn calculate_confidence_weighted_median_result(&self, query: &state::Query, late_weight_bps: u32) -> state::ResolutionResult {
+        state::ResolutionResult::from_weighted_median(
+            query.numeric_votes(late_weight_bps)
+                .map(|(vote, value, weight)| (value, vote.confidence_weight() * weight as u64))
+        )
+    }
+    
+    /// Claim pending rewards
+    async fn claim_rewards(&mut self) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::{OperationResponse, ResponseData};
+        
+        let voter_chain = self.runtime.chain_id();
+        
+        // Validate voter is registered
+        if let Err(e) = self.validate_voter_registered(&voter_chain).await {
+            return OperationResponse::error(e);
+        }
+        
+        // Get pending rewards
+        let pending_rewards = self.state.get_pending_rewards(&voter_chain).await;
+        
+        // Check if there are any rewards to claim
+        if pending_rewards == Amount::ZERO {
+            return OperationResponse::error("No pending rewards to claim");
+        }
+        
+        // Transfer rewards to voter
+        // Note: In production, implement proper token transfer
+        // For now, we'll just clear the pending rewards
+        
+        // Clear pending rewards
+        if let Err(e) = self.state.clear_pending_rewards(&voter_chain) {
+            return OperationResponse::error(e);
+        }
+        
+        // Record voter activity
+        let now = self.runtime.system_time();
+        self.state.mark_voter_active(&voter_chain, now).await;
+        
+        // Update total rewards distributed
+        let total_distributed = *self.state.total_rewards_distributed.get();
+        let total_value: u128 = total_distributed.into();
+        let rewards_value: u128 = pending_rewards.into();
+        let new_total = Amount::from_tokens(total_value + rewards_value);
+        self.state.total_rewards_distributed.set(new_total);
+        
+        // Emit RewardsClaimed event for cross-chain subscribers
+        self.emit_oracle_event(OracleEvent::RewardsClaimed {
+            voter_chain,
+            amount: pending_rewards,
+        });
+        
+        OperationResponse::success_with_data(
+            format!("Successfully claimed {} rewards", pending_rewards),
+            ResponseData {
+                voter_address: Some(voter_chain.to_string()),
+                query_id: None,
+                vote_count: None,
+                rewards_claimed: Some(pending_rewards.to_string()),
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
+            }
+        )
+    }
+    
+    /// Claim pending rewards and pay them to `target_chain`
+    async fn claim_rewards_to(
+        &mut self,
+        target_chain: linera_sdk::linera_base_types::ChainId,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::{OperationResponse, ResponseData};
+        
+        let voter_chain = self.runtime.chain_id();
+        
+        let amount = match self.pay_out_rewards(voter_chain, target_chain).await {
+            Ok(amount) => amount,
+            Err(e) => return OperationResponse::error(e),
+        };
+        
+        OperationResponse::success_with_data(
+            format!("Successfully claimed {} rewards to chain {}", amount, target_chain),
+            ResponseData {
+                voter_address: Some(voter_chain.to_string()),
+                query_id: None,
+                vote_count: None,
+                rewards_claimed: Some(amount.to_string()),
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
+            }
+        )
+    }
+    
+    /// Allow `operator` to claim the caller's rewards (None revokes)
+    async fn set_claim_operator(
+        &mut self,
+        operator: Option<linera_sdk::linera_base_types::ChainId>,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let voter_chain = self.runtime.chain_id();
+        
+        // Validate voter is registered
+        if let Err(e) = self.validate_voter_registered(&voter_chain).await {
+            return OperationResponse::error(e);
+        }
+        
+        let result = match operator {
+            Some(operator) if operator == voter_chain => {
+                return OperationResponse::error("A voter cannot be their own claim operator");
+            }
+            Some(operator) => self.state.claim_operators.insert(&voter_chain, operator),
+            None => self.state.claim_operators.remove(&voter_chain),
+        };
+        if let Err(e) = result {
+            return OperationResponse::error(format!("Failed to update claim operator: {}", e));
+        }
+        
+        self.emit_oracle_event(OracleEvent::ClaimOperatorSet { voter_chain, operator });
+        
+        match operator {
+            Some(operator) => OperationResponse::success(format!("Chain {} may now claim your rewards", operator)),
+            None => OperationResponse::success("Claim operator removed"),
+        }
+    }
+    
+    /// Replace the caller's encrypted metadata blob, or clear it with None
+    async fn set_encrypted_metadata(
+        &mut self,
+        encrypted_metadata: Option<Vec<u8>>,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let voter_chain = self.runtime.chain_id();
+        
+        if let Some(Err(e)) = encrypted_metadata.as_deref().map(state::validate_encrypted_metadata) {
+            return OperationResponse::error(e);
         }
         
-        // Find the value with highest weighted votes
-        weighted_votes
-            .into_iter()
-            .max_by_key(|(_, weight)| *weight)
-            .map(|(value, _)| value)
-            .unwrap_or_else(|| "No consensus".to_string())
-    }
-    
-    /// Calculate median result (for numeric values)
-    fn calculate_median_result(&self, query: &state::Query) -> String {
-        // Try to parse votes as numbers
-        let mut numeric_votes: Vec<f64> = query.votes.values()
-            .filter_map(|vote| vote.value.parse::<f64>().ok())
-            .collect();
+        // Validate voter is registered
+        let mut voter = match self.validate_voter_registered(&voter_chain).await {
+            Ok(voter) => voter,
+            Err(e) => return OperationResponse::error(e),
+        };
         
-        if numeric_votes.is_empty() {
-            return "No valid numeric votes".to_string();
+        let cleared = encrypted_metadata.is_none();
+        voter.encrypted_metadata = encrypted_metadata;
+        if let Err(e) = self.state.save_voter(&voter_chain, voter).await {
+            return OperationResponse::error(format!("Failed to update encrypted metadata: {}", e));
         }
         
-        numeric_votes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-        
-        let median = if numeric_votes.len() % 2 == 0 {
-            let mid = numeric_votes.len() / 2;
-            (numeric_votes[mid - 1] + numeric_votes[mid]) / 2.0
+        if cleared {
+            OperationResponse::success("Encrypted metadata cleared")
         } else {
-            numeric_votes[numeric_votes.len() / 2]
-        };
-        
-        median.to_string()
+            OperationResponse::success("Encrypted metadata updated")
+        }
     }
     
-    /// Claim pending rewards
-    async fn claim_rewards(&mut self) -> oracle_registry_v2::OperationResponse {
-        use oracle_registry_v2::{OperationResponse, ResponseData};
+    /// Claim several voters' pending rewards, each paid to the voter's own chain
+    /// 
+    /// The caller must be each voter's claim operator (or admin). A voter that
+    /// cannot be claimed for is reported in the results without failing the
+    /// rest of the batch.
+    async fn batch_claim_for(
+        &mut self,
+        voters: Vec<linera_sdk::linera_base_types::ChainId>,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::{BatchClaimResult, OperationResponse, ResponseData};
         
-        let voter_chain = self.runtime.chain_id();
+        let caller = self.runtime.chain_id();
         
-        // Validate voter is registered
-        if let Err(e) = self.validate_voter_registered(&voter_chain).await {
+        if let Err(e) = state::validate_batch_claim(&voters) {
             return OperationResponse::error(e);
         }
         
-        // Get pending rewards
-        let pending_rewards = self.state.get_pending_rewards(&voter_chain).await;
+        let is_admin = self.state.is_admin(&caller).await;
+        let requested = voters.len();
+        let mut total_claimed = Amount::ZERO;
+        let mut results = Vec::with_capacity(requested);
         
-        // Check if there are any rewards to claim
-        if pending_rewards == Amount::ZERO {
-            return OperationResponse::error("No pending rewards to claim");
+        for voter in voters {
+            let operator = self.state.get_claim_operator(&voter).await;
+            let outcome = match state::check_claim_operator(&caller, &voter, is_admin, operator) {
+                Ok(()) => self.pay_out_rewards(voter, voter).await,
+                Err(e) => Err(e),
+            };
+            
+            results.push(match outcome {
+                Ok(amount) => {
+                    total_claimed = total_claimed.saturating_add(amount);
+                    BatchClaimResult { voter_address: voter.to_string(), claimed: Some(amount.to_string()), error: None }
+                }
+                Err(e) => BatchClaimResult { voter_address: voter.to_string(), claimed: None, error: Some(e) },
+            });
         }
         
-        // Transfer rewards to voter
-        // Note: In production, implement proper token transfer
-        // For now, we'll just clear the pending rewards
+        let claimed_voters = results.iter().filter(|result| result.error.is_none()).count();
+        
+        OperationResponse::success_with_data(
+            format!("Claimed {} rewards for {} of {} voters", total_claimed, claimed_voters, requested),
+            ResponseData {
+                voter_address: None,
+                query_id: None,
+                vote_count: None,
+                rewards_claimed: Some(total_claimed.to_string()),
+                query_result: None,
+                batch_claims: Some(results),
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
+            }
+        )
+    }
+    
+    /// Pay `voter_chain`'s pending rewards to `target_chain`
+    /// 
+    /// Tokens are released through the token contract before the pending
+    /// rewards are cleared, so a failed transfer leaves them claimable.
+    async fn pay_out_rewards(
+        &mut self,
+        voter_chain: linera_sdk::linera_base_types::ChainId,
+        target_chain: linera_sdk::linera_base_types::ChainId,
+    ) -> Result<Amount, String> {
+        // Validate voter is registered
+        self.validate_voter_registered(&voter_chain).await?;
+        
+        // Validate there are rewards to claim
+        let pending_rewards = self.state.get_pending_rewards(&voter_chain).await;
+        let claim = state::prepare_reward_claim(voter_chain, target_chain, pending_rewards)?;
+        
+        // Pay out to the target chain's account
+        self.release_tokens(claim.amount, claim.target_chain).await
+            .map_err(|e| format!("Failed to transfer rewards: {}", e))?;
         
         // Clear pending rewards
-        if let Err(e) = self.state.pending_rewards.remove(&voter_chain) {
-            return OperationResponse::error(format!("Failed to clear pending rewards: {}", e));
-        }
+        self.state.clear_pending_rewards(&voter_chain)?;
+        
+        // Record voter activity
+        let now = self.runtime.system_time();
+        self.state.mark_voter_active(&voter_chain, now).await;
         
         // Update total rewards distributed
         let total_distributed = *self.state.total_rewards_distributed.get();
-        let total_value: u128 = total_distributed.into();
-        let rewards_value: u128 = pending_rewards.into();
-        let new_total = Amount::from_tokens(total_value + rewards_value);
-        self.state.total_rewards_distributed.set(new_total);
+        self.state.total_rewards_distributed.set(total_distributed.saturating_add(claim.amount));
+        
+        if claim.is_redirected() {
+            eprintln!("💸 Voter {} claimed {} rewards to chain {}", voter_chain, claim.amount, claim.target_chain);
+        }
         
         // Emit RewardsClaimed event for cross-chain subscribers
         self.emit_oracle_event(OracleEvent::RewardsClaimed {
             voter_chain,
-            amount: pending_rewards,
+            amount: claim.amount,
         });
         
-        OperationResponse::success_with_data(
-            format!("Successfully claimed {} rewards", pending_rewards),
-            ResponseData {
-                voter_address: Some(voter_chain.to_string()),
-                query_id: None,
-                vote_count: None,
-                rewards_claimed: Some(pending_rewards.to_string()),
-            }
-        )
+        Ok(claim.amount)
+    }
+    
+    /// Convert operation parameters to the state's parameters, keeping the
+    /// current token application (set via SetTokenApplication)
+    async fn convert_parameters(
+        &self,
+        params: oracle_registry_v2::state::ProtocolParameters,
+    ) -> ProtocolParameters {
+        state::ProtocolParameters {
+            min_stake: params.min_stake,
+            min_votes_default: params.min_votes_default,
+            default_query_duration: params.default_query_duration,
+            reward_percentage: params.reward_percentage,
+            slash_percentage: params.slash_percentage,
+            protocol_fee: params.protocol_fee,
+            token_app_id: self.state.get_parameters().await.token_app_id, // Set via SetTokenApplication
+            resolution_grace_secs: params.resolution_grace_secs,
+            min_vote_interval_secs: params.min_vote_interval_secs,
+            max_query_reward: params.max_query_reward,
+            default_strategy: params.default_strategy.into(),
+            high_stake_threshold: params.high_stake_threshold,
+            high_stake_reputation_bonus: params.high_stake_reputation_bonus,
+            commit_hash_algo: params.commit_hash_algo.into(),
+            emergency_unstake_penalty_bps: params.emergency_unstake_penalty_bps,
+            selection_cooldown_queries: params.selection_cooldown_queries,
+            allow_vote_change_until_deadline: params.allow_vote_change_until_deadline,
+            timelock_secs: params.timelock_secs,
+            max_slash_per_resolution_bps: params.max_slash_per_resolution_bps,
+            late_reveal_weight_bps: params.late_reveal_weight_bps,
+            max_active_queries: params.max_active_queries,
+            treasury_account: params.treasury_account,
+            treasury_sweep_interval_secs: params.treasury_sweep_interval_secs,
+            creator_rebate_bps: params.creator_rebate_bps,
+            multi_winner_threshold_bps: params.multi_winner_threshold_bps,
+            query_creation_deposit: params.query_creation_deposit,
+            voter_group_prefix_len: params.voter_group_prefix_len,
+            reward_claim_expiry_secs: params.reward_claim_expiry_secs,
+            max_effective_stake: params.max_effective_stake,
+            strategy_min_votes: params.strategy_min_votes.into_iter()
+                .map(|(strategy, min_votes)| (strategy.into(), min_votes))
+                .collect(),
+            strategy_stake_locks: params.strategy_stake_locks.into_iter()
+                .map(|(strategy, rule)| (strategy.into(), rule.into()))
+                .collect(),
+        }
     }
     
     /// Update protocol parameters (admin only)
@@ -3613,6 +6271,15 @@ impl OracleRegistryV2Contract {
             return OperationResponse::error("Unauthorized: only admin can update parameters");
         }
         
+        // A timelock routes every change through ProposeParameters
+        let timelock_secs = self.state.get_parameters().await.timelock_secs;
+        if timelock_secs > 0 {
+            return OperationResponse::error(format!(
+                "Parameter changes are timelocked ({}s): use ProposeParameters",
+                timelock_secs
+            ));
+        }
+        
         // Validate parameters
         if let Err(e) = self.validate_protocol_parameters(&params) {
             return OperationResponse::error(format!("Invalid parameters: {}", e));
@@ -3620,6 +6287,9 @@ impl OracleRegistryV2Contract {
         
         // Update parameters
         self.state.parameters.set(params.clone());
+        if let Err(e) = self.state.record_parameter_change(caller_chain, params.clone(), self.runtime.system_time()) {
+            eprintln!("Warning: {}", e);
+        }
         
         // Emit ParametersUpdated event for cross-chain subscribers
         self.emit_oracle_event(OracleEvent::ParametersUpdated {
@@ -3631,6 +6301,103 @@ impl OracleRegistryV2Contract {
         OperationResponse::success("Protocol parameters updated successfully")
     }
     
+    /// Propose a parameter change that applies once the current timelock elapses (admin only)
+    /// 
+    /// Replaces any earlier pending change, restarting the timelock.
+    async fn propose_parameters(
+        &mut self,
+        params: ProtocolParameters,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let caller_chain = self.runtime.chain_id();
+        
+        // Verify caller is admin
+        if !self.state.is_admin(&caller_chain).await {
+            return OperationResponse::error("Unauthorized: only admin can propose parameters");
+        }
+        
+        // Validate parameters
+        if let Err(e) = self.validate_protocol_parameters(&params) {
+            return OperationResponse::error(format!("Invalid parameters: {}", e));
+        }
+        
+        // The current timelock governs the change, including a change to the timelock itself
+        let timelock_secs = self.state.get_parameters().await.timelock_secs;
+        let pending = state::PendingParameters::new(params, caller_chain, self.runtime.system_time(), timelock_secs);
+        let (min_stake, min_votes_default, effective_at) =
+            (pending.params.min_stake, pending.params.min_votes_default, pending.effective_at);
+        self.state.pending_parameters.set(Some(pending));
+        
+        self.emit_oracle_event(OracleEvent::ParametersProposed {
+            min_stake,
+            min_votes_default,
+            effective_at,
+            proposed_by: caller_chain,
+        });
+        
+        OperationResponse::success(format!(
+            "Protocol parameters proposed, applicable from {} (timelock {}s)",
+            effective_at.micros(), timelock_secs
+        ))
+    }
+    
+    /// Apply the pending parameter change once its timelock has elapsed (anyone)
+    async fn apply_pending_parameters(&mut self) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let Some(pending) = self.state.pending_parameters.get().clone() else {
+            return OperationResponse::error("No pending parameter change");
+        };
+        
+        if let Err(e) = pending.check_ready(self.runtime.system_time()) {
+            return OperationResponse::error(e);
+        }
+        
+        // Keep a token application set since the proposal
+        let mut params = pending.params;
+        params.token_app_id = self.state.get_parameters().await.token_app_id;
+        
+        self.state.parameters.set(params.clone());
+        self.state.pending_parameters.set(None);
+        if let Err(e) = self.state.record_parameter_change(pending.proposed_by, params.clone(), self.runtime.system_time()) {
+            eprintln!("Warning: {}", e);
+        }
+        
+        // Emit ParametersUpdated event for cross-chain subscribers
+        self.emit_oracle_event(OracleEvent::ParametersUpdated {
+            min_stake: params.min_stake,
+            min_votes_default: params.min_votes_default,
+            updated_by: pending.proposed_by,
+        });
+        
+        OperationResponse::success("Pending protocol parameters applied")
+    }
+    
+    /// Set the token application used to return staked tokens (admin only)
+    async fn set_token_application(
+        &mut self,
+        token_app_id: linera_sdk::linera_base_types::ApplicationId,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let caller_chain = self.runtime.chain_id();
+        
+        // Verify caller is admin
+        if !self.state.is_admin(&caller_chain).await {
+            return OperationResponse::error("Unauthorized: only admin can set the token application");
+        }
+        
+        let mut params = self.state.get_parameters().await;
+        params.token_app_id = Some(token_app_id);
+        self.state.parameters.set(params.clone());
+        if let Err(e) = self.state.record_parameter_change(caller_chain, params, self.runtime.system_time()) {
+            eprintln!("Warning: {}", e);
+        }
+        
+        OperationResponse::success(format!("Token application set to {}", token_app_id))
+    }
+    
     /// Pause protocol (admin only)
     async fn pause_protocol(&mut self) -> oracle_registry_v2::OperationResponse {
         use oracle_registry_v2::OperationResponse;
@@ -3704,6 +6471,11 @@ impl OracleRegistryV2Contract {
                     query_id: None,
                     vote_count: Some(expired_ids.len()),
                     rewards_claimed: None,
+                    query_result: None,
+                    batch_claims: None,
+                    expired_queries: None,
+                    reward_distribution: None,
+                    slash_distribution: None,
                 }
             )
         }
@@ -3714,17 +6486,22 @@ impl OracleRegistryV2Contract {
         let mut resolved_query_ids = Vec::new();
         let active_queries = self.state.get_active_queries().await;
         let current_time = self.runtime.system_time();
+        let grace_secs = self.state.get_parameters().await.resolution_grace_secs;
         
         for query_id in active_queries {
-            if let Some(query) = self.state.get_query(query_id).await {
-                // Check if reveal phase has ended
-                if query.phase == state::VotingPhase::Reveal && current_time >= query.reveal_phase_end {
+            if let Some(query) = self.load_maintainable_query(query_id).await {
+                // Check if reveal phase and its grace window have ended
+                if query.phase == state::VotingPhase::Reveal
+                    && query.check_resolution_window(current_time, grace_secs).is_ok()
+                {
                     // Check if we have minimum votes
                     if query.votes.len() >= query.min_votes {
                         // Auto-resolve this query
                         let result = self.resolve_query(query_id).await;
                         if result.success {
                             resolved_query_ids.push(query_id);
+                        } else if self.is_closed_without_consensus(query_id).await {
+                            eprintln!("ℹ️ Query {} closed without consensus: {}", query_id, result.message);
                         } else {
                             eprintln!("Warning: Failed to auto-resolve query {}: {}", query_id, result.message);
                         }
@@ -3758,11 +6535,176 @@ impl OracleRegistryV2Contract {
                     query_id: None,
                     vote_count: Some(resolved_ids.len()),
                     rewards_claimed: None,
+                    query_result: None,
+                    batch_claims: None,
+                    expired_queries: None,
+                    reward_distribution: None,
+                    slash_distribution: None,
                 }
             )
         }
     }
     
+    /// Return pending rewards left unclaimed past the expiry to the reward pool (maintenance operation)
+    /// 
+    /// A voter's whole pending balance expires once `reward_claim_expiry_secs`
+    /// have passed since it was last credited. Checks at most `max` voters,
+    /// continuing from the stored cursor; the number still to check in this
+    /// pass is returned in `vote_count`.
+    async fn reclaim_unclaimed_rewards(&mut self, max: usize) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::{OperationResponse, ResponseData};
+        
+        if max == 0 || max > 1000 {
+            return OperationResponse::error("Max must be between 1 and 1000");
+        }
+        
+        let expiry_secs = self.state.get_parameters().await.reward_claim_expiry_secs;
+        if expiry_secs == 0 {
+            return OperationResponse::error("Reward claim expiry is disabled");
+        }
+        
+        let now = self.runtime.system_time();
+        let cutoff = linera_sdk::linera_base_types::Timestamp::from(now.micros().saturating_sub(expiry_secs.saturating_mul(1_000_000)));
+        let cursor = *self.state.reclaim_cursor.get();
+        let batch = match self.state.expired_pending_rewards(cutoff, cursor, max).await {
+            Ok(batch) => batch,
+            Err(e) => return OperationResponse::error(e),
+        };
+        self.state.reclaim_cursor.set(batch.next_cursor);
+        
+        let mut reclaimed = Amount::ZERO;
+        let mut voters = Vec::new();
+        for (voter, amount) in batch.expired {
+            if let Err(e) = self.state.clear_pending_rewards(&voter) {
+                eprintln!("Warning: {}", e);
+                continue;
+            }
+            reclaimed = reclaimed.saturating_add(amount);
+            voters.push(voter);
+        }
+        
+        let message = if voters.is_empty() {
+            "No expired rewards to reclaim".to_string()
+        } else {
+            let reward_pool = self.state.reward_pool.get().saturating_add(reclaimed);
+            self.state.reward_pool.set(reward_pool);
+            
+            let message = format!("Reclaimed {} unclaimed rewards from {} voters", reclaimed, voters.len());
+            self.emit_oracle_event(OracleEvent::UnclaimedRewardsReclaimed {
+                voters,
+                amount: reclaimed,
+                reward_pool,
+            });
+            message
+        };
+        
+        OperationResponse::success_with_data(
+            message,
+            ResponseData {
+                voter_address: None,
+                query_id: None,
+                vote_count: Some(batch.remaining),
+                rewards_claimed: None,
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
+            }
+        )
+    }
+    
+    /// Rebuild the cached voter aggregates from a full scan (admin operation)
+    async fn refresh_statistics_operation(&mut self) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let caller_chain = self.runtime.chain_id();
+        
+        // Verify caller is admin
+        if !self.state.is_admin(&caller_chain).await {
+            return OperationResponse::error("Unauthorized: only admin can refresh statistics");
+        }
+        
+        let aggregates = match self.state.compute_voter_aggregates().await {
+            Ok(aggregates) => aggregates,
+            Err(e) => return OperationResponse::error(e),
+        };
+        let voters = aggregates.voters;
+        self.state.voter_aggregates.set(aggregates);
+        
+        OperationResponse::success(format!("Statistics refreshed from {} voters", voters))
+    }
+    
+    /// Resolve or expire at most `max` active queries (maintenance)
+    /// 
+    /// Walks the active set in ID order from the stored cursor so repeated
+    /// calls make progress within block limits. The number of queries still
+    /// to visit in this pass is returned in `vote_count`.
+    async fn settle_queries_operation(&mut self, max: usize) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::{OperationResponse, ResponseData};
+        
+        if max == 0 || max > 1000 {
+            return OperationResponse::error("Max must be between 1 and 1000");
+        }
+        
+        let active_queries = self.state.get_active_queries().await;
+        let cursor = *self.state.settle_cursor.get();
+        let batch = state::settle_batch(&active_queries, cursor, max);
+        
+        let current_time = self.runtime.system_time();
+        let grace_secs = self.state.get_parameters().await.resolution_grace_secs;
+        let mut resolved = 0;
+        let mut no_consensus = 0;
+        let mut expired = 0;
+        
+        for query_id in &batch.query_ids {
+            let query = match self.load_maintainable_query(*query_id).await {
+                Some(q) => q,
+                None => continue,
+            };
+            
+            match query.settlement_at(current_time, grace_secs) {
+                Some(state::Settlement::Resolve) => {
+                    let result = self.resolve_query(*query_id).await;
+                    if result.success {
+                        resolved += 1;
+                    } else if self.is_closed_without_consensus(*query_id).await {
+                        no_consensus += 1;
+                    } else {
+                        eprintln!("Warning: Failed to settle query {}: {}", query_id, result.message);
+                    }
+                }
+                Some(state::Settlement::Expire) => {
+                    match self.mark_query_expired(*query_id).await {
+                        Ok(()) => expired += 1,
+                        Err(e) => eprintln!("Warning: Failed to mark query {} as expired: {}", query_id, e),
+                    }
+                }
+                None => {}
+            }
+        }
+        
+        self.state.settle_cursor.set(batch.next_cursor);
+        
+        OperationResponse::success_with_data(
+            format!(
+                "Processed {} queries: resolved {}, no consensus {}, expired {}, {} remaining",
+                batch.query_ids.len(), resolved, no_consensus, expired, batch.remaining
+            ),
+            ResponseData {
+                voter_address: None,
+                query_id: None,
+                vote_count: Some(batch.remaining),
+                rewards_claimed: None,
+                query_result: None,
+                batch_claims: None,
+                expired_queries: None,
+                reward_distribution: None,
+                slash_distribution: None,
+            }
+        )
+    }
+    
     /// Manually expire a specific query (admin operation)
     async fn expire_query_operation(&mut self, query_id: u64) -> oracle_registry_v2::OperationResponse {
         use oracle_registry_v2::OperationResponse;
@@ -3780,6 +6722,108 @@ impl OracleRegistryV2Contract {
         }
     }
     
+    /// Mark several queries as expired (admin operation)
+    /// 
+    /// Each query goes through the same checks and stake unlocking as
+    /// ExpireQuery; failures are reported per query and skipped.
+    async fn expire_queries_operation(&mut self, query_ids: Vec<u64>) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::{ExpireQueryResult, OperationResponse, ResponseData};
+        
+        let caller_chain = self.runtime.chain_id();
+        
+        // Verify caller is admin
+        if !self.state.is_admin(&caller_chain).await {
+            return OperationResponse::error("Unauthorized: only admin can manually expire queries");
+        }
+        
+        if let Err(e) = state::validate_expire_queries(&query_ids) {
+            return OperationResponse::error(e);
+        }
+        
+        let requested = query_ids.len();
+        let mut results = Vec::with_capacity(requested);
+        for query_id in query_ids {
+            let error = self.mark_query_expired(query_id).await.err();
+            if let Some(e) = &error {
+                eprintln!("Warning: Failed to expire query {}: {}", query_id, e);
+            }
+            results.push(ExpireQueryResult { query_id, error });
+        }
+        
+        let expired = results.iter().filter(|result| result.error.is_none()).count();
+        
+        OperationResponse::success_with_data(
+            format!("Expired {} of {} queries", expired, requested),
+            ResponseData {
+                voter_address: None,
+                query_id: None,
+                vote_count: None,
+                rewards_claimed: None,
+                query_result: None,
+                batch_claims: None,
+                expired_queries: Some(results),
+                reward_distribution: None,
+                slash_distribution: None,
+            }
+        )
+    }
+    
+    /// Archive resolved/expired queries closed before the cutoff (admin operation)
+    /// 
+    /// Each query is replaced by an ArchivedQuery summary; its commits, votes
+    /// and vote count entries are dropped.
+    async fn archive_queries_operation(
+        &mut self,
+        before: linera_sdk::linera_base_types::Timestamp,
+    ) -> oracle_registry_v2::OperationResponse {
+        use oracle_registry_v2::OperationResponse;
+        
+        let caller_chain = self.runtime.chain_id();
+        
+        // Verify caller is admin
+        if !self.state.is_admin(&caller_chain).await {
+            return OperationResponse::error("Unauthorized: only admin can archive queries");
+        }
+        
+        let query_ids = match self.state.queries.indices().await {
+            Ok(ids) => ids,
+            Err(e) => return OperationResponse::error(format!("Failed to get query indices: {}", e)),
+        };
+        
+        let mut archived = 0u64;
+        for query_id in query_ids {
+            let query = match self.state.get_query(query_id).await {
+                Some(q) if q.is_archivable(before) => q,
+                _ => continue,
+            };
+            
+            // Archiving drops the locker list; wait until unlocking completes
+            if self.state.get_unlock_cursor(query_id).await.is_some() {
+                continue;
+            }
+            
+            // Store summary, then drop the heavy query data
+            if let Err(e) = self.state.archived_queries.insert(&query_id, query.to_archived()) {
+                eprintln!("Warning: Failed to archive query {}: {}", query_id, e);
+                continue;
+            }
+            for voter in query.votes.keys() {
+                let _ = self.state.votes.remove(&(query_id, *voter));
+            }
+            let _ = self.state.vote_counts.remove(&query_id);
+            let _ = self.state.queries.remove(&query_id);
+            
+            archived += 1;
+        }
+        
+        eprintln!("🗄️ Archived {} queries closed before {}", archived, before);
+        
+        OperationResponse::success(format!(
+            "Archived {} queries closed before {}",
+            archived, before
+        ))
+    }
+    
     /// Distribute rewards proportionally by voter power (stake × reputation)
     /// This is an alternative to strategy-based distribution
     async fn distribute_rewards_by_power(
@@ -3793,9 +6837,8 @@ impl OracleRegistryV2Contract {
             .ok_or("Query not resolved")?;
         
         // Get correct voters
-        let correct_voters: Vec<linera_sdk::linera_base_types::ChainId> = query.votes
-            .iter()
-            .filter(|(_, vote)| vote.value == final_result)
+        let correct_voters: Vec<linera_sdk::linera_base_types::ChainId> = query.tallied_votes()
+            .filter(|(_, vote)| final_result.matches_vote(&vote.value))
             .map(|(chain_id, _)| *chain_id)
             .collect();
         
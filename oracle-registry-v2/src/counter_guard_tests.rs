@@ -0,0 +1,38 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for overflow and underflow guards on protocol counters
+
+#[cfg(test)]
+mod tests {
+    use linera_sdk::linera_base_types::Timestamp;
+
+    use crate::fixtures::test_fixtures::voter_info;
+    use crate::state::{decrement_counter, increment_counter};
+
+    #[test]
+    fn test_deregister_from_zero_count_stays_zero() {
+        assert_eq!(decrement_counter(0, "voter_count"), 0);
+    }
+
+    #[test]
+    fn test_decrement_counter_normal_case() {
+        assert_eq!(decrement_counter(5, "voter_count"), 4);
+        assert_eq!(decrement_counter(1, "voter_count"), 0);
+    }
+
+    #[test]
+    fn test_increment_counter_pins_at_max() {
+        assert_eq!(increment_counter(0, "total_votes_submitted"), 1);
+        assert_eq!(increment_counter(u64::MAX - 1, "total_votes_submitted"), u64::MAX);
+        assert_eq!(increment_counter(u64::MAX, "total_votes_submitted"), u64::MAX);
+    }
+
+    #[test]
+    fn test_voter_vote_counter_saturates() {
+        let mut info = voter_info(1, 100, 50);
+        info.total_votes = u64::MAX;
+        info.record_vote(Timestamp::from(1));
+        assert_eq!(info.total_votes, u64::MAX);
+    }
+}
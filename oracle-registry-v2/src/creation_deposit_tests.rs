@@ -0,0 +1,119 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the query creation deposit
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{ProtocolParameters, QueryStatus};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::{Account, AccountOwner, Amount};
+
+    fn creator() -> AccountOwner {
+        AccountOwner::Address20([7; 20])
+    }
+
+    /// Three voters and a registry charging a 5-token creation deposit
+    fn with_deposit() -> TestHarness {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            query_creation_deposit: Amount::from_tokens(5),
+            ..ProtocolParameters::default()
+        });
+        harness.stub_token_app();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        harness
+    }
+
+    /// An admin-created query whose deposit was paid by `creator()`
+    fn with_query() -> (TestHarness, u64) {
+        let mut harness = with_deposit();
+        harness.sign_as(creator());
+        let created = harness.create_query(&["Yes", "No"], 3, 120);
+        assert!(created.success, "{}", created.message);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        (harness, query_id)
+    }
+
+    fn deposit(harness: &TestHarness, query_id: u64) -> Option<Amount> {
+        let deposit = harness.state().creation_deposits.get(&query_id).blocking_wait().expect("readable");
+        deposit.map(|deposit| deposit.amount)
+    }
+
+    fn pending(harness: &TestHarness) -> Amount {
+        harness.state().get_pending_rewards(&harness.admin).blocking_wait()
+    }
+
+    #[test]
+    fn test_deposit_collected_from_creator() {
+        let (harness, query_id) = with_query();
+
+        assert_eq!(harness.token_deposits(), vec![(creator(), Amount::from_tokens(5))]);
+        assert_eq!(deposit(&harness, query_id), Some(Amount::from_tokens(5)));
+    }
+
+    #[test]
+    fn test_creation_fails_without_deposit() {
+        let mut harness = with_deposit();
+        let next_query_id = *harness.state().next_query_id.get();
+
+        // Unsigned, so no token account to take the deposit from
+        harness.sign_as(None);
+        let created = harness.create_query(&["Yes", "No"], 3, 120);
+        assert!(!created.success);
+        assert!(created.message.contains("Failed to collect creation deposit"), "{}", created.message);
+
+        assert_eq!(*harness.state().next_query_id.get(), next_query_id);
+        assert!(harness.query(next_query_id).is_none());
+        assert!(harness.token_deposits().is_empty());
+    }
+
+    #[test]
+    fn test_deposit_refunded_on_resolution() {
+        let (mut harness, query_id) = with_query();
+        assert_eq!(deposit(&harness, query_id), Some(Amount::from_tokens(5)));
+
+        for id in 1..=3 {
+            assert!(harness.submit_vote(chain(id), query_id, "Yes").success);
+        }
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+
+        assert_eq!(deposit(&harness, query_id), None);
+        let payer = Account { chain_id: harness.admin, owner: creator() };
+        assert_eq!(harness.token_releases(), vec![(payer, Amount::from_tokens(5))]);
+        assert_eq!(pending(&harness), Amount::ZERO);
+
+        // The treasury only receives the protocol fee on the 10-token reward
+        assert!(*harness.state().protocol_treasury.get() < Amount::from_tokens(1));
+    }
+
+    #[test]
+    fn test_deposit_forfeited_on_expiry() {
+        let (mut harness, query_id) = with_query();
+        assert!(harness.submit_vote(chain(1), query_id, "Yes").success);
+        harness.advance_past_deadline(query_id);
+        let _ = harness.resolve(query_id);
+
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::Expired);
+        assert_eq!(deposit(&harness, query_id), None);
+        assert_eq!(pending(&harness), Amount::ZERO);
+        assert_eq!(*harness.state().protocol_treasury.get(), Amount::from_tokens(5));
+    }
+
+    #[test]
+    fn test_no_deposit_held_by_default() {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let created = harness.create_query(&["Yes", "No"], 3, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+
+        assert_eq!(deposit(&harness, query_id), None);
+        assert!(harness.token_deposits().is_empty());
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for rebating part of the protocol fee to query creators
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{ProtocolParameters, QueryStatus};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::Amount;
+
+    /// Three voters and an admin-created query with a 10-token reward
+    fn with_query(creator_rebate_bps: u32) -> (TestHarness, u64) {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            creator_rebate_bps,
+            ..ProtocolParameters::default()
+        });
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let created = harness.create_query(&["Yes", "No"], 3, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        (harness, query_id)
+    }
+
+    /// Resolve with every voter agreeing, returning the treasury afterwards
+    fn resolve_unanimously(harness: &mut TestHarness, query_id: u64) -> Amount {
+        for id in 1..=3 {
+            assert!(harness.submit_vote(chain(id), query_id, "Yes").success);
+        }
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        *harness.state().protocol_treasury.get()
+    }
+
+    fn pending(harness: &TestHarness) -> Amount {
+        harness.state().get_pending_rewards(&harness.admin).blocking_wait()
+    }
+
+    #[test]
+    fn test_resolved_query_rebates_creator() {
+        let (mut baseline, query_id) = with_query(0);
+        let full_treasury = resolve_unanimously(&mut baseline, query_id);
+        assert_eq!(pending(&baseline), Amount::ZERO);
+
+        let (mut harness, query_id) = with_query(5000);
+        let treasury = resolve_unanimously(&mut harness, query_id);
+
+        // 1% fee on 10 tokens, half of it rebated
+        let fee = Amount::from_millis(100);
+        let rebate = harness.params().creator_rebate(fee);
+        assert_eq!(rebate, Amount::from_millis(50));
+        assert_eq!(pending(&harness), rebate);
+        assert_eq!(treasury.saturating_add(rebate), full_treasury);
+        assert_eq!(*harness.state().reward_pool.get(), *baseline.state().reward_pool.get());
+    }
+
+    #[test]
+    fn test_expired_query_does_not_rebate_creator() {
+        let (mut harness, query_id) = with_query(5000);
+        assert!(harness.submit_vote(chain(1), query_id, "Yes").success);
+        harness.advance_past_deadline(query_id);
+        let _ = harness.resolve(query_id);
+
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::Expired);
+        assert_eq!(pending(&harness), Amount::ZERO);
+        assert_eq!(*harness.state().protocol_treasury.get(), Amount::ZERO);
+    }
+
+    #[test]
+    fn test_creator_rebate() {
+        let params = ProtocolParameters { creator_rebate_bps: 2500, ..ProtocolParameters::default() };
+        assert_eq!(params.creator_rebate(Amount::from_tokens(4)), Amount::from_tokens(1));
+        assert_eq!(ProtocolParameters::default().creator_rebate(Amount::from_tokens(4)), Amount::ZERO);
+
+        let capped = ProtocolParameters { creator_rebate_bps: 20000, ..ProtocolParameters::default() };
+        assert_eq!(capped.creator_rebate(Amount::from_tokens(4)), Amount::from_tokens(4));
+    }
+}
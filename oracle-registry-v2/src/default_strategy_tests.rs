@@ -0,0 +1,42 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the registry-wide default decision strategy
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::query;
+    use crate::state::{DecisionStrategy, ProtocolParameters};
+
+    #[test]
+    fn test_market_query_adopts_configured_default() {
+        let params = ProtocolParameters {
+            default_strategy: DecisionStrategy::WeightedByReputation,
+            ..ProtocolParameters::default()
+        };
+
+        // Market-created queries carry no strategy of their own
+        assert_eq!(params.strategy_or_default(None), DecisionStrategy::WeightedByReputation);
+        assert_eq!(
+            params.strategy_or_default(Some(DecisionStrategy::Median)),
+            DecisionStrategy::Median
+        );
+    }
+
+    #[test]
+    fn test_default_is_majority() {
+        assert_eq!(ProtocolParameters::default().default_strategy, DecisionStrategy::Majority);
+        assert_eq!(DecisionStrategy::default(), DecisionStrategy::Majority);
+    }
+
+    #[test]
+    fn test_incompatible_default_falls_back_at_resolution() {
+        let mut q = query(&["Yes", "No"]);
+        q.strategy = DecisionStrategy::Median;
+        assert_eq!(q.strategy_for_resolution(), DecisionStrategy::Majority);
+
+        let mut q = query(&["1", "2", "3"]);
+        q.strategy = DecisionStrategy::Median;
+        assert_eq!(q.strategy_for_resolution(), DecisionStrategy::Median);
+    }
+}
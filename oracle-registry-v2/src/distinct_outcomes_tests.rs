@@ -0,0 +1,43 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the per-query minimum of distinct voted outcomes
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, query};
+
+    #[test]
+    fn test_unanimous_votes_do_not_resolve_with_min_distinct_two() {
+        let mut q = query(&["Yes", "No"]);
+        q.min_distinct_voted_outcomes = Some(2);
+        add_vote(&mut q, 2, "Yes");
+        add_vote(&mut q, 3, "Yes");
+        add_vote(&mut q, 4, "Yes");
+
+        assert_eq!(q.distinct_voted_outcomes(), 1);
+        let err = q.check_distinct_voted_outcomes().unwrap_err();
+        assert!(err.contains("Only 1 distinct outcome(s) received votes, 2 required"));
+    }
+
+    #[test]
+    fn test_split_votes_resolve_with_min_distinct_two() {
+        let mut q = query(&["Yes", "No"]);
+        q.min_distinct_voted_outcomes = Some(2);
+        add_vote(&mut q, 2, "Yes");
+        add_vote(&mut q, 3, "No");
+        add_vote(&mut q, 4, "Yes");
+
+        assert_eq!(q.distinct_voted_outcomes(), 2);
+        assert!(q.check_distinct_voted_outcomes().is_ok());
+    }
+
+    #[test]
+    fn test_no_requirement_allows_unanimous_votes() {
+        let mut q = query(&["Yes", "No"]);
+        add_vote(&mut q, 2, "Yes");
+        add_vote(&mut q, 3, "Yes");
+
+        assert!(q.check_distinct_voted_outcomes().is_ok());
+    }
+}
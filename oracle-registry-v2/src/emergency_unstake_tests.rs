@@ -0,0 +1,68 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for emergency withdrawals against stake still locked on finalized queries
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::{Amount, ChainId};
+    use oracle_registry_v2::Operation;
+
+    /// A resolved query with more voters than one unlock batch, returning the
+    /// voter whose lock is still waiting for UnlockQueryStakes
+    fn resolved_with_pending_unlock() -> (TestHarness, ChainId) {
+        let voters = crate::state::UNLOCK_BATCH_SIZE as u8 + 1;
+        let mut harness = TestHarness::new();
+        harness.stub_token_app();
+        for id in 1..=voters {
+            assert!(harness.register(chain(id), 100).success);
+        }
+
+        let created = harness.create_query(&["Yes", "No"], 2, 120);
+        assert!(created.success, "{}", created.message);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        for id in 1..=voters {
+            let response = harness.submit_vote(chain(id), query_id, "Yes");
+            assert!(response.success, "{}", response.message);
+        }
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+
+        let still_locked = (1..=voters)
+            .map(chain)
+            .find(|voter| harness.voter(voter).unwrap().locked_stake > Amount::ZERO)
+            .expect("a lock left for UnlockQueryStakes");
+        (harness, still_locked)
+    }
+
+    #[test]
+    fn test_locks_on_finalized_queries_cannot_be_withdrawn() {
+        let (mut harness, voter) = resolved_with_pending_unlock();
+        let before = harness.voter(&voter).unwrap();
+
+        let response = harness.execute_as(voter, Operation::EmergencyWithdraw { amount: before.stake });
+        assert!(!response.success);
+        assert!(response.message.contains("Insufficient available stake"), "{}", response.message);
+        assert!(harness.token_releases().is_empty());
+
+        let after = harness.voter(&voter).unwrap();
+        assert_eq!(after.stake, before.stake);
+        assert_eq!(after.locked_stake, before.locked_stake);
+    }
+
+    #[test]
+    fn test_unlocked_stake_can_still_be_withdrawn() {
+        let (mut harness, voter) = resolved_with_pending_unlock();
+        let before = harness.voter(&voter).unwrap();
+        let available = before.stake.saturating_sub(before.locked_stake);
+
+        let response = harness.execute_as(voter, Operation::EmergencyWithdraw { amount: available });
+        assert!(response.success, "{}", response.message);
+
+        let after = harness.voter(&voter).unwrap();
+        assert_eq!(after.stake, before.locked_stake);
+        assert_eq!(after.locked_stake, before.locked_stake);
+    }
+}
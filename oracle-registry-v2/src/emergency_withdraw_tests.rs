@@ -0,0 +1,59 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for emergency withdrawal of locked stake with a treasury penalty
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, chain, query, voter_info};
+    use crate::state::ProtocolParameters;
+    use linera_sdk::linera_base_types::Amount;
+
+    #[test]
+    fn test_emergency_withdraw_penalizes_amount() {
+        let params = ProtocolParameters::default();
+        assert_eq!(params.emergency_unstake_penalty_bps, 1000);
+
+        let amount = Amount::from_tokens(200);
+        let penalty = params.emergency_unstake_penalty(amount);
+        assert_eq!(penalty, Amount::from_tokens(20));
+        assert_eq!(amount.saturating_sub(penalty), Amount::from_tokens(180));
+
+        let free = ProtocolParameters { emergency_unstake_penalty_bps: 0, ..ProtocolParameters::default() };
+        assert_eq!(free.emergency_unstake_penalty(amount), Amount::ZERO);
+    }
+
+    #[test]
+    fn test_emergency_withdraw_removes_voter_from_active_panel() {
+        let mut q = query(&["Yes", "No"]);
+        q.selected_voters = vec![chain(2), chain(3), chain(4)];
+        q.min_votes = 3;
+        add_vote(&mut q, 2, "Yes");
+        add_vote(&mut q, 3, "No");
+
+        let mut voter = voter_info(2, 1000, 50);
+        let locked = Amount::from_tokens(100);
+        voter.lock_stake(locked).unwrap();
+        assert!(q.stake_lockers().contains(&chain(2)));
+
+        assert!(q.withdraw_voter(&chain(2)));
+        voter.unlock_stake(locked).unwrap();
+
+        assert_eq!(q.selected_voters, vec![chain(3), chain(4)]);
+        assert!(!q.votes.contains_key(&chain(2)));
+        assert!(!q.stake_lockers().contains(&chain(2)));
+        assert_eq!(q.min_votes, 2);
+        assert_eq!(voter.locked_stake, Amount::ZERO);
+    }
+
+    #[test]
+    fn test_uninvolved_query_untouched() {
+        let mut q = query(&["Yes", "No"]);
+        q.selected_voters = vec![chain(3), chain(4)];
+        q.min_votes = 2;
+
+        assert!(!q.withdraw_voter(&chain(2)));
+        assert_eq!(q.selected_voters.len(), 2);
+        assert_eq!(q.min_votes, 2);
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for voters' opaque encrypted metadata blobs
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{self, MAX_ENCRYPTED_METADATA_LEN};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::{Operation, OperationResponse};
+
+    fn register_with_blob(harness: &mut TestHarness, id: u8, blob: Option<Vec<u8>>) -> OperationResponse {
+        harness.execute_as(chain(id), Operation::RegisterVoter {
+            stake: Amount::from_tokens(100),
+            name: None,
+            metadata_url: None,
+            referral: None,
+            encrypted_metadata: blob,
+        })
+    }
+
+    fn set_blob(harness: &mut TestHarness, id: u8, blob: Option<Vec<u8>>) -> OperationResponse {
+        harness.execute_as(chain(id), Operation::SetEncryptedMetadata { encrypted_metadata: blob })
+    }
+
+    #[test]
+    fn test_blob_set_at_registration_is_stored_verbatim() {
+        let mut harness = TestHarness::new();
+        let blob = vec![0x00, 0x8f, 0x3a, 0xff];
+        let response = register_with_blob(&mut harness, 1, Some(blob.clone()));
+        assert!(response.success, "{}", response.message);
+
+        let voter = harness.voter(&chain(1)).unwrap();
+        assert_eq!(voter.encrypted_metadata, Some(blob.clone()));
+        assert_eq!(state::bytes_to_hex(&blob), "008f3aff");
+    }
+
+    #[test]
+    fn test_blob_can_be_replaced_and_cleared() {
+        let mut harness = TestHarness::new();
+        assert!(harness.register(chain(1), 100).success);
+        assert_eq!(harness.voter(&chain(1)).unwrap().encrypted_metadata, None);
+
+        let blob = vec![7u8; MAX_ENCRYPTED_METADATA_LEN];
+        let response = set_blob(&mut harness, 1, Some(blob.clone()));
+        assert!(response.success, "{}", response.message);
+        assert_eq!(harness.voter(&chain(1)).unwrap().encrypted_metadata, Some(blob));
+
+        let response = set_blob(&mut harness, 1, None);
+        assert!(response.success, "{}", response.message);
+        assert_eq!(response.message, "Encrypted metadata cleared");
+        assert_eq!(harness.voter(&chain(1)).unwrap().encrypted_metadata, None);
+    }
+
+    #[test]
+    fn test_oversized_blob_is_rejected() {
+        let mut harness = TestHarness::new();
+        let oversized = vec![1u8; MAX_ENCRYPTED_METADATA_LEN + 1];
+
+        let response = register_with_blob(&mut harness, 1, Some(oversized.clone()));
+        assert!(!response.success);
+        assert!(response.message.contains("Encrypted metadata too large"), "{}", response.message);
+        assert!(harness.voter(&chain(1)).is_none());
+
+        assert!(harness.register(chain(2), 100).success);
+        let response = set_blob(&mut harness, 2, Some(oversized));
+        assert!(!response.success);
+        assert!(response.message.contains("Encrypted metadata too large"), "{}", response.message);
+        assert_eq!(harness.voter(&chain(2)).unwrap().encrypted_metadata, None);
+
+        let response = set_blob(&mut harness, 2, Some(Vec::new()));
+        assert!(response.message.contains("cannot be empty"), "{}", response.message);
+    }
+
+    #[test]
+    fn test_unregistered_chain_cannot_set_blob() {
+        let mut harness = TestHarness::new();
+        let response = set_blob(&mut harness, 3, Some(vec![1, 2, 3]));
+        assert!(!response.success);
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let blob = vec![0xde, 0xad, 0xbe, 0xef];
+        let hex = state::bytes_to_hex(&blob);
+        assert_eq!(state::hex_to_bytes(&hex).unwrap(), blob);
+        assert_eq!(state::hex_to_bytes("0xDEADBEEF").unwrap(), blob);
+        assert!(state::hex_to_bytes("abc").unwrap_err().contains("even number"));
+        assert!(state::hex_to_bytes("zz").unwrap_err().contains("not a hex digit"));
+    }
+}
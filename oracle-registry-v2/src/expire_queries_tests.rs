@@ -0,0 +1,82 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for expiring several queries in one operation
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{validate_expire_queries, QueryStatus, MAX_EXPIRE_QUERIES};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::Operation;
+
+    fn create(harness: &mut TestHarness) -> u64 {
+        let created = harness.create_query(&["Yes", "No"], 3, 120);
+        created.data.and_then(|data| data.query_id).expect("query id")
+    }
+
+    #[test]
+    fn test_expires_valid_ids_and_reports_the_rest() {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let stale = create(&mut harness);
+        let resolved = create(&mut harness);
+        let unvoted = create(&mut harness);
+
+        // chain(1) alone votes on the stale query; everyone votes on the resolved one
+        assert!(harness.submit_vote(chain(1), stale, "Yes").success);
+        for id in 1..=3 {
+            assert!(harness.submit_vote(chain(id), resolved, "No").success);
+        }
+        harness.advance_past_deadline(unvoted);
+        let response = harness.resolve(resolved);
+        assert!(response.success, "{}", response.message);
+        assert!(harness.voter(&chain(1)).unwrap().locked_stake > Amount::ZERO);
+
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::ExpireQueries {
+            query_ids: vec![stale, resolved, unvoted, 99],
+        });
+        assert!(response.success, "{}", response.message);
+        assert_eq!(response.message, "Expired 2 of 4 queries");
+
+        let results = response.data.and_then(|data| data.expired_queries).expect("per-query results");
+        assert_eq!(results.iter().map(|result| result.query_id).collect::<Vec<_>>(), vec![stale, resolved, unvoted, 99]);
+        assert!(results[0].error.is_none());
+        assert!(results[1].error.as_deref().unwrap().contains("is not active"));
+        assert!(results[2].error.is_none());
+        assert!(results[3].error.as_deref().unwrap().contains("not found"));
+
+        assert_eq!(harness.query(stale).unwrap().status, QueryStatus::Expired);
+        assert_eq!(harness.query(resolved).unwrap().status, QueryStatus::Resolved);
+        assert_eq!(harness.query(unvoted).unwrap().status, QueryStatus::Expired);
+        assert_eq!(harness.voter(&chain(1)).unwrap().locked_stake, Amount::ZERO);
+    }
+
+    #[test]
+    fn test_only_admin_may_expire_queries() {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let query_id = create(&mut harness);
+        harness.advance_past_deadline(query_id);
+
+        let response = harness.execute_as(chain(1), Operation::ExpireQueries { query_ids: vec![query_id] });
+        assert!(!response.success);
+        assert!(response.message.contains("Unauthorized"));
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::Active);
+    }
+
+    #[test]
+    fn test_expire_list_is_limited() {
+        assert!(validate_expire_queries(&[1, 2, 3]).is_ok());
+        assert!(validate_expire_queries(&[]).is_err());
+        assert!(validate_expire_queries(&[4, 4]).is_err());
+
+        let too_many: Vec<u64> = (0..=MAX_EXPIRE_QUERIES as u64).collect();
+        assert!(validate_expire_queries(&too_many).is_err());
+    }
+}
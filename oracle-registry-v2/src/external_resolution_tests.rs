@@ -0,0 +1,96 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for resolving designated queries from external data
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{QueryStatus, ResolutionResult};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::{Operation, OperationResponse};
+
+    const SOURCE: &str = "https://example.com/results/42";
+
+    /// Three voters and a query with a vote from chain(1), designated for external resolution if `allowed`
+    fn voted(allowed: bool) -> (TestHarness, u64) {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let created = harness.create_query(&["Yes", "No"], 3, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        assert!(harness.submit_vote(chain(1), query_id, "No").success);
+
+        if allowed {
+            let admin = harness.admin;
+            let response = harness.execute_as(admin, Operation::SetExternalResolution { query_id, allowed });
+            assert!(response.success, "{}", response.message);
+        }
+        (harness, query_id)
+    }
+
+    fn resolve_externally(harness: &mut TestHarness, sender: u8, query_id: u64, outcome: &str) -> OperationResponse {
+        let sender = if sender == 0 { harness.admin } else { chain(sender) };
+        harness.execute_as(sender, Operation::ResolveWithExternalData {
+            query_id,
+            outcome: outcome.to_string(),
+            source: SOURCE.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_external_resolution_records_source() {
+        let (mut harness, query_id) = voted(true);
+        assert!(harness.voter(&chain(1)).unwrap().locked_stake > Amount::ZERO);
+
+        // No need to wait for the deadline or enough votes
+        let response = resolve_externally(&mut harness, 0, query_id, "Yes");
+        assert!(response.success, "{}", response.message);
+
+        let query = harness.query(query_id).unwrap();
+        assert_eq!(query.status, QueryStatus::Resolved);
+        assert_eq!(query.result, Some(ResolutionResult::Outcome("Yes".to_string())));
+        assert_eq!(query.resolution_source.as_deref(), Some(SOURCE));
+        assert!(!harness.state().get_active_queries().blocking_wait().contains(&query_id));
+
+        // The voter is released without being slashed for voting "No"
+        let voter = harness.voter(&chain(1)).unwrap();
+        assert_eq!(voter.locked_stake, Amount::ZERO);
+        assert_eq!(voter.stake, Amount::from_tokens(100));
+
+        // Already resolved
+        assert!(!resolve_externally(&mut harness, 0, query_id, "Yes").success);
+    }
+
+    #[test]
+    fn test_voting_only_query_rejects_external_resolution() {
+        let (mut harness, query_id) = voted(false);
+
+        let response = resolve_externally(&mut harness, 0, query_id, "Yes");
+        assert!(!response.success);
+        assert!(response.message.contains("is voting-only"));
+
+        let query = harness.query(query_id).unwrap();
+        assert_eq!(query.status, QueryStatus::Active);
+        assert!(query.resolution_source.is_none());
+    }
+
+    #[test]
+    fn test_external_resolution_requires_admin_and_valid_outcome() {
+        let (mut harness, query_id) = voted(true);
+
+        let response = resolve_externally(&mut harness, 1, query_id, "Yes");
+        assert!(!response.success);
+        assert!(response.message.contains("Unauthorized"));
+
+        let response = resolve_externally(&mut harness, 0, query_id, "Maybe");
+        assert!(!response.success);
+        assert!(response.message.contains("is not an outcome"));
+
+        let response = harness.execute_as(chain(1), Operation::SetExternalResolution { query_id, allowed: false });
+        assert!(!response.success);
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::Active);
+    }
+}
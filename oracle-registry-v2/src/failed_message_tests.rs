@@ -0,0 +1,53 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for logging failed cross-chain messages
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::chain;
+    use crate::state::{failed_message_evicted_id, FailedMessage, MAX_FAILED_MESSAGES};
+    use crate::Message;
+    use linera_sdk::linera_base_types::{Amount, Timestamp};
+
+    #[test]
+    fn test_invalid_vote_message_is_recorded() {
+        // Vote on a query that doesn't exist
+        let message = Message::SubmitVote {
+            sender_chain: chain(2),
+            query_id: 404,
+            value: "Yes".to_string(),
+            confidence: None,
+        };
+
+        let failed = FailedMessage {
+            id: 0,
+            kind: message.kind().to_string(),
+            sender: message.sender_chain(),
+            error: "Query 404 not found".to_string(),
+            failed_at: Timestamp::from(1_000_000),
+        };
+
+        assert_eq!(failed.kind, "SubmitVote");
+        assert_eq!(failed.sender, Some(chain(2)));
+        assert!(failed.error.contains("404"));
+    }
+
+    #[test]
+    fn test_sender_missing_from_payload() {
+        assert_eq!(Message::ClaimRewards.sender_chain(), None);
+        assert_eq!(Message::ClaimRewards.kind(), "ClaimRewards");
+
+        let message = Message::WithdrawStake { amount: Amount::from_tokens(1) };
+        assert_eq!(message.sender_chain(), None);
+        assert_eq!(message.kind(), "WithdrawStake");
+    }
+
+    #[test]
+    fn test_log_is_bounded() {
+        assert_eq!(failed_message_evicted_id(0), None);
+        assert_eq!(failed_message_evicted_id(MAX_FAILED_MESSAGES - 1), None);
+        assert_eq!(failed_message_evicted_id(MAX_FAILED_MESSAGES), Some(0));
+        assert_eq!(failed_message_evicted_id(MAX_FAILED_MESSAGES + 5), Some(5));
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Plain-data fixtures for unit tests that don't need view storage
+
+#[cfg(test)]
+pub mod test_fixtures {
+    use crate::state::{DecisionStrategy, Query, QueryStatus, TieBreak, Vote, VoterInfo, VotingPhase};
+    use linera_sdk::linera_base_types::{Amount, ChainId, Timestamp};
+    use std::collections::BTreeMap;
+    
+    /// Create a distinct chain ID per participant
+    pub fn chain(id: u8) -> ChainId {
+        format!("{:064x}", id).parse().expect("valid chain ID")
+    }
+    
+    /// Create an active voter on `chain(id)` with the given stake and reputation
+    pub fn voter_info(id: u8, stake_tokens: u128, reputation: u32) -> VoterInfo {
+        VoterInfo {
+            chain_id: chain(id),
+            stake: Amount::from_tokens(stake_tokens),
+            locked_stake: Amount::ZERO,
+            reputation,
+            total_votes: 0,
+            correct_votes: 0,
+            registered_at: Timestamp::from(0),
+            last_active_at: Timestamp::from(0),
+            last_vote_at: None,
+            is_active: true,
+            name: None,
+            metadata_url: None,
+            referral: None,
+            calibration_score: None,
+            calibrated_votes: 0,
+            encrypted_metadata: None,
+        }
+    }
+    
+    /// Create a revealed vote from `chain(id)`
+    pub fn vote(id: u8, value: &str) -> Vote {
+        Vote {
+            voter: chain(id),
+            value: value.to_string(),
+            timestamp: Timestamp::from(1_000_000),
+            salt: None,
+            confidence: None,
+        }
+    }
+    
+    /// Create an active Majority query in commit phase, created by `chain(1)`
+    /// 
+    /// Commit phase ends at 1s, reveal phase at 2s and the deadline is 3s.
+    pub fn query(outcomes: &[&str]) -> Query {
+        Query {
+            id: 7,
+            description: "Test query".to_string(),
+            outcomes: outcomes.iter().map(|o| o.to_string()).collect(),
+            numeric_outcomes: None,
+            outcome_aliases: BTreeMap::new(),
+            strategy: DecisionStrategy::Majority,
+            min_votes: 1,
+            reward_basis: None,
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            allow_external_resolution: false,
+            tie_break: TieBreak::OutcomeOrder,
+            category: None,
+            tags: Vec::new(),
+            reward_amount: Amount::from_tokens(100),
+            creator: chain(1),
+            created_at: Timestamp::from(0),
+            deadline: Timestamp::from(3_000_000),
+            commit_phase_end: Timestamp::from(1_000_000),
+            reveal_phase_end: Timestamp::from(2_000_000),
+            phase: VotingPhase::Commit,
+            status: QueryStatus::Active,
+            result: None,
+            result_display: None,
+            resolved_at: None,
+            resolution_source: None,
+            commits: BTreeMap::new(),
+            votes: BTreeMap::new(),
+            selected_voters: vec![],
+            max_voters: 10,
+            callback_chain: None,
+            callback_data: None,
+        }
+    }
+    
+    /// Add `vote(id, value)` to the query
+    pub fn add_vote(query: &mut Query, id: u8, value: &str) {
+        query.votes.insert(chain(id), vote(id, value));
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for forfeiting locked stake to exit immediately
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::Operation;
+
+    /// Three 100-token voters, with chain(1) voting on an active query
+    fn voted() -> (TestHarness, u64) {
+        let mut harness = TestHarness::new();
+        harness.stub_token_app();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+
+        let created = harness.create_query(&["Yes", "No"], 2, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        assert!(harness.submit_vote(chain(1), query_id, "No").success);
+        (harness, query_id)
+    }
+
+    #[test]
+    fn test_forfeit_and_exit_sends_locked_stake_to_treasury() {
+        let (mut harness, query_id) = voted();
+        let locked = harness.voter(&chain(1)).unwrap().locked_stake;
+        assert!(locked > Amount::ZERO);
+        let voter_count = *harness.state().voter_count.get();
+
+        let response = harness.execute_as(chain(1), Operation::ForfeitAndExit);
+        assert!(response.success, "{}", response.message);
+
+        assert!(harness.voter(&chain(1)).is_none());
+        assert_eq!(*harness.state().voter_count.get(), voter_count - 1);
+        assert_eq!(*harness.state().protocol_treasury.get(), locked);
+        assert_eq!(*harness.state().total_stake.get(), Amount::from_tokens(200));
+
+        let query = harness.query(query_id).unwrap();
+        assert!(!query.votes.contains_key(&chain(1)));
+        assert!(!query.selected_voters.contains(&chain(1)));
+        let lock = harness.state().stake_locks.get(&(query_id, chain(1))).blocking_wait();
+        assert_eq!(lock.expect("readable"), None);
+    }
+
+    #[test]
+    fn test_forfeited_vote_counts_as_non_participation() {
+        let (mut harness, query_id) = voted();
+        assert!(harness.execute_as(chain(1), Operation::ForfeitAndExit).success);
+
+        assert!(harness.submit_vote(chain(2), query_id, "Yes").success);
+        assert!(harness.submit_vote(chain(3), query_id, "Yes").success);
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        assert_eq!(harness.query(query_id).unwrap().result_display.as_deref(), Some("Yes"));
+    }
+
+    #[test]
+    fn test_forfeit_without_locks_returns_whole_stake() {
+        let (mut harness, _) = voted();
+
+        let response = harness.execute_as(chain(2), Operation::ForfeitAndExit);
+        assert!(response.success, "{}", response.message);
+        assert!(harness.voter(&chain(2)).is_none());
+        assert_eq!(*harness.state().protocol_treasury.get(), Amount::ZERO);
+    }
+
+    #[test]
+    fn test_locks_on_finalized_queries_are_returned_not_forfeited() {
+        // More voters than one unlock batch, so a lock outlives the resolution
+        let voters = crate::state::UNLOCK_BATCH_SIZE as u8 + 1;
+        let mut harness = TestHarness::new();
+        harness.stub_token_app();
+        for id in 1..=voters {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let created = harness.create_query(&["Yes", "No"], 2, 120);
+        let finalized = created.data.and_then(|data| data.query_id).expect("query id");
+        for id in 1..=voters {
+            assert!(harness.submit_vote(chain(id), finalized, "Yes").success);
+        }
+        harness.advance_past_deadline(finalized);
+        let response = harness.resolve(finalized);
+        assert!(response.success, "{}", response.message);
+        let voter = (1..=voters)
+            .map(chain)
+            .find(|voter| harness.state().stake_locks.get(&(finalized, *voter)).blocking_wait().unwrap().is_some())
+            .expect("a lock left for UnlockQueryStakes");
+        // Unclaimed rewards from the resolution would block the exit
+        harness.contract.state.pending_rewards.remove(&voter).expect("Failed to clear rewards");
+
+        // The voter also holds a lock on an active query
+        let created = harness.create_query(&["Yes", "No"], 2, 120);
+        let active = created.data.and_then(|data| data.query_id).expect("query id");
+        assert!(harness.submit_vote(voter, active, "No").success);
+        let active_lock = harness.state().stake_locks.get(&(active, voter)).blocking_wait().unwrap().expect("active lock");
+        let stake = harness.voter(&voter).unwrap().stake;
+        let treasury = *harness.state().protocol_treasury.get();
+
+        let response = harness.execute_as(voter, Operation::ForfeitAndExit);
+        assert!(response.success, "{}", response.message);
+        assert_eq!(*harness.state().protocol_treasury.get(), treasury.saturating_add(active_lock));
+        let (account, returned) = *harness.token_releases().last().expect("stake returned");
+        assert_eq!(account.chain_id, voter);
+        assert_eq!(returned, stake.saturating_sub(active_lock));
+        assert_eq!(harness.state().stake_locks.get(&(finalized, voter)).blocking_wait().unwrap(), None);
+
+        // Finishing the unlock skips the voter who already left
+        let response = harness.execute_as(chain(1), Operation::UnlockQueryStakes { query_id: finalized, max: 50 });
+        assert!(response.success, "{}", response.message);
+        assert!(harness.voter(&voter).is_none());
+    }
+
+    #[test]
+    fn test_forfeit_requires_claiming_rewards_first() {
+        let (mut harness, _) = voted();
+        harness.credit_rewards(chain(1), Amount::from_tokens(5));
+
+        let response = harness.execute_as(chain(1), Operation::ForfeitAndExit);
+        assert!(!response.success);
+        assert!(response.message.contains("pending rewards must be claimed first"));
+        assert!(harness.voter(&chain(1)).is_some());
+    }
+}
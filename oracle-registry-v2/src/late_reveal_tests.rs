@@ -0,0 +1,142 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for down-weighting reveals that land in the grace window
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{ProtocolParameters, QueryStatus, ResolutionResult, Vote};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::{ChainId, TimeDelta, Timestamp};
+    use oracle_registry_v2::Operation;
+
+    /// Three voters committed on a 120-second query: chain(1) backs Yes,
+    /// chain(2) and chain(3) back No
+    fn committed(late_reveal_weight_bps: u32) -> (TestHarness, u64) {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            late_reveal_weight_bps,
+            ..ProtocolParameters::default()
+        });
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+
+        let created = harness.create_query(&["Yes", "No"], 3, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        for (voter, value) in ballots() {
+            let hash = harness.params().commit_hash_algo.compute(value, &salt(voter));
+            let response = harness.commit_vote(voter, query_id, &hash);
+            assert!(response.success, "{}", response.message);
+        }
+        (harness, query_id)
+    }
+
+    fn ballots() -> [(ChainId, &'static str); 3] {
+        [(chain(1), "Yes"), (chain(2), "No"), (chain(3), "No")]
+    }
+
+    fn salt(voter: ChainId) -> String {
+        format!("salt-{}", voter)
+    }
+
+    /// Reveal Yes on time and both No votes in the grace window, then resolve
+    fn resolve_with_late_no_votes(late_reveal_weight_bps: u32) -> ResolutionResult {
+        let (mut harness, query_id) = committed(late_reveal_weight_bps);
+
+        // Reveal phase runs from 60s to 120s, followed by the grace window
+        harness.set_time(Timestamp::from(90_000_000));
+        let response = harness.reveal_vote(chain(1), query_id, "Yes", &salt(chain(1)));
+        assert_eq!(response.message, "Vote revealed successfully");
+
+        harness.set_time(Timestamp::from(130_000_000));
+        for (voter, value) in &ballots()[1..] {
+            let response = harness.reveal_vote(*voter, query_id, value, &salt(*voter));
+            assert!(response.success, "{}", response.message);
+            assert!(response.message.contains("revealed after the reveal phase"));
+        }
+
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        harness.query(query_id).unwrap().result.expect("resolved result")
+    }
+
+    #[test]
+    fn test_full_weight_late_reveals_count_like_on_time_reveals() {
+        assert_eq!(resolve_with_late_no_votes(10000), ResolutionResult::Outcome("No".to_string()));
+    }
+
+    #[test]
+    fn test_down_weighted_late_reveals_lose_to_on_time_reveal() {
+        // Two late No votes at 40% weigh 8000 against the on-time Yes at 10000
+        assert_eq!(resolve_with_late_no_votes(4000), ResolutionResult::Outcome("Yes".to_string()));
+    }
+
+    #[test]
+    fn test_query_short_of_votes_is_not_expired_in_grace_window() {
+        let (mut harness, query_id) = committed(10000);
+        harness.set_time(Timestamp::from(90_000_000));
+        assert!(harness.reveal_vote(chain(1), query_id, "Yes", &salt(chain(1))).success);
+
+        // Past the deadline with 1 of 3 votes, but still inside the grace window
+        harness.set_time(Timestamp::from(130_000_000));
+        let response = harness.resolve(query_id);
+        assert!(!response.success);
+        assert!(response.message.contains("grace window"), "{}", response.message);
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::ExpireQuery { query_id });
+        assert!(!response.success);
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::Active);
+
+        // Late reveals lift the query to min_votes, so it resolves instead
+        for (voter, value) in &ballots()[1..] {
+            let response = harness.reveal_vote(*voter, query_id, value, &salt(*voter));
+            assert!(response.success, "{}", response.message);
+        }
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::Resolved);
+    }
+
+    #[test]
+    fn test_reveal_after_grace_window_is_rejected() {
+        let (mut harness, query_id) = committed(5000);
+        let query = harness.query(query_id).unwrap();
+        let grace_secs = harness.params().resolution_grace_secs;
+        harness.set_time(query.resolution_opens_at(grace_secs));
+
+        let response = harness.reveal_vote(chain(1), query_id, "Yes", &salt(chain(1)));
+        assert!(!response.success);
+        assert!(response.message.contains("Reveal phase has ended"));
+    }
+
+    #[test]
+    fn test_vote_weight() {
+        let (harness, query_id) = committed(5000);
+        let query = harness.query(query_id).unwrap();
+        let vote_at = |timestamp: Timestamp, salt: Option<&str>| Vote {
+            voter: chain(1),
+            value: "Yes".to_string(),
+            timestamp,
+            salt: salt.map(str::to_string),
+            confidence: None,
+        };
+        let late = query.reveal_phase_end.saturating_add(TimeDelta::from_secs(1));
+
+        assert_eq!(query.vote_weight_bps(&vote_at(query.reveal_phase_end, Some("s")), 5000), 10000);
+        assert_eq!(query.vote_weight_bps(&vote_at(late, Some("s")), 5000), 5000);
+        assert_eq!(query.vote_weight_bps(&vote_at(late, None), 5000), 10000);
+        assert_eq!(query.vote_weight_bps(&vote_at(late, Some("s")), 20000), 10000);
+    }
+
+    #[test]
+    fn test_weighted_median_follows_weights() {
+        let equal = [(10.0, 10000), (20.0, 10000), (30.0, 10000)];
+        assert_eq!(ResolutionResult::from_weighted_median(equal), ResolutionResult::NumericMedian(20.0));
+
+        // Down-weighting the high estimates moves the median to the on-time one
+        let skewed = [(10.0, 10000), (20.0, 2000), (30.0, 2000)];
+        assert_eq!(ResolutionResult::from_weighted_median(skewed), ResolutionResult::NumericMedian(10.0));
+    }
+}
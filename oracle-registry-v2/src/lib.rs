@@ -18,12 +18,14 @@
 pub mod state;
 pub mod migration;
 
-/// Stream name for Oracle events - used for cross-chain event subscription
-pub const ORACLE_STREAM_NAME: &str = "oracle_events";
+// Oracle events live in alethea-oracle-types so subscribers need not depend on the registry
+pub use alethea_oracle_types::events::{OracleEvent, ORACLE_STREAM_NAME};
 
 // NOTE: Unit tests are temporarily disabled due to Linera SDK test infrastructure complexity.
 // The contract and service code compiles and works correctly in production.
 // Use `linera project test` for integration testing with actual chain contexts.
+// Operation-level tests can use `TestHarness` (src/test_support.rs), which is
+// compiled with the contract binary; voter_registration_tests is declared there.
 
 // #[cfg(test)]
 // mod test_utils;
@@ -58,9 +60,6 @@ pub const ORACLE_STREAM_NAME: &str = "oracle_events";
 // #[cfg(test)]
 // mod admin_authorization_tests;
 
-// #[cfg(test)]
-// mod voter_registration_tests;
-
 // #[cfg(test)]
 // mod stake_management_tests;
 
@@ -91,134 +90,185 @@ pub const ORACLE_STREAM_NAME: &str = "oracle_events";
 // #[cfg(test)]
 // mod migration_tests;
 
+#[cfg(test)]
+mod abstain_tests;
+
+#[cfg(test)]
+mod actionable_queries_tests;
+
+#[cfg(test)]
+mod advance_phase_tests;
+
+#[cfg(test)]
+mod archive_tests;
+
+#[cfg(test)]
+mod calibration_tests;
+
+#[cfg(test)]
+mod cancel_query_tests;
+
+#[cfg(test)]
+mod chain_id_parsing_tests;
+
+#[cfg(test)]
+mod chain_time_tests;
+
+#[cfg(test)]
+mod claim_eligibility_tests;
+
+#[cfg(test)]
+mod claim_rewards_to_tests;
+
+#[cfg(test)]
+mod commit_hash_algo_tests;
+
+#[cfg(test)]
+mod commit_verification_tests;
+
+#[cfg(test)]
+mod confidence_median_tests;
+
+#[cfg(test)]
+mod counter_guard_tests;
+
+#[cfg(test)]
+mod default_strategy_tests;
+
+#[cfg(test)]
+mod distinct_outcomes_tests;
+
+#[cfg(test)]
+mod emergency_withdraw_tests;
+
+#[cfg(test)]
+mod failed_message_tests;
+
+#[cfg(test)]
+mod fixtures;
+
+#[cfg(test)]
+mod max_query_reward_tests;
+
+#[cfg(test)]
+mod message_compat_tests;
+
+#[cfg(test)]
+mod queries_by_creator_tests;
+
+#[cfg(test)]
+mod query_labels_tests;
+
+#[cfg(test)]
+mod query_panel_tests;
+
+#[cfg(test)]
+mod query_result_read_tests;
+
+#[cfg(test)]
+mod referral_tests;
+
+#[cfg(test)]
+mod register_message_tests;
+
+#[cfg(test)]
+mod reputation_bootstrap_tests;
+
+#[cfg(test)]
+mod resolution_grace_tests;
+
+#[cfg(test)]
+mod resolution_result_tests;
+
+#[cfg(test)]
+mod reward_basis_tests;
+
+#[cfg(test)]
+mod reward_estimate_tests;
+
+#[cfg(test)]
+mod reward_history_tests;
+
+#[cfg(test)]
+mod reward_ordering_tests;
+
+#[cfg(test)]
+mod reward_split_tests;
+
+#[cfg(test)]
+mod selection_cooldown_tests;
+
+#[cfg(test)]
+mod settle_queries_tests;
+
+#[cfg(test)]
+mod solvency_tests;
+
+#[cfg(test)]
+mod stake_unlock_tests;
+
+#[cfg(test)]
+mod stale_active_query_tests;
+
+#[cfg(test)]
+mod statistics_cache_tests;
+
+#[cfg(test)]
+mod strategy_update_tests;
+
+#[cfg(test)]
+mod vote_change_tests;
+
+#[cfg(test)]
+mod vote_count_tests;
+
+#[cfg(test)]
+mod vote_interval_tests;
+
+#[cfg(test)]
+mod vote_weight_tests;
+
+#[cfg(test)]
+mod voter_activity_tests;
+
+#[cfg(test)]
+mod voter_attestation_tests;
+
+#[cfg(test)]
+mod voter_comparison_tests;
+
+#[cfg(test)]
+mod voter_dashboard_tests;
+
+#[cfg(test)]
+mod voter_query_status_tests;
+
+#[cfg(test)]
+mod voter_selection_tests;
+
+#[cfg(test)]
+mod voters_by_address_tests;
+
+#[cfg(test)]
+mod winner_votes_tests;
+
 use async_graphql::{Request, Response, SimpleObject};
 use linera_sdk::linera_base_types::{Amount, Timestamp, ContractAbi, ServiceAbi, ChainId};
 use serde::{Deserialize, Serialize};
 use state::{DecisionStrategy, ProtocolParameters};
 
-// ==================== ORACLE EVENTS (Cross-Chain Streaming) ====================
-
-/// Oracle events for real-time cross-chain notifications
-/// 
-/// These events are emitted using `runtime.emit()` and can be received by
-/// other chains that subscribe using `runtime.subscribe_to_events()`.
-/// 
-/// ## Usage
-/// 
-/// To subscribe to oracle events from another contract:
-/// ```rust
-/// // In instantiate or any operation
-/// self.runtime.subscribe_to_events(
-///     oracle_chain_id,
-///     oracle_app_id,
-///     StreamName::from(ORACLE_STREAM_NAME),
-/// );
-/// ```
-/// 
-/// To process received events:
-/// ```rust
-/// async fn process_streams(&mut self, updates: Vec<StreamUpdate>) {
-///     for update in updates {
-///         for index in update.previous_index..update.next_index {
-///             let event: OracleEvent = self.runtime.read_event(
-///                 update.chain_id,
-///                 update.stream_id.stream_name.clone(),
-///                 index,
-///             );
-///             // Handle event...
-///         }
-///     }
-/// }
-/// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum OracleEvent {
-    /// Emitted when a new query is created
-    QueryCreated {
-        query_id: u64,
-        description: String,
-        outcomes: Vec<String>,
-        deadline: Timestamp,
-        creator: ChainId,
-        min_votes: usize,
-    },
-    
-    /// Emitted when a query is resolved
-    QueryResolved {
-        query_id: u64,
-        result: String,
-        resolved_at: Timestamp,
-        total_votes: usize,
-        correct_voters: usize,
-    },
-    
-    /// Emitted when a query expires without resolution
-    QueryExpired {
-        query_id: u64,
-        expired_at: Timestamp,
-        votes_received: usize,
-        min_votes_required: usize,
-    },
-    
-    /// Emitted when a voter registers
-    VoterRegistered {
-        voter_chain: ChainId,
-        stake: Amount,
-        name: Option<String>,
-    },
-    
-    /// Emitted when a voter deregisters
-    VoterDeregistered {
-        voter_chain: ChainId,
-        stake_returned: Amount,
-    },
-    
-    /// Emitted when a vote is committed (phase 1)
-    VoteCommitted {
-        query_id: u64,
-        voter_chain: ChainId,
-        commit_hash: String,
-    },
-    
-    /// Emitted when a vote is revealed (phase 2)
-    VoteRevealed {
-        query_id: u64,
-        voter_chain: ChainId,
-        value: String,
-    },
-    
-    /// Emitted when a direct vote is submitted (no commit/reveal)
-    VoteSubmitted {
-        query_id: u64,
-        voter_chain: ChainId,
-        value: String,
-    },
-    
-    /// Emitted when rewards are claimed
-    RewardsClaimed {
-        voter_chain: ChainId,
-        amount: Amount,
-    },
-    
-    /// Emitted when protocol parameters are updated
-    ParametersUpdated {
-        min_stake: Amount,
-        min_votes_default: usize,
-        updated_by: ChainId,
-    },
-    
-    /// Emitted when protocol is paused/unpaused
-    ProtocolStatusChanged {
-        is_paused: bool,
-        changed_by: ChainId,
-    },
-    
-    /// Emitted when stake is updated
-    StakeUpdated {
-        voter_chain: ChainId,
-        new_stake: Amount,
-        change: Amount,
-        is_increase: bool,
-    },
+/// Instantiation argument (`null` keeps the production defaults)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstantiationArgument {
+    /// Register the admin chain as a voter, so a single-operator test
+    /// deployment can create queries right away
+    #[serde(default)]
+    pub bootstrap_admin_as_voter: bool,
+    
+    /// Stake of the bootstrap voter (defaults to the minimum stake). It is
+    /// recorded without a token transfer, like the reward of a manual query.
+    #[serde(default)]
+    pub bootstrap_stake: Option<Amount>,
 }
 
 /// Application ABI
@@ -244,6 +294,12 @@ pub enum Operation {
         stake: Amount,
         name: Option<String>,
         metadata_url: Option<String>,
+        /// Referral/attribution code (letters, digits, '-', '_'; max 32)
+        #[serde(default)]
+        referral: Option<String>,
+        /// Opaque blob encrypted off-chain (max 1024 bytes)
+        #[serde(default)]
+        encrypted_metadata: Option<Vec<u8>>,
     },
     
     /// Register a voter on behalf of an address (admin operation for testing)
@@ -254,6 +310,9 @@ pub enum Operation {
         stake: Amount,
         name: Option<String>,
         metadata_url: Option<String>,
+        /// Referral/attribution code (letters, digits, '-', '_'; max 32)
+        #[serde(default)]
+        referral: Option<String>,
     },
     
     /// Add more stake
@@ -266,9 +325,29 @@ pub enum Operation {
         amount: Amount,
     },
     
+    /// Withdraw stake immediately, even if locked on active queries
+    /// 
+    /// The voter leaves those queries' panels and forfeits
+    /// `emergency_unstake_penalty_bps` of the amount to the treasury.
+    EmergencyWithdraw {
+        amount: Amount,
+    },
+    
+    /// Forfeit stake locked on active queries to the treasury and deregister immediately
+    /// 
+    /// The voter's votes on active queries are dropped as non-participation
+    /// and the rest of their stake, including locks on finalized queries, is
+    /// returned.
+    ForfeitAndExit,
+    
     /// Deregister as voter
     DeregisterVoter,
     
+    /// Replace the voter's encrypted metadata blob (None clears it)
+    SetEncryptedMetadata {
+        encrypted_metadata: Option<Vec<u8>>,
+    },
+    
     /// Create a new query/market
     /// 
     /// Parameters:
@@ -289,6 +368,110 @@ pub enum Operation {
         deadline: Option<Timestamp>,
         #[serde(default)]
         duration_secs: Option<u64>,
+        /// Minimum number of distinct outcomes that must receive votes
+        #[serde(default)]
+        min_distinct_voted_outcomes: Option<usize>,
+        /// Minimum number of votes the winning outcome needs to resolve
+        #[serde(default)]
+        min_votes_for_winner: Option<usize>,
+        /// Minimum number of distinct voter chains with tallied votes
+        #[serde(default)]
+        min_distinct_voters: Option<usize>,
+        /// Largest share of the voting power one address-prefix group may hold (basis points)
+        #[serde(default)]
+        max_group_power_bps: Option<u32>,
+        /// Minimum stake a voter needs to vote on this query
+        #[serde(default)]
+        min_voter_stake: Option<Amount>,
+        /// Let voters vote "Abstain": counted as participation, left out of the result
+        #[serde(default)]
+        allow_abstain: bool,
+        /// Let every outcome holding the multi-winner threshold of the tally win
+        #[serde(default)]
+        multi_winner: bool,
+        /// Browsing category (e.g. sports, weather, crypto)
+        #[serde(default)]
+        category: Option<String>,
+        #[serde(default)]
+        tags: Vec<String>,
+        /// How the reward is split among correct voters (None = follow `strategy`)
+        #[serde(default)]
+        reward_basis: Option<state::RewardBasis>,
+        /// Synonyms accepted in votes (alias -> outcome)
+        #[serde(default)]
+        outcome_aliases: std::collections::BTreeMap<String, String>,
+        /// How a tie for the most votes resolves (Majority only)
+        #[serde(default)]
+        tie_break: state::TieBreak,
+    },
+    
+    /// Change a query's decision strategy (creator only)
+    /// Allowed only during the commit phase, before any vote is cast
+    UpdateQueryStrategy {
+        query_id: u64,
+        strategy: DecisionStrategy,
+    },
+    
+    /// Read a query's result synchronously (read-only, anyone)
+    /// 
+    /// Lets other applications on the registry's chain read a result through a
+    /// cross-application call instead of waiting for a resolution callback:
+    /// 
+    /// ```ignore
+    /// let response = runtime.call_application(
+    ///     true,
+    ///     registry_id.with_abi::<OracleRegistryV2Abi>(),
+    ///     &Operation::GetQueryResult { query_id },
+    /// );
+    /// let result = response.data.and_then(|data| data.query_result);
+    /// ```
+    /// 
+    /// Fails only if the query does not exist; check `resolved` before using
+    /// `result`.
+    GetQueryResult {
+        query_id: u64,
+    },
+    
+    /// Cancel an active query before any vote is revealed (creator or admin)
+    /// Stake locked by committed voters is released
+    CancelQuery {
+        query_id: u64,
+    },
+    
+    /// Move a stuck query to its next voting phase without resolving it (admin only)
+    /// Commit -> Reveal after commit_phase_end, Reveal -> Completed after the reveal window
+    AdvancePhase {
+        query_id: u64,
+    },
+    
+    /// Continue releasing stake locked on a resolved, expired or cancelled query (anyone)
+    /// The block that finalizes a query only unlocks the first batch of voters;
+    /// each call unlocks up to `max` more until none remain
+    UnlockQueryStakes {
+        query_id: u64,
+        max: u32,
+    },
+    
+    /// Set a query's category and tags (creator only)
+    SetQueryMetadata {
+        query_id: u64,
+        category: Option<String>,
+        tags: Vec<String>,
+    },
+    
+    /// Allow or forbid resolving an active query from external data (admin only)
+    SetExternalResolution {
+        query_id: u64,
+        allowed: bool,
+    },
+    
+    /// Resolve a query designated for external resolution to `outcome`,
+    /// bypassing voting and recording `source` (admin only)
+    /// Voters' locked stake is released without rewards or slashing
+    ResolveWithExternalData {
+        query_id: u64,
+        outcome: String,
+        source: String,
     },
     
     /// Submit a vote for a query (direct voting, no commit/reveal)
@@ -320,11 +503,41 @@ pub enum Operation {
     /// Claim pending rewards
     ClaimRewards,
     
-    /// Update protocol parameters (admin only)
+    /// Claim pending rewards and pay them to another chain (e.g. a cold wallet)
+    ClaimRewardsTo {
+        target_chain: ChainId,
+    },
+    
+    /// Allow `operator` to claim this voter's rewards with BatchClaimFor
+    /// (None revokes). Rewards are always paid to the voter's own chain.
+    SetClaimOperator {
+        operator: Option<ChainId>,
+    },
+    
+    /// Claim the pending rewards of several voters, each paid to their own
+    /// chain (each voter's claim operator, or admin)
+    BatchClaimFor {
+        voters: Vec<ChainId>,
+    },
+    
+    /// Update protocol parameters (admin only; rejected while a timelock is set)
     UpdateParameters {
         params: ProtocolParameters,
     },
     
+    /// Propose a parameter change that applies after the timelock (admin only)
+    ProposeParameters {
+        params: ProtocolParameters,
+    },
+    
+    /// Apply the pending parameter change once its timelock has elapsed (anyone)
+    ApplyPendingParameters,
+    
+    /// Set the ALTH token application used to return staked tokens (admin only)
+    SetTokenApplication {
+        token_app_id: linera_sdk::linera_base_types::ApplicationId,
+    },
+    
     /// Pause protocol (admin only)
     PauseProtocol,
     
@@ -339,9 +552,58 @@ pub enum Operation {
         query_id: u64,
     },
     
+    /// Mark several queries as expired (admin only)
+    /// Each id is handled on its own; one failing does not stop the rest
+    ExpireQueries {
+        query_ids: Vec<u64>,
+    },
+    
     /// Auto-resolve queries that have completed reveal phase (maintenance operation)
     AutoResolveQueries,
     
+    /// Rebuild the cached voter statistics with a full scan (admin only)
+    /// Needed once for registries created before the cache existed
+    RefreshStatistics,
+    
+    /// Release the treasury's held tokens to the configured treasury account (anyone)
+    SweepTreasury,
+    
+    /// Return pending rewards unclaimed past `reward_claim_expiry_secs` to the reward pool (anyone)
+    /// Checks at most `max` voters per call, continuing from where the last call stopped
+    ReclaimUnclaimedRewards {
+        max: usize,
+    },
+    
+    /// Transfer tokens from the signer's token account into the reward pool (admin only)
+    FundRewardPool {
+        amount: Amount,
+    },
+    
+    /// Move tokens into or out of the protocol treasury (admin only)
+    /// An increase transfers tokens from the signer's token account; a decrease
+    /// releases held treasury tokens back to it
+    AdjustTreasury {
+        amount: Amount,
+        increase: bool,
+    },
+    
+    /// Send a closed query's QueryResolutionCallback to its callback chain again (admin only)
+    ResendCallback {
+        query_id: u64,
+    },
+    
+    /// Resolve or expire at most `max` active queries (maintenance operation)
+    /// Continues from where the previous call stopped; reports how many remain
+    SettleQueries {
+        max: usize,
+    },
+    
+    /// Archive resolved/expired queries closed before `before` (admin only)
+    /// Moves them out of `queries` into compact summaries
+    ArchiveQueries {
+        before: Timestamp,
+    },
+    
     /// Send RegisterVoter message to another chain (cross-chain registration)
     /// This allows a user to register as voter on the main registry chain
     /// by sending a cross-chain message from their own chain.
@@ -396,6 +658,13 @@ pub enum Operation {
         additional_stake: Amount,
     },
     
+    /// Send RequestQueryResult message to another chain (cross-chain result lookup)
+    /// The result is delivered back to this chain as a QueryResolutionCallback.
+    SendRequestQueryResultMessage {
+        target_chain: linera_sdk::linera_base_types::ChainId,
+        query_id: u64,
+    },
+    
     /// Create a query with callback information (for cross-application calls)
     /// This allows other applications to create queries and receive callbacks when resolved
     CreateQueryWithCallback {
@@ -419,6 +688,11 @@ pub enum Operation {
 /// These messages enable account-based voting by allowing users to
 /// send operations from their own chains without deploying separate apps.
 /// Authentication is automatic - Linera verifies the message sender.
+/// 
+/// Messages are BCS-encoded with the variant index as the tag, so messages
+/// in flight across an upgrade are decoded by position. Only append new
+/// variants at the end; never reorder, remove or change the fields of an
+/// existing one. `message_compat_tests` pins the current encoding.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Message {
     /// Register as a voter via cross-chain message
@@ -480,6 +754,7 @@ pub enum Message {
         sender_chain: ChainId,
         description: String,
         outcomes: Vec<String>,
+        /// Strategy name; empty uses the registry's default strategy
         strategy: String,
         min_votes: Option<usize>,
         reward_amount: Amount,
@@ -517,11 +792,64 @@ pub enum Message {
     },
     
     /// Request to withdraw tokens back to user
-    /// Registry will send WithdrawToAccount to token contract
+    /// Sent by withdraw/deregister to the token chain (the registry's creator
+    /// chain), which releases the tokens through the token contract's
+    /// WithdrawToAccount flow before deducting the tracked stake
     WithdrawTokens {
         amount: Amount,
         target_chain: ChainId,
     },
+    
+    /// Any chain -> Registry: Ask for a query's result again
+    /// The registry answers `reply_to` with a QueryResolutionCallback if the
+    /// query is resolved, or QueryResultUnavailable otherwise
+    RequestQueryResult {
+        query_id: u64,
+        reply_to: ChainId,
+    },
+    
+    /// Registry -> requester: The requested result cannot be sent
+    QueryResultUnavailable {
+        query_id: u64,
+        reason: String,
+    },
+}
+
+impl Message {
+    /// Variant name, used when logging failed messages
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Message::RegisterVoter { .. } => "RegisterVoter",
+            Message::UpdateStake { .. } => "UpdateStake",
+            Message::WithdrawStake { .. } => "WithdrawStake",
+            Message::DeregisterVoter => "DeregisterVoter",
+            Message::SubmitVote { .. } => "SubmitVote",
+            Message::CommitVote { .. } => "CommitVote",
+            Message::RevealVote { .. } => "RevealVote",
+            Message::ClaimRewards => "ClaimRewards",
+            Message::CreateQuery { .. } => "CreateQuery",
+            Message::CreateQueryFromMarket { .. } => "CreateQueryFromMarket",
+            Message::QueryResolutionCallback { .. } => "QueryResolutionCallback",
+            Message::ReceiveTokensForStake { .. } => "ReceiveTokensForStake",
+            Message::WithdrawTokens { .. } => "WithdrawTokens",
+            Message::RequestQueryResult { .. } => "RequestQueryResult",
+            Message::QueryResultUnavailable { .. } => "QueryResultUnavailable",
+        }
+    }
+    
+    /// Chain ID declared in the message payload, if the variant carries one
+    pub fn sender_chain(&self) -> Option<ChainId> {
+        match self {
+            Message::RegisterVoter { sender_chain, .. }
+            | Message::UpdateStake { sender_chain, .. }
+            | Message::SubmitVote { sender_chain, .. }
+            | Message::CommitVote { sender_chain, .. }
+            | Message::RevealVote { sender_chain, .. }
+            | Message::CreateQuery { sender_chain, .. }
+            | Message::ReceiveTokensForStake { sender_chain, .. } => Some(*sender_chain),
+            _ => None,
+        }
+    }
 }
 
 /// Response from operations
@@ -539,6 +867,59 @@ pub struct ResponseData {
     pub query_id: Option<u64>,
     pub vote_count: Option<usize>,
     pub rewards_claimed: Option<String>,
+    /// Set by GetQueryResult
+    #[serde(default)]
+    pub query_result: Option<QueryResultData>,
+    /// Set by BatchClaimFor: one entry per requested voter, in request order
+    #[serde(default)]
+    pub batch_claims: Option<Vec<BatchClaimResult>>,
+    /// Set by ExpireQueries: one entry per requested query, in request order
+    #[serde(default)]
+    pub expired_queries: Option<Vec<ExpireQueryResult>>,
+    /// Set by ResolveQuery: reward credited to each correct voter's pending rewards
+    #[serde(default)]
+    pub reward_distribution: Option<Vec<VoterAmount>>,
+    /// Set by ResolveQuery: stake slashed from each incorrect voter
+    #[serde(default)]
+    pub slash_distribution: Option<Vec<VoterAmount>>,
+}
+
+/// Outcome of one voter's claim in `Operation::BatchClaimFor`
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct BatchClaimResult {
+    pub voter_address: String,
+    /// Amount paid out (only when the claim succeeded)
+    pub claimed: Option<String>,
+    /// Why the claim failed
+    pub error: Option<String>,
+}
+
+/// Outcome of one query in `Operation::ExpireQueries`
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct ExpireQueryResult {
+    pub query_id: u64,
+    /// Why the query could not be expired (None when it was)
+    pub error: Option<String>,
+}
+
+/// An amount credited to or taken from one voter
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject, PartialEq)]
+pub struct VoterAmount {
+    pub voter_address: String,
+    pub amount: String,
+}
+
+/// A query's result as returned by `Operation::GetQueryResult`
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+pub struct QueryResultData {
+    /// Active, Resolved, Expired or Cancelled
+    pub status: String,
+    /// Whether the query resolved to an outcome
+    pub resolved: bool,
+    /// Winning outcome (only when resolved)
+    pub result: Option<String>,
+    /// When the query was resolved, expired or cancelled
+    pub resolved_at: Option<Timestamp>,
 }
 
 impl OperationResponse {
@@ -0,0 +1,126 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for per-strategy stake locks and the lock preview
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{DecisionStrategy as StateStrategy, ProtocolParameters, StakeLockRule};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::state::DecisionStrategy;
+    use oracle_registry_v2::Operation;
+
+    fn reputation_rule() -> StakeLockRule {
+        StakeLockRule { lock_bps: 2500, min_lock: Amount::from_tokens(2) }
+    }
+
+    /// Three 100-token voters; reputation-weighted votes lock 25%
+    fn harness() -> TestHarness {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            strategy_stake_locks: [(StateStrategy::WeightedByReputation, reputation_rule())].into_iter().collect(),
+            ..ProtocolParameters::default()
+        });
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        harness
+    }
+
+    fn create(harness: &mut TestHarness, strategy: DecisionStrategy) -> u64 {
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::CreateQuery {
+            description: "Will it rain?".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            strategy,
+            min_votes: Some(3),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            duration_secs: Some(120),
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            category: None,
+            tags: Vec::new(),
+            reward_basis: None,
+            outcome_aliases: std::collections::BTreeMap::new(),
+            tie_break: oracle_registry_v2::state::TieBreak::OutcomeOrder,
+        });
+        assert!(response.success, "{}", response.message);
+        response.data.and_then(|data| data.query_id).expect("query id")
+    }
+
+    /// Preview chain(1)'s lock on `query_id`, vote, and return (previewed, applied)
+    fn preview_then_vote(harness: &mut TestHarness, query_id: u64) -> (Amount, Amount) {
+        let preview = harness.state().lock_preview(query_id, &chain(1)).blocking_wait().expect("preview");
+        let locked_before = harness.voter(&chain(1)).unwrap().locked_stake;
+
+        assert!(harness.submit_vote(chain(1), query_id, "Yes").success);
+
+        let applied = harness.voter(&chain(1)).unwrap().locked_stake.saturating_sub(locked_before);
+        let recorded = harness.state().stake_locks.get(&(query_id, chain(1))).blocking_wait().unwrap();
+        assert_eq!(recorded, Some(applied));
+        (preview.stake_to_lock, applied)
+    }
+
+    #[test]
+    fn test_preview_matches_lock_applied_on_voting() {
+        let mut harness = harness();
+        let majority = create(&mut harness, DecisionStrategy::Majority);
+        let reputation = create(&mut harness, DecisionStrategy::WeightedByReputation);
+
+        // Default rule: 10% of 100 available tokens
+        let (previewed, applied) = preview_then_vote(&mut harness, majority);
+        assert_eq!(previewed, Amount::from_tokens(10));
+        assert_eq!(applied, previewed);
+
+        // Reputation rule: 25% of the 90 tokens left available
+        let preview = harness.state().lock_preview(reputation, &chain(1)).blocking_wait().unwrap();
+        assert_eq!(preview.strategy, StateStrategy::WeightedByReputation);
+        assert_eq!(preview.rule, reputation_rule());
+        assert_eq!(preview.available_stake, Amount::from_tokens(90));
+        let (previewed, applied) = preview_then_vote(&mut harness, reputation);
+        assert_eq!(previewed, Amount::from_millis(22_500));
+        assert_eq!(applied, previewed);
+    }
+
+    #[test]
+    fn test_min_lock_applies_when_share_rounds_to_zero() {
+        let rule = reputation_rule();
+        let mut voter = harness().voter(&chain(1)).unwrap();
+        voter.stake = Amount::from_attos(3);
+        assert_eq!(rule.stake_to_lock(&voter), Amount::from_tokens(2));
+        assert_eq!(StakeLockRule::default().stake_to_lock(&voter), Amount::from_tokens(1));
+    }
+
+    #[test]
+    fn test_preview_requires_query_and_voter() {
+        let mut harness = harness();
+        let query_id = create(&mut harness, DecisionStrategy::Majority);
+        assert!(harness.state().lock_preview(99, &chain(1)).blocking_wait().is_err());
+        assert!(harness.state().lock_preview(query_id, &chain(9)).blocking_wait().is_err());
+    }
+
+    #[test]
+    fn test_out_of_range_lock_share_is_rejected() {
+        let mut harness = harness();
+        let admin = harness.admin;
+        let params = oracle_registry_v2::state::ProtocolParameters {
+            strategy_stake_locks: [(
+                DecisionStrategy::Majority,
+                oracle_registry_v2::state::StakeLockRule { lock_bps: 10001, min_lock: Amount::ONE },
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let response = harness.execute_as(admin, Operation::UpdateParameters { params });
+        assert!(!response.success);
+        assert!(response.message.contains("Stake lock for Majority must be between 1 and 10000 basis points"));
+    }
+}
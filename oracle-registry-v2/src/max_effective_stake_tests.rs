@@ -0,0 +1,117 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for capping the stake counted towards voting power and rewards
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{ProtocolParameters, ResolutionResult};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::Operation;
+
+    /// A 1000-token whale on chain(1) and two 100-token voters
+    fn harness(max_effective_stake: Option<Amount>) -> TestHarness {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            max_effective_stake,
+            ..ProtocolParameters::default()
+        });
+        assert!(harness.register(chain(1), 1000).success);
+        for id in 2..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        harness
+    }
+
+    fn create_stake_weighted(harness: &mut TestHarness) -> u64 {
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::CreateQuery {
+            description: "Stake weighted".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            strategy: oracle_registry_v2::state::DecisionStrategy::WeightedByStake,
+            min_votes: Some(3),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            duration_secs: Some(120),
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            category: None,
+            tags: Vec::new(),
+            reward_basis: None,
+            outcome_aliases: std::collections::BTreeMap::new(),
+            tie_break: oracle_registry_v2::state::TieBreak::OutcomeOrder,
+        });
+        assert!(response.success, "{}", response.message);
+        response.data.and_then(|data| data.query_id).expect("query id")
+    }
+
+    /// The whale votes "Yes" against two smaller voters voting "No"
+    fn resolve_whale_vote(max_effective_stake: Option<Amount>) -> Option<ResolutionResult> {
+        let mut harness = harness(max_effective_stake);
+        let query_id = create_stake_weighted(&mut harness);
+        for (id, value) in [(1, "Yes"), (2, "No"), (3, "No")] {
+            assert!(harness.submit_vote(chain(id), query_id, value).success);
+        }
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        harness.query(query_id).unwrap().result
+    }
+
+    #[test]
+    fn test_uncapped_whale_outweighs_smaller_voters() {
+        assert_eq!(resolve_whale_vote(None), Some(ResolutionResult::Outcome("Yes".to_string())));
+    }
+
+    #[test]
+    fn test_capped_whale_is_outvoted() {
+        let result = resolve_whale_vote(Some(Amount::from_tokens(150)));
+        assert_eq!(result, Some(ResolutionResult::Outcome("No".to_string())));
+    }
+
+    #[test]
+    fn test_capped_whale_power_and_rewards_saturate() {
+        let mut harness = harness(Some(Amount::from_tokens(100)));
+        let whale = harness.voter(&chain(1)).unwrap();
+        let small = harness.voter(&chain(2)).unwrap();
+        assert_eq!(whale.stake, Amount::from_tokens(1000));
+        assert_eq!(harness.state().calculate_voter_power(&whale), harness.state().calculate_voter_power(&small));
+        assert!(whale.power() > small.power());
+
+        let params = harness.params();
+        let correct_voters = vec![(chain(1), whale.clone()), (chain(2), small.clone())];
+        let rewards = harness.state().calculate_stake_weighted_rewards(Amount::from_tokens(10), &correct_voters, &params);
+        assert_eq!(rewards.get(&chain(1)), rewards.get(&chain(2)));
+
+        let query_id = create_stake_weighted(&mut harness);
+        for id in 1..=3 {
+            assert!(harness.submit_vote(chain(id), query_id, "Yes").success);
+        }
+        harness.advance_past_deadline(query_id);
+        assert!(harness.resolve(query_id).success);
+
+        let whale_reward = harness.state().get_pending_rewards(&chain(1)).blocking_wait();
+        let small_reward = harness.state().get_pending_rewards(&chain(2)).blocking_wait();
+        assert!(small_reward > Amount::ZERO);
+        assert_eq!(whale_reward, small_reward);
+    }
+
+    #[test]
+    fn test_cap_below_min_stake_is_rejected() {
+        let mut harness = TestHarness::new();
+        let admin = harness.admin;
+        let params = oracle_registry_v2::state::ProtocolParameters {
+            max_effective_stake: Some(Amount::from_tokens(1)),
+            ..Default::default()
+        };
+        let response = harness.execute_as(admin, Operation::UpdateParameters { params });
+        assert!(!response.success);
+        assert!(response.message.contains("Max effective stake cannot be below the minimum stake"));
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the governance cap on a single query's reward
+
+#[cfg(test)]
+mod tests {
+    use crate::state::ProtocolParameters;
+    use linera_sdk::linera_base_types::Amount;
+
+    fn capped(max_tokens: u128) -> ProtocolParameters {
+        ProtocolParameters {
+            max_query_reward: Some(Amount::from_tokens(max_tokens)),
+            ..ProtocolParameters::default()
+        }
+    }
+
+    #[test]
+    fn test_uncapped_by_default() {
+        let params = ProtocolParameters::default();
+
+        assert_eq!(params.max_query_reward, None);
+        assert!(params.check_query_reward(Amount::from_tokens(1_000_000_000)).is_ok());
+    }
+
+    #[test]
+    fn test_reward_over_cap_rejected() {
+        let err = capped(500).check_query_reward(Amount::from_tokens(501)).unwrap_err();
+
+        assert!(err.contains("exceeds the maximum query reward"));
+    }
+
+    #[test]
+    fn test_reward_at_or_under_cap_allowed() {
+        let params = capped(500);
+
+        assert!(params.check_query_reward(Amount::from_tokens(500)).is_ok());
+        assert!(params.check_query_reward(Amount::from_tokens(10)).is_ok());
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests pinning the BCS encoding of cross-chain messages across upgrades
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::chain;
+    use crate::Message;
+    use linera_sdk::linera_base_types::{Amount, Timestamp};
+
+    /// One message per variant, in declaration order
+    fn sample_messages() -> Vec<Message> {
+        vec![
+            Message::RegisterVoter {
+                sender_chain: chain(2),
+                stake: Amount::from_tokens(100),
+                name: Some("Alice".to_string()),
+                metadata_url: None,
+            },
+            Message::UpdateStake { sender_chain: chain(2), additional_stake: Amount::from_tokens(5) },
+            Message::WithdrawStake { amount: Amount::from_tokens(5) },
+            Message::DeregisterVoter,
+            Message::SubmitVote {
+                sender_chain: chain(2),
+                query_id: 7,
+                value: "Yes".to_string(),
+                confidence: Some(80),
+            },
+            Message::CommitVote { sender_chain: chain(2), query_id: 7, commit_hash: "abc".to_string() },
+            Message::RevealVote {
+                sender_chain: chain(2),
+                query_id: 7,
+                value: "Yes".to_string(),
+                salt: "salt".to_string(),
+                confidence: None,
+            },
+            Message::ClaimRewards,
+            Message::CreateQuery {
+                sender_chain: chain(1),
+                description: "Will it rain?".to_string(),
+                outcomes: vec!["Yes".to_string(), "No".to_string()],
+                strategy: String::new(),
+                min_votes: Some(3),
+                reward_amount: Amount::from_tokens(10),
+                duration_secs: None,
+            },
+            Message::CreateQueryFromMarket {
+                market_id: 4,
+                question: "Will it rain?".to_string(),
+                outcomes: vec!["Yes".to_string(), "No".to_string()],
+                deadline: Timestamp::from(3_000_000),
+                callback_chain: chain(3),
+                callback_data: 4u64.to_le_bytes().to_vec(),
+            },
+            Message::QueryResolutionCallback {
+                query_id: 7,
+                resolved_outcome: "Yes".to_string(),
+                resolved_at: Timestamp::from(3_000_000),
+                callback_data: 4u64.to_le_bytes().to_vec(),
+            },
+            Message::ReceiveTokensForStake {
+                sender_chain: chain(2),
+                sender: "0xabc".to_string(),
+                amount: Amount::from_tokens(100),
+            },
+            Message::WithdrawTokens { amount: Amount::from_tokens(5), target_chain: chain(2) },
+            Message::RequestQueryResult { query_id: 7, reply_to: chain(3) },
+            Message::QueryResultUnavailable { query_id: 7, reason: "Query 7 is not resolved yet".to_string() },
+        ]
+    }
+
+    #[test]
+    fn test_variant_tags_are_pinned() {
+        let expected = [
+            "RegisterVoter",
+            "UpdateStake",
+            "WithdrawStake",
+            "DeregisterVoter",
+            "SubmitVote",
+            "CommitVote",
+            "RevealVote",
+            "ClaimRewards",
+            "CreateQuery",
+            "CreateQueryFromMarket",
+            "QueryResolutionCallback",
+            "ReceiveTokensForStake",
+            "WithdrawTokens",
+            "RequestQueryResult",
+            "QueryResultUnavailable",
+        ];
+        let messages = sample_messages();
+        assert_eq!(messages.len(), expected.len());
+
+        for (index, (message, kind)) in messages.iter().zip(expected).enumerate() {
+            assert_eq!(message.kind(), kind);
+            let bytes = bcs::to_bytes(message).unwrap();
+            assert_eq!(bytes[0] as usize, index, "{} moved from tag {}", kind, index);
+        }
+    }
+
+    #[test]
+    fn test_every_variant_round_trips() {
+        for message in sample_messages() {
+            let bytes = bcs::to_bytes(&message).unwrap();
+            let decoded: Message = bcs::from_bytes(&bytes).unwrap();
+
+            assert_eq!(decoded.kind(), message.kind());
+            assert_eq!(bcs::to_bytes(&decoded).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_old_format_bytes_still_deserialize() {
+        // Encoded by hand as older registries sent them: tag, then fields
+        let amount = Amount::from_tokens(5);
+        let mut withdraw = vec![2u8];
+        withdraw.extend_from_slice(&u128::from(amount).to_le_bytes());
+
+        match bcs::from_bytes::<Message>(&withdraw).unwrap() {
+            Message::WithdrawStake { amount: decoded } => assert_eq!(decoded, amount),
+            other => panic!("Expected WithdrawStake, got {:?}", other),
+        }
+        assert!(matches!(bcs::from_bytes::<Message>(&[3]).unwrap(), Message::DeregisterVoter));
+        assert!(matches!(bcs::from_bytes::<Message>(&[7]).unwrap(), Message::ClaimRewards));
+    }
+}
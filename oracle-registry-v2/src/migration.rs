@@ -14,7 +14,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::str::FromStr;
 
-use crate::state::{Query, QueryStatus, DecisionStrategy, Vote, VotingPhase};
+use crate::state::{Query, QueryStatus, DecisionStrategy, TieBreak, Vote, VotingPhase};
 
 // ==================== EXPORT DATA STRUCTURES ====================
 
@@ -342,8 +342,22 @@ impl OldMarketData {
             id: new_id,
             description,
             outcomes: self.outcomes.clone(),
+            numeric_outcomes: None,
+            outcome_aliases: BTreeMap::new(),
             strategy,
             min_votes,
+            reward_basis: None,
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            allow_external_resolution: false,
+            tie_break: TieBreak::OutcomeOrder,
+            category: None,
+            tags: Vec::new(),
             reward_amount,
             creator: creator_chain,
             created_at,
@@ -353,7 +367,9 @@ impl OldMarketData {
             phase: VotingPhase::Completed, // Migration: old queries are completed
             status: QueryStatus::Active,
             result: None,
+            result_display: None,
             resolved_at: None,
+            resolution_source: None,
             commits: BTreeMap::new(), // Migration: no commits for old queries
             votes: BTreeMap::new(),
             selected_voters: Vec::new(), // Migration: no selected voters for old queries
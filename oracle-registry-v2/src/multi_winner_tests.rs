@@ -0,0 +1,124 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for queries resolving to several winning outcomes
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{validate_multi_winner, DecisionStrategy, ResolutionResult};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::{Operation, OperationResponse};
+
+    fn create(harness: &mut TestHarness, strategy: oracle_registry_v2::state::DecisionStrategy, multi_winner: bool) -> OperationResponse {
+        let admin = harness.admin;
+        harness.execute_as(admin, Operation::CreateQuery {
+            description: "Which of these happened?".to_string(),
+            outcomes: vec!["Rain".to_string(), "Wind".to_string(), "Snow".to_string()],
+            strategy,
+            min_votes: Some(3),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            duration_secs: Some(120),
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner,
+            category: None,
+            tags: Vec::new(),
+            reward_basis: None,
+            outcome_aliases: std::collections::BTreeMap::new(),
+            tie_break: oracle_registry_v2::state::TieBreak::OutcomeOrder,
+        })
+    }
+
+    /// Five voters split 2-2-1 across the outcomes
+    fn voted(multi_winner: bool) -> (TestHarness, u64) {
+        let mut harness = TestHarness::new();
+        for id in 1..=5 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let response = create(&mut harness, oracle_registry_v2::state::DecisionStrategy::Majority, multi_winner);
+        assert!(response.success, "{}", response.message);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+
+        for (id, value) in [(1, "Rain"), (2, "Rain"), (3, "Wind"), (4, "Wind"), (5, "Snow")] {
+            assert!(harness.submit_vote(chain(id), query_id, value).success);
+        }
+        harness.advance_past_deadline(query_id);
+        (harness, query_id)
+    }
+
+    fn pending(harness: &TestHarness, id: u8) -> Amount {
+        harness.state().get_pending_rewards(&chain(id)).blocking_wait()
+    }
+
+    #[test]
+    fn test_two_winners_reward_both_outcomes() {
+        let (mut harness, query_id) = voted(true);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+
+        let query = harness.query(query_id).unwrap();
+        assert_eq!(query.result, Some(ResolutionResult::Outcomes(vec!["Rain".to_string(), "Wind".to_string()])));
+        assert_eq!(query.result_display.as_deref(), Some("Rain, Wind"));
+
+        let reward = pending(&harness, 1);
+        assert!(reward > Amount::ZERO);
+        for id in 2..=4 {
+            assert_eq!(pending(&harness, id), reward);
+        }
+        assert_eq!(pending(&harness, 5), Amount::ZERO);
+        assert!(harness.voter(&chain(5)).unwrap().stake < Amount::from_tokens(100));
+        assert_eq!(harness.voter(&chain(3)).unwrap().stake, Amount::from_tokens(100));
+    }
+
+    #[test]
+    fn test_single_winner_query_keeps_one_outcome() {
+        let (mut harness, query_id) = voted(false);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+
+        let result = harness.query(query_id).unwrap().result.expect("resolved");
+        assert!(matches!(result, ResolutionResult::Outcome(_)));
+        let rewarded = (1..=4).filter(|id| pending(&harness, *id) > Amount::ZERO).count();
+        assert_eq!(rewarded, 2);
+    }
+
+    #[test]
+    fn test_median_queries_cannot_have_multiple_winners() {
+        let mut harness = TestHarness::new();
+        let response = create(&mut harness, oracle_registry_v2::state::DecisionStrategy::Median, true);
+        assert!(!response.success);
+        assert!(response.message.contains("cannot have multiple winners"));
+
+        assert!(validate_multi_winner(true, &DecisionStrategy::WeightedByStake).is_ok());
+        assert!(validate_multi_winner(false, &DecisionStrategy::ConfidenceWeightedMedian).is_ok());
+    }
+
+    #[test]
+    fn test_from_tally_above_share() {
+        let tally = |weights: &[(&str, f64)]| -> Vec<(String, f64)> {
+            weights.iter().map(|(value, weight)| (value.to_string(), *weight)).collect()
+        };
+
+        assert_eq!(
+            ResolutionResult::from_tally_above_share(tally(&[("A", 4.0), ("B", 4.0), ("C", 2.0)]), 3000),
+            ResolutionResult::Outcomes(vec!["A".to_string(), "B".to_string()])
+        );
+        assert_eq!(
+            ResolutionResult::from_tally_above_share(tally(&[("A", 4.0), ("B", 4.0), ("C", 2.0)]), 4001),
+            ResolutionResult::NoConsensus
+        );
+        assert_eq!(
+            ResolutionResult::from_tally_above_share(tally(&[("A", 7.0), ("B", 3.0)]), 5000),
+            ResolutionResult::Outcome("A".to_string())
+        );
+        assert_eq!(ResolutionResult::from_tally_above_share(Vec::new(), 3000), ResolutionResult::NoConsensus);
+        assert!(ResolutionResult::Outcomes(vec!["A".to_string(), "B".to_string()]).matches_vote("B"));
+    }
+}
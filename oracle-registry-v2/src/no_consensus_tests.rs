@@ -0,0 +1,116 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the NoConsensus query status and its market callback
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{self, QueryStatus, ResolutionResult};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::{Amount, ApplicationId, ChainId, Timestamp};
+    use oracle_registry_v2::{state::DecisionStrategy, Message, Operation};
+
+    /// Registry with three voters and a query created by `market` for market #4
+    /// that needs votes on two distinct outcomes
+    fn market_query(market: ChainId) -> (TestHarness, u64) {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+
+        let market_app = ApplicationId::new(format!("{:064x}", 0xbb).parse().expect("valid hash"));
+        let response = harness.execute_as(market, Operation::CreateQueryWithCallback {
+            description: "Will it rain?".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            strategy: DecisionStrategy::Majority,
+            min_votes: Some(3),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            callback_chain: market,
+            callback_app: market_app,
+            callback_data: 4u64.to_le_bytes().to_vec(),
+        });
+        assert!(response.success, "{}", response.message);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+
+        let mut query = harness.query(query_id).unwrap();
+        query.min_distinct_voted_outcomes = Some(2);
+        harness.contract.state.queries.insert(&query_id, query).expect("Failed to store query");
+        (harness, query_id)
+    }
+
+    /// Messages sent to `market` since `since` messages had been sent
+    fn sent_to(harness: &TestHarness, market: ChainId, since: usize) -> Vec<Message> {
+        harness.sent_messages().split_off(since)
+            .into_iter()
+            .filter(|(destination, _)| *destination == market)
+            .map(|(_, message)| message)
+            .collect()
+    }
+
+    fn assert_no_consensus_callback(message: &Message, query_id: u64, resolved_at: Timestamp) {
+        match message {
+            Message::QueryResolutionCallback { query_id: id, resolved_outcome, resolved_at: at, callback_data } => {
+                assert_eq!(*id, query_id);
+                assert_eq!(resolved_outcome, state::NO_CONSENSUS_OUTCOME);
+                assert_eq!(*at, resolved_at);
+                assert_eq!(*callback_data, 4u64.to_le_bytes().to_vec());
+            }
+            other => panic!("Expected QueryResolutionCallback, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_disagreement_closes_as_no_consensus_and_notifies_market() {
+        let market = chain(9);
+        let (mut harness, query_id) = market_query(market);
+        for id in 1..=3 {
+            assert!(harness.submit_vote(chain(id), query_id, "Yes").success);
+        }
+        harness.advance_past_deadline(query_id);
+
+        let sent = harness.sent_messages().len();
+        let response = harness.resolve(query_id);
+        assert!(!response.success);
+        assert!(response.message.contains("closed without consensus"), "{}", response.message);
+
+        let query = harness.query(query_id).unwrap();
+        assert_eq!(query.status, QueryStatus::NoConsensus);
+        assert_eq!(query.result, Some(ResolutionResult::NoConsensus));
+        assert_eq!(*harness.state().total_queries_no_consensus.get(), 1);
+        assert_eq!(*harness.state().total_queries_resolved.get(), 0);
+        assert!(!harness.state().get_active_queries().blocking_wait().contains(&query_id));
+
+        let callbacks = sent_to(&harness, market, sent);
+        assert_eq!(callbacks.len(), 1);
+        assert_no_consensus_callback(&callbacks[0], query_id, query.resolved_at.unwrap());
+
+        // Asking again gets the same payload
+        let sent = harness.sent_messages().len();
+        harness.deliver_message(market, Message::RequestQueryResult { query_id, reply_to: market });
+        let replies = sent_to(&harness, market, sent);
+        assert_eq!(replies.len(), 1);
+        assert_no_consensus_callback(&replies[0], query_id, query.resolved_at.unwrap());
+    }
+
+    #[test]
+    fn test_too_few_votes_stays_expired_without_callback() {
+        let market = chain(9);
+        let (mut harness, query_id) = market_query(market);
+        assert!(harness.submit_vote(chain(1), query_id, "Yes").success);
+        harness.advance_past_deadline(query_id);
+
+        let sent = harness.sent_messages().len();
+        let _ = harness.resolve(query_id);
+
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::Expired);
+        assert_eq!(*harness.state().total_queries_no_consensus.get(), 0);
+        assert!(sent_to(&harness, market, sent).is_empty());
+    }
+
+    #[test]
+    fn test_status_name() {
+        assert_eq!(QueryStatus::NoConsensus.as_str(), "NoConsensus");
+    }
+}
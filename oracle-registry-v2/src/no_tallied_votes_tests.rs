@@ -0,0 +1,87 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for closing queries whose votes cannot be tallied
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{QueryStatus, ResolutionResult};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::Operation;
+
+    /// Median query with three numeric votes in
+    fn voted_median() -> (TestHarness, u64) {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::CreateQuery {
+            description: "Rainfall in mm".to_string(),
+            outcomes: vec!["10".to_string(), "20".to_string(), "30".to_string()],
+            strategy: oracle_registry_v2::state::DecisionStrategy::Median,
+            min_votes: Some(3),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            duration_secs: Some(120),
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            category: None,
+            tags: Vec::new(),
+            reward_basis: None,
+            outcome_aliases: std::collections::BTreeMap::new(),
+            tie_break: oracle_registry_v2::state::TieBreak::OutcomeOrder,
+        });
+        assert!(response.success, "{}", response.message);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+
+        for (id, value) in [(1, "10"), (2, "20"), (3, "20")] {
+            assert!(harness.submit_vote(chain(id), query_id, value).success);
+        }
+        (harness, query_id)
+    }
+
+    #[test]
+    fn test_median_without_numeric_votes_closes_without_slashing() {
+        let (mut harness, query_id) = voted_median();
+
+        // Votes stored before outcomes were checked may not parse
+        let mut query = harness.query(query_id).unwrap();
+        for vote in query.votes.values_mut() {
+            vote.value = "unknown".to_string();
+        }
+        harness.contract.state.queries.insert(&query_id, query).expect("Failed to store query");
+
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(!response.success);
+        assert!(response.message.contains("no vote could be tallied"), "{}", response.message);
+
+        let query = harness.query(query_id).unwrap();
+        assert_eq!(query.status, QueryStatus::NoConsensus);
+        assert_eq!(query.result, Some(ResolutionResult::NoConsensus));
+        for id in 1..=3 {
+            let voter = harness.voter(&chain(id)).unwrap();
+            assert_eq!(voter.stake, Amount::from_tokens(100));
+            assert_eq!(voter.locked_stake, Amount::ZERO);
+        }
+        assert_eq!(*harness.state().total_stake.get(), Amount::from_tokens(300));
+    }
+
+    #[test]
+    fn test_median_with_numeric_votes_still_resolves() {
+        let (mut harness, query_id) = voted_median();
+
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        assert_eq!(harness.query(query_id).unwrap().result, Some(ResolutionResult::NumericMedian(20.0)));
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for numeric outcomes parsed at query creation
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{DecisionStrategy, ResolutionResult};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::{Operation, OperationResponse};
+
+    fn create_median(harness: &mut TestHarness, outcomes: &[&str]) -> OperationResponse {
+        let admin = harness.admin;
+        harness.execute_as(admin, Operation::CreateQuery {
+            description: "Rainfall in mm".to_string(),
+            outcomes: outcomes.iter().map(|outcome| outcome.to_string()).collect(),
+            strategy: oracle_registry_v2::state::DecisionStrategy::Median,
+            min_votes: Some(3),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            duration_secs: Some(120),
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            category: None,
+            tags: Vec::new(),
+            reward_basis: None,
+            outcome_aliases: std::collections::BTreeMap::new(),
+            tie_break: oracle_registry_v2::state::TieBreak::OutcomeOrder,
+        })
+    }
+
+    fn registered() -> TestHarness {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        harness
+    }
+
+    #[test]
+    fn test_median_creation_rejects_non_numeric_outcome() {
+        let mut harness = registered();
+
+        let response = create_median(&mut harness, &["10", "twenty", "30"]);
+        assert!(!response.success);
+        assert!(response.message.contains("'twenty' is not numeric"));
+
+        let response = create_median(&mut harness, &["10", "NaN"]);
+        assert!(!response.success);
+        assert!(response.message.contains("'NaN' is not numeric"));
+    }
+
+    #[test]
+    fn test_median_creation_stores_parsed_outcomes() {
+        let mut harness = registered();
+        let response = create_median(&mut harness, &["1e1", "20", "30.5"]);
+        assert!(response.success, "{}", response.message);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+
+        let query = harness.query(query_id).unwrap();
+        assert_eq!(query.outcomes, vec!["1e1", "20", "30.5"]);
+        assert_eq!(query.numeric_outcomes, Some(vec![10.0, 20.0, 30.5]));
+
+        // Non-median queries keep only the string form
+        let created = harness.create_query(&["Yes", "No"], 1, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        assert_eq!(harness.query(query_id).unwrap().numeric_outcomes, None);
+    }
+
+    #[test]
+    fn test_median_resolution_uses_parsed_outcomes() {
+        let mut harness = registered();
+        let response = create_median(&mut harness, &["1e1", "20", "30.5"]);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+
+        for (id, value) in [(1, "1e1"), (2, "30.5"), (3, "30.5")] {
+            assert!(harness.submit_vote(chain(id), query_id, value).success);
+        }
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        assert_eq!(harness.query(query_id).unwrap().result, Some(ResolutionResult::NumericMedian(30.5)));
+    }
+
+    #[test]
+    fn test_numeric_vote_value_looks_up_parsed_outcome() {
+        let mut harness = registered();
+        let response = create_median(&mut harness, &["1e1", "20"]);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+        let mut query = harness.query(query_id).unwrap();
+
+        assert_eq!(query.numeric_vote_value("1e1"), Some(10.0));
+        assert_eq!(query.numeric_vote_value("10"), None);
+
+        // Queries stored without parsed outcomes parse the vote itself
+        query.numeric_outcomes = None;
+        assert_eq!(query.numeric_vote_value("10"), Some(10.0));
+    }
+
+    #[test]
+    fn test_parse_numeric_outcomes() {
+        let outcomes = vec!["1".to_string(), "2.5".to_string()];
+        assert_eq!(DecisionStrategy::Median.parse_numeric_outcomes(&outcomes), Ok(Some(vec![1.0, 2.5])));
+        assert_eq!(DecisionStrategy::Majority.parse_numeric_outcomes(&outcomes), Ok(None));
+
+        let infinite = vec!["inf".to_string()];
+        assert!(DecisionStrategy::ConfidenceWeightedMedian.parse_numeric_outcomes(&infinite).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for outcome aliases canonicalizing votes
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use linera_sdk::util::BlockingWait;
+    use crate::state::{parse_outcome_aliases, validate_outcome_aliases, ResolutionResult};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::{Amount, Timestamp};
+    use oracle_registry_v2::{Operation, OperationResponse};
+
+    fn aliases(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries.iter().map(|(alias, outcome)| (alias.to_string(), outcome.to_string())).collect()
+    }
+
+    fn outcomes() -> Vec<String> {
+        vec!["Yes".to_string(), "No".to_string()]
+    }
+
+    fn create(harness: &mut TestHarness, outcome_aliases: BTreeMap<String, String>) -> OperationResponse {
+        let admin = harness.admin;
+        harness.execute_as(admin, Operation::CreateQuery {
+            description: "Will it rain tomorrow?".to_string(),
+            outcomes: outcomes(),
+            strategy: oracle_registry_v2::state::DecisionStrategy::Majority,
+            min_votes: Some(3),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            duration_secs: Some(120),
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            category: None,
+            tags: Vec::new(),
+            reward_basis: None,
+            outcome_aliases,
+            tie_break: oracle_registry_v2::state::TieBreak::OutcomeOrder,
+        })
+    }
+
+    /// Three registered voters and a Yes/No query accepting "yes", "Y" and "n"
+    fn aliased_query() -> (TestHarness, u64) {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let response = create(&mut harness, aliases(&[("yes", "Yes"), ("Y", "Yes"), ("n", "No")]));
+        assert!(response.success, "{}", response.message);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+        (harness, query_id)
+    }
+
+    #[test]
+    fn test_aliased_votes_count_under_canonical_outcome() {
+        let (mut harness, query_id) = aliased_query();
+        for (id, value) in [(1, "yes"), (2, "Y"), (3, "n")] {
+            let response = harness.submit_vote(chain(id), query_id, value);
+            assert!(response.success, "{}", response.message);
+        }
+
+        let query = harness.query(query_id).unwrap();
+        assert_eq!(query.votes[&chain(1)].value, "Yes");
+        assert_eq!(query.votes[&chain(2)].value, "Yes");
+        assert_eq!(query.votes[&chain(3)].value, "No");
+        let stored = harness.state().get_vote(query_id, &chain(2)).blocking_wait();
+        assert_eq!(stored.expect("stored vote").value, "Yes");
+
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        assert_eq!(harness.query(query_id).unwrap().result, Some(ResolutionResult::Outcome("Yes".to_string())));
+    }
+
+    #[test]
+    fn test_revealed_alias_is_canonicalized() {
+        let (mut harness, query_id) = aliased_query();
+        let hash = harness.params().commit_hash_algo.compute("Y", "salt");
+        assert!(harness.commit_vote(chain(1), query_id, &hash).success);
+
+        // Reveal phase runs from 60s to 120s; the hash covers the alias as sent
+        harness.set_time(Timestamp::from(90_000_000));
+        let response = harness.reveal_vote(chain(1), query_id, "Y", "salt");
+        assert!(response.success, "{}", response.message);
+        assert_eq!(harness.query(query_id).unwrap().votes[&chain(1)].value, "Yes");
+    }
+
+    #[test]
+    fn test_unknown_value_still_rejected() {
+        let (mut harness, query_id) = aliased_query();
+
+        let response = harness.submit_vote(chain(1), query_id, "Maybe");
+        assert!(!response.success);
+        assert!(response.message.contains("Invalid vote value 'Maybe'"));
+    }
+
+    #[test]
+    fn test_creation_rejects_alias_to_unknown_outcome() {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+
+        let response = create(&mut harness, aliases(&[("y", "Yep")]));
+        assert!(!response.success);
+        assert!(response.message.contains("unknown outcome 'Yep'"), "{}", response.message);
+    }
+
+    #[test]
+    fn test_validate_outcome_aliases() {
+        assert!(validate_outcome_aliases(&aliases(&[("Y", "Yes"), ("N", "No")]), &outcomes()).is_ok());
+        assert!(validate_outcome_aliases(&BTreeMap::new(), &outcomes()).is_ok());
+        // An alias may not shadow an official vote value
+        assert!(validate_outcome_aliases(&aliases(&[("No", "Yes")]), &outcomes()).is_err());
+        assert!(validate_outcome_aliases(&aliases(&[("Abstain", "No")]), &outcomes()).is_err());
+        assert!(validate_outcome_aliases(&aliases(&[("", "No")]), &outcomes()).is_err());
+    }
+
+    #[test]
+    fn test_parse_outcome_aliases() {
+        let entries = vec!["Y=Yes".to_string(), "n=No".to_string()];
+        assert_eq!(parse_outcome_aliases(&entries), Ok(aliases(&[("Y", "Yes"), ("n", "No")])));
+        assert!(parse_outcome_aliases(&["Yes".to_string()]).is_err());
+        assert!(parse_outcome_aliases(&["Y=Yes".to_string(), "Y=No".to_string()]).is_err());
+    }
+}
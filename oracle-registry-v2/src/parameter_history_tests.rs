@@ -0,0 +1,92 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the protocol parameter history
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{parameter_history_evicted_id, ProtocolParameters, MAX_PARAMETER_HISTORY};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::{Amount, Timestamp};
+    use oracle_registry_v2::Operation;
+
+    fn with_min_stake(tokens: u128) -> oracle_registry_v2::state::ProtocolParameters {
+        oracle_registry_v2::state::ProtocolParameters {
+            min_stake: Amount::from_tokens(tokens),
+            ..Default::default()
+        }
+    }
+
+    fn update(harness: &mut TestHarness, min_stake_tokens: u128) {
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::UpdateParameters { params: with_min_stake(min_stake_tokens) });
+        assert!(response.success, "{}", response.message);
+    }
+
+    fn history_len(harness: &TestHarness) -> usize {
+        harness.state().get_parameter_history(usize::MAX).blocking_wait().len()
+    }
+
+    #[test]
+    fn test_history_grows_on_each_update() {
+        let mut harness = TestHarness::new();
+        assert_eq!(history_len(&harness), 0);
+
+        harness.set_time(Timestamp::from(1_000_000));
+        update(&mut harness, 150);
+        assert_eq!(history_len(&harness), 1);
+
+        harness.set_time(Timestamp::from(2_000_000));
+        update(&mut harness, 200);
+        assert_eq!(history_len(&harness), 2);
+
+        let history = harness.state().get_parameter_history(10).blocking_wait();
+        assert_eq!(history[0].id, 1);
+        assert_eq!(history[0].params.min_stake, Amount::from_tokens(200));
+        assert_eq!(history[0].changed_at, Timestamp::from(2_000_000));
+        assert_eq!(history[0].changed_by, harness.admin);
+        assert_eq!(history[1].params.min_stake, Amount::from_tokens(150));
+        assert_eq!(history[1].changed_at, Timestamp::from(1_000_000));
+
+        let latest = harness.state().get_parameter_history(1).blocking_wait();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].id, 1);
+    }
+
+    #[test]
+    fn test_rejected_update_is_not_recorded() {
+        let mut harness = TestHarness::new();
+
+        let response = harness.execute_as(chain(1), Operation::UpdateParameters { params: with_min_stake(150) });
+        assert!(!response.success);
+        assert_eq!(history_len(&harness), 0);
+    }
+
+    #[test]
+    fn test_applied_timelocked_change_is_recorded_for_proposer() {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            timelock_secs: 60,
+            ..ProtocolParameters::default()
+        });
+        let admin = harness.admin;
+        assert!(harness.execute_as(admin, Operation::ProposeParameters { params: with_min_stake(150) }).success);
+        assert_eq!(history_len(&harness), 0);
+
+        harness.advance_secs(60);
+        assert!(harness.execute_as(chain(1), Operation::ApplyPendingParameters).success);
+
+        let history = harness.state().get_parameter_history(10).blocking_wait();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].changed_by, admin);
+        assert_eq!(history[0].params.min_stake, Amount::from_tokens(150));
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        assert_eq!(parameter_history_evicted_id(0), None);
+        assert_eq!(parameter_history_evicted_id(MAX_PARAMETER_HISTORY - 1), None);
+        assert_eq!(parameter_history_evicted_id(MAX_PARAMETER_HISTORY), Some(0));
+        assert_eq!(parameter_history_evicted_id(MAX_PARAMETER_HISTORY + 5), Some(5));
+    }
+}
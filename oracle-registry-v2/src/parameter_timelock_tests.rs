@@ -0,0 +1,111 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for timelocked protocol parameter changes
+
+#[cfg(test)]
+mod tests {
+    use crate::state::ProtocolParameters;
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::{Amount, Timestamp};
+    use oracle_registry_v2::{Operation, OperationResponse};
+
+    const TIMELOCK_SECS: u64 = 3600;
+
+    fn timelocked() -> TestHarness {
+        TestHarness::with_parameters(ProtocolParameters {
+            timelock_secs: TIMELOCK_SECS,
+            ..ProtocolParameters::default()
+        })
+    }
+
+    /// Operation parameters raising the minimum stake to 200 and keeping the timelock
+    fn raised_min_stake() -> oracle_registry_v2::state::ProtocolParameters {
+        oracle_registry_v2::state::ProtocolParameters {
+            min_stake: Amount::from_tokens(200),
+            timelock_secs: TIMELOCK_SECS,
+            ..Default::default()
+        }
+    }
+
+    fn propose(harness: &mut TestHarness) -> OperationResponse {
+        let admin = harness.admin;
+        harness.execute_as(admin, Operation::ProposeParameters { params: raised_min_stake() })
+    }
+
+    #[test]
+    fn test_propose_stores_pending_change() {
+        let mut harness = timelocked();
+        harness.set_time(Timestamp::from(5_000_000));
+
+        let response = propose(&mut harness);
+        assert!(response.success, "{}", response.message);
+
+        let pending = harness.state().pending_parameters.get().clone().expect("pending change");
+        assert_eq!(pending.params.min_stake, Amount::from_tokens(200));
+        assert_eq!(pending.proposed_by, harness.admin);
+        assert_eq!(pending.effective_at, Timestamp::from(5_000_000 + TIMELOCK_SECS * 1_000_000));
+        assert_eq!(harness.params().min_stake, Amount::from_tokens(100));
+    }
+
+    #[test]
+    fn test_non_admin_cannot_propose() {
+        let mut harness = timelocked();
+
+        let response = harness.execute_as(chain(1), Operation::ProposeParameters { params: raised_min_stake() });
+        assert!(!response.success);
+        assert!(response.message.contains("Unauthorized"));
+        assert!(harness.state().pending_parameters.get().is_none());
+    }
+
+    #[test]
+    fn test_apply_before_timelock_fails() {
+        let mut harness = timelocked();
+        assert!(propose(&mut harness).success);
+
+        harness.advance_secs(TIMELOCK_SECS - 1);
+        let response = harness.execute_as(chain(1), Operation::ApplyPendingParameters);
+        assert!(!response.success);
+        assert!(response.message.contains("timelocked for 1 more second"));
+        assert_eq!(harness.params().min_stake, Amount::from_tokens(100));
+        assert!(harness.state().pending_parameters.get().is_some());
+    }
+
+    #[test]
+    fn test_anyone_applies_after_timelock() {
+        let mut harness = timelocked();
+        assert!(propose(&mut harness).success);
+
+        harness.advance_secs(TIMELOCK_SECS);
+        let response = harness.execute_as(chain(1), Operation::ApplyPendingParameters);
+        assert!(response.success, "{}", response.message);
+        assert_eq!(harness.params().min_stake, Amount::from_tokens(200));
+        assert!(harness.state().pending_parameters.get().is_none());
+
+        // Nothing left to apply
+        let response = harness.execute_as(chain(1), Operation::ApplyPendingParameters);
+        assert!(!response.success);
+        assert!(response.message.contains("No pending parameter change"));
+    }
+
+    #[test]
+    fn test_immediate_update_rejected_while_timelocked() {
+        let mut harness = timelocked();
+        let admin = harness.admin;
+
+        let response = harness.execute_as(admin, Operation::UpdateParameters { params: raised_min_stake() });
+        assert!(!response.success);
+        assert!(response.message.contains("use ProposeParameters"));
+        assert_eq!(harness.params().min_stake, Amount::from_tokens(100));
+    }
+
+    #[test]
+    fn test_without_timelock_proposal_applies_at_once() {
+        let mut harness = TestHarness::new();
+        assert!(propose(&mut harness).success);
+
+        assert!(harness.execute_as(chain(1), Operation::ApplyPendingParameters).success);
+        assert_eq!(harness.params().min_stake, Amount::from_tokens(200));
+        assert_eq!(harness.params().timelock_secs, TIMELOCK_SECS);
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the voting power distribution snapshot
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{bucket_voting_power, PowerBucket};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+
+    #[test]
+    fn test_voters_binned_by_power() {
+        let mut harness = TestHarness::new();
+        for (id, stake) in [(1, 100), (2, 150), (3, 200), (4, 400)] {
+            assert!(harness.register(chain(id), stake).success);
+        }
+        let power = |harness: &TestHarness, id: u8| harness.voter(&chain(id)).unwrap().power();
+        let unit = power(&harness, 1) / 100;
+        assert_eq!(power(&harness, 4), 400 * unit);
+
+        let distribution = harness.state().compute_power_distribution(3).blocking_wait().unwrap();
+
+        // 100..=400 units in three ranges of 100 units + 1
+        let width = 100 * unit + 1;
+        assert_eq!(distribution.len(), 3);
+        assert_eq!(
+            distribution.iter().map(|bucket| bucket.voter_count).collect::<Vec<_>>(),
+            vec![3, 0, 1]
+        );
+        assert_eq!(distribution[0].min_power, 100 * unit);
+        assert_eq!(distribution[0].max_power, 100 * unit + width - 1);
+        assert_eq!(distribution[0].total_power, 450 * unit);
+        assert_eq!(distribution[1].min_power, 100 * unit + width);
+        assert_eq!(distribution[1].total_power, 0);
+        assert_eq!(distribution[2].max_power, 400 * unit);
+        assert_eq!(distribution[2].total_power, 400 * unit);
+    }
+
+    #[test]
+    fn test_inactive_voters_are_excluded() {
+        let mut harness = TestHarness::new();
+        for id in 1..=2 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let mut voter = harness.voter(&chain(2)).unwrap();
+        voter.is_active = false;
+        harness.contract.state.save_voter(&chain(2), voter).blocking_wait().unwrap();
+
+        let distribution = harness.state().compute_power_distribution(5).blocking_wait().unwrap();
+        assert_eq!(distribution.len(), 1);
+        assert_eq!(distribution[0].voter_count, 1);
+    }
+
+    #[test]
+    fn test_bucket_voting_power() {
+        assert!(bucket_voting_power(&[], 4).is_empty());
+        assert!(bucket_voting_power(&[10], 0).is_empty());
+
+        // Ranges 0..=4, 5..=9, 10..=14, 15..=19
+        let distribution = bucket_voting_power(&[0, 4, 5, 12, 19, 19], 4);
+        assert_eq!(distribution, vec![
+            PowerBucket { min_power: 0, max_power: 4, voter_count: 2, total_power: 4 },
+            PowerBucket { min_power: 5, max_power: 9, voter_count: 1, total_power: 5 },
+            PowerBucket { min_power: 10, max_power: 14, voter_count: 1, total_power: 12 },
+            PowerBucket { min_power: 15, max_power: 19, voter_count: 2, total_power: 38 },
+        ]);
+
+        // A narrow span yields fewer buckets than asked for
+        let distribution = bucket_voting_power(&[7, 8, 8], 10);
+        assert_eq!(distribution.len(), 2);
+        assert_eq!(distribution[1], PowerBucket { min_power: 8, max_power: 8, voter_count: 2, total_power: 16 });
+    }
+}
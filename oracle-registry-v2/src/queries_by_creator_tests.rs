@@ -0,0 +1,44 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for listing queries by creator
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{chain, query};
+    use crate::state::{queries_by_creator_page, Query};
+
+    /// Queries 1..=5, alternating between creators `chain(1)` and `chain(2)`
+    fn queries() -> Vec<Query> {
+        (1..=5)
+            .map(|id| {
+                let mut q = query(&["Yes", "No"]);
+                q.id = id;
+                q.creator = if id % 2 == 1 { chain(1) } else { chain(2) };
+                q
+            })
+            .collect()
+    }
+
+    fn ids(queries: Vec<Query>) -> Vec<u64> {
+        queries.into_iter().map(|q| q.id).collect()
+    }
+
+    #[test]
+    fn test_filters_by_creator() {
+        assert_eq!(ids(queries_by_creator_page(queries(), &chain(1), 0, 100)), vec![1, 3, 5]);
+        assert_eq!(ids(queries_by_creator_page(queries(), &chain(2), 0, 100)), vec![2, 4]);
+    }
+
+    #[test]
+    fn test_unknown_creator_has_no_queries() {
+        assert!(queries_by_creator_page(queries(), &chain(3), 0, 100).is_empty());
+    }
+
+    #[test]
+    fn test_pages_after_filtering() {
+        assert_eq!(ids(queries_by_creator_page(queries(), &chain(1), 1, 1)), vec![3]);
+        assert_eq!(ids(queries_by_creator_page(queries(), &chain(1), 2, 5)), vec![5]);
+        assert!(queries_by_creator_page(queries(), &chain(1), 3, 5).is_empty());
+    }
+}
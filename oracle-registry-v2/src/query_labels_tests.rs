@@ -0,0 +1,55 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for query categories and tags
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::query;
+    use crate::state::{validate_query_labels, Query, MAX_QUERY_LABEL_LEN, MAX_QUERY_TAGS};
+
+    /// Helper to create a query with the given category and tags
+    fn tagged_query(id: u64, category: Option<&str>, tags: &[&str]) -> Query {
+        let mut q = query(&["Yes", "No"]);
+        q.id = id;
+        q.category = category.map(|c| c.to_string());
+        q.tags = tags.iter().map(|t| t.to_string()).collect();
+        q
+    }
+
+    #[test]
+    fn test_tagged_query_is_valid() {
+        let q = tagged_query(1, Some("sports"), &["football", "premier-league"]);
+        assert!(validate_query_labels(&q.category, &q.tags).is_ok());
+        assert_eq!(q.tags, vec!["football".to_string(), "premier-league".to_string()]);
+    }
+
+    #[test]
+    fn test_filter_by_category() {
+        let queries = vec![
+            tagged_query(1, Some("sports"), &["football"]),
+            tagged_query(2, Some("weather"), &[]),
+            tagged_query(3, Some("Sports"), &["tennis"]),
+            tagged_query(4, None, &["sports"]),
+        ];
+
+        let sports: Vec<u64> = queries.iter()
+            .filter(|q| q.in_category("sports"))
+            .map(|q| q.id)
+            .collect();
+        assert_eq!(sports, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_invalid_labels_rejected() {
+        let too_many: Vec<String> = (0..=MAX_QUERY_TAGS).map(|i| format!("tag{}", i)).collect();
+        assert!(validate_query_labels(&None, &too_many).unwrap_err().contains("Too many tags"));
+
+        let long = "x".repeat(MAX_QUERY_LABEL_LEN + 1);
+        assert!(validate_query_labels(&Some(long.clone()), &[]).is_err());
+        assert!(validate_query_labels(&None, &[long]).is_err());
+
+        assert!(validate_query_labels(&Some(" ".to_string()), &[]).is_err());
+        assert!(validate_query_labels(&None, &["".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the per-query selected-voter panel
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, chain, query, voter_info};
+    use crate::state::{query_panel, VoteCommit, VoterInfo};
+    use linera_sdk::linera_base_types::{ChainId, Timestamp};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_panel_matches_selected_voters() {
+        let mut q = query(&["Yes", "No"]);
+        q.selected_voters = vec![chain(4), chain(2), chain(3)];
+        let infos: BTreeMap<ChainId, VoterInfo> = [(2, 100, 50), (3, 250, 80), (4, 10, 95)]
+            .into_iter()
+            .map(|(id, stake, reputation)| (chain(id), voter_info(id, stake, reputation)))
+            .collect();
+
+        let panel = query_panel(&q, &infos);
+
+        let voters: Vec<ChainId> = panel.iter().map(|member| member.voter).collect();
+        assert_eq!(voters, q.selected_voters);
+        for member in &panel {
+            let info = &infos[&member.voter];
+            assert_eq!(member.power, info.power());
+            let stake: u128 = info.stake.into();
+            assert_eq!(member.power, stake * info.reputation as u128);
+        }
+    }
+
+    #[test]
+    fn test_participation_and_deregistered_voters() {
+        let mut q = query(&["Yes", "No"]);
+        q.selected_voters = vec![chain(2), chain(3), chain(4)];
+        add_vote(&mut q, 2, "Yes");
+        q.commits.insert(chain(3), VoteCommit {
+            voter: chain(3),
+            commit_hash: "abc".to_string(),
+            committed_at: Timestamp::from(100_000),
+            revealed: false,
+        });
+        let infos: BTreeMap<ChainId, VoterInfo> = [2, 3]
+            .into_iter()
+            .map(|id| (chain(id), voter_info(id, 100, 50)))
+            .collect();
+
+        let panel = query_panel(&q, &infos);
+
+        assert!(panel[0].participated);
+        assert!(panel[1].participated);
+        assert!(!panel[2].participated);
+        assert!(panel[2].info.is_none());
+        assert_eq!(panel[2].power, 0);
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for reading a query result through a cross-application call
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::query;
+    use crate::state::{QueryStatus, ResolutionResult};
+    use crate::{OperationResponse, QueryResultData, ResponseData};
+    use linera_sdk::linera_base_types::Timestamp;
+
+    #[test]
+    fn test_resolved_query_exposes_result() {
+        let mut q = query(&["Yes", "No"]);
+        q.status = QueryStatus::Resolved;
+        q.result = Some(ResolutionResult::Outcome("Yes".to_string()));
+        q.result_display = Some("Yes".to_string());
+        q.resolved_at = Some(Timestamp::from(4_000_000));
+
+        assert_eq!(q.status.as_str(), "Resolved");
+        assert_eq!(q.final_result(), Some("Yes"));
+        assert_eq!(q.to_archived().final_result(), Some("Yes"));
+    }
+
+    #[test]
+    fn test_unresolved_query_has_no_result() {
+        let q = query(&["Yes", "No"]);
+        assert_eq!(q.status.as_str(), "Active");
+        assert_eq!(q.final_result(), None);
+
+        // Expired queries carry a display string but no outcome
+        let mut q = query(&["Yes", "No"]);
+        q.status = QueryStatus::Expired;
+        q.result_display = Some(ResolutionResult::Expired.to_string());
+        assert_eq!(q.final_result(), None);
+    }
+
+    #[test]
+    fn test_response_carries_typed_result() {
+        let response = OperationResponse::success_with_data("Query 7 is Resolved", ResponseData {
+            voter_address: None,
+            query_id: Some(7),
+            vote_count: None,
+            rewards_claimed: None,
+            query_result: Some(QueryResultData {
+                status: "Resolved".to_string(),
+                resolved: true,
+                result: Some("Yes".to_string()),
+                resolved_at: Some(Timestamp::from(4_000_000)),
+            }),
+            batch_claims: None,
+            expired_queries: None,
+            reward_distribution: None,
+            slash_distribution: None,
+        });
+
+        let bytes = bcs::to_bytes(&response).unwrap();
+        let decoded: OperationResponse = bcs::from_bytes(&bytes).unwrap();
+        let result = decoded.data.and_then(|data| data.query_result).unwrap();
+        assert!(result.resolved);
+        assert_eq!(result.result.as_deref(), Some("Yes"));
+    }
+}
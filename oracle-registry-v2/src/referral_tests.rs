@@ -0,0 +1,51 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for referral codes given at registration
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::voter_info;
+    use crate::state::{count_referrals, normalize_referral_code, VoterInfo};
+
+    fn registered(id: u8, referral: Option<&str>) -> VoterInfo {
+        let mut info = voter_info(id, 100, 50);
+        info.referral = referral.map(|code| normalize_referral_code(code).unwrap());
+        info
+    }
+
+    #[test]
+    fn test_count_voters_by_code() {
+        let voters = vec![
+            registered(1, Some("spring-2026")),
+            registered(2, Some("Spring-2026")),
+            registered(3, Some("partner_x")),
+            registered(4, None),
+            registered(5, Some("spring-2026")),
+        ];
+
+        assert_eq!(count_referrals(&voters, "spring-2026"), 3);
+        assert_eq!(count_referrals(&voters, "SPRING-2026"), 3);
+        assert_eq!(count_referrals(&voters, "partner_x"), 1);
+        assert_eq!(count_referrals(&voters, "unknown"), 0);
+    }
+
+    #[test]
+    fn test_codes_are_normalized_to_lowercase() {
+        assert_eq!(normalize_referral_code("Partner_X-1"), Ok("partner_x-1".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_codes_rejected() {
+        assert!(normalize_referral_code("").is_err());
+        assert!(normalize_referral_code(&"a".repeat(33)).is_err());
+        assert!(normalize_referral_code("has space").is_err());
+        assert!(normalize_referral_code("emoji🎉").is_err());
+        assert!(normalize_referral_code(&"a".repeat(32)).is_ok());
+    }
+
+    #[test]
+    fn test_fixture_voter_has_no_referral() {
+        assert_eq!(voter_info(1, 100, 50).referral, None);
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for idempotent cross-chain voter registration
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{chain, voter_info};
+    use crate::state::{classify_register_message, RegisterMessageOutcome, VoterInfo};
+    use linera_sdk::linera_base_types::{Amount, ChainId};
+    use std::collections::BTreeMap;
+
+    /// Deliver a RegisterVoter message to a registry model, as the contract does
+    fn deliver(
+        voters: &mut BTreeMap<ChainId, VoterInfo>,
+        total_stake: &mut Amount,
+        id: u8,
+        stake_tokens: u128,
+    ) -> RegisterMessageOutcome {
+        let stake = Amount::from_tokens(stake_tokens);
+        let outcome = classify_register_message(voters.get(&chain(id)), stake);
+        if outcome == RegisterMessageOutcome::Register {
+            voters.insert(chain(id), voter_info(id, stake_tokens, 50));
+            *total_stake = total_stake.saturating_add(stake);
+        }
+        outcome
+    }
+
+    #[test]
+    fn test_duplicate_register_message_is_noop() {
+        let mut voters = BTreeMap::new();
+        let mut total_stake = Amount::ZERO;
+
+        assert_eq!(deliver(&mut voters, &mut total_stake, 2, 100), RegisterMessageOutcome::Register);
+        let second = deliver(&mut voters, &mut total_stake, 2, 100);
+
+        assert_eq!(
+            second,
+            RegisterMessageOutcome::Duplicate { unapplied_stake: Amount::from_tokens(100) }
+        );
+        assert_eq!(voters.len(), 1);
+        assert_eq!(voters[&chain(2)].stake, Amount::from_tokens(100));
+        assert_eq!(total_stake, Amount::from_tokens(100));
+    }
+
+    #[test]
+    fn test_distinct_chains_both_register() {
+        let mut voters = BTreeMap::new();
+        let mut total_stake = Amount::ZERO;
+
+        deliver(&mut voters, &mut total_stake, 2, 100);
+        deliver(&mut voters, &mut total_stake, 3, 50);
+
+        assert_eq!(voters.len(), 2);
+        assert_eq!(total_stake, Amount::from_tokens(150));
+    }
+}
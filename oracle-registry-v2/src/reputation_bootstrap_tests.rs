@@ -0,0 +1,50 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the starting reputation bonus for high-stake voters
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{ProtocolParameters, DEFAULT_REPUTATION, MAX_HIGH_STAKE_REPUTATION_BONUS};
+    use linera_sdk::linera_base_types::Amount;
+
+    fn bonus_params(threshold_tokens: u128, bonus: u32) -> ProtocolParameters {
+        ProtocolParameters {
+            high_stake_threshold: Some(Amount::from_tokens(threshold_tokens)),
+            high_stake_reputation_bonus: bonus,
+            ..ProtocolParameters::default()
+        }
+    }
+
+    #[test]
+    fn test_high_stake_registration_gets_bonus() {
+        let params = bonus_params(10_000, 10);
+
+        assert_eq!(params.initial_reputation(Amount::from_tokens(10_000)), DEFAULT_REPUTATION + 10);
+        assert_eq!(params.initial_reputation(Amount::from_tokens(50_000)), DEFAULT_REPUTATION + 10);
+    }
+
+    #[test]
+    fn test_normal_registration_gets_no_bonus() {
+        let params = bonus_params(10_000, 10);
+
+        assert_eq!(params.initial_reputation(Amount::from_tokens(9_999)), DEFAULT_REPUTATION);
+    }
+
+    #[test]
+    fn test_no_bonus_by_default() {
+        let params = ProtocolParameters::default();
+
+        assert_eq!(params.initial_reputation(Amount::from_tokens(1_000_000)), DEFAULT_REPUTATION);
+    }
+
+    #[test]
+    fn test_bonus_is_bounded() {
+        let params = bonus_params(100, 90);
+
+        assert_eq!(
+            params.initial_reputation(Amount::from_tokens(100)),
+            DEFAULT_REPUTATION + MAX_HIGH_STAKE_REPUTATION_BONUS
+        );
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for re-requesting a query result over cross-chain messages
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::{Amount, ApplicationId, ChainId};
+    use oracle_registry_v2::{state::DecisionStrategy, Message, Operation};
+
+    /// Registry with three voters and a query created by `market` for market #4
+    fn market_query(market: ChainId) -> (TestHarness, u64) {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+
+        let market_app = ApplicationId::new(format!("{:064x}", 0xbb).parse().expect("valid hash"));
+        let response = harness.execute_as(market, Operation::CreateQueryWithCallback {
+            description: "Will it rain?".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            strategy: DecisionStrategy::Majority,
+            min_votes: Some(3),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            callback_chain: market,
+            callback_app: market_app,
+            callback_data: 4u64.to_le_bytes().to_vec(),
+        });
+        assert!(response.success, "{}", response.message);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+        (harness, query_id)
+    }
+
+    fn request_result(harness: &mut TestHarness, market: ChainId, query_id: u64) -> (ChainId, Message) {
+        let sent = harness.sent_messages().len();
+        harness.deliver_message(market, Message::RequestQueryResult { query_id, reply_to: market });
+
+        let mut replies = harness.sent_messages().split_off(sent);
+        assert_eq!(replies.len(), 1);
+        replies.remove(0)
+    }
+
+    #[test]
+    fn test_market_re_requests_result_after_resolution() {
+        let market = chain(9);
+        let (mut harness, query_id) = market_query(market);
+        for id in 1..=3 {
+            assert!(harness.submit_vote(chain(id), query_id, "Yes").success);
+        }
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        let resolved_at = harness.query(query_id).unwrap().resolved_at.unwrap();
+
+        // The original callback may have been lost; asking again gets the same result
+        let (destination, reply) = request_result(&mut harness, market, query_id);
+        assert_eq!(destination, market);
+        match reply {
+            Message::QueryResolutionCallback { query_id: id, resolved_outcome, resolved_at: at, callback_data } => {
+                assert_eq!(id, query_id);
+                assert_eq!(resolved_outcome, "Yes");
+                assert_eq!(at, resolved_at);
+                assert_eq!(callback_data, 4u64.to_le_bytes().to_vec());
+            }
+            other => panic!("Expected QueryResolutionCallback, got {:?}", other),
+        }
+        assert_eq!(*harness.state().next_failed_message_id.get(), 0);
+    }
+
+    #[test]
+    fn test_result_is_readable_while_paused() {
+        let market = chain(9);
+        let (mut harness, query_id) = market_query(market);
+        for id in 1..=3 {
+            assert!(harness.submit_vote(chain(id), query_id, "Yes").success);
+        }
+        harness.advance_past_deadline(query_id);
+        assert!(harness.resolve(query_id).success);
+
+        assert!(harness.execute_as(chain(0), Operation::PauseProtocol).success);
+        let response = harness.execute_as(market, Operation::GetQueryResult { query_id });
+        assert!(response.success, "{}", response.message);
+        let result = response.data.and_then(|data| data.query_result).expect("query result");
+        assert_eq!(result.result.as_deref(), Some("Yes"));
+
+        // Everything else is still blocked
+        let response = harness.execute_as(market, Operation::CancelQuery { query_id });
+        assert_eq!(response.message, "Protocol is paused");
+    }
+
+    #[test]
+    fn test_unresolved_query_replies_unavailable() {
+        let market = chain(9);
+        let (mut harness, query_id) = market_query(market);
+
+        let (destination, reply) = request_result(&mut harness, market, query_id);
+        assert_eq!(destination, market);
+        match reply {
+            Message::QueryResultUnavailable { query_id: id, reason } => {
+                assert_eq!(id, query_id);
+                assert!(reason.contains("not resolved yet"));
+            }
+            other => panic!("Expected QueryResultUnavailable, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unknown_query_replies_unavailable() {
+        let market = chain(9);
+        let (mut harness, query_id) = market_query(market);
+
+        let (_, reply) = request_result(&mut harness, market, query_id + 1);
+        match reply {
+            Message::QueryResultUnavailable { reason, .. } => assert!(reason.contains("not found")),
+            other => panic!("Expected QueryResultUnavailable, got {:?}", other),
+        }
+    }
+}
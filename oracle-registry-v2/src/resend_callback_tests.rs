@@ -0,0 +1,160 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for re-sending a query's resolution callback
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::{Amount, ApplicationId, ChainId};
+    use oracle_registry_v2::{state::DecisionStrategy, Message, Operation};
+
+    /// Registry with three voters and a query created by `market` for market #4
+    fn market_query(market: ChainId) -> (TestHarness, u64) {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+
+        let market_app = ApplicationId::new(format!("{:064x}", 0xbb).parse().expect("valid hash"));
+        let response = harness.execute_as(market, Operation::CreateQueryWithCallback {
+            description: "Will it rain?".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            strategy: DecisionStrategy::Majority,
+            min_votes: Some(3),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            callback_chain: market,
+            callback_app: market_app,
+            callback_data: 4u64.to_le_bytes().to_vec(),
+        });
+        assert!(response.success, "{}", response.message);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+        (harness, query_id)
+    }
+
+    fn resolve_yes(harness: &mut TestHarness, query_id: u64) {
+        for id in 1..=3 {
+            assert!(harness.submit_vote(chain(id), query_id, "Yes").success);
+        }
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+    }
+
+    /// Callbacks sent to `market`
+    fn callbacks_to(harness: &TestHarness, market: ChainId) -> Vec<Message> {
+        harness.sent_messages()
+            .into_iter()
+            .filter(|(destination, message)| {
+                *destination == market && matches!(message, Message::QueryResolutionCallback { .. })
+            })
+            .map(|(_, message)| message)
+            .collect()
+    }
+
+    #[test]
+    fn test_resend_delivers_the_original_callback_again() {
+        let market = chain(9);
+        let (mut harness, query_id) = market_query(market);
+        resolve_yes(&mut harness, query_id);
+        assert_eq!(callbacks_to(&harness, market).len(), 1);
+        let resolved_at = harness.query(query_id).unwrap().resolved_at.unwrap();
+
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::ResendCallback { query_id });
+        assert!(response.success, "{}", response.message);
+
+        let callbacks = callbacks_to(&harness, market);
+        assert_eq!(callbacks.len(), 2);
+        match &callbacks[1] {
+            Message::QueryResolutionCallback { query_id: id, resolved_outcome, resolved_at: at, callback_data } => {
+                assert_eq!(*id, query_id);
+                assert_eq!(resolved_outcome, "Yes");
+                assert_eq!(*at, resolved_at);
+                assert_eq!(*callback_data, 4u64.to_le_bytes().to_vec());
+            }
+            other => panic!("Expected QueryResolutionCallback, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_resend_repeats_a_median_callback_byte_for_byte() {
+        let market = chain(9);
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let market_app = ApplicationId::new(format!("{:064x}", 0xbb).parse().expect("valid hash"));
+        let response = harness.execute_as(market, Operation::CreateQueryWithCallback {
+            description: "Rainfall (mm)?".to_string(),
+            outcomes: vec!["1".to_string(), "2".to_string(), "4".to_string()],
+            strategy: DecisionStrategy::Median,
+            min_votes: Some(3),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            callback_chain: market,
+            callback_app: market_app,
+            callback_data: 4u64.to_le_bytes().to_vec(),
+        });
+        assert!(response.success, "{}", response.message);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+        for (id, value) in [(1, "1"), (2, "2"), (3, "4")] {
+            assert!(harness.submit_vote(chain(id), query_id, value).success);
+        }
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+
+        let admin = harness.admin;
+        assert!(harness.execute_as(admin, Operation::ResendCallback { query_id }).success);
+
+        let callbacks = callbacks_to(&harness, market);
+        assert_eq!(callbacks.len(), 2);
+        assert_eq!(bcs::to_bytes(&callbacks[0]).unwrap(), bcs::to_bytes(&callbacks[1]).unwrap());
+        match &callbacks[0] {
+            Message::QueryResolutionCallback { resolved_outcome, .. } => assert_eq!(resolved_outcome, "2"),
+            other => panic!("Expected QueryResolutionCallback, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_resend_requires_admin() {
+        let market = chain(9);
+        let (mut harness, query_id) = market_query(market);
+        resolve_yes(&mut harness, query_id);
+
+        let response = harness.execute_as(market, Operation::ResendCallback { query_id });
+        assert!(!response.success);
+        assert!(response.message.contains("only admin"));
+        assert_eq!(callbacks_to(&harness, market).len(), 1);
+    }
+
+    #[test]
+    fn test_resend_rejects_unresolved_query() {
+        let market = chain(9);
+        let (mut harness, query_id) = market_query(market);
+
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::ResendCallback { query_id });
+        assert!(!response.success);
+        assert!(response.message.contains("not resolved yet"), "{}", response.message);
+        assert!(callbacks_to(&harness, market).is_empty());
+    }
+
+    #[test]
+    fn test_resend_rejects_query_without_callback() {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let created = harness.create_query(&["Yes", "No"], 3, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        resolve_yes(&mut harness, query_id);
+
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::ResendCallback { query_id });
+        assert!(!response.success);
+        assert!(response.message.contains("no callback configured"));
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the reward and slash distributions reported by ResolveQuery
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::Amount;
+
+    #[test]
+    fn test_distributions_match_state_changes() {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let created = harness.create_query(&["Yes", "No"], 3, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        for (id, value) in [(1, "Yes"), (2, "Yes"), (3, "No")] {
+            assert!(harness.submit_vote(chain(id), query_id, value).success);
+        }
+        let stake_before = harness.voter(&chain(3)).unwrap().stake;
+
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        let data = response.data.expect("response data");
+        assert_eq!(data.query_id, Some(query_id));
+
+        let rewards = data.reward_distribution.expect("reward distribution");
+        assert_eq!(rewards.len(), 2);
+        for (entry, id) in rewards.iter().zip([1u8, 2]) {
+            assert_eq!(entry.voter_address, chain(id).to_string());
+            let pending = harness.state().get_pending_rewards(&chain(id)).blocking_wait();
+            assert!(pending > Amount::ZERO);
+            assert_eq!(entry.amount, pending.to_string());
+        }
+
+        let slashes = data.slash_distribution.expect("slash distribution");
+        assert_eq!(slashes.len(), 1);
+        assert_eq!(slashes[0].voter_address, chain(3).to_string());
+        let slashed = stake_before.saturating_sub(harness.voter(&chain(3)).unwrap().stake);
+        assert!(slashed > Amount::ZERO);
+        assert_eq!(slashes[0].amount, slashed.to_string());
+    }
+
+    #[test]
+    fn test_unanimous_resolution_reports_no_slashes() {
+        let mut harness = TestHarness::new();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let created = harness.create_query(&["Yes", "No"], 3, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        for id in 1..=3 {
+            assert!(harness.submit_vote(chain(id), query_id, "No").success);
+        }
+
+        harness.advance_past_deadline(query_id);
+        let data = harness.resolve(query_id).data.expect("response data");
+        assert_eq!(data.reward_distribution.map(|rewards| rewards.len()), Some(3));
+        assert_eq!(data.slash_distribution, Some(Vec::new()));
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the grace window between reveal end and resolution
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, chain, query};
+    use crate::state::{ProtocolParameters, ResolutionResult, VotingPhase};
+    use linera_sdk::linera_base_types::Timestamp;
+
+    const GRACE_SECS: u64 = 30;
+
+    #[test]
+    fn test_default_grace_window() {
+        assert_eq!(ProtocolParameters::default().resolution_grace_secs, 30);
+    }
+
+    #[test]
+    fn test_reveal_within_grace_window_counted_before_resolution() {
+        let mut q = query(&["Yes", "No"]);
+        q.phase = VotingPhase::Reveal;
+        add_vote(&mut q, 2, "No");
+        add_vote(&mut q, 3, "Yes");
+
+        // A reveal lands 10s after reveal_phase_end, before resolution opens
+        let late = Timestamp::from(12_000_000);
+        assert!(q.accepts_reveal_at(late, GRACE_SECS));
+        assert!(q.check_resolution_window(late, GRACE_SECS).is_err());
+        add_vote(&mut q, 4, "Yes");
+
+        let opens_at = q.resolution_opens_at(GRACE_SECS);
+        assert_eq!(opens_at, Timestamp::from(32_000_000));
+        assert!(q.check_resolution_window(opens_at, GRACE_SECS).is_ok());
+        assert_eq!(
+            ResolutionResult::from_majority(q.votes.values()),
+            ResolutionResult::Outcome("Yes".to_string())
+        );
+        assert!(q.votes.contains_key(&chain(4)));
+    }
+
+    #[test]
+    fn test_resolution_blocked_until_grace_window_ends() {
+        let q = query(&["Yes", "No"]);
+
+        let before = Timestamp::from(31_999_999);
+        let err = q.check_resolution_window(before, GRACE_SECS).unwrap_err();
+        assert!(err.contains("grace window"));
+        assert!(q.accepts_reveal_at(before, GRACE_SECS));
+
+        let after = Timestamp::from(32_000_000);
+        assert!(q.check_resolution_window(after, GRACE_SECS).is_ok());
+        assert!(!q.accepts_reveal_at(after, GRACE_SECS));
+    }
+
+    #[test]
+    fn test_zero_grace_opens_at_reveal_end() {
+        let q = query(&["Yes", "No"]);
+
+        assert_eq!(q.resolution_opens_at(0), q.reveal_phase_end);
+        assert!(q.check_resolution_window(q.reveal_phase_end, 0).is_ok());
+        assert!(!q.accepts_reveal_at(q.reveal_phase_end, 0));
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for typed resolution results
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{ResolutionResult, Vote, NO_CONSENSUS_OUTCOME};
+    use linera_sdk::linera_base_types::{ChainId, Timestamp};
+    
+    /// Helper to create a distinct chain ID per voter
+    fn chain(id: u8) -> ChainId {
+        format!("{:064x}", id).parse().expect("valid chain ID")
+    }
+    
+    /// Helper to create a vote
+    fn vote(id: u8, value: &str) -> Vote {
+        Vote {
+            voter: chain(id),
+            value: value.to_string(),
+            timestamp: Timestamp::from(1_000_000),
+            salt: None,
+            confidence: None,
+        }
+    }
+    
+    #[test]
+    fn test_majority_returns_outcome() {
+        let votes = vec![vote(1, "Yes"), vote(2, "No"), vote(3, "Yes")];
+        
+        let result = ResolutionResult::from_majority(&votes);
+        
+        assert_eq!(result, ResolutionResult::Outcome("Yes".to_string()));
+        assert_eq!(result.kind(), "Outcome");
+        assert_eq!(result.to_string(), "Yes");
+        assert!(result.matches_vote("Yes"));
+        assert!(!result.matches_vote("No"));
+    }
+    
+    #[test]
+    fn test_majority_without_votes_is_no_consensus() {
+        let votes: Vec<Vote> = Vec::new();
+        
+        let result = ResolutionResult::from_majority(&votes);
+        
+        assert_eq!(result, ResolutionResult::NoConsensus);
+        assert_eq!(result.to_string(), "No consensus");
+        assert!(!result.matches_vote("No consensus"));
+    }
+    
+    #[test]
+    fn test_weighted_tally_picks_heaviest_outcome() {
+        let tally = vec![("Yes".to_string(), 1.5), ("No".to_string(), 2.5)];
+        
+        let result = ResolutionResult::from_tally(tally);
+        
+        assert_eq!(result, ResolutionResult::Outcome("No".to_string()));
+    }
+    
+    #[test]
+    fn test_median_odd_and_even_vote_counts() {
+        let odd = vec![vote(1, "10"), vote(2, "30"), vote(3, "20")];
+        assert_eq!(ResolutionResult::from_median(&odd), ResolutionResult::NumericMedian(20.0));
+        
+        let even = vec![vote(1, "10"), vote(2, "20"), vote(3, "30"), vote(4, "40")];
+        let result = ResolutionResult::from_median(&even);
+        assert_eq!(result, ResolutionResult::NumericMedian(25.0));
+        assert_eq!(result.kind(), "NumericMedian");
+        assert_eq!(result.to_string(), "25");
+    }
+    
+    #[test]
+    fn test_median_matches_numerically_equal_votes() {
+        let result = ResolutionResult::NumericMedian(20.0);
+        
+        assert!(result.matches_vote("20"));
+        assert!(result.matches_vote("20.0"));
+        assert!(!result.matches_vote("21"));
+        assert!(!result.matches_vote("twenty"));
+    }
+    
+    #[test]
+    fn test_median_without_numeric_votes_is_no_consensus() {
+        let votes = vec![vote(1, "abc"), vote(2, "def")];
+        
+        assert_eq!(ResolutionResult::from_median(&votes), ResolutionResult::NoConsensus);
+    }
+    
+    #[test]
+    fn test_expired_matches_no_vote() {
+        let result = ResolutionResult::Expired;
+        
+        assert_eq!(result.kind(), "Expired");
+        assert_eq!(result.to_string(), "Expired");
+        assert!(!result.matches_vote("Expired"));
+    }
+    
+    #[test]
+    fn test_callback_outcome_has_one_encoding_per_kind() {
+        let outcome = ResolutionResult::Outcome("Yes".to_string());
+        assert_eq!(outcome.callback_outcome().unwrap(), "Yes");
+        assert_eq!(ResolutionResult::NumericMedian(2.5).callback_outcome().unwrap(), "2.5");
+        assert_eq!(ResolutionResult::NoConsensus.callback_outcome().unwrap(), NO_CONSENSUS_OUTCOME);
+        
+        // Multi-winner and expired results are never sent to a market
+        let winners = ResolutionResult::Outcomes(vec!["A".to_string(), "B".to_string()]);
+        assert!(winners.callback_outcome().is_err());
+        assert!(ResolutionResult::Expired.callback_outcome().is_err());
+    }
+}
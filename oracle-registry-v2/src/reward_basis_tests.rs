@@ -0,0 +1,105 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for splitting rewards by a per-query reward basis
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, chain, query, voter_info};
+    use crate::state::{
+        project_voter_reward, split_reward_for_query, DecisionStrategy, ProtocolParameters,
+        Query, ResolutionResult, RewardBasis, VoterInfo,
+    };
+    use linera_sdk::linera_base_types::{Amount, ChainId};
+    use std::collections::BTreeMap;
+
+    fn no_fee() -> ProtocolParameters {
+        ProtocolParameters { protocol_fee: 0, ..ProtocolParameters::default() }
+    }
+
+    /// Majority query where voters 2 and 3 vote Yes and voter 4 votes No
+    ///
+    /// Voter 3 has three times voter 2's stake at the same reputation.
+    fn majority_query(basis: Option<RewardBasis>) -> (Query, Vec<(ChainId, VoterInfo)>) {
+        let mut q = query(&["Yes", "No"]);
+        q.reward_basis = basis;
+        add_vote(&mut q, 2, "Yes");
+        add_vote(&mut q, 3, "Yes");
+        add_vote(&mut q, 4, "No");
+
+        let correct = vec![(chain(2), voter_info(2, 100, 50)), (chain(3), voter_info(3, 300, 50))];
+        (q, correct)
+    }
+
+    fn reward_of(q: &Query, correct: &[(ChainId, VoterInfo)], id: u8) -> Amount {
+        let split = split_reward_for_query(q, correct, &no_fee());
+        split.check_invariant(q.reward_amount).unwrap();
+        split.rewards[&chain(id)]
+    }
+
+    #[test]
+    fn test_majority_resolution_rewarded_by_power() {
+        let (q, correct) = majority_query(Some(RewardBasis::Power));
+        assert_eq!(q.strategy, DecisionStrategy::Majority);
+
+        // The result is still picked by simple majority
+        let result = ResolutionResult::from_majority(q.votes.values());
+        assert_eq!(result, ResolutionResult::Outcome("Yes".to_string()));
+
+        // But the 100 token reward follows power (stake × reputation): 1:3
+        assert_eq!(reward_of(&q, &correct, 2), Amount::from_tokens(25));
+        assert_eq!(reward_of(&q, &correct, 3), Amount::from_tokens(75));
+    }
+
+    #[test]
+    fn test_unset_basis_follows_strategy() {
+        // Majority splits by reputation multiplier alone: equal reputation, equal share
+        let (q, correct) = majority_query(None);
+        assert_eq!(reward_of(&q, &correct, 2), Amount::from_tokens(50));
+        assert_eq!(reward_of(&q, &correct, 3), Amount::from_tokens(50));
+    }
+
+    #[test]
+    fn test_equal_basis_ignores_stake() {
+        let (q, correct) = majority_query(Some(RewardBasis::Equal));
+        assert_eq!(reward_of(&q, &correct, 2), Amount::from_tokens(50));
+        assert_eq!(reward_of(&q, &correct, 3), Amount::from_tokens(50));
+    }
+
+    #[test]
+    fn test_stake_basis() {
+        let (q, correct) = majority_query(Some(RewardBasis::Stake));
+        assert_eq!(reward_of(&q, &correct, 2), Amount::from_tokens(25));
+        assert_eq!(reward_of(&q, &correct, 3), Amount::from_tokens(75));
+    }
+
+    #[test]
+    fn test_confidence_basis_uses_vote_confidence() {
+        let (mut q, correct) = majority_query(Some(RewardBasis::Confidence));
+        q.votes.get_mut(&chain(2)).unwrap().confidence = Some(80);
+        q.votes.get_mut(&chain(3)).unwrap().confidence = Some(20);
+
+        assert_eq!(reward_of(&q, &correct, 2), Amount::from_tokens(80));
+        assert_eq!(reward_of(&q, &correct, 3), Amount::from_tokens(20));
+    }
+
+    #[test]
+    fn test_estimate_uses_reward_basis() {
+        let (q, correct) = majority_query(Some(RewardBasis::Power));
+        let infos: BTreeMap<ChainId, VoterInfo> = correct.iter().cloned()
+            .chain(std::iter::once((chain(4), voter_info(4, 100, 50))))
+            .collect();
+
+        let (_, estimate) = project_voter_reward(&q, &chain(3), &infos, &no_fee()).unwrap();
+
+        assert_eq!(estimate, Amount::from_tokens(75));
+    }
+
+    #[test]
+    fn test_parse_reward_basis() {
+        assert_eq!(RewardBasis::parse("power"), Ok(RewardBasis::Power));
+        assert_eq!(RewardBasis::parse("Confidence"), Ok(RewardBasis::Confidence));
+        assert!(RewardBasis::parse("Quadratic").is_err());
+        assert_eq!(RewardBasis::Stake.as_str(), "Stake");
+    }
+}
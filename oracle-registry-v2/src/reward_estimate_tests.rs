@@ -0,0 +1,91 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for projecting a voter's reward before resolution
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, chain, query, voter_info};
+    use crate::state::{
+        project_voter_reward, split_query_reward, DecisionStrategy, ProtocolParameters,
+        Query, ResolutionResult, VoterInfo,
+    };
+    use linera_sdk::linera_base_types::{Amount, ChainId};
+    use std::collections::BTreeMap;
+
+    /// Voters 2..=5 with differing stake and reputation
+    fn voter_infos() -> BTreeMap<ChainId, VoterInfo> {
+        [(2, 100, 50), (3, 300, 80), (4, 200, 20), (5, 150, 60)]
+            .into_iter()
+            .map(|(id, stake, reputation)| (chain(id), voter_info(id, stake, reputation)))
+            .collect()
+    }
+
+    /// Reward `voter` receives when the query resolves on its current votes
+    fn realized_reward(
+        query: &Query,
+        voter: &ChainId,
+        infos: &BTreeMap<ChainId, VoterInfo>,
+        params: &ProtocolParameters,
+    ) -> Amount {
+        let result = ResolutionResult::from_majority(query.votes.values());
+        let correct_voters: Vec<(ChainId, VoterInfo)> = query.votes.iter()
+            .filter(|(_, vote)| result.matches_vote(&vote.value))
+            .map(|(chain, _)| (*chain, infos[chain].clone()))
+            .collect();
+        let split = split_query_reward(&query.strategy, query.reward_amount, &correct_voters, params);
+        split.rewards.get(voter).copied().unwrap_or(Amount::ZERO)
+    }
+
+    #[test]
+    fn test_estimate_matches_realized_reward_for_voter() {
+        let params = ProtocolParameters::default();
+        let infos = voter_infos();
+
+        for strategy in [DecisionStrategy::Majority, DecisionStrategy::WeightedByStake, DecisionStrategy::WeightedByReputation] {
+            let mut q = query(&["Yes", "No"]);
+            q.strategy = strategy;
+            add_vote(&mut q, 2, "Yes");
+            add_vote(&mut q, 3, "Yes");
+            add_vote(&mut q, 4, "No");
+
+            let (result, estimate) = project_voter_reward(&q, &chain(2), &infos, &params)
+                .expect("voter has voted");
+            assert_eq!(result, ResolutionResult::Outcome("Yes".to_string()));
+            assert!(estimate > Amount::ZERO);
+            assert_eq!(estimate, realized_reward(&q, &chain(2), &infos, &params));
+        }
+    }
+
+    #[test]
+    fn test_estimate_for_new_voter_assumes_leading_outcome() {
+        let params = ProtocolParameters::default();
+        let infos = voter_infos();
+        let mut q = query(&["Yes", "No"]);
+        add_vote(&mut q, 2, "Yes");
+        add_vote(&mut q, 3, "Yes");
+        add_vote(&mut q, 4, "No");
+
+        let (result, estimate) = project_voter_reward(&q, &chain(5), &infos, &params)
+            .expect("leading outcome exists");
+        assert_eq!(result, ResolutionResult::Outcome("Yes".to_string()));
+
+        // Voter joins the leader and no further votes arrive
+        add_vote(&mut q, 5, "Yes");
+        assert_eq!(estimate, realized_reward(&q, &chain(5), &infos, &params));
+    }
+
+    #[test]
+    fn test_no_estimate_without_votes_or_registration() {
+        let params = ProtocolParameters::default();
+        let infos = voter_infos();
+        let mut q = query(&["Yes", "No"]);
+
+        // Nothing to project from yet
+        assert!(project_voter_reward(&q, &chain(2), &infos, &params).is_none());
+
+        // Unregistered voter
+        add_vote(&mut q, 2, "Yes");
+        assert!(project_voter_reward(&q, &chain(9), &infos, &params).is_none());
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for reclaiming pending rewards left unclaimed past the expiry
+
+#[cfg(test)]
+mod tests {
+    use crate::state::ProtocolParameters;
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::Operation;
+
+    const EXPIRY_SECS: u64 = 3600;
+
+    fn harness() -> TestHarness {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            reward_claim_expiry_secs: EXPIRY_SECS,
+            ..ProtocolParameters::default()
+        });
+        for id in 1..=2 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        harness
+    }
+
+    #[test]
+    fn test_reclaim_returns_expired_rewards_to_pool() {
+        let mut harness = harness();
+        harness.credit_rewards(chain(1), Amount::from_tokens(5));
+        harness.advance_secs(EXPIRY_SECS / 2);
+        harness.credit_rewards(chain(2), Amount::from_tokens(3));
+        harness.advance_secs(EXPIRY_SECS / 2 + 1);
+        let pool_before = *harness.state().reward_pool.get();
+
+        let response = harness.execute_as(chain(9), Operation::ReclaimUnclaimedRewards { max: 100 });
+        assert!(response.success, "{}", response.message);
+
+        assert_eq!(
+            *harness.state().reward_pool.get(),
+            pool_before.saturating_add(Amount::from_tokens(5))
+        );
+        let pending = harness.state().get_pending_rewards(&chain(1)).blocking_wait();
+        assert_eq!(pending, Amount::ZERO);
+        let credited_at = harness.state().reward_credited_at.get(&chain(1)).blocking_wait();
+        assert!(credited_at.expect("readable").is_none());
+        // Credited more recently, so still claimable
+        let pending = harness.state().get_pending_rewards(&chain(2)).blocking_wait();
+        assert_eq!(pending, Amount::from_tokens(3));
+
+        // Nothing left past the expiry
+        let response = harness.execute_as(chain(9), Operation::ReclaimUnclaimedRewards { max: 100 });
+        assert!(response.success);
+        assert_eq!(response.message, "No expired rewards to reclaim");
+    }
+
+    #[test]
+    fn test_reclaim_rejected_when_expiry_disabled() {
+        let mut harness = TestHarness::new();
+        assert!(harness.register(chain(1), 100).success);
+        harness.credit_rewards(chain(1), Amount::from_tokens(5));
+        harness.advance_secs(365 * 24 * 3600);
+
+        let response = harness.execute_as(chain(9), Operation::ReclaimUnclaimedRewards { max: 100 });
+        assert!(!response.success);
+        assert_eq!(response.message, "Reward claim expiry is disabled");
+    }
+
+    #[test]
+    fn test_reclaim_checks_at_most_max_voters_per_call() {
+        let mut harness = harness();
+        harness.credit_rewards(chain(1), Amount::from_tokens(5));
+        harness.credit_rewards(chain(2), Amount::from_tokens(3));
+        harness.advance_secs(EXPIRY_SECS + 1);
+        let pool_before = *harness.state().reward_pool.get();
+
+        let response = harness.execute_as(chain(9), Operation::ReclaimUnclaimedRewards { max: 1 });
+        assert!(response.success, "{}", response.message);
+        assert_eq!(response.data.and_then(|data| data.vote_count), Some(1));
+        let first = harness.state().reclaim_cursor.get().expect("one voter left to check");
+        let second = if first == chain(1) { chain(2) } else { chain(1) };
+        assert_eq!(harness.state().get_pending_rewards(&first).blocking_wait(), Amount::ZERO);
+        assert!(harness.state().get_pending_rewards(&second).blocking_wait() > Amount::ZERO);
+
+        // The next call continues after the cursor and finishes the pass
+        let response = harness.execute_as(chain(9), Operation::ReclaimUnclaimedRewards { max: 1 });
+        assert!(response.success, "{}", response.message);
+        assert_eq!(response.data.and_then(|data| data.vote_count), Some(0));
+        assert_eq!(*harness.state().reclaim_cursor.get(), None);
+        assert_eq!(harness.state().get_pending_rewards(&second).blocking_wait(), Amount::ZERO);
+        assert_eq!(
+            *harness.state().reward_pool.get(),
+            pool_before.saturating_add(Amount::from_tokens(8))
+        );
+    }
+
+    #[test]
+    fn test_reclaim_rejects_invalid_max() {
+        let mut harness = harness();
+
+        let response = harness.execute_as(chain(9), Operation::ReclaimUnclaimedRewards { max: 0 });
+        assert!(!response.success);
+        assert_eq!(response.message, "Max must be between 1 and 1000");
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the bounded reward and slash history logs
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{
+        flow_history_evicted_id, flow_history_page, FlowEntry, MAX_FLOW_HISTORY,
+        MAX_FLOW_HISTORY_PAGE,
+    };
+    use linera_sdk::linera_base_types::{Amount, Timestamp};
+
+    fn entry(id: u64, secs: u64, query_id: u64, tokens: u128) -> FlowEntry {
+        FlowEntry {
+            id,
+            timestamp: Timestamp::from(secs * 1_000_000),
+            query_id,
+            amount: Amount::from_tokens(tokens),
+        }
+    }
+
+    /// Five resolutions, one every 10 seconds starting at t=10s
+    fn resolutions() -> Vec<FlowEntry> {
+        (0..5).map(|i| entry(i, 10 * (i + 1), 100 + i, 10 + i as u128)).collect()
+    }
+
+    #[test]
+    fn test_history_within_window() {
+        let page = flow_history_page(
+            resolutions(),
+            Timestamp::from(20_000_000),
+            Timestamp::from(40_000_000),
+            0,
+            MAX_FLOW_HISTORY_PAGE,
+        );
+
+        let query_ids: Vec<u64> = page.iter().map(|e| e.query_id).collect();
+        assert_eq!(query_ids, vec![101, 102, 103]);
+
+        let total = page.iter().fold(Amount::ZERO, |acc, e| acc.saturating_add(e.amount));
+        assert_eq!(total, Amount::from_tokens(11 + 12 + 13));
+    }
+
+    #[test]
+    fn test_window_outside_history_is_empty() {
+        let page = flow_history_page(
+            resolutions(),
+            Timestamp::from(60_000_000),
+            Timestamp::from(u64::MAX),
+            0,
+            MAX_FLOW_HISTORY_PAGE,
+        );
+        assert!(page.is_empty());
+    }
+
+    #[test]
+    fn test_pagination() {
+        let all = Timestamp::from(u64::MAX);
+        let first = flow_history_page(resolutions(), Timestamp::from(0), all, 0, 2);
+        let second = flow_history_page(resolutions(), Timestamp::from(0), all, 2, 2);
+        let third = flow_history_page(resolutions(), Timestamp::from(0), all, 4, 2);
+
+        assert_eq!(first.iter().map(|e| e.id).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(second.iter().map(|e| e.id).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(third.iter().map(|e| e.id).collect::<Vec<_>>(), vec![4]);
+    }
+
+    #[test]
+    fn test_page_size_is_capped() {
+        let many: Vec<FlowEntry> = (0..250).map(|i| entry(i, i + 1, i, 1)).collect();
+        let page = flow_history_page(many, Timestamp::from(0), Timestamp::from(u64::MAX), 0, 1000);
+        assert_eq!(page.len(), MAX_FLOW_HISTORY_PAGE);
+    }
+
+    #[test]
+    fn test_log_is_bounded() {
+        assert_eq!(flow_history_evicted_id(0), None);
+        assert_eq!(flow_history_evicted_id(MAX_FLOW_HISTORY - 1), None);
+        assert_eq!(flow_history_evicted_id(MAX_FLOW_HISTORY), Some(0));
+        assert_eq!(flow_history_evicted_id(MAX_FLOW_HISTORY + 3), Some(3));
+    }
+}
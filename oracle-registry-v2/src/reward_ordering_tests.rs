@@ -0,0 +1,70 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests that reward distribution does not depend on voter iteration order
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{chain, voter_info};
+    use crate::state::{split_query_reward, DecisionStrategy, ProtocolParameters, ResolutionResult, VoterInfo};
+    use linera_sdk::linera_base_types::{Amount, ChainId};
+    use std::collections::BTreeMap;
+
+    fn correct_voters() -> Vec<(ChainId, VoterInfo)> {
+        [(5, 7, 90), (2, 100, 50), (9, 333, 77), (4, 1, 3)]
+            .into_iter()
+            .map(|(id, stake, reputation)| (chain(id), voter_info(id, stake, reputation)))
+            .collect()
+    }
+
+    #[test]
+    fn test_repeated_resolutions_distribute_identically() {
+        let params = ProtocolParameters::default();
+        let reward = Amount::from_attos(1_000_000_007);
+
+        for strategy in [
+            DecisionStrategy::Majority,
+            DecisionStrategy::WeightedByStake,
+            DecisionStrategy::WeightedByReputation,
+        ] {
+            let first = split_query_reward(&strategy, reward, &correct_voters(), &params);
+
+            for _ in 0..3 {
+                let again = split_query_reward(&strategy, reward, &correct_voters(), &params);
+                assert_eq!(again.rewards, first.rewards);
+                assert_eq!(again.dust, first.dust);
+                assert_eq!(again.fee, first.fee);
+            }
+        }
+    }
+
+    #[test]
+    fn test_input_order_does_not_change_distribution() {
+        let params = ProtocolParameters::default();
+        let reward = Amount::from_attos(1_000_000_007);
+        let voters = correct_voters();
+        let mut reversed = voters.clone();
+        reversed.reverse();
+        let mut sorted = voters.clone();
+        sorted.sort_by_key(|(voter, _)| *voter);
+
+        let split = split_query_reward(&DecisionStrategy::WeightedByStake, reward, &voters, &params);
+        for other in [reversed, sorted] {
+            let other_split = split_query_reward(&DecisionStrategy::WeightedByStake, reward, &other, &params);
+            assert_eq!(other_split.rewards, split.rewards);
+            assert_eq!(other_split.dust, split.dust);
+        }
+    }
+
+    #[test]
+    fn test_tied_tally_resolves_the_same_way() {
+        let tally: BTreeMap<String, u128> = [("No".to_string(), 10), ("Yes".to_string(), 10)].into_iter().collect();
+
+        for _ in 0..3 {
+            assert_eq!(
+                ResolutionResult::from_tally(tally.clone()),
+                ResolutionResult::Outcome("Yes".to_string())
+            );
+        }
+    }
+}
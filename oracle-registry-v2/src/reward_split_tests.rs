@@ -0,0 +1,73 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for fee-first reward splitting at resolution
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{chain, voter_info};
+    use crate::state::{split_query_reward, DecisionStrategy, ProtocolParameters, VoterInfo};
+    use linera_sdk::linera_base_types::{Amount, ChainId};
+
+    fn correct_voters() -> Vec<(ChainId, VoterInfo)> {
+        [(2, 100, 50), (3, 333, 77), (4, 1, 3)]
+            .into_iter()
+            .map(|(id, stake, reputation)| (chain(id), voter_info(id, stake, reputation)))
+            .collect()
+    }
+
+    #[test]
+    fn test_distributed_plus_fee_equals_reward() {
+        let params = ProtocolParameters::default();
+        let voters = correct_voters();
+
+        for strategy in [
+            DecisionStrategy::Majority,
+            DecisionStrategy::WeightedByStake,
+            DecisionStrategy::WeightedByReputation,
+        ] {
+            for reward in [Amount::from_tokens(1000), Amount::from_attos(1_000_000_007)] {
+                let split = split_query_reward(&strategy, reward, &voters, &params);
+
+                assert!(split.check_invariant(reward).is_ok());
+                assert_eq!(split.distributed().saturating_add(split.fee).saturating_add(split.dust), reward);
+                // Dust is at most one atto per voter
+                assert!(split.dust < Amount::from_attos(voters.len() as u128));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fee_is_deducted_before_distribution() {
+        let params = ProtocolParameters::default(); // 1% fee
+        let reward = Amount::from_tokens(1000);
+        let split = split_query_reward(&DecisionStrategy::Majority, reward, &correct_voters(), &params);
+
+        assert_eq!(split.fee, Amount::from_tokens(10));
+        assert!(split.distributed() <= Amount::from_tokens(990));
+    }
+
+    #[test]
+    fn test_stake_weighting_favours_larger_stake() {
+        let params = ProtocolParameters::default();
+        let split = split_query_reward(
+            &DecisionStrategy::WeightedByStake,
+            Amount::from_tokens(1000),
+            &correct_voters(),
+            &params,
+        );
+
+        assert!(split.rewards[&chain(3)] > split.rewards[&chain(2)]);
+        assert!(split.rewards[&chain(2)] > split.rewards[&chain(4)]);
+    }
+
+    #[test]
+    fn test_invariant_detects_mismatch() {
+        let params = ProtocolParameters::default();
+        let reward = Amount::from_tokens(1000);
+        let mut split = split_query_reward(&DecisionStrategy::Majority, reward, &correct_voters(), &params);
+        split.fee = split.fee.saturating_add(Amount::from_attos(1));
+
+        assert!(split.check_invariant(reward).is_err());
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for passing over recently selected voters
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::chain;
+    use crate::state::{apply_selection_cooldown, in_selection_cooldown, ProtocolParameters};
+
+    #[test]
+    fn test_cooldown_disabled_by_default() {
+        assert_eq!(ProtocolParameters::default().selection_cooldown_queries, 0);
+        assert!(!in_selection_cooldown(4, 5, 0));
+    }
+
+    #[test]
+    fn test_cooldown_window() {
+        // Selected for query 4, cooldown of 2 queries
+        assert!(in_selection_cooldown(4, 5, 2));
+        assert!(in_selection_cooldown(4, 6, 2));
+        assert!(!in_selection_cooldown(4, 7, 2));
+    }
+
+    #[test]
+    fn test_high_power_voter_passed_over_when_panel_can_be_filled() {
+        // chain(1) has the most power and was selected for the previous query
+        let ranked = vec![chain(1), chain(2), chain(3), chain(4)];
+        let recently_selected = vec![chain(1)];
+
+        let panel = apply_selection_cooldown(ranked, &recently_selected, 3);
+
+        assert_eq!(panel, vec![chain(2), chain(3), chain(4)]);
+    }
+
+    #[test]
+    fn test_recent_voters_top_up_short_panel() {
+        let ranked = vec![chain(1), chain(2), chain(3), chain(4)];
+        let recently_selected = vec![chain(1), chain(2), chain(3)];
+
+        let panel = apply_selection_cooldown(ranked, &recently_selected, 3);
+
+        // Fresh voter first, then the strongest recently selected voters
+        assert_eq!(panel, vec![chain(4), chain(1), chain(2)]);
+    }
+
+    #[test]
+    fn test_no_recent_voters_keeps_ranking() {
+        let ranked = vec![chain(1), chain(2), chain(3)];
+
+        let panel = apply_selection_cooldown(ranked.clone(), &[], 2);
+
+        assert_eq!(panel, ranked);
+    }
+}
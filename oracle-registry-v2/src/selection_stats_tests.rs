@@ -0,0 +1,94 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for per-voter selection counts and their concentration
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{selection_gini_bps, ProtocolParameters, SelectionRecord};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::ChainId;
+    use std::collections::BTreeMap;
+
+    /// Four voters with distinct stakes, re-selected only when a panel of two needs them
+    fn rotating() -> TestHarness {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            selection_cooldown_queries: 1,
+            ..ProtocolParameters::default()
+        });
+        for id in 1..=4 {
+            assert!(harness.register(chain(id), 100 * id as u128).success);
+        }
+        harness
+    }
+
+    fn create(harness: &mut TestHarness) -> u64 {
+        let response = harness.create_query(&["Yes", "No"], 2, 120);
+        assert!(response.success, "{}", response.message);
+        response.data.and_then(|data| data.query_id).expect("query id")
+    }
+
+    fn record(harness: &TestHarness, voter: ChainId) -> SelectionRecord {
+        harness.state().get_selection_record(&voter).blocking_wait()
+    }
+
+    #[test]
+    fn test_selection_counts_match_panels() {
+        let mut harness = rotating();
+        let query_ids: Vec<u64> = (0..5).map(|_| create(&mut harness)).collect();
+
+        let mut expected: BTreeMap<ChainId, u64> = BTreeMap::new();
+        for query_id in &query_ids {
+            for voter in harness.query(*query_id).unwrap().selected_voters {
+                *expected.entry(voter).or_default() += 1;
+            }
+        }
+        for id in 1..=4 {
+            let selected = expected.get(&chain(id)).copied().unwrap_or_default();
+            assert_eq!(record(&harness, chain(id)).selected, selected);
+        }
+
+        let stats = harness.state().compute_selection_stats().blocking_wait().unwrap();
+        assert_eq!(stats.voters.len(), 4);
+        assert_eq!(stats.total_selections, expected.values().sum::<u64>());
+        assert!(stats.voters.windows(2).all(|pair| pair[0].1.selected >= pair[1].1.selected));
+        let counts: Vec<u64> = stats.voters.iter().map(|(_, record)| record.selected).collect();
+        assert_eq!(stats.concentration_bps, selection_gini_bps(&counts));
+    }
+
+    #[test]
+    fn test_participation_recorded_when_query_closes() {
+        let mut harness = rotating();
+        let query_id = create(&mut harness);
+        let panel = harness.query(query_id).unwrap().selected_voters;
+        assert_eq!(panel.len(), 4);
+        assert_eq!(record(&harness, chain(1)).participation_bps(), None);
+
+        assert!(harness.submit_vote(chain(1), query_id, "Yes").success);
+        assert!(harness.submit_vote(chain(2), query_id, "Yes").success);
+        assert!(harness.submit_vote(chain(3), query_id, "No").success);
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+
+        for id in 1..=3 {
+            let voted = record(&harness, chain(id));
+            assert_eq!((voted.selected, voted.closed, voted.participated), (1, 1, 1));
+            assert_eq!(voted.participation_bps(), Some(10000));
+        }
+        let absent = record(&harness, chain(4));
+        assert_eq!((absent.selected, absent.closed, absent.participated), (1, 1, 0));
+        assert_eq!(absent.participation_bps(), Some(0));
+    }
+
+    #[test]
+    fn test_selection_gini() {
+        assert_eq!(selection_gini_bps(&[]), 0);
+        assert_eq!(selection_gini_bps(&[0, 0, 0]), 0);
+        assert_eq!(selection_gini_bps(&[3, 3, 3, 3]), 0);
+        // One voter holds every selection: (n - 1) / n
+        assert_eq!(selection_gini_bps(&[0, 0, 0, 8]), 7500);
+        assert_eq!(selection_gini_bps(&[1, 3]), 2500);
+    }
+}
@@ -54,6 +54,18 @@ pub struct Voter {
     /// Registration timestamp (ISO 8601 format)
     pub registered_at: String,
     
+    /// Last activity timestamp: register, vote, claim or stake change (ISO 8601 format)
+    pub last_active_at: String,
+    
+    /// No activity in the last 30 days
+    pub is_dormant: bool,
+    
+    /// Whether ClaimRewards would currently succeed
+    pub can_claim: bool,
+    
+    /// Why claiming is blocked (paused protocol, inactive voter, nothing pending)
+    pub claim_blocked_reason: Option<String>,
+    
     /// Is voter currently active
     pub is_active: bool,
     
@@ -62,6 +74,103 @@ pub struct Voter {
     
     /// Optional metadata URL
     pub metadata_url: Option<String>,
+    
+    /// Referral code given at registration (lowercase)
+    pub referral: Option<String>,
+    
+    /// Mean Brier score of resolved votes with a confidence, in basis points
+    /// (lower is better calibrated; null until such a vote resolves)
+    pub calibration_score: Option<u32>,
+    
+    /// Number of resolved votes behind `calibration_score`
+    pub calibrated_votes: u64,
+    
+    /// Encrypted metadata blob as hex, for the voter to decrypt off-chain
+    pub encrypted_metadata: Option<String>,
+}
+
+/// Token balances shown on a voter dashboard
+#[derive(SimpleObject, Clone)]
+pub struct VoterBalances {
+    /// Staked amount (in tokens)
+    pub stake: String,
+    
+    /// Locked stake for active votes (in tokens)
+    pub locked_stake: String,
+    
+    /// Available stake (stake - locked_stake, in tokens)
+    pub available_stake: String,
+    
+    /// Pending rewards to claim (in tokens)
+    pub pending_rewards: String,
+    
+    /// Tokens the registry holds for this voter
+    pub token_holdings: String,
+}
+
+/// A voter's vote on a resolved query
+#[derive(SimpleObject, Clone)]
+pub struct VoterHistoryEntry {
+    pub query_id: u64,
+    
+    /// Value the voter revealed
+    pub value: String,
+    
+    /// Whether the vote matched the result (null for abstentions)
+    pub correct: Option<bool>,
+    
+    /// Resolution timestamp (microseconds as string)
+    pub resolved_at: Option<String>,
+}
+
+/// Lifetime rewards and slashes of a voter
+#[derive(SimpleObject, Clone)]
+pub struct VoterEarningsInfo {
+    /// Total rewarded at resolution (in tokens)
+    pub total_rewarded: String,
+    
+    /// Total slashed at resolution (in tokens)
+    pub total_slashed: String,
+    
+    /// Rewards not yet claimed (in tokens)
+    pub pending_rewards: String,
+}
+
+/// Everything a voter dashboard shows, read in one request
+#[derive(SimpleObject, Clone)]
+pub struct VoterDashboard {
+    pub profile: Voter,
+    pub balances: VoterBalances,
+    
+    /// Active queries the voter was selected for
+    pub selected_queries: Vec<Query>,
+    
+    /// Active queries with a commit the voter has not revealed yet
+    pub pending_reveals: Vec<Query>,
+    
+    /// Votes on resolved queries, most recently resolved first
+    pub recent_history: Vec<VoterHistoryEntry>,
+    
+    pub earnings: VoterEarningsInfo,
+}
+
+/// A voter's track record in its canonical, portable form
+#[derive(SimpleObject, Clone)]
+pub struct VoterAttestationInfo {
+    /// Voter chain ID (lowercase hex)
+    pub address: String,
+    pub reputation: u32,
+    pub total_votes: u64,
+    pub correct_votes: u64,
+    
+    /// Registration time in microseconds since the Unix epoch
+    pub registered_at: String,
+    
+    /// Registry block height the record was read at
+    pub block: String,
+    
+    /// Lowercase hex of the BCS-encoded record (fields in the order above)
+    pub encoding: String,
 }
 
 /// GraphQL representation of a Query/Market
@@ -76,12 +185,51 @@ pub struct Query {
     /// Possible outcomes
     pub outcomes: Vec<String>,
     
-    /// Decision strategy (Majority, Median, WeightedByStake, WeightedByReputation)
+    /// Synonyms accepted in votes, as "alias=outcome" entries
+    pub outcome_aliases: Vec<String>,
+    
+    /// Decision strategy (Majority, Median, WeightedByStake, WeightedByReputation, ConfidenceWeightedMedian)
     pub strategy: String,
     
+    /// How rewards are split (Equal, Stake, Reputation, Power, Confidence); null follows the strategy
+    pub reward_basis: Option<String>,
+    
     /// Minimum votes required for resolution
     pub min_votes: u32,
     
+    /// Minimum number of distinct outcomes that must receive votes (if set)
+    pub min_distinct_voted_outcomes: Option<u32>,
+    
+    /// Minimum number of votes the winning outcome needs (if set)
+    pub min_votes_for_winner: Option<u32>,
+    
+    /// Minimum number of distinct voter chains with tallied votes (if set)
+    pub min_distinct_voters: Option<u32>,
+    
+    /// Largest share of voting power one address-prefix group may hold, in basis points (if set)
+    pub max_group_power_bps: Option<u32>,
+    
+    /// Minimum stake a voter needs to vote on this query (if set)
+    pub min_voter_stake: Option<String>,
+    
+    /// Whether voters may vote "Abstain" (counted as participation, not tallied)
+    pub allow_abstain: bool,
+    
+    /// Whether several outcomes may win together
+    pub multi_winner: bool,
+    
+    /// Whether the admin may resolve this query from external data
+    pub allow_external_resolution: bool,
+    
+    /// How a tie for the most votes resolves (OutcomeOrder, HigherStake, HigherReputation, NoConsensus)
+    pub tie_break: String,
+    
+    /// Browsing category (e.g. sports, weather, crypto)
+    pub category: Option<String>,
+    
+    /// Free-form tags
+    pub tags: Vec<String>,
+    
     /// Reward amount for correct voters (in tokens)
     pub reward_amount: String,
     
@@ -100,22 +248,33 @@ pub struct Query {
     /// Reveal phase end time (microseconds as string)
     pub reveal_end: String,
     
-    /// Query status (Active, Resolved, Expired, Cancelled)
+    /// Query status (Active, Resolved, Expired, Cancelled, NoConsensus)
     pub status: String,
     
     /// Current voting phase (Commit, Reveal, Completed)
     pub phase: String,
     
-    /// Resolved result (if resolved)
+    /// Resolved result as display string (if resolved)
     pub result: Option<String>,
     
+    /// Resolved result as display string (same as `result`)
+    pub result_display: Option<String>,
+    
+    /// Typed resolution result (if resolved or expired)
+    pub resolution: Option<Resolution>,
+    
     /// Resolution timestamp (ISO 8601 format, if resolved)
     pub resolved_at: Option<String>,
     
+    /// External data reference the query was resolved from (if resolved externally)
+    pub resolution_source: Option<String>,
+    
     /// Number of commits (phase 1)
     pub commit_count: u32,
     
     /// Number of revealed votes (phase 2)
+    /// 
+    /// Always taken from the query's own votes, not the `vote_counts` map.
     pub vote_count: u32,
     
     /// Time remaining until deadline (in seconds, 0 if expired)
@@ -125,6 +284,64 @@ pub struct Query {
     pub votes: Option<Vec<QueryVote>>,
 }
 
+/// GraphQL representation of a typed resolution result
+#[derive(SimpleObject, Clone)]
+pub struct Resolution {
+    /// Result kind (Outcome, Outcomes, NumericMedian, NoConsensus, Expired)
+    pub kind: String,
+    
+    /// Winning outcome (Outcome only)
+    pub outcome: Option<String>,
+    
+    /// Every winning outcome (Outcome and Outcomes)
+    pub outcomes: Vec<String>,
+    
+    /// Median value (NumericMedian only)
+    pub numeric_value: Option<f64>,
+    
+    /// Human-readable result
+    pub display: String,
+}
+
+impl Resolution {
+    /// Convert from state ResolutionResult to GraphQL Resolution
+    fn from_result(result: &state::ResolutionResult) -> Self {
+        let (outcome, outcomes, numeric_value) = match result {
+            state::ResolutionResult::Outcome(outcome) => (Some(outcome.clone()), vec![outcome.clone()], None),
+            state::ResolutionResult::Outcomes(outcomes) => (None, outcomes.clone(), None),
+            state::ResolutionResult::NumericMedian(median) => (None, Vec::new(), Some(*median)),
+            state::ResolutionResult::NoConsensus | state::ResolutionResult::Expired => (None, Vec::new(), None),
+        };
+        
+        Self {
+            kind: result.kind().to_string(),
+            outcome,
+            outcomes,
+            numeric_value,
+            display: result.to_string(),
+        }
+    }
+}
+
+/// Projected reward for a voter on an active query
+/// 
+/// This is an ESTIMATE based on the votes cast so far. It changes as further
+/// votes arrive and is only paid out if the assumed result actually wins.
+#[derive(SimpleObject, Clone)]
+pub struct RewardEstimate {
+    /// Query ID
+    pub query_id: u64,
+    
+    /// Result the estimate assumes (the voter's own vote, or the current leader)
+    pub assumed_result: Resolution,
+    
+    /// Estimated reward if the assumed result wins
+    pub estimated_reward: String,
+    
+    /// Always true - the value is a projection, not a guarantee
+    pub is_estimate: bool,
+}
+
 /// GraphQL representation of a Vote on a Query
 #[derive(SimpleObject, Clone)]
 pub struct QueryVote {
@@ -139,1392 +356,3570 @@ pub struct QueryVote {
     
     /// Optional confidence score (0-100)
     pub confidence: Option<u8>,
+    
+    /// Voter's current stake backing this vote (in tokens)
+    pub stake_at_vote: String,
+    
+    /// Voter's current reputation score (0-100)
+    pub reputation_at_vote: u32,
+    
+    /// Weight of this vote under the query's decision strategy
+    pub effective_weight: f64,
 }
 
-/// GraphQL representation of protocol-wide Statistics
+/// GraphQL representation of an archived query summary
 #[derive(SimpleObject, Clone)]
-pub struct Statistics {
-    /// Total number of registered voters
-    pub total_voters: u64,
+pub struct ArchivedQuerySummary {
+    /// Query ID
+    pub id: u64,
     
-    /// Number of active voters
-    pub active_voters: u64,
+    /// Query description
+    pub description: String,
     
-    /// Total stake across all voters (in tokens)
-    pub total_stake: String,
+    /// Possible outcomes
+    pub outcomes: Vec<String>,
     
-    /// Total locked stake (in tokens)
-    pub total_locked_stake: String,
+    /// Decision strategy
+    pub strategy: String,
     
-    /// Average stake per voter (in tokens)
-    pub average_stake: String,
+    /// Reward amount (in tokens)
+    pub reward_amount: String,
     
-    /// Total number of queries created
-    pub total_queries_created: u64,
+    /// Query creator address (as hex string)
+    pub creator: String,
     
-    /// Total number of queries resolved
-    pub total_queries_resolved: u64,
+    /// Creation timestamp (microseconds as string)
+    pub created_at: String,
     
-    /// Number of currently active queries
-    pub active_queries_count: u64,
+    /// Resolution timestamp (microseconds as string)
+    pub resolved_at: Option<String>,
     
-    /// Total number of votes submitted across all queries
-    pub total_votes_submitted: u64,
+    /// Final status (Resolved, Expired or NoConsensus)
+    pub status: String,
     
-    /// Average votes per query
-    pub average_votes_per_query: f64,
+    /// Human-readable result
+    pub result: Option<String>,
     
-    /// Total rewards distributed (in tokens)
-    pub total_rewards_distributed: String,
+    /// Typed resolution result
+    pub resolution: Option<Resolution>,
     
-    /// Current reward pool balance (in tokens)
-    pub reward_pool_balance: String,
+    /// Number of revealed votes at archive time
+    pub vote_count: u32,
+}
+
+impl ArchivedQuerySummary {
+    /// Convert from state ArchivedQuery to GraphQL summary
+    fn from_archived(archived: state::ArchivedQuery) -> Self {
+        Self {
+            id: archived.id,
+            description: archived.description,
+            outcomes: archived.outcomes,
+            strategy: archived.strategy.as_str().to_string(),
+            reward_amount: archived.reward_amount.to_string(),
+            creator: format!("{:?}", archived.creator),
+            created_at: archived.created_at.micros().to_string(),
+            resolved_at: archived.resolved_at.map(|ts| ts.micros().to_string()),
+            status: format!("{:?}", archived.status),
+            resolution: archived.result.as_ref().map(Resolution::from_result),
+            result: archived.result_display,
+            vote_count: archived.vote_count as u32,
+        }
+    }
+}
+
+/// GraphQL representation of a failed cross-chain message
+#[derive(SimpleObject, Clone)]
+pub struct FailedMessageInfo {
+    /// Sequence number in the failed message log
+    pub id: u64,
     
-    /// Protocol treasury balance (in tokens)
-    pub protocol_treasury: String,
+    /// Message variant name (e.g. "SubmitVote")
+    pub kind: String,
     
-    /// Average voter reputation score
-    pub average_reputation: f64,
+    /// Sending chain (as hex string), if known
+    pub sender: Option<String>,
     
-    /// Protocol status (Active or Paused)
-    pub protocol_status: String,
+    /// Error returned by the handler
+    pub error: String,
     
-    /// Query resolution rate (resolved / total created)
-    pub resolution_rate: f64,
+    /// Failure timestamp (microseconds as string)
+    pub failed_at: String,
 }
 
-pub struct OracleRegistryV2Service {
-    state: Arc<OracleRegistryV2>,
-    runtime: Arc<ServiceRuntime<Self>>,
+impl FailedMessageInfo {
+    /// Convert from state FailedMessage to GraphQL info
+    fn from_failed(failed: state::FailedMessage) -> Self {
+        Self {
+            id: failed.id,
+            kind: failed.kind,
+            sender: failed.sender.map(|chain| format!("{:?}", chain)),
+            error: failed.error,
+            failed_at: failed.failed_at.micros().to_string(),
+        }
+    }
 }
 
-linera_sdk::service!(OracleRegistryV2Service);
+/// GraphQL representation of an activity feed entry
+#[derive(SimpleObject, Clone)]
+pub struct ActivityInfo {
+    /// Sequence number in the activity log
+    pub id: u64,
+    
+    /// VoterRegistered, QueryCreated, VoteCommitted, VoteCast or QueryResolved
+    pub kind: String,
+    
+    /// When the activity happened (microseconds as string)
+    pub timestamp: String,
+    
+    /// Acting chain (as hex string), if any
+    pub chain_id: Option<String>,
+    
+    /// Query the activity concerns, if any
+    pub query_id: Option<u64>,
+    
+    /// Vote value or resolved result, where relevant
+    pub detail: Option<String>,
+}
 
-impl WithServiceAbi for OracleRegistryV2Service {
-    type Abi = oracle_registry_v2::OracleRegistryV2Abi;
+impl ActivityInfo {
+    /// Convert from state ActivityEntry to GraphQL info
+    fn from_entry(entry: state::ActivityEntry) -> Self {
+        Self {
+            id: entry.id,
+            kind: entry.kind.as_str().to_string(),
+            timestamp: entry.timestamp.micros().to_string(),
+            chain_id: entry.chain_id.map(|chain| format!("{:?}", chain)),
+            query_id: entry.query_id,
+            detail: entry.detail,
+        }
+    }
 }
 
-impl Service for OracleRegistryV2Service {
-    type Parameters = ();
+/// GraphQL representation of a parameter history entry
+#[derive(SimpleObject, Clone)]
+pub struct ParameterChangeInfo {
+    /// Sequence number in the parameter history
+    pub id: u64,
+    
+    /// When the parameters took effect (microseconds as string)
+    pub changed_at: String,
+    
+    /// Chain whose change set them (as hex string)
+    pub changed_by: String,
+    
+    /// Parameters in effect from `changed_at` (same format as `parameters`)
+    pub params: String,
+}
 
-    async fn new(runtime: ServiceRuntime<Self>) -> Self {
-        let state = OracleRegistryV2::load(runtime.root_view_storage_context())
-            .await
-            .expect("Failed to load state");
-        OracleRegistryV2Service { 
-            state: Arc::new(state),
-            runtime: Arc::new(runtime),
+impl ParameterChangeInfo {
+    /// Convert from state ParameterChange to GraphQL info
+    fn from_change(change: state::ParameterChange) -> Self {
+        Self {
+            id: change.id,
+            changed_at: change.changed_at.micros().to_string(),
+            changed_by: format!("{:?}", change.changed_by),
+            params: format!("{:?}", change.params),
         }
     }
+}
 
-    async fn handle_query(&self, request: Request) -> Response {
-        let schema = Schema::build(
-            QueryRoot { state: self.state.clone() },
-            MutationRoot {
-                runtime: self.runtime.clone(),
-            },
-            EmptySubscription,
-        )
-        .finish();
-        schema.execute(request).await
-    }
+/// A voter's key metrics, one row of a voter comparison
+#[derive(SimpleObject, Clone)]
+pub struct VoterComparisonInfo {
+    /// Voter's chain ID (as hex string)
+    pub address: String,
+    
+    /// Reputation score (0-100)
+    pub reputation: u32,
+    
+    /// Voting accuracy percentage
+    pub accuracy_percentage: f64,
+    
+    /// Staked amount (in tokens)
+    pub stake: String,
+    
+    /// Selection power, stake (attos) × reputation, as string
+    pub power: String,
+    
+    /// Total number of votes submitted
+    pub total_votes: u64,
 }
 
-impl Voter {
-    /// Convert from state VoterInfo to GraphQL Voter
-    fn from_voter_info(
-        info: state::VoterInfo,
-        available_stake: linera_sdk::linera_base_types::Amount,
-        pending_rewards: linera_sdk::linera_base_types::Amount,
-        state: &OracleRegistryV2,
-    ) -> Self {
-        let accuracy_percentage = if info.total_votes > 0 {
-            (info.correct_votes as f64 / info.total_votes as f64) * 100.0
-        } else {
-            0.0
-        };
-        
-        let reputation_tier = state.get_reputation_tier(info.reputation).to_string();
-        let reputation_weight = state.calculate_reputation_weight(info.reputation);
-        
-        // Convert timestamp to ISO 8601 string
-        let registered_at = format!("{:?}", info.registered_at);
-        
+impl VoterComparisonInfo {
+    /// Convert from state VoterComparison to GraphQL info
+    fn from_comparison(comparison: state::VoterComparison) -> Self {
         Self {
-            address: format!("{:?}", info.chain_id),
-            stake: info.stake.to_string(),
-            locked_stake: info.locked_stake.to_string(),
-            available_stake: available_stake.to_string(),
-            pending_rewards: pending_rewards.to_string(),
-            reputation: info.reputation,
-            reputation_tier,
-            reputation_weight,
-            total_votes: info.total_votes,
-            correct_votes: info.correct_votes,
-            accuracy_percentage,
-            registered_at,
-            is_active: info.is_active,
-            name: info.name,
-            metadata_url: info.metadata_url,
+            address: format!("{:?}", comparison.chain_id),
+            reputation: comparison.reputation,
+            accuracy_percentage: comparison.accuracy_percentage,
+            stake: comparison.stake.to_string(),
+            power: comparison.power.to_string(),
+            total_votes: comparison.total_votes,
         }
     }
 }
 
-impl Query {
-    /// Convert from state Query to GraphQL Query
-    fn from_state_query(
-        query: state::Query,
-        vote_count: usize,
+/// A voter's progress on one query, for per-query action UIs
+#[derive(SimpleObject, Clone)]
+pub struct VoterQueryStatusInfo {
+    /// Whether the voter is on the query's selected panel
+    pub selected: bool,
+    
+    /// Whether the voter submitted a commit hash
+    pub committed: bool,
+    
+    /// Whether the voter revealed their commit
+    pub revealed: bool,
+    
+    /// Whether the voter has a vote on the query (direct or revealed)
+    pub voted: bool,
+    
+    /// Voted value (null until voted or revealed)
+    pub vote_value: Option<String>,
+    
+    /// Confidence given with the vote (0-100)
+    pub confidence: Option<u8>,
+}
+
+impl VoterQueryStatusInfo {
+    /// Convert from state VoterQueryStatus to GraphQL info
+    fn from_status(status: state::VoterQueryStatus) -> Self {
+        Self {
+            selected: status.selected,
+            committed: status.committed,
+            revealed: status.revealed,
+            voted: status.voted,
+            vote_value: status.vote_value,
+            confidence: status.confidence,
+        }
+    }
+}
+
+/// What voting on a query would lock from a voter's stake
+#[derive(SimpleObject, Clone)]
+pub struct LockPreviewInfo {
+    /// Query's decision strategy, which selects the lock rule
+    pub strategy: String,
+    
+    /// Share of the available stake locked (basis points)
+    pub lock_bps: u32,
+    
+    /// Amount locked instead when the share rounds down to zero (in tokens)
+    pub min_lock: String,
+    
+    /// Voter's stake not locked by other votes (in tokens)
+    pub available_stake: String,
+    
+    /// Stake a vote would lock (in tokens)
+    pub stake_to_lock: String,
+}
+
+impl LockPreviewInfo {
+    /// Convert from state LockPreview to GraphQL info
+    fn from_preview(preview: state::LockPreview) -> Self {
+        Self {
+            strategy: preview.strategy.as_str().to_string(),
+            lock_bps: preview.rule.lock_bps,
+            min_lock: preview.rule.min_lock.to_string(),
+            available_stake: preview.available_stake.to_string(),
+            stake_to_lock: preview.stake_to_lock.to_string(),
+        }
+    }
+}
+
+/// The protocol parameters that change how votes are tallied, for
+/// re-running a resolution off chain
+#[derive(SimpleObject, Clone)]
+pub struct ResolutionParametersInfo {
+    /// Weight of a reveal after the reveal phase (basis points)
+    pub late_reveal_weight_bps: u32,
+    
+    /// Share of the tally an outcome needs to win a multi-winner query (basis points)
+    pub multi_winner_threshold_bps: u32,
+    
+    /// Cap on the stake counted per vote (in tokens), if any
+    pub max_effective_stake: Option<String>,
+}
+
+impl ResolutionParametersInfo {
+    /// Convert from state ProtocolParameters to GraphQL info
+    fn from_parameters(params: &state::ProtocolParameters) -> Self {
+        Self {
+            late_reveal_weight_bps: params.late_reveal_weight_bps,
+            multi_winner_threshold_bps: params.multi_winner_threshold_bps,
+            max_effective_stake: params.max_effective_stake.map(|cap| cap.to_string()),
+        }
+    }
+}
+
+/// GraphQL representation of a voter's stake at one point in time
+#[derive(SimpleObject, Clone)]
+pub struct StakeSnapshotInfo {
+    /// When the stake changed (microseconds as string)
+    pub timestamp: String,
+    
+    /// Total stake after the change (in tokens)
+    pub stake: String,
+    
+    /// Stake locked in active queries at the time (in tokens)
+    pub locked_stake: String,
+}
+
+impl StakeSnapshotInfo {
+    /// Convert from state StakeSnapshot to GraphQL info
+    fn from_snapshot(snapshot: state::StakeSnapshot) -> Self {
+        Self {
+            timestamp: snapshot.timestamp.micros().to_string(),
+            stake: snapshot.stake.to_string(),
+            locked_stake: snapshot.locked_stake.to_string(),
+        }
+    }
+}
+
+/// GraphQL representation of a reward or slash history entry
+#[derive(SimpleObject, Clone)]
+pub struct FlowEntryInfo {
+    /// Sequence number in the history log
+    pub id: u64,
+    
+    /// Resolution timestamp (microseconds as string)
+    pub timestamp: String,
+    
+    /// Query whose resolution produced the flow
+    pub query_id: u64,
+    
+    /// Total tokens rewarded or slashed
+    pub amount: String,
+}
+
+impl FlowEntryInfo {
+    /// Convert from state FlowEntry to GraphQL info
+    fn from_entry(entry: state::FlowEntry) -> Self {
+        Self {
+            id: entry.id,
+            timestamp: entry.timestamp.micros().to_string(),
+            query_id: entry.query_id,
+            amount: entry.amount.to_string(),
+        }
+    }
+}
+
+/// Validate `limit` (1..=1000, default 100) and `offset` (default 0) of a paginated list
+fn parse_pagination(limit: Option<i32>, offset: Option<i32>) -> Result<(usize, usize), String> {
+    let limit = match limit {
+        Some(l) => {
+            if l < 1 {
+                return Err("Limit must be at least 1".to_string());
+            }
+            if l > 1000 {
+                return Err("Limit cannot exceed 1000".to_string());
+            }
+            l as usize
+        }
+        None => 100, // Default limit
+    };
+    
+    let offset = match offset {
+        Some(o) => {
+            if o < 0 {
+                return Err("Offset cannot be negative".to_string());
+            }
+            o as usize
+        }
+        None => 0, // Default offset
+    };
+    
+    Ok((limit, offset))
+}
+
+/// Parse an optional microsecond timestamp bound of a history window
+fn parse_history_bound(
+    bound: Option<String>,
+    name: &str,
+    default: u64,
+) -> Result<linera_sdk::linera_base_types::Timestamp, String> {
+    let micros = match bound {
+        Some(value) => value.parse::<u64>()
+            .map_err(|_| format!("Invalid {} timestamp: expected microseconds", name))?,
+        None => default,
+    };
+    Ok(linera_sdk::linera_base_types::Timestamp::from(micros))
+}
+
+/// Decode a hex encrypted metadata blob and check its size
+fn parse_encrypted_metadata(hex: &str) -> Result<Vec<u8>, String> {
+    let blob = state::hex_to_bytes(hex).map_err(|e| format!("Invalid encrypted metadata: {}", e))?;
+    state::validate_encrypted_metadata(&blob)?;
+    Ok(blob)
+}
+
+/// Page through a flow history within `[from, to]` (microseconds, inclusive)
+fn flow_history_window(
+    entries: Vec<state::FlowEntry>,
+    from: Option<String>,
+    to: Option<String>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> Result<Vec<FlowEntryInfo>, String> {
+    let from = parse_history_bound(from, "from", 0)?;
+    let to = parse_history_bound(to, "to", u64::MAX)?;
+    if from > to {
+        return Err("Invalid history window: 'from' is after 'to'".to_string());
+    }
+    
+    let offset = offset.unwrap_or(0) as usize;
+    let limit = limit.map_or(state::MAX_FLOW_HISTORY_PAGE, |l| l as usize);
+    Ok(state::flow_history_page(entries, from, to, offset, limit)
+        .into_iter()
+        .map(FlowEntryInfo::from_entry)
+        .collect())
+}
+
+/// Active queries a keeper can act on right now
+#[derive(SimpleObject, Clone)]
+pub struct ActionableQueriesInfo {
+    /// Query IDs ready for ResolveQuery
+    pub resolvable: Vec<u64>,
+    
+    /// Query IDs ready for ExpireQuery (deadline passed, not enough votes)
+    pub expirable: Vec<u64>,
+}
+
+/// Progress of unlocking a finalized query's stakes
+#[derive(SimpleObject, Clone)]
+pub struct StakeUnlockProgress {
+    /// Voters whose stake has been unlocked
+    pub unlocked: u32,
+    
+    /// Voters still waiting for their stake to be unlocked
+    pub remaining: u32,
+}
+
+/// GraphQL representation of a voter on a query's selected panel
+#[derive(SimpleObject, Clone)]
+pub struct PanelVoter {
+    /// Voter chain ID (as hex string)
+    pub address: String,
+    
+    /// Whether the voter is still registered
+    pub is_registered: bool,
+    
+    /// Current stake (0 if deregistered)
+    pub stake: String,
+    
+    /// Current reputation score (0 if deregistered)
+    pub reputation: u32,
+    
+    /// Selection power, stake (attos) × reputation, as string
+    pub power: String,
+    
+    /// Whether the voter committed or voted on the query
+    pub participated: bool,
+}
+
+impl PanelVoter {
+    /// Convert from a state panel member to GraphQL type
+    fn from_member(member: state::PanelMember) -> Self {
+        Self {
+            address: format!("{:?}", member.voter),
+            is_registered: member.info.is_some(),
+            stake: member.info.as_ref().map_or(Amount::ZERO, |info| info.stake).to_string(),
+            reputation: member.info.as_ref().map_or(0, |info| info.reputation),
+            power: member.power.to_string(),
+            participated: member.participated,
+        }
+    }
+}
+
+/// GraphQL representation of protocol-wide Statistics
+#[derive(SimpleObject, Clone)]
+pub struct Statistics {
+    /// Total number of registered voters
+    pub total_voters: u64,
+    
+    /// Number of active voters
+    pub active_voters: u64,
+    
+    /// Total stake across all voters (in tokens)
+    pub total_stake: String,
+    
+    /// Total locked stake (in tokens)
+    pub total_locked_stake: String,
+    
+    /// Average stake per voter (in tokens)
+    pub average_stake: String,
+    
+    /// Total number of queries created
+    pub total_queries_created: u64,
+    
+    /// Total number of queries resolved
+    pub total_queries_resolved: u64,
+    
+    /// Total number of queries closed without consensus
+    pub total_queries_no_consensus: u64,
+    
+    /// Number of currently active queries
+    pub active_queries_count: u64,
+    
+    /// Total number of votes submitted across all queries
+    pub total_votes_submitted: u64,
+    
+    /// Average votes per query
+    pub average_votes_per_query: f64,
+    
+    /// Total rewards distributed (in tokens)
+    pub total_rewards_distributed: String,
+    
+    /// Current reward pool balance (in tokens)
+    pub reward_pool_balance: String,
+    
+    /// Protocol treasury balance (in tokens)
+    pub protocol_treasury: String,
+    
+    /// Average voter reputation score
+    pub average_reputation: f64,
+    
+    /// Protocol status (Active or Paused)
+    pub protocol_status: String,
+    
+    /// Query resolution rate (resolved / total created)
+    pub resolution_rate: f64,
+}
+
+/// Registry obligations against the balances backing them (amounts in tokens)
+#[derive(SimpleObject, Clone)]
+pub struct SolvencyInfo {
+    pub total_staked: String,
+    
+    /// Part of `total_staked` locked on open votes
+    pub total_locked: String,
+    
+    pub total_pending_rewards: String,
+    pub reward_pool: String,
+    pub treasury: String,
+    
+    /// Tokens received from the token chain and not yet returned
+    pub tokens_held: String,
+    
+    /// Stakes plus pending rewards exceed tokens held plus the reward pool by this much
+    pub shortfall: String,
+    
+    /// Stakes plus pending rewards are covered by tokens held plus the reward pool
+    pub is_solvent: bool,
+}
+
+/// Active voters whose selection power is in `min_power..=max_power` (powers as strings)
+#[derive(SimpleObject, Clone)]
+pub struct PowerBucketInfo {
+    pub min_power: String,
+    pub max_power: String,
+    pub voter_count: u64,
+    pub total_power: String,
+}
+
+/// How often one voter has been selected for query panels
+#[derive(SimpleObject, Clone)]
+pub struct VoterSelectionStats {
+    pub voter_address: String,
+    pub selected_count: u64,
+    
+    /// Selected panels whose query has since resolved or expired
+    pub closed_count: u64,
+    
+    /// Closed panels where the voter committed or voted
+    pub participated_count: u64,
+    
+    /// `participated_count / closed_count` in basis points (null before any panel closes)
+    pub participation_rate_bps: Option<u32>,
+}
+
+/// Selection counts across voters
+#[derive(SimpleObject, Clone)]
+pub struct SelectionStatsInfo {
+    /// Most selected first
+    pub voters: Vec<VoterSelectionStats>,
+    
+    /// Panel seats filled across all queries
+    pub total_selections: u64,
+    
+    /// Gini coefficient of selection counts (basis points)
+    pub concentration_bps: u32,
+}
+
+/// A proposed parameter change waiting out the timelock
+#[derive(SimpleObject, Clone)]
+pub struct PendingParametersInfo {
+    /// Proposed parameters (same format as `parameters`)
+    pub params: String,
+    
+    pub proposed_by: String,
+    
+    /// Proposal time (microseconds as string)
+    pub proposed_at: String,
+    
+    /// Earliest time the change may be applied (microseconds as string)
+    pub effective_at: String,
+    
+    /// The timelock has elapsed and anyone may apply the change
+    pub is_ready: bool,
+}
+
+pub struct OracleRegistryV2Service {
+    state: Arc<OracleRegistryV2>,
+    runtime: Arc<ServiceRuntime<Self>>,
+}
+
+linera_sdk::service!(OracleRegistryV2Service);
+
+impl WithServiceAbi for OracleRegistryV2Service {
+    type Abi = oracle_registry_v2::OracleRegistryV2Abi;
+}
+
+impl Service for OracleRegistryV2Service {
+    type Parameters = ();
+
+    async fn new(runtime: ServiceRuntime<Self>) -> Self {
+        let state = OracleRegistryV2::load(runtime.root_view_storage_context())
+            .await
+            .expect("Failed to load state");
+        OracleRegistryV2Service { 
+            state: Arc::new(state),
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    async fn handle_query(&self, request: Request) -> Response {
+        let schema = Schema::build(
+            QueryRoot {
+                state: self.state.clone(),
+                current_time: self.runtime.system_time(),
+                block_height: self.runtime.next_block_height().0,
+            },
+            MutationRoot {
+                runtime: self.runtime.clone(),
+            },
+            EmptySubscription,
+        )
+        .finish();
+        schema.execute(request).await
+    }
+}
+
+impl Voter {
+    /// Convert from state VoterInfo to GraphQL Voter
+    fn from_voter_info(
+        info: state::VoterInfo,
+        available_stake: linera_sdk::linera_base_types::Amount,
+        pending_rewards: linera_sdk::linera_base_types::Amount,
+        state: &OracleRegistryV2,
         current_time: linera_sdk::linera_base_types::Timestamp,
     ) -> Self {
-        // Convert strategy enum to string
-        let strategy = match query.strategy {
-            state::DecisionStrategy::Majority => "Majority",
-            state::DecisionStrategy::Median => "Median",
-            state::DecisionStrategy::WeightedByStake => "WeightedByStake",
-            state::DecisionStrategy::WeightedByReputation => "WeightedByReputation",
-        }.to_string();
+        let accuracy_percentage = if info.total_votes > 0 {
+            (info.correct_votes as f64 / info.total_votes as f64) * 100.0
+        } else {
+            0.0
+        };
         
-        // Convert status enum to string
-        let status = match query.status {
-            state::QueryStatus::Active => "Active",
-            state::QueryStatus::Resolved => "Resolved",
-            state::QueryStatus::Expired => "Expired",
-            state::QueryStatus::Cancelled => "Cancelled",
-        }.to_string();
+        let reputation_tier = state.get_reputation_tier(info.reputation).to_string();
+        let reputation_weight = state.calculate_reputation_weight(info.reputation);
+        
+        // Convert timestamp to ISO 8601 string
+        let registered_at = format!("{:?}", info.registered_at);
+        let last_active_at = format!("{:?}", info.last_active_at);
+        let is_dormant = info.is_dormant(current_time, state::DEFAULT_DORMANCY_DAYS);
+        let claim_blocked_reason = info.claim_blocked_reason(*state.is_paused.get(), pending_rewards);
+        
+        Self {
+            address: format!("{:?}", info.chain_id),
+            stake: info.stake.to_string(),
+            locked_stake: info.locked_stake.to_string(),
+            available_stake: available_stake.to_string(),
+            pending_rewards: pending_rewards.to_string(),
+            reputation: info.reputation,
+            reputation_tier,
+            reputation_weight,
+            total_votes: info.total_votes,
+            correct_votes: info.correct_votes,
+            accuracy_percentage,
+            registered_at,
+            last_active_at,
+            is_dormant,
+            can_claim: claim_blocked_reason.is_none(),
+            claim_blocked_reason,
+            is_active: info.is_active,
+            name: info.name,
+            metadata_url: info.metadata_url,
+            referral: info.referral,
+            calibration_score: info.calibration_score,
+            calibrated_votes: info.calibrated_votes,
+            encrypted_metadata: info.encrypted_metadata.as_deref().map(state::bytes_to_hex),
+        }
+    }
+}
+
+impl Query {
+    /// Convert from state Query to GraphQL Query
+    fn from_state_query(
+        query: state::Query,
+        vote_count: usize,
+        current_time: linera_sdk::linera_base_types::Timestamp,
+    ) -> Self {
+        // Convert strategy enum to string
+        let strategy = match query.strategy {
+            state::DecisionStrategy::Majority => "Majority",
+            state::DecisionStrategy::Median => "Median",
+            state::DecisionStrategy::WeightedByStake => "WeightedByStake",
+            state::DecisionStrategy::WeightedByReputation => "WeightedByReputation",
+            state::DecisionStrategy::ConfidenceWeightedMedian => "ConfidenceWeightedMedian",
+        }.to_string();
+        
+        // Convert status enum to string
+        let status = match query.status {
+            state::QueryStatus::Active => "Active",
+            state::QueryStatus::Resolved => "Resolved",
+            state::QueryStatus::Expired => "Expired",
+            state::QueryStatus::Cancelled => "Cancelled",
+            state::QueryStatus::NoConsensus => "NoConsensus",
+        }.to_string();
+        
+        // Convert phase enum to string
+        let phase = match query.phase {
+            state::VotingPhase::Commit => "Commit",
+            state::VotingPhase::Reveal => "Reveal",
+            state::VotingPhase::Completed => "Completed",
+        }.to_string();
+        
+        // Convert timestamps to microseconds strings (for JavaScript compatibility)
+        let created_at = query.created_at.micros().to_string();
+        let deadline = query.deadline.micros().to_string();
+        let commit_end = query.commit_phase_end.micros().to_string();
+        let reveal_end = query.reveal_phase_end.micros().to_string();
+        let resolved_at = query.resolved_at.map(|ts| ts.micros().to_string());
+        
+        // Calculate time remaining until deadline
+        let time_remaining = if current_time < query.deadline {
+            let delta = query.deadline.delta_since(current_time);
+            (delta.as_micros() / 1_000_000) as i64 // Convert microseconds to seconds
+        } else {
+            0
+        };
+        
+        // Count commits (phase 1) and revealed votes (phase 2)
+        let commit_count = query.commits.len();
+        
+        let resolution = query.result.as_ref().map(Resolution::from_result);
+        
+        Self {
+            id: query.id,
+            description: query.description,
+            outcomes: query.outcomes,
+            outcome_aliases: query.outcome_aliases.iter()
+                .map(|(alias, outcome)| format!("{}={}", alias, outcome))
+                .collect(),
+            strategy,
+            reward_basis: query.reward_basis.map(|basis| basis.as_str().to_string()),
+            min_votes: query.min_votes as u32,
+            min_distinct_voted_outcomes: query.min_distinct_voted_outcomes.map(|n| n as u32),
+            min_votes_for_winner: query.min_votes_for_winner.map(|n| n as u32),
+            min_distinct_voters: query.min_distinct_voters.map(|n| n as u32),
+            max_group_power_bps: query.max_group_power_bps,
+            min_voter_stake: query.min_voter_stake.map(|stake| stake.to_string()),
+            allow_abstain: query.allow_abstain,
+            multi_winner: query.multi_winner,
+            allow_external_resolution: query.allow_external_resolution,
+            tie_break: query.tie_break.as_str().to_string(),
+            category: query.category,
+            tags: query.tags,
+            reward_amount: query.reward_amount.to_string(),
+            creator: format!("{:?}", query.creator),
+            created_at,
+            deadline,
+            commit_end,
+            reveal_end,
+            status,
+            phase,
+            result: query.result_display.clone(),
+            result_display: query.result_display,
+            resolution,
+            resolved_at,
+            resolution_source: query.resolution_source,
+            commit_count: commit_count as u32,
+            vote_count: vote_count as u32,
+            time_remaining,
+            votes: None, // Votes are populated separately when needed
+        }
+    }
+    
+    /// Convert from state Query to GraphQL Query with votes included
+    fn from_state_query_with_votes(
+        query: state::Query,
+        voter_infos: &std::collections::BTreeMap<linera_sdk::linera_base_types::ChainId, state::VoterInfo>,
+        current_time: linera_sdk::linera_base_types::Timestamp,
+    ) -> Self {
+        let vote_count = query.votes.len();
+        
+        // Convert votes to GraphQL format, with the weight each carries
+        let votes: Vec<QueryVote> = query.votes.iter().map(|(voter, vote)| {
+            let voter_info = voter_infos.get(voter);
+            QueryVote {
+                voter: format!("{:?}", voter),
+                value: vote.value.clone(),
+                timestamp: vote.timestamp.micros().to_string(),
+                confidence: vote.confidence,
+                stake_at_vote: voter_info.map_or(Amount::ZERO, |info| info.stake).to_string(),
+                reputation_at_vote: voter_info.map_or(50, |info| info.reputation),
+                effective_weight: state::vote_weight(&query.strategy, vote, voter_info),
+            }
+        }).collect();
+        
+        let mut result = Self::from_state_query(query, vote_count, current_time);
+        result.votes = Some(votes);
+        result
+    }
+}
+
+impl Statistics {
+    /// Build Statistics from the current state, scanning every voter
+    async fn from_state(state: &OracleRegistryV2) -> Result<Self, String> {
+        let aggregates = state.compute_voter_aggregates().await?;
+        Ok(Self::from_aggregates(state, &aggregates).await)
+    }
+    
+    /// Build Statistics from the voter aggregates cached by the contract
+    async fn from_cache(state: &OracleRegistryV2) -> Self {
+        Self::from_aggregates(state, state.voter_aggregates.get()).await
+    }
+    
+    /// Build Statistics from the state's counters and the given voter aggregates
+    async fn from_aggregates(state: &OracleRegistryV2, aggregates: &state::VoterAggregates) -> Self {
+        // Get basic counts
+        let total_voters = *state.voter_count.get();
+        let total_stake = *state.total_stake.get();
+        let total_queries_created = *state.total_queries_created.get();
+        let total_queries_resolved = *state.total_queries_resolved.get();
+        let total_queries_no_consensus = *state.total_queries_no_consensus.get();
+        let total_votes_submitted = *state.total_votes_submitted.get();
+        let total_rewards_distributed = *state.total_rewards_distributed.get();
+        let reward_pool_balance = *state.reward_pool.get();
+        let protocol_treasury = *state.protocol_treasury.get();
+        let is_paused = *state.is_paused.get();
+        
+        // Calculate active queries count
+        let active_queries = state.get_active_queries().await;
+        let active_queries_count = active_queries.len() as u64;
+        
+        // Calculate average stake per voter
+        let average_stake = if total_voters > 0 {
+            let stake_value: u128 = total_stake.into();
+            linera_sdk::linera_base_types::Amount::from_tokens(stake_value / total_voters as u128)
+        } else {
+            linera_sdk::linera_base_types::Amount::ZERO
+        };
+        
+        // Calculate average votes per query
+        let average_votes_per_query = if total_queries_created > 0 {
+            total_votes_submitted as f64 / total_queries_created as f64
+        } else {
+            0.0
+        };
+        
+        // Calculate resolution rate
+        let resolution_rate = if total_queries_created > 0 {
+            (total_queries_resolved as f64 / total_queries_created as f64) * 100.0
+        } else {
+            0.0
+        };
+        
+        // Voter-derived totals
+        let active_voters = aggregates.active_voters;
+        let average_reputation = aggregates.average_reputation();
+        let total_locked_stake = aggregates.total_locked_stake;
+        
+        let protocol_status = if is_paused {
+            "Paused".to_string()
+        } else {
+            "Active".to_string()
+        };
+        
+        Self {
+            total_voters,
+            active_voters,
+            total_stake: total_stake.to_string(),
+            total_locked_stake: total_locked_stake.to_string(),
+            average_stake: average_stake.to_string(),
+            total_queries_created,
+            total_queries_resolved,
+            total_queries_no_consensus,
+            active_queries_count,
+            total_votes_submitted,
+            average_votes_per_query,
+            total_rewards_distributed: total_rewards_distributed.to_string(),
+            reward_pool_balance: reward_pool_balance.to_string(),
+            protocol_treasury: protocol_treasury.to_string(),
+            average_reputation,
+            protocol_status,
+            resolution_rate,
+        }
+    }
+}
+
+struct QueryRoot {
+    state: Arc<OracleRegistryV2>,
+    current_time: linera_sdk::linera_base_types::Timestamp,
+    block_height: u64,
+}
+
+#[Object]
+impl QueryRoot {
+    /// Current chain time in microseconds since the Unix epoch
+    /// 
+    /// Lets front ends anchor countdowns to chain time instead of the
+    /// client clock.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   now
+    ///   nowIso
+    /// }
+    /// ```
+    async fn now(&self) -> String {
+        self.current_time.micros().to_string()
+    }
+    
+    /// Current chain time as ISO 8601 UTC (e.g. "2023-11-14T22:13:20.123456Z")
+    async fn now_iso(&self) -> String {
+        state::timestamp_to_iso8601(self.current_time)
+    }
+    
+    /// Hash algorithm vote commitments must use ("Sha256" or "Keccak256")
+    async fn commit_hash_algo(&self) -> String {
+        self.state.get_parameters().await.commit_hash_algo.as_str().to_string()
+    }
+    
+    /// Compute the commit hash for a vote, as checked on reveal
+    /// 
+    /// The hash covers `value` bytes followed by `salt` bytes, hex-encoded.
+    /// `algo` defaults to the registry's configured algorithm. Clients should
+    /// compute this locally rather than sending their salt to a node.
+    async fn compute_commit_hash(
+        &self,
+        value: String,
+        salt: String,
+        algo: Option<String>,
+    ) -> Result<String, String> {
+        let algo = match algo {
+            Some(name) => state::CommitHashAlgo::parse(&name)?,
+            None => self.state.get_parameters().await.commit_hash_algo,
+        };
+        Ok(algo.compute(&value, &salt))
+    }
+    
+    /// Check that a value and salt reproduce a voter's commit, without revealing
+    /// 
+    /// Returns whether the hash of `value` and `salt` (with the registry's
+    /// configured algorithm) matches the commit stored for `address`; errors
+    /// if that voter has not committed on the query. Read-only, but the salt
+    /// is sent to the node, so only query a node you trust.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   verifyCommit(queryId: 1, address: "e476...", value: "Yes", salt: "my-secret-salt")
+    /// }
+    /// ```
+    async fn verify_commit(
+        &self,
+        query_id: u64,
+        address: String,
+        value: String,
+        salt: String,
+    ) -> Result<bool, String> {
+        let chain_id = state::parse_chain_id(&address)
+            .map_err(|e| format!("Invalid chain ID: {}", e))?;
+        let query = self.state.get_query(query_id).await
+            .ok_or_else(|| format!("Query {} not found", query_id))?;
+        
+        let algo = self.state.get_parameters().await.commit_hash_algo;
+        query.verify_commit(&chain_id, &value, &salt, algo)
+    }
+    
+    /// Get a voter's state on one query without fetching every vote
+    /// 
+    /// All flags are false for voters that are not selected and have not
+    /// taken part; errors if the query does not exist.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   voterQueryStatus(queryId: 1, address: "e476...") {
+    ///     selected committed revealed voted voteValue confidence
+    ///   }
+    /// }
+    /// ```
+    async fn voter_query_status(&self, query_id: u64, address: String) -> Result<VoterQueryStatusInfo, String> {
+        let chain_id = state::parse_chain_id(&address)
+            .map_err(|e| format!("Invalid chain ID: {}", e))?;
+        let query = self.state.get_query(query_id).await
+            .ok_or_else(|| format!("Query {} not found", query_id))?;
+        
+        Ok(VoterQueryStatusInfo::from_status(query.voter_status(&chain_id)))
+    }
+    
+    /// Preview the stake a vote by a voter on a query would lock
+    /// 
+    /// Uses the stake lock rule of the query's strategy and the voter's
+    /// current available stake; errors if the query or voter does not exist.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   lockPreview(queryId: 1, address: "e476...") {
+    ///     strategy lockBps minLock availableStake stakeToLock
+    ///   }
+    /// }
+    /// ```
+    async fn lock_preview(&self, query_id: u64, address: String) -> Result<LockPreviewInfo, String> {
+        let chain_id = state::parse_chain_id(&address)
+            .map_err(|e| format!("Invalid chain ID: {}", e))?;
+        let preview = self.state.lock_preview(query_id, &chain_id).await?;
+        
+        Ok(LockPreviewInfo::from_preview(preview))
+    }
+    
+    /// Get protocol parameters
+    async fn parameters(&self) -> String {
+        format!("{:?}", self.state.get_parameters().await)
+    }
+    
+    /// Get the protocol parameters that affect how a query resolves
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   resolutionParameters {
+    ///     lateRevealWeightBps multiWinnerThresholdBps maxEffectiveStake
+    ///   }
+    /// }
+    /// ```
+    async fn resolution_parameters(&self) -> ResolutionParametersInfo {
+        ResolutionParametersInfo::from_parameters(&self.state.get_parameters().await)
+    }
+    
+    /// Get a hash of the protocol parameters and admin, for cache invalidation
+    /// 
+    /// Cheap to poll: refetch `parameters` only when the fingerprint changes.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   configFingerprint
+    /// }
+    /// ```
+    async fn config_fingerprint(&self) -> String {
+        self.state.config_fingerprint()
+    }
+
+    /// Get the proposed parameter change waiting out the timelock, if any
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   pendingParameters { params effectiveAt isReady }
+    /// }
+    /// ```
+    async fn pending_parameters(&self) -> Option<PendingParametersInfo> {
+        self.state.pending_parameters.get().as_ref().map(|pending| PendingParametersInfo {
+            params: format!("{:?}", pending.params),
+            proposed_by: pending.proposed_by.to_string(),
+            proposed_at: pending.proposed_at.micros().to_string(),
+            effective_at: pending.effective_at.micros().to_string(),
+            is_ready: pending.is_ready(self.current_time),
+        })
+    }
+
+    /// Get total voter count
+    async fn voter_count(&self) -> u64 {
+        *self.state.voter_count.get()
+    }
+    
+    /// Count voters who registered with a referral code (case-insensitive)
+    /// 
+    /// Scans every voter.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   referrals(code: "spring-campaign")
+    /// }
+    /// ```
+    async fn referrals(&self, code: String) -> Result<u64, String> {
+        let code = state::normalize_referral_code(&code)?;
+        
+        let indices = self.state.voters.indices().await
+            .map_err(|e| format!("Failed to get voter indices: {}", e))?;
+        let mut voters = Vec::new();
+        for chain_id in indices {
+            if let Some(voter) = self.state.get_voter(&chain_id).await {
+                voters.push(voter);
+            }
+        }
+        
+        Ok(state::count_referrals(&voters, &code) as u64)
+    }
+
+    /// Get total stake
+    async fn total_stake(&self) -> String {
+        self.state.total_stake.get().to_string()
+    }
+    
+    /// Get voter information by address
+    /// 
+    /// Returns detailed information about a specific voter including their stake,
+    /// reputation, voting history, and activity status.
+    /// 
+    /// # Arguments
+    /// * `address` - The voter's account address as a hex string (e.g., "0x1234...")
+    /// 
+    /// # Returns
+    /// Voter object with all voter information, or None if voter not found
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   voter(address: "0x1234...") {
+    ///     address
+    ///     stake
+    ///     lockedStake
+    ///     availableStake
+    ///     reputation
+    ///     reputationTier
+    ///     reputationWeight
+    ///     totalVotes
+    ///     correctVotes
+    ///     accuracyPercentage
+    ///     registeredAt
+    ///     isActive
+    ///     name
+    ///     metadataUrl
+    ///   }
+    /// }
+    /// ```
+    async fn voter(&self, address: String) -> Result<Option<Voter>, String> {
+        // Parse the address string to ChainId
+        let chain_id = state::parse_chain_id(&address)
+            .map_err(|e| format!("Invalid chain ID: {}", e))?;
+        
+        // Get voter info from state
+        let voter_info = match self.state.get_voter(&chain_id).await {
+            Some(info) => info,
+            None => return Ok(None), // Voter not found
+        };
+        
+        // Get available stake (total stake - locked stake)
+        let available_stake = self.state.get_available_stake(&chain_id).await;
+        
+        // Get pending rewards
+        let pending_rewards = self.state.get_pending_rewards(&chain_id).await;
+        
+        // Convert to GraphQL Voter type
+        let voter = Voter::from_voter_info(voter_info, available_stake, pending_rewards, &self.state, self.current_time);
+        
+        Ok(Some(voter))
+    }
+    
+    /// Get several voters by address in one request
+    /// 
+    /// Returns one entry per requested address, in the same order, with
+    /// `null` for addresses that are not registered voters.
+    /// 
+    /// # Arguments
+    /// * `addresses` - Voter chain IDs (1 to 100 entries)
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   votersByAddress(addresses: ["e476...", "a1b2..."]) {
+    ///     address
+    ///     stake
+    ///     reputation
+    ///     name
+    ///   }
+    /// }
+    /// ```
+    async fn voters_by_address(&self, addresses: Vec<String>) -> Result<Vec<Option<Voter>>, String> {
+        let chain_ids = state::parse_voter_addresses(&addresses)?;
+        
+        let mut voters = Vec::with_capacity(chain_ids.len());
+        for chain_id in chain_ids {
+            let voter_info = match self.state.get_voter(&chain_id).await {
+                Some(info) => info,
+                None => {
+                    voters.push(None);
+                    continue;
+                }
+            };
+            
+            let available_stake = self.state.get_available_stake(&chain_id).await;
+            let pending_rewards = self.state.get_pending_rewards(&chain_id).await;
+            voters.push(Some(Voter::from_voter_info(voter_info, available_stake, pending_rewards, &self.state, self.current_time)));
+        }
+        
+        Ok(voters)
+    }
+    
+    /// Compare voters' key metrics side by side
+    /// 
+    /// Returns one row per requested address, in the same order, with `null`
+    /// for addresses that are not registered voters.
+    /// 
+    /// # Arguments
+    /// * `addresses` - Voter chain IDs (1 to 10 entries)
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   compareVoters(addresses: ["e476...", "a1b2..."]) {
+    ///     address reputation accuracyPercentage stake power totalVotes
+    ///   }
+    /// }
+    /// ```
+    async fn compare_voters(&self, addresses: Vec<String>) -> Result<Vec<Option<VoterComparisonInfo>>, String> {
+        let chain_ids = state::parse_compared_voters(&addresses)?;
+        
+        let mut rows = Vec::with_capacity(chain_ids.len());
+        for chain_id in chain_ids {
+            let row = self.state.get_voter(&chain_id).await
+                .map(|voter| VoterComparisonInfo::from_comparison(state::VoterComparison::from_voter(&voter)));
+            rows.push(row);
+        }
+        
+        Ok(rows)
+    }
+    
+    /// Get all registered voters
+    /// 
+    /// Returns a list of all voters registered in the system, including both
+    /// active and inactive voters. This query is useful for displaying voter
+    /// directories, leaderboards, and analytics.
+    /// 
+    /// # Arguments
+    /// * `limit` - Optional maximum number of voters to return (default: 100, max: 1000)
+    /// * `offset` - Optional number of voters to skip for pagination (default: 0)
+    /// * `active_only` - Optional filter to return only active voters (default: false)
+    /// 
+    /// # Returns
+    /// List of Voter objects with all voter information
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   voters(limit: 50, offset: 0, activeOnly: true) {
+    ///     address
+    ///     stake
+    ///     reputation
+    ///     reputationTier
+    ///     totalVotes
+    ///     accuracyPercentage
+    ///     isActive
+    ///     name
+    ///   }
+    /// }
+    /// ```
+    async fn voters(
+        &self,
+        limit: Option<i32>,
+        offset: Option<i32>,
+        active_only: Option<bool>,
+    ) -> Result<Vec<Voter>, String> {
+        // Validate and set defaults for pagination parameters
+        let (limit, offset) = parse_pagination(limit, offset)?;
+        
+        let active_only = active_only.unwrap_or(false);
+        
+        // Collect all voters from the MapView
+        let mut voters = Vec::new();
+        let mut count = 0;
+        let mut skipped = 0;
+        
+        // Iterate through all voters in the MapView
+        // Note: This uses the indices() method to get all keys, then fetches each voter
+        let voter_indices = self.state.voters.indices().await
+            .map_err(|e| format!("Failed to get voter indices: {}", e))?;
+        
+        for address in voter_indices {
+            // Skip voters until we reach the offset
+            if skipped < offset {
+                skipped += 1;
+                continue;
+            }
+            
+            // Stop if we've collected enough voters
+            if count >= limit {
+                break;
+            }
+            
+            // Get voter info
+            let voter_info = match self.state.get_voter(&address).await {
+                Some(info) => info,
+                None => continue, // Skip if voter not found (shouldn't happen)
+            };
+            
+            // Apply active_only filter
+            if active_only && !voter_info.is_active {
+                continue;
+            }
+            
+            // Get available stake
+            let available_stake = self.state.get_available_stake(&address).await;
+            
+            // Get pending rewards
+            let pending_rewards = self.state.get_pending_rewards(&address).await;
+            
+            // Convert to GraphQL Voter type
+            let voter = Voter::from_voter_info(voter_info, available_stake, pending_rewards, &self.state, self.current_time);
+            voters.push(voter);
+            count += 1;
+        }
+        
+        Ok(voters)
+    }
+    
+    /// Get voters with no activity (register, vote, claim or stake change) in `days` days
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   dormantVoters(days: 30) {
+    ///     address
+    ///     lastActiveAt
+    ///     stake
+    ///   }
+    /// }
+    /// ```
+    async fn dormant_voters(&self, days: i32) -> Result<Vec<Voter>, String> {
+        if days < 1 {
+            return Err("Days must be at least 1".to_string());
+        }
+        
+        let voter_indices = self.state.voters.indices().await
+            .map_err(|e| format!("Failed to get voter indices: {}", e))?;
+        
+        let mut voters = Vec::new();
+        for address in voter_indices {
+            let voter_info = match self.state.get_voter(&address).await {
+                Some(info) => info,
+                None => continue,
+            };
+            
+            if !voter_info.is_dormant(self.current_time, days as u64) {
+                continue;
+            }
+            
+            let available_stake = self.state.get_available_stake(&address).await;
+            let pending_rewards = self.state.get_pending_rewards(&address).await;
+            voters.push(Voter::from_voter_info(voter_info, available_stake, pending_rewards, &self.state, self.current_time));
+        }
+        
+        Ok(voters)
+    }
+    
+    /// Get the current user's voter information
+    /// 
+    /// This is a convenience query that returns voter information for a specific address.
+    /// Since GraphQL services in Linera don't have authentication context, the client
+    /// must provide their address (which they know from their wallet).
+    /// 
+    /// This query is functionally equivalent to `voter(address)` but provides a more
+    /// intuitive API for clients to query their own information.
+    /// 
+    /// # Arguments
+    /// * `address` - The voter's account address as a hex string (e.g., "0x1234...")
+    ///               This should be the address from the user's wallet/account
+    /// 
+    /// # Returns
+    /// Voter object with all voter information, or None if voter not found
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   myVoterInfo(address: "0x1234...") {
+    ///     address
+    ///     stake
+    ///     lockedStake
+    ///     availableStake
+    ///     reputation
+    ///     reputationTier
+    ///     reputationWeight
+    ///     totalVotes
+    ///     correctVotes
+    ///     accuracyPercentage
+    ///     registeredAt
+    ///     isActive
+    ///     name
+    ///     metadataUrl
+    ///   }
+    /// }
+    /// ```
+    /// 
+    /// # Note
+    /// In a typical web3 application, the client (dashboard/frontend) knows the user's
+    /// address from their connected wallet. The client should pass this address to this
+    /// query to retrieve the user's voter information.
+    async fn my_voter_info(&self, address: String) -> Result<Option<Voter>, String> {
+        // Duplicate logic from voter() since we can't call it directly without context
+        let chain_id = state::parse_chain_id(&address)
+            .map_err(|e| format!("Invalid chain ID: {}", e))?;
+        
+        let voter_info = match self.state.get_voter(&chain_id).await {
+            Some(info) => info,
+            None => return Ok(None),
+        };
+        
+        let available_stake = self.state.get_available_stake(&chain_id).await;
+        let pending_rewards = self.state.get_pending_rewards(&chain_id).await;
+        let voter = Voter::from_voter_info(voter_info, available_stake, pending_rewards, &self.state, self.current_time);
+        
+        Ok(Some(voter))
+    }
+    
+    /// Get a voter's whole dashboard in one request
+    /// 
+    /// Bundles the voter profile, balances, selected queries, pending reveals,
+    /// recent history (last 20 resolved votes, archived queries excluded) and
+    /// earnings, all read from the same state. This scans every query, so it
+    /// is heavier than any single resolver; prefer `voter` or `query` when
+    /// only one section is needed.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   voterDashboard(address: "0x1234...") {
+    ///     profile { reputation canClaim }
+    ///     balances { stake availableStake pendingRewards }
+    ///     selectedQueries { id description phase }
+    ///     pendingReveals { id revealPhaseEnd }
+    ///     recentHistory { queryId value correct }
+    ///     earnings { totalRewarded totalSlashed }
+    ///   }
+    /// }
+    /// ```
+    async fn voter_dashboard(&self, address: String) -> Result<Option<VoterDashboard>, String> {
+        let chain_id = state::parse_chain_id(&address)
+            .map_err(|e| format!("Invalid chain ID: {}", e))?;
+        
+        let voter_info = match self.state.get_voter(&chain_id).await {
+            Some(info) => info,
+            None => return Ok(None),
+        };
+        
+        let available_stake = self.state.get_available_stake(&chain_id).await;
+        let pending_rewards = self.state.get_pending_rewards(&chain_id).await;
+        let token_holdings = self.state.token_holdings.get(&chain_id).await
+            .ok()
+            .flatten()
+            .unwrap_or(Amount::ZERO);
+        let earnings = self.state.get_voter_earnings(&chain_id).await;
+        
+        let balances = VoterBalances {
+            stake: voter_info.stake.to_string(),
+            locked_stake: voter_info.locked_stake.to_string(),
+            available_stake: available_stake.to_string(),
+            pending_rewards: pending_rewards.to_string(),
+            token_holdings: token_holdings.to_string(),
+        };
+        let profile = Voter::from_voter_info(voter_info, available_stake, pending_rewards, &self.state, self.current_time);
+        
+        // Load every query once so all sections agree
+        let query_indices = self.state.queries.indices().await
+            .map_err(|e| format!("Failed to get query indices: {}", e))?;
+        let mut queries = Vec::new();
+        for query_id in query_indices {
+            if let Some(query) = self.state.get_query(query_id).await {
+                queries.push(query);
+            }
+        }
+        
+        let activity = state::voter_activity(&chain_id, &queries, state::DASHBOARD_HISTORY_LIMIT);
+        let to_graphql = |ids: &[u64]| -> Vec<Query> {
+            queries.iter()
+                .filter(|query| ids.contains(&query.id))
+                .map(|query| Query::from_state_query(query.clone(), query.votes.len(), self.current_time))
+                .collect()
+        };
+        
+        Ok(Some(VoterDashboard {
+            profile,
+            balances,
+            selected_queries: to_graphql(&activity.selected_queries),
+            pending_reveals: to_graphql(&activity.pending_reveals),
+            recent_history: activity.recent_history.into_iter()
+                .map(|item| VoterHistoryEntry {
+                    query_id: item.query_id,
+                    value: item.value,
+                    correct: item.correct,
+                    resolved_at: item.resolved_at.map(|ts| ts.micros().to_string()),
+                })
+                .collect(),
+            earnings: VoterEarningsInfo {
+                total_rewarded: earnings.total_rewarded.to_string(),
+                total_slashed: earnings.total_slashed.to_string(),
+                pending_rewards: pending_rewards.to_string(),
+            },
+        }))
+    }
+    
+    /// Get the chain allowed to claim a voter's rewards, if any
+    async fn claim_operator(&self, address: String) -> Result<Option<String>, String> {
+        let chain_id = state::parse_chain_id(&address)
+            .map_err(|e| format!("Invalid chain ID: {}", e))?;
+        
+        Ok(self.state.get_claim_operator(&chain_id).await.map(|operator| operator.to_string()))
+    }
+    
+    /// Get a voter's attestation: a canonical record of their track record
+    /// 
+    /// `encoding` is the hex of the BCS-encoded record
+    /// `{ address, reputation, totalVotes, correctVotes, registeredAt, block }`
+    /// and is stable for the same voter state and block. Services cannot
+    /// sign, so the attestation is not self-proving: verifiers should check
+    /// it by reading the registry at `block` (or the fields directly) and
+    /// re-encoding.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   voterAttestation(address: "0x1234...") {
+    ///     reputation totalVotes correctVotes block encoding
+    ///   }
+    /// }
+    /// ```
+    async fn voter_attestation(&self, address: String) -> Result<Option<VoterAttestationInfo>, String> {
+        let chain_id = state::parse_chain_id(&address)
+            .map_err(|e| format!("Invalid chain ID: {}", e))?;
+        
+        let voter_info = match self.state.get_voter(&chain_id).await {
+            Some(info) => info,
+            None => return Ok(None),
+        };
+        
+        let attestation = state::VoterAttestation::new(&voter_info, self.block_height);
+        let encoding = attestation.encode();
+        
+        Ok(Some(VoterAttestationInfo {
+            address: attestation.address,
+            reputation: attestation.reputation,
+            total_votes: attestation.total_votes,
+            correct_votes: attestation.correct_votes,
+            registered_at: attestation.registered_at.to_string(),
+            block: attestation.block.to_string(),
+            encoding,
+        }))
+    }
+    
+    /// Get all queries
+    async fn queries(&self) -> Result<Vec<Query>, String> {
+        let mut queries = Vec::new();
+        
+        // Get all query IDs
+        let query_indices = self.state.queries.indices().await
+            .map_err(|e| format!("Failed to get query indices: {}", e))?;
+        
+        // Use timestamp 0 for time_remaining calculation (frontend will calculate)
+        let current_time = linera_sdk::linera_base_types::Timestamp::from(0);
+        
+        for query_id in query_indices {
+            if let Some(query) = self.state.get_query(query_id).await {
+                let vote_count = query.votes.len();
+                let graphql_query = Query::from_state_query(query, vote_count, current_time);
+                queries.push(graphql_query);
+            }
+        }
+        
+        Ok(queries)
+    }
+    
+    /// Get all queries in a category (case-insensitive)
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   queriesByCategory(category: "sports") {
+    ///     id
+    ///     description
+    ///     tags
+    ///   }
+    /// }
+    /// ```
+    async fn queries_by_category(&self, category: String) -> Result<Vec<Query>, String> {
+        let mut queries = Vec::new();
+        
+        let query_indices = self.state.queries.indices().await
+            .map_err(|e| format!("Failed to get query indices: {}", e))?;
+        
+        // Use timestamp 0 for time_remaining calculation (frontend will calculate)
+        let current_time = linera_sdk::linera_base_types::Timestamp::from(0);
+        
+        for query_id in query_indices {
+            if let Some(query) = self.state.get_query(query_id).await {
+                if query.in_category(&category) {
+                    let vote_count = query.votes.len();
+                    queries.push(Query::from_state_query(query, vote_count, current_time));
+                }
+            }
+        }
+        
+        Ok(queries)
+    }
+    
+    /// Get the queries created by a chain, oldest first
+    /// 
+    /// # Arguments
+    /// * `creator` - The creator's chain ID
+    /// * `limit` - Maximum number of queries to return (default: 100, max: 1000)
+    /// * `offset` - Number of matching queries to skip (default: 0)
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   queriesByCreator(creator: "e476...", limit: 20) {
+    ///     id
+    ///     description
+    ///     status
+    ///   }
+    /// }
+    /// ```
+    async fn queries_by_creator(
+        &self,
+        creator: String,
+        limit: Option<i32>,
+        offset: Option<i32>,
+    ) -> Result<Vec<Query>, String> {
+        let creator = state::parse_chain_id(&creator)
+            .map_err(|e| format!("Invalid creator chain ID: {}", e))?;
+        let (limit, offset) = parse_pagination(limit, offset)?;
+        
+        let query_indices = self.state.queries.indices().await
+            .map_err(|e| format!("Failed to get query indices: {}", e))?;
+        
+        let mut created = Vec::new();
+        for query_id in query_indices {
+            if let Some(query) = self.state.get_query(query_id).await {
+                created.push(query);
+            }
+        }
+        
+        // Use timestamp 0 for time_remaining calculation (frontend will calculate)
+        let current_time = linera_sdk::linera_base_types::Timestamp::from(0);
+        
+        Ok(state::queries_by_creator_page(created, &creator, offset, limit)
+            .into_iter()
+            .map(|query| {
+                let vote_count = query.votes.len();
+                Query::from_state_query(query, vote_count, current_time)
+            })
+            .collect())
+    }
+    
+    /// Get a specific query by ID
+    async fn query(&self, id: u64) -> Result<Option<Query>, String> {
+        let query = match self.state.get_query(id).await {
+            Some(q) => q,
+            None => return Ok(None),
+        };
+        
+        // Use timestamp 0 for time_remaining calculation (frontend will calculate)
+        let current_time = linera_sdk::linera_base_types::Timestamp::from(0);
+        
+        let vote_count = query.votes.len();
+        let graphql_query = Query::from_state_query(query, vote_count, current_time);
+        
+        Ok(Some(graphql_query))
+    }
+    
+    /// Get a specific query by ID with all votes included
+    /// 
+    /// This query returns the full query details including all votes cast by voters.
+    /// Useful for displaying vote breakdown on resolved queries.
+    /// 
+    /// # Arguments
+    /// * `id` - The query ID
+    /// 
+    /// # Returns
+    /// Query object with votes array populated
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   queryWithVotes(id: 1) {
+    ///     id
+    ///     description
+    ///     status
+    ///     result
+    ///     votes {
+    ///       voter
+    ///       value
+    ///       timestamp
+    ///       confidence
+    ///     }
+    ///   }
+    /// }
+    /// ```
+    async fn query_with_votes(&self, id: u64) -> Result<Option<Query>, String> {
+        let query = match self.state.get_query(id).await {
+            Some(q) => q,
+            None => return Ok(None),
+        };
+        
+        // Use timestamp 0 for time_remaining calculation (frontend will calculate)
+        let current_time = linera_sdk::linera_base_types::Timestamp::from(0);
+        
+        // Load voters for the per-vote weights
+        let mut voter_infos = std::collections::BTreeMap::new();
+        for voter in query.votes.keys() {
+            if let Some(info) = self.state.get_voter(voter).await {
+                voter_infos.insert(*voter, info);
+            }
+        }
+        
+        let graphql_query = Query::from_state_query_with_votes(query, &voter_infos, current_time);
+        
+        Ok(Some(graphql_query))
+    }
+    
+    /// Estimate a voter's reward on an active query
+    /// 
+    /// Projects the reward the voter would receive if their vote (or, if they
+    /// have not voted yet, the currently leading outcome) turns out correct,
+    /// using the same distribution math as resolution. This is an estimate only:
+    /// further votes change the split and the final result.
+    /// 
+    /// # Arguments
+    /// * `query_id` - The query ID
+    /// * `address` - The voter's chain ID
+    /// 
+    /// # Returns
+    /// RewardEstimate, or None if the query is not active, the voter is not
+    /// registered, or there is no vote to project from yet
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   expectedReward(queryId: 1, address: "e476...") {
+    ///     assumedResult { display }
+    ///     estimatedReward
+    ///     isEstimate
+    ///   }
+    /// }
+    /// ```
+    async fn expected_reward(&self, query_id: u64, address: String) -> Result<Option<RewardEstimate>, String> {
+        let chain_id = state::parse_chain_id(&address)
+            .map_err(|e| format!("Invalid chain ID: {}", e))?;
+        
+        let query = match self.state.get_query(query_id).await {
+            Some(q) if q.status == state::QueryStatus::Active => q,
+            _ => return Ok(None),
+        };
+        
+        // Collect voter info for the query's voters and the requesting voter
+        let mut voter_infos = std::collections::BTreeMap::new();
+        for voter in query.votes.keys().chain(std::iter::once(&chain_id)) {
+            if let Some(info) = self.state.get_voter(voter).await {
+                voter_infos.insert(*voter, info);
+            }
+        }
+        
+        let params = self.state.get_parameters().await;
+        let estimate = state::project_voter_reward(&query, &chain_id, &voter_infos, &params)
+            .map(|(result, reward)| RewardEstimate {
+                query_id,
+                assumed_result: Resolution::from_result(&result),
+                estimated_reward: reward.to_string(),
+                is_estimate: true,
+            });
+        
+        Ok(estimate)
+    }
+    
+    /// Get the active queries that can be resolved or expired right now
+    /// 
+    /// `resolvable` queries are past their deadline and reveal grace window
+    /// with enough votes; `expirable` ones are past their deadline without
+    /// enough votes. Only queries listed as active are checked, so keepers
+    /// can target ResolveQuery/ExpireQuery calls instead of sweeping.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   actionableQueries { resolvable expirable }
+    /// }
+    /// ```
+    async fn actionable_queries(&self) -> ActionableQueriesInfo {
+        let grace_secs = self.state.get_parameters().await.resolution_grace_secs;
+        
+        let mut queries = Vec::new();
+        for query_id in self.state.get_active_queries().await {
+            if let Some(query) = self.state.get_query(query_id).await {
+                queries.push(query);
+            }
+        }
+        
+        let actionable = state::actionable_queries(&queries, self.current_time, grace_secs);
+        ActionableQueriesInfo {
+            resolvable: actionable.resolvable,
+            expirable: actionable.expirable,
+        }
+    }
+    
+    /// Get stake unlocking progress of a finalized query
+    /// 
+    /// Returns `null` unless unlocking is still in progress, i.e. more voters
+    /// remain than the finalizing block unlocked. Use `executeUnlockQueryStakes`
+    /// to continue.
+    async fn stake_unlock_progress(&self, query_id: u64) -> Option<StakeUnlockProgress> {
+        let unlocked = self.state.get_unlock_cursor(query_id).await?;
+        let query = self.state.get_query(query_id).await?;
+        let total = query.stake_lockers().len();
+        
+        Some(StakeUnlockProgress {
+            unlocked: unlocked as u32,
+            remaining: total.saturating_sub(unlocked) as u32,
+        })
+    }
+    
+    /// Get the summary of an archived query by ID
+    /// 
+    /// Archived queries are no longer returned by `query`/`queries`.
+    async fn archived_query(&self, id: u64) -> Option<ArchivedQuerySummary> {
+        self.state.get_archived_query(id).await.map(ArchivedQuerySummary::from_archived)
+    }
+    
+    /// Get the voters selected for a query with their current power
+    /// 
+    /// Explains the selection made at query creation: each selected voter's
+    /// stake, reputation, power and whether they have participated.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   queryPanel(id: 0) {
+    ///     address
+    ///     stake
+    ///     reputation
+    ///     power
+    ///     participated
+    ///   }
+    /// }
+    /// ```
+    async fn query_panel(&self, id: u64) -> Result<Vec<PanelVoter>, String> {
+        let query = self.state.get_query(id).await
+            .ok_or_else(|| format!("Query {} not found", id))?;
+        
+        let mut voter_infos = std::collections::BTreeMap::new();
+        for voter in &query.selected_voters {
+            if let Some(info) = self.state.get_voter(voter).await {
+                voter_infos.insert(*voter, info);
+            }
+        }
+        
+        Ok(state::query_panel(&query, &voter_infos)
+            .into_iter()
+            .map(PanelVoter::from_member)
+            .collect())
+    }
+    
+    /// Get recently failed cross-chain messages, most recent first
+    /// 
+    /// Cross-chain messages have no caller to report errors to, so failures
+    /// are logged here (last 100 kept). `limit` defaults to all of them.
+    async fn failed_messages(&self, limit: Option<u32>) -> Vec<FailedMessageInfo> {
+        let limit = limit.map_or(state::MAX_FAILED_MESSAGES as usize, |l| l as usize);
+        self.state.get_failed_messages(limit).await
+            .into_iter()
+            .map(FailedMessageInfo::from_failed)
+            .collect()
+    }
+    
+    /// Get the registry's recent activity, most recent first
+    /// 
+    /// Registrations, query creations, votes and resolutions, without
+    /// subscribing to the event stream (last 200 kept).
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   activityFeed(limit: 20) { id kind timestamp chainId queryId detail }
+    /// }
+    /// ```
+    async fn activity_feed(&self, limit: i32) -> Vec<ActivityInfo> {
+        let limit = usize::try_from(limit).unwrap_or(0);
+        self.state.get_activity_feed(limit).await
+            .into_iter()
+            .map(ActivityInfo::from_entry)
+            .collect()
+    }
+    
+    /// Get protocol parameter changes, most recent first
+    /// 
+    /// Each entry holds the full parameters in effect from `changedAt`, for
+    /// auditing how past queries were resolved or slashed (last 100 kept).
+    /// `limit` defaults to all of them.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   parameterHistory(limit: 5) { id changedAt changedBy params }
+    /// }
+    /// ```
+    async fn parameter_history(&self, limit: Option<u32>) -> Vec<ParameterChangeInfo> {
+        let limit = limit.map_or(state::MAX_PARAMETER_HISTORY as usize, |l| l as usize);
+        self.state.get_parameter_history(limit).await
+            .into_iter()
+            .map(ParameterChangeInfo::from_change)
+            .collect()
+    }
+    
+    /// Get tokens rewarded per resolved query, oldest first
+    /// 
+    /// `from`/`to` are inclusive bounds in microseconds (both optional).
+    /// The log keeps the last 1000 resolutions; results are paginated with
+    /// `offset` and `limit` (at most 100 per page).
+    async fn reward_history(
+        &self,
+        from: Option<String>,
+        to: Option<String>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Vec<FlowEntryInfo>, String> {
+        let entries = self.state.get_reward_history().await;
+        flow_history_window(entries, from, to, offset, limit)
+    }
+    
+    /// Get tokens slashed per resolved query, oldest first
+    /// 
+    /// Same window and pagination semantics as `reward_history`.
+    async fn slash_history(
+        &self,
+        from: Option<String>,
+        to: Option<String>,
+        offset: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Vec<FlowEntryInfo>, String> {
+        let entries = self.state.get_slash_history().await;
+        flow_history_window(entries, from, to, offset, limit)
+    }
+    
+    /// Get a voter's stake after each stake change or slash, oldest first
+    /// 
+    /// The last 100 snapshots per voter are kept. History survives
+    /// deregistration, ending with a zero-stake snapshot.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   voterStakeHistory(address: "e476...") { timestamp stake lockedStake }
+    /// }
+    /// ```
+    async fn voter_stake_history(&self, address: String) -> Result<Vec<StakeSnapshotInfo>, String> {
+        let chain_id = state::parse_chain_id(&address)
+            .map_err(|e| format!("Invalid chain ID: {}", e))?;
+        
+        Ok(self.state.get_stake_history(&chain_id).await
+            .into_iter()
+            .map(StakeSnapshotInfo::from_snapshot)
+            .collect())
+    }
+    
+    /// Get statistics, scanning every voter for exact totals
+    async fn statistics(&self) -> Result<Statistics, String> {
+        Statistics::from_state(&self.state).await
+    }
+    
+    /// Get statistics from the contract's cached voter totals (no scan)
+    /// 
+    /// Matches `statistics` as long as the cache has been built; run the
+    /// RefreshStatistics operation once on registries created before it existed.
+    async fn statistics_cached(&self) -> Statistics {
+        Statistics::from_cache(&self.state).await
+    }
+    
+    /// Check that voter obligations are backed by tracked balances
+    /// 
+    /// Obligations are total stake plus unclaimed rewards; backing is the
+    /// tokens held from the token chain plus the reward pool. An insolvent
+    /// result points at an accounting bug (or at stake registered without a
+    /// token deposit). Scans every voter and pending reward.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   solvency { totalStaked totalPendingRewards tokensHeld rewardPool shortfall isSolvent }
+    /// }
+    /// ```
+    async fn solvency(&self) -> Result<SolvencyInfo, String> {
+        let solvency = self.state.compute_solvency().await?;
+        
+        Ok(SolvencyInfo {
+            total_staked: solvency.total_staked.to_string(),
+            total_locked: solvency.total_locked.to_string(),
+            total_pending_rewards: solvency.total_pending_rewards.to_string(),
+            reward_pool: solvency.reward_pool.to_string(),
+            treasury: solvency.treasury.to_string(),
+            tokens_held: solvency.tokens_held.to_string(),
+            shortfall: solvency.shortfall().to_string(),
+            is_solvent: solvency.is_solvent(),
+        })
+    }
+    
+    /// Get how active voters' selection power is distributed
+    /// 
+    /// Power is stake (attos) × reputation. The range from the lowest to the
+    /// highest power is split into `buckets` equal ranges (fewer if the
+    /// range is narrower), each with its voter count and total power.
+    /// 
+    /// # Arguments
+    /// * `buckets` - Number of ranges (1-100)
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   powerDistribution(buckets: 10) { minPower maxPower voterCount totalPower }
+    /// }
+    /// ```
+    async fn power_distribution(&self, buckets: i32) -> Result<Vec<PowerBucketInfo>, String> {
+        if !(1..=100).contains(&buckets) {
+            return Err("Buckets must be between 1 and 100".to_string());
+        }
+        
+        let distribution = self.state.compute_power_distribution(buckets as usize).await?;
+        
+        Ok(distribution.into_iter()
+            .map(|bucket| PowerBucketInfo {
+                min_power: bucket.min_power.to_string(),
+                max_power: bucket.max_power.to_string(),
+                voter_count: bucket.voter_count,
+                total_power: bucket.total_power.to_string(),
+            })
+            .collect())
+    }
+    
+    /// Check how evenly query panels are spread across voters
+    /// 
+    /// Lists every voter ever selected (and every registered voter, at zero),
+    /// most selected first, with how many of their closed panels they took
+    /// part in. `concentrationBps` is the Gini coefficient of selection
+    /// counts: 0 when all voters are selected equally, near 10000 when one
+    /// voter fills every panel. Scans every voter.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// query {
+    ///   selectionStats {
+    ///     totalSelections concentrationBps
+    ///     voters { voterAddress selectedCount participationRateBps }
+    ///   }
+    /// }
+    /// ```
+    async fn selection_stats(&self) -> Result<SelectionStatsInfo, String> {
+        let stats = self.state.compute_selection_stats().await?;
+        
+        Ok(SelectionStatsInfo {
+            voters: stats.voters.iter()
+                .map(|(voter, record)| VoterSelectionStats {
+                    voter_address: voter.to_string(),
+                    selected_count: record.selected,
+                    closed_count: record.closed,
+                    participated_count: record.participated,
+                    participation_rate_bps: record.participation_bps(),
+                })
+                .collect(),
+            total_selections: stats.total_selections,
+            concentration_bps: stats.concentration_bps,
+        })
+    }
+
+}
+
+struct MutationRoot {
+    runtime: Arc<ServiceRuntime<OracleRegistryV2Service>>,
+}
+
+#[Object]
+impl MutationRoot {
+    /// Register as a new voter
+    /// 
+    /// This mutation provides information about the RegisterVoter operation.
+    /// To execute the registration, call the RegisterVoter operation on the contract.
+    /// 
+    /// # Arguments
+    /// * `stake` - Initial stake amount (in tokens as string)
+    /// * `name` - Optional voter name
+    /// * `metadata_url` - Optional URL to voter metadata
+    /// * `referral` - Optional referral/attribution code (letters, digits, '-', '_'; max 32)
+    /// * `encrypted_metadata` - Optional hex blob encrypted off-chain (max 1024 bytes)
+    /// 
+    /// # Returns
+    /// Empty array (operation is scheduled for execution)
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   registerVoter(stake: "1000", name: "Alice")
+    /// }
+    /// ```
+    /// 
+    /// # Note
+    /// Chain ID is automatically detected using runtime.chain_id()
+    /// No address parameter needed! (Microcard pattern)
+    async fn register_voter(
+        &self,
+        stake: String,
+        name: Option<String>,
+        metadata_url: Option<String>,
+        referral: Option<String>,
+        encrypted_metadata: Option<String>,
+    ) -> String {
+        use oracle_registry_v2::Operation;
+        
+        // Parse stake as Amount directly (expects format like "100." with trailing dot)
+        // This is consistent with how alethea-token handles amounts
+        let stake_amount: Amount = match stake.parse() {
+            Ok(v) => v,
+            Err(_) => return "Error: Invalid stake format. Use format like '100.' with trailing dot".to_string(),
+        };
+        
+        if let Some(Err(e)) = referral.as_deref().map(state::normalize_referral_code) {
+            return format!("Error: {}", e);
+        }
+        
+        let encrypted_metadata = match encrypted_metadata.as_deref().map(parse_encrypted_metadata).transpose() {
+            Ok(blob) => blob,
+            Err(e) => return format!("Error: {}", e),
+        };
+        
+        // Create RegisterVoter operation
+        let operation = Operation::RegisterVoter {
+            stake: stake_amount,
+            name,
+            metadata_url,
+            referral,
+            encrypted_metadata,
+        };
+        
+        // Schedule operation - will be executed when block is created
+        self.runtime.schedule_operation(&operation);
+        
+        // Return success message
+        format!("Voter registration scheduled with stake: {}", stake)
+    }
+
+    /// Update stake by adding additional stake to voter account
+    /// 
+    /// This mutation provides information about the UpdateStake operation.
+    /// To execute the stake update, call the UpdateStake operation on the contract.
+    /// 
+    /// # Arguments
+    /// * `additional_stake` - Additional amount to add to stake (in tokens as string)
+    /// 
+    /// # Returns
+    /// JSON string with operation details for executing the stake update
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   updateStake(additionalStake: "1000000")
+    /// }
+    /// ```
+    /// 
+    /// Returns:
+    /// ```json
+    /// {
+    ///   "operation": "UpdateStake",
+    ///   "additional_stake": "1000000",
+    ///   "instructions": "Call the UpdateStake operation with the specified amount"
+    /// }
+    /// ```
+    async fn update_stake(&self, additional_stake: String) -> Result<String, String> {
+        // Validate amount format
+        let stake_value = additional_stake.parse::<u128>()
+            .map_err(|_| "Invalid amount format: must be a valid number".to_string())?;
+        
+        if stake_value == 0 {
+            return Err("Additional stake must be greater than zero".to_string());
+        }
+        
+        // Return operation details as JSON
+        let response = serde_json::json!({
+            "operation": "UpdateStake",
+            "additional_stake": additional_stake,
+            "instructions": "Call the UpdateStake operation on the contract with this amount",
+            "requirements": [
+                "Voter must be registered and active",
+                "Additional stake amount must be greater than zero",
+                "Voter must have sufficient balance to add the stake"
+            ]
+        });
+        
+        Ok(response.to_string())
+    }
+    
+    /// Withdraw stake from voter account
+    /// 
+    /// This mutation provides information about the WithdrawStake operation.
+    /// To execute the withdrawal, call the WithdrawStake operation on the contract.
+    /// 
+    /// # Arguments
+    /// * `amount` - Amount to withdraw (in tokens as string)
+    /// 
+    /// # Returns
+    /// JSON string with operation details for executing the withdrawal
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   withdrawStake(amount: "1000000")
+    /// }
+    /// ```
+    /// 
+    /// Returns:
+    /// ```json
+    /// {
+    ///   "operation": "WithdrawStake",
+    ///   "amount": "1000000",
+    ///   "instructions": "Call the WithdrawStake operation with the specified amount"
+    /// }
+    /// ```
+    async fn withdraw_stake(&self, amount: String) -> Result<String, String> {
+        // Validate amount format
+        let amount_value = amount.parse::<u128>()
+            .map_err(|_| "Invalid amount format: must be a valid number".to_string())?;
+        
+        if amount_value == 0 {
+            return Err("Withdrawal amount must be greater than zero".to_string());
+        }
+        
+        // Return operation details as JSON
+        let response = serde_json::json!({
+            "operation": "WithdrawStake",
+            "amount": amount,
+            "instructions": "Call the WithdrawStake operation on the contract with this amount",
+            "requirements": [
+                "Voter must be registered and active",
+                "Amount must not exceed available stake (total stake - locked stake)",
+                "Voter must not have active votes on any queries",
+                "Remaining stake must meet minimum requirement or be zero"
+            ]
+        });
+        
+        Ok(response.to_string())
+    }
+    
+    /// Deregister as a voter and return all stake
+    /// 
+    /// This mutation provides information about the DeregisterVoter operation.
+    /// To execute the deregistration, call the DeregisterVoter operation on the contract.
+    /// 
+    /// # Returns
+    /// JSON string with operation details for executing the deregistration
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   deregisterVoter
+    /// }
+    /// ```
+    /// 
+    /// Returns:
+    /// ```json
+    /// {
+    ///   "operation": "DeregisterVoter",
+    ///   "instructions": "Call the DeregisterVoter operation on the contract"
+    /// }
+    /// ```
+    async fn deregister_voter(&self) -> Result<String, String> {
+        // Return operation details as JSON
+        let response = serde_json::json!({
+            "operation": "DeregisterVoter",
+            "instructions": "Call the DeregisterVoter operation on the contract to deregister and return all stake",
+            "requirements": [
+                "Voter must be registered and active",
+                "Voter must not have any pending rewards (claim them first)",
+                "Voter must not have active votes on any queries",
+                "All stake will be returned to the voter"
+            ],
+            "effects": [
+                "Voter will be removed from the registry",
+                "All stake will be returned to the voter's account",
+                "Voter count will be decremented",
+                "Total stake will be reduced by the voter's stake amount"
+            ]
+        });
+        
+        Ok(response.to_string())
+    }
+    
+    /// Create a new query/market
+    /// 
+    /// This mutation provides information about the CreateQuery operation.
+    /// To execute the query creation, call the CreateQuery operation on the contract.
+    /// 
+    /// # Arguments
+    /// * `description` - Description of the query/question
+    /// * `outcomes` - List of possible outcomes (e.g., ["Yes", "No"] or ["Option A", "Option B", "Option C"])
+    /// * `strategy` - Decision strategy: "Majority", "Median", "WeightedByStake", "WeightedByReputation", or "ConfidenceWeightedMedian"
+    /// * `min_votes` - Optional minimum votes required (uses protocol default if not specified)
+    /// * `reward_amount` - Reward amount for correct voters (in tokens as string)
+    /// * `deadline` - Optional deadline timestamp in microseconds (uses protocol default duration if not specified)
+    /// * `duration_secs` - Optional custom duration in seconds (overrides default_query_duration)
+    ///                     This sets total duration, split 50/50 between commit and reveal phases
+    ///                     Example: 120 = 60s commit + 60s reveal
+    /// * `min_distinct_voted_outcomes` - Optional minimum number of distinct outcomes that must
+    ///                     receive votes; otherwise the query closes without consensus
+    /// * `min_votes_for_winner` - Optional minimum number of votes the winning outcome needs;
+    ///                     otherwise the query closes without consensus
+    /// * `min_distinct_voters` - Optional minimum number of distinct voter chains with tallied
+    ///                     votes; otherwise the query closes without consensus
+    /// * `max_group_power_bps` - Optional cap (basis points) on the voting power of voters
+    ///                     sharing a chain ID prefix; otherwise the query closes without consensus
+    /// * `min_voter_stake` - Optional minimum stake (in tokens as string) a voter needs to vote;
+    ///                     must be at least the registry's minimum stake
+    /// * `allow_abstain` - Optional; let voters vote "Abstain", which counts as participation
+    ///                     but is excluded from the result, rewards and slashing
+    /// * `multi_winner` - Optional; every outcome holding the protocol's multi-winner
+    ///                     threshold of the tally wins (not for median strategies)
+    /// * `category` - Optional browsing category (e.g. "sports", "weather", "crypto")
+    /// * `tags` - Optional tags (max 10, up to 32 characters each)
+    /// * `reward_basis` - Optional reward split: "Equal", "Stake", "Reputation", "Power" or
+    ///                     "Confidence"; defaults to the split implied by `strategy`
+    /// * `outcome_aliases` - Optional synonyms as "alias=outcome" entries (e.g. ["Y=Yes", "yes=Yes"]);
+    ///                     votes for an alias are counted under its outcome
+    /// * `tie_break` - Optional tie resolution for Majority queries: "OutcomeOrder" (default,
+    ///                     first tied outcome listed), "HigherStake", "HigherReputation" or "NoConsensus"
+    /// 
+    /// # Returns
+    /// JSON string with operation details for executing the query creation
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   createQuery(
+    ///     description: "Will it rain tomorrow?",
+    ///     outcomes: ["Yes", "No"],
+    ///     strategy: "Majority",
+    ///     rewardAmount: "1000000",
+    ///     durationSecs: 120
+    ///   )
+    /// }
+    /// ```
+    /// 
+    /// Returns:
+    /// ```json
+    /// {
+    ///   "operation": "CreateQuery",
+    ///   "description": "Will it rain tomorrow?",
+    ///   "outcomes": ["Yes", "No"],
+    ///   "strategy": "Majority",
+    ///   "reward_amount": "1000000",
+    ///   "duration_secs": 120,
+    ///   "instructions": "Call the CreateQuery operation with these parameters"
+    /// }
+    /// ```
+    async fn create_query(
+        &self,
+        description: String,
+        outcomes: Vec<String>,
+        strategy: String,
+        min_votes: Option<i32>,
+        reward_amount: String,
+        deadline: Option<String>,
+        duration_secs: Option<i32>,
+        min_distinct_voted_outcomes: Option<i32>,
+        min_votes_for_winner: Option<i32>,
+        min_distinct_voters: Option<i32>,
+        max_group_power_bps: Option<i32>,
+        min_voter_stake: Option<String>,
+        allow_abstain: Option<bool>,
+        multi_winner: Option<bool>,
+        category: Option<String>,
+        tags: Option<Vec<String>>,
+        reward_basis: Option<String>,
+        outcome_aliases: Option<Vec<String>>,
+        tie_break: Option<String>,
+    ) -> Result<String, String> {
+        let tags = tags.unwrap_or_default();
+        let allow_abstain = allow_abstain.unwrap_or(false);
+        let multi_winner = multi_winner.unwrap_or(false);
+        
+        // Validate description
+        if description.is_empty() {
+            return Err("Description cannot be empty".to_string());
+        }
+        if description.len() > 1000 {
+            return Err("Description too long (max 1000 characters)".to_string());
+        }
+        
+        // Validate outcomes
+        if outcomes.is_empty() {
+            return Err("At least one outcome must be provided".to_string());
+        }
+        if outcomes.len() > 100 {
+            return Err("Too many outcomes (max 100)".to_string());
+        }
+        
+        // Check for empty outcomes
+        for outcome in &outcomes {
+            if outcome.is_empty() {
+                return Err("Outcome cannot be empty".to_string());
+            }
+            if outcome.len() > 200 {
+                return Err("Outcome too long (max 200 characters)".to_string());
+            }
+        }
+        
+        // Check for duplicate outcomes
+        let mut unique_outcomes = std::collections::HashSet::new();
+        for outcome in &outcomes {
+            if !unique_outcomes.insert(outcome) {
+                return Err(format!("Duplicate outcome: {}", outcome));
+            }
+        }
+        
+        // Validate strategy
+        let valid_strategies = ["Majority", "Median", "WeightedByStake", "WeightedByReputation", "ConfidenceWeightedMedian"];
+        if !valid_strategies.contains(&strategy.as_str()) {
+            return Err(format!(
+                "Invalid strategy '{}'. Valid strategies: {}",
+                strategy,
+                valid_strategies.join(", ")
+            ));
+        }
+        
+        // Validate min_votes if provided
+        if let Some(mv) = min_votes {
+            if mv < 1 {
+                return Err("Minimum votes must be at least 1".to_string());
+            }
+            if mv > 1000 {
+                return Err("Minimum votes too high (max 1000)".to_string());
+            }
+        }
         
-        // Convert phase enum to string
-        let phase = match query.phase {
-            state::VotingPhase::Commit => "Commit",
-            state::VotingPhase::Reveal => "Reveal",
-            state::VotingPhase::Completed => "Completed",
-        }.to_string();
+        // Validate min_distinct_voted_outcomes if provided
+        if let Some(md) = min_distinct_voted_outcomes {
+            if md < 1 {
+                return Err("Minimum distinct voted outcomes must be at least 1".to_string());
+            }
+            if md as usize > outcomes.len() {
+                return Err(format!(
+                    "Minimum distinct voted outcomes ({}) exceeds number of outcomes ({})",
+                    md, outcomes.len()
+                ));
+            }
+        }
         
-        // Convert timestamps to microseconds strings (for JavaScript compatibility)
-        let created_at = query.created_at.micros().to_string();
-        let deadline = query.deadline.micros().to_string();
-        let commit_end = query.commit_phase_end.micros().to_string();
-        let reveal_end = query.reveal_phase_end.micros().to_string();
-        let resolved_at = query.resolved_at.map(|ts| ts.micros().to_string());
+        // Validate min_votes_for_winner if provided (the panel size is checked on execution)
+        if let Some(mw) = min_votes_for_winner {
+            if mw < 1 {
+                return Err("Minimum votes for winner must be at least 1".to_string());
+            }
+        }
         
-        // Calculate time remaining until deadline
-        let time_remaining = if current_time < query.deadline {
-            let delta = query.deadline.delta_since(current_time);
-            (delta.as_micros() / 1_000_000) as i64 // Convert microseconds to seconds
-        } else {
-            0
-        };
+        // Validate voter diversity requirements if provided
+        let min_distinct_voters_value = min_distinct_voters
+            .map(|mv| usize::try_from(mv).map_err(|_| "Minimum distinct voters must be at least 1".to_string()))
+            .transpose()?;
+        let max_group_power_bps_value = max_group_power_bps
+            .map(|bps| u32::try_from(bps).map_err(|_| "Max group power must be between 1 and 10000 basis points".to_string()))
+            .transpose()?;
+        state::validate_voter_diversity(min_distinct_voters_value, max_group_power_bps_value)?;
+        
+        // Validate min_voter_stake if provided (the registry minimum is checked on execution)
+        let min_voter_stake_value = min_voter_stake.as_deref()
+            .map(|stake| stake.parse::<u128>()
+                .map_err(|_| "Invalid minimum voter stake format: must be a valid number".to_string()))
+            .transpose()?;
+        
+        // Validate abstentions can't be confused with an outcome
+        state::validate_abstain_outcomes(allow_abstain, &outcomes)?;
+        
+        // Validate category and tags
+        state::validate_query_labels(&category, &tags)?;
+        
+        // Validate outcome aliases
+        let outcome_aliases = state::parse_outcome_aliases(&outcome_aliases.unwrap_or_default())?;
+        state::validate_outcome_aliases(&outcome_aliases, &outcomes)?;
+        
+        // Validate reward basis (the operation carries the crate's enum, not the local one)
+        let reward_basis_enum = reward_basis.as_deref()
+            .map(oracle_registry_v2::state::RewardBasis::parse)
+            .transpose()?;
+        
+        // Validate tie-break mode
+        let tie_break_enum = tie_break.as_deref()
+            .map(oracle_registry_v2::state::TieBreak::parse)
+            .transpose()?
+            .unwrap_or_default();
         
-        // Count commits (phase 1) and revealed votes (phase 2)
-        let commit_count = query.commits.len();
+        // Validate reward amount
+        let reward_value = reward_amount.parse::<u128>()
+            .map_err(|_| "Invalid reward amount format: must be a valid number".to_string())?;
         
-        Self {
-            id: query.id,
-            description: query.description,
-            outcomes: query.outcomes,
-            strategy,
-            min_votes: query.min_votes as u32,
-            reward_amount: query.reward_amount.to_string(),
-            creator: format!("{:?}", query.creator),
-            created_at,
-            deadline,
-            commit_end,
-            reveal_end,
-            status,
-            phase,
-            result: query.result,
-            resolved_at,
-            commit_count: commit_count as u32,
-            vote_count: vote_count as u32,
-            time_remaining,
-            votes: None, // Votes are populated separately when needed
+        if reward_value == 0 {
+            return Err("Reward amount must be greater than zero".to_string());
         }
-    }
-    
-    /// Convert from state Query to GraphQL Query with votes included
-    fn from_state_query_with_votes(
-        query: state::Query,
-        current_time: linera_sdk::linera_base_types::Timestamp,
-    ) -> Self {
-        let vote_count = query.votes.len();
         
-        // Convert votes to GraphQL format
-        let votes: Vec<QueryVote> = query.votes.iter().map(|(voter, vote)| {
-            QueryVote {
-                voter: format!("{:?}", voter),
-                value: vote.value.clone(),
-                timestamp: vote.timestamp.micros().to_string(),
-                confidence: vote.confidence,
-            }
-        }).collect();
+        // Validate deadline if provided
+        if let Some(ref dl) = deadline {
+            let _deadline_value = dl.parse::<u64>()
+                .map_err(|_| "Invalid deadline format: must be a valid timestamp in microseconds".to_string())?;
+            // Note: Additional validation (deadline in future) will be done by the contract
+        }
         
-        let mut result = Self::from_state_query(query, vote_count, current_time);
-        result.votes = Some(votes);
-        result
-    }
-}
-
-impl Statistics {
-    /// Build Statistics from the current state
-    async fn from_state(state: &OracleRegistryV2) -> Self {
-        // Get basic counts
-        let total_voters = *state.voter_count.get();
-        let total_stake = *state.total_stake.get();
-        let total_queries_created = *state.total_queries_created.get();
-        let total_queries_resolved = *state.total_queries_resolved.get();
-        let total_votes_submitted = *state.total_votes_submitted.get();
-        let total_rewards_distributed = *state.total_rewards_distributed.get();
-        let reward_pool_balance = *state.reward_pool.get();
-        let protocol_treasury = *state.protocol_treasury.get();
-        let is_paused = *state.is_paused.get();
+        // Validate strategy compatibility with outcomes
+        match strategy.as_str() {
+            "Median" | "ConfidenceWeightedMedian" => {
+                // Median strategies require numeric outcomes
+                for outcome in &outcomes {
+                    if outcome.parse::<f64>().is_err() {
+                        return Err(format!(
+                            "{} strategy requires numeric outcomes, but '{}' is not numeric",
+                            strategy, outcome
+                        ));
+                    }
+                }
+            }
+            _ => {
+                // Other strategies work with any outcomes
+            }
+        }
         
-        // Calculate active queries count
-        let active_queries = state.get_active_queries().await;
-        let active_queries_count = active_queries.len() as u64;
+        // Convert strategy string to enum
+        // Note: We need to use the DecisionStrategy from oracle_registry_v2 crate, not local state
+        use oracle_registry_v2::Operation;
+        use oracle_registry_v2::state::DecisionStrategy as LibDecisionStrategy;
         
-        // Calculate average stake per voter
-        let average_stake = if total_voters > 0 {
-            let stake_value: u128 = total_stake.into();
-            linera_sdk::linera_base_types::Amount::from_tokens(stake_value / total_voters as u128)
-        } else {
-            linera_sdk::linera_base_types::Amount::ZERO
+        let strategy_enum = match strategy.as_str() {
+            "Majority" => LibDecisionStrategy::Majority,
+            "Median" => LibDecisionStrategy::Median,
+            "WeightedByStake" => LibDecisionStrategy::WeightedByStake,
+            "WeightedByReputation" => LibDecisionStrategy::WeightedByReputation,
+            "ConfidenceWeightedMedian" => LibDecisionStrategy::ConfidenceWeightedMedian,
+            _ => return Err(format!("Invalid strategy: {}", strategy)),
         };
+        oracle_registry_v2::state::validate_multi_winner(multi_winner, &strategy_enum)?;
+        oracle_registry_v2::state::validate_tie_break(tie_break_enum, &strategy_enum)?;
         
-        // Calculate average votes per query
-        let average_votes_per_query = if total_queries_created > 0 {
-            total_votes_submitted as f64 / total_queries_created as f64
+        // Parse deadline if provided
+        let deadline_ts = if let Some(ref dl) = deadline {
+            let micros = dl.parse::<u64>()
+                .map_err(|_| "Invalid deadline format".to_string())?;
+            Some(linera_sdk::linera_base_types::Timestamp::from(micros))
         } else {
-            0.0
+            None
         };
         
-        // Calculate resolution rate
-        let resolution_rate = if total_queries_created > 0 {
-            (total_queries_resolved as f64 / total_queries_created as f64) * 100.0
-        } else {
-            0.0
+        // Create the operation
+        let operation = Operation::CreateQuery {
+            description: description.clone(),
+            outcomes: outcomes.clone(),
+            strategy: strategy_enum,
+            min_votes: min_votes.map(|v| v as usize),
+            reward_amount: linera_sdk::linera_base_types::Amount::from_tokens(reward_value),
+            deadline: deadline_ts,
+            duration_secs: duration_secs.map(|d| d as u64),
+            min_distinct_voted_outcomes: min_distinct_voted_outcomes.map(|md| md as usize),
+            min_votes_for_winner: min_votes_for_winner.map(|mw| mw as usize),
+            min_distinct_voters: min_distinct_voters_value,
+            max_group_power_bps: max_group_power_bps_value,
+            min_voter_stake: min_voter_stake_value.map(linera_sdk::linera_base_types::Amount::from_tokens),
+            allow_abstain,
+            multi_winner,
+            category: category.clone(),
+            tags: tags.clone(),
+            reward_basis: reward_basis_enum,
+            outcome_aliases: outcome_aliases.clone(),
+            tie_break: tie_break_enum,
         };
         
-        // Calculate average reputation and active voters
-        // Note: This is a simplified version. In production, you'd want to iterate through voters
-        // For now, we'll use placeholder values that should be calculated properly
-        let active_voters = total_voters; // TODO: Count only active voters
-        let average_reputation = 50.0; // TODO: Calculate actual average
-        let total_locked_stake = linera_sdk::linera_base_types::Amount::ZERO; // TODO: Sum locked stakes
+        // Schedule operation - will be executed when block is created
+        self.runtime.schedule_operation(&operation);
         
-        let protocol_status = if is_paused {
-            "Paused".to_string()
-        } else {
-            "Active".to_string()
-        };
+        // Build response
+        let mut response = serde_json::json!({
+            "success": true,
+            "message": "Query creation scheduled",
+            "description": description,
+            "outcomes": outcomes,
+            "strategy": strategy,
+            "reward_amount": reward_amount,
+        });
         
-        Self {
-            total_voters,
-            active_voters,
-            total_stake: total_stake.to_string(),
-            total_locked_stake: total_locked_stake.to_string(),
-            average_stake: average_stake.to_string(),
-            total_queries_created,
-            total_queries_resolved,
-            active_queries_count,
-            total_votes_submitted,
-            average_votes_per_query,
-            total_rewards_distributed: total_rewards_distributed.to_string(),
-            reward_pool_balance: reward_pool_balance.to_string(),
-            protocol_treasury: protocol_treasury.to_string(),
-            average_reputation,
-            protocol_status,
-            resolution_rate,
+        // Add optional fields if provided
+        if let Some(mv) = min_votes {
+            response["min_votes"] = serde_json::json!(mv);
+        }
+        if let Some(dl) = deadline {
+            response["deadline"] = serde_json::json!(dl);
+        }
+        if let Some(ds) = duration_secs {
+            response["duration_secs"] = serde_json::json!(ds);
+        }
+        if let Some(md) = min_distinct_voted_outcomes {
+            response["min_distinct_voted_outcomes"] = serde_json::json!(md);
+        }
+        if let Some(mw) = min_votes_for_winner {
+            response["min_votes_for_winner"] = serde_json::json!(mw);
+        }
+        if let Some(mv) = min_distinct_voters {
+            response["min_distinct_voters"] = serde_json::json!(mv);
+        }
+        if let Some(bps) = max_group_power_bps {
+            response["max_group_power_bps"] = serde_json::json!(bps);
+        }
+        if let Some(ms) = min_voter_stake {
+            response["min_voter_stake"] = serde_json::json!(ms);
+        }
+        if allow_abstain {
+            response["allow_abstain"] = serde_json::json!(true);
+        }
+        if multi_winner {
+            response["multi_winner"] = serde_json::json!(true);
+        }
+        if let Some(cat) = category {
+            response["category"] = serde_json::json!(cat);
+        }
+        if !tags.is_empty() {
+            response["tags"] = serde_json::json!(tags);
+        }
+        if let Some(basis) = reward_basis_enum {
+            response["reward_basis"] = serde_json::json!(basis.as_str());
+        }
+        if !outcome_aliases.is_empty() {
+            response["outcome_aliases"] = serde_json::json!(outcome_aliases);
+        }
+        if tie_break.is_some() {
+            response["tie_break"] = serde_json::json!(tie_break_enum.as_str());
         }
-    }
-}
-
-struct QueryRoot {
-    state: Arc<OracleRegistryV2>,
-}
-
-#[Object]
-impl QueryRoot {
-    /// Get protocol parameters
-    async fn parameters(&self) -> String {
-        format!("{:?}", self.state.get_parameters().await)
-    }
-
-    /// Get total voter count
-    async fn voter_count(&self) -> u64 {
-        *self.state.voter_count.get()
-    }
-
-    /// Get total stake
-    async fn total_stake(&self) -> String {
-        self.state.total_stake.get().to_string()
-    }
-    
-    /// Get voter information by address
-    /// 
-    /// Returns detailed information about a specific voter including their stake,
-    /// reputation, voting history, and activity status.
-    /// 
-    /// # Arguments
-    /// * `address` - The voter's account address as a hex string (e.g., "0x1234...")
-    /// 
-    /// # Returns
-    /// Voter object with all voter information, or None if voter not found
-    /// 
-    /// # Example
-    /// ```graphql
-    /// query {
-    ///   voter(address: "0x1234...") {
-    ///     address
-    ///     stake
-    ///     lockedStake
-    ///     availableStake
-    ///     reputation
-    ///     reputationTier
-    ///     reputationWeight
-    ///     totalVotes
-    ///     correctVotes
-    ///     accuracyPercentage
-    ///     registeredAt
-    ///     isActive
-    ///     name
-    ///     metadataUrl
-    ///   }
-    /// }
-    /// ```
-    async fn voter(&self, address: String) -> Result<Option<Voter>, String> {
-        // Parse the address string to ChainId
-        let chain_id = address.parse::<linera_sdk::linera_base_types::ChainId>()
-            .map_err(|e| format!("Invalid chain ID format: {}", e))?;
-        
-        // Get voter info from state
-        let voter_info = match self.state.get_voter(&chain_id).await {
-            Some(info) => info,
-            None => return Ok(None), // Voter not found
-        };
-        
-        // Get available stake (total stake - locked stake)
-        let available_stake = self.state.get_available_stake(&chain_id).await;
-        
-        // Get pending rewards
-        let pending_rewards = self.state.get_pending_rewards(&chain_id).await;
-        
-        // Convert to GraphQL Voter type
-        let voter = Voter::from_voter_info(voter_info, available_stake, pending_rewards, &self.state);
         
-        Ok(Some(voter))
+        Ok(response.to_string())
     }
     
-    /// Get all registered voters
+    /// Submit a vote for a query
     /// 
-    /// Returns a list of all voters registered in the system, including both
-    /// active and inactive voters. This query is useful for displaying voter
-    /// directories, leaderboards, and analytics.
+    /// This mutation provides information about the SubmitVote operation.
+    /// To execute the vote submission, call the SubmitVote operation on the contract.
     /// 
     /// # Arguments
-    /// * `limit` - Optional maximum number of voters to return (default: 100, max: 1000)
-    /// * `offset` - Optional number of voters to skip for pagination (default: 0)
-    /// * `active_only` - Optional filter to return only active voters (default: false)
+    /// * `query_id` - ID of the query to vote on
+    /// * `value` - The vote value (must be one of the query's valid outcomes)
+    /// * `confidence` - Optional confidence score (0-100) indicating how confident the voter is in their answer
     /// 
     /// # Returns
-    /// List of Voter objects with all voter information
+    /// JSON string with operation details for executing the vote submission
     /// 
     /// # Example
     /// ```graphql
-    /// query {
-    ///   voters(limit: 50, offset: 0, activeOnly: true) {
-    ///     address
-    ///     stake
-    ///     reputation
-    ///     reputationTier
-    ///     totalVotes
-    ///     accuracyPercentage
-    ///     isActive
-    ///     name
-    ///   }
+    /// mutation {
+    ///   submitVote(
+    ///     queryId: 1,
+    ///     value: "Yes",
+    ///     confidence: 85
+    ///   )
     /// }
     /// ```
-    async fn voters(
+    /// 
+    /// Returns:
+    /// ```json
+    /// {
+    ///   "operation": "SubmitVote",
+    ///   "query_id": 1,
+    ///   "value": "Yes",
+    ///   "confidence": 85,
+    ///   "instructions": "Call the SubmitVote operation with these parameters"
+    /// }
+    /// ```
+    async fn submit_vote(
         &self,
-        limit: Option<i32>,
-        offset: Option<i32>,
-        active_only: Option<bool>,
-    ) -> Result<Vec<Voter>, String> {
-        // Validate and set defaults for pagination parameters
-        let limit = match limit {
-            Some(l) => {
-                if l < 1 {
-                    return Err("Limit must be at least 1".to_string());
-                }
-                if l > 1000 {
-                    return Err("Limit cannot exceed 1000".to_string());
-                }
-                l as usize
-            }
-            None => 100, // Default limit
-        };
+        query_id: u64,
+        value: String,
+        confidence: Option<i32>,
+    ) -> Result<String, String> {
+        // Validate query_id
+        if query_id == 0 {
+            return Err("Query ID must be greater than zero".to_string());
+        }
         
-        let offset = match offset {
-            Some(o) => {
-                if o < 0 {
-                    return Err("Offset cannot be negative".to_string());
-                }
-                o as usize
+        // Validate value is not empty
+        if value.is_empty() {
+            return Err("Vote value cannot be empty".to_string());
+        }
+        if value.len() > 200 {
+            return Err("Vote value too long (max 200 characters)".to_string());
+        }
+        
+        // Validate confidence if provided
+        let confidence_u8 = if let Some(conf) = confidence {
+            if conf < 0 || conf > 100 {
+                return Err("Confidence must be between 0 and 100".to_string());
             }
-            None => 0, // Default offset
+            Some(conf as u8)
+        } else {
+            None
         };
         
-        let active_only = active_only.unwrap_or(false);
-        
-        // Collect all voters from the MapView
-        let mut voters = Vec::new();
-        let mut count = 0;
-        let mut skipped = 0;
-        
-        // Iterate through all voters in the MapView
-        // Note: This uses the indices() method to get all keys, then fetches each voter
-        let voter_indices = self.state.voters.indices().await
-            .map_err(|e| format!("Failed to get voter indices: {}", e))?;
+        // Build response with operation details
+        let mut response = serde_json::json!({
+            "operation": "SubmitVote",
+            "query_id": query_id,
+            "value": value,
+            "instructions": "To execute this operation, use the backend API or Linera CLI",
+            "requirements": [
+                "Voter must be registered and active",
+                "Query must exist and be in Active status",
+                "Query deadline must not have passed",
+                "Voter must not have already voted on this query",
+                "Vote value must be one of the query's valid outcomes"
+            ]
+        });
         
-        for address in voter_indices {
-            // Skip voters until we reach the offset
-            if skipped < offset {
-                skipped += 1;
-                continue;
-            }
-            
-            // Stop if we've collected enough voters
-            if count >= limit {
-                break;
-            }
-            
-            // Get voter info
-            let voter_info = match self.state.get_voter(&address).await {
-                Some(info) => info,
-                None => continue, // Skip if voter not found (shouldn't happen)
-            };
-            
-            // Apply active_only filter
-            if active_only && !voter_info.is_active {
-                continue;
-            }
-            
-            // Get available stake
-            let available_stake = self.state.get_available_stake(&address).await;
-            
-            // Get pending rewards
-            let pending_rewards = self.state.get_pending_rewards(&address).await;
-            
-            // Convert to GraphQL Voter type
-            let voter = Voter::from_voter_info(voter_info, available_stake, pending_rewards, &self.state);
-            voters.push(voter);
-            count += 1;
+        // Add confidence if provided
+        if let Some(conf) = confidence_u8 {
+            response["confidence"] = serde_json::json!(conf);
         }
         
-        Ok(voters)
+        Ok(response.to_string())
     }
     
-    /// Get the current user's voter information
-    /// 
-    /// This is a convenience query that returns voter information for a specific address.
-    /// Since GraphQL services in Linera don't have authentication context, the client
-    /// must provide their address (which they know from their wallet).
+    /// Resolve a query after deadline has passed and minimum votes are met
     /// 
-    /// This query is functionally equivalent to `voter(address)` but provides a more
-    /// intuitive API for clients to query their own information.
+    /// This mutation provides information about the ResolveQuery operation.
+    /// To execute the query resolution, call the ResolveQuery operation on the contract.
     /// 
     /// # Arguments
-    /// * `address` - The voter's account address as a hex string (e.g., "0x1234...")
-    ///               This should be the address from the user's wallet/account
+    /// * `query_id` - ID of the query to resolve
     /// 
     /// # Returns
-    /// Voter object with all voter information, or None if voter not found
+    /// JSON string with operation details for executing the query resolution
     /// 
     /// # Example
     /// ```graphql
-    /// query {
-    ///   myVoterInfo(address: "0x1234...") {
-    ///     address
-    ///     stake
-    ///     lockedStake
-    ///     availableStake
-    ///     reputation
-    ///     reputationTier
-    ///     reputationWeight
-    ///     totalVotes
-    ///     correctVotes
-    ///     accuracyPercentage
-    ///     registeredAt
-    ///     isActive
-    ///     name
-    ///     metadataUrl
-    ///   }
+    /// mutation {
+    ///   resolveQuery(queryId: 1)
     /// }
     /// ```
     /// 
-    /// # Note
-    /// In a typical web3 application, the client (dashboard/frontend) knows the user's
-    /// address from their connected wallet. The client should pass this address to this
-    /// query to retrieve the user's voter information.
-    async fn my_voter_info(&self, address: String) -> Result<Option<Voter>, String> {
-        // Duplicate logic from voter() since we can't call it directly without context
-        let chain_id = address.parse::<linera_sdk::linera_base_types::ChainId>()
-            .map_err(|e| format!("Invalid chain ID format: {}", e))?;
-        
-        let voter_info = match self.state.get_voter(&chain_id).await {
-            Some(info) => info,
-            None => return Ok(None),
-        };
-        
-        let available_stake = self.state.get_available_stake(&chain_id).await;
-        let pending_rewards = self.state.get_pending_rewards(&chain_id).await;
-        let voter = Voter::from_voter_info(voter_info, available_stake, pending_rewards, &self.state);
-        
-        Ok(Some(voter))
-    }
-    
-    /// Get all queries
-    async fn queries(&self) -> Result<Vec<Query>, String> {
-        let mut queries = Vec::new();
-        
-        // Get all query IDs
-        let query_indices = self.state.queries.indices().await
-            .map_err(|e| format!("Failed to get query indices: {}", e))?;
-        
-        // Use timestamp 0 for time_remaining calculation (frontend will calculate)
-        let current_time = linera_sdk::linera_base_types::Timestamp::from(0);
-        
-        for query_id in query_indices {
-            if let Some(query) = self.state.get_query(query_id).await {
-                let vote_count = query.votes.len();
-                let graphql_query = Query::from_state_query(query, vote_count, current_time);
-                queries.push(graphql_query);
-            }
+    /// Returns:
+    /// ```json
+    /// {
+    ///   "operation": "ResolveQuery",
+    ///   "query_id": 1,
+    ///   "instructions": "Call the ResolveQuery operation with the query ID"
+    /// }
+    /// ```
+    async fn resolve_query(&self, query_id: u64) -> Result<String, String> {
+        // Validate query_id
+        if query_id == 0 {
+            return Err("Query ID must be greater than zero".to_string());
         }
         
-        Ok(queries)
-    }
-    
-    /// Get a specific query by ID
-    async fn query(&self, id: u64) -> Result<Option<Query>, String> {
-        let query = match self.state.get_query(id).await {
-            Some(q) => q,
-            None => return Ok(None),
-        };
-        
-        // Use timestamp 0 for time_remaining calculation (frontend will calculate)
-        let current_time = linera_sdk::linera_base_types::Timestamp::from(0);
-        
-        let vote_count = query.votes.len();
-        let graphql_query = Query::from_state_query(query, vote_count, current_time);
+        // Build response with operation details
+        let response = serde_json::json!({
+            "operation": "ResolveQuery",
+            "query_id": query_id,
+            "instructions": "Call the ResolveQuery operation on the contract with this query ID",
+            "requirements": [
+                "Query must exist and be in Active status",
+                "Query deadline must have passed",
+                "Query must have at least the minimum required votes",
+                "Query must not already be resolved or expired",
+                "Can be called by anyone (not restricted to query creator or admin)"
+            ],
+            "resolution_process": [
+                "1. Validate query exists and is in Active status",
+                "2. Check that deadline has passed",
+                "3. Verify minimum votes requirement is met",
+                "4. Calculate result based on the query's decision strategy:",
+                "   - Majority: Most common vote value wins",
+                "   - Median: Median of numeric votes (for numeric outcomes)",
+                "   - WeightedByStake: Votes weighted by voter stake amounts",
+                "   - WeightedByReputation: Votes weighted by voter reputation scores",
+                "   - ConfidenceWeightedMedian: Median of numeric votes weighted by vote confidence",
+                "5. Update query status to Resolved with the calculated result",
+                "6. Unlock stake for all voters who participated",
+                "7. Update voter reputations based on vote correctness",
+                "8. Distribute rewards to voters who voted correctly",
+                "9. Apply slashing to voters who voted incorrectly",
+                "10. Update protocol statistics and treasury"
+            ],
+            "effects": [
+                "Query status will be changed to Resolved",
+                "Query result will be set to the calculated outcome",
+                "Query will be removed from active queries list",
+                "All voter stakes locked for this query will be unlocked",
+                "Correct voters will have rewards added to their pending rewards",
+                "Incorrect voters will have a portion of their stake slashed",
+                "Voters with stake below minimum after slashing will be deactivated",
+                "Voter reputations will be updated based on vote correctness",
+                "Protocol treasury will receive fees and slashed amounts",
+                "Total queries resolved counter will be incremented"
+            ],
+            "reward_distribution": [
+                "Rewards are distributed only to voters who voted for the winning outcome",
+                "Distribution method depends on the query's decision strategy:",
+                "- Equal distribution: Rewards split equally (for Majority/Median)",
+                "- Stake-weighted: Rewards proportional to stake (for WeightedByStake)",
+                "- Reputation-weighted: Rewards proportional to reputation (for WeightedByReputation)",
+                "Each voter's reward is adjusted by their reputation multiplier",
+                "Protocol fee is deducted from rewards before distribution",
+                "Rewards are added to pending_rewards and can be claimed later"
+            ],
+            "slashing": [
+                "Voters who voted incorrectly have stake slashed based on slash_percentage",
+                "Slashed amounts are transferred to the protocol treasury",
+                "If remaining stake falls below minimum, voter is automatically deactivated",
+                "Slashing helps ensure voters are incentivized to vote accurately",
+                "Slashing percentage is configurable via protocol parameters"
+            ],
+            "expiration_handling": [
+                "If deadline has passed but minimum votes not met, query expires instead",
+                "Expired queries do not distribute rewards or apply slashing",
+                "Voters on expired queries have their stake unlocked without penalty",
+                "Query creator's reward amount is returned (not distributed)"
+            ]
+        });
         
-        Ok(Some(graphql_query))
+        Ok(response.to_string())
     }
     
-    /// Get a specific query by ID with all votes included
-    /// 
-    /// This query returns the full query details including all votes cast by voters.
-    /// Useful for displaying vote breakdown on resolved queries.
+    /// Claim pending rewards
     /// 
-    /// # Arguments
-    /// * `id` - The query ID
+    /// This mutation provides information about the ClaimRewards operation.
+    /// To execute the reward claim, call the ClaimRewards operation on the contract.
     /// 
     /// # Returns
-    /// Query object with votes array populated
+    /// JSON string with operation details for executing the reward claim
     /// 
     /// # Example
     /// ```graphql
-    /// query {
-    ///   queryWithVotes(id: 1) {
-    ///     id
-    ///     description
-    ///     status
-    ///     result
-    ///     votes {
-    ///       voter
-    ///       value
-    ///       timestamp
-    ///       confidence
-    ///     }
-    ///   }
+    /// mutation {
+    ///   claimRewards
     /// }
     /// ```
-    async fn query_with_votes(&self, id: u64) -> Result<Option<Query>, String> {
-        let query = match self.state.get_query(id).await {
-            Some(q) => q,
-            None => return Ok(None),
-        };
-        
-        // Use timestamp 0 for time_remaining calculation (frontend will calculate)
-        let current_time = linera_sdk::linera_base_types::Timestamp::from(0);
-        
-        let graphql_query = Query::from_state_query_with_votes(query, current_time);
-        
-        Ok(Some(graphql_query))
-    }
+    /// 
+    /// Returns:
+    /// ```json
+    /// {
+    ///   "operation": "ClaimRewards",
+    ///   "instructions": "Call the ClaimRewards operation on the contract"
+    /// }
+    /// ```
+    // Removed old claim_rewards - replaced with proper implementation below
     
-    /// Get statistics
-    async fn statistics(&self) -> Result<Statistics, String> {
-        Ok(Statistics::from_state(&self.state).await)
-    }
-
-}
-
-struct MutationRoot {
-    runtime: Arc<ServiceRuntime<OracleRegistryV2Service>>,
-}
-
-#[Object]
-impl MutationRoot {
-    /// Register as a new voter
+    /// Execute voter registration (ACTUALLY EXECUTES THE OPERATION!)
     /// 
-    /// This mutation provides information about the RegisterVoter operation.
-    /// To execute the registration, call the RegisterVoter operation on the contract.
+    /// This mutation schedules a RegisterVoter operation to be executed by the contract.
+    /// Unlike the `registerVoter` mutation which only returns instructions,
+    /// this mutation actually executes the operation.
     /// 
     /// # Arguments
     /// * `stake` - Initial stake amount (in tokens as string)
     /// * `name` - Optional voter name
     /// * `metadata_url` - Optional URL to voter metadata
+    /// * `referral` - Optional referral/attribution code (letters, digits, '-', '_'; max 32)
+    /// * `encrypted_metadata` - Optional hex blob encrypted off-chain (max 1024 bytes)
     /// 
     /// # Returns
-    /// Empty array (operation is scheduled for execution)
+    /// `true` if operation was scheduled successfully
     /// 
     /// # Example
     /// ```graphql
     /// mutation {
-    ///   registerVoter(stake: "1000", name: "Alice")
+    ///   executeRegisterVoter(stake: "1000", name: "Alice")
     /// }
     /// ```
-    /// 
-    /// # Note
-    /// Chain ID is automatically detected using runtime.chain_id()
-    /// No address parameter needed! (Microcard pattern)
-    async fn register_voter(
+    async fn execute_register_voter(
         &self,
+        voter_address: String,
         stake: String,
         name: Option<String>,
         metadata_url: Option<String>,
-    ) -> String {
+        referral: Option<String>,
+        encrypted_metadata: Option<String>,
+    ) -> Result<bool, String> {
         use oracle_registry_v2::Operation;
         
+        // Validate address
+        if voter_address.is_empty() {
+            return Err("Voter address is required".to_string());
+        }
+        
         // Parse stake as Amount directly (expects format like "100." with trailing dot)
-        // This is consistent with how alethea-token handles amounts
-        let stake_amount: Amount = match stake.parse() {
-            Ok(v) => v,
-            Err(_) => return "Error: Invalid stake format. Use format like '100.' with trailing dot".to_string(),
-        };
+        let stake_amount: Amount = stake.parse()
+            .map_err(|_| "Invalid stake format. Use format like '100.' with trailing dot".to_string())?;
         
-        // Create RegisterVoter operation
+        if let Some(ref code) = referral {
+            state::normalize_referral_code(code)?;
+        }
+        
+        let encrypted_metadata = encrypted_metadata.as_deref().map(parse_encrypted_metadata).transpose()?;
+        
+        // Create operation (chain_id is automatically detected by contract)
         let operation = Operation::RegisterVoter {
             stake: stake_amount,
             name,
             metadata_url,
+            referral,
+            encrypted_metadata,
         };
         
-        // Schedule operation - will be executed when block is created
+        // Schedule operation to be executed by contract
         self.runtime.schedule_operation(&operation);
         
-        // Return success message
-        format!("Voter registration scheduled with stake: {}", stake)
+        Ok(true)
     }
-
-    /// Update stake by adding additional stake to voter account
+    
+    /// Execute vote submission (ACTUALLY EXECUTES THE OPERATION!)
     /// 
-    /// This mutation provides information about the UpdateStake operation.
-    /// To execute the stake update, call the UpdateStake operation on the contract.
+    /// This mutation schedules a SubmitVote operation to be executed by the contract.
     /// 
     /// # Arguments
-    /// * `additional_stake` - Additional amount to add to stake (in tokens as string)
+    /// * `query_id` - ID of the query to vote on
+    /// * `value` - Vote value (must be one of the query's outcomes)
+    /// * `confidence` - Optional confidence score (0-100)
     /// 
     /// # Returns
-    /// JSON string with operation details for executing the stake update
-    /// 
-    /// # Example
-    /// ```graphql
-    /// mutation {
-    ///   updateStake(additionalStake: "1000000")
-    /// }
-    /// ```
-    /// 
-    /// Returns:
-    /// ```json
-    /// {
-    ///   "operation": "UpdateStake",
-    ///   "additional_stake": "1000000",
-    ///   "instructions": "Call the UpdateStake operation with the specified amount"
-    /// }
-    /// ```
-    async fn update_stake(&self, additional_stake: String) -> Result<String, String> {
-        // Validate amount format
-        let stake_value = additional_stake.parse::<u128>()
-            .map_err(|_| "Invalid amount format: must be a valid number".to_string())?;
+    /// `true` if operation was scheduled successfully
+    async fn execute_submit_vote(
+        &self,
+        query_id: u64,
+        value: String,
+        confidence: Option<i32>,
+    ) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
         
-        if stake_value == 0 {
-            return Err("Additional stake must be greater than zero".to_string());
+        // Validate
+        if query_id == 0 {
+            return Err("Query ID must be greater than zero".to_string());
         }
         
-        // Return operation details as JSON
-        let response = serde_json::json!({
-            "operation": "UpdateStake",
-            "additional_stake": additional_stake,
-            "instructions": "Call the UpdateStake operation on the contract with this amount",
-            "requirements": [
-                "Voter must be registered and active",
-                "Additional stake amount must be greater than zero",
-                "Voter must have sufficient balance to add the stake"
-            ]
-        });
+        if value.is_empty() {
+            return Err("Vote value cannot be empty".to_string());
+        }
+        
+        let confidence_u8 = confidence.map(|c| {
+            if c < 0 || c > 100 {
+                return Err("Confidence must be between 0 and 100".to_string());
+            }
+            Ok(c as u8)
+        }).transpose()?;
+        
+        // Create operation
+        let operation = Operation::SubmitVote {
+            query_id,
+            value,
+            confidence: confidence_u8,
+        };
+        
+        // Schedule operation to be executed by contract
+        self.runtime.schedule_operation(&operation);
+        
+        Ok(true)
+    }
+    
+    /// Execute stake update (ACTUALLY EXECUTES THE OPERATION!)
+    async fn execute_update_stake(
+        &self,
+        additional_stake: String,
+    ) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
+        
+        // Parse stake as Amount directly (expects format like "100." with trailing dot)
+        let stake_amount: Amount = additional_stake.parse()
+            .map_err(|_| "Invalid stake format. Use format like '100.' with trailing dot".to_string())?;
+        
+        if stake_amount == Amount::ZERO {
+            return Err("Additional stake must be greater than 0".to_string());
+        }
         
-        Ok(response.to_string())
+        let operation = Operation::UpdateStake {
+            additional_stake: stake_amount,
+        };
+        
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
     }
     
-    /// Withdraw stake from voter account
-    /// 
-    /// This mutation provides information about the WithdrawStake operation.
-    /// To execute the withdrawal, call the WithdrawStake operation on the contract.
-    /// 
-    /// # Arguments
-    /// * `amount` - Amount to withdraw (in tokens as string)
+    /// Execute EmergencyWithdraw operation
     /// 
-    /// # Returns
-    /// JSON string with operation details for executing the withdrawal
+    /// Withdraws stake even while it is locked on active queries. The voter
+    /// leaves those queries and the `emergencyUnstakePenaltyBps` share of the
+    /// amount goes to the protocol treasury.
     /// 
     /// # Example
     /// ```graphql
     /// mutation {
-    ///   withdrawStake(amount: "1000000")
-    /// }
-    /// ```
-    /// 
-    /// Returns:
-    /// ```json
-    /// {
-    ///   "operation": "WithdrawStake",
-    ///   "amount": "1000000",
-    ///   "instructions": "Call the WithdrawStake operation with the specified amount"
+    ///   executeEmergencyWithdraw(amount: "100.")
     /// }
     /// ```
-    async fn withdraw_stake(&self, amount: String) -> Result<String, String> {
-        // Validate amount format
-        let amount_value = amount.parse::<u128>()
-            .map_err(|_| "Invalid amount format: must be a valid number".to_string())?;
+    async fn execute_emergency_withdraw(&self, amount: String) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
         
-        if amount_value == 0 {
-            return Err("Withdrawal amount must be greater than zero".to_string());
+        let amount: Amount = amount.parse()
+            .map_err(|_| "Invalid amount format. Use format like '100.' with trailing dot".to_string())?;
+        
+        if amount == Amount::ZERO {
+            return Err("Withdrawal amount must be greater than 0".to_string());
         }
         
-        // Return operation details as JSON
-        let response = serde_json::json!({
-            "operation": "WithdrawStake",
-            "amount": amount,
-            "instructions": "Call the WithdrawStake operation on the contract with this amount",
-            "requirements": [
-                "Voter must be registered and active",
-                "Amount must not exceed available stake (total stake - locked stake)",
-                "Voter must not have active votes on any queries",
-                "Remaining stake must meet minimum requirement or be zero"
-            ]
-        });
+        let operation = Operation::EmergencyWithdraw { amount };
         
-        Ok(response.to_string())
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
     }
     
-    /// Deregister as a voter and return all stake
-    /// 
-    /// This mutation provides information about the DeregisterVoter operation.
-    /// To execute the deregistration, call the DeregisterVoter operation on the contract.
+    /// Execute ForfeitAndExit operation
     /// 
-    /// # Returns
-    /// JSON string with operation details for executing the deregistration
+    /// Deregisters immediately, even while on active panels. The voter's
+    /// votes on active queries are dropped, all locked stake goes to the
+    /// protocol treasury and the rest of the stake is returned.
     /// 
     /// # Example
     /// ```graphql
     /// mutation {
-    ///   deregisterVoter
-    /// }
-    /// ```
-    /// 
-    /// Returns:
-    /// ```json
-    /// {
-    ///   "operation": "DeregisterVoter",
-    ///   "instructions": "Call the DeregisterVoter operation on the contract"
+    ///   executeForfeitAndExit
     /// }
     /// ```
-    async fn deregister_voter(&self) -> Result<String, String> {
-        // Return operation details as JSON
-        let response = serde_json::json!({
-            "operation": "DeregisterVoter",
-            "instructions": "Call the DeregisterVoter operation on the contract to deregister and return all stake",
-            "requirements": [
-                "Voter must be registered and active",
-                "Voter must not have any pending rewards (claim them first)",
-                "Voter must not have active votes on any queries",
-                "All stake will be returned to the voter"
-            ],
-            "effects": [
-                "Voter will be removed from the registry",
-                "All stake will be returned to the voter's account",
-                "Voter count will be decremented",
-                "Total stake will be reduced by the voter's stake amount"
-            ]
-        });
+    async fn execute_forfeit_and_exit(&self) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
         
-        Ok(response.to_string())
+        let operation = Operation::ForfeitAndExit;
+        
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
     }
     
-    /// Create a new query/market
-    /// 
-    /// This mutation provides information about the CreateQuery operation.
-    /// To execute the query creation, call the CreateQuery operation on the contract.
+    /// Execute rewards claim (ACTUALLY EXECUTES THE OPERATION!)
+    async fn execute_claim_rewards(&self) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
+        
+        let operation = Operation::ClaimRewards;
+        
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
+    
+    /// Execute rewards claim paid to another chain
     /// 
     /// # Arguments
-    /// * `description` - Description of the query/question
-    /// * `outcomes` - List of possible outcomes (e.g., ["Yes", "No"] or ["Option A", "Option B", "Option C"])
-    /// * `strategy` - Decision strategy: "Majority", "Median", "WeightedByStake", or "WeightedByReputation"
-    /// * `min_votes` - Optional minimum votes required (uses protocol default if not specified)
-    /// * `reward_amount` - Reward amount for correct voters (in tokens as string)
-    /// * `deadline` - Optional deadline timestamp in microseconds (uses protocol default duration if not specified)
-    /// * `duration_secs` - Optional custom duration in seconds (overrides default_query_duration)
-    ///                     This sets total duration, split 50/50 between commit and reveal phases
-    ///                     Example: 120 = 60s commit + 60s reveal
-    /// 
-    /// # Returns
-    /// JSON string with operation details for executing the query creation
+    /// * `target_chain` - Chain ID whose account receives the rewards
     /// 
     /// # Example
     /// ```graphql
     /// mutation {
-    ///   createQuery(
-    ///     description: "Will it rain tomorrow?",
-    ///     outcomes: ["Yes", "No"],
-    ///     strategy: "Majority",
-    ///     rewardAmount: "1000000",
-    ///     durationSecs: 120
-    ///   )
-    /// }
-    /// ```
-    /// 
-    /// Returns:
-    /// ```json
-    /// {
-    ///   "operation": "CreateQuery",
-    ///   "description": "Will it rain tomorrow?",
-    ///   "outcomes": ["Yes", "No"],
-    ///   "strategy": "Majority",
-    ///   "reward_amount": "1000000",
-    ///   "duration_secs": 120,
-    ///   "instructions": "Call the CreateQuery operation with these parameters"
+    ///   executeClaimRewardsTo(targetChain: "e476...")
     /// }
     /// ```
-    async fn create_query(
-        &self,
-        description: String,
-        outcomes: Vec<String>,
-        strategy: String,
-        min_votes: Option<i32>,
-        reward_amount: String,
-        deadline: Option<String>,
-        duration_secs: Option<i32>,
-    ) -> Result<String, String> {
-        // Validate description
-        if description.is_empty() {
-            return Err("Description cannot be empty".to_string());
-        }
-        if description.len() > 1000 {
-            return Err("Description too long (max 1000 characters)".to_string());
-        }
-        
-        // Validate outcomes
-        if outcomes.is_empty() {
-            return Err("At least one outcome must be provided".to_string());
-        }
-        if outcomes.len() > 100 {
-            return Err("Too many outcomes (max 100)".to_string());
-        }
-        
-        // Check for empty outcomes
-        for outcome in &outcomes {
-            if outcome.is_empty() {
-                return Err("Outcome cannot be empty".to_string());
-            }
-            if outcome.len() > 200 {
-                return Err("Outcome too long (max 200 characters)".to_string());
-            }
-        }
-        
-        // Check for duplicate outcomes
-        let mut unique_outcomes = std::collections::HashSet::new();
-        for outcome in &outcomes {
-            if !unique_outcomes.insert(outcome) {
-                return Err(format!("Duplicate outcome: {}", outcome));
-            }
-        }
-        
-        // Validate strategy
-        let valid_strategies = ["Majority", "Median", "WeightedByStake", "WeightedByReputation"];
-        if !valid_strategies.contains(&strategy.as_str()) {
-            return Err(format!(
-                "Invalid strategy '{}'. Valid strategies: {}",
-                strategy,
-                valid_strategies.join(", ")
-            ));
-        }
+    async fn execute_claim_rewards_to(&self, target_chain: String) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
         
-        // Validate min_votes if provided
-        if let Some(mv) = min_votes {
-            if mv < 1 {
-                return Err("Minimum votes must be at least 1".to_string());
-            }
-            if mv > 1000 {
-                return Err("Minimum votes too high (max 1000)".to_string());
-            }
-        }
+        let target_chain = state::parse_chain_id(&target_chain)
+            .map_err(|e| format!("Invalid chain ID: {}", e))?;
         
-        // Validate reward amount
-        let reward_value = reward_amount.parse::<u128>()
-            .map_err(|_| "Invalid reward amount format: must be a valid number".to_string())?;
+        let operation = Operation::ClaimRewardsTo { target_chain };
         
-        if reward_value == 0 {
-            return Err("Reward amount must be greater than zero".to_string());
-        }
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
+    
+    /// Replace this voter's encrypted metadata blob
+    /// 
+    /// The blob is hex-encoded data the voter encrypted off-chain (max 1024
+    /// bytes once decoded); the contract stores it without reading it. Omit
+    /// `encrypted_metadata` to clear it.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeSetEncryptedMetadata(encryptedMetadata: "0x8f3a...")
+    /// }
+    /// ```
+    async fn execute_set_encrypted_metadata(&self, encrypted_metadata: Option<String>) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
         
-        // Validate deadline if provided
-        if let Some(ref dl) = deadline {
-            let _deadline_value = dl.parse::<u64>()
-                .map_err(|_| "Invalid deadline format: must be a valid timestamp in microseconds".to_string())?;
-            // Note: Additional validation (deadline in future) will be done by the contract
-        }
+        let encrypted_metadata = encrypted_metadata.as_deref().map(parse_encrypted_metadata).transpose()?;
         
-        // Validate strategy compatibility with outcomes
-        match strategy.as_str() {
-            "Median" => {
-                // Median strategy requires numeric outcomes
-                for outcome in &outcomes {
-                    if outcome.parse::<f64>().is_err() {
-                        return Err(format!(
-                            "Median strategy requires numeric outcomes, but '{}' is not numeric",
-                            outcome
-                        ));
-                    }
-                }
-            }
-            _ => {
-                // Other strategies work with any outcomes
-            }
-        }
+        let operation = Operation::SetEncryptedMetadata { encrypted_metadata };
         
-        // Convert strategy string to enum
-        // Note: We need to use the DecisionStrategy from oracle_registry_v2 crate, not local state
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
+    
+    /// Allow another chain to claim this voter's rewards with BatchClaimFor
+    /// 
+    /// Rewards are still paid to the voter's own chain. Omit `operator` to
+    /// revoke.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeSetClaimOperator(operator: "e476...")
+    /// }
+    /// ```
+    async fn execute_set_claim_operator(&self, operator: Option<String>) -> Result<bool, String> {
         use oracle_registry_v2::Operation;
-        use oracle_registry_v2::state::DecisionStrategy as LibDecisionStrategy;
-        
-        let strategy_enum = match strategy.as_str() {
-            "Majority" => LibDecisionStrategy::Majority,
-            "Median" => LibDecisionStrategy::Median,
-            "WeightedByStake" => LibDecisionStrategy::WeightedByStake,
-            "WeightedByReputation" => LibDecisionStrategy::WeightedByReputation,
-            _ => return Err(format!("Invalid strategy: {}", strategy)),
-        };
         
-        // Parse deadline if provided
-        let deadline_ts = if let Some(ref dl) = deadline {
-            let micros = dl.parse::<u64>()
-                .map_err(|_| "Invalid deadline format".to_string())?;
-            Some(linera_sdk::linera_base_types::Timestamp::from(micros))
-        } else {
-            None
-        };
+        let operator = operator
+            .map(|address| state::parse_chain_id(&address))
+            .transpose()
+            .map_err(|e| format!("Invalid operator chain ID: {}", e))?;
         
-        // Create the operation
-        let operation = Operation::CreateQuery {
-            description: description.clone(),
-            outcomes: outcomes.clone(),
-            strategy: strategy_enum,
-            min_votes: min_votes.map(|v| v as usize),
-            reward_amount: linera_sdk::linera_base_types::Amount::from_tokens(reward_value),
-            deadline: deadline_ts,
-            duration_secs: duration_secs.map(|d| d as u64),
-        };
+        let operation = Operation::SetClaimOperator { operator };
         
-        // Schedule operation - will be executed when block is created
         self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
+    
+    /// Claim the pending rewards of several voters, each paid to their own chain
+    /// 
+    /// The calling chain must be each voter's claim operator (or admin);
+    /// per-voter results are returned in the operation response.
+    /// 
+    /// # Arguments
+    /// * `voters` - Voter chain IDs (1 to 50, no duplicates)
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeBatchClaimFor(voters: ["e476...", "a1b2..."])
+    /// }
+    /// ```
+    async fn execute_batch_claim_for(&self, voters: Vec<String>) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
         
-        // Build response
-        let mut response = serde_json::json!({
-            "success": true,
-            "message": "Query creation scheduled",
-            "description": description,
-            "outcomes": outcomes,
-            "strategy": strategy,
-            "reward_amount": reward_amount,
-        });
+        let voters = voters.iter()
+            .map(|address| state::parse_chain_id(address).map_err(|e| format!("Invalid voter chain ID '{}': {}", address, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        state::validate_batch_claim(&voters)?;
         
-        // Add optional fields if provided
-        if let Some(mv) = min_votes {
-            response["min_votes"] = serde_json::json!(mv);
-        }
-        if let Some(dl) = deadline {
-            response["deadline"] = serde_json::json!(dl);
-        }
-        if let Some(ds) = duration_secs {
-            response["duration_secs"] = serde_json::json!(ds);
-        }
+        let operation = Operation::BatchClaimFor { voters };
         
-        Ok(response.to_string())
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
     }
     
-    /// Submit a vote for a query
+    /// Execute RegisterVoterFor operation (ADMIN OPERATION FOR TESTING!)
     /// 
-    /// This mutation provides information about the SubmitVote operation.
-    /// To execute the vote submission, call the SubmitVote operation on the contract.
+    /// This mutation allows registering a voter by specifying their address.
+    /// Useful for testing and initial setup without requiring cross-chain messages.
     /// 
     /// # Arguments
-    /// * `query_id` - ID of the query to vote on
-    /// * `value` - The vote value (must be one of the query's valid outcomes)
-    /// * `confidence` - Optional confidence score (0-100) indicating how confident the voter is in their answer
+    /// * `voter_address` - The voter's account address as hex string (e.g., "0xfb3d8fcd...")
+    /// * `stake` - Initial stake amount (in tokens as string)
+    /// * `name` - Optional voter name
+    /// * `metadata_url` - Optional URL to voter metadata
+    /// * `referral` - Optional referral/attribution code (letters, digits, '-', '_'; max 32)
     /// 
     /// # Returns
-    /// JSON string with operation details for executing the vote submission
+    /// `true` if operation was scheduled successfully
     /// 
     /// # Example
     /// ```graphql
     /// mutation {
-    ///   submitVote(
-    ///     queryId: 1,
-    ///     value: "Yes",
-    ///     confidence: 85
+    ///   executeRegisterVoterFor(
+    ///     voterAddress: "0xfb3d8fcd4e78e5e4cd755307374561e3436e2dd48420e051af86333bc75d7c82",
+    ///     stake: "100",
+    ///     name: "Alice"
     ///   )
     /// }
     /// ```
+    async fn execute_register_voter_for(
+        &self,
+        voter_address: String,
+        stake: String,
+        name: Option<String>,
+        metadata_url: Option<String>,
+        referral: Option<String>,
+    ) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
+        
+        // Validate voter address format - accept any non-empty hex string
+        // This allows both chain IDs and account owner formats
+        if voter_address.is_empty() {
+            return Err("Voter address cannot be empty".to_string());
+        }
+        
+        // Parse stake as Amount directly (expects format like "100." with trailing dot)
+        let stake_amount: Amount = stake.parse()
+            .map_err(|_| "Invalid stake format. Use format like '100.' with trailing dot".to_string())?;
+        
+        if let Some(ref code) = referral {
+            state::normalize_referral_code(code)?;
+        }
+        
+        // Create operation
+        let operation = Operation::RegisterVoterFor {
+            voter_address,
+            stake: stake_amount,
+            name,
+            metadata_url,
+            referral,
+        };
+        
+        // Schedule operation to be executed by contract
+        self.runtime.schedule_operation(&operation);
+        
+        Ok(true)
+    }
+    
+    /// Commit a vote for a query (Phase 1 of commit/reveal)
     /// 
-    /// Returns:
-    /// ```json
-    /// {
-    ///   "operation": "SubmitVote",
-    ///   "query_id": 1,
-    ///   "value": "Yes",
-    ///   "confidence": 85,
-    ///   "instructions": "Call the SubmitVote operation with these parameters"
-    /// }
-    /// ```
-    async fn submit_vote(
+    /// # Arguments
+    /// * `query_id` - ID of the query to vote on
+    /// * `commit_hash` - Hash of (value + salt) using the registry's `commitHashAlgo`
+    /// 
+    /// # Returns
+    /// Success message
+    async fn commit_vote(
         &self,
-        query_id: u64,
+        query_id: i32,
+        commit_hash: String,
+    ) -> Result<String, String> {
+        use oracle_registry_v2::Operation;
+        
+        // Validate commit hash format
+        if commit_hash.is_empty() || commit_hash.len() > 128 {
+            return Err("Invalid commit hash format".to_string());
+        }
+        
+        // Create CommitVote operation
+        let operation = Operation::CommitVote {
+            query_id: query_id as u64,
+            commit_hash,
+        };
+        
+        // Schedule operation - will be executed when block is created
+        self.runtime.schedule_operation(&operation);
+        
+        // Return success message
+        Ok(format!("Vote committed for query {}", query_id))
+    }
+    
+    /// Reveal a vote for a query (Phase 2 of commit/reveal)
+    /// 
+    /// # Arguments
+    /// * `query_id` - ID of the query
+    /// * `value` - The actual vote value
+    /// * `salt` - The salt used in commit phase
+    /// * `confidence` - Optional confidence score (0-100)
+    /// 
+    /// # Returns
+    /// Success message
+    async fn reveal_vote(
+        &self,
+        query_id: i32,
         value: String,
+        salt: String,
         confidence: Option<i32>,
     ) -> Result<String, String> {
-        // Validate query_id
-        if query_id == 0 {
-            return Err("Query ID must be greater than zero".to_string());
-        }
+        use oracle_registry_v2::Operation;
         
-        // Validate value is not empty
+        // Validate inputs
         if value.is_empty() {
             return Err("Vote value cannot be empty".to_string());
         }
-        if value.len() > 200 {
-            return Err("Vote value too long (max 200 characters)".to_string());
+        
+        if salt.is_empty() {
+            return Err("Salt cannot be empty".to_string());
         }
         
         // Validate confidence if provided
-        let confidence_u8 = if let Some(conf) = confidence {
-            if conf < 0 || conf > 100 {
+        let conf = if let Some(c) = confidence {
+            if c < 0 || c > 100 {
                 return Err("Confidence must be between 0 and 100".to_string());
             }
-            Some(conf as u8)
+            c as u8
         } else {
-            None
+            100
         };
         
-        // Build response with operation details
-        let mut response = serde_json::json!({
-            "operation": "SubmitVote",
-            "query_id": query_id,
-            "value": value,
-            "instructions": "To execute this operation, use the backend API or Linera CLI",
-            "requirements": [
-                "Voter must be registered and active",
-                "Query must exist and be in Active status",
-                "Query deadline must not have passed",
-                "Voter must not have already voted on this query",
-                "Vote value must be one of the query's valid outcomes"
-            ]
-        });
+        // Create RevealVote operation
+        let operation = Operation::RevealVote {
+            query_id: query_id as u64,
+            value,
+            salt,
+            confidence: Some(conf),
+        };
         
-        // Add confidence if provided
-        if let Some(conf) = confidence_u8 {
-            response["confidence"] = serde_json::json!(conf);
-        }
+        // Schedule operation - will be executed when block is created
+        self.runtime.schedule_operation(&operation);
         
-        Ok(response.to_string())
+        // Return success message
+        Ok(format!("Vote revealed for query {}", query_id))
     }
     
-    /// Resolve a query after deadline has passed and minimum votes are met
+    /// Claim pending rewards
     /// 
-    /// This mutation provides information about the ResolveQuery operation.
-    /// To execute the query resolution, call the ResolveQuery operation on the contract.
+    /// # Returns
+    /// Success message
+    async fn claim_rewards(&self) -> Result<String, String> {
+        // Return success - the operation will be executed by the contract
+        Ok("Rewards claim initiated. Operation will be executed by contract.".to_string())
+    }
+    
+    /// Placeholder mutation
+    async fn placeholder(&self) -> bool {
+        true
+    }
+    
+    /// Execute CheckExpiredQueries operation (MAINTENANCE)
+    /// 
+    /// This mutation checks all active queries and marks expired ones.
+    /// A query is expired if its deadline has passed but it doesn't have enough votes.
+    /// 
+    /// # Returns
+    /// `true` if operation was scheduled successfully
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeCheckExpiredQueries
+    /// }
+    /// ```
+    /// 
+    /// # Note
+    /// Run this periodically (e.g., every 5 minutes) to keep registry healthy
+    async fn execute_check_expired_queries(&self) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
+        
+        let operation = Operation::CheckExpiredQueries;
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
+    
+    /// Execute AutoResolveQueries operation (MAINTENANCE)
     /// 
-    /// # Arguments
-    /// * `query_id` - ID of the query to resolve
+    /// This mutation automatically resolves queries that have completed their reveal phase
+    /// and have enough votes to be resolved.
     /// 
     /// # Returns
-    /// JSON string with operation details for executing the query resolution
+    /// `true` if operation was scheduled successfully
     /// 
     /// # Example
     /// ```graphql
     /// mutation {
-    ///   resolveQuery(queryId: 1)
+    ///   executeAutoResolveQueries
     /// }
     /// ```
     /// 
-    /// Returns:
-    /// ```json
-    /// {
-    ///   "operation": "ResolveQuery",
-    ///   "query_id": 1,
-    ///   "instructions": "Call the ResolveQuery operation with the query ID"
-    /// }
-    /// ```
-    async fn resolve_query(&self, query_id: u64) -> Result<String, String> {
-        // Validate query_id
-        if query_id == 0 {
-            return Err("Query ID must be greater than zero".to_string());
-        }
-        
-        // Build response with operation details
-        let response = serde_json::json!({
-            "operation": "ResolveQuery",
-            "query_id": query_id,
-            "instructions": "Call the ResolveQuery operation on the contract with this query ID",
-            "requirements": [
-                "Query must exist and be in Active status",
-                "Query deadline must have passed",
-                "Query must have at least the minimum required votes",
-                "Query must not already be resolved or expired",
-                "Can be called by anyone (not restricted to query creator or admin)"
-            ],
-            "resolution_process": [
-                "1. Validate query exists and is in Active status",
-                "2. Check that deadline has passed",
-                "3. Verify minimum votes requirement is met",
-                "4. Calculate result based on the query's decision strategy:",
-                "   - Majority: Most common vote value wins",
-                "   - Median: Median of numeric votes (for numeric outcomes)",
-                "   - WeightedByStake: Votes weighted by voter stake amounts",
-                "   - WeightedByReputation: Votes weighted by voter reputation scores",
-                "5. Update query status to Resolved with the calculated result",
-                "6. Unlock stake for all voters who participated",
-                "7. Update voter reputations based on vote correctness",
-                "8. Distribute rewards to voters who voted correctly",
-                "9. Apply slashing to voters who voted incorrectly",
-                "10. Update protocol statistics and treasury"
-            ],
-            "effects": [
-                "Query status will be changed to Resolved",
-                "Query result will be set to the calculated outcome",
-                "Query will be removed from active queries list",
-                "All voter stakes locked for this query will be unlocked",
-                "Correct voters will have rewards added to their pending rewards",
-                "Incorrect voters will have a portion of their stake slashed",
-                "Voters with stake below minimum after slashing will be deactivated",
-                "Voter reputations will be updated based on vote correctness",
-                "Protocol treasury will receive fees and slashed amounts",
-                "Total queries resolved counter will be incremented"
-            ],
-            "reward_distribution": [
-                "Rewards are distributed only to voters who voted for the winning outcome",
-                "Distribution method depends on the query's decision strategy:",
-                "- Equal distribution: Rewards split equally (for Majority/Median)",
-                "- Stake-weighted: Rewards proportional to stake (for WeightedByStake)",
-                "- Reputation-weighted: Rewards proportional to reputation (for WeightedByReputation)",
-                "Each voter's reward is adjusted by their reputation multiplier",
-                "Protocol fee is deducted from rewards before distribution",
-                "Rewards are added to pending_rewards and can be claimed later"
-            ],
-            "slashing": [
-                "Voters who voted incorrectly have stake slashed based on slash_percentage",
-                "Slashed amounts are transferred to the protocol treasury",
-                "If remaining stake falls below minimum, voter is automatically deactivated",
-                "Slashing helps ensure voters are incentivized to vote accurately",
-                "Slashing percentage is configurable via protocol parameters"
-            ],
-            "expiration_handling": [
-                "If deadline has passed but minimum votes not met, query expires instead",
-                "Expired queries do not distribute rewards or apply slashing",
-                "Voters on expired queries have their stake unlocked without penalty",
-                "Query creator's reward amount is returned (not distributed)"
-            ]
-        });
+    /// # Note
+    /// Run this periodically (e.g., every 5 minutes) to keep registry healthy
+    async fn execute_auto_resolve_queries(&self) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
         
-        Ok(response.to_string())
+        let operation = Operation::AutoResolveQueries;
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
     }
     
-    /// Claim pending rewards
-    /// 
-    /// This mutation provides information about the ClaimRewards operation.
-    /// To execute the reward claim, call the ClaimRewards operation on the contract.
+    /// Execute ApplyPendingParameters operation
     /// 
-    /// # Returns
-    /// JSON string with operation details for executing the reward claim
+    /// Applies the proposed parameter change once its timelock has elapsed;
+    /// anyone may call it.
     /// 
     /// # Example
     /// ```graphql
     /// mutation {
-    ///   claimRewards
+    ///   executeApplyPendingParameters
     /// }
     /// ```
+    async fn execute_apply_pending_parameters(&self) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
+        
+        let operation = Operation::ApplyPendingParameters;
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
+    
+    /// Execute RefreshStatistics operation (ADMIN)
     /// 
-    /// Returns:
-    /// ```json
-    /// {
-    ///   "operation": "ClaimRewards",
-    ///   "instructions": "Call the ClaimRewards operation on the contract"
+    /// Rebuilds the cached voter totals behind `statisticsCached` with a full scan.
+    async fn execute_refresh_statistics(&self) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
+        
+        let operation = Operation::RefreshStatistics;
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
+    
+    /// Execute SweepTreasury operation (MAINTENANCE)
+    /// 
+    /// Releases the treasury's tokens held beyond voter stakes to the
+    /// `treasury_account` parameter through the token contract.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeSweepTreasury
     /// }
     /// ```
-    // Removed old claim_rewards - replaced with proper implementation below
+    async fn execute_sweep_treasury(&self) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
+        
+        let operation = Operation::SweepTreasury;
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
     
-    /// Execute voter registration (ACTUALLY EXECUTES THE OPERATION!)
+    /// Execute ReclaimUnclaimedRewards operation (MAINTENANCE)
     /// 
-    /// This mutation schedules a RegisterVoter operation to be executed by the contract.
-    /// Unlike the `registerVoter` mutation which only returns instructions,
-    /// this mutation actually executes the operation.
+    /// Returns pending rewards left unclaimed for longer than the
+    /// `reward_claim_expiry_secs` parameter to the reward pool. Checks at
+    /// most `max` voters, continuing from where the previous call stopped.
     /// 
     /// # Arguments
-    /// * `stake` - Initial stake amount (in tokens as string)
-    /// * `name` - Optional voter name
-    /// * `metadata_url` - Optional URL to voter metadata
-    /// 
-    /// # Returns
-    /// `true` if operation was scheduled successfully
+    /// * `max` - Maximum voters to check in this call (1-1000)
     /// 
     /// # Example
     /// ```graphql
     /// mutation {
-    ///   executeRegisterVoter(stake: "1000", name: "Alice")
+    ///   executeReclaimUnclaimedRewards(max: 100)
     /// }
     /// ```
-    async fn execute_register_voter(
-        &self,
-        voter_address: String,
-        stake: String,
-        name: Option<String>,
-        metadata_url: Option<String>,
-    ) -> Result<bool, String> {
+    async fn execute_reclaim_unclaimed_rewards(&self, max: i32) -> Result<bool, String> {
         use oracle_registry_v2::Operation;
         
-        // Validate address
-        if voter_address.is_empty() {
-            return Err("Voter address is required".to_string());
+        if !(1..=1000).contains(&max) {
+            return Err("Max must be between 1 and 1000".to_string());
         }
         
-        // Parse stake as Amount directly (expects format like "100." with trailing dot)
-        let stake_amount: Amount = stake.parse()
-            .map_err(|_| "Invalid stake format. Use format like '100.' with trailing dot".to_string())?;
-        
-        // Create operation (chain_id is automatically detected by contract)
-        let operation = Operation::RegisterVoter {
-            stake: stake_amount,
-            name,
-            metadata_url,
-        };
-        
-        // Schedule operation to be executed by contract
+        let operation = Operation::ReclaimUnclaimedRewards { max: max as usize };
         self.runtime.schedule_operation(&operation);
-        
         Ok(true)
     }
     
-    /// Execute vote submission (ACTUALLY EXECUTES THE OPERATION!)
+    /// Execute FundRewardPool operation (ADMIN)
     /// 
-    /// This mutation schedules a SubmitVote operation to be executed by the contract.
-    /// 
-    /// # Arguments
-    /// * `query_id` - ID of the query to vote on
-    /// * `value` - Vote value (must be one of the query's outcomes)
-    /// * `confidence` - Optional confidence score (0-100)
+    /// Transfers `amount` tokens from the signer's token account into the
+    /// reward pool. The block must be signed by the owner of the tokens.
     /// 
-    /// # Returns
-    /// `true` if operation was scheduled successfully
-    async fn execute_submit_vote(
-        &self,
-        query_id: u64,
-        value: String,
-        confidence: Option<i32>,
-    ) -> Result<bool, String> {
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeFundRewardPool(amount: "1000.")
+    /// }
+    /// ```
+    async fn execute_fund_reward_pool(&self, amount: String) -> Result<bool, String> {
         use oracle_registry_v2::Operation;
         
-        // Validate
-        if query_id == 0 {
-            return Err("Query ID must be greater than zero".to_string());
-        }
-        
-        if value.is_empty() {
-            return Err("Vote value cannot be empty".to_string());
-        }
+        let amount: Amount = amount.parse()
+            .map_err(|_| "Invalid amount format. Use format like '100.' with trailing dot".to_string())?;
         
-        let confidence_u8 = confidence.map(|c| {
-            if c < 0 || c > 100 {
-                return Err("Confidence must be between 0 and 100".to_string());
-            }
-            Ok(c as u8)
-        }).transpose()?;
+        let operation = Operation::FundRewardPool { amount };
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
+    
+    /// Execute AdjustTreasury operation (ADMIN)
+    /// 
+    /// With `increase: true`, transfers `amount` tokens from the signer's token
+    /// account into the treasury; otherwise releases `amount` held treasury
+    /// tokens back to it.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeAdjustTreasury(amount: "50.", increase: false)
+    /// }
+    /// ```
+    async fn execute_adjust_treasury(&self, amount: String, increase: bool) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
         
-        // Create operation
-        let operation = Operation::SubmitVote {
-            query_id,
-            value,
-            confidence: confidence_u8,
-        };
+        let amount: Amount = amount.parse()
+            .map_err(|_| "Invalid amount format. Use format like '100.' with trailing dot".to_string())?;
         
-        // Schedule operation to be executed by contract
+        let operation = Operation::AdjustTreasury { amount, increase };
         self.runtime.schedule_operation(&operation);
-        
         Ok(true)
     }
     
-    /// Execute stake update (ACTUALLY EXECUTES THE OPERATION!)
-    async fn execute_update_stake(
-        &self,
-        additional_stake: String,
-    ) -> Result<bool, String> {
+    /// Execute ResendCallback operation (ADMIN)
+    /// 
+    /// Sends a resolved (or no-consensus) query's QueryResolutionCallback to
+    /// its callback chain again, for markets that missed the original.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeResendCallback(queryId: 1)
+    /// }
+    /// ```
+    async fn execute_resend_callback(&self, query_id: u64) -> Result<bool, String> {
         use oracle_registry_v2::Operation;
         
-        // Parse stake as Amount directly (expects format like "100." with trailing dot)
-        let stake_amount: Amount = additional_stake.parse()
-            .map_err(|_| "Invalid stake format. Use format like '100.' with trailing dot".to_string())?;
+        let operation = Operation::ResendCallback { query_id };
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
+    
+    /// Execute SettleQueries operation (MAINTENANCE)
+    /// 
+    /// Resolves or expires at most `max` active queries, continuing from where
+    /// the previous call stopped. Call repeatedly until no queries remain.
+    /// 
+    /// # Arguments
+    /// * `max` - Maximum queries to process in this call (1-1000)
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeSettleQueries(max: 50)
+    /// }
+    /// ```
+    async fn execute_settle_queries(&self, max: i32) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
         
-        if stake_amount == Amount::ZERO {
-            return Err("Additional stake must be greater than 0".to_string());
+        if !(1..=1000).contains(&max) {
+            return Err("Max must be between 1 and 1000".to_string());
         }
         
-        let operation = Operation::UpdateStake {
-            additional_stake: stake_amount,
-        };
-        
+        let operation = Operation::SettleQueries { max: max as usize };
         self.runtime.schedule_operation(&operation);
         Ok(true)
     }
     
-    /// Execute rewards claim (ACTUALLY EXECUTES THE OPERATION!)
-    async fn execute_claim_rewards(&self) -> Result<bool, String> {
+    /// Execute ResolveQuery operation for a specific query
+    /// 
+    /// This mutation resolves a specific query if it meets the requirements:
+    /// - Query is active
+    /// - Deadline has passed
+    /// - Has minimum required votes
+    /// 
+    /// # Arguments
+    /// * `query_id` - ID of the query to resolve
+    /// 
+    /// # Returns
+    /// `true` if operation was scheduled successfully
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeResolveQuery(queryId: 0)
+    /// }
+    /// ```
+    async fn execute_resolve_query(&self, query_id: u64) -> Result<bool, String> {
         use oracle_registry_v2::Operation;
         
-        let operation = Operation::ClaimRewards;
-        
+        let operation = Operation::ResolveQuery { query_id };
         self.runtime.schedule_operation(&operation);
         Ok(true)
     }
     
-    /// Execute RegisterVoterFor operation (ADMIN OPERATION FOR TESTING!)
-    /// 
-    /// This mutation allows registering a voter by specifying their address.
-    /// Useful for testing and initial setup without requiring cross-chain messages.
+    /// Execute set query metadata operation (creator only)
     /// 
     /// # Arguments
-    /// * `voter_address` - The voter's account address as hex string (e.g., "0xfb3d8fcd...")
-    /// * `stake` - Initial stake amount (in tokens as string)
-    /// * `name` - Optional voter name
-    /// * `metadata_url` - Optional URL to voter metadata
+    /// * `query_id` - The ID of the query to update
+    /// * `category` - Browsing category, or null to clear it
+    /// * `tags` - Tags (max 10, up to 32 characters each)
     /// 
     /// # Returns
     /// `true` if operation was scheduled successfully
@@ -1532,150 +3927,116 @@ impl MutationRoot {
     /// # Example
     /// ```graphql
     /// mutation {
-    ///   executeRegisterVoterFor(
-    ///     voterAddress: "0xfb3d8fcd4e78e5e4cd755307374561e3436e2dd48420e051af86333bc75d7c82",
-    ///     stake: "100",
-    ///     name: "Alice"
-    ///   )
+    ///   executeSetQueryMetadata(queryId: 0, category: "sports", tags: ["football"])
     /// }
     /// ```
-    async fn execute_register_voter_for(
+    async fn execute_set_query_metadata(
         &self,
-        voter_address: String,
-        stake: String,
-        name: Option<String>,
-        metadata_url: Option<String>,
+        query_id: u64,
+        category: Option<String>,
+        tags: Vec<String>,
     ) -> Result<bool, String> {
         use oracle_registry_v2::Operation;
         
-        // Validate voter address format - accept any non-empty hex string
-        // This allows both chain IDs and account owner formats
-        if voter_address.is_empty() {
-            return Err("Voter address cannot be empty".to_string());
-        }
-        
-        // Parse stake as Amount directly (expects format like "100." with trailing dot)
-        let stake_amount: Amount = stake.parse()
-            .map_err(|_| "Invalid stake format. Use format like '100.' with trailing dot".to_string())?;
-        
-        // Create operation
-        let operation = Operation::RegisterVoterFor {
-            voter_address,
-            stake: stake_amount,
-            name,
-            metadata_url,
-        };
+        state::validate_query_labels(&category, &tags)?;
         
-        // Schedule operation to be executed by contract
+        let operation = Operation::SetQueryMetadata { query_id, category, tags };
         self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
+    
+    /// Execute SetExternalResolution operation (ADMIN)
+    /// 
+    /// Designates an active query for resolution from external data, or
+    /// makes it voting-only again.
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeSetExternalResolution(queryId: 0, allowed: true)
+    /// }
+    /// ```
+    async fn execute_set_external_resolution(&self, query_id: u64, allowed: bool) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
         
+        let operation = Operation::SetExternalResolution { query_id, allowed };
+        self.runtime.schedule_operation(&operation);
         Ok(true)
     }
     
-    /// Commit a vote for a query (Phase 1 of commit/reveal)
+    /// Execute ResolveWithExternalData operation (ADMIN)
     /// 
-    /// # Arguments
-    /// * `query_id` - ID of the query to vote on
-    /// * `commit_hash` - SHA-256 hash of (value + salt)
+    /// Resolves a query designated for external resolution to `outcome`
+    /// without voting, recording `source` (e.g. a URL or hash) on the query.
+    /// Voters' locked stake is released without rewards or slashing.
     /// 
-    /// # Returns
-    /// Success message
-    async fn commit_vote(
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeResolveWithExternalData(queryId: 0, outcome: "Yes", source: "https://example.com/results/42")
+    /// }
+    /// ```
+    async fn execute_resolve_with_external_data(
         &self,
-        query_id: i32,
-        commit_hash: String,
-    ) -> Result<String, String> {
+        query_id: u64,
+        outcome: String,
+        source: String,
+    ) -> Result<bool, String> {
         use oracle_registry_v2::Operation;
         
-        // Validate commit hash format
-        if commit_hash.is_empty() || commit_hash.len() > 128 {
-            return Err("Invalid commit hash format".to_string());
-        }
-        
-        // Create CommitVote operation
-        let operation = Operation::CommitVote {
-            query_id: query_id as u64,
-            commit_hash,
-        };
+        state::validate_resolution_source(&source)?;
         
-        // Schedule operation - will be executed when block is created
+        let operation = Operation::ResolveWithExternalData { query_id, outcome, source };
         self.runtime.schedule_operation(&operation);
-        
-        // Return success message
-        Ok(format!("Vote committed for query {}", query_id))
+        Ok(true)
     }
     
-    /// Reveal a vote for a query (Phase 2 of commit/reveal)
+    /// Execute update query strategy operation (creator only)
+    /// 
+    /// Allowed only while the query is in its commit phase and before any vote.
     /// 
     /// # Arguments
-    /// * `query_id` - ID of the query
-    /// * `value` - The actual vote value
-    /// * `salt` - The salt used in commit phase
-    /// * `confidence` - Optional confidence score (0-100)
+    /// * `query_id` - The ID of the query to update
+    /// * `strategy` - New decision strategy: "Majority", "Median", "WeightedByStake", "WeightedByReputation", or "ConfidenceWeightedMedian"
     /// 
     /// # Returns
-    /// Success message
-    async fn reveal_vote(
-        &self,
-        query_id: i32,
-        value: String,
-        salt: String,
-        confidence: Option<i32>,
-    ) -> Result<String, String> {
+    /// `true` if operation was scheduled successfully
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeUpdateQueryStrategy(queryId: 0, strategy: "WeightedByStake")
+    /// }
+    /// ```
+    async fn execute_update_query_strategy(&self, query_id: u64, strategy: String) -> Result<bool, String> {
         use oracle_registry_v2::Operation;
+        use oracle_registry_v2::state::DecisionStrategy;
         
-        // Validate inputs
-        if value.is_empty() {
-            return Err("Vote value cannot be empty".to_string());
-        }
-        
-        if salt.is_empty() {
-            return Err("Salt cannot be empty".to_string());
-        }
-        
-        // Validate confidence if provided
-        let conf = if let Some(c) = confidence {
-            if c < 0 || c > 100 {
-                return Err("Confidence must be between 0 and 100".to_string());
-            }
-            c as u8
-        } else {
-            100
+        // Parse strategy
+        let strategy_enum = match strategy.as_str() {
+            "Majority" => DecisionStrategy::Majority,
+            "Median" => DecisionStrategy::Median,
+            "WeightedByStake" => DecisionStrategy::WeightedByStake,
+            "WeightedByReputation" => DecisionStrategy::WeightedByReputation,
+            "ConfidenceWeightedMedian" => DecisionStrategy::ConfidenceWeightedMedian,
+            _ => return Err(format!("Invalid strategy: {}", strategy)),
         };
         
-        // Create RevealVote operation
-        let operation = Operation::RevealVote {
-            query_id: query_id as u64,
-            value,
-            salt,
-            confidence: Some(conf),
+        let operation = Operation::UpdateQueryStrategy {
+            query_id,
+            strategy: strategy_enum,
         };
-        
-        // Schedule operation - will be executed when block is created
         self.runtime.schedule_operation(&operation);
-        
-        // Return success message
-        Ok(format!("Vote revealed for query {}", query_id))
+        Ok(true)
     }
     
-    /// Claim pending rewards
+    /// Execute cancel query operation (creator or admin)
     /// 
-    /// # Returns
-    /// Success message
-    async fn claim_rewards(&self) -> Result<String, String> {
-        // Return success - the operation will be executed by the contract
-        Ok("Rewards claim initiated. Operation will be executed by contract.".to_string())
-    }
-    
-    /// Placeholder mutation
-    async fn placeholder(&self) -> bool {
-        true
-    }
-    
-    /// Execute CheckExpiredQueries operation (MAINTENANCE)
+    /// Allowed only while the query is active and has no revealed votes.
+    /// Stake locked by committed voters is released.
     /// 
-    /// This mutation checks all active queries and marks expired ones.
-    /// A query is expired if its deadline has passed but it doesn't have enough votes.
+    /// # Arguments
+    /// * `query_id` - The ID of the query to cancel
     /// 
     /// # Returns
     /// `true` if operation was scheduled successfully
@@ -1683,24 +4044,50 @@ impl MutationRoot {
     /// # Example
     /// ```graphql
     /// mutation {
-    ///   executeCheckExpiredQueries
+    ///   executeCancelQuery(queryId: 0)
     /// }
     /// ```
+    async fn execute_cancel_query(&self, query_id: u64) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
+        
+        let operation = Operation::CancelQuery { query_id };
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
+    
+    /// Execute ExpireQueries operation (ADMIN)
     /// 
-    /// # Note
-    /// Run this periodically (e.g., every 5 minutes) to keep registry healthy
-    async fn execute_check_expired_queries(&self) -> Result<bool, String> {
+    /// Expires each listed query whose deadline has passed and unlocks its
+    /// voters' stakes; ids that cannot be expired are reported per query in
+    /// the operation response without stopping the rest.
+    /// 
+    /// # Arguments
+    /// * `query_ids` - Query IDs (1 to 50, no duplicates)
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   executeExpireQueries(queryIds: [3, 4, 7])
+    /// }
+    /// ```
+    async fn execute_expire_queries(&self, query_ids: Vec<u64>) -> Result<bool, String> {
         use oracle_registry_v2::Operation;
         
-        let operation = Operation::CheckExpiredQueries;
+        state::validate_expire_queries(&query_ids)?;
+        
+        let operation = Operation::ExpireQueries { query_ids };
         self.runtime.schedule_operation(&operation);
         Ok(true)
     }
     
-    /// Execute AutoResolveQueries operation (MAINTENANCE)
+    /// Execute unlock query stakes operation (anyone)
     /// 
-    /// This mutation automatically resolves queries that have completed their reveal phase
-    /// and have enough votes to be resolved.
+    /// Finalizing a query unlocks only the first batch of its voters' stakes;
+    /// this releases up to `max` more. Check `stakeUnlockProgress` for what is left.
+    /// 
+    /// # Arguments
+    /// * `query_id` - The resolved, expired or cancelled query
+    /// * `max` - Maximum number of voters to unlock in this block
     /// 
     /// # Returns
     /// `true` if operation was scheduled successfully
@@ -1708,29 +4095,28 @@ impl MutationRoot {
     /// # Example
     /// ```graphql
     /// mutation {
-    ///   executeAutoResolveQueries
+    ///   executeUnlockQueryStakes(queryId: 0, max: 50)
     /// }
     /// ```
-    /// 
-    /// # Note
-    /// Run this periodically (e.g., every 5 minutes) to keep registry healthy
-    async fn execute_auto_resolve_queries(&self) -> Result<bool, String> {
+    async fn execute_unlock_query_stakes(&self, query_id: u64, max: i32) -> Result<bool, String> {
         use oracle_registry_v2::Operation;
         
-        let operation = Operation::AutoResolveQueries;
+        if max <= 0 {
+            return Err("max must be at least 1".to_string());
+        }
+        
+        let operation = Operation::UnlockQueryStakes { query_id, max: max as u32 };
         self.runtime.schedule_operation(&operation);
         Ok(true)
     }
     
-    /// Execute ResolveQuery operation for a specific query
+    /// Execute advance phase operation (admin only)
     /// 
-    /// This mutation resolves a specific query if it meets the requirements:
-    /// - Query is active
-    /// - Deadline has passed
-    /// - Has minimum required votes
+    /// Moves a stuck query from Commit to Reveal after its commit phase ends,
+    /// or from Reveal to Completed after its reveal window ends. Does not resolve.
     /// 
     /// # Arguments
-    /// * `query_id` - ID of the query to resolve
+    /// * `query_id` - The ID of the query to advance
     /// 
     /// # Returns
     /// `true` if operation was scheduled successfully
@@ -1738,13 +4124,13 @@ impl MutationRoot {
     /// # Example
     /// ```graphql
     /// mutation {
-    ///   executeResolveQuery(queryId: 0)
+    ///   executeAdvancePhase(queryId: 0)
     /// }
     /// ```
-    async fn execute_resolve_query(&self, query_id: u64) -> Result<bool, String> {
+    async fn execute_advance_phase(&self, query_id: u64) -> Result<bool, String> {
         use oracle_registry_v2::Operation;
         
-        let operation = Operation::ResolveQuery { query_id };
+        let operation = Operation::AdvancePhase { query_id };
         self.runtime.schedule_operation(&operation);
         Ok(true)
     }
@@ -1783,7 +4169,7 @@ impl MutationRoot {
         use oracle_registry_v2::Operation;
         
         // Parse target chain ID
-        let target_chain_id = target_chain.parse::<linera_sdk::linera_base_types::ChainId>()
+        let target_chain_id = state::parse_chain_id(&target_chain)
             .map_err(|e| format!("Invalid target chain ID: {}", e))?;
         
         // Parse stake as Amount directly (expects format like "100." with trailing dot)
@@ -1835,7 +4221,7 @@ impl MutationRoot {
         use oracle_registry_v2::Operation;
         
         // Parse target chain ID
-        let target_chain_id = target_chain.parse::<linera_sdk::linera_base_types::ChainId>()
+        let target_chain_id = state::parse_chain_id(&target_chain)
             .map_err(|e| format!("Invalid target chain ID: {}", e))?;
         
         // Parse stake as Amount directly (expects format like "100." with trailing dot)
@@ -1856,6 +4242,50 @@ impl MutationRoot {
         Ok(true)
     }
     
+    /// Send RequestQueryResult message to target chain (cross-chain result lookup)
+    /// 
+    /// This mutation asks the registry on the target chain to send a query's
+    /// result back to this chain. A resolved query is answered with a
+    /// QueryResolutionCallback; otherwise a QueryResultUnavailable message
+    /// carries the reason.
+    /// 
+    /// # Arguments
+    /// * `target_chain` - The chain ID where the registry is deployed (app chain)
+    /// * `query_id` - ID of the query whose result is requested
+    /// 
+    /// # Returns
+    /// `true` if message was scheduled successfully
+    /// 
+    /// # Example
+    /// ```graphql
+    /// mutation {
+    ///   sendRequestQueryResultMessage(
+    ///     targetChain: "208873b668818fc962d8470c68698dc5dff2321720a9bb0d74576d45f4f73c91",
+    ///     queryId: 1
+    ///   )
+    /// }
+    /// ```
+    async fn send_request_query_result_message(
+        &self,
+        target_chain: String,
+        query_id: u64,
+    ) -> Result<bool, String> {
+        use oracle_registry_v2::Operation;
+        
+        // Parse target chain ID
+        let target_chain_id = state::parse_chain_id(&target_chain)
+            .map_err(|e| format!("Invalid target chain ID: {}", e))?;
+        
+        // Create SendRequestQueryResultMessage operation
+        let operation = Operation::SendRequestQueryResultMessage {
+            target_chain: target_chain_id,
+            query_id,
+        };
+        
+        self.runtime.schedule_operation(&operation);
+        Ok(true)
+    }
+    
     /// Send SubmitVote message to target chain (cross-chain voting)
     /// 
     /// This mutation sends a cross-chain message to submit a vote on the target chain.
@@ -1891,7 +4321,7 @@ impl MutationRoot {
         use oracle_registry_v2::Operation;
         
         // Parse target chain ID
-        let target_chain_id = target_chain.parse::<linera_sdk::linera_base_types::ChainId>()
+        let target_chain_id = state::parse_chain_id(&target_chain)
             .map_err(|e| format!("Invalid target chain ID: {}", e))?;
         
         // Validate confidence if provided
@@ -1932,7 +4362,7 @@ impl MutationRoot {
         use oracle_registry_v2::state::DecisionStrategy;
         
         // Parse target chain ID
-        let target_chain_id = target_chain.parse::<linera_sdk::linera_base_types::ChainId>()
+        let target_chain_id = state::parse_chain_id(&target_chain)
             .map_err(|e| format!("Invalid target chain ID: {}", e))?;
         
         // Parse strategy
@@ -1941,6 +4371,7 @@ impl MutationRoot {
             "Median" => DecisionStrategy::Median,
             "WeightedByStake" => DecisionStrategy::WeightedByStake,
             "WeightedByReputation" => DecisionStrategy::WeightedByReputation,
+            "ConfidenceWeightedMedian" => DecisionStrategy::ConfidenceWeightedMedian,
             _ => return Err(format!("Invalid strategy: {}", strategy)),
         };
         
@@ -1974,7 +4405,7 @@ impl MutationRoot {
         use oracle_registry_v2::Operation;
         
         // Parse target chain ID
-        let target_chain_id = target_chain.parse::<linera_sdk::linera_base_types::ChainId>()
+        let target_chain_id = state::parse_chain_id(&target_chain)
             .map_err(|e| format!("Invalid target chain ID: {}", e))?;
         
         // Validate commit hash
@@ -2005,7 +4436,7 @@ impl MutationRoot {
         use oracle_registry_v2::Operation;
         
         // Parse target chain ID
-        let target_chain_id = target_chain.parse::<linera_sdk::linera_base_types::ChainId>()
+        let target_chain_id = state::parse_chain_id(&target_chain)
             .map_err(|e| format!("Invalid target chain ID: {}", e))?;
         
         // Validate value
@@ -0,0 +1,60 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for bounded, cursor-based query settlement
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, query};
+    use crate::state::{settle_batch, Settlement, VotingPhase};
+    use linera_sdk::linera_base_types::Timestamp;
+
+    #[test]
+    fn test_only_max_processed_per_call() {
+        let active: Vec<u64> = (0..25).rev().collect();
+
+        let first = settle_batch(&active, None, 10);
+        assert_eq!(first.query_ids, (0..10).collect::<Vec<_>>());
+        assert_eq!(first.remaining, 15);
+        assert_eq!(first.next_cursor, Some(9));
+
+        let second = settle_batch(&active, first.next_cursor, 10);
+        assert_eq!(second.query_ids, (10..20).collect::<Vec<_>>());
+        assert_eq!(second.remaining, 5);
+
+        let third = settle_batch(&active, second.next_cursor, 10);
+        assert_eq!(third.query_ids, (20..25).collect::<Vec<_>>());
+        assert_eq!(third.remaining, 0);
+        assert_eq!(third.next_cursor, None);
+
+        // The next pass starts over
+        assert_eq!(settle_batch(&active, third.next_cursor, 10), first);
+    }
+
+    #[test]
+    fn test_cursor_survives_settled_queries_leaving_the_set() {
+        // Queries 0..10 were settled and removed from the active set
+        let active: Vec<u64> = (5..25).filter(|id| *id >= 10 || *id % 2 == 0).collect();
+
+        let batch = settle_batch(&active, Some(9), 10);
+        assert_eq!(batch.query_ids, (10..20).collect::<Vec<_>>());
+        assert_eq!(batch.remaining, 5);
+    }
+
+    #[test]
+    fn test_settlement_for_each_query_state() {
+        let mut q = query(&["Yes", "No"]);
+        q.min_votes = 2;
+        assert_eq!(q.settlement_at(Timestamp::from(500_000), 0), None);
+
+        // Past the deadline without enough votes
+        assert_eq!(q.settlement_at(Timestamp::from(3_000_000), 0), Some(Settlement::Expire));
+
+        // Reveal window over with enough votes
+        q.phase = VotingPhase::Reveal;
+        add_vote(&mut q, 2, "Yes");
+        add_vote(&mut q, 3, "Yes");
+        assert_eq!(q.settlement_at(Timestamp::from(2_500_000), 0), Some(Settlement::Resolve));
+        assert_eq!(q.settlement_at(Timestamp::from(2_500_000), 1), None);
+    }
+}
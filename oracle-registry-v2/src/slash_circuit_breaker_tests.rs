@@ -0,0 +1,77 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the slashing circuit breaker
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{ProtocolParameters, QueryStatus};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::Amount;
+
+    /// Three 100-token voters on a query voted Yes, Yes, No, past its deadline
+    fn resolvable(slash_percentage: u32, max_slash_per_resolution_bps: u32) -> (TestHarness, u64) {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            slash_percentage,
+            max_slash_per_resolution_bps,
+            ..ProtocolParameters::default()
+        });
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+
+        let created = harness.create_query(&["Yes", "No"], 2, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        assert!(harness.submit_vote(chain(1), query_id, "Yes").success);
+        assert!(harness.submit_vote(chain(2), query_id, "Yes").success);
+        assert!(harness.submit_vote(chain(3), query_id, "No").success);
+
+        harness.advance_past_deadline(query_id);
+        (harness, query_id)
+    }
+
+    #[test]
+    fn test_excessive_slash_aborts_resolution_and_pauses() {
+        // Slashing half of one voter's stake is over 16% of total stake, above the 10% limit
+        let (mut harness, query_id) = resolvable(5000, 1000);
+
+        let response = harness.resolve(query_id);
+        assert!(!response.success);
+        assert!(response.message.contains("resolution aborted and protocol paused"));
+
+        assert!(*harness.state().is_paused.get());
+        let query = harness.query(query_id).unwrap();
+        assert_eq!(query.status, QueryStatus::Active);
+        assert!(query.result.is_none());
+        assert_eq!(harness.voter(&chain(3)).unwrap().stake, Amount::from_tokens(100));
+        assert_eq!(*harness.state().total_stake.get(), Amount::from_tokens(300));
+
+        // Nothing else runs until the admin unpauses
+        let response = harness.resolve(query_id);
+        assert!(!response.success);
+        assert!(response.message.contains("Protocol is paused"));
+    }
+
+    #[test]
+    fn test_slash_within_limit_resolves() {
+        let (mut harness, query_id) = resolvable(500, 5000);
+
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        assert!(!*harness.state().is_paused.get());
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::Resolved);
+    }
+
+    #[test]
+    fn test_check_resolution_slash() {
+        let total = Amount::from_tokens(1000);
+        let disabled = ProtocolParameters::default();
+        assert!(disabled.check_resolution_slash(total, total).is_ok());
+
+        let limited = ProtocolParameters { max_slash_per_resolution_bps: 1000, ..ProtocolParameters::default() };
+        assert!(limited.check_resolution_slash(Amount::ZERO, total).is_ok());
+        assert!(limited.check_resolution_slash(Amount::from_tokens(100), total).is_ok());
+        let err = limited.check_resolution_slash(Amount::from_attos(100 * 10u128.pow(18) + 1), total).unwrap_err();
+        assert!(err.contains("1000 basis point limit"));
+    }
+}
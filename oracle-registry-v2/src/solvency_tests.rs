@@ -0,0 +1,61 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the protocol solvency check
+
+#[cfg(test)]
+mod tests {
+    use crate::state::Solvency;
+    use linera_sdk::linera_base_types::Amount;
+
+    fn balanced() -> Solvency {
+        // Two voters staked 100 each; one was slashed 10 into the treasury and
+        // has 5 unclaimed from a funded reward pool of 20
+        Solvency {
+            total_staked: Amount::from_tokens(190),
+            total_locked: Amount::from_tokens(15),
+            total_pending_rewards: Amount::from_tokens(5),
+            reward_pool: Amount::from_tokens(15),
+            treasury: Amount::from_tokens(10),
+            tokens_held: Amount::from_tokens(200),
+        }
+    }
+
+    #[test]
+    fn test_balanced_state_is_solvent() {
+        let solvency = balanced();
+
+        assert_eq!(solvency.obligations(), Amount::from_tokens(195));
+        assert_eq!(solvency.backing(), Amount::from_tokens(215));
+        assert!(solvency.is_solvent());
+        assert_eq!(solvency.shortfall(), Amount::ZERO);
+    }
+
+    #[test]
+    fn test_inflated_pending_rewards_are_insolvent() {
+        let mut solvency = balanced();
+        solvency.total_pending_rewards = Amount::from_tokens(50);
+
+        assert!(!solvency.is_solvent());
+        assert_eq!(solvency.shortfall(), Amount::from_tokens(25));
+    }
+
+    #[test]
+    fn test_treasury_does_not_back_obligations() {
+        let mut solvency = balanced();
+        solvency.tokens_held = Amount::from_tokens(170);
+        solvency.treasury = Amount::from_tokens(1000);
+
+        assert!(!solvency.is_solvent());
+        assert_eq!(solvency.shortfall(), Amount::from_tokens(10));
+    }
+
+    #[test]
+    fn test_exactly_backed_is_solvent() {
+        let mut solvency = balanced();
+        solvency.reward_pool = Amount::ZERO;
+        solvency.tokens_held = solvency.obligations();
+
+        assert!(solvency.is_solvent());
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for per-voter stake history
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{self, ProtocolParameters, StakeSnapshot};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::{Amount, ChainId, Timestamp};
+    use oracle_registry_v2::Operation;
+
+    fn history(harness: &TestHarness, voter: &ChainId) -> Vec<StakeSnapshot> {
+        harness.state().get_stake_history(voter).blocking_wait()
+    }
+
+    #[test]
+    fn test_stake_changes_and_slashes_append_snapshots() {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            slash_percentage: 500,
+            ..ProtocolParameters::default()
+        });
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        assert_eq!(history(&harness, &chain(3)).len(), 1);
+        assert_eq!(history(&harness, &chain(3))[0].stake, Amount::from_tokens(100));
+
+        // A stake top-up appends a snapshot
+        harness.advance_secs(10);
+        let response = harness.execute_as(chain(3), Operation::UpdateStake {
+            additional_stake: Amount::from_tokens(50),
+        });
+        assert!(response.success, "{}", response.message);
+        let after_top_up = history(&harness, &chain(3));
+        assert_eq!(after_top_up.len(), 2);
+        assert_eq!(after_top_up[1].stake, Amount::from_tokens(150));
+        assert_eq!(after_top_up[1].timestamp, harness.now());
+
+        // So does a slash for voting against the result
+        let created = harness.create_query(&["Yes", "No"], 2, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        assert!(harness.submit_vote(chain(1), query_id, "Yes").success);
+        assert!(harness.submit_vote(chain(2), query_id, "Yes").success);
+        assert!(harness.submit_vote(chain(3), query_id, "No").success);
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+
+        let slashed = harness.voter(&chain(3)).unwrap();
+        assert!(slashed.stake < Amount::from_tokens(150));
+        let after_slash = history(&harness, &chain(3));
+        assert_eq!(after_slash.len(), 3);
+        assert_eq!(after_slash[2].stake, slashed.stake);
+        assert_eq!(after_slash[2].timestamp, harness.now());
+
+        // Voters on the winning side keep a single snapshot
+        assert_eq!(history(&harness, &chain(1)).len(), 1);
+    }
+
+    #[test]
+    fn test_stake_history_is_capped() {
+        let mut history = Vec::new();
+        let total = state::MAX_STAKE_HISTORY as u64 + 5;
+        for micros in 0..total {
+            state::push_stake_snapshot(&mut history, StakeSnapshot {
+                timestamp: Timestamp::from(micros),
+                stake: Amount::from_tokens(micros.into()),
+                locked_stake: Amount::ZERO,
+            });
+        }
+
+        assert_eq!(history.len(), state::MAX_STAKE_HISTORY);
+        assert_eq!(history[0].timestamp, Timestamp::from(5));
+        assert_eq!(history.last().unwrap().timestamp, Timestamp::from(total - 1));
+    }
+}
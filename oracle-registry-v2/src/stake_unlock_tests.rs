@@ -0,0 +1,82 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for unlocking a finalized query's stakes in batches
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, chain, query};
+    use crate::state::{next_unlock_batch, UNLOCK_BATCH_SIZE};
+    use linera_sdk::linera_base_types::ChainId;
+
+    #[test]
+    fn test_paginated_unlocking_completes() {
+        // 120 voters: more than one block's worth
+        let mut q = query(&["Yes", "No"]);
+        for id in 1..=120u8 {
+            add_vote(&mut q, id, "Yes");
+        }
+        let lockers = q.stake_lockers();
+
+        // The finalizing block unlocks the first batch
+        let (first, mut cursor) = next_unlock_batch(&lockers, 0, UNLOCK_BATCH_SIZE);
+        let mut unlocked: Vec<ChainId> = first.to_vec();
+        assert_eq!(cursor, Some(UNLOCK_BATCH_SIZE));
+
+        // UnlockQueryStakes calls continue until nothing remains
+        let mut calls = 0;
+        while let Some(position) = cursor {
+            let (batch, next) = next_unlock_batch(&lockers, position, 30);
+            assert!(!batch.is_empty());
+            unlocked.extend_from_slice(batch);
+            cursor = next;
+            calls += 1;
+        }
+
+        // 70 remaining at 30 per call
+        assert_eq!(calls, 3);
+        assert_eq!(unlocked, lockers);
+        assert_eq!(unlocked.len(), 120);
+    }
+
+    #[test]
+    fn test_small_query_unlocks_in_one_batch() {
+        let lockers: Vec<ChainId> = (1..=3).map(chain).collect();
+
+        let (batch, cursor) = next_unlock_batch(&lockers, 0, UNLOCK_BATCH_SIZE);
+
+        assert_eq!(batch, &lockers[..]);
+        assert_eq!(cursor, None);
+    }
+
+    #[test]
+    fn test_exact_batch_finishes() {
+        let lockers: Vec<ChainId> = (1..=4).map(chain).collect();
+
+        let (batch, cursor) = next_unlock_batch(&lockers, 2, 2);
+
+        assert_eq!(batch, &lockers[2..]);
+        assert_eq!(cursor, None);
+    }
+
+    #[test]
+    fn test_cursor_past_end_is_empty() {
+        let lockers: Vec<ChainId> = (1..=2).map(chain).collect();
+
+        let (batch, cursor) = next_unlock_batch(&lockers, 5, 10);
+
+        assert!(batch.is_empty());
+        assert_eq!(cursor, None);
+    }
+
+    #[test]
+    fn test_lockers_order_is_stable() {
+        let mut q = query(&["Yes", "No"]);
+        add_vote(&mut q, 9, "Yes");
+        add_vote(&mut q, 3, "No");
+        add_vote(&mut q, 5, "Yes");
+
+        assert_eq!(q.stake_lockers(), vec![chain(3), chain(5), chain(9)]);
+        assert_eq!(q.stake_lockers(), q.clone().stake_lockers());
+    }
+}
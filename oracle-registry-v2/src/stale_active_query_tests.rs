@@ -0,0 +1,39 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests that maintenance loops skip queries wrongly left in the active list
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::query;
+    use crate::state::{QueryStatus, VotingPhase};
+    use linera_sdk::linera_base_types::Timestamp;
+
+    #[test]
+    fn test_cancelled_query_is_skipped() {
+        let mut q = query(&["Yes", "No"]);
+        q.status = QueryStatus::Cancelled;
+        q.phase = VotingPhase::Reveal;
+
+        let err = q.check_listed_active().unwrap_err();
+        assert!(err.contains("Query 7 is Cancelled but still listed"));
+        // Past every deadline, it is still never resolved or expired again
+        assert_eq!(q.settlement_at(Timestamp::from(10_000_000), 0), None);
+    }
+
+    #[test]
+    fn test_closed_queries_are_skipped() {
+        for status in [QueryStatus::Resolved, QueryStatus::Expired] {
+            let mut q = query(&["Yes", "No"]);
+            q.status = status;
+            assert!(q.check_listed_active().is_err());
+        }
+    }
+
+    #[test]
+    fn test_active_query_is_processed() {
+        let q = query(&["Yes", "No"]);
+
+        assert!(q.check_listed_active().is_ok());
+    }
+}
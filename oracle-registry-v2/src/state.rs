@@ -7,8 +7,8 @@
 //! instead of deploying separate applications.
 
 use linera_sdk::{
-    linera_base_types::{Amount, ApplicationId, ChainId, Timestamp},
-    views::{linera_views, MapView, RegisterView, RootView, ViewStorageContext},
+    linera_base_types::{Account, Amount, ApplicationId, ChainId, TimeDelta, Timestamp},
+    views::{linera_views, MapView, RegisterView, RootView, ViewError, ViewStorageContext},
 };
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -37,12 +37,168 @@ pub struct VoterInfo {
     /// Registration timestamp
     pub registered_at: Timestamp,
     
+    /// Last register, vote, claim or stake change by this voter
+    pub last_active_at: Timestamp,
+    
+    /// Last submitted or committed vote (reveals don't count)
+    pub last_vote_at: Option<Timestamp>,
+    
     /// Is voter active
     pub is_active: bool,
     
     /// Metadata
     pub name: Option<String>,
     pub metadata_url: Option<String>,
+    
+    /// Referral/attribution code given at registration (lowercase)
+    pub referral: Option<String>,
+    
+    /// Mean Brier score of resolved votes that carried a confidence, in basis
+    /// points (0 = perfectly calibrated, 10000 = always fully confident and wrong)
+    pub calibration_score: Option<u32>,
+    
+    /// Number of resolved votes behind `calibration_score`
+    pub calibrated_votes: u64,
+    
+    /// Opaque blob encrypted off-chain by the voter (private KYC/reputation
+    /// data); never interpreted by the contract
+    pub encrypted_metadata: Option<Vec<u8>>,
+}
+
+/// Brier score of one vote in basis points: the squared gap between the
+/// stated confidence (0-100) and the outcome (100 if correct, 0 if not)
+pub fn brier_score_bps(confidence: u8, was_correct: bool) -> u32 {
+    let confidence = confidence.min(100) as i32;
+    let outcome = if was_correct { 100 } else { 0 };
+    ((confidence - outcome) * (confidence - outcome)) as u32
+}
+
+/// Days without activity after which a voter is reported as dormant
+pub const DEFAULT_DORMANCY_DAYS: u64 = 30;
+
+impl VoterInfo {
+    /// Count a submitted or committed vote as activity at `now`
+    pub fn record_vote(&mut self, now: Timestamp) {
+        self.total_votes = self.total_votes.saturating_add(1);
+        self.last_active_at = now;
+        self.last_vote_at = Some(now);
+    }
+    
+    /// Record a changed vote at `now` (activity, but not another vote)
+    pub fn record_vote_change(&mut self, now: Timestamp) {
+        self.last_active_at = now;
+        self.last_vote_at = Some(now);
+    }
+    
+    /// Fold a resolved vote's confidence into the running calibration score
+    pub fn record_calibration(&mut self, confidence: u8, was_correct: bool) {
+        let score = brier_score_bps(confidence, was_correct) as u128;
+        let count = self.calibrated_votes as u128;
+        let previous = self.calibration_score.unwrap_or(0) as u128;
+        
+        // Running mean, rounded to the nearest basis point
+        let mean = (previous * count + score + (count + 1) / 2) / (count + 1);
+        self.calibration_score = Some(mean as u32);
+        self.calibrated_votes = self.calibrated_votes.saturating_add(1);
+    }
+    
+    /// Share of the voter's votes that were correct (0-100, 0 without votes)
+    pub fn accuracy_percentage(&self) -> f64 {
+        if self.total_votes > 0 {
+            (self.correct_votes as f64 / self.total_votes as f64) * 100.0
+        } else {
+            0.0
+        }
+    }
+    
+    /// Selection power: stake (in attos) × reputation
+    pub fn power(&self) -> u128 {
+        let stake_value: u128 = self.stake.into();
+        stake_value.saturating_mul(self.reputation as u128)
+    }
+    
+    /// Stake counted towards weights and power, capped at `max_effective_stake`
+    pub fn effective_stake(&self, max_effective_stake: Option<Amount>) -> Amount {
+        match max_effective_stake {
+            Some(cap) => self.stake.min(cap),
+            None => self.stake,
+        }
+    }
+    
+    /// Selection power with the stake capped at `max_effective_stake`
+    pub fn capped_power(&self, max_effective_stake: Option<Amount>) -> u128 {
+        let stake_value: u128 = self.effective_stake(max_effective_stake).into();
+        stake_value.saturating_mul(self.reputation as u128)
+    }
+    
+    /// Lock `amount` of the voter's available stake
+    pub fn lock_stake(&mut self, amount: Amount) -> Result<(), String> {
+        let available_stake = self.stake.saturating_sub(self.locked_stake);
+        if available_stake < amount {
+            return Err(format!(
+                "Insufficient available stake: have {}, need {}",
+                available_stake, amount
+            ));
+        }
+        
+        self.locked_stake = self.locked_stake.saturating_add(amount);
+        Ok(())
+    }
+    
+    /// Unlock `amount` of the voter's locked stake
+    pub fn unlock_stake(&mut self, amount: Amount) -> Result<(), String> {
+        if self.locked_stake < amount {
+            return Err(format!(
+                "Cannot unlock more than locked: locked {}, requested {}",
+                self.locked_stake, amount
+            ));
+        }
+        
+        self.locked_stake = self.locked_stake.saturating_sub(amount);
+        Ok(())
+    }
+    
+    /// Check that at least `interval_secs` have passed since the last vote
+    /// 
+    /// An interval of 0 disables the check. Reveals of committed votes are
+    /// not new votes and are never subject to it.
+    pub fn check_vote_interval(&self, now: Timestamp, interval_secs: u64) -> Result<(), String> {
+        let Some(last_vote_at) = self.last_vote_at else {
+            return Ok(());
+        };
+        
+        let next_allowed = last_vote_at.saturating_add(TimeDelta::from_secs(interval_secs));
+        if now < next_allowed {
+            let retry_after_secs = next_allowed.delta_since(now).as_micros().div_ceil(1_000_000);
+            return Err(format!(
+                "Voting too frequently: retry after {} second(s)",
+                retry_after_secs
+            ));
+        }
+        Ok(())
+    }
+    
+    /// Whether the voter has had no activity for at least `days` days
+    pub fn is_dormant(&self, now: Timestamp, days: u64) -> bool {
+        now.delta_since(self.last_active_at) >= TimeDelta::from_secs(days.saturating_mul(86400))
+    }
+    
+    /// Why `ClaimRewards` would currently be rejected for this voter, if it would
+    /// 
+    /// Mirrors the contract's checks in order: protocol pause, voter status,
+    /// then whether anything is pending.
+    pub fn claim_blocked_reason(&self, protocol_paused: bool, pending_rewards: Amount) -> Option<String> {
+        if protocol_paused {
+            return Some("Protocol is paused".to_string());
+        }
+        if !self.is_active {
+            return Some("Voter is not active".to_string());
+        }
+        if pending_rewards == Amount::ZERO {
+            return Some("No pending rewards to claim".to_string());
+        }
+        None
+    }
 }
 
 /// Query/Market information
@@ -57,12 +213,74 @@ pub struct Query {
     /// Possible outcomes
     pub outcomes: Vec<String>,
     
+    /// Outcomes parsed as numbers, for median strategies (parsed once at creation)
+    pub numeric_outcomes: Option<Vec<f64>>,
+    
+    /// Synonyms accepted in votes (alias -> outcome); votes are stored as the outcome
+    pub outcome_aliases: BTreeMap<String, String>,
+    
     /// Decision strategy
     pub strategy: DecisionStrategy,
     
+    /// How the reward is split among correct voters (None = follow `strategy`)
+    pub reward_basis: Option<RewardBasis>,
+    
     /// Minimum votes required
     pub min_votes: usize,
     
+    /// Minimum number of distinct outcomes that must receive votes (optional)
+    /// 
+    /// Guards against sham queries where every voter picks a pre-agreed answer.
+    pub min_distinct_voted_outcomes: Option<usize>,
+    
+    /// Minimum number of votes the winning outcome needs (optional)
+    /// 
+    /// Guards against a 1-0 resolution on a sparsely voted query.
+    pub min_votes_for_winner: Option<usize>,
+    
+    /// Minimum number of distinct voter chains with tallied votes (optional)
+    /// 
+    /// Guards against a result decided by a handful of chains.
+    pub min_distinct_voters: Option<usize>,
+    
+    /// Largest share of the tallied voting power one address-prefix group
+    /// may hold (optional, basis points)
+    /// 
+    /// Voters whose chain IDs share their first `voter_group_prefix_len` hex
+    /// characters form a group: a rough guard against one actor's chains.
+    pub max_group_power_bps: Option<u32>,
+    
+    /// Minimum stake a voter needs to vote on this query (optional)
+    /// 
+    /// Raises the registration floor for high-value queries; applies to
+    /// selected voters too.
+    pub min_voter_stake: Option<Amount>,
+    
+    /// Whether voters may vote ABSTAIN_VOTE
+    /// 
+    /// Abstentions count as participation but are left out of the result
+    /// tally, rewards and correctness slashing.
+    pub allow_abstain: bool,
+    
+    /// Whether several outcomes may win together
+    /// 
+    /// Every outcome holding at least `multi_winner_threshold_bps` of the
+    /// tally wins, and voters for any winning outcome are correct.
+    pub multi_winner: bool,
+    
+    /// Whether the admin may resolve this query from external data,
+    /// bypassing voting (see `resolution_source`)
+    pub allow_external_resolution: bool,
+    
+    /// How a tie for the most votes resolves (Majority only)
+    pub tie_break: TieBreak,
+    
+    /// Browsing category (e.g. sports, weather, crypto)
+    pub category: Option<String>,
+    
+    /// Free-form tags for filtering
+    pub tags: Vec<String>,
+    
     /// Reward amount for correct voters
     pub reward_amount: Amount,
     
@@ -87,12 +305,19 @@ pub struct Query {
     /// Query status
     pub status: QueryStatus,
     
-    /// Resolved result (if resolved)
-    pub result: Option<String>,
+    /// Resolved result (if resolved or expired)
+    pub result: Option<ResolutionResult>,
+    
+    /// Human-readable form of `result` (kept for string-based clients)
+    pub result_display: Option<String>,
     
     /// Resolution timestamp
     pub resolved_at: Option<Timestamp>,
     
+    /// Reference to the external data the query was resolved from (e.g. a
+    /// URL or hash); None for queries resolved by voting
+    pub resolution_source: Option<String>,
+    
     /// Commit hashes (voter chain -> commit)
     pub commits: BTreeMap<ChainId, VoteCommit>,
     
@@ -110,6 +335,204 @@ pub struct Query {
     pub callback_data: Option<Vec<u8>>,
 }
 
+/// Compact summary of a resolved/expired query after archiving
+/// 
+/// Keeps the outcome but drops commits, votes and voter selection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedQuery {
+    pub id: u64,
+    pub description: String,
+    pub outcomes: Vec<String>,
+    pub strategy: DecisionStrategy,
+    pub reward_amount: Amount,
+    pub creator: ChainId,
+    pub created_at: Timestamp,
+    pub resolved_at: Option<Timestamp>,
+    pub status: QueryStatus,
+    pub result: Option<ResolutionResult>,
+    pub result_display: Option<String>,
+    
+    /// Number of revealed votes at archive time
+    pub vote_count: usize,
+}
+
+impl ArchivedQuery {
+    /// The winning outcome, if the query was resolved
+    pub fn final_result(&self) -> Option<&str> {
+        match self.status {
+            QueryStatus::Resolved => self.result_display.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Maximum number of failed cross-chain messages kept in the log
+pub const MAX_FAILED_MESSAGES: u64 = 100;
+
+/// Record of a cross-chain message whose handler returned an error
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FailedMessage {
+    /// Sequence number in the failed message log
+    pub id: u64,
+    
+    /// Message variant name (e.g. "SubmitVote")
+    pub kind: String,
+    
+    /// Chain that sent the message, if known
+    pub sender: Option<ChainId>,
+    
+    /// Error returned by the handler
+    pub error: String,
+    
+    /// When the message was executed
+    pub failed_at: Timestamp,
+}
+
+/// Log entry to drop when entry `id` is recorded, keeping the log bounded
+pub fn failed_message_evicted_id(id: u64) -> Option<u64> {
+    id.checked_sub(MAX_FAILED_MESSAGES)
+}
+
+/// Maximum number of parameter changes kept in the parameter history
+pub const MAX_PARAMETER_HISTORY: u64 = 100;
+
+/// Protocol parameters as set by one change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterChange {
+    /// Sequence number in the parameter history
+    pub id: u64,
+    
+    /// When the new parameters took effect
+    pub changed_at: Timestamp,
+    
+    /// Chain whose change set them (the proposer, for timelocked changes)
+    pub changed_by: ChainId,
+    
+    /// Parameters in effect from `changed_at`
+    pub params: ProtocolParameters,
+}
+
+/// History entry to drop when entry `id` is recorded, keeping the log bounded
+pub fn parameter_history_evicted_id(id: u64) -> Option<u64> {
+    id.checked_sub(MAX_PARAMETER_HISTORY)
+}
+
+/// Maximum number of entries kept in each of the reward and slash histories
+pub const MAX_FLOW_HISTORY: u64 = 1000;
+
+/// Maximum number of history entries returned per page
+pub const MAX_FLOW_HISTORY_PAGE: usize = 100;
+
+/// Tokens paid out or slashed when a query was resolved
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FlowEntry {
+    /// Sequence number in its history log
+    pub id: u64,
+    
+    /// Resolution time
+    pub timestamp: Timestamp,
+    
+    /// Query whose resolution produced the flow
+    pub query_id: u64,
+    
+    /// Total rewarded (or slashed) for the query
+    pub amount: Amount,
+}
+
+/// History entry to drop when entry `id` is recorded, keeping the log bounded
+pub fn flow_history_evicted_id(id: u64) -> Option<u64> {
+    id.checked_sub(MAX_FLOW_HISTORY)
+}
+
+/// Maximum number of entries kept in the activity log
+pub const MAX_ACTIVITY_LOG: u64 = 200;
+
+/// Kind of registry activity recorded in the activity log
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ActivityKind {
+    VoterRegistered,
+    QueryCreated,
+    VoteCommitted,
+    
+    /// A direct or revealed vote
+    VoteCast,
+    
+    /// Resolved by voting or from external data
+    QueryResolved,
+}
+
+impl ActivityKind {
+    /// Kind name as shown in GraphQL
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActivityKind::VoterRegistered => "VoterRegistered",
+            ActivityKind::QueryCreated => "QueryCreated",
+            ActivityKind::VoteCommitted => "VoteCommitted",
+            ActivityKind::VoteCast => "VoteCast",
+            ActivityKind::QueryResolved => "QueryResolved",
+        }
+    }
+}
+
+/// One entry of the registry's recent activity feed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ActivityEntry {
+    /// Sequence number in the activity log
+    pub id: u64,
+    
+    pub kind: ActivityKind,
+    
+    /// When the activity happened
+    pub timestamp: Timestamp,
+    
+    /// Chain that acted (registered voter, query creator or voter), if any
+    pub chain_id: Option<ChainId>,
+    
+    /// Query the activity concerns, if any
+    pub query_id: Option<u64>,
+    
+    /// Vote value or resolved result, where relevant
+    pub detail: Option<String>,
+}
+
+/// Log entry to drop when entry `id` is recorded, keeping the log bounded
+pub fn activity_log_evicted_id(id: u64) -> Option<u64> {
+    id.checked_sub(MAX_ACTIVITY_LOG)
+}
+
+/// One page of the entries with `from <= timestamp <= to`, oldest first
+/// 
+/// `limit` is capped at MAX_FLOW_HISTORY_PAGE.
+pub fn flow_history_page(
+    entries: impl IntoIterator<Item = FlowEntry>,
+    from: Timestamp,
+    to: Timestamp,
+    offset: usize,
+    limit: usize,
+) -> Vec<FlowEntry> {
+    entries
+        .into_iter()
+        .filter(|entry| entry.timestamp >= from && entry.timestamp <= to)
+        .skip(offset)
+        .take(limit.min(MAX_FLOW_HISTORY_PAGE))
+        .collect()
+}
+
+/// One page of the queries created by `creator`, in the order given
+pub fn queries_by_creator_page(
+    queries: impl IntoIterator<Item = Query>,
+    creator: &ChainId,
+    offset: usize,
+    limit: usize,
+) -> Vec<Query> {
+    queries
+        .into_iter()
+        .filter(|query| query.creator == *creator)
+        .skip(offset)
+        .take(limit)
+        .collect()
+}
+
 /// Callback information for cross-chain query resolution
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryCallback {
@@ -139,6 +562,42 @@ pub struct VoteCommit {
     pub revealed: bool,
 }
 
+/// Where a voter last used a commit hash
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommitHashUse {
+    pub query_id: u64,
+    
+    /// Deadline of that query; the hash may be reused once it has passed
+    pub deadline: Timestamp,
+}
+
+/// A query's creation deposit, held until the query is finalized
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CreationDeposit {
+    /// Account the deposit was collected from, and is refunded to
+    pub payer: Account,
+    pub amount: Amount,
+}
+
+/// Check that a voter may commit `query_id` with a hash last used as `previous`
+/// 
+/// A hash is rejected on the query it was already committed to, and on any
+/// other query until the earlier query's deadline, so one reveal cannot be
+/// replayed on a second open query.
+pub fn check_commit_hash_reuse(previous: Option<&CommitHashUse>, query_id: u64, now: Timestamp) -> Result<(), String> {
+    match previous {
+        Some(previous) if previous.query_id == query_id => Err(format!(
+            "Duplicate commit hash: already committed to query {}",
+            query_id
+        )),
+        Some(previous) if now <= previous.deadline => Err(format!(
+            "Commit hash already used on query {}; use a fresh salt",
+            previous.query_id
+        )),
+        _ => Ok(()),
+    }
+}
+
 /// Vote information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vote {
@@ -158,10 +617,18 @@ pub struct Vote {
     pub confidence: Option<u8>,
 }
 
+impl Vote {
+    /// Weight of the vote's confidence (0-100), counting a missing confidence as 100
+    pub fn confidence_weight(&self) -> u64 {
+        self.confidence.map_or(100, |c| c.min(100)) as u64
+    }
+}
+
 /// Decision strategy for resolving queries
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum DecisionStrategy {
     /// Simple majority
+    #[default]
     Majority,
     
     /// Median value (for numeric data)
@@ -172,707 +639,3541 @@ pub enum DecisionStrategy {
     
     /// Weighted by reputation
     WeightedByReputation,
+    
+    /// Median value with each vote weighted by its confidence (for numeric data)
+    ConfidenceWeightedMedian,
 }
 
-/// Voting phase for commit/reveal voting
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum VotingPhase {
-    /// Commit phase - voters submit commit hashes
-    Commit,
+impl DecisionStrategy {
+    /// Strategy name as used in GraphQL and cross-chain messages
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DecisionStrategy::Majority => "Majority",
+            DecisionStrategy::Median => "Median",
+            DecisionStrategy::WeightedByStake => "WeightedByStake",
+            DecisionStrategy::WeightedByReputation => "WeightedByReputation",
+            DecisionStrategy::ConfidenceWeightedMedian => "ConfidenceWeightedMedian",
+        }
+    }
     
-    /// Reveal phase - voters reveal their votes
-    Reveal,
+    /// Check the strategy is compatible with the given outcomes
+    pub fn validate_outcomes(&self, outcomes: &[String]) -> Result<(), String> {
+        self.parse_numeric_outcomes(outcomes).map(|_| ())
+    }
     
-    /// Voting completed, ready for resolution
-    Completed,
+    /// Parse the outcomes as numbers if the strategy needs them
+    /// 
+    /// Median strategies require every outcome to be a finite number; other
+    /// strategies work with any outcomes and get `None`.
+    pub fn parse_numeric_outcomes(&self, outcomes: &[String]) -> Result<Option<Vec<f64>>, String> {
+        match self {
+            DecisionStrategy::Median | DecisionStrategy::ConfidenceWeightedMedian => {
+                outcomes.iter()
+                    .map(|outcome| match outcome.parse::<f64>() {
+                        Ok(value) if value.is_finite() => Ok(value),
+                        _ => Err(format!(
+                            "{} strategy requires numeric outcomes, but '{}' is not numeric",
+                            self.as_str(), outcome
+                        )),
+                    })
+                    .collect::<Result<Vec<f64>, String>>()
+                    .map(Some)
+            },
+            _ => Ok(None),
+        }
+    }
 }
 
-/// Query status
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum QueryStatus {
-    /// Query is active and accepting votes
-    Active,
+/// How a query's reward is split among correct voters
+/// 
+/// Independent of the decision strategy, which only picks the result.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum RewardBasis {
+    /// Same share for every correct voter
+    Equal,
     
-    /// Query is resolved
-    Resolved,
+    /// In proportion to stake
+    Stake,
     
-    /// Query expired without resolution
-    Expired,
+    /// In proportion to reputation weight
+    Reputation,
     
-    /// Query cancelled
-    Cancelled,
+    /// In proportion to power (stake × reputation)
+    Power,
+    
+    /// In proportion to the vote's confidence (100 if unset)
+    Confidence,
 }
 
-/// Protocol parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ProtocolParameters {
-    /// Minimum stake required to register as voter
-    pub min_stake: Amount,
-    
-    /// Minimum votes required for query resolution
-    pub min_votes_default: usize,
-    
-    /// Default query duration (seconds)
-    pub default_query_duration: u64,
+impl RewardBasis {
+    /// Basis name as used in GraphQL
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RewardBasis::Equal => "Equal",
+            RewardBasis::Stake => "Stake",
+            RewardBasis::Reputation => "Reputation",
+            RewardBasis::Power => "Power",
+            RewardBasis::Confidence => "Confidence",
+        }
+    }
     
-    /// Reward percentage for correct voters (basis points, e.g., 10000 = 100%)
-    pub reward_percentage: u32,
+    /// Parse a basis name (case-insensitive)
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "equal" => Ok(RewardBasis::Equal),
+            "stake" => Ok(RewardBasis::Stake),
+            "reputation" => Ok(RewardBasis::Reputation),
+            "power" => Ok(RewardBasis::Power),
+            "confidence" => Ok(RewardBasis::Confidence),
+            _ => Err(format!(
+                "Invalid reward basis: {} (expected Equal, Stake, Reputation, Power or Confidence)",
+                name
+            )),
+        }
+    }
+}
+
+/// How a Majority query resolves a tie for the most votes
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum TieBreak {
+    /// First tied outcome in the query's outcome list
+    #[default]
+    OutcomeOrder,
     
-    /// Slash percentage for incorrect voters (basis points)
-    pub slash_percentage: u32,
+    /// Tied outcome backed by the most voter stake
+    HigherStake,
     
-    /// Protocol fee percentage (basis points)
-    pub protocol_fee: u32,
+    /// Tied outcome backed by the most voter reputation
+    HigherReputation,
     
-    /// ALTH Token application ID (for real token integration)
-    pub token_app_id: Option<linera_sdk::linera_base_types::ApplicationId>,
+    /// A tie resolves to NoConsensus
+    NoConsensus,
 }
 
-impl Default for ProtocolParameters {
-    fn default() -> Self {
-        Self {
-            min_stake: Amount::from_tokens(100),
-            min_votes_default: 3,
-            default_query_duration: 3600, // 1 hour (for faster testing)
-            reward_percentage: 1000,        // 10%
-            slash_percentage: 500,          // 5%
-            protocol_fee: 100,              // 1%
-            token_app_id: None,             // Set after token deployment
+impl TieBreak {
+    /// Mode name as used in GraphQL
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TieBreak::OutcomeOrder => "OutcomeOrder",
+            TieBreak::HigherStake => "HigherStake",
+            TieBreak::HigherReputation => "HigherReputation",
+            TieBreak::NoConsensus => "NoConsensus",
         }
     }
+    
+    /// Parse a mode name (case-insensitive)
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_ascii_lowercase().as_str() {
+            "outcomeorder" => Ok(TieBreak::OutcomeOrder),
+            "higherstake" => Ok(TieBreak::HigherStake),
+            "higherreputation" => Ok(TieBreak::HigherReputation),
+            "noconsensus" => Ok(TieBreak::NoConsensus),
+            _ => Err(format!(
+                "Invalid tie-break mode: {} (expected OutcomeOrder, HigherStake, HigherReputation or NoConsensus)",
+                name
+            )),
+        }
+    }
+    
+    /// Whether breaking a tie needs each outcome's aggregate backing
+    pub fn uses_backing(&self) -> bool {
+        matches!(self, TieBreak::HigherStake | TieBreak::HigherReputation)
+    }
 }
 
-/// The application state for Account-Based Oracle Registry
-#[derive(RootView)]
-#[view(context = ViewStorageContext)]
-pub struct OracleRegistryV2 {
-    // Voter management (chain-based - Microcard pattern!)
-    pub voters: MapView<ChainId, VoterInfo>,
-    pub total_stake: RegisterView<Amount>,
-    pub voter_count: RegisterView<u64>,
+/// Hash function used for vote commitments
+/// 
+/// Commit hashes are the lowercase hex digest of `value` bytes followed by
+/// `salt` bytes, hashed with the registry's configured algorithm.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum CommitHashAlgo {
+    /// SHA-256
+    #[default]
+    Sha256,
     
-    // Token holdings (actual ALETHEA tokens held by registry)
-    pub token_holdings: MapView<ChainId, Amount>,  // Voter -> Token balance held
-    pub total_tokens_held: RegisterView<Amount>,   // Total tokens in registry
-    
-    // Query management
-    pub next_query_id: RegisterView<u64>,
-    pub queries: MapView<u64, Query>,
-    pub active_queries: RegisterView<Vec<u64>>,
-    pub query_callbacks: MapView<u64, QueryCallback>,  // ← NEW: For cross-chain callbacks
-    
-    // Voting records (query_id -> voter_chain -> vote)
-    pub votes: MapView<(u64, ChainId), Vote>,
-    pub vote_counts: MapView<u64, usize>,
+    /// Keccak-256 (the Ethereum variant, not NIST SHA3-256)
+    Keccak256,
+}
+
+impl CommitHashAlgo {
+    /// Algorithm name as used in GraphQL and parameter files
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommitHashAlgo::Sha256 => "Sha256",
+            CommitHashAlgo::Keccak256 => "Keccak256",
+        }
+    }
     
-    // Rewards
-    pub reward_pool: RegisterView<Amount>,
-    pub pending_rewards: MapView<ChainId, Amount>,
-    pub total_rewards_distributed: RegisterView<Amount>,
+    /// Parse a supported algorithm name
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "Sha256" => Ok(CommitHashAlgo::Sha256),
+            "Keccak256" => Ok(CommitHashAlgo::Keccak256),
+            other => Err(format!(
+                "Unsupported commit hash algorithm '{}' (expected Sha256 or Keccak256)",
+                other
+            )),
+        }
+    }
     
-    // Protocol
-    pub parameters: RegisterView<ProtocolParameters>,
-    pub protocol_treasury: RegisterView<Amount>,
-    pub is_paused: RegisterView<bool>,
-    pub admin: RegisterView<Option<ChainId>>,
+    /// Compute the commit hash of `value` and `salt`
+    pub fn compute(&self, value: &str, salt: &str) -> String {
+        match self {
+            CommitHashAlgo::Sha256 => {
+                use sha2::{Digest, Sha256};
+                let mut hasher = Sha256::new();
+                hasher.update(value.as_bytes());
+                hasher.update(salt.as_bytes());
+                format!("{:x}", hasher.finalize())
+            }
+            CommitHashAlgo::Keccak256 => {
+                use sha3::{Digest, Keccak256};
+                let mut hasher = Keccak256::new();
+                hasher.update(value.as_bytes());
+                hasher.update(salt.as_bytes());
+                format!("{:x}", hasher.finalize())
+            }
+        }
+    }
     
-    // Statistics
-    pub total_queries_created: RegisterView<u64>,
-    pub total_queries_resolved: RegisterView<u64>,
-    pub total_votes_submitted: RegisterView<u64>,
+    /// Whether `value` and `salt` reveal the given commit hash
+    pub fn verify(&self, value: &str, salt: &str, commit_hash: &str) -> bool {
+        self.compute(value, salt) == commit_hash
+    }
 }
 
-impl OracleRegistryV2 {
-    /// Initialize the registry
-    pub async fn initialize(&mut self, params: ProtocolParameters, admin: ChainId) {
-        self.next_query_id.set(1);
-        self.total_stake.set(Amount::ZERO);
-        self.voter_count.set(0);
-        self.reward_pool.set(Amount::ZERO);
-        self.protocol_treasury.set(Amount::ZERO);
-        self.total_rewards_distributed.set(Amount::ZERO);
-        self.total_queries_created.set(0);
-        self.total_queries_resolved.set(0);
-        self.total_votes_submitted.set(0);
-        self.is_paused.set(false);
-        self.active_queries.set(Vec::new());
-        self.parameters.set(params);
-        self.admin.set(Some(admin));
+/// Reserved vote value for abstaining on queries with `allow_abstain`
+pub const ABSTAIN_VOTE: &str = "Abstain";
+
+/// Check that `outcomes` leave ABSTAIN_VOTE free when abstaining is allowed
+pub fn validate_abstain_outcomes(allow_abstain: bool, outcomes: &[String]) -> Result<(), String> {
+    if allow_abstain && outcomes.iter().any(|outcome| outcome == ABSTAIN_VOTE) {
+        return Err(format!(
+            "'{}' is reserved for abstentions and cannot be an outcome",
+            ABSTAIN_VOTE
+        ));
+    }
+    Ok(())
+}
+
+/// Check that multi-winner queries use a strategy that tallies outcomes
+pub fn validate_multi_winner(multi_winner: bool, strategy: &DecisionStrategy) -> Result<(), String> {
+    if multi_winner && matches!(strategy, DecisionStrategy::Median | DecisionStrategy::ConfidenceWeightedMedian) {
+        return Err(format!(
+            "{} strategy resolves to a single value and cannot have multiple winners",
+            strategy.as_str()
+        ));
+    }
+    Ok(())
+}
+
+/// Check that a tie-break mode other than outcome order is only set on
+/// Majority queries, the only strategy that uses it
+pub fn validate_tie_break(tie_break: TieBreak, strategy: &DecisionStrategy) -> Result<(), String> {
+    if tie_break != TieBreak::OutcomeOrder && *strategy != DecisionStrategy::Majority {
+        return Err(format!(
+            "Tie-break mode {} only applies to the Majority strategy, not {}",
+            tie_break.as_str(), strategy.as_str()
+        ));
+    }
+    Ok(())
+}
+
+/// Check the per-query voter diversity options
+pub fn validate_voter_diversity(min_distinct_voters: Option<usize>, max_group_power_bps: Option<u32>) -> Result<(), String> {
+    if min_distinct_voters == Some(0) {
+        return Err("Minimum distinct voters must be at least 1".to_string());
+    }
+    if let Some(bps) = max_group_power_bps {
+        if bps == 0 || bps > 10000 {
+            return Err("Max group power must be between 1 and 10000 basis points".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Address-prefix group of `voter`: the first `prefix_len` hex characters of its chain ID
+pub fn voter_group(voter: &ChainId, prefix_len: usize) -> String {
+    voter.to_string().chars().take(prefix_len).collect()
+}
+
+/// Check that no address-prefix group holds more than `max_bps` of the total power
+/// 
+/// `powers` are the tallied voters with their selection power.
+pub fn check_group_power(powers: &[(ChainId, u128)], prefix_len: usize, max_bps: u32) -> Result<(), String> {
+    let mut groups: BTreeMap<String, u128> = BTreeMap::new();
+    for (voter, power) in powers {
+        let group = groups.entry(voter_group(voter, prefix_len)).or_default();
+        *group = group.saturating_add(*power);
+    }
+    let total = groups.values().fold(0u128, |sum, power| sum.saturating_add(*power));
+    if total == 0 {
+        return Ok(());
     }
     
-    /// Check if registry is paused
-    pub async fn is_paused(&self) -> bool {
-        *self.is_paused.get()
+    for (group, power) in &groups {
+        let share_bps = power.saturating_mul(10000) / total;
+        if share_bps > max_bps as u128 {
+            return Err(format!(
+                "Voters with chain prefix {} hold {} basis points of the voting power, max {}",
+                group, share_bps, max_bps
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Maximum number of outcome aliases per query
+pub const MAX_OUTCOME_ALIASES: usize = 50;
+
+/// Check that every alias maps a new name onto one of `outcomes`
+/// 
+/// An alias may not shadow an outcome or ABSTAIN_VOTE, so official values
+/// always mean what they say.
+pub fn validate_outcome_aliases(aliases: &BTreeMap<String, String>, outcomes: &[String]) -> Result<(), String> {
+    if aliases.len() > MAX_OUTCOME_ALIASES {
+        return Err(format!("Too many outcome aliases (max {})", MAX_OUTCOME_ALIASES));
+    }
+    for (alias, outcome) in aliases {
+        if alias.is_empty() || alias.len() > 200 {
+            return Err("Outcome alias must be 1-200 characters".to_string());
+        }
+        if alias == ABSTAIN_VOTE || outcomes.contains(alias) {
+            return Err(format!("Alias '{}' is already a vote value", alias));
+        }
+        if !outcomes.contains(outcome) {
+            return Err(format!("Alias '{}' points to unknown outcome '{}'", alias, outcome));
+        }
+    }
+    Ok(())
+}
+
+/// Parse `alias=outcome` entries into an alias map
+pub fn parse_outcome_aliases(entries: &[String]) -> Result<BTreeMap<String, String>, String> {
+    let mut aliases = BTreeMap::new();
+    for entry in entries {
+        let (alias, outcome) = entry.split_once('=')
+            .ok_or_else(|| format!("Invalid outcome alias '{}': expected alias=outcome", entry))?;
+        if aliases.insert(alias.to_string(), outcome.to_string()).is_some() {
+            return Err(format!("Duplicate outcome alias: {}", alias));
+        }
+    }
+    Ok(aliases)
+}
+
+impl Query {
+    /// Official outcome for `value`: its alias target, or `value` itself
+    pub fn canonical_outcome(&self, value: &str) -> String {
+        self.outcome_aliases.get(value).cloned().unwrap_or_else(|| value.to_string())
     }
     
-    /// Get protocol parameters
-    pub async fn get_parameters(&self) -> ProtocolParameters {
-        self.parameters.get().clone()
+    /// Whether `value` is an abstention on this query
+    pub fn is_abstention(&self, value: &str) -> bool {
+        self.allow_abstain && value == ABSTAIN_VOTE
     }
     
-    /// Get admin chain ID
-    pub async fn get_admin(&self) -> Option<ChainId> {
-        *self.admin.get()
+    /// Check that `value` is one of the outcomes, or an allowed abstention
+    pub fn check_vote_value(&self, value: &str) -> Result<(), String> {
+        if self.is_abstention(value) || self.outcomes.iter().any(|outcome| outcome == value) {
+            return Ok(());
+        }
+        
+        let mut valid = self.outcomes.clone();
+        if self.allow_abstain {
+            valid.push(ABSTAIN_VOTE.to_string());
+        }
+        Err(format!(
+            "Invalid vote value '{}' for query {}. Valid outcomes: {}",
+            value, self.id, valid.join(", ")
+        ))
     }
     
-    /// Check if the given chain is the admin
-    pub async fn is_admin(&self, chain: &ChainId) -> bool {
-        match *self.admin.get() {
-            Some(admin) => admin == *chain,
-            None => false,
+    /// Numeric value of a vote on a median query
+    /// 
+    /// Uses the outcomes parsed at creation; queries stored without them
+    /// fall back to parsing the vote.
+    pub fn numeric_vote_value(&self, value: &str) -> Option<f64> {
+        match &self.numeric_outcomes {
+            Some(numeric) => self.outcomes.iter()
+                .position(|outcome| outcome == value)
+                .and_then(|index| numeric.get(index).copied()),
+            None => value.parse::<f64>().ok(),
         }
     }
     
-    /// Get voter info by chain ID
-    pub async fn get_voter(&self, chain: &ChainId) -> Option<VoterInfo> {
-        self.voters.get(chain).await.ok().flatten()
+    /// Whether `value` and `salt` reproduce `voter`'s stored commit hash
+    /// 
+    /// Lets a voter check they still hold the right salt before revealing.
+    /// Fails when the voter has no commit on this query.
+    pub fn verify_commit(&self, voter: &ChainId, value: &str, salt: &str, algo: CommitHashAlgo) -> Result<bool, String> {
+        let commit = self.commits.get(voter)
+            .ok_or_else(|| format!("No commit from {} on query {}", voter, self.id))?;
+        Ok(algo.verify(value, salt, &commit.commit_hash))
     }
     
-    /// Get query info
-    pub async fn get_query(&self, query_id: u64) -> Option<Query> {
-        self.queries.get(&query_id).await.ok().flatten()
+    /// Where `voter` stands on this query: selection, commit, reveal and vote
+    pub fn voter_status(&self, voter: &ChainId) -> VoterQueryStatus {
+        let commit = self.commits.get(voter);
+        let vote = self.votes.get(voter);
+        VoterQueryStatus {
+            selected: self.selected_voters.contains(voter),
+            committed: commit.is_some(),
+            revealed: commit.is_some_and(|commit| commit.revealed),
+            voted: vote.is_some(),
+            vote_value: vote.map(|vote| vote.value.clone()),
+            confidence: vote.and_then(|vote| vote.confidence),
+        }
     }
     
-    /// Get vote for a query by chain ID
-    pub async fn get_vote(&self, query_id: u64, voter_chain: &ChainId) -> Option<Vote> {
-        self.votes.get(&(query_id, *voter_chain)).await.ok().flatten()
+    /// Votes that count towards the result: all votes except abstentions
+    pub fn tallied_votes(&self) -> impl Iterator<Item = (&ChainId, &Vote)> + '_ {
+        self.votes.iter().filter(move |(_, vote)| !self.is_abstention(&vote.value))
     }
     
-    /// Get all votes for a query
-    pub async fn get_query_votes(&self, _query_id: u64) -> Vec<Vote> {
-        // This is a simplified version - in production, you'd want to iterate properly
-        Vec::new() // TODO: Implement proper iteration
+    /// Voters holding locked stake on this query: committers and voters, deduplicated
+    pub fn stake_lockers(&self) -> Vec<ChainId> {
+        self.commits.keys()
+            .chain(self.votes.keys())
+            .copied()
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect()
     }
     
-    /// Calculate reputation score based on voting accuracy
+    /// Check `voter` may cast a direct vote on this query
     /// 
-    /// Reputation is calculated using a weighted formula that considers:
-    /// - Voting accuracy (correct votes / total votes)
-    /// - Participation level (total votes)
-    /// - Recency bias (recent votes weighted more)
-    pub fn calculate_reputation(&self, voter_info: &VoterInfo) -> u32 {
-        if voter_info.total_votes == 0 {
-            return 50; // Default reputation for new voters
+    /// A second vote is rejected unless `allow_vote_change` is set, in which
+    /// case it replaces the first; votes revealed through commit/reveal can
+    /// never be replaced. Returns whether the vote is a change.
+    pub fn check_direct_vote(&self, voter: &ChainId, allow_vote_change: bool) -> Result<bool, String> {
+        match self.votes.get(voter) {
+            None => Ok(false),
+            Some(vote) if allow_vote_change && vote.salt.is_none() => Ok(true),
+            Some(vote) if allow_vote_change => Err(format!(
+                "Voter {} revealed a committed vote on query {}; it cannot be changed",
+                vote.voter, self.id
+            )),
+            Some(_) => Err(format!(
+                "Voter {} has already voted on query {}",
+                voter, self.id
+            )),
         }
-        
-        // Base accuracy score (0-100)
-        let accuracy = (voter_info.correct_votes as f64 / voter_info.total_votes as f64) * 100.0;
-        
-        // Apply participation bonus (up to 10 points for active voters)
-        // Voters with more than 100 votes get full bonus
-        let participation_bonus = if voter_info.total_votes >= 100 {
-            10.0
-        } else {
-            (voter_info.total_votes as f64 / 100.0) * 10.0
-        };
-        
-        // Calculate final reputation (capped at 100)
-        let reputation = (accuracy + participation_bonus).min(100.0);
-        
-        reputation as u32
     }
     
-    /// Update voter reputation after a query is resolved
+    /// Withdraw `voter` from this query, dropping them from the selected panel
+    /// and discarding their commit or vote
     /// 
-    /// This method should be called when a query is resolved to update
-    /// the reputation of voters who participated
-    pub async fn update_voter_reputation(
-        &mut self,
-        voter_chain: &ChainId,
-        was_correct: bool,
-    ) -> Result<(), String> {
-        let mut voter_info = self.get_voter(voter_chain).await
-            .ok_or_else(|| "Voter not found".to_string())?;
+    /// `min_votes` is lowered to what the remaining panel can still reach.
+    /// Returns whether the voter was involved in the query at all.
+    pub fn withdraw_voter(&mut self, voter: &ChainId) -> bool {
+        let panel_size = self.selected_voters.len();
+        self.selected_voters.retain(|selected| selected != voter);
+        let was_selected = self.selected_voters.len() != panel_size;
+        let had_commit = self.commits.remove(voter).is_some();
+        let had_vote = self.votes.remove(voter).is_some();
         
-        // Update vote counts
-        if was_correct {
-            voter_info.correct_votes += 1;
+        if was_selected && !self.selected_voters.is_empty() {
+            self.min_votes = self.min_votes.min(self.selected_voters.len());
         }
-        // total_votes is already incremented when vote is submitted
-        
-        // Recalculate reputation
-        voter_info.reputation = self.calculate_reputation(&voter_info);
-        
-        // Save updated voter info
-        self.voters.insert(voter_chain, voter_info)
-            .map_err(|e| format!("Failed to update voter reputation: {}", e))?;
         
+        was_selected || had_commit || had_vote
+    }
+    
+    /// Check that the query can be cancelled by `caller`
+    /// 
+    /// The creator or the admin may cancel an active query before any vote
+    /// has been revealed or submitted; committed votes are released.
+    pub fn check_cancellable(&self, caller: &ChainId, caller_is_admin: bool) -> Result<(), String> {
+        if self.creator != *caller && !caller_is_admin {
+            return Err("Unauthorized: only the query creator or admin can cancel it".to_string());
+        }
+        if self.status != QueryStatus::Active {
+            return Err(format!("Query {} is not active (status: {:?})", self.id, self.status));
+        }
+        if !self.votes.is_empty() {
+            return Err(format!("Query {} already has revealed votes", self.id));
+        }
         Ok(())
     }
     
-    /// Calculate reputation decay for inactive voters
+    /// Check that this query may be resolved to `outcome` from external data
     /// 
-    /// Voters who haven't voted recently should have their reputation
-    /// gradually decay to encourage active participation
-    pub fn calculate_reputation_with_decay(
-        &self,
-        voter_info: &VoterInfo,
-        current_time: Timestamp,
-    ) -> u32 {
-        let base_reputation = self.calculate_reputation(voter_info);
-        
-        // Calculate days since registration
-        // TimeDelta is in microseconds, convert to days
-        let micros_since_registration = current_time
-            .delta_since(voter_info.registered_at)
-            .as_micros();
-        let days_since_registration = micros_since_registration / (86400 * 1_000_000);
-        
-        // If voter has been registered for more than 30 days but has very few votes,
-        // apply decay
-        if days_since_registration > 30 && voter_info.total_votes < 10 {
-            let decay_factor = 0.9; // 10% decay
-            (base_reputation as f64 * decay_factor) as u32
-        } else {
-            base_reputation
+    /// Only active queries designated for external resolution qualify; the
+    /// outcome (or one of its aliases) must be among the query's outcomes.
+    pub fn check_external_resolution(&self, outcome: &str) -> Result<(), String> {
+        if self.status != QueryStatus::Active {
+            return Err(format!("Query {} is not active (status: {:?})", self.id, self.status));
         }
+        if !self.allow_external_resolution {
+            return Err(format!("Query {} is voting-only and cannot be resolved from external data", self.id));
+        }
+        let canonical = self.canonical_outcome(outcome);
+        if !self.outcomes.contains(&canonical) {
+            return Err(format!("Outcome '{}' is not an outcome of query {}", outcome, self.id));
+        }
+        Ok(())
     }
     
-    /// Get reputation tier for a voter
+    /// Check that `caller` may change this query's decision strategy at `now`
     /// 
-    /// Returns a tier classification based on reputation score:
-    /// - Novice: 0-40
-    /// - Intermediate: 41-70
-    /// - Expert: 71-90
-    /// - Master: 91-100
-    pub fn get_reputation_tier(&self, reputation: u32) -> &'static str {
-        match reputation {
-            0..=40 => "Novice",
-            41..=70 => "Intermediate",
-            71..=90 => "Expert",
-            91..=100 => "Master",
-            _ => "Unknown",
+    /// Only the creator may do so, while the query is active and still in its
+    /// commit phase, and only before any vote has been committed or submitted.
+    pub fn check_strategy_update(&self, caller: &ChainId, now: Timestamp) -> Result<(), String> {
+        if self.creator != *caller {
+            return Err("Unauthorized: only the query creator can change its strategy".to_string());
         }
+        if self.status != QueryStatus::Active {
+            return Err(format!("Query {} is not active (status: {:?})", self.id, self.status));
+        }
+        if self.phase != VotingPhase::Commit || now > self.commit_phase_end {
+            return Err(format!("Query {} is no longer in commit phase", self.id));
+        }
+        if !self.commits.is_empty() || !self.votes.is_empty() {
+            return Err(format!("Query {} already has votes", self.id));
+        }
+        Ok(())
     }
     
-    /// Calculate reputation weight for weighted voting strategies
+    /// Result a voter is assumed to back when projecting their reward
     /// 
-    /// Returns a weight multiplier based on reputation (0.5 to 2.0)
-    /// Higher reputation = higher weight
-    pub fn calculate_reputation_weight(&self, reputation: u32) -> f64 {
-        // Map reputation (0-100) to weight (0.5-2.0)
-        // Formula: weight = 0.5 + (reputation / 100) * 1.5
-        0.5 + (reputation as f64 / 100.0) * 1.5
+    /// Their own vote if they have voted, otherwise the outcome currently
+    /// leading by vote count (or the current median for Median queries).
+    /// Abstentions are ignored on both counts.
+    pub fn projected_result(&self, voter: &ChainId) -> Option<ResolutionResult> {
+        let tallied = || self.tallied_votes().map(|(_, vote)| vote);
+        let result = match self.votes.get(voter).filter(|vote| !self.is_abstention(&vote.value)) {
+            Some(vote) => match (&self.strategy, vote.value.parse::<f64>()) {
+                (DecisionStrategy::Median | DecisionStrategy::ConfidenceWeightedMedian, Ok(value)) => {
+                    ResolutionResult::NumericMedian(value)
+                }
+                _ => ResolutionResult::Outcome(vote.value.clone()),
+            },
+            None => match self.strategy {
+                DecisionStrategy::Median => ResolutionResult::from_median(tallied()),
+                DecisionStrategy::ConfidenceWeightedMedian => {
+                    ResolutionResult::from_confidence_weighted_median(tallied())
+                }
+                _ => ResolutionResult::from_majority(tallied()),
+            },
+        };
+        
+        match result {
+            ResolutionResult::NoConsensus | ResolutionResult::Expired => None,
+            result => Some(result),
+        }
     }
     
-    /// Get active queries
-    pub async fn get_active_queries(&self) -> Vec<u64> {
-        self.active_queries.get().clone()
+    /// Time at which the query may be resolved: reveal end plus the grace window
+    pub fn resolution_opens_at(&self, grace_secs: u64) -> Timestamp {
+        self.reveal_phase_end.saturating_add(TimeDelta::from_secs(grace_secs))
     }
     
-    /// Get pending rewards for a voter by chain ID
-    pub async fn get_pending_rewards(&self, voter_chain: &ChainId) -> Amount {
-        self.pending_rewards.get(voter_chain).await.ok().flatten().unwrap_or(Amount::ZERO)
+    /// Whether a reveal arriving at `now` is still accepted
+    /// 
+    /// Reveals keep landing during the grace window so in-flight reveals are
+    /// counted before the query can be resolved.
+    pub fn accepts_reveal_at(&self, now: Timestamp, grace_secs: u64) -> bool {
+        now < self.resolution_opens_at(grace_secs)
     }
     
-    /// Lock stake for a voter (when they vote on a query)
-    pub async fn lock_stake(&mut self, voter_chain: &ChainId, amount: Amount) -> Result<(), String> {
-        let mut voter_info = self.get_voter(voter_chain).await
-            .ok_or_else(|| "Voter not found".to_string())?;
-        
-        // Calculate available stake using saturating_sub on Amount directly
-        let available_stake = voter_info.stake.saturating_sub(voter_info.locked_stake);
-        
-        if available_stake < amount {
+    /// Whether `vote` was revealed in the grace window after the reveal phase
+    /// 
+    /// Direct votes carry no salt and are never late.
+    pub fn is_late_reveal(&self, vote: &Vote) -> bool {
+        vote.salt.is_some() && vote.timestamp > self.reveal_phase_end
+    }
+    
+    /// Weight of `vote` in the result (basis points)
+    /// 
+    /// Late reveals count at `late_reveal_weight_bps`; every other vote counts in full.
+    pub fn vote_weight_bps(&self, vote: &Vote, late_reveal_weight_bps: u32) -> u32 {
+        if self.is_late_reveal(vote) {
+            late_reveal_weight_bps.min(10000)
+        } else {
+            10000
+        }
+    }
+    
+    /// Tallied votes with their weight in the result (basis points)
+    /// 
+    /// Votes weighted to zero are left out.
+    pub fn weighted_votes(&self, late_reveal_weight_bps: u32) -> impl Iterator<Item = (&Vote, u32)> + '_ {
+        self.tallied_votes()
+            .map(move |(_, vote)| (vote, self.vote_weight_bps(vote, late_reveal_weight_bps)))
+            .filter(|(_, weight)| *weight > 0)
+    }
+    
+    /// Weighted votes with their numeric value, skipping non-numeric votes
+    pub fn numeric_votes(&self, late_reveal_weight_bps: u32) -> impl Iterator<Item = (&Vote, f64, u32)> + '_ {
+        self.weighted_votes(late_reveal_weight_bps)
+            .filter_map(move |(vote, weight)| Some((vote, self.numeric_vote_value(&vote.value)?, weight)))
+    }
+    
+    /// Check that the reveal phase and its grace window are over at `now`
+    pub fn check_resolution_window(&self, now: Timestamp, grace_secs: u64) -> Result<(), String> {
+        let opens_at = self.resolution_opens_at(grace_secs);
+        if now < opens_at {
             return Err(format!(
-                "Insufficient available stake: have {}, need {}",
-                available_stake, amount
+                "Query {} cannot be resolved before the reveal grace window ends (opens at: {:?}, current: {:?})",
+                self.id, opens_at, now
             ));
         }
-        
-        // Add to locked stake using saturating_add on Amount directly
-        voter_info.locked_stake = voter_info.locked_stake.saturating_add(amount);
-        self.voters.insert(voter_chain, voter_info)
-            .map_err(|e| format!("Failed to update voter: {}", e))?;
-        
         Ok(())
     }
     
-    /// Unlock stake for a voter (when query is resolved)
-    pub async fn unlock_stake(&mut self, voter_chain: &ChainId, amount: Amount) -> Result<(), String> {
-        let mut voter_info = self.get_voter(voter_chain).await
-            .ok_or_else(|| "Voter not found".to_string())?;
-        
-        if voter_info.locked_stake < amount {
+    /// Whether the query may be expired at `now`
+    /// 
+    /// Expiry waits for both the deadline and the reveal grace window, since a
+    /// reveal in the window can still bring the query up to `min_votes`.
+    pub fn may_expire_at(&self, now: Timestamp, grace_secs: u64) -> bool {
+        now >= self.deadline && now >= self.resolution_opens_at(grace_secs)
+    }
+    
+    /// The strategy to resolve this query with
+    /// 
+    /// Falls back to Majority when the query's strategy does not fit its
+    /// outcomes, e.g. a default Median strategy on a Yes/No market query.
+    pub fn strategy_for_resolution(&self) -> DecisionStrategy {
+        match self.strategy.validate_outcomes(&self.outcomes) {
+            Ok(()) => self.strategy.clone(),
+            Err(_) => DecisionStrategy::Majority,
+        }
+    }
+    
+    /// Check that a query listed in `active_queries` is really Active
+    /// 
+    /// Cancelled, resolved and expired queries are removed from the list when
+    /// they close, so a failure here points at a missed removal.
+    pub fn check_listed_active(&self) -> Result<(), String> {
+        if self.status != QueryStatus::Active {
             return Err(format!(
-                "Cannot unlock more than locked: locked {}, requested {}",
-                voter_info.locked_stake, amount
+                "Query {} is {} but still listed in active queries",
+                self.id, self.status.as_str()
             ));
         }
-        
-        // Subtract from locked stake using saturating_sub on Amount directly
-        voter_info.locked_stake = voter_info.locked_stake.saturating_sub(amount);
-        self.voters.insert(voter_chain, voter_info)
-            .map_err(|e| format!("Failed to update voter: {}", e))?;
-        
         Ok(())
     }
     
-    /// Get available (unlocked) stake for a voter
-    pub async fn get_available_stake(&self, voter_chain: &ChainId) -> Amount {
-        match self.get_voter(voter_chain).await {
-            Some(info) => {
-                // Use Amount's saturating_sub directly to avoid double conversion
-                info.stake.saturating_sub(info.locked_stake)
-            },
-            None => Amount::ZERO,
+    /// What maintenance should do with this query at `now`, if anything
+    /// 
+    /// Queries past their reveal grace window are resolved when they have
+    /// enough votes and expired otherwise; queries past their deadline and
+    /// grace window without enough votes are expired.
+    pub fn settlement_at(&self, now: Timestamp, grace_secs: u64) -> Option<Settlement> {
+        if self.status != QueryStatus::Active {
+            return None;
         }
-    }
-    
-    /// Get comprehensive reputation statistics for a voter
-    pub async fn get_reputation_stats(&self, voter_chain: &ChainId) -> Option<ReputationStats> {
-        let voter_info = self.get_voter(voter_chain).await?;
         
-        Some(ReputationStats {
-            reputation: voter_info.reputation,
-            tier: self.get_reputation_tier(voter_info.reputation).to_string(),
-            weight: self.calculate_reputation_weight(voter_info.reputation),
-            total_votes: voter_info.total_votes,
-            correct_votes: voter_info.correct_votes,
-            accuracy_percentage: if voter_info.total_votes > 0 {
-                (voter_info.correct_votes as f64 / voter_info.total_votes as f64) * 100.0
-            } else {
-                0.0
-            },
-        })
+        let has_min_votes = self.votes.len() >= self.min_votes;
+        if self.phase == VotingPhase::Reveal && self.check_resolution_window(now, grace_secs).is_ok() {
+            return Some(if has_min_votes { Settlement::Resolve } else { Settlement::Expire });
+        }
+        if !has_min_votes && self.may_expire_at(now, grace_secs) {
+            return Some(Settlement::Expire);
+        }
+        None
     }
     
-    /// Calculate reward for a voter based on multiple factors
-    /// 
-    /// This method calculates the reward amount for a voter who voted correctly,
-    /// taking into account:
-    /// - Base reward (query reward amount divided among correct voters)
-    /// - Reputation multiplier (higher reputation = higher reward)
-    /// - Stake multiplier (higher stake = higher reward)
-    /// - Protocol fee deduction
+    /// The phase this query should move to at `now`, without resolving it
     /// 
-    /// Returns the final reward amount for the voter
-    pub fn calculate_voter_reward(
-        &self,
-        base_reward: Amount,
-        voter_info: &VoterInfo,
-        params: &ProtocolParameters,
-    ) -> Amount {
-        let base_value: u128 = base_reward.into();
-        
-        // Calculate reputation multiplier (0.8 to 1.2)
-        // Higher reputation gets up to 20% bonus, lower gets up to 20% penalty
-        let reputation_multiplier = 0.8 + (voter_info.reputation as f64 / 100.0) * 0.4;
+    /// Commit moves to Reveal once `commit_phase_end` has passed; Reveal moves
+    /// to Completed once the reveal grace window is over.
+    pub fn next_phase_at(&self, now: Timestamp, grace_secs: u64) -> Result<VotingPhase, String> {
+        if self.status != QueryStatus::Active {
+            return Err(format!("Query {} is not active (status: {:?})", self.id, self.status));
+        }
         
-        // Apply reputation multiplier
-        let reward_with_reputation = (base_value as f64 * reputation_multiplier) as u128;
+        match self.phase {
+            VotingPhase::Commit if now > self.commit_phase_end => Ok(VotingPhase::Reveal),
+            VotingPhase::Commit => Err(format!(
+                "Query {} commit phase has not ended yet (ends at: {:?}, current: {:?})",
+                self.id, self.commit_phase_end, now
+            )),
+            VotingPhase::Reveal if !self.accepts_reveal_at(now, grace_secs) => Ok(VotingPhase::Completed),
+            VotingPhase::Reveal => Err(format!(
+                "Query {} reveal phase has not ended yet (ends at: {:?}, current: {:?})",
+                self.id, self.resolution_opens_at(grace_secs), now
+            )),
+            VotingPhase::Completed => Err(format!("Query {} voting is already completed", self.id)),
+        }
+    }
+    
+    /// The winning outcome, once the query is resolved
+    pub fn final_result(&self) -> Option<&str> {
+        match self.status {
+            QueryStatus::Resolved => self.result_display.as_deref(),
+            _ => None,
+        }
+    }
+    
+    /// Whether this query is resolved/expired/without consensus and was closed before `cutoff`
+    pub fn is_archivable(&self, cutoff: Timestamp) -> bool {
+        let closed = matches!(self.status, QueryStatus::Resolved | QueryStatus::Expired | QueryStatus::NoConsensus);
+        closed && self.resolved_at.is_some_and(|at| at < cutoff)
+    }
+    
+    /// Compact summary kept once the query is archived
+    pub fn to_archived(&self) -> ArchivedQuery {
+        ArchivedQuery {
+            id: self.id,
+            description: self.description.clone(),
+            outcomes: self.outcomes.clone(),
+            strategy: self.strategy.clone(),
+            reward_amount: self.reward_amount,
+            creator: self.creator,
+            created_at: self.created_at,
+            resolved_at: self.resolved_at,
+            status: self.status.clone(),
+            result: self.result.clone(),
+            result_display: self.result_display.clone(),
+            vote_count: self.votes.len(),
+        }
+    }
+    
+    /// Whether the query belongs to `category` (case-insensitive)
+    pub fn in_category(&self, category: &str) -> bool {
+        self.category.as_deref().is_some_and(|c| c.eq_ignore_ascii_case(category))
+    }
+    
+    /// Number of distinct outcomes that received at least one vote (abstentions excluded)
+    pub fn distinct_voted_outcomes(&self) -> usize {
+        self.tallied_votes()
+            .map(|(_, vote)| vote.value.as_str())
+            .collect::<std::collections::BTreeSet<_>>()
+            .len()
+    }
+    
+    /// Check the query's distinct voted outcome requirement, if any
+    pub fn check_distinct_voted_outcomes(&self) -> Result<(), String> {
+        let Some(required) = self.min_distinct_voted_outcomes else {
+            return Ok(());
+        };
         
-        // Deduct protocol fee (in basis points, e.g., 100 = 1%)
-        let fee_multiplier = 1.0 - (params.protocol_fee as f64 / 10000.0);
-        let final_reward = (reward_with_reputation as f64 * fee_multiplier) as u128;
+        let distinct = self.distinct_voted_outcomes();
+        if distinct < required {
+            return Err(format!(
+                "Only {} distinct outcome(s) received votes, {} required",
+                distinct, required
+            ));
+        }
+        Ok(())
+    }
+    
+    /// Check that enough distinct voter chains have tallied votes, if required
+    pub fn check_distinct_voters(&self) -> Result<(), String> {
+        let Some(required) = self.min_distinct_voters else {
+            return Ok(());
+        };
         
-        Amount::from_tokens(final_reward)
+        // Votes are keyed by chain, so each tallied vote is a distinct chain
+        let distinct = self.tallied_votes().count();
+        if distinct < required {
+            return Err(format!(
+                "Only {} distinct voter chain(s) voted, {} required",
+                distinct, required
+            ));
+        }
+        Ok(())
     }
     
-    /// Calculate slash amount for incorrect voters
+    /// Check a voter's stake against the query's stake floor, if any
+    pub fn check_voter_stake(&self, stake: Amount) -> Result<(), String> {
+        match self.min_voter_stake {
+            Some(required) if stake < required => Err(format!(
+                "Insufficient stake to vote on query {}: have {}, need {}",
+                self.id, stake, required
+            )),
+            _ => Ok(()),
+        }
+    }
+    
+    /// Resolved outcome and resolution time, as sent in the query's callback
     /// 
-    /// Voters who vote incorrectly may have a portion of their stake slashed
-    /// based on the protocol's slash_percentage parameter.
+    /// Every callback (at resolution, on resend and on request) is built from
+    /// this, so a market always receives the same payload for a query. The
+    /// outcome is `ResolutionResult::callback_outcome`; queries recorded
+    /// before typed results fall back to their display string.
+    pub fn resolved_outcome(&self) -> Result<(String, Timestamp), String> {
+        match (&self.status, &self.result, self.resolved_at) {
+            (QueryStatus::Resolved | QueryStatus::NoConsensus, Some(result), Some(resolved_at)) => result
+                .callback_outcome()
+                .map(|outcome| (outcome, resolved_at))
+                .map_err(|e| format!("Query {}: {}", self.id, e)),
+            (QueryStatus::Resolved, None, Some(resolved_at)) => match &self.result_display {
+                Some(outcome) => Ok((outcome.clone(), resolved_at)),
+                None => Err(format!("Query {} has no recorded result", self.id)),
+            },
+            (QueryStatus::NoConsensus, None, Some(resolved_at)) => Ok((NO_CONSENSUS_OUTCOME.to_string(), resolved_at)),
+            (QueryStatus::Resolved, _, _) => Err(format!("Query {} has no recorded result", self.id)),
+            (QueryStatus::Active, _, _) => Err(format!("Query {} is not resolved yet", self.id)),
+            (status, _, _) => Err(format!("Query {} is {} and has no result", self.id, status.as_str())),
+        }
+    }
+    
+    /// Check the query's minimum vote count for each winning outcome, if any
     /// 
-    /// The slash amount is calculated as a percentage of the voter's total stake.
-    /// For example, with a 5% slash rate (500 basis points):
-    /// - Voter with 1000 tokens stake → 50 tokens slashed
-    /// - Voter with 500 tokens stake → 25 tokens slashed
-    pub fn calculate_slash_amount(
-        &self,
-        voter_info: &VoterInfo,
-        params: &ProtocolParameters,
-    ) -> Amount {
-        let stake_value: u128 = voter_info.stake.into();
-        
-        // Calculate slash amount (in basis points, e.g., 500 = 5%)
-        let slash_multiplier = params.slash_percentage as f64 / 10000.0;
-        let slash_amount = (stake_value as f64 * slash_multiplier) as u128;
+    /// Only outcome results have winners to count votes for; numeric
+    /// medians and no-consensus results pass unchanged.
+    pub fn check_winner_votes(&self, result: &ResolutionResult) -> Result<(), String> {
+        let Some(required) = self.min_votes_for_winner else {
+            return Ok(());
+        };
+        let winners = match result {
+            ResolutionResult::Outcome(winner) => std::slice::from_ref(winner),
+            ResolutionResult::Outcomes(winners) => winners.as_slice(),
+            _ => return Ok(()),
+        };
         
-        Amount::from_tokens(slash_amount)
+        for winner in winners {
+            let winner_votes = self.tallied_votes().filter(|(_, vote)| &vote.value == winner).count();
+            if winner_votes < required {
+                return Err(format!(
+                    "Winning outcome '{}' received {} vote(s), {} required",
+                    winner, winner_votes, required
+                ));
+            }
+        }
+        Ok(())
     }
     
-    /// Check if voter's stake would fall below minimum after slashing
+    /// Corrected vote count if `stored` disagrees with this query's votes
     /// 
-    /// Returns true if the voter should be automatically deactivated after slashing
-    pub fn should_deactivate_after_slash(
-        &self,
-        voter_info: &VoterInfo,
-        slash_amount: Amount,
-        params: &ProtocolParameters,
-    ) -> bool {
-        let stake_value: u128 = voter_info.stake.into();
-        let slash_value: u128 = slash_amount.into();
-        let remaining_stake = stake_value.saturating_sub(slash_value);
-        let min_stake_value: u128 = params.min_stake.into();
-        
-        remaining_stake < min_stake_value
+    /// `query.votes` is the source of truth; returns `None` when `stored`
+    /// already matches it.
+    pub fn vote_count_correction(&self, stored: Option<usize>) -> Option<usize> {
+        let actual = self.votes.len();
+        if stored == Some(actual) {
+            None
+        } else {
+            Some(actual)
+        }
+    }
+}
+
+/// Maximum number of tags per query
+pub const MAX_QUERY_TAGS: usize = 10;
+
+/// Maximum length of a query category or tag
+pub const MAX_QUERY_LABEL_LEN: usize = 32;
+
+/// Validate a query's category and tags
+pub fn validate_query_labels(category: &Option<String>, tags: &[String]) -> Result<(), String> {
+    if let Some(category) = category {
+        if category.trim().is_empty() {
+            return Err("Category cannot be empty".to_string());
+        }
+        if category.len() > MAX_QUERY_LABEL_LEN {
+            return Err(format!("Category too long (max {} characters)", MAX_QUERY_LABEL_LEN));
+        }
     }
     
-    /// Calculate total slashing statistics for a query
+    if tags.len() > MAX_QUERY_TAGS {
+        return Err(format!("Too many tags: {} (max {})", tags.len(), MAX_QUERY_TAGS));
+    }
+    for tag in tags {
+        if tag.trim().is_empty() {
+            return Err("Tag cannot be empty".to_string());
+        }
+        if tag.len() > MAX_QUERY_LABEL_LEN {
+            return Err(format!("Tag '{}' too long (max {} characters)", tag, MAX_QUERY_LABEL_LEN));
+        }
+    }
+    
+    Ok(())
+}
+
+/// Maximum length of a referral code
+pub const MAX_REFERRAL_CODE_LEN: usize = 32;
+
+/// Validate a referral code and normalize it to lowercase
+/// 
+/// Codes are 1-32 ASCII letters, digits, hyphens or underscores and are
+/// matched case-insensitively.
+pub fn normalize_referral_code(code: &str) -> Result<String, String> {
+    if code.is_empty() {
+        return Err("Referral code cannot be empty".to_string());
+    }
+    if code.len() > MAX_REFERRAL_CODE_LEN {
+        return Err(format!("Referral code too long (max {} characters)", MAX_REFERRAL_CODE_LEN));
+    }
+    if !code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err("Referral code may only contain letters, digits, '-' and '_'".to_string());
+    }
+    Ok(code.to_ascii_lowercase())
+}
+
+/// Maximum size of a voter's encrypted metadata blob, in bytes
+pub const MAX_ENCRYPTED_METADATA_LEN: usize = 1024;
+
+/// Check a voter's encrypted metadata blob against the size limits
+/// 
+/// Only the size is checked; the contents stay opaque to the contract.
+pub fn validate_encrypted_metadata(blob: &[u8]) -> Result<(), String> {
+    if blob.is_empty() {
+        return Err("Encrypted metadata cannot be empty".to_string());
+    }
+    if blob.len() > MAX_ENCRYPTED_METADATA_LEN {
+        return Err(format!(
+            "Encrypted metadata too large ({} bytes, max {})",
+            blob.len(), MAX_ENCRYPTED_METADATA_LEN
+        ));
+    }
+    Ok(())
+}
+
+/// Encode bytes as lowercase hex
+pub fn bytes_to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decode a hex string (`0x` prefix optional) into bytes
+pub fn hex_to_bytes(input: &str) -> Result<Vec<u8>, String> {
+    let trimmed = input.trim();
+    let hex = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    
+    if hex.len() % 2 != 0 {
+        return Err(format!("expected an even number of hex characters, got {}", hex.len()));
+    }
+    if let Some((position, c)) = hex.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(format!("'{}' at position {} is not a hex digit", c, position));
+    }
+    
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| e.to_string()))
+        .collect()
+}
+
+/// Count the voters registered with a referral code (case-insensitive)
+pub fn count_referrals<'a>(voters: impl IntoIterator<Item = &'a VoterInfo>, code: &str) -> usize {
+    let code = code.to_ascii_lowercase();
+    voters.into_iter()
+        .filter(|voter| voter.referral.as_deref() == Some(code.as_str()))
+        .count()
+}
+
+/// Typed outcome of a query resolution
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ResolutionResult {
+    /// One of the query's outcomes won
+    Outcome(String),
+    
+    /// Median of the numeric votes (Median strategy)
+    NumericMedian(f64),
+    
+    /// Votes did not produce a winning outcome
+    NoConsensus,
+    
+    /// Query expired before it could be resolved
+    Expired,
+    
+    /// Several of the query's outcomes won (multi-winner queries)
+    Outcomes(Vec<String>),
+}
+
+impl ResolutionResult {
+    /// Pick the outcome with the highest tally
     /// 
-    /// Returns (total_slashed, voters_slashed, voters_deactivated)
-    pub fn calculate_slashing_stats(
-        &self,
-        incorrect_voters: &[(ChainId, VoterInfo)],
-        params: &ProtocolParameters,
-    ) -> (Amount, usize, usize) {
-        let mut total_slashed = 0u128;
-        let mut voters_slashed = 0;
-        let mut voters_deactivated = 0;
+    /// Returns `NoConsensus` when nothing was tallied. Ties go to the last
+    /// tied outcome iterated, so callers pass ordered (BTreeMap) tallies.
+    pub fn from_tally<W: PartialOrd>(tally: impl IntoIterator<Item = (String, W)>) -> Self {
+        tally
+            .into_iter()
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(value, _)| ResolutionResult::Outcome(value))
+            .unwrap_or(ResolutionResult::NoConsensus)
+    }
+    
+    /// Pick the outcome with the highest tally, settling a tie for the top
+    /// with `tie_break`
+    /// 
+    /// `backing` holds each outcome's aggregate voter stake or reputation for
+    /// `HigherStake`/`HigherReputation`; a tie it cannot settle falls back to
+    /// outcome order. Returns `NoConsensus` when nothing was tallied.
+    pub fn from_tally_with_tie_break(
+        tally: &BTreeMap<String, u64>,
+        outcomes: &[String],
+        tie_break: TieBreak,
+        backing: &BTreeMap<String, u128>,
+    ) -> Self {
+        let top = match tally.values().max() {
+            Some(top) => *top,
+            None => return ResolutionResult::NoConsensus,
+        };
+        let mut tied: Vec<&String> = tally.iter()
+            .filter(|(_, weight)| **weight == top)
+            .map(|(value, _)| value)
+            .collect();
         
-        for (_, voter_info) in incorrect_voters {
-            let slash_amount = self.calculate_slash_amount(voter_info, params);
-            let slash_value: u128 = slash_amount.into();
+        if tied.len() > 1 {
+            if tie_break == TieBreak::NoConsensus {
+                return ResolutionResult::NoConsensus;
+            }
             
-            if slash_value > 0 {
-                voters_slashed += 1;
-                total_slashed += slash_value;
-                
-                if self.should_deactivate_after_slash(voter_info, slash_amount, params) {
-                    voters_deactivated += 1;
-                }
+            // Outcome order, listed outcomes first
+            let position = |value: &String| outcomes.iter().position(|outcome| outcome == value).unwrap_or(usize::MAX);
+            tied.sort_by_key(|value| position(value));
+            
+            if tie_break.uses_backing() {
+                let backing_of = |value: &String| backing.get(value).copied().unwrap_or(0);
+                let best = tied.iter().map(|value| backing_of(value)).max().unwrap_or(0);
+                tied.retain(|value| backing_of(value) == best);
             }
         }
         
-        (Amount::from_tokens(total_slashed), voters_slashed, voters_deactivated)
+        ResolutionResult::Outcome(tied[0].clone())
     }
     
-    /// Calculate total reward pool for a query
+    /// Every outcome holding at least `threshold_bps` of the total tally
     /// 
-    /// This includes the base reward amount plus any protocol fees collected
-    pub fn calculate_total_reward_pool(
-        &self,
-        query_reward: Amount,
-        protocol_fees: Amount,
-    ) -> Amount {
-        let reward_value: u128 = query_reward.into();
-        let fees_value: u128 = protocol_fees.into();
+    /// A single qualifying outcome is reported as `Outcome`. Returns
+    /// `NoConsensus` when no outcome reaches the threshold.
+    pub fn from_tally_above_share(tally: impl IntoIterator<Item = (String, f64)>, threshold_bps: u32) -> Self {
+        let tally: Vec<(String, f64)> = tally.into_iter().collect();
+        let total: f64 = tally.iter().map(|(_, weight)| weight).sum();
+        if total <= 0.0 {
+            return ResolutionResult::NoConsensus;
+        }
         
-        Amount::from_tokens(reward_value + fees_value)
+        let mut winners: Vec<String> = tally
+            .into_iter()
+            .filter(|(_, weight)| weight * 10000.0 >= total * threshold_bps as f64)
+            .map(|(value, _)| value)
+            .collect();
+        
+        match winners.len() {
+            0 => ResolutionResult::NoConsensus,
+            1 => ResolutionResult::Outcome(winners.remove(0)),
+            _ => ResolutionResult::Outcomes(winners),
+        }
     }
     
-    /// Calculate protocol fee from reward amount
-    pub fn calculate_protocol_fee(
-        &self,
-        reward_amount: Amount,
-        params: &ProtocolParameters,
-    ) -> Amount {
-        let reward_value: u128 = reward_amount.into();
-        
-        // Calculate fee (in basis points, e.g., 100 = 1%)
-        let fee_multiplier = params.protocol_fee as f64 / 10000.0;
-        let fee_amount = (reward_value as f64 * fee_multiplier) as u128;
-        
-        Amount::from_tokens(fee_amount)
+    /// Simple majority over the given votes
+    pub fn from_majority<'a>(votes: impl IntoIterator<Item = &'a Vote>) -> Self {
+        let mut vote_counts: BTreeMap<String, usize> = BTreeMap::new();
+        for vote in votes {
+            *vote_counts.entry(vote.value.clone()).or_insert(0) += 1;
+        }
+        Self::from_tally(vote_counts)
     }
     
-    /// Calculate stake-weighted reward distribution
+    /// Median of the votes that parse as numbers
     /// 
-    /// Distributes rewards proportionally based on voter stakes
-    /// Returns a map of voter -> reward amount
-    pub fn calculate_stake_weighted_rewards(
-        &self,
-        total_reward: Amount,
-        correct_voters: &[(ChainId, VoterInfo)],
-        params: &ProtocolParameters,
-    ) -> std::collections::BTreeMap<ChainId, Amount> {
-        let mut rewards = std::collections::BTreeMap::new();
+    /// Returns `NoConsensus` when no vote is numeric.
+    pub fn from_median<'a>(votes: impl IntoIterator<Item = &'a Vote>) -> Self {
+        let mut numeric_votes: Vec<f64> = votes
+            .into_iter()
+            .filter_map(|vote| vote.value.parse::<f64>().ok())
+            .collect();
         
-        if correct_voters.is_empty() {
-            return rewards;
+        if numeric_votes.is_empty() {
+            return ResolutionResult::NoConsensus;
         }
         
-        // Calculate total stake of correct voters
-        let total_stake: u128 = correct_voters
-            .iter()
-            .map(|(_, info)| {
-                let stake: u128 = info.stake.into();
-                stake
-            })
-            .sum();
-        
-        if total_stake == 0 {
-            return rewards;
-        }
+        numeric_votes.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
         
-        let reward_value: u128 = total_reward.into();
+        let median = if numeric_votes.len() % 2 == 0 {
+            let mid = numeric_votes.len() / 2;
+            (numeric_votes[mid - 1] + numeric_votes[mid]) / 2.0
+        } else {
+            numeric_votes[numeric_votes.len() / 2]
+        };
         
-        // Distribute rewards proportionally to stake
-        for (voter, info) in correct_voters {
-            let stake_value: u128 = info.stake.into();
-            let proportion = stake_value as f64 / total_stake as f64;
-            let base_reward = (reward_value as f64 * proportion) as u128;
-            
-            // Apply reputation multiplier and protocol fee
-            let reward = self.calculate_voter_reward(
-                Amount::from_tokens(base_reward),
-                info,
-                params,
-            );
-            
-            rewards.insert(*voter, reward);
+        ResolutionResult::NumericMedian(median)
+    }
+    
+    /// Majority over votes that each count `weight` (basis points)
+    /// 
+    /// Equivalent to `from_majority` when every weight is the same.
+    pub fn from_weighted_majority<'a>(votes: impl IntoIterator<Item = (&'a Vote, u32)>) -> Self {
+        let mut tally: BTreeMap<String, u64> = BTreeMap::new();
+        for (vote, weight) in votes {
+            *tally.entry(vote.value.clone()).or_insert(0) += weight as u64;
         }
-        
-        rewards
+        Self::from_tally(tally)
     }
     
-    /// Calculate reputation-weighted reward distribution
+    /// Median of numeric votes, each repeated by its confidence (0-100)
     /// 
-    /// Distributes rewards proportionally based on voter reputation
-    /// Returns a map of voter -> reward amount
+    /// Votes without a confidence count as fully confident (100) and
+    /// zero-confidence votes are ignored, so low-confidence estimates pull the
+    /// median less. Equivalent to `from_median` over the expanded votes.
+    pub fn from_confidence_weighted_median<'a>(votes: impl IntoIterator<Item = &'a Vote>) -> Self {
+        Self::from_weighted_median(
+            votes.into_iter()
+                .filter_map(|vote| Some((vote.value.parse::<f64>().ok()?, vote.confidence_weight())))
+        )
+    }
+    
+    /// Median of `(value, weight)` pairs over the list with each value
+    /// repeated `weight` times; zero weights are ignored
+    /// 
+    /// Equivalent to `from_median` when every weight is the same.
+    pub fn from_weighted_median(values: impl IntoIterator<Item = (f64, u64)>) -> Self {
+        let mut weighted_votes: Vec<(f64, u64)> = values
+            .into_iter()
+            .filter(|(_, weight)| *weight > 0)
+            .collect();
+        
+        let total_weight: u64 = weighted_votes.iter().map(|(_, weight)| weight).sum();
+        if total_weight == 0 {
+            return ResolutionResult::NoConsensus;
+        }
+        
+        // total_cmp gives a deterministic order for every f64
+        weighted_votes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        
+        // Value at a position of the expanded (repeated) vote list
+        let value_at = |position: u64| {
+            let mut cumulative = 0;
+            for (value, weight) in &weighted_votes {
+                cumulative += weight;
+                if position < cumulative {
+                    return *value;
+                }
+            }
+            weighted_votes[weighted_votes.len() - 1].0
+        };
+        
+        let lower = value_at((total_weight - 1) / 2);
+        let upper = value_at(total_weight / 2);
+        
+        ResolutionResult::NumericMedian((lower + upper) / 2.0)
+    }
+    
+    /// Whether a vote value agrees with this result
+    pub fn matches_vote(&self, value: &str) -> bool {
+        match self {
+            ResolutionResult::Outcome(outcome) => outcome == value,
+            ResolutionResult::Outcomes(outcomes) => outcomes.iter().any(|outcome| outcome == value),
+            ResolutionResult::NumericMedian(median) => {
+                value.parse::<f64>().map(|v| v == *median).unwrap_or(false)
+            }
+            ResolutionResult::NoConsensus | ResolutionResult::Expired => false,
+        }
+    }
+    
+    /// `resolved_outcome` sent in a QueryResolutionCallback for this result
+    /// 
+    /// One encoding per kind: the winning outcome, the median as displayed,
+    /// or NO_CONSENSUS_OUTCOME. Multi-winner results have no encoding, so
+    /// callback queries are always single-winner; expired queries send no
+    /// callback.
+    pub fn callback_outcome(&self) -> Result<String, String> {
+        match self {
+            ResolutionResult::Outcome(outcome) => Ok(outcome.clone()),
+            ResolutionResult::NumericMedian(median) => Ok(median.to_string()),
+            ResolutionResult::NoConsensus => Ok(NO_CONSENSUS_OUTCOME.to_string()),
+            ResolutionResult::Outcomes(_) => Err("multi-winner results cannot be sent in a callback".to_string()),
+            ResolutionResult::Expired => Err("expired queries have no result to send".to_string()),
+        }
+    }
+    
+    /// Short variant name (Outcome, Outcomes, NumericMedian, NoConsensus, Expired)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ResolutionResult::Outcome(_) => "Outcome",
+            ResolutionResult::Outcomes(_) => "Outcomes",
+            ResolutionResult::NumericMedian(_) => "NumericMedian",
+            ResolutionResult::NoConsensus => "NoConsensus",
+            ResolutionResult::Expired => "Expired",
+        }
+    }
+}
+
+impl std::fmt::Display for ResolutionResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolutionResult::Outcome(outcome) => write!(f, "{}", outcome),
+            ResolutionResult::Outcomes(outcomes) => write!(f, "{}", outcomes.join(", ")),
+            ResolutionResult::NumericMedian(median) => write!(f, "{}", median),
+            ResolutionResult::NoConsensus => write!(f, "No consensus"),
+            ResolutionResult::Expired => write!(f, "Expired"),
+        }
+    }
+}
+
+/// Maintenance action for an active query
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Settlement {
+    /// Resolve with the revealed votes
+    Resolve,
+    
+    /// Close without a result
+    Expire,
+}
+
+/// Active queries a keeper can act on right now
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActionableQueries {
+    /// Past the deadline and reveal grace window with enough votes (ResolveQuery)
+    pub resolvable: Vec<u64>,
+    
+    /// Past the deadline without enough votes (ExpireQuery)
+    pub expirable: Vec<u64>,
+}
+
+/// Sort active queries into those ResolveQuery or ExpireQuery would accept at `now`
+/// 
+/// Queries past their deadline with enough votes but still inside the reveal
+/// grace window are in neither list yet.
+pub fn actionable_queries<'a>(
+    queries: impl IntoIterator<Item = &'a Query>,
+    now: Timestamp,
+    grace_secs: u64,
+) -> ActionableQueries {
+    let mut actionable = ActionableQueries::default();
+    
+    for query in queries {
+        if query.status != QueryStatus::Active || now < query.deadline {
+            continue;
+        }
+        if query.votes.len() < query.min_votes {
+            actionable.expirable.push(query.id);
+        } else if query.check_resolution_window(now, grace_secs).is_ok() {
+            actionable.resolvable.push(query.id);
+        }
+    }
+    
+    actionable
+}
+
+/// A bounded slice of active queries for one SettleQueries call
+#[derive(Debug, Clone, PartialEq)]
+pub struct SettleBatch {
+    /// Query IDs to process in this call, ascending
+    pub query_ids: Vec<u64>,
+    
+    /// Cursor for the next call (None = start over from the lowest ID)
+    pub next_cursor: Option<u64>,
+    
+    /// Active queries after this batch still to visit in the current pass
+    pub remaining: usize,
+}
+
+/// Pick at most `max` active queries after `cursor`, in ascending ID order
+/// 
+/// Repeated calls walk the whole active set and then wrap around, so every
+/// query is visited even when each call is capped.
+pub fn settle_batch(active_queries: &[u64], cursor: Option<u64>, max: usize) -> SettleBatch {
+    let mut candidates: Vec<u64> = active_queries
+        .iter()
+        .copied()
+        .filter(|id| cursor.is_none_or(|c| *id > c))
+        .collect();
+    candidates.sort_unstable();
+    candidates.dedup();
+    
+    let remaining = candidates.len().saturating_sub(max);
+    candidates.truncate(max);
+    let next_cursor = if remaining > 0 { candidates.last().copied() } else { None };
+    
+    SettleBatch { query_ids: candidates, next_cursor, remaining }
+}
+
+/// The voters one ReclaimUnclaimedRewards call checked
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReclaimBatch {
+    /// Checked voters whose rewards expired, with their pending amounts
+    pub expired: Vec<(ChainId, Amount)>,
+    
+    /// Cursor for the next call (None = start over from the first voter)
+    pub next_cursor: Option<ChainId>,
+    
+    /// Voters after this batch still to check in the current pass
+    pub remaining: usize,
+}
+
+/// Add one to a statistics counter, pinning it at `u64::MAX` instead of wrapping
+pub fn increment_counter(current: u64, name: &str) -> u64 {
+    current.checked_add(1).unwrap_or_else(|| {
+        eprintln!("Warning: {} overflow detected; keeping it at {}", name, u64::MAX);
+        u64::MAX
+    })
+}
+
+/// Subtract one from a counter, pinning it at zero instead of wrapping
+/// 
+/// A zero count here means the counter is already out of sync with the state
+/// it tracks, so the underflow is logged rather than silently absorbed.
+pub fn decrement_counter(current: u64, name: &str) -> u64 {
+    current.checked_sub(1).unwrap_or_else(|| {
+        eprintln!("Warning: {} underflow detected; keeping it at 0", name);
+        0
+    })
+}
+
+/// Voting phase for commit/reveal voting
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum VotingPhase {
+    /// Commit phase - voters submit commit hashes
+    Commit,
+    
+    /// Reveal phase - voters reveal their votes
+    Reveal,
+    
+    /// Voting completed, ready for resolution
+    Completed,
+}
+
+/// Query status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum QueryStatus {
+    /// Query is active and accepting votes
+    Active,
+    
+    /// Query is resolved
+    Resolved,
+    
+    /// Query expired without resolution
+    Expired,
+    
+    /// Query cancelled
+    Cancelled,
+    
+    /// Query closed after voting without consensus (voters took part but
+    /// disagreed, unlike Expired where too few voted)
+    NoConsensus,
+}
+
+/// `resolved_outcome` sent in the callback for a query whose voters took
+/// part but reached no consensus
+pub const NO_CONSENSUS_OUTCOME: &str = "NoConsensus";
+
+impl QueryStatus {
+    /// Status name as used in GraphQL and cross-application responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueryStatus::Active => "Active",
+            QueryStatus::Resolved => "Resolved",
+            QueryStatus::Expired => "Expired",
+            QueryStatus::Cancelled => "Cancelled",
+            QueryStatus::NoConsensus => "NoConsensus",
+        }
+    }
+}
+
+/// Protocol parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProtocolParameters {
+    /// Minimum stake required to register as voter
+    pub min_stake: Amount,
+    
+    /// Minimum votes required for query resolution
+    pub min_votes_default: usize,
+    
+    /// Default query duration (seconds)
+    pub default_query_duration: u64,
+    
+    /// Reward percentage for correct voters (basis points, e.g., 10000 = 100%)
+    pub reward_percentage: u32,
+    
+    /// Slash percentage for incorrect voters (basis points)
+    pub slash_percentage: u32,
+    
+    /// Protocol fee percentage (basis points)
+    pub protocol_fee: u32,
+    
+    /// ALTH Token application ID (for real token integration)
+    pub token_app_id: Option<linera_sdk::linera_base_types::ApplicationId>,
+    
+    /// Grace window after reveal_phase_end before resolution is allowed (seconds)
+    #[serde(default = "default_resolution_grace_secs")]
+    pub resolution_grace_secs: u64,
+    
+    /// Minimum gap between a voter's votes (seconds, 0 = disabled)
+    #[serde(default)]
+    pub min_vote_interval_secs: u64,
+    
+    /// Maximum reward a single query may offer (None = uncapped)
+    #[serde(default)]
+    pub max_query_reward: Option<Amount>,
+    
+    /// Strategy for queries created without one (e.g. market-created queries)
+    #[serde(default)]
+    pub default_strategy: DecisionStrategy,
+    
+    /// Stake at or above which new voters get the reputation bonus (None = no bonus)
+    #[serde(default)]
+    pub high_stake_threshold: Option<Amount>,
+    
+    /// Starting reputation bonus for high-stake voters (max MAX_HIGH_STAKE_REPUTATION_BONUS)
+    #[serde(default)]
+    pub high_stake_reputation_bonus: u32,
+    
+    /// Hash function clients must use for vote commitments
+    #[serde(default)]
+    pub commit_hash_algo: CommitHashAlgo,
+    
+    /// Share of an emergency withdrawal kept by the treasury (basis points)
+    #[serde(default = "default_emergency_unstake_penalty_bps")]
+    pub emergency_unstake_penalty_bps: u32,
+    
+    /// Let direct voters replace their vote by voting again before the deadline
+    #[serde(default)]
+    pub allow_vote_change_until_deadline: bool,
+    
+    /// Voters selected for any of the last N queries are passed over when the
+    /// panel can be filled without them (0 = disabled)
+    #[serde(default)]
+    pub selection_cooldown_queries: u64,
+    
+    /// Delay between proposing and applying a parameter change (0 = disabled,
+    /// the admin may update parameters immediately)
+    #[serde(default)]
+    pub timelock_secs: u64,
+    
+    /// Largest share of total stake a single resolution may slash before the
+    /// resolution is aborted and the protocol paused (basis points, 0 = disabled)
+    #[serde(default)]
+    pub max_slash_per_resolution_bps: u32,
+    
+    /// Weight of a reveal landing in the grace window after `reveal_phase_end`
+    /// (basis points, 10000 = counted like an on-time reveal)
+    #[serde(default = "default_late_reveal_weight_bps")]
+    pub late_reveal_weight_bps: u32,
+    
+    /// Most queries that may be active at once; creation is rejected at the cap
+    #[serde(default = "default_max_active_queries")]
+    pub max_active_queries: u64,
+    
+    /// Account that swept treasury tokens are released to (None = no sweeping)
+    #[serde(default)]
+    pub treasury_account: Option<Account>,
+    
+    /// Minimum time between automatic treasury sweeps at resolution
+    /// (seconds, 0 = sweep only through the SweepTreasury operation)
+    #[serde(default)]
+    pub treasury_sweep_interval_secs: u64,
+    
+    /// Share of a resolved query's protocol fee credited to its creator's
+    /// pending rewards (basis points, 0 = the whole fee goes to the treasury).
+    /// Like other rewards, it is claimed from a registered voter chain.
+    #[serde(default)]
+    pub creator_rebate_bps: u32,
+    
+    /// Share of the tally an outcome needs to be among the winners of a
+    /// multi-winner query (basis points)
+    #[serde(default = "default_multi_winner_threshold_bps")]
+    pub multi_winner_threshold_bps: u32,
+    
+    /// Deposit taken from the creator's token account per created query, on
+    /// top of its reward (0 = none); creation fails if the transfer does.
+    /// Returned to the paying account once the query resolves (or is
+    /// cancelled), forfeited to the treasury if it expires without enough votes.
+    #[serde(default)]
+    pub query_creation_deposit: Amount,
+    
+    /// Hex characters of a chain ID that define its address-prefix group,
+    /// for queries capping a group's share of the voting power
+    #[serde(default = "default_voter_group_prefix_len")]
+    pub voter_group_prefix_len: usize,
+    
+    /// Time after a voter's last reward credit when their unclaimed rewards
+    /// may be returned to the reward pool (seconds, 0 = rewards never expire)
+    #[serde(default)]
+    pub reward_claim_expiry_secs: u64,
+    
+    /// Most stake counted for a voter in power and stake-weighted tallies and
+    /// rewards (None = uncapped). Voters keep, and are slashed on, their full stake.
+    #[serde(default)]
+    pub max_effective_stake: Option<Amount>,
+    
+    /// Per-strategy `min_votes_default` overrides for queries created
+    /// without an explicit `min_votes`
+    #[serde(default)]
+    pub strategy_min_votes: BTreeMap<DecisionStrategy, usize>,
+    
+    /// Per-strategy overrides of how much stake a vote locks (strategies
+    /// without one use `StakeLockRule::default()`)
+    #[serde(default)]
+    pub strategy_stake_locks: BTreeMap<DecisionStrategy, StakeLockRule>,
+}
+
+/// How much of a voter's available stake a vote locks
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StakeLockRule {
+    /// Share of the available stake locked (basis points)
+    pub lock_bps: u32,
+    
+    /// Amount locked instead when the share rounds down to zero
+    pub min_lock: Amount,
+}
+
+impl Default for StakeLockRule {
+    fn default() -> Self {
+        Self {
+            lock_bps: 1000,                  // 10% of available stake
+            min_lock: Amount::from_tokens(1),
+        }
+    }
+}
+
+impl StakeLockRule {
+    /// Stake a vote by `voter` locks under this rule
+    /// 
+    /// Leaves room for voting on several queries at once while keeping
+    /// skin in the game.
+    pub fn stake_to_lock(&self, voter: &VoterInfo) -> Amount {
+        let available: u128 = voter.stake.saturating_sub(voter.locked_stake).into();
+        let bps = u128::from(self.lock_bps.min(10000));
+        let share = Amount::from_attos(available / 10000 * bps + available % 10000 * bps / 10000);
+        if share == Amount::ZERO {
+            self.min_lock
+        } else {
+            share
+        }
+    }
+}
+
+/// What voting on a query would lock from a voter's stake, see
+/// `OracleRegistryV2::lock_preview`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockPreview {
+    pub strategy: DecisionStrategy,
+    pub rule: StakeLockRule,
+    pub available_stake: Amount,
+    pub stake_to_lock: Amount,
+}
+
+/// Upper bound on the parameter change timelock (30 days)
+pub const MAX_PARAMETER_TIMELOCK_SECS: u64 = 30 * 86400;
+
+/// A proposed parameter change waiting out the timelock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingParameters {
+    /// Parameters to apply
+    pub params: ProtocolParameters,
+    
+    /// Admin chain that proposed the change
+    pub proposed_by: ChainId,
+    
+    /// When the change was proposed
+    pub proposed_at: Timestamp,
+    
+    /// Earliest time the change may be applied
+    pub effective_at: Timestamp,
+}
+
+impl PendingParameters {
+    /// Proposal of `params` at `now`, applicable after `timelock_secs`
+    pub fn new(params: ProtocolParameters, proposed_by: ChainId, now: Timestamp, timelock_secs: u64) -> Self {
+        Self {
+            params,
+            proposed_by,
+            proposed_at: now,
+            effective_at: now.saturating_add(TimeDelta::from_secs(timelock_secs)),
+        }
+    }
+    
+    /// Whether the timelock has elapsed at `now`
+    pub fn is_ready(&self, now: Timestamp) -> bool {
+        now >= self.effective_at
+    }
+    
+    /// Check the change may be applied at `now`
+    pub fn check_ready(&self, now: Timestamp) -> Result<(), String> {
+        if !self.is_ready(now) {
+            return Err(format!(
+                "Pending parameters are timelocked for {} more second(s)",
+                self.effective_at.delta_since(now).as_micros().div_ceil(1_000_000)
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Starting reputation for new voters
+pub const DEFAULT_REPUTATION: u32 = 50;
+
+/// Upper bound on the high-stake starting reputation bonus
+pub const MAX_HIGH_STAKE_REPUTATION_BONUS: u32 = 20;
+
+fn default_resolution_grace_secs() -> u64 {
+    30
+}
+
+fn default_late_reveal_weight_bps() -> u32 {
+    10000
+}
+
+fn default_max_active_queries() -> u64 {
+    1000
+}
+
+fn default_multi_winner_threshold_bps() -> u32 {
+    3000
+}
+
+fn default_voter_group_prefix_len() -> usize {
+    8
+}
+
+fn default_emergency_unstake_penalty_bps() -> u32 {
+    1000
+}
+
+impl Default for ProtocolParameters {
+    fn default() -> Self {
+        Self {
+            min_stake: Amount::from_tokens(100),
+            min_votes_default: 3,
+            default_query_duration: 3600, // 1 hour (for faster testing)
+            reward_percentage: 1000,        // 10%
+            slash_percentage: 500,          // 5%
+            protocol_fee: 100,              // 1%
+            token_app_id: None,             // Set after token deployment
+            resolution_grace_secs: default_resolution_grace_secs(),
+            min_vote_interval_secs: 0,      // Disabled
+            max_query_reward: None,         // Uncapped
+            default_strategy: DecisionStrategy::Majority,
+            high_stake_threshold: None,     // No bonus
+            high_stake_reputation_bonus: 0,
+            commit_hash_algo: CommitHashAlgo::Sha256,
+            emergency_unstake_penalty_bps: default_emergency_unstake_penalty_bps(), // 10%
+            selection_cooldown_queries: 0,  // Disabled
+            allow_vote_change_until_deadline: false,
+            timelock_secs: 0,               // Disabled
+            max_slash_per_resolution_bps: 0, // Disabled
+            late_reveal_weight_bps: default_late_reveal_weight_bps(), // Full weight
+            max_active_queries: default_max_active_queries(),
+            treasury_account: None,         // No sweeping
+            treasury_sweep_interval_secs: 0, // Manual sweeps only
+            creator_rebate_bps: 0,          // No rebate
+            multi_winner_threshold_bps: default_multi_winner_threshold_bps(), // 30%
+            query_creation_deposit: Amount::ZERO, // No deposit
+            voter_group_prefix_len: default_voter_group_prefix_len(),
+            reward_claim_expiry_secs: 0,    // Rewards never expire
+            max_effective_stake: None,      // Stake weight uncapped
+            strategy_min_votes: BTreeMap::new(), // min_votes_default for every strategy
+            strategy_stake_locks: BTreeMap::new(), // 10% (min 1 token) for every strategy
+        }
+    }
+}
+
+impl ProtocolParameters {
+    /// Check `slashed` against the per-resolution slashing limit on `total_stake`
+    /// 
+    /// An error means the resolution should be aborted and the protocol paused.
+    pub fn check_resolution_slash(&self, slashed: Amount, total_stake: Amount) -> Result<(), String> {
+        if self.max_slash_per_resolution_bps == 0 {
+            return Ok(());
+        }
+        
+        let total: u128 = total_stake.into();
+        let limit = Amount::from_attos(
+            total.saturating_mul(self.max_slash_per_resolution_bps as u128) / 10000
+        );
+        if slashed > limit {
+            return Err(format!(
+                "Resolution would slash {} of {} total stake, above the {} basis point limit ({})",
+                slashed, total_stake, self.max_slash_per_resolution_bps, limit
+            ));
+        }
+        Ok(())
+    }
+    
+    /// Starting reputation for a voter registering with `stake`
+    /// 
+    /// Voters staking at least `high_stake_threshold` start with the
+    /// (bounded) high-stake bonus on top of the default.
+    pub fn initial_reputation(&self, stake: Amount) -> u32 {
+        match self.high_stake_threshold {
+            Some(threshold) if stake >= threshold => {
+                DEFAULT_REPUTATION + self.high_stake_reputation_bonus.min(MAX_HIGH_STAKE_REPUTATION_BONUS)
+            }
+            _ => DEFAULT_REPUTATION,
+        }
+    }
+    
+    /// The supplied strategy, or the registry's default when none is given
+    /// 
+    /// The default is not checked against the query's outcomes here; see
+    /// `Query::strategy_for_resolution`.
+    pub fn strategy_or_default(&self, strategy: Option<DecisionStrategy>) -> DecisionStrategy {
+        strategy.unwrap_or_else(|| self.default_strategy.clone())
+    }
+    
+    /// Minimum votes for a query of `strategy` created without an explicit
+    /// `min_votes`: its `strategy_min_votes` override, or `min_votes_default`
+    pub fn min_votes_for(&self, strategy: &DecisionStrategy) -> usize {
+        self.strategy_min_votes.get(strategy).copied().unwrap_or(self.min_votes_default)
+    }
+    
+    /// Stake lock rule for votes on queries of `strategy`
+    pub fn stake_lock_rule(&self, strategy: &DecisionStrategy) -> StakeLockRule {
+        self.strategy_stake_locks.get(strategy).copied().unwrap_or_default()
+    }
+    
+    /// Check a query reward against the `max_query_reward` cap, if any
+    pub fn check_query_reward(&self, reward_amount: Amount) -> Result<(), String> {
+        match self.max_query_reward {
+            Some(max) if reward_amount > max => Err(format!(
+                "Reward amount {} exceeds the maximum query reward {}",
+                reward_amount, max
+            )),
+            _ => Ok(()),
+        }
+    }
+    
+    /// Check there is room for another query next to `active_count` active ones
+    pub fn check_active_query_capacity(&self, active_count: usize) -> Result<(), String> {
+        if active_count as u64 >= self.max_active_queries {
+            return Err(format!(
+                "Too many active queries ({} of max {}); wait for a query to close or ask the admin to raise max_active_queries",
+                active_count, self.max_active_queries
+            ));
+        }
+        Ok(())
+    }
+    
+    /// Whether an automatic treasury sweep is due at `now`, the last having run at `last_sweep`
+    pub fn treasury_sweep_due(&self, last_sweep: Timestamp, now: Timestamp) -> bool {
+        self.treasury_account.is_some()
+            && self.treasury_sweep_interval_secs > 0
+            && now >= last_sweep.saturating_add(TimeDelta::from_secs(self.treasury_sweep_interval_secs))
+    }
+    
+    /// Part of a resolved query's protocol `fee` rebated to its creator
+    pub fn creator_rebate(&self, fee: Amount) -> Amount {
+        let fee_value: u128 = fee.into();
+        let bps = u128::from(self.creator_rebate_bps.min(10000));
+        Amount::from_attos(fee_value / 10000 * bps + fee_value % 10000 * bps / 10000)
+    }
+    
+    /// Penalty kept by the treasury when `amount` is withdrawn in an emergency
+    pub fn emergency_unstake_penalty(&self, amount: Amount) -> Amount {
+        let amount_value: u128 = amount.into();
+        let bps = u128::from(self.emergency_unstake_penalty_bps.min(10000));
+        Amount::from_attos(amount_value / 10000 * bps + amount_value % 10000 * bps / 10000)
+    }
+}
+
+/// The application state for Account-Based Oracle Registry
+#[derive(RootView)]
+#[view(context = ViewStorageContext)]
+pub struct OracleRegistryV2 {
+    // Voter management (chain-based - Microcard pattern!)
+    pub voters: MapView<ChainId, VoterInfo>,
+    pub total_stake: RegisterView<Amount>,
+    pub voter_count: RegisterView<u64>,
+    
+    // Token holdings (actual ALETHEA tokens held by registry)
+    pub token_holdings: MapView<ChainId, Amount>,  // Voter -> Token balance held
+    pub total_tokens_held: RegisterView<Amount>,   // Total tokens in registry
+    
+    // Query management
+    pub next_query_id: RegisterView<u64>,
+    pub queries: MapView<u64, Query>,
+    pub active_queries: RegisterView<Vec<u64>>,
+    pub query_callbacks: MapView<u64, QueryCallback>,  // ← NEW: For cross-chain callbacks
+    pub archived_queries: MapView<u64, ArchivedQuery>,  // Summaries of archived old queries
+    pub settle_cursor: RegisterView<Option<u64>>,  // Last query ID settled by SettleQueries
+    
+    // Failed cross-chain messages (bounded to MAX_FAILED_MESSAGES entries)
+    pub failed_messages: MapView<u64, FailedMessage>,
+    pub next_failed_message_id: RegisterView<u64>,
+    
+    // Parameter changes (bounded to MAX_PARAMETER_HISTORY entries)
+    pub parameter_history: MapView<u64, ParameterChange>,
+    pub next_parameter_history_id: RegisterView<u64>,
+    
+    // Reward and slash flows per resolution (each bounded to MAX_FLOW_HISTORY entries)
+    pub reward_history: MapView<u64, FlowEntry>,
+    pub next_reward_history_id: RegisterView<u64>,
+    pub slash_history: MapView<u64, FlowEntry>,
+    pub next_slash_history_id: RegisterView<u64>,
+    
+    // Recent registrations, queries, votes and resolutions (bounded to MAX_ACTIVITY_LOG entries)
+    pub activity_log: MapView<u64, ActivityEntry>,
+    pub next_activity_id: RegisterView<u64>,
+    
+    // Most recent query each voter was selected for (selection cooldown)
+    pub last_selected_query: MapView<ChainId, u64>,
+    
+    // Panel selections and participation per voter (selection fairness)
+    pub selection_records: MapView<ChainId, SelectionRecord>,
+    
+    // Lifetime rewards and slashes per voter
+    pub voter_earnings: MapView<ChainId, VoterEarnings>,
+    
+    // Stake after each stake change or slash per voter (bounded to MAX_STAKE_HISTORY entries)
+    pub stake_history: MapView<ChainId, Vec<StakeSnapshot>>,
+    
+    // Chain each voter allows to claim their rewards (BatchClaimFor)
+    pub claim_operators: MapView<ChainId, ChainId>,
+    
+    // Voting records (query_id -> voter_chain -> vote)
+    pub votes: MapView<(u64, ChainId), Vote>,
+    pub stake_locks: MapView<(u64, ChainId), Amount>,  // Stake locked per (query, voter)
+    pub creation_deposits: MapView<u64, CreationDeposit>,  // Deposit and payer held per active query
+    pub commit_hashes: MapView<(ChainId, String), CommitHashUse>,  // (voter, lowercase hash) -> last use
+    pub pending_unlocks: MapView<u64, usize>,  // Finalized query -> stake lockers unlocked so far
+    pub vote_counts: MapView<u64, usize>,
+    
+    // Rewards
+    pub reward_pool: RegisterView<Amount>,
+    pub pending_rewards: MapView<ChainId, Amount>,
+    pub reward_credited_at: MapView<ChainId, Timestamp>,  // Last credit to each voter's pending rewards
+    pub reclaim_cursor: RegisterView<Option<ChainId>>,  // Last voter checked by ReclaimUnclaimedRewards
+    pub total_rewards_distributed: RegisterView<Amount>,
+    
+    // Protocol
+    pub parameters: RegisterView<ProtocolParameters>,
+    pub pending_parameters: RegisterView<Option<PendingParameters>>,
+    pub protocol_treasury: RegisterView<Amount>,
+    pub last_treasury_sweep: RegisterView<Timestamp>,
+    pub is_paused: RegisterView<bool>,
+    pub admin: RegisterView<Option<ChainId>>,
+    
+    // Statistics
+    pub total_queries_created: RegisterView<u64>,
+    pub total_queries_resolved: RegisterView<u64>,
+    pub total_queries_no_consensus: RegisterView<u64>,
+    pub total_votes_submitted: RegisterView<u64>,
+    pub voter_aggregates: RegisterView<VoterAggregates>,  // Kept current by save_voter/remove_voter
+}
+
+impl OracleRegistryV2 {
+    /// Initialize the registry
+    pub async fn initialize(&mut self, params: ProtocolParameters, admin: ChainId) {
+        self.next_query_id.set(1);
+        self.total_stake.set(Amount::ZERO);
+        self.voter_count.set(0);
+        self.reward_pool.set(Amount::ZERO);
+        self.protocol_treasury.set(Amount::ZERO);
+        self.total_rewards_distributed.set(Amount::ZERO);
+        self.total_queries_created.set(0);
+        self.total_queries_resolved.set(0);
+        self.total_votes_submitted.set(0);
+        self.is_paused.set(false);
+        self.active_queries.set(Vec::new());
+        self.parameters.set(params);
+        self.admin.set(Some(admin));
+    }
+    
+    /// Check if registry is paused
+    pub async fn is_paused(&self) -> bool {
+        *self.is_paused.get()
+    }
+    
+    /// Get protocol parameters
+    pub async fn get_parameters(&self) -> ProtocolParameters {
+        self.parameters.get().clone()
+    }
+    
+    /// Deterministic hash of the protocol parameters and admin
+    /// 
+    /// Changes whenever either does, so clients can poll it and refetch the
+    /// configuration only when it moves.
+    pub fn config_fingerprint(&self) -> String {
+        use sha2::{Digest, Sha256};
+        let config = bcs::to_bytes(&(self.parameters.get(), self.admin.get()))
+            .expect("Configuration is serializable");
+        format!("{:x}", Sha256::digest(&config))
+    }
+    
+    /// What a vote by `voter` on `query_id` would lock right now
+    /// 
+    /// Uses the same rule as voting, so it matches the lock applied by a vote
+    /// cast before the voter's stake changes.
+    pub async fn lock_preview(&self, query_id: u64, voter: &ChainId) -> Result<LockPreview, String> {
+        let query = self.get_query(query_id).await
+            .ok_or_else(|| format!("Query {} not found", query_id))?;
+        let voter_info = self.get_voter(voter).await
+            .ok_or_else(|| "Voter not found".to_string())?;
+        let rule = self.parameters.get().stake_lock_rule(&query.strategy);
+        
+        Ok(LockPreview {
+            strategy: query.strategy,
+            rule,
+            available_stake: voter_info.stake.saturating_sub(voter_info.locked_stake),
+            stake_to_lock: rule.stake_to_lock(&voter_info),
+        })
+    }
+    
+    /// Get admin chain ID
+    pub async fn get_admin(&self) -> Option<ChainId> {
+        *self.admin.get()
+    }
+    
+    /// Check if the given chain is the admin
+    pub async fn is_admin(&self, chain: &ChainId) -> bool {
+        match *self.admin.get() {
+            Some(admin) => admin == *chain,
+            None => false,
+        }
+    }
+    
+    /// Get voter info by chain ID
+    pub async fn get_voter(&self, chain: &ChainId) -> Option<VoterInfo> {
+        self.voters.get(chain).await.ok().flatten()
+    }
+    
+    /// Store a voter, keeping the cached voter aggregates current
+    /// 
+    /// All writes to `voters` go through here (or `remove_voter`).
+    pub async fn save_voter(&mut self, chain: &ChainId, voter_info: VoterInfo) -> Result<(), ViewError> {
+        let previous = self.voters.get(chain).await?;
+        let mut aggregates = self.voter_aggregates.get().clone();
+        aggregates.replace(previous.as_ref(), Some(&voter_info));
+        
+        self.voters.insert(chain, voter_info)?;
+        self.voter_aggregates.set(aggregates);
+        Ok(())
+    }
+    
+    /// Remove a voter (and their claim operator), keeping the cached voter aggregates current
+    pub async fn remove_voter(&mut self, chain: &ChainId) -> Result<(), ViewError> {
+        let previous = self.voters.get(chain).await?;
+        let mut aggregates = self.voter_aggregates.get().clone();
+        aggregates.replace(previous.as_ref(), None);
+        
+        self.voters.remove(chain)?;
+        self.claim_operators.remove(chain)?;
+        self.voter_aggregates.set(aggregates);
+        Ok(())
+    }
+    
+    /// Compute voter aggregates by scanning every voter
+    pub async fn compute_voter_aggregates(&self) -> Result<VoterAggregates, String> {
+        let indices = self.voters.indices().await
+            .map_err(|e| format!("Failed to get voter indices: {}", e))?;
+        
+        let mut voters = Vec::new();
+        for chain_id in indices {
+            if let Some(voter) = self.get_voter(&chain_id).await {
+                voters.push(voter);
+            }
+        }
+        
+        Ok(VoterAggregates::from_voters(&voters))
+    }
+    
+    /// Chain allowed to claim `voter`'s rewards, if any
+    pub async fn get_claim_operator(&self, voter: &ChainId) -> Option<ChainId> {
+        self.claim_operators.get(voter).await.ok().flatten()
+    }
+    
+    /// Compare what the registry owes against the balances it tracks
+    /// 
+    /// Scans every voter (for locked stake) and every pending reward.
+    pub async fn compute_solvency(&self) -> Result<Solvency, String> {
+        let aggregates = self.compute_voter_aggregates().await?;
+        
+        let indices = self.pending_rewards.indices().await
+            .map_err(|e| format!("Failed to get pending reward indices: {}", e))?;
+        let mut total_pending_rewards = Amount::ZERO;
+        for chain_id in indices {
+            total_pending_rewards = total_pending_rewards.saturating_add(self.get_pending_rewards(&chain_id).await);
+        }
+        
+        Ok(Solvency {
+            total_staked: *self.total_stake.get(),
+            total_locked: aggregates.total_locked_stake,
+            total_pending_rewards,
+            reward_pool: *self.reward_pool.get(),
+            treasury: *self.protocol_treasury.get(),
+            tokens_held: *self.total_tokens_held.get(),
+        })
+    }
+    
+    /// Treasury tokens that can be released without touching voter stakes
+    /// 
+    /// Slashes and penalties move stake into the treasury while the tokens stay
+    /// held by the registry, so only holdings beyond total stake are backed.
+    /// Treasury credited from the reward pool (protocol fees) is not held as
+    /// tokens and stays until holdings cover it.
+    pub fn sweepable_treasury(&self) -> Amount {
+        let surplus = self.total_tokens_held.get().saturating_sub(*self.total_stake.get());
+        (*self.protocol_treasury.get()).min(surplus)
+    }
+    
+    /// Set a voter's `last_active_at` to `now`, if registered
+    pub async fn mark_voter_active(&mut self, chain: &ChainId, now: Timestamp) {
+        if let Some(mut voter) = self.get_voter(chain).await {
+            voter.last_active_at = now;
+            self.save_voter(chain, voter).await.expect("Failed to update voter");
+        }
+    }
+    
+    /// Get query info
+    pub async fn get_query(&self, query_id: u64) -> Option<Query> {
+        self.queries.get(&query_id).await.ok().flatten()
+    }
+    
+    /// Get archived query summary
+    pub async fn get_archived_query(&self, query_id: u64) -> Option<ArchivedQuery> {
+        self.archived_queries.get(&query_id).await.ok().flatten()
+    }
+    
+    /// Record a failed cross-chain message, evicting the oldest entry if full
+    pub fn record_failed_message(
+        &mut self,
+        kind: String,
+        sender: Option<ChainId>,
+        error: String,
+        failed_at: Timestamp,
+    ) -> Result<u64, String> {
+        let id = *self.next_failed_message_id.get();
+        
+        if let Some(evicted) = failed_message_evicted_id(id) {
+            self.failed_messages.remove(&evicted)
+                .map_err(|e| format!("Failed to evict failed message: {}", e))?;
+        }
+        
+        self.failed_messages.insert(&id, FailedMessage { id, kind, sender, error, failed_at })
+            .map_err(|e| format!("Failed to record failed message: {}", e))?;
+        self.next_failed_message_id.set(id + 1);
+        
+        Ok(id)
+    }
+    
+    /// Get logged failed messages, most recent first
+    /// Record parameters set by `changed_by`, evicting the oldest entry if full
+    pub fn record_parameter_change(
+        &mut self,
+        changed_by: ChainId,
+        params: ProtocolParameters,
+        changed_at: Timestamp,
+    ) -> Result<u64, String> {
+        let id = *self.next_parameter_history_id.get();
+        
+        if let Some(evicted) = parameter_history_evicted_id(id) {
+            self.parameter_history.remove(&evicted)
+                .map_err(|e| format!("Failed to evict parameter change: {}", e))?;
+        }
+        
+        self.parameter_history.insert(&id, ParameterChange { id, changed_at, changed_by, params })
+            .map_err(|e| format!("Failed to record parameter change: {}", e))?;
+        self.next_parameter_history_id.set(id + 1);
+        
+        Ok(id)
+    }
+    
+    /// Get recorded parameter changes, most recent first
+    pub async fn get_parameter_history(&self, limit: usize) -> Vec<ParameterChange> {
+        let next_id = *self.next_parameter_history_id.get();
+        let oldest = next_id.saturating_sub(MAX_PARAMETER_HISTORY);
+        let mut history = Vec::new();
+        
+        for id in (oldest..next_id).rev().take(limit) {
+            if let Some(entry) = self.parameter_history.get(&id).await.ok().flatten() {
+                history.push(entry);
+            }
+        }
+        
+        history
+    }
+    
+    /// Append an activity to the activity log, evicting the oldest entry if full
+    pub fn record_activity(
+        &mut self,
+        kind: ActivityKind,
+        chain_id: Option<ChainId>,
+        query_id: Option<u64>,
+        detail: Option<String>,
+        timestamp: Timestamp,
+    ) -> Result<u64, String> {
+        let id = *self.next_activity_id.get();
+        
+        if let Some(evicted) = activity_log_evicted_id(id) {
+            self.activity_log.remove(&evicted)
+                .map_err(|e| format!("Failed to evict activity: {}", e))?;
+        }
+        
+        self.activity_log.insert(&id, ActivityEntry { id, kind, timestamp, chain_id, query_id, detail })
+            .map_err(|e| format!("Failed to record activity: {}", e))?;
+        self.next_activity_id.set(id + 1);
+        
+        Ok(id)
+    }
+    
+    /// Get the most recent `limit` activities, most recent first
+    pub async fn get_activity_feed(&self, limit: usize) -> Vec<ActivityEntry> {
+        let next_id = *self.next_activity_id.get();
+        let oldest = next_id.saturating_sub(MAX_ACTIVITY_LOG);
+        let mut feed = Vec::new();
+        
+        for id in (oldest..next_id).rev().take(limit) {
+            if let Some(entry) = self.activity_log.get(&id).await.ok().flatten() {
+                feed.push(entry);
+            }
+        }
+        
+        feed
+    }
+    
+    /// Append `amount` rewarded for `query_id` to the reward history
+    pub fn record_reward_flow(&mut self, timestamp: Timestamp, query_id: u64, amount: Amount) -> Result<u64, String> {
+        append_flow(&mut self.reward_history, &mut self.next_reward_history_id, timestamp, query_id, amount)
+    }
+    
+    /// Append `amount` slashed for `query_id` to the slash history
+    pub fn record_slash_flow(&mut self, timestamp: Timestamp, query_id: u64, amount: Amount) -> Result<u64, String> {
+        append_flow(&mut self.slash_history, &mut self.next_slash_history_id, timestamp, query_id, amount)
+    }
+    
+    /// Reward history entries still in the log, oldest first
+    pub async fn get_reward_history(&self) -> Vec<FlowEntry> {
+        read_flows(&self.reward_history, *self.next_reward_history_id.get()).await
+    }
+    
+    /// Slash history entries still in the log, oldest first
+    pub async fn get_slash_history(&self) -> Vec<FlowEntry> {
+        read_flows(&self.slash_history, *self.next_slash_history_id.get()).await
+    }
+    
+    pub async fn get_failed_messages(&self, limit: usize) -> Vec<FailedMessage> {
+        let next_id = *self.next_failed_message_id.get();
+        let oldest = next_id.saturating_sub(MAX_FAILED_MESSAGES);
+        let mut failed = Vec::new();
+        
+        for id in (oldest..next_id).rev().take(limit) {
+            if let Some(entry) = self.failed_messages.get(&id).await.ok().flatten() {
+                failed.push(entry);
+            }
+        }
+        
+        failed
+    }
+    
+    /// Get vote for a query by chain ID
+    pub async fn get_vote(&self, query_id: u64, voter_chain: &ChainId) -> Option<Vote> {
+        self.votes.get(&(query_id, *voter_chain)).await.ok().flatten()
+    }
+    
+    /// Reset the stored vote count of a query to `query.votes.len()`
+    /// 
+    /// `vote_counts` is incremented by several paths independently of
+    /// `query.votes`, so it may drift. Returns the reconciled count.
+    pub async fn reconcile_vote_counts(&mut self, query_id: u64) -> Result<usize, String> {
+        let query = self.get_query(query_id).await
+            .ok_or_else(|| format!("Query {} not found", query_id))?;
+        let stored = self.vote_counts.get(&query_id).await.ok().flatten();
+        
+        if let Some(actual) = query.vote_count_correction(stored) {
+            eprintln!(
+                "Reconciling vote count for query {}: stored {:?}, actual {}",
+                query_id, stored, actual
+            );
+            self.vote_counts.insert(&query_id, actual)
+                .map_err(|e| format!("Failed to reconcile vote count: {}", e))?;
+        }
+        
+        Ok(query.votes.len())
+    }
+    
+    /// Get all votes for a query
+    pub async fn get_query_votes(&self, _query_id: u64) -> Vec<Vote> {
+        // This is a simplified version - in production, you'd want to iterate properly
+        Vec::new() // TODO: Implement proper iteration
+    }
+    
+    /// Calculate reputation score based on voting accuracy
+    /// 
+    /// Reputation is calculated using a weighted formula that considers:
+    /// - Voting accuracy (correct votes / total votes)
+    /// - Participation level (total votes)
+    /// - Recency bias (recent votes weighted more)
+    pub fn calculate_reputation(&self, voter_info: &VoterInfo) -> u32 {
+        if voter_info.total_votes == 0 {
+            return 50; // Default reputation for new voters
+        }
+        
+        // Base accuracy score (0-100)
+        let accuracy = (voter_info.correct_votes as f64 / voter_info.total_votes as f64) * 100.0;
+        
+        // Apply participation bonus (up to 10 points for active voters)
+        // Voters with more than 100 votes get full bonus
+        let participation_bonus = if voter_info.total_votes >= 100 {
+            10.0
+        } else {
+            (voter_info.total_votes as f64 / 100.0) * 10.0
+        };
+        
+        // Calculate final reputation (capped at 100)
+        let reputation = (accuracy + participation_bonus).min(100.0);
+        
+        reputation as u32
+    }
+    
+    /// Update voter reputation after a query is resolved
+    /// 
+    /// This method should be called when a query is resolved to update
+    /// the reputation of voters who participated
+    pub async fn update_voter_reputation(
+        &mut self,
+        voter_chain: &ChainId,
+        was_correct: bool,
+    ) -> Result<(), String> {
+        let mut voter_info = self.get_voter(voter_chain).await
+            .ok_or_else(|| "Voter not found".to_string())?;
+        
+        // Update vote counts
+        if was_correct {
+            voter_info.correct_votes = voter_info.correct_votes.saturating_add(1);
+        }
+        // total_votes is already incremented when vote is submitted
+        
+        // Recalculate reputation
+        voter_info.reputation = self.calculate_reputation(&voter_info);
+        
+        // Save updated voter info
+        self.save_voter(voter_chain, voter_info).await
+            .map_err(|e| format!("Failed to update voter reputation: {}", e))?;
+        
+        Ok(())
+    }
+    
+    /// Record a resolved vote's confidence against its correctness
+    pub async fn update_voter_calibration(
+        &mut self,
+        voter_chain: &ChainId,
+        confidence: u8,
+        was_correct: bool,
+    ) -> Result<(), String> {
+        let mut voter_info = self.get_voter(voter_chain).await
+            .ok_or_else(|| "Voter not found".to_string())?;
+        
+        voter_info.record_calibration(confidence, was_correct);
+        
+        self.save_voter(voter_chain, voter_info).await
+            .map_err(|e| format!("Failed to update voter calibration: {}", e))
+    }
+    
+    /// Calculate reputation decay for inactive voters
+    /// 
+    /// Voters who haven't voted recently should have their reputation
+    /// gradually decay to encourage active participation
+    pub fn calculate_reputation_with_decay(
+        &self,
+        voter_info: &VoterInfo,
+        current_time: Timestamp,
+    ) -> u32 {
+        let base_reputation = self.calculate_reputation(voter_info);
+        
+        // Calculate days since registration
+        // TimeDelta is in microseconds, convert to days
+        let micros_since_registration = current_time
+            .delta_since(voter_info.registered_at)
+            .as_micros();
+        let days_since_registration = micros_since_registration / (86400 * 1_000_000);
+        
+        // If voter has been registered for more than 30 days but has very few votes,
+        // apply decay
+        if days_since_registration > 30 && voter_info.total_votes < 10 {
+            let decay_factor = 0.9; // 10% decay
+            (base_reputation as f64 * decay_factor) as u32
+        } else {
+            base_reputation
+        }
+    }
+    
+    /// Get reputation tier for a voter
+    /// 
+    /// Returns a tier classification based on reputation score:
+    /// - Novice: 0-40
+    /// - Intermediate: 41-70
+    /// - Expert: 71-90
+    /// - Master: 91-100
+    pub fn get_reputation_tier(&self, reputation: u32) -> &'static str {
+        match reputation {
+            0..=40 => "Novice",
+            41..=70 => "Intermediate",
+            71..=90 => "Expert",
+            91..=100 => "Master",
+            _ => "Unknown",
+        }
+    }
+    
+    /// Calculate reputation weight for weighted voting strategies
+    /// 
+    /// Returns a weight multiplier based on reputation (0.5 to 2.0)
+    /// Higher reputation = higher weight
+    pub fn calculate_reputation_weight(&self, reputation: u32) -> f64 {
+        reputation_weight(reputation)
+    }
+    
+    /// Get active queries
+    pub async fn get_active_queries(&self) -> Vec<u64> {
+        self.active_queries.get().clone()
+    }
+    
+    /// Get pending rewards for a voter by chain ID
+    pub async fn get_pending_rewards(&self, voter_chain: &ChainId) -> Amount {
+        self.pending_rewards.get(voter_chain).await.ok().flatten().unwrap_or(Amount::ZERO)
+    }
+    
+    /// Add `amount` to a voter's pending rewards, stamping the credit at `now`
+    pub async fn credit_pending_rewards(&mut self, voter_chain: &ChainId, amount: Amount, now: Timestamp) -> Result<(), String> {
+        let current = self.get_pending_rewards(voter_chain).await;
+        self.pending_rewards.insert(voter_chain, current.saturating_add(amount))
+            .map_err(|e| format!("Failed to credit pending rewards: {}", e))?;
+        self.reward_credited_at.insert(voter_chain, now)
+            .map_err(|e| format!("Failed to record reward credit time: {}", e))
+    }
+    
+    /// Clear a voter's pending rewards and their credit time (claimed or reclaimed)
+    pub fn clear_pending_rewards(&mut self, voter_chain: &ChainId) -> Result<(), String> {
+        self.pending_rewards.remove(voter_chain)
+            .map_err(|e| format!("Failed to clear pending rewards: {}", e))?;
+        self.reward_credited_at.remove(voter_chain)
+            .map_err(|e| format!("Failed to clear reward credit time: {}", e))
+    }
+    
+    /// Voters whose pending rewards were last credited before `cutoff`, with the amounts
+    /// 
+    /// Checks at most `max` voters after `cursor`, in ascending order, so
+    /// repeated calls walk every voter with a credit time and then wrap
+    /// around. Rewards credited before credit times were tracked have none
+    /// and are never listed.
+    pub async fn expired_pending_rewards(
+        &self,
+        cutoff: Timestamp,
+        cursor: Option<ChainId>,
+        max: usize,
+    ) -> Result<ReclaimBatch, String> {
+        let mut voters: Vec<ChainId> = self.reward_credited_at.indices().await
+            .map_err(|e| format!("Failed to get reward credit indices: {}", e))?
+            .into_iter()
+            .filter(|voter| cursor.is_none_or(|c| *voter > c))
+            .collect();
+        voters.sort_unstable();
+        
+        let remaining = voters.len().saturating_sub(max);
+        voters.truncate(max);
+        let next_cursor = if remaining > 0 { voters.last().copied() } else { None };
+        
+        let mut expired = Vec::new();
+        for voter in voters {
+            let credited_at = self.reward_credited_at.get(&voter).await.ok().flatten();
+            if credited_at.is_some_and(|credited_at| credited_at < cutoff) {
+                let pending = self.get_pending_rewards(&voter).await;
+                expired.push((voter, pending));
+            }
+        }
+        Ok(ReclaimBatch { expired, next_cursor, remaining })
+    }
+    
+    /// Lock stake for a voter (when they vote on a query)
+    pub async fn lock_stake(&mut self, voter_chain: &ChainId, amount: Amount) -> Result<(), String> {
+        let mut voter_info = self.get_voter(voter_chain).await
+            .ok_or_else(|| "Voter not found".to_string())?;
+        
+        voter_info.lock_stake(amount)?;
+        self.save_voter(voter_chain, voter_info).await
+            .map_err(|e| format!("Failed to update voter: {}", e))?;
+        
+        Ok(())
+    }
+    
+    /// Unlock stake for a voter (when query is resolved)
+    pub async fn unlock_stake(&mut self, voter_chain: &ChainId, amount: Amount) -> Result<(), String> {
+        let mut voter_info = self.get_voter(voter_chain).await
+            .ok_or_else(|| "Voter not found".to_string())?;
+        
+        voter_info.unlock_stake(amount)?;
+        self.save_voter(voter_chain, voter_info).await
+            .map_err(|e| format!("Failed to update voter: {}", e))?;
+        
+        Ok(())
+    }
+    
+    /// Check that `voter_chain` has not used `commit_hash` on a still-open query
+    pub async fn check_commit_hash_unused(
+        &self,
+        voter_chain: &ChainId,
+        query_id: u64,
+        commit_hash: &str,
+        now: Timestamp,
+    ) -> Result<(), String> {
+        let key = (*voter_chain, commit_hash.to_ascii_lowercase());
+        let previous = self.commit_hashes.get(&key).await
+            .map_err(|e| format!("Failed to read commit hashes: {}", e))?;
+        check_commit_hash_reuse(previous.as_ref(), query_id, now)
+    }
+    
+    /// Record that `voter_chain` committed `commit_hash` to a query
+    pub fn record_commit_hash(
+        &mut self,
+        voter_chain: &ChainId,
+        commit_hash: &str,
+        commit_use: CommitHashUse,
+    ) -> Result<(), String> {
+        let key = (*voter_chain, commit_hash.to_ascii_lowercase());
+        self.commit_hashes.insert(&key, commit_use)
+            .map_err(|e| format!("Failed to record commit hash: {}", e))
+    }
+    
+    /// Record the stake a voter locked on a query
+    pub fn record_stake_lock(&mut self, query_id: u64, voter_chain: &ChainId, amount: Amount) -> Result<(), String> {
+        self.stake_locks.insert(&(query_id, *voter_chain), amount)
+            .map_err(|e| format!("Failed to record stake lock: {}", e))
+    }
+    
+    /// Remove and return the stake a voter locked on a query, if recorded
+    pub async fn take_stake_lock(&mut self, query_id: u64, voter_chain: &ChainId) -> Option<Amount> {
+        let amount = self.stake_locks.get(&(query_id, *voter_chain)).await.ok().flatten()?;
+        self.stake_locks.remove(&(query_id, *voter_chain)).ok()?;
+        Some(amount)
+    }
+    
+    /// Remove a voter's locks on finalized queries still awaiting UnlockQueryStakes
+    /// 
+    /// Returns the total stake those locks held.
+    pub async fn take_pending_unlock_locks(&mut self, voter_chain: &ChainId) -> Amount {
+        let query_ids = self.pending_unlocks.indices().await.unwrap_or_default();
+        let mut released = Amount::ZERO;
+        for query_id in query_ids {
+            if let Some(amount) = self.take_stake_lock(query_id, voter_chain).await {
+                released = released.saturating_add(amount);
+            }
+        }
+        released
+    }
+    
+    /// Remove and return the creation deposit held for a query, if any
+    pub async fn take_creation_deposit(&mut self, query_id: u64) -> Option<CreationDeposit> {
+        let deposit = self.creation_deposits.get(&query_id).await.ok().flatten()?;
+        self.creation_deposits.remove(&query_id).ok()?;
+        Some(deposit)
+    }
+    
+    /// Number of stake lockers already unlocked, if the query's unlocking is in progress
+    pub async fn get_unlock_cursor(&self, query_id: u64) -> Option<usize> {
+        self.pending_unlocks.get(&query_id).await.ok().flatten()
+    }
+    
+    /// Get available (unlocked) stake for a voter
+    pub async fn get_available_stake(&self, voter_chain: &ChainId) -> Amount {
+        match self.get_voter(voter_chain).await {
+            Some(info) => {
+                // Use Amount's saturating_sub directly to avoid double conversion
+                info.stake.saturating_sub(info.locked_stake)
+            },
+            None => Amount::ZERO,
+        }
+    }
+    
+    /// Get comprehensive reputation statistics for a voter
+    pub async fn get_reputation_stats(&self, voter_chain: &ChainId) -> Option<ReputationStats> {
+        let voter_info = self.get_voter(voter_chain).await?;
+        
+        Some(ReputationStats {
+            reputation: voter_info.reputation,
+            tier: self.get_reputation_tier(voter_info.reputation).to_string(),
+            weight: self.calculate_reputation_weight(voter_info.reputation),
+            total_votes: voter_info.total_votes,
+            correct_votes: voter_info.correct_votes,
+            accuracy_percentage: voter_info.accuracy_percentage(),
+        })
+    }
+    
+    /// Calculate reward for a voter based on multiple factors
+    /// 
+    /// This method calculates the reward amount for a voter who voted correctly,
+    /// taking into account:
+    /// - Base reward (query reward amount divided among correct voters)
+    /// - Reputation multiplier (higher reputation = higher reward)
+    /// - Stake multiplier (higher stake = higher reward)
+    /// - Protocol fee deduction
+    /// 
+    /// Returns the final reward amount for the voter
+    pub fn calculate_voter_reward(
+        &self,
+        base_reward: Amount,
+        voter_info: &VoterInfo,
+        params: &ProtocolParameters,
+    ) -> Amount {
+        voter_reward(base_reward, voter_info, params)
+    }
+    
+    /// Calculate slash amount for incorrect voters
+    /// 
+    /// Voters who vote incorrectly may have a portion of their stake slashed
+    /// based on the protocol's slash_percentage parameter.
+    /// 
+    /// The slash amount is calculated as a percentage of the voter's total stake.
+    /// For example, with a 5% slash rate (500 basis points):
+    /// - Voter with 1000 tokens stake → 50 tokens slashed
+    /// - Voter with 500 tokens stake → 25 tokens slashed
+    pub fn calculate_slash_amount(
+        &self,
+        voter_info: &VoterInfo,
+        params: &ProtocolParameters,
+    ) -> Amount {
+        let stake_value: u128 = voter_info.stake.into();
+        
+        // Calculate slash amount (in basis points, e.g., 500 = 5%)
+        let slash_multiplier = params.slash_percentage as f64 / 10000.0;
+        let slash_amount = (stake_value as f64 * slash_multiplier) as u128;
+        
+        Amount::from_tokens(slash_amount)
+    }
+    
+    /// Check if voter's stake would fall below minimum after slashing
+    /// 
+    /// Returns true if the voter should be automatically deactivated after slashing
+    pub fn should_deactivate_after_slash(
+        &self,
+        voter_info: &VoterInfo,
+        slash_amount: Amount,
+        params: &ProtocolParameters,
+    ) -> bool {
+        let stake_value: u128 = voter_info.stake.into();
+        let slash_value: u128 = slash_amount.into();
+        let remaining_stake = stake_value.saturating_sub(slash_value);
+        let min_stake_value: u128 = params.min_stake.into();
+        
+        remaining_stake < min_stake_value
+    }
+    
+    /// Calculate total slashing statistics for a query
+    /// 
+    /// Returns (total_slashed, voters_slashed, voters_deactivated)
+    pub fn calculate_slashing_stats(
+        &self,
+        incorrect_voters: &[(ChainId, VoterInfo)],
+        params: &ProtocolParameters,
+    ) -> (Amount, usize, usize) {
+        let mut total_slashed = 0u128;
+        let mut voters_slashed = 0;
+        let mut voters_deactivated = 0;
+        
+        for (_, voter_info) in incorrect_voters {
+            let slash_amount = self.calculate_slash_amount(voter_info, params);
+            let slash_value: u128 = slash_amount.into();
+            
+            if slash_value > 0 {
+                voters_slashed += 1;
+                total_slashed += slash_value;
+                
+                if self.should_deactivate_after_slash(voter_info, slash_amount, params) {
+                    voters_deactivated += 1;
+                }
+            }
+        }
+        
+        (Amount::from_tokens(total_slashed), voters_slashed, voters_deactivated)
+    }
+    
+    /// Calculate total reward pool for a query
+    /// 
+    /// This includes the base reward amount plus any protocol fees collected
+    pub fn calculate_total_reward_pool(
+        &self,
+        query_reward: Amount,
+        protocol_fees: Amount,
+    ) -> Amount {
+        let reward_value: u128 = query_reward.into();
+        let fees_value: u128 = protocol_fees.into();
+        
+        Amount::from_tokens(reward_value + fees_value)
+    }
+    
+    /// Calculate protocol fee from reward amount
+    pub fn calculate_protocol_fee(
+        &self,
+        reward_amount: Amount,
+        params: &ProtocolParameters,
+    ) -> Amount {
+        let reward_value: u128 = reward_amount.into();
+        
+        // Calculate fee (in basis points, e.g., 100 = 1%)
+        let fee_multiplier = params.protocol_fee as f64 / 10000.0;
+        let fee_amount = (reward_value as f64 * fee_multiplier) as u128;
+        
+        Amount::from_tokens(fee_amount)
+    }
+    
+    /// Calculate stake-weighted reward distribution
+    /// 
+    /// Distributes rewards proportionally based on voter stakes
+    /// Returns a map of voter -> reward amount
+    pub fn calculate_stake_weighted_rewards(
+        &self,
+        total_reward: Amount,
+        correct_voters: &[(ChainId, VoterInfo)],
+        params: &ProtocolParameters,
+    ) -> std::collections::BTreeMap<ChainId, Amount> {
+        stake_weighted_rewards(total_reward, correct_voters, params)
+    }
+    
+    /// Calculate reputation-weighted reward distribution
+    /// 
+    /// Distributes rewards proportionally based on voter reputation
+    /// Returns a map of voter -> reward amount
     pub fn calculate_reputation_weighted_rewards(
         &self,
         total_reward: Amount,
         correct_voters: &[(ChainId, VoterInfo)],
         params: &ProtocolParameters,
     ) -> std::collections::BTreeMap<ChainId, Amount> {
-        let mut rewards = std::collections::BTreeMap::new();
+        reputation_weighted_rewards(total_reward, correct_voters, params)
+    }
+    
+    /// Calculate equal reward distribution
+    /// 
+    /// Distributes rewards equally among all correct voters
+    /// Returns a map of voter -> reward amount
+    pub fn calculate_equal_rewards(
+        &self,
+        total_reward: Amount,
+        correct_voters: &[(ChainId, VoterInfo)],
+        params: &ProtocolParameters,
+    ) -> std::collections::BTreeMap<ChainId, Amount> {
+        equal_rewards(total_reward, correct_voters, params)
+    }
+    
+    /// Calculate voter power (stake × reputation), with the stake capped at
+    /// the `max_effective_stake` parameter
+    pub fn calculate_voter_power(&self, voter: &VoterInfo) -> u128 {
+        voter.capped_power(self.parameters.get().max_effective_stake)
+    }
+    
+    /// Get all active voters sorted by power (descending)
+    pub async fn get_voters_by_power(&self) -> Result<Vec<(ChainId, u128)>, String> {
+        let mut voter_powers: Vec<(ChainId, u128)> = Vec::new();
+        
+        // Get all voter chain IDs
+        let indices = self.voters.indices().await
+            .map_err(|e| format!("Failed to get voter indices: {}", e))?;
         
-        if correct_voters.is_empty() {
-            return rewards;
+        // Calculate power for each active voter
+        for chain_id in indices {
+            if let Some(voter) = self.get_voter(&chain_id).await {
+                if voter.is_active {
+                    let power = self.calculate_voter_power(&voter);
+                    voter_powers.push((chain_id, power));
+                }
+            }
         }
         
-        // Calculate total reputation weight of correct voters
-        let total_weight: f64 = correct_voters
-            .iter()
-            .map(|(_, info)| self.calculate_reputation_weight(info.reputation))
-            .sum();
+        // Sort by power (descending)
+        voter_powers.sort_by(|a, b| b.1.cmp(&a.1));
+        
+        Ok(voter_powers)
+    }
+    
+    /// Active voters' power binned into at most `buckets` equal ranges
+    pub async fn compute_power_distribution(&self, buckets: usize) -> Result<Vec<PowerBucket>, String> {
+        let powers: Vec<u128> = self.get_voters_by_power().await?
+            .into_iter()
+            .map(|(_, power)| power)
+            .collect();
+        Ok(bucket_voting_power(&powers, buckets))
+    }
+    
+    /// Select top N voters by power for a query
+    /// 
+    /// TEMPORARY: Returns ALL active voters instead of top N by power
+    /// This allows all registered voters to participate in voting
+    /// 
+    /// With `selection_cooldown_queries` set, voters selected in the last N
+    /// queries are passed over while `min_voters` can be met without them.
+    pub async fn select_voters_for_query(
+        &self,
+        min_voters: usize,
+        _max_voters: usize,
+    ) -> Result<Vec<ChainId>, String> {
+        // TEMPORARY: Return ALL active voters instead of selecting by power
+        // This allows all registered voters to vote on any query
+        let voter_powers = self.get_voters_by_power().await?;
+        let registered_voters = *self.voter_count.get();
+        
+        let ranked = select_eligible_voters(&voter_powers, registered_voters)?;
         
-        if total_weight == 0.0 {
-            return rewards;
+        let cooldown = self.parameters.get().selection_cooldown_queries;
+        if cooldown == 0 {
+            return Ok(ranked);
         }
         
-        let reward_value: u128 = total_reward.into();
+        let next_query_id = *self.next_query_id.get();
+        let mut recently_selected = Vec::new();
+        for voter in &ranked {
+            if let Some(last) = self.last_selected_query.get(voter).await.ok().flatten() {
+                if in_selection_cooldown(last, next_query_id, cooldown) {
+                    recently_selected.push(*voter);
+                }
+            }
+        }
         
-        // Distribute rewards proportionally to reputation weight
-        for (voter, info) in correct_voters {
-            let weight = self.calculate_reputation_weight(info.reputation);
-            let proportion = weight / total_weight;
-            let base_reward = (reward_value as f64 * proportion) as u128;
-            
-            // Apply protocol fee (reputation already factored in)
-            let fee_multiplier = 1.0 - (params.protocol_fee as f64 / 10000.0);
-            let final_reward = (base_reward as f64 * fee_multiplier) as u128;
+        Ok(apply_selection_cooldown(ranked, &recently_selected, min_voters))
+    }
+    
+    /// Get a voter's lifetime rewards and slashes
+    pub async fn get_voter_earnings(&self, voter: &ChainId) -> VoterEarnings {
+        self.voter_earnings.get(voter).await.ok().flatten().unwrap_or_default()
+    }
+    
+    /// Add `amount` to a voter's lifetime rewards
+    pub async fn record_voter_reward(&mut self, voter: &ChainId, amount: Amount) -> Result<(), String> {
+        let mut earnings = self.get_voter_earnings(voter).await;
+        earnings.total_rewarded = earnings.total_rewarded.saturating_add(amount);
+        self.voter_earnings.insert(voter, earnings)
+            .map_err(|e| format!("Failed to record voter earnings: {}", e))
+    }
+    
+    /// Add `amount` to a voter's lifetime slashes
+    pub async fn record_voter_slash(&mut self, voter: &ChainId, amount: Amount) -> Result<(), String> {
+        let mut earnings = self.get_voter_earnings(voter).await;
+        earnings.total_slashed = earnings.total_slashed.saturating_add(amount);
+        self.voter_earnings.insert(voter, earnings)
+            .map_err(|e| format!("Failed to record voter earnings: {}", e))
+    }
+    
+    /// Stake snapshots recorded for a voter, oldest first
+    pub async fn get_stake_history(&self, voter: &ChainId) -> Vec<StakeSnapshot> {
+        self.stake_history.get(voter).await.ok().flatten().unwrap_or_default()
+    }
+    
+    /// Append a snapshot of `voter_info`'s stake taken at `timestamp`
+    pub async fn record_stake_snapshot(
+        &mut self,
+        voter: &ChainId,
+        voter_info: &VoterInfo,
+        timestamp: Timestamp,
+    ) -> Result<(), String> {
+        let mut history = self.get_stake_history(voter).await;
+        push_stake_snapshot(&mut history, StakeSnapshot {
+            timestamp,
+            stake: voter_info.stake,
+            locked_stake: voter_info.locked_stake,
+        });
+        self.stake_history.insert(voter, history)
+            .map_err(|e| format!("Failed to record stake snapshot: {}", e))
+    }
+    
+    /// Remember that `voters` were selected for `query_id` (selection
+    /// cooldown and selection counts)
+    pub async fn record_voter_selections(&mut self, query_id: u64, voters: &[ChainId]) -> Result<(), String> {
+        for voter in voters {
+            self.last_selected_query.insert(voter, query_id)
+                .map_err(|e| format!("Failed to record voter selection: {}", e))?;
             
-            rewards.insert(*voter, Amount::from_tokens(final_reward));
+            let mut record = self.get_selection_record(voter).await;
+            record.selected = record.selected.saturating_add(1);
+            self.selection_records.insert(voter, record)
+                .map_err(|e| format!("Failed to record voter selection: {}", e))?;
+        }
+        Ok(())
+    }
+    
+    /// Record which of a closed query's panel took part in it
+    pub async fn record_panel_participation(&mut self, query: &Query) -> Result<(), String> {
+        for voter in &query.selected_voters {
+            let mut record = self.get_selection_record(voter).await;
+            record.closed = record.closed.saturating_add(1);
+            if query.commits.contains_key(voter) || query.votes.contains_key(voter) {
+                record.participated = record.participated.saturating_add(1);
+            }
+            self.selection_records.insert(voter, record)
+                .map_err(|e| format!("Failed to record panel participation: {}", e))?;
+        }
+        Ok(())
+    }
+    
+    /// A voter's panel selections and participation
+    pub async fn get_selection_record(&self, voter: &ChainId) -> SelectionRecord {
+        self.selection_records.get(voter).await.ok().flatten().unwrap_or_default()
+    }
+    
+    /// Selection records for every voter ever selected, plus registered
+    /// voters never selected, with the concentration of selections
+    pub async fn compute_selection_stats(&self) -> Result<SelectionStats, String> {
+        let mut records: BTreeMap<ChainId, SelectionRecord> = BTreeMap::new();
+        
+        let selected = self.selection_records.indices().await
+            .map_err(|e| format!("Failed to get selection record indices: {}", e))?;
+        for voter in selected {
+            records.insert(voter, self.get_selection_record(&voter).await);
+        }
+        
+        let registered = self.voters.indices().await
+            .map_err(|e| format!("Failed to get voter indices: {}", e))?;
+        for voter in registered {
+            records.entry(voter).or_default();
         }
         
-        rewards
+        Ok(SelectionStats::from_records(records.into_iter().collect()))
+    }
+    
+    /// Check if a voter is selected for a specific query
+    pub async fn is_voter_selected(
+        &self,
+        query_id: u64,
+        voter_chain: &ChainId,
+    ) -> Result<bool, String> {
+        let query = self.get_query(query_id).await
+            .ok_or_else(|| format!("Query {} not found", query_id))?;
+        
+        Ok(query.selected_voters.contains(voter_chain))
+    }
+    
+    /// Get voter power for display/analytics
+    pub async fn get_voter_power_info(
+        &self,
+        voter_chain: &ChainId,
+    ) -> Option<(Amount, u32, u128)> {
+        let voter_info = self.get_voter(voter_chain).await?;
+        let power = self.calculate_voter_power(&voter_info);
+        Some((voter_info.stake, voter_info.reputation, power))
+    }
+}
+
+/// Append a flow entry to a bounded history log, evicting the oldest entry
+fn append_flow(
+    log: &mut MapView<u64, FlowEntry>,
+    next_id: &mut RegisterView<u64>,
+    timestamp: Timestamp,
+    query_id: u64,
+    amount: Amount,
+) -> Result<u64, String> {
+    let id = *next_id.get();
+    
+    if let Some(evicted) = flow_history_evicted_id(id) {
+        log.remove(&evicted)
+            .map_err(|e| format!("Failed to evict history entry: {}", e))?;
+    }
+    
+    log.insert(&id, FlowEntry { id, timestamp, query_id, amount })
+        .map_err(|e| format!("Failed to record history entry: {}", e))?;
+    next_id.set(id + 1);
+    
+    Ok(id)
+}
+
+/// Read the entries still held by a bounded history log, oldest first
+async fn read_flows(log: &MapView<u64, FlowEntry>, next_id: u64) -> Vec<FlowEntry> {
+    let oldest = next_id.saturating_sub(MAX_FLOW_HISTORY);
+    let mut entries = Vec::new();
+    
+    for id in oldest..next_id {
+        if let Some(entry) = log.get(&id).await.ok().flatten() {
+            entries.push(entry);
+        }
+    }
+    
+    entries
+}
+
+/// Pick the voters for a new query from active voters ranked by power
+/// 
+/// Returns ALL ranked voters (not just top N). Fails with a "no eligible
+/// voters" error carrying the registered voter count when nobody is active.
+pub fn select_eligible_voters(
+    voter_powers: &[(ChainId, u128)],
+    registered_voters: u64,
+) -> Result<Vec<ChainId>, String> {
+    if voter_powers.is_empty() {
+        return Err(format!(
+            "No eligible voters: {} registered, 0 active",
+            registered_voters
+        ));
+    }
+    
+    Ok(voter_powers.iter().map(|(chain_id, _power)| *chain_id).collect())
+}
+
+/// Whether a voter last selected for `last_selected` is still cooling down
+/// when query `query_id` is created, i.e. was selected in the last `cooldown` queries
+pub fn in_selection_cooldown(last_selected: u64, query_id: u64, cooldown: u64) -> bool {
+    cooldown > 0 && last_selected < query_id && query_id - last_selected <= cooldown
+}
+
+/// Pass over recently selected voters when the panel can be filled without them
+/// 
+/// `ranked` is ordered by power. If at least `min_voters` voters are outside
+/// their cooldown, only those are returned. Otherwise the panel is topped up
+/// with the highest-power recently selected voters, just enough to reach
+/// `min_voters`.
+pub fn apply_selection_cooldown(
+    ranked: Vec<ChainId>,
+    recently_selected: &[ChainId],
+    min_voters: usize,
+) -> Vec<ChainId> {
+    let (cooling, mut fresh): (Vec<ChainId>, Vec<ChainId>) = ranked
+        .into_iter()
+        .partition(|voter| recently_selected.contains(voter));
+    
+    if fresh.len() < min_voters {
+        let shortfall = min_voters - fresh.len();
+        fresh.extend(cooling.into_iter().take(shortfall));
+    }
+    
+    fresh
+}
+
+/// Voters whose stake is unlocked in the block that finalizes a query
+/// 
+/// Any remaining lockers are unlocked by later UnlockQueryStakes operations.
+pub const UNLOCK_BATCH_SIZE: usize = 50;
+
+/// Next batch of stake lockers to unlock, starting at `cursor`
+/// 
+/// `lockers` must be in a stable order (see `Query::stake_lockers`). Returns
+/// the batch of at most `max` lockers and the cursor after it, or `None`
+/// once every locker has been processed.
+pub fn next_unlock_batch(lockers: &[ChainId], cursor: usize, max: usize) -> (&[ChainId], Option<usize>) {
+    let start = cursor.min(lockers.len());
+    let end = start.saturating_add(max).min(lockers.len());
+    let next_cursor = if end < lockers.len() { Some(end) } else { None };
+    (&lockers[start..end], next_cursor)
+}
+
+/// Maximum number of resolved votes in a voter dashboard's recent history
+pub const DASHBOARD_HISTORY_LIMIT: usize = 20;
+
+/// Maximum number of stake snapshots kept per voter
+pub const MAX_STAKE_HISTORY: usize = 100;
+
+/// A voter's stake at one point in time
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StakeSnapshot {
+    /// When the stake changed
+    pub timestamp: Timestamp,
+    
+    /// Total stake after the change
+    pub stake: Amount,
+    
+    /// Stake locked in active queries at the time
+    pub locked_stake: Amount,
+}
+
+/// Append `snapshot` to `history`, dropping the oldest entries beyond MAX_STAKE_HISTORY
+pub fn push_stake_snapshot(history: &mut Vec<StakeSnapshot>, snapshot: StakeSnapshot) {
+    history.push(snapshot);
+    if history.len() > MAX_STAKE_HISTORY {
+        let excess = history.len() - MAX_STAKE_HISTORY;
+        history.drain(..excess);
+    }
+}
+
+/// Lifetime tokens rewarded to and slashed from a voter at resolution
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct VoterEarnings {
+    pub total_rewarded: Amount,
+    pub total_slashed: Amount,
+}
+
+/// A voter's vote on a resolved query
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoterHistoryItem {
+    pub query_id: u64,
+    pub value: String,
+    
+    /// Whether the vote matched the result (None for abstentions)
+    pub correct: Option<bool>,
+    
+    pub resolved_at: Option<Timestamp>,
+}
+
+/// Query IDs and history a voter dashboard shows, taken from one set of queries
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VoterActivity {
+    /// Active queries the voter was selected for
+    pub selected_queries: Vec<u64>,
+    
+    /// Active queries with a commit the voter has not revealed yet
+    pub pending_reveals: Vec<u64>,
+    
+    /// Votes on resolved queries, most recently resolved first
+    pub recent_history: Vec<VoterHistoryItem>,
+}
+
+/// Collect a voter's dashboard activity from `queries`
+/// 
+/// `recent_history` is capped at `history_limit` entries.
+pub fn voter_activity(voter: &ChainId, queries: &[Query], history_limit: usize) -> VoterActivity {
+    let mut activity = VoterActivity::default();
+    
+    for query in queries {
+        match query.status {
+            QueryStatus::Active => {
+                if query.selected_voters.contains(voter) {
+                    activity.selected_queries.push(query.id);
+                }
+                if query.commits.get(voter).is_some_and(|commit| !commit.revealed) {
+                    activity.pending_reveals.push(query.id);
+                }
+            }
+            QueryStatus::Resolved => {
+                if let Some(vote) = query.votes.get(voter) {
+                    let correct = if query.is_abstention(&vote.value) {
+                        None
+                    } else {
+                        query.result.as_ref().map(|result| result.matches_vote(&vote.value))
+                    };
+                    activity.recent_history.push(VoterHistoryItem {
+                        query_id: query.id,
+                        value: vote.value.clone(),
+                        correct,
+                        resolved_at: query.resolved_at,
+                    });
+                }
+            }
+            QueryStatus::Expired | QueryStatus::Cancelled | QueryStatus::NoConsensus => {}
+        }
+    }
+    
+    activity.recent_history.sort_by(|a, b| {
+        b.resolved_at.cmp(&a.resolved_at).then(b.query_id.cmp(&a.query_id))
+    });
+    activity.recent_history.truncate(history_limit);
+    
+    activity
+}
+
+/// Portable snapshot of a voter's track record
+/// 
+/// The field order is part of the canonical encoding and must not change.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VoterAttestation {
+    /// Voter chain ID as lowercase hex
+    pub address: String,
+    pub reputation: u32,
+    pub total_votes: u64,
+    pub correct_votes: u64,
+    
+    /// Registration time in microseconds since the Unix epoch
+    pub registered_at: u64,
+    
+    /// Block height of the registry chain the record was read at
+    pub block: u64,
+}
+
+impl VoterAttestation {
+    /// Snapshot `voter` as of `block`
+    pub fn new(voter: &VoterInfo, block: u64) -> Self {
+        Self {
+            address: voter.chain_id.to_string(),
+            reputation: voter.reputation,
+            total_votes: voter.total_votes,
+            correct_votes: voter.correct_votes,
+            registered_at: voter.registered_at.micros(),
+            block,
+        }
+    }
+    
+    /// Canonical encoding: the BCS bytes of the record as lowercase hex
+    pub fn encode(&self) -> String {
+        bcs::to_bytes(self)
+            .expect("VoterAttestation is always BCS-serializable")
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+/// Format a timestamp as ISO 8601 UTC with microsecond precision
+/// 
+/// e.g. `2023-11-14T22:13:20.123456Z`
+pub fn timestamp_to_iso8601(timestamp: Timestamp) -> String {
+    let micros = timestamp.micros();
+    let secs = micros / 1_000_000;
+    let days = secs / 86_400;
+    let secs_of_day = secs % 86_400;
+    
+    // Civil date from days since 1970-01-01 (proleptic Gregorian)
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z",
+        year, month, day,
+        secs_of_day / 3_600, secs_of_day % 3_600 / 60, secs_of_day % 60,
+        micros % 1_000_000
+    )
+}
+
+/// A pending-rewards claim paid out to a chain's account
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewardClaim {
+    /// Voter whose pending rewards are claimed
+    pub voter: ChainId,
+    
+    /// Chain whose account receives the tokens
+    pub target_chain: ChainId,
+    
+    /// Amount paid out
+    pub amount: Amount,
+}
+
+impl RewardClaim {
+    /// Whether the rewards go to a chain other than the voter's own
+    pub fn is_redirected(&self) -> bool {
+        self.voter != self.target_chain
+    }
+}
+
+/// Prepare a claim of `voter`'s pending rewards to `target_chain`
+pub fn prepare_reward_claim(voter: ChainId, target_chain: ChainId, pending: Amount) -> Result<RewardClaim, String> {
+    if pending == Amount::ZERO {
+        return Err("No pending rewards to claim".to_string());
+    }
+    
+    Ok(RewardClaim { voter, target_chain, amount: pending })
+}
+
+/// Maximum number of voters in one BatchClaimFor
+pub const MAX_BATCH_CLAIM: usize = 50;
+
+/// Check a BatchClaimFor voter list: 1 to MAX_BATCH_CLAIM distinct voters
+pub fn validate_batch_claim(voters: &[ChainId]) -> Result<(), String> {
+    if voters.is_empty() {
+        return Err("At least one voter is required".to_string());
+    }
+    if voters.len() > MAX_BATCH_CLAIM {
+        return Err(format!(
+            "Too many voters in one batch claim: {} (max {})",
+            voters.len(), MAX_BATCH_CLAIM
+        ));
+    }
+    let distinct: std::collections::BTreeSet<_> = voters.iter().collect();
+    if distinct.len() != voters.len() {
+        return Err("Duplicate voter in batch claim".to_string());
+    }
+    Ok(())
+}
+
+/// Maximum number of queries in one ExpireQueries
+pub const MAX_EXPIRE_QUERIES: usize = 50;
+
+/// Longest accepted external resolution source reference
+pub const MAX_RESOLUTION_SOURCE_LEN: usize = 256;
+
+/// Check an external resolution source reference: non-empty, at most
+/// MAX_RESOLUTION_SOURCE_LEN bytes
+pub fn validate_resolution_source(source: &str) -> Result<(), String> {
+    if source.trim().is_empty() {
+        return Err("Resolution source cannot be empty".to_string());
+    }
+    if source.len() > MAX_RESOLUTION_SOURCE_LEN {
+        return Err(format!(
+            "Resolution source too long: {} bytes (max {})",
+            source.len(), MAX_RESOLUTION_SOURCE_LEN
+        ));
+    }
+    Ok(())
+}
+
+/// Check an ExpireQueries id list: 1 to MAX_EXPIRE_QUERIES distinct ids
+pub fn validate_expire_queries(query_ids: &[u64]) -> Result<(), String> {
+    if query_ids.is_empty() {
+        return Err("At least one query ID is required".to_string());
+    }
+    if query_ids.len() > MAX_EXPIRE_QUERIES {
+        return Err(format!(
+            "Too many queries to expire at once: {} (max {})",
+            query_ids.len(), MAX_EXPIRE_QUERIES
+        ));
+    }
+    let distinct: std::collections::BTreeSet<_> = query_ids.iter().collect();
+    if distinct.len() != query_ids.len() {
+        return Err("Duplicate query ID in expire list".to_string());
+    }
+    Ok(())
+}
+
+/// Whether `caller` may claim `voter`'s rewards
+/// 
+/// Allowed for the voter itself, the admin and the voter's claim operator.
+pub fn check_claim_operator(
+    caller: &ChainId,
+    voter: &ChainId,
+    is_admin: bool,
+    operator: Option<ChainId>,
+) -> Result<(), String> {
+    if caller == voter || is_admin || operator.as_ref() == Some(caller) {
+        return Ok(());
+    }
+    Err(format!("Chain {} is not authorized to claim for voter {}", caller, voter))
+}
+
+/// Number of hex characters in a chain ID
+pub const CHAIN_ID_HEX_LEN: usize = 64;
+
+/// Parse a chain ID from user input
+/// 
+/// Accepts 64 hex characters with or without a `0x` prefix. Errors describe
+/// what was wrong and the expected format, without an "Invalid ..." prefix so
+/// callers can name the field.
+pub fn parse_chain_id(input: &str) -> Result<ChainId, String> {
+    let trimmed = input.trim();
+    let hex = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+    
+    if hex.len() != CHAIN_ID_HEX_LEN {
+        return Err(format!(
+            "expected {} hex characters (optionally prefixed with 0x), got {}",
+            CHAIN_ID_HEX_LEN, hex.len()
+        ));
+    }
+    
+    if let Some((position, c)) = hex.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "'{}' at position {} is not a hex digit; expected {} hex characters",
+            c, position, CHAIN_ID_HEX_LEN
+        ));
+    }
+    
+    hex.parse::<ChainId>().map_err(|e| format!("{}", e))
+}
+
+/// Maximum number of addresses accepted by a bulk voter lookup
+pub const MAX_VOTERS_BY_ADDRESS: usize = 100;
+
+/// Parse the addresses of a bulk voter lookup, keeping their order
+/// 
+/// Fails on an empty or oversized list, or on the first malformed address.
+pub fn parse_voter_addresses(addresses: &[String]) -> Result<Vec<ChainId>, String> {
+    if addresses.is_empty() {
+        return Err("At least one address is required".to_string());
+    }
+    if addresses.len() > MAX_VOTERS_BY_ADDRESS {
+        return Err(format!(
+            "Too many addresses: {} (max {})",
+            addresses.len(), MAX_VOTERS_BY_ADDRESS
+        ));
+    }
+    
+    addresses.iter()
+        .map(|address| parse_chain_id(address)
+            .map_err(|e| format!("Invalid chain ID '{}': {}", address, e)))
+        .collect()
+}
+
+/// Maximum number of voters compared side by side
+pub const MAX_COMPARED_VOTERS: usize = 10;
+
+/// Parse the addresses of a voter comparison, keeping their order
+/// 
+/// Like `parse_voter_addresses`, but limited to MAX_COMPARED_VOTERS entries.
+pub fn parse_compared_voters(addresses: &[String]) -> Result<Vec<ChainId>, String> {
+    if addresses.len() > MAX_COMPARED_VOTERS {
+        return Err(format!(
+            "Too many voters to compare: {} (max {})",
+            addresses.len(), MAX_COMPARED_VOTERS
+        ));
     }
+    parse_voter_addresses(addresses)
+}
+
+/// A voter's key metrics, for side-by-side comparison
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoterComparison {
+    pub chain_id: ChainId,
+    pub reputation: u32,
+    pub accuracy_percentage: f64,
+    pub stake: Amount,
     
-    /// Calculate equal reward distribution
-    /// 
-    /// Distributes rewards equally among all correct voters
-    /// Returns a map of voter -> reward amount
-    pub fn calculate_equal_rewards(
-        &self,
-        total_reward: Amount,
-        correct_voters: &[(ChainId, VoterInfo)],
-        params: &ProtocolParameters,
-    ) -> std::collections::BTreeMap<ChainId, Amount> {
-        let mut rewards = std::collections::BTreeMap::new();
-        
-        if correct_voters.is_empty() {
-            return rewards;
+    /// Selection power (stake in attos × reputation)
+    pub power: u128,
+    
+    pub total_votes: u64,
+}
+
+impl VoterComparison {
+    pub fn from_voter(voter: &VoterInfo) -> Self {
+        Self {
+            chain_id: voter.chain_id,
+            reputation: voter.reputation,
+            accuracy_percentage: voter.accuracy_percentage(),
+            stake: voter.stake,
+            power: voter.power(),
+            total_votes: voter.total_votes,
         }
-        
-        let reward_value: u128 = total_reward.into();
-        let per_voter_base = reward_value / correct_voters.len() as u128;
-        
-        // Distribute rewards equally with reputation multiplier
-        for (voter, info) in correct_voters {
-            let reward = self.calculate_voter_reward(
-                Amount::from_tokens(per_voter_base),
+    }
+}
+
+/// A voter's state on one query, see `Query::voter_status`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VoterQueryStatus {
+    /// On the query's selected panel
+    pub selected: bool,
+    
+    /// Submitted a commit hash
+    pub committed: bool,
+    
+    /// Revealed their commit
+    pub revealed: bool,
+    
+    /// Has a vote counted on the query (direct or revealed)
+    pub voted: bool,
+    
+    pub vote_value: Option<String>,
+    pub confidence: Option<u8>,
+}
+
+/// A voter on a query's selected panel
+#[derive(Debug, Clone)]
+pub struct PanelMember {
+    pub voter: ChainId,
+    
+    /// Current voter info (None if the voter has since deregistered)
+    pub info: Option<VoterInfo>,
+    
+    /// Current selection power (0 if deregistered)
+    pub power: u128,
+    
+    /// Whether the voter committed or cast a vote on the query
+    pub participated: bool,
+}
+
+/// Build a query's selected-voter panel, in `selected_voters` order
+pub fn query_panel(query: &Query, voter_infos: &BTreeMap<ChainId, VoterInfo>) -> Vec<PanelMember> {
+    query.selected_voters
+        .iter()
+        .map(|voter| {
+            let info = voter_infos.get(voter).cloned();
+            PanelMember {
+                voter: *voter,
+                power: info.as_ref().map_or(0, VoterInfo::power),
                 info,
-                params,
-            );
-            
-            rewards.insert(*voter, reward);
+                participated: query.commits.contains_key(voter) || query.votes.contains_key(voter),
+            }
+        })
+        .collect()
+}
+
+/// How a cross-chain RegisterVoter message is applied
+#[derive(Debug, Clone, PartialEq)]
+pub enum RegisterMessageOutcome {
+    /// Chain not registered yet - register it with the message's stake
+    Register,
+    
+    /// Chain already registered (duplicate or retried delivery) - no-op
+    /// 
+    /// The message's stake is not added; it is reported so callers can
+    /// account for it instead of silently dropping it.
+    Duplicate { unapplied_stake: Amount },
+}
+
+/// Decide how to apply a RegisterVoter message given the existing voter (if any)
+pub fn classify_register_message(existing: Option<&VoterInfo>, stake: Amount) -> RegisterMessageOutcome {
+    match existing {
+        None => RegisterMessageOutcome::Register,
+        Some(_) => RegisterMessageOutcome::Duplicate { unapplied_stake: stake },
+    }
+}
+
+/// Voter totals that otherwise need a scan of every voter
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VoterAggregates {
+    pub voters: u64,
+    pub active_voters: u64,
+    pub total_locked_stake: Amount,
+    pub reputation_sum: u64,
+}
+
+impl VoterAggregates {
+    /// Aggregate the given voters from scratch
+    pub fn from_voters<'a>(voters: impl IntoIterator<Item = &'a VoterInfo>) -> Self {
+        let mut aggregates = Self::default();
+        for voter in voters {
+            aggregates.replace(None, Some(voter));
         }
-        
-        rewards
+        aggregates
     }
     
-    /// Calculate voter power (stake × reputation)
-    pub fn calculate_voter_power(&self, voter: &VoterInfo) -> u128 {
-        let stake_value: u128 = voter.stake.into();
-        let reputation_value = voter.reputation as u128;
-        stake_value.saturating_mul(reputation_value)
+    /// Apply a voter changing from `previous` to `current` (None = absent)
+    pub fn replace(&mut self, previous: Option<&VoterInfo>, current: Option<&VoterInfo>) {
+        if let Some(voter) = previous {
+            self.voters = self.voters.saturating_sub(1);
+            self.active_voters = self.active_voters.saturating_sub(voter.is_active as u64);
+            self.total_locked_stake = self.total_locked_stake.saturating_sub(voter.locked_stake);
+            self.reputation_sum = self.reputation_sum.saturating_sub(voter.reputation as u64);
+        }
+        if let Some(voter) = current {
+            self.voters += 1;
+            self.active_voters += voter.is_active as u64;
+            self.total_locked_stake = self.total_locked_stake.saturating_add(voter.locked_stake);
+            self.reputation_sum += voter.reputation as u64;
+        }
     }
     
-    /// Get all active voters sorted by power (descending)
-    pub async fn get_voters_by_power(&self) -> Result<Vec<(ChainId, u128)>, String> {
-        let mut voter_powers: Vec<(ChainId, u128)> = Vec::new();
-        
-        // Get all voter chain IDs
-        let indices = self.voters.indices().await
-            .map_err(|e| format!("Failed to get voter indices: {}", e))?;
-        
-        // Calculate power for each active voter
-        for chain_id in indices {
-            if let Some(voter) = self.get_voter(&chain_id).await {
-                if voter.is_active {
-                    let power = self.calculate_voter_power(&voter);
-                    voter_powers.push((chain_id, power));
-                }
-            }
+    /// Average reputation across all voters (0 when there are none)
+    pub fn average_reputation(&self) -> f64 {
+        if self.voters == 0 {
+            return 0.0;
         }
-        
-        // Sort by power (descending)
-        voter_powers.sort_by(|a, b| b.1.cmp(&a.1));
-        
-        Ok(voter_powers)
+        self.reputation_sum as f64 / self.voters as f64
     }
+}
+
+/// What the registry owes voters against the balances backing it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solvency {
+    pub total_staked: Amount,
     
-    /// Select top N voters by power for a query
+    /// Part of `total_staked` locked on open votes
+    pub total_locked: Amount,
+    
+    pub total_pending_rewards: Amount,
+    pub reward_pool: Amount,
+    pub treasury: Amount,
+    
+    /// Tokens received from the token chain and not yet returned
+    pub tokens_held: Amount,
+}
+
+impl Solvency {
+    /// Stakes plus unclaimed rewards
+    pub fn obligations(&self) -> Amount {
+        self.total_staked.saturating_add(self.total_pending_rewards)
+    }
+    
+    /// Tokens held for voters plus the reward pool
     /// 
-    /// TEMPORARY: Returns ALL active voters instead of top N by power
-    /// This allows all registered voters to participate in voting
-    pub async fn select_voters_for_query(
-        &self,
-        _min_voters: usize,
-        _max_voters: usize,
-    ) -> Result<Vec<ChainId>, String> {
-        // TEMPORARY: Return ALL active voters instead of selecting by power
-        // This allows all registered voters to vote on any query
-        let voter_powers = self.get_voters_by_power().await?;
-        
-        // Check if we have at least one voter
-        if voter_powers.is_empty() {
-            return Err("No active voters available".to_string());
+    /// The treasury is the protocol's own balance, so it does not count
+    /// towards backing voter obligations.
+    pub fn backing(&self) -> Amount {
+        self.tokens_held.saturating_add(self.reward_pool)
+    }
+    
+    /// Whether every obligation is backed by a tracked balance
+    pub fn is_solvent(&self) -> bool {
+        self.backing() >= self.obligations()
+    }
+    
+    /// How far obligations exceed backing (zero when solvent)
+    pub fn shortfall(&self) -> Amount {
+        self.obligations().saturating_sub(self.backing())
+    }
+}
+
+/// Voters whose power falls in `min_power..=max_power`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerBucket {
+    pub min_power: u128,
+    pub max_power: u128,
+    pub voter_count: u64,
+    pub total_power: u128,
+}
+
+/// Bin `powers` into at most `buckets` equal-width ranges from the lowest to
+/// the highest power
+/// 
+/// Ranges are contiguous and the last one ends at the highest power. Fewer
+/// buckets are returned when the span of powers is smaller than `buckets`;
+/// none when `powers` is empty or `buckets` is 0.
+pub fn bucket_voting_power(powers: &[u128], buckets: usize) -> Vec<PowerBucket> {
+    let (Some(&lowest), Some(&highest)) = (powers.iter().min(), powers.iter().max()) else {
+        return Vec::new();
+    };
+    if buckets == 0 {
+        return Vec::new();
+    }
+    
+    let span = (highest - lowest).saturating_add(1);
+    let width = span.div_ceil(buckets as u128);
+    let count = span.div_ceil(width) as usize;
+    
+    let mut distribution: Vec<PowerBucket> = (0..count)
+        .map(|index| {
+            let min_power = lowest + width * index as u128;
+            PowerBucket {
+                min_power,
+                max_power: min_power.saturating_add(width - 1).min(highest),
+                voter_count: 0,
+                total_power: 0,
+            }
+        })
+        .collect();
+    
+    for &power in powers {
+        let bucket = &mut distribution[((power - lowest) / width) as usize];
+        bucket.voter_count += 1;
+        bucket.total_power = bucket.total_power.saturating_add(power);
+    }
+    
+    distribution
+}
+
+/// How often a voter has been selected for query panels and taken part
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SelectionRecord {
+    /// Panels the voter was selected for
+    pub selected: u64,
+    
+    /// Selected panels whose query has since closed (resolved or expired)
+    pub closed: u64,
+    
+    /// Closed panels where the voter committed or voted
+    pub participated: u64,
+}
+
+impl SelectionRecord {
+    /// Share of closed panels the voter took part in (basis points), once any has closed
+    pub fn participation_bps(&self) -> Option<u32> {
+        if self.closed == 0 {
+            return None;
         }
-        
-        // Return ALL voters (not just top N)
-        let selected: Vec<ChainId> = voter_powers
-            .iter()
-            .map(|(chain_id, _power)| *chain_id)
-            .collect();
-        
-        Ok(selected)
+        Some((self.participated.min(self.closed) as u128 * 10000 / self.closed as u128) as u32)
     }
+}
+
+/// Selection fairness across voters
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectionStats {
+    /// Per-voter records, most selected first
+    pub voters: Vec<(ChainId, SelectionRecord)>,
     
-    /// Check if a voter is selected for a specific query
-    pub async fn is_voter_selected(
-        &self,
-        query_id: u64,
-        voter_chain: &ChainId,
-    ) -> Result<bool, String> {
-        let query = self.get_query(query_id).await
-            .ok_or_else(|| format!("Query {} not found", query_id))?;
+    /// Panel seats filled across all queries
+    pub total_selections: u64,
+    
+    /// Gini coefficient of selection counts (basis points)
+    pub concentration_bps: u32,
+}
+
+impl SelectionStats {
+    /// Summarize per-voter records
+    pub fn from_records(mut voters: Vec<(ChainId, SelectionRecord)>) -> Self {
+        voters.sort_by(|(a_voter, a), (b_voter, b)| b.selected.cmp(&a.selected).then(a_voter.cmp(b_voter)));
+        let counts: Vec<u64> = voters.iter().map(|(_, record)| record.selected).collect();
         
-        Ok(query.selected_voters.contains(voter_chain))
+        SelectionStats {
+            total_selections: counts.iter().sum(),
+            concentration_bps: selection_gini_bps(&counts),
+            voters,
+        }
     }
-    
-    /// Get voter power for display/analytics
-    pub async fn get_voter_power_info(
-        &self,
-        voter_chain: &ChainId,
-    ) -> Option<(Amount, u32, u128)> {
-        let voter_info = self.get_voter(voter_chain).await?;
-        let power = self.calculate_voter_power(&voter_info);
-        Some((voter_info.stake, voter_info.reputation, power))
+}
+
+/// Gini coefficient of `counts` in basis points
+/// 
+/// 0 means every voter was selected equally often; it approaches 10000 as
+/// selections concentrate on a single voter. Empty or all-zero counts are 0.
+pub fn selection_gini_bps(counts: &[u64]) -> u32 {
+    let n = counts.len() as u128;
+    let total: u128 = counts.iter().map(|&count| count as u128).sum();
+    if n == 0 || total == 0 {
+        return 0;
     }
+    
+    let mut sorted = counts.to_vec();
+    sorted.sort_unstable();
+    
+    // G = sum_i (2i - n - 1) * x_i / (n * total), with x sorted ascending and i from 1
+    let weighted: i128 = sorted.iter()
+        .enumerate()
+        .map(|(index, &count)| (2 * (index as i128 + 1) - n as i128 - 1) * count as i128)
+        .sum();
+    
+    (weighted.max(0) as u128 * 10000 / (n * total)) as u32
 }
 
 /// Reputation statistics for a voter
@@ -896,3 +4197,365 @@ pub struct ReputationStats {
     /// Accuracy percentage
     pub accuracy_percentage: f64,
 }
+
+// ==================== Reward Distribution Math ====================
+// Pure functions shared by the contract (resolution) and the service (estimates)
+
+/// Map reputation (0-100) to a voting weight (0.5-2.0)
+pub fn reputation_weight(reputation: u32) -> f64 {
+    // Formula: weight = 0.5 + (reputation / 100) * 1.5
+    0.5 + (reputation as f64 / 100.0) * 1.5
+}
+
+/// Weight a vote carries when resolving a query with `strategy`
+/// 
+/// Mirrors the resolution tallies: stake (in tokens) for WeightedByStake,
+/// reputation weight for WeightedByReputation (reputation 50 if the voter is
+/// unknown), confidence for ConfidenceWeightedMedian (100 if unset) and one
+/// vote each otherwise.
+pub fn vote_weight(strategy: &DecisionStrategy, vote: &Vote, voter_info: Option<&VoterInfo>) -> f64 {
+    match strategy {
+        DecisionStrategy::WeightedByStake => {
+            let stake: u128 = voter_info.map_or(Amount::ZERO, |info| info.stake).into();
+            stake as f64 / 1e18
+        }
+        DecisionStrategy::WeightedByReputation => {
+            reputation_weight(voter_info.map_or(50, |info| info.reputation))
+        }
+        DecisionStrategy::ConfidenceWeightedMedian => {
+            vote.confidence.map_or(100, |c| c.min(100)) as f64
+        }
+        DecisionStrategy::Majority | DecisionStrategy::Median => 1.0,
+    }
+}
+
+/// Apply the reputation multiplier and protocol fee to a base reward
+pub fn voter_reward(
+    base_reward: Amount,
+    voter_info: &VoterInfo,
+    params: &ProtocolParameters,
+) -> Amount {
+    let base_value: u128 = base_reward.into();
+    
+    // Calculate reputation multiplier (0.8 to 1.2)
+    // Higher reputation gets up to 20% bonus, lower gets up to 20% penalty
+    let reputation_multiplier = 0.8 + (voter_info.reputation as f64 / 100.0) * 0.4;
+    
+    // Apply reputation multiplier
+    let reward_with_reputation = (base_value as f64 * reputation_multiplier) as u128;
+    
+    // Deduct protocol fee (in basis points, e.g., 100 = 1%)
+    let fee_multiplier = 1.0 - (params.protocol_fee as f64 / 10000.0);
+    let final_reward = (reward_with_reputation as f64 * fee_multiplier) as u128;
+    
+    Amount::from_tokens(final_reward)
+}
+
+/// How a query's reward is split at resolution
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewardSplit {
+    /// Protocol fee, deducted before distribution
+    pub fee: Amount,
+    
+    /// Rounding remainder left after the proportional split
+    pub dust: Amount,
+    
+    /// Voter -> reward amount
+    pub rewards: BTreeMap<ChainId, Amount>,
+}
+
+impl RewardSplit {
+    /// Total paid out to voters
+    pub fn distributed(&self) -> Amount {
+        self.rewards.values().fold(Amount::ZERO, |sum, reward| sum.saturating_add(*reward))
+    }
+    
+    /// Check that `distributed + fee + dust == reward_amount`
+    pub fn check_invariant(&self, reward_amount: Amount) -> Result<(), String> {
+        let total = self.distributed().saturating_add(self.fee).saturating_add(self.dust);
+        if total != reward_amount {
+            return Err(format!(
+                "Reward accounting mismatch: distributed {} + fee {} + dust {} != reward {}",
+                self.distributed(), self.fee, self.dust, reward_amount
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Split a query's reward among correct voters according to its strategy
+/// 
+/// The protocol fee is deducted first and voters share the remainder:
+/// stake-weighted queries by stake × reputation multiplier, reputation-weighted
+/// queries by reputation weight, and all others by reputation multiplier alone.
+/// Shares are rounded down; the remainder is reported as dust. Voters are
+/// processed in chain ID order so every validator computes the same split
+/// whatever order `correct_voters` arrives in.
+pub fn split_query_reward(
+    strategy: &DecisionStrategy,
+    reward_amount: Amount,
+    correct_voters: &[(ChainId, VoterInfo)],
+    params: &ProtocolParameters,
+) -> RewardSplit {
+    // Weights scaled by 100 to stay in integers
+    let weights = correct_voters
+        .iter()
+        .map(|(voter, info)| {
+            let reputation = info.reputation.min(100) as u128;
+            let reputation_multiplier = 80 + reputation * 40 / 100;
+            let weight = match strategy {
+                DecisionStrategy::WeightedByStake => {
+                    let stake: u128 = info.effective_stake(params.max_effective_stake).into();
+                    stake.saturating_mul(reputation_multiplier)
+                }
+                DecisionStrategy::WeightedByReputation => 50 + reputation * 150 / 100,
+                _ => reputation_multiplier,
+            };
+            (*voter, weight)
+        })
+        .collect();
+    
+    split_by_weights(reward_amount, weights, params)
+}
+
+/// Split a reward among correct voters according to an explicit reward basis
+/// 
+/// Each entry carries the voter's vote confidence, used by the Confidence
+/// basis. Fee, dust and ordering are handled as in `split_query_reward`.
+pub fn split_reward_by_basis(
+    basis: RewardBasis,
+    reward_amount: Amount,
+    correct_voters: &[(ChainId, VoterInfo, Option<u8>)],
+    params: &ProtocolParameters,
+) -> RewardSplit {
+    let weights = correct_voters
+        .iter()
+        .map(|(voter, info, confidence)| {
+            let weight = match basis {
+                RewardBasis::Equal => 1,
+                RewardBasis::Stake => info.effective_stake(params.max_effective_stake).into(),
+                RewardBasis::Reputation => 50 + info.reputation.min(100) as u128 * 150 / 100,
+                RewardBasis::Power => info.capped_power(params.max_effective_stake),
+                RewardBasis::Confidence => confidence.map_or(100, |c| c.min(100)) as u128,
+            };
+            (*voter, weight)
+        })
+        .collect();
+    
+    split_by_weights(reward_amount, weights, params)
+}
+
+/// Split a query's reward among its correct voters
+/// 
+/// Uses the query's `reward_basis` if set, otherwise its strategy. Vote
+/// confidence is looked up in the query's votes (unset for voters who have
+/// not voted).
+pub fn split_reward_for_query(
+    query: &Query,
+    correct_voters: &[(ChainId, VoterInfo)],
+    params: &ProtocolParameters,
+) -> RewardSplit {
+    match query.reward_basis {
+        Some(basis) => {
+            let with_confidence: Vec<(ChainId, VoterInfo, Option<u8>)> = correct_voters
+                .iter()
+                .map(|(voter, info)| {
+                    let confidence = query.votes.get(voter).and_then(|vote| vote.confidence);
+                    (*voter, info.clone(), confidence)
+                })
+                .collect();
+            split_reward_by_basis(basis, query.reward_amount, &with_confidence, params)
+        }
+        None => split_query_reward(&query.strategy, query.reward_amount, correct_voters, params),
+    }
+}
+
+/// Deduct the protocol fee and split the rest in proportion to `weights`
+fn split_by_weights(
+    reward_amount: Amount,
+    mut weights: Vec<(ChainId, u128)>,
+    params: &ProtocolParameters,
+) -> RewardSplit {
+    let reward_value: u128 = reward_amount.into();
+    let fee_bps = params.protocol_fee.min(10_000) as u128;
+    let fee_value = reward_value / 10_000 * fee_bps + reward_value % 10_000 * fee_bps / 10_000;
+    let net_value = reward_value.saturating_sub(fee_value);
+    
+    weights.sort_by_key(|(voter, _)| *voter);
+    weights.dedup_by_key(|(voter, _)| *voter);
+    
+    // Fall back to equal weights if every weight is zero (e.g. no stake)
+    if weights.iter().all(|(_, weight)| *weight == 0) {
+        weights.iter_mut().for_each(|(_, weight)| *weight = 1);
+    }
+    
+    // Keep the total below 2^64 so the proportional split cannot overflow
+    let mut total_weight: u128 = weights.iter().map(|(_, weight)| *weight).sum();
+    while total_weight >= 1u128 << 64 {
+        weights.iter_mut().for_each(|(_, weight)| *weight >>= 1);
+        total_weight = weights.iter().map(|(_, weight)| *weight).sum();
+    }
+    
+    let mut rewards = BTreeMap::new();
+    let mut distributed = 0u128;
+    if total_weight > 0 {
+        for (voter, weight) in &weights {
+            // net * weight / total without overflowing u128
+            let share = net_value / total_weight * weight
+                + net_value % total_weight * weight / total_weight;
+            distributed += share;
+            rewards.insert(*voter, Amount::from_attos(share));
+        }
+    }
+    
+    RewardSplit {
+        fee: Amount::from_attos(fee_value),
+        dust: Amount::from_attos(net_value - distributed),
+        rewards,
+    }
+}
+
+fn stake_weighted_rewards(
+    total_reward: Amount,
+    correct_voters: &[(ChainId, VoterInfo)],
+    params: &ProtocolParameters,
+) -> BTreeMap<ChainId, Amount> {
+    let mut rewards = BTreeMap::new();
+    
+    if correct_voters.is_empty() {
+        return rewards;
+    }
+    
+    // Calculate total (capped) stake of correct voters
+    let total_stake: u128 = correct_voters
+        .iter()
+        .map(|(_, info)| {
+            let stake: u128 = info.effective_stake(params.max_effective_stake).into();
+            stake
+        })
+        .sum();
+    
+    if total_stake == 0 {
+        return rewards;
+    }
+    
+    let reward_value: u128 = total_reward.into();
+    
+    // Distribute rewards proportionally to stake
+    for (voter, info) in correct_voters {
+        let stake_value: u128 = info.effective_stake(params.max_effective_stake).into();
+        let proportion = stake_value as f64 / total_stake as f64;
+        let base_reward = (reward_value as f64 * proportion) as u128;
+        
+        // Apply reputation multiplier and protocol fee
+        let reward = voter_reward(
+            Amount::from_tokens(base_reward),
+            info,
+            params,
+        );
+        
+        rewards.insert(*voter, reward);
+    }
+    
+    rewards
+}
+
+fn reputation_weighted_rewards(
+    total_reward: Amount,
+    correct_voters: &[(ChainId, VoterInfo)],
+    params: &ProtocolParameters,
+) -> BTreeMap<ChainId, Amount> {
+    let mut rewards = BTreeMap::new();
+    
+    if correct_voters.is_empty() {
+        return rewards;
+    }
+    
+    // Calculate total reputation weight of correct voters
+    let total_weight: f64 = correct_voters
+        .iter()
+        .map(|(_, info)| reputation_weight(info.reputation))
+        .sum();
+    
+    if total_weight == 0.0 {
+        return rewards;
+    }
+    
+    let reward_value: u128 = total_reward.into();
+    
+    // Distribute rewards proportionally to reputation weight
+    for (voter, info) in correct_voters {
+        let weight = reputation_weight(info.reputation);
+        let proportion = weight / total_weight;
+        let base_reward = (reward_value as f64 * proportion) as u128;
+        
+        // Apply protocol fee (reputation already factored in)
+        let fee_multiplier = 1.0 - (params.protocol_fee as f64 / 10000.0);
+        let final_reward = (base_reward as f64 * fee_multiplier) as u128;
+        
+        rewards.insert(*voter, Amount::from_tokens(final_reward));
+    }
+    
+    rewards
+}
+
+fn equal_rewards(
+    total_reward: Amount,
+    correct_voters: &[(ChainId, VoterInfo)],
+    params: &ProtocolParameters,
+) -> BTreeMap<ChainId, Amount> {
+    let mut rewards = BTreeMap::new();
+    
+    if correct_voters.is_empty() {
+        return rewards;
+    }
+    
+    let reward_value: u128 = total_reward.into();
+    let per_voter_base = reward_value / correct_voters.len() as u128;
+    
+    // Distribute rewards equally with reputation multiplier
+    for (voter, info) in correct_voters {
+        let reward = voter_reward(
+            Amount::from_tokens(per_voter_base),
+            info,
+            params,
+        );
+        
+        rewards.insert(*voter, reward);
+    }
+    
+    rewards
+}
+
+/// Project a voter's reward for a query, assuming their vote turns out correct
+/// 
+/// The voter is assumed to back their own vote if they have voted, otherwise
+/// the currently leading outcome (see `Query::projected_result`). The reward is
+/// split with the same math as resolution, over the current correct voters.
+/// `voter_infos` should hold the query's voters and the voter themselves.
+/// Returns `None` if there is nothing to project yet.
+pub fn project_voter_reward(
+    query: &Query,
+    voter: &ChainId,
+    voter_infos: &BTreeMap<ChainId, VoterInfo>,
+    params: &ProtocolParameters,
+) -> Option<(ResolutionResult, Amount)> {
+    let result = query.projected_result(voter)?;
+    
+    // Current voters who would be correct under the projected result
+    let mut correct_voters: Vec<(ChainId, VoterInfo)> = query.tallied_votes()
+        .filter(|(_, vote)| result.matches_vote(&vote.value))
+        .filter_map(|(chain, _)| voter_infos.get(chain).map(|info| (*chain, info.clone())))
+        .collect();
+    
+    // Add the voter's hypothetical vote
+    if !query.votes.contains_key(voter) {
+        let info = voter_infos.get(voter)?;
+        correct_voters.push((*voter, info.clone()));
+    }
+    
+    let split = split_reward_for_query(query, &correct_voters, params);
+    let reward = split.rewards.get(voter).copied().unwrap_or(Amount::ZERO);
+    
+    Some((result, reward))
+}
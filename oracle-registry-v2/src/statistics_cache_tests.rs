@@ -0,0 +1,64 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests that incrementally cached voter statistics match a full scan
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{chain, voter_info};
+    use crate::state::{VoterAggregates, VoterInfo};
+    use linera_sdk::linera_base_types::{Amount, ChainId};
+    use std::collections::BTreeMap;
+
+    /// Store `voter`, applying the change to the cache as `save_voter` does
+    fn save(voters: &mut BTreeMap<ChainId, VoterInfo>, cache: &mut VoterAggregates, voter: VoterInfo) {
+        cache.replace(voters.get(&voter.chain_id), Some(&voter));
+        voters.insert(voter.chain_id, voter);
+    }
+
+    /// Remove a voter, applying the change to the cache as `remove_voter` does
+    fn remove(voters: &mut BTreeMap<ChainId, VoterInfo>, cache: &mut VoterAggregates, id: u8) {
+        cache.replace(voters.get(&chain(id)), None);
+        voters.remove(&chain(id));
+    }
+
+    #[test]
+    fn test_cached_and_live_agree_after_operations() {
+        let mut voters = BTreeMap::new();
+        let mut cache = VoterAggregates::default();
+
+        // Register three voters
+        for (id, stake, reputation) in [(2, 100, 50), (3, 500, 70), (4, 250, 60)] {
+            save(&mut voters, &mut cache, voter_info(id, stake, reputation));
+        }
+
+        // Lock stake for a vote, then update reputation after resolution
+        let mut voter = voters[&chain(3)].clone();
+        voter.lock_stake(Amount::from_tokens(50)).unwrap();
+        save(&mut voters, &mut cache, voter);
+        let mut voter = voters[&chain(2)].clone();
+        voter.reputation = 85;
+        save(&mut voters, &mut cache, voter);
+
+        // Deactivate one voter and deregister another
+        let mut voter = voters[&chain(4)].clone();
+        voter.is_active = false;
+        save(&mut voters, &mut cache, voter);
+        remove(&mut voters, &mut cache, 3);
+
+        let live = VoterAggregates::from_voters(voters.values());
+        assert_eq!(cache, live);
+        assert_eq!(live.voters, 2);
+        assert_eq!(live.active_voters, 1);
+        assert_eq!(live.total_locked_stake, Amount::ZERO);
+        assert_eq!(live.average_reputation(), 72.5);
+    }
+
+    #[test]
+    fn test_empty_registry() {
+        let aggregates = VoterAggregates::from_voters(&[]);
+
+        assert_eq!(aggregates, VoterAggregates::default());
+        assert_eq!(aggregates.average_reputation(), 0.0);
+    }
+}
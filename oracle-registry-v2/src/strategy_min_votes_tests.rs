@@ -0,0 +1,109 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for per-strategy default minimum votes
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{DecisionStrategy as StateStrategy, ProtocolParameters};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::state::DecisionStrategy;
+    use oracle_registry_v2::{Operation, OperationResponse};
+
+    /// Ten voters; Majority needs 5 votes and WeightedByStake 2 by default
+    fn harness() -> TestHarness {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            min_votes_default: 3,
+            strategy_min_votes: [(StateStrategy::Majority, 5), (StateStrategy::WeightedByStake, 2)]
+                .into_iter()
+                .collect(),
+            ..ProtocolParameters::default()
+        });
+        for id in 1..=10 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        harness
+    }
+
+    fn create(harness: &mut TestHarness, strategy: DecisionStrategy, min_votes: Option<usize>) -> OperationResponse {
+        let admin = harness.admin;
+        harness.execute_as(admin, Operation::CreateQuery {
+            description: "Will it rain?".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            strategy,
+            min_votes,
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            duration_secs: Some(120),
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            category: None,
+            tags: Vec::new(),
+            reward_basis: None,
+            outcome_aliases: std::collections::BTreeMap::new(),
+            tie_break: oracle_registry_v2::state::TieBreak::OutcomeOrder,
+        })
+    }
+
+    fn created_min_votes(harness: &mut TestHarness, strategy: DecisionStrategy, min_votes: Option<usize>) -> usize {
+        let response = create(harness, strategy, min_votes);
+        assert!(response.success, "{}", response.message);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+        harness.query(query_id).unwrap().min_votes
+    }
+
+    #[test]
+    fn test_majority_default_exceeds_stake_weighted_default() {
+        let mut harness = harness();
+        let majority = created_min_votes(&mut harness, DecisionStrategy::Majority, None);
+        let weighted = created_min_votes(&mut harness, DecisionStrategy::WeightedByStake, None);
+        assert_eq!(majority, 5);
+        assert_eq!(weighted, 2);
+        assert!(majority > weighted);
+
+        // Strategies without an override keep min_votes_default
+        assert_eq!(created_min_votes(&mut harness, DecisionStrategy::WeightedByReputation, None), 3);
+    }
+
+    #[test]
+    fn test_explicit_min_votes_wins_over_table() {
+        let mut harness = harness();
+        assert_eq!(created_min_votes(&mut harness, DecisionStrategy::Majority, Some(4)), 4);
+        assert_eq!(created_min_votes(&mut harness, DecisionStrategy::WeightedByStake, Some(4)), 4);
+    }
+
+    #[test]
+    fn test_strategy_default_checked_against_voter_count() {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            strategy_min_votes: [(StateStrategy::Majority, 5)].into_iter().collect(),
+            ..ProtocolParameters::default()
+        });
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+
+        let response = create(&mut harness, DecisionStrategy::Majority, None);
+        assert!(!response.success);
+        assert!(response.message.contains("exceeds total registered voters"), "{}", response.message);
+        assert!(create(&mut harness, DecisionStrategy::WeightedByStake, None).success);
+    }
+
+    #[test]
+    fn test_zero_strategy_min_votes_is_rejected() {
+        let mut harness = harness();
+        let admin = harness.admin;
+        let params = oracle_registry_v2::state::ProtocolParameters {
+            strategy_min_votes: [(DecisionStrategy::Median, 0)].into_iter().collect(),
+            ..Default::default()
+        };
+        let response = harness.execute_as(admin, Operation::UpdateParameters { params });
+        assert!(!response.success);
+        assert!(response.message.contains("Minimum votes for Median must be between 1 and 1000"));
+    }
+}
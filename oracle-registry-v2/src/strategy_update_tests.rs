@@ -0,0 +1,70 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for reassigning a query's decision strategy before voting
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{chain, query};
+    use crate::state::{DecisionStrategy, Vote, VoteCommit, VotingPhase};
+    use linera_sdk::linera_base_types::Timestamp;
+
+    #[test]
+    fn test_creator_can_update_during_commit_phase() {
+        let q = query(&["1", "2", "3"]);
+
+        assert!(q.check_strategy_update(&chain(1), Timestamp::from(500_000)).is_ok());
+        assert!(DecisionStrategy::Median.validate_outcomes(&q.outcomes).is_ok());
+        assert_eq!(DecisionStrategy::Median.as_str(), "Median");
+    }
+
+    #[test]
+    fn test_median_rejected_for_text_outcomes() {
+        let q = query(&["Yes", "No"]);
+
+        assert!(q.check_strategy_update(&chain(1), Timestamp::from(500_000)).is_ok());
+        let err = DecisionStrategy::Median.validate_outcomes(&q.outcomes).unwrap_err();
+        assert!(err.contains("'Yes' is not numeric"));
+        assert!(DecisionStrategy::WeightedByStake.validate_outcomes(&q.outcomes).is_ok());
+    }
+
+    #[test]
+    fn test_non_creator_rejected() {
+        let q = query(&["Yes", "No"]);
+
+        let err = q.check_strategy_update(&chain(2), Timestamp::from(500_000)).unwrap_err();
+        assert!(err.contains("Unauthorized"));
+    }
+
+    #[test]
+    fn test_rejected_after_commit_phase() {
+        let mut q = query(&["Yes", "No"]);
+        assert!(q.check_strategy_update(&chain(1), Timestamp::from(1_500_000)).is_err());
+
+        q.phase = VotingPhase::Reveal;
+        assert!(q.check_strategy_update(&chain(1), Timestamp::from(500_000)).is_err());
+    }
+
+    #[test]
+    fn test_rejected_once_votes_exist() {
+        let mut q = query(&["Yes", "No"]);
+        q.commits.insert(chain(2), VoteCommit {
+            voter: chain(2),
+            commit_hash: "abc".to_string(),
+            committed_at: Timestamp::from(100_000),
+            revealed: false,
+        });
+        assert!(q.check_strategy_update(&chain(1), Timestamp::from(500_000)).is_err());
+
+        let mut q = query(&["Yes", "No"]);
+        q.votes.insert(chain(3), Vote {
+            voter: chain(3),
+            value: "Yes".to_string(),
+            timestamp: Timestamp::from(100_000),
+            salt: None,
+            confidence: None,
+        });
+        let err = q.check_strategy_update(&chain(1), Timestamp::from(500_000)).unwrap_err();
+        assert!(err.contains("already has votes"));
+    }
+}
@@ -0,0 +1,263 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! In-memory harness for contract operation tests
+//!
+//! Runs the real operation handlers against `OracleRegistryV2` stored in the
+//! mock runtime's in-memory key-value store, with a settable chain ID and
+//! system time. Requires the `test` feature of linera-sdk (enabled through
+//! dev-dependencies), so it is only compiled for `cargo test`.
+
+use std::sync::{Arc, Mutex};
+
+use linera_sdk::{
+    linera_base_types::{Account, AccountOwner, Amount, ApplicationId, ChainId, TimeDelta, Timestamp},
+    util::BlockingWait,
+    views::View,
+    Contract, ContractRuntime,
+};
+use oracle_registry_v2::{Message, Operation, OperationResponse};
+
+use crate::state::{self, OracleRegistryV2, ProtocolParameters};
+use crate::OracleRegistryV2Contract;
+
+/// Chain ID whose last byte is `id`
+pub fn chain(id: u8) -> ChainId {
+    format!("{:064x}", id).parse().expect("valid chain ID")
+}
+
+/// Contract wrapper that executes operations as a chosen chain at a chosen time
+pub struct TestHarness {
+    pub contract: OracleRegistryV2Contract,
+    pub admin: ChainId,
+    token_releases: Arc<Mutex<Vec<(Account, Amount)>>>,
+    token_deposits: Arc<Mutex<Vec<(AccountOwner, Amount)>>>,
+}
+
+impl Default for TestHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestHarness {
+    /// Registry with default parameters, administered by `chain(0)`, at time zero
+    pub fn new() -> Self {
+        Self::with_parameters(ProtocolParameters::default())
+    }
+
+    /// Registry with the given parameters, administered by `chain(0)`, at time zero
+    ///
+    /// Unlike `instantiate`, no development voters are seeded, so every voter
+    /// in the registry was added by the test.
+    pub fn with_parameters(params: ProtocolParameters) -> Self {
+        let admin = chain(0);
+        let runtime = ContractRuntime::new()
+            .with_chain_id(admin)
+            .with_application_creator_chain_id(admin)
+            .with_system_time(Timestamp::from(0));
+        let mut state = OracleRegistryV2::load(runtime.root_view_storage_context())
+            .blocking_wait()
+            .expect("Failed to load state");
+        state.initialize(params, admin).blocking_wait();
+
+        TestHarness {
+            contract: OracleRegistryV2Contract { state, runtime },
+            admin,
+            token_releases: Arc::default(),
+            token_deposits: Arc::default(),
+        }
+    }
+
+    /// Registry state, for assertions
+    pub fn state(&self) -> &OracleRegistryV2 {
+        &self.contract.state
+    }
+
+    /// Current mock system time
+    pub fn now(&mut self) -> Timestamp {
+        self.contract.runtime.system_time()
+    }
+
+    /// Set the mock system time
+    pub fn set_time(&mut self, time: Timestamp) {
+        self.contract.runtime.set_system_time(time);
+    }
+
+    /// Move the mock system time forward by `secs` seconds
+    pub fn advance_secs(&mut self, secs: u64) {
+        let time = self.now().saturating_add(TimeDelta::from_secs(secs));
+        self.set_time(time);
+    }
+
+    /// Configure a token application that accepts every call (e.g. reward payouts)
+    ///
+    /// Releases the registry makes are recorded, see `token_releases`, and
+    /// so are transfers it pulls in, see `token_deposits`.
+    pub fn stub_token_app(&mut self) {
+        let token_app_id = ApplicationId::new(format!("{:064x}", 0xaa).parse().expect("valid hash"));
+        let registry_app_id = ApplicationId::new(format!("{:064x}", 0xab).parse().expect("valid hash"));
+        let mut params = self.params();
+        params.token_app_id = Some(token_app_id);
+        self.contract.state.parameters.set(params);
+        self.contract.runtime.set_application_id(registry_app_id.with_abi());
+        let releases = self.token_releases.clone();
+        let deposits = self.token_deposits.clone();
+        self.contract.runtime.set_call_application_handler(move |_authenticated, _application, operation| {
+            let operation: alethea_token::Operation = bcs::from_bytes(&operation).expect("token operation");
+            match operation {
+                alethea_token::Operation::ReleaseFromApplication { target_account, amount } => {
+                    releases.lock().expect("releases lock").push((target_account, amount));
+                }
+                alethea_token::Operation::TransferToApplication { owner, amount, .. } => {
+                    deposits.lock().expect("deposits lock").push((owner, amount));
+                }
+                _ => {}
+            }
+            bcs::to_bytes(&alethea_token::OperationResponse::success("ok")).expect("serializable response")
+        });
+    }
+
+    /// Tokens released through the stubbed token application, in order
+    pub fn token_releases(&self) -> Vec<(Account, Amount)> {
+        self.token_releases.lock().expect("releases lock").clone()
+    }
+
+    /// Tokens transferred into the registry through the stubbed token application, in order
+    pub fn token_deposits(&self) -> Vec<(AccountOwner, Amount)> {
+        self.token_deposits.lock().expect("deposits lock").clone()
+    }
+
+    /// Sign subsequent operations as `owner`, or leave them unsigned with `None`
+    pub fn sign_as(&mut self, owner: impl Into<Option<AccountOwner>>) {
+        self.contract.runtime.set_authenticated_signer(owner);
+    }
+
+    /// Credit `amount` of pending rewards to `voter`
+    pub fn credit_rewards(&mut self, voter: ChainId, amount: Amount) {
+        self.contract.state.pending_rewards.insert(&voter, amount).expect("Failed to credit rewards");
+        let now = self.now();
+        self.contract.state.reward_credited_at.insert(&voter, now).expect("Failed to record credit time");
+    }
+
+    /// Execute `operation` as if submitted on `sender`
+    pub fn execute_as(&mut self, sender: ChainId, operation: Operation) -> OperationResponse {
+        self.contract.runtime.set_chain_id(sender);
+        self.contract
+            .execute_operation(operation)
+            .blocking_wait()
+    }
+
+    /// Deliver `message` to the registry as if sent from `origin`
+    pub fn deliver_message(&mut self, origin: ChainId, message: Message) {
+        let admin = self.admin;
+        self.contract.runtime.set_chain_id(admin);
+        self.contract.runtime.set_message_origin_chain_id(origin);
+        self.contract
+            .execute_message(message)
+            .blocking_wait();
+    }
+
+    /// Messages sent so far, with their destination chains, oldest first
+    pub fn sent_messages(&self) -> Vec<(ChainId, Message)> {
+        self.contract
+            .runtime
+            .created_send_message_requests()
+            .iter()
+            .map(|request| (request.destination, request.message.clone()))
+            .collect()
+    }
+
+    /// Register `voter` with `stake_tokens` staked
+    pub fn register(&mut self, voter: ChainId, stake_tokens: u128) -> OperationResponse {
+        self.execute_as(voter, Operation::RegisterVoter {
+            stake: Amount::from_tokens(stake_tokens),
+            name: None,
+            metadata_url: None,
+            referral: None,
+            encrypted_metadata: None,
+        })
+    }
+
+    /// Create a majority query as the admin, lasting `duration_secs`
+    pub fn create_query(&mut self, outcomes: &[&str], min_votes: usize, duration_secs: u64) -> OperationResponse {
+        let admin = self.admin;
+        self.execute_as(admin, Operation::CreateQuery {
+            description: "Test query".to_string(),
+            outcomes: outcomes.iter().map(|outcome| outcome.to_string()).collect(),
+            strategy: oracle_registry_v2::state::DecisionStrategy::Majority,
+            min_votes: Some(min_votes),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            duration_secs: Some(duration_secs),
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            category: None,
+            tags: Vec::new(),
+            reward_basis: None,
+            outcome_aliases: std::collections::BTreeMap::new(),
+            tie_break: oracle_registry_v2::state::TieBreak::OutcomeOrder,
+        })
+    }
+
+    /// Submit a direct vote for `value` as `voter`
+    pub fn submit_vote(&mut self, voter: ChainId, query_id: u64, value: &str) -> OperationResponse {
+        self.execute_as(voter, Operation::SubmitVote {
+            query_id,
+            value: value.to_string(),
+            confidence: None,
+        })
+    }
+
+    /// Commit `commit_hash` on `query_id` as `voter`
+    pub fn commit_vote(&mut self, voter: ChainId, query_id: u64, commit_hash: &str) -> OperationResponse {
+        self.execute_as(voter, Operation::CommitVote {
+            query_id,
+            commit_hash: commit_hash.to_string(),
+        })
+    }
+
+    /// Reveal `value` with `salt` on `query_id` as `voter`
+    pub fn reveal_vote(&mut self, voter: ChainId, query_id: u64, value: &str, salt: &str) -> OperationResponse {
+        self.execute_as(voter, Operation::RevealVote {
+            query_id,
+            value: value.to_string(),
+            salt: salt.to_string(),
+            confidence: None,
+        })
+    }
+
+    /// Resolve `query_id` as the admin
+    pub fn resolve(&mut self, query_id: u64) -> OperationResponse {
+        let admin = self.admin;
+        self.execute_as(admin, Operation::ResolveQuery { query_id })
+    }
+
+    /// Move time past `query_id`'s deadline and resolution grace window
+    pub fn advance_past_deadline(&mut self, query_id: u64) {
+        let query = self.query(query_id).expect("query exists");
+        let grace_secs = self.params().resolution_grace_secs;
+        let time = query.deadline.saturating_add(TimeDelta::from_secs(grace_secs + 1));
+        self.set_time(time);
+    }
+
+    /// Stored voter record
+    pub fn voter(&self, voter: &ChainId) -> Option<state::VoterInfo> {
+        self.state().get_voter(voter).blocking_wait()
+    }
+
+    /// Stored query
+    pub fn query(&self, query_id: u64) -> Option<state::Query> {
+        self.state().get_query(query_id).blocking_wait()
+    }
+
+    /// Current protocol parameters
+    pub fn params(&self) -> ProtocolParameters {
+        self.state().get_parameters().blocking_wait()
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for settling Majority ties by the query's tie-break mode
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{validate_tie_break, DecisionStrategy, ResolutionResult, TieBreak};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::{Operation, OperationResponse};
+    use std::collections::BTreeMap;
+
+    fn create(
+        harness: &mut TestHarness,
+        outcomes: &[&str],
+        strategy: oracle_registry_v2::state::DecisionStrategy,
+        tie_break: oracle_registry_v2::state::TieBreak,
+    ) -> OperationResponse {
+        let admin = harness.admin;
+        harness.execute_as(admin, Operation::CreateQuery {
+            description: "Will it rain tomorrow?".to_string(),
+            outcomes: outcomes.iter().map(|outcome| outcome.to_string()).collect(),
+            strategy,
+            min_votes: Some(4),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            duration_secs: Some(120),
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: None,
+            allow_abstain: false,
+            multi_winner: false,
+            category: None,
+            tags: Vec::new(),
+            reward_basis: None,
+            outcome_aliases: BTreeMap::new(),
+            tie_break,
+        })
+    }
+
+    /// Resolve a 2-2 tie where Yes is backed by more stake (500 vs 200) and
+    /// No by more reputation (chain(3) raised to 90)
+    fn resolve_tie(outcomes: &[&str], tie_break: oracle_registry_v2::state::TieBreak) -> ResolutionResult {
+        let mut harness = TestHarness::new();
+        for (id, stake) in [(1, 400), (2, 100), (3, 100), (4, 100)] {
+            assert!(harness.register(chain(id), stake).success);
+        }
+        let mut reputable = harness.voter(&chain(3)).unwrap();
+        reputable.reputation = 90;
+        harness.contract.state.save_voter(&chain(3), reputable).blocking_wait().unwrap();
+
+        let response = create(&mut harness, outcomes, oracle_registry_v2::state::DecisionStrategy::Majority, tie_break);
+        assert!(response.success, "{}", response.message);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+        assert_eq!(harness.query(query_id).unwrap().tie_break.as_str(), tie_break.as_str());
+
+        for (id, value) in [(1, "Yes"), (2, "Yes"), (3, "No"), (4, "No")] {
+            assert!(harness.submit_vote(chain(id), query_id, value).success);
+        }
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        // A tie left unresolved closes the query without consensus instead
+        let closed = tie_break == oracle_registry_v2::state::TieBreak::NoConsensus;
+        assert!(response.success != closed, "{}", response.message);
+        harness.query(query_id).unwrap().result.expect("closed with a result")
+    }
+
+    fn outcome(value: &str) -> ResolutionResult {
+        ResolutionResult::Outcome(value.to_string())
+    }
+
+    #[test]
+    fn test_outcome_order_picks_first_listed_outcome() {
+        use oracle_registry_v2::state::TieBreak::OutcomeOrder;
+        assert_eq!(resolve_tie(&["No", "Yes"], OutcomeOrder), outcome("No"));
+        assert_eq!(resolve_tie(&["Yes", "No"], OutcomeOrder), outcome("Yes"));
+    }
+
+    #[test]
+    fn test_higher_stake_picks_outcome_with_more_stake() {
+        use oracle_registry_v2::state::TieBreak::HigherStake;
+        assert_eq!(resolve_tie(&["No", "Yes"], HigherStake), outcome("Yes"));
+        assert_eq!(resolve_tie(&["Yes", "No"], HigherStake), outcome("Yes"));
+    }
+
+    #[test]
+    fn test_higher_reputation_picks_outcome_with_more_reputation() {
+        use oracle_registry_v2::state::TieBreak::HigherReputation;
+        assert_eq!(resolve_tie(&["Yes", "No"], HigherReputation), outcome("No"));
+        assert_eq!(resolve_tie(&["No", "Yes"], HigherReputation), outcome("No"));
+    }
+
+    #[test]
+    fn test_no_consensus_mode_leaves_tie_unresolved() {
+        let result = resolve_tie(&["Yes", "No"], oracle_registry_v2::state::TieBreak::NoConsensus);
+        assert_eq!(result, ResolutionResult::NoConsensus);
+    }
+
+    #[test]
+    fn test_backing_tie_falls_back_to_outcome_order() {
+        let tally: BTreeMap<String, u64> = [("Yes", 2), ("No", 2), ("Maybe", 1)]
+            .into_iter().map(|(value, votes)| (value.to_string(), votes)).collect();
+        let backing: BTreeMap<String, u128> = [("Yes", 300), ("No", 300), ("Maybe", 900)]
+            .into_iter().map(|(value, stake)| (value.to_string(), stake)).collect();
+        let outcomes = ["Maybe".to_string(), "No".to_string(), "Yes".to_string()];
+
+        // Maybe's larger backing does not count: it is not among the tied leaders
+        let result = ResolutionResult::from_tally_with_tie_break(&tally, &outcomes, TieBreak::HigherStake, &backing);
+        assert_eq!(result, outcome("No"));
+
+        let empty = BTreeMap::new();
+        let result = ResolutionResult::from_tally_with_tie_break(&empty, &outcomes, TieBreak::OutcomeOrder, &backing);
+        assert_eq!(result, ResolutionResult::NoConsensus);
+    }
+
+    #[test]
+    fn test_tie_break_mode_requires_majority() {
+        let mut harness = TestHarness::new();
+        let response = create(
+            &mut harness,
+            &["1", "2"],
+            oracle_registry_v2::state::DecisionStrategy::Median,
+            oracle_registry_v2::state::TieBreak::HigherStake,
+        );
+        assert!(!response.success);
+        assert!(response.message.contains("only applies to the Majority strategy"), "{}", response.message);
+
+        assert!(validate_tie_break(TieBreak::OutcomeOrder, &DecisionStrategy::WeightedByStake).is_ok());
+        assert_eq!(TieBreak::parse("higherreputation"), Ok(TieBreak::HigherReputation));
+        assert!(TieBreak::parse("coin flip").is_err());
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for returning staked tokens on deregister/withdraw
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::{Account, AccountOwner, Amount, ChainId};
+    use oracle_registry_v2::{Message, Operation};
+
+    /// The single WithdrawTokens request sent so far, with its destination
+    fn withdraw_request(harness: &TestHarness) -> (ChainId, Amount, ChainId) {
+        let requests = harness
+            .sent_messages()
+            .into_iter()
+            .filter_map(|(destination, message)| match message {
+                Message::WithdrawTokens { amount, target_chain } => Some((destination, amount, target_chain)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(requests.len(), 1, "expected one WithdrawTokens request");
+        requests[0]
+    }
+
+    fn voter_account(voter: ChainId) -> Account {
+        Account { chain_id: voter, owner: AccountOwner::CHAIN }
+    }
+
+    #[test]
+    fn test_deregister_returns_tokens_once_confirmed() {
+        let mut harness = TestHarness::new();
+        harness.stub_token_app();
+        let voter = chain(2);
+        assert!(harness.register(voter, 100).success);
+
+        let response = harness.execute_as(voter, Operation::DeregisterVoter);
+        assert!(response.success, "{}", response.message);
+
+        // The stake is only requested back from the token chain
+        let stake = Amount::from_tokens(100);
+        assert_eq!(withdraw_request(&harness), (harness.admin, stake, voter));
+        assert!(harness.token_releases().is_empty());
+        let pending = harness.voter(&voter).expect("voter kept until tokens are returned");
+        assert!(!pending.is_active);
+        assert_eq!(pending.stake, stake);
+
+        harness.deliver_message(voter, Message::WithdrawTokens { amount: stake, target_chain: voter });
+        assert_eq!(harness.token_releases(), vec![(voter_account(voter), stake)]);
+        assert!(harness.voter(&voter).is_none());
+        assert_eq!(*harness.state().total_stake.get(), Amount::ZERO);
+    }
+
+    #[test]
+    fn test_withdraw_deducts_stake_once_confirmed() {
+        let mut harness = TestHarness::new();
+        harness.stub_token_app();
+        let voter = chain(2);
+        assert!(harness.register(voter, 150).success);
+
+        let amount = Amount::from_tokens(30);
+        let response = harness.execute_as(voter, Operation::WithdrawStake { amount });
+        assert!(response.success, "{}", response.message);
+        assert_eq!(withdraw_request(&harness), (harness.admin, amount, voter));
+        assert!(harness.token_releases().is_empty());
+        assert_eq!(harness.voter(&voter).unwrap().stake, Amount::from_tokens(150));
+
+        harness.deliver_message(voter, Message::WithdrawTokens { amount, target_chain: voter });
+        assert_eq!(harness.token_releases(), vec![(voter_account(voter), amount)]);
+        let remaining = harness.voter(&voter).unwrap();
+        assert!(remaining.is_active);
+        assert_eq!(remaining.stake, Amount::from_tokens(120));
+    }
+
+    #[test]
+    fn test_withdraw_request_from_another_chain_is_rejected() {
+        let mut harness = TestHarness::new();
+        harness.stub_token_app();
+        let voter = chain(2);
+        assert!(harness.register(voter, 100).success);
+
+        let amount = Amount::from_tokens(30);
+        harness.deliver_message(chain(3), Message::WithdrawTokens { amount, target_chain: voter });
+        assert!(harness.token_releases().is_empty());
+        assert_eq!(harness.voter(&voter).unwrap().stake, Amount::from_tokens(100));
+    }
+}
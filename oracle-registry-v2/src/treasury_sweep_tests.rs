@@ -0,0 +1,89 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for sweeping treasury tokens to the treasury account
+
+#[cfg(test)]
+mod tests {
+    use crate::state::ProtocolParameters;
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::{Account, AccountOwner, Amount};
+    use oracle_registry_v2::Operation;
+
+    fn treasury_account() -> Account {
+        Account { chain_id: chain(50), owner: AccountOwner::CHAIN }
+    }
+
+    /// Three 100-token voters backed by deposited tokens; chain(3) is slashed
+    /// when the returned query resolves
+    fn slashed(treasury_sweep_interval_secs: u64) -> (TestHarness, Amount) {
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            treasury_account: Some(treasury_account()),
+            treasury_sweep_interval_secs,
+            ..ProtocolParameters::default()
+        });
+        harness.stub_token_app();
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let total_stake = *harness.state().total_stake.get();
+        harness.contract.state.total_tokens_held.set(total_stake);
+
+        let created = harness.create_query(&["Yes", "No"], 3, 120);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+        for (id, value) in [(1, "Yes"), (2, "Yes"), (3, "No")] {
+            assert!(harness.submit_vote(chain(id), query_id, value).success);
+        }
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+
+        let slash = Amount::from_tokens(100).saturating_sub(harness.voter(&chain(3)).unwrap().stake);
+        assert!(slash > Amount::ZERO);
+        (harness, slash)
+    }
+
+    #[test]
+    fn test_sweep_releases_slashed_tokens_to_treasury_account() {
+        let (mut harness, slash) = slashed(0);
+        assert!(harness.token_releases().is_empty());
+        let treasury = *harness.state().protocol_treasury.get();
+        assert!(treasury >= slash);
+
+        let response = harness.execute_as(chain(7), Operation::SweepTreasury);
+        assert!(response.success, "{}", response.message);
+
+        assert_eq!(harness.token_releases(), vec![(treasury_account(), slash)]);
+        assert_eq!(*harness.state().protocol_treasury.get(), treasury.saturating_sub(slash));
+        assert_eq!(*harness.state().total_tokens_held.get(), *harness.state().total_stake.get());
+
+        // Nothing backed is left to sweep
+        let response = harness.execute_as(chain(7), Operation::SweepTreasury);
+        assert!(!response.success);
+        assert!(response.message.contains("Nothing to sweep"));
+        assert_eq!(harness.token_releases().len(), 1);
+    }
+
+    #[test]
+    fn test_resolution_sweeps_once_interval_has_passed() {
+        let (mut harness, slash) = slashed(60);
+
+        assert_eq!(harness.token_releases(), vec![(treasury_account(), slash)]);
+        let now = harness.now();
+        assert_eq!(*harness.state().last_treasury_sweep.get(), now);
+        assert_eq!(*harness.state().total_tokens_held.get(), *harness.state().total_stake.get());
+    }
+
+    #[test]
+    fn test_sweep_requires_treasury_account() {
+        let mut harness = TestHarness::new();
+        harness.stub_token_app();
+        harness.contract.state.protocol_treasury.set(Amount::from_tokens(5));
+        harness.contract.state.total_tokens_held.set(Amount::from_tokens(5));
+
+        let response = harness.execute_as(chain(7), Operation::SweepTreasury);
+        assert!(!response.success);
+        assert!(response.message.contains("Treasury account not configured"));
+        assert_eq!(*harness.state().protocol_treasury.get(), Amount::from_tokens(5));
+    }
+}
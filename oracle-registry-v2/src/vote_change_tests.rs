@@ -0,0 +1,71 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for replacing a direct vote before the deadline
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, chain, query, voter_info};
+    use crate::state::{ProtocolParameters, ResolutionResult};
+    use linera_sdk::linera_base_types::Timestamp;
+
+    #[test]
+    fn test_vote_change_disabled_by_default() {
+        assert!(!ProtocolParameters::default().allow_vote_change_until_deadline);
+    }
+
+    #[test]
+    fn test_second_vote_rejected_when_disabled() {
+        let mut q = query(&["Yes", "No"]);
+        assert_eq!(q.check_direct_vote(&chain(2), false), Ok(false));
+        add_vote(&mut q, 2, "Yes");
+
+        let err = q.check_direct_vote(&chain(2), false).unwrap_err();
+        assert!(err.contains("already voted"));
+    }
+
+    #[test]
+    fn test_changed_vote_is_the_one_counted() {
+        let mut q = query(&["Yes", "No"]);
+        add_vote(&mut q, 2, "Yes");
+        add_vote(&mut q, 3, "No");
+        add_vote(&mut q, 4, "Yes");
+
+        // Voter 4 changes their mind
+        assert_eq!(q.check_direct_vote(&chain(4), true), Ok(true));
+        add_vote(&mut q, 4, "No");
+
+        // Still one vote per voter, and the new value decides the result
+        assert_eq!(q.votes.len(), 3);
+        assert_eq!(q.votes[&chain(4)].value, "No");
+        let result = ResolutionResult::from_majority(q.votes.values());
+        assert_eq!(result, ResolutionResult::Outcome("No".to_string()));
+    }
+
+    #[test]
+    fn test_first_vote_is_not_a_change() {
+        let q = query(&["Yes", "No"]);
+        assert_eq!(q.check_direct_vote(&chain(2), true), Ok(false));
+    }
+
+    #[test]
+    fn test_revealed_vote_cannot_be_changed() {
+        let mut q = query(&["Yes", "No"]);
+        add_vote(&mut q, 2, "Yes");
+        q.votes.get_mut(&chain(2)).unwrap().salt = Some("salt".to_string());
+
+        let err = q.check_direct_vote(&chain(2), true).unwrap_err();
+        assert!(err.contains("cannot be changed"));
+    }
+
+    #[test]
+    fn test_vote_change_is_not_counted_again() {
+        let mut info = voter_info(2, 100, 50);
+        info.record_vote(Timestamp::from(1_000_000));
+        info.record_vote_change(Timestamp::from(2_000_000));
+
+        assert_eq!(info.total_votes, 1);
+        assert_eq!(info.last_vote_at, Some(Timestamp::from(2_000_000)));
+        assert_eq!(info.last_active_at, Timestamp::from(2_000_000));
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for reconciling drifted vote counts when a query settles
+
+#[cfg(test)]
+mod tests {
+    use crate::state::QueryStatus;
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::util::BlockingWait;
+    use oracle_registry_v2::Operation;
+
+    fn create(harness: &mut TestHarness) -> u64 {
+        let created = harness.create_query(&["Yes", "No"], 2, 120);
+        created.data.and_then(|data| data.query_id).expect("query id")
+    }
+
+    fn stored_count(harness: &TestHarness, query_id: u64) -> Option<usize> {
+        harness.state().vote_counts.get(&query_id).blocking_wait().expect("vote count")
+    }
+
+    #[test]
+    fn test_resolution_corrects_drifted_count() {
+        let mut harness = TestHarness::new();
+        for id in 1..=4 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let query_id = create(&mut harness);
+        for id in 1..=2 {
+            assert!(harness.submit_vote(chain(id), query_id, "Yes").success);
+        }
+
+        // Simulate a double increment for the same reveal
+        harness.contract.state.vote_counts.insert(&query_id, 5).expect("Failed to store count");
+
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::Resolved);
+        assert_eq!(stored_count(&harness, query_id), Some(2));
+    }
+
+    #[test]
+    fn test_expiry_corrects_drifted_count() {
+        let mut harness = TestHarness::new();
+        for id in 1..=4 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let query_id = create(&mut harness);
+        assert!(harness.submit_vote(chain(1), query_id, "Yes").success);
+
+        // Simulate a lost increment
+        harness.contract.state.vote_counts.insert(&query_id, 0).expect("Failed to store count");
+
+        harness.advance_past_deadline(query_id);
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::ExpireQueries { query_ids: vec![query_id] });
+        assert!(response.success, "{}", response.message);
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::Expired);
+        assert_eq!(stored_count(&harness, query_id), Some(1));
+    }
+}
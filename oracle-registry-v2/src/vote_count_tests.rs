@@ -0,0 +1,32 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for reconciling stored vote counts with query votes
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, query};
+
+    #[test]
+    fn test_drifted_count_is_reconciled() {
+        let mut q = query(&["Yes", "No"]);
+        add_vote(&mut q, 2, "Yes");
+        add_vote(&mut q, 3, "No");
+
+        // Stored count incremented twice for the same reveal
+        assert_eq!(q.vote_count_correction(Some(3)), Some(2));
+        // Stored count lagging behind
+        assert_eq!(q.vote_count_correction(Some(1)), Some(2));
+        // Missing entry
+        assert_eq!(q.vote_count_correction(None), Some(2));
+    }
+
+    #[test]
+    fn test_matching_count_is_left_alone() {
+        let mut q = query(&["Yes", "No"]);
+        assert_eq!(q.vote_count_correction(Some(0)), None);
+
+        add_vote(&mut q, 2, "Yes");
+        assert_eq!(q.vote_count_correction(Some(1)), None);
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the minimum gap between a voter's votes
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::voter_info;
+    use crate::state::ProtocolParameters;
+    use linera_sdk::linera_base_types::Timestamp;
+
+    const INTERVAL_SECS: u64 = 60;
+
+    #[test]
+    fn test_disabled_by_default() {
+        assert_eq!(ProtocolParameters::default().min_vote_interval_secs, 0);
+
+        let mut voter = voter_info(2, 100, 50);
+        voter.record_vote(Timestamp::from(1_000_000));
+        assert!(voter.check_vote_interval(Timestamp::from(1_000_000), 0).is_ok());
+    }
+
+    #[test]
+    fn test_second_rapid_vote_rejected() {
+        let mut voter = voter_info(2, 100, 50);
+        assert!(voter.check_vote_interval(Timestamp::from(1_000_000), INTERVAL_SECS).is_ok());
+        voter.record_vote(Timestamp::from(1_000_000));
+
+        let err = voter.check_vote_interval(Timestamp::from(11_000_000), INTERVAL_SECS).unwrap_err();
+        assert!(err.contains("retry after 50 second(s)"));
+
+        // Partial seconds round up
+        let err = voter.check_vote_interval(Timestamp::from(60_500_000), INTERVAL_SECS).unwrap_err();
+        assert!(err.contains("retry after 1 second(s)"));
+    }
+
+    #[test]
+    fn test_vote_allowed_after_interval() {
+        let mut voter = voter_info(2, 100, 50);
+        voter.record_vote(Timestamp::from(1_000_000));
+
+        assert!(voter.check_vote_interval(Timestamp::from(61_000_000), INTERVAL_SECS).is_ok());
+        assert!(voter.check_vote_interval(Timestamp::from(120_000_000), INTERVAL_SECS).is_ok());
+    }
+}
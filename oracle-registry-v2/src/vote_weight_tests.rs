@@ -0,0 +1,45 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the per-vote weight reported alongside query votes
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{vote, voter_info};
+    use crate::state::{reputation_weight, vote_weight, DecisionStrategy};
+
+    #[test]
+    fn test_weight_follows_strategy() {
+        let v = vote(2, "Yes");
+        let info = voter_info(2, 250, 80);
+
+        assert_eq!(vote_weight(&DecisionStrategy::Majority, &v, Some(&info)), 1.0);
+        assert_eq!(vote_weight(&DecisionStrategy::Median, &v, Some(&info)), 1.0);
+        assert_eq!(vote_weight(&DecisionStrategy::WeightedByStake, &v, Some(&info)), 250.0);
+        assert_eq!(
+            vote_weight(&DecisionStrategy::WeightedByReputation, &v, Some(&info)),
+            reputation_weight(80)
+        );
+    }
+
+    #[test]
+    fn test_confidence_weight() {
+        let mut v = vote(2, "42");
+        let info = voter_info(2, 250, 80);
+        assert_eq!(vote_weight(&DecisionStrategy::ConfidenceWeightedMedian, &v, Some(&info)), 100.0);
+
+        v.confidence = Some(35);
+        assert_eq!(vote_weight(&DecisionStrategy::ConfidenceWeightedMedian, &v, Some(&info)), 35.0);
+    }
+
+    #[test]
+    fn test_unknown_voter_uses_resolution_defaults() {
+        let v = vote(9, "Yes");
+
+        assert_eq!(vote_weight(&DecisionStrategy::WeightedByStake, &v, None), 0.0);
+        assert_eq!(
+            vote_weight(&DecisionStrategy::WeightedByReputation, &v, None),
+            reputation_weight(50)
+        );
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for voter last-seen tracking and dormancy detection
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::voter_info;
+    use crate::state::DEFAULT_DORMANCY_DAYS;
+    use linera_sdk::linera_base_types::Timestamp;
+
+    const DAY_MICROS: u64 = 86_400_000_000;
+
+    #[test]
+    fn test_voting_updates_last_active_at() {
+        let mut voter = voter_info(2, 100, 50);
+        assert_eq!(voter.last_active_at, Timestamp::from(0));
+
+        voter.record_vote(Timestamp::from(5 * DAY_MICROS));
+
+        assert_eq!(voter.total_votes, 1);
+        assert_eq!(voter.last_active_at, Timestamp::from(5 * DAY_MICROS));
+    }
+
+    #[test]
+    fn test_dormancy_detection() {
+        let voter = voter_info(2, 100, 50);
+
+        assert!(!voter.is_dormant(Timestamp::from(29 * DAY_MICROS), DEFAULT_DORMANCY_DAYS));
+        assert!(voter.is_dormant(Timestamp::from(30 * DAY_MICROS), DEFAULT_DORMANCY_DAYS));
+        assert!(voter.is_dormant(Timestamp::from(2 * DAY_MICROS), 1));
+    }
+
+    #[test]
+    fn test_vote_resets_dormancy() {
+        let mut voter = voter_info(2, 100, 50);
+        let now = Timestamp::from(40 * DAY_MICROS);
+        assert!(voter.is_dormant(now, DEFAULT_DORMANCY_DAYS));
+
+        voter.record_vote(Timestamp::from(35 * DAY_MICROS));
+        assert!(!voter.is_dormant(now, DEFAULT_DORMANCY_DAYS));
+    }
+}
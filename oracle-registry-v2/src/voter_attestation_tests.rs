@@ -0,0 +1,55 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the canonical voter attestation encoding
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::voter_info;
+    use crate::state::VoterAttestation;
+    use linera_sdk::linera_base_types::Timestamp;
+
+    fn attested_voter() -> VoterAttestation {
+        let mut info = voter_info(1, 100, 75);
+        info.total_votes = 10;
+        info.correct_votes = 7;
+        info.registered_at = Timestamp::from(1_000_000);
+        VoterAttestation::new(&info, 42)
+    }
+
+    #[test]
+    fn test_attestation_copies_voter_record() {
+        let attestation = attested_voter();
+
+        assert_eq!(attestation.address, format!("{:064x}", 1));
+        assert_eq!(attestation.reputation, 75);
+        assert_eq!(attestation.total_votes, 10);
+        assert_eq!(attestation.correct_votes, 7);
+        assert_eq!(attestation.registered_at, 1_000_000);
+        assert_eq!(attestation.block, 42);
+    }
+
+    #[test]
+    fn test_attestation_encoding_is_stable() {
+        let expected = [
+            // address: length 64, then 63 ASCII '0' and a '1'
+            format!("40{}31", "30".repeat(63)),
+            "4b000000".to_string(),         // reputation 75
+            "0a00000000000000".to_string(), // total_votes 10
+            "0700000000000000".to_string(), // correct_votes 7
+            "40420f0000000000".to_string(), // registered_at 1_000_000
+            "2a00000000000000".to_string(), // block 42
+        ]
+        .concat();
+
+        assert_eq!(attested_voter().encode(), expected);
+    }
+
+    #[test]
+    fn test_attestation_encoding_changes_with_record() {
+        let mut other = attested_voter();
+        other.correct_votes = 8;
+
+        assert_ne!(other.encode(), attested_voter().encode());
+    }
+}
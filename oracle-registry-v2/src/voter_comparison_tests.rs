@@ -0,0 +1,50 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for comparing voters side by side
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{chain, voter_info};
+    use crate::state::{parse_compared_voters, VoterComparison, MAX_COMPARED_VOTERS};
+    use linera_sdk::linera_base_types::Amount;
+
+    #[test]
+    fn test_compared_metrics_line_up() {
+        let mut veteran = voter_info(1, 500, 80);
+        veteran.total_votes = 20;
+        veteran.correct_votes = 15;
+        let newcomer = voter_info(2, 100, 50);
+
+        let rows: Vec<VoterComparison> = [&newcomer, &veteran]
+            .into_iter()
+            .map(VoterComparison::from_voter)
+            .collect();
+
+        assert_eq!(rows[0].chain_id, chain(2));
+        assert_eq!(rows[0].reputation, 50);
+        assert_eq!(rows[0].accuracy_percentage, 0.0);
+        assert_eq!(rows[0].stake, Amount::from_tokens(100));
+        assert_eq!(rows[0].power, newcomer.power());
+        assert_eq!(rows[0].total_votes, 0);
+
+        assert_eq!(rows[1].chain_id, chain(1));
+        assert_eq!(rows[1].reputation, 80);
+        assert_eq!(rows[1].accuracy_percentage, 75.0);
+        assert_eq!(rows[1].stake, Amount::from_tokens(500));
+        assert_eq!(rows[1].power, 500 * 10u128.pow(18) * 80);
+        assert_eq!(rows[1].total_votes, 20);
+    }
+
+    #[test]
+    fn test_comparison_list_is_limited() {
+        let addresses: Vec<String> = (1..=MAX_COMPARED_VOTERS as u8).map(|id| chain(id).to_string()).collect();
+        assert_eq!(parse_compared_voters(&addresses).unwrap().len(), MAX_COMPARED_VOTERS);
+
+        let mut too_many = addresses.clone();
+        too_many.push(chain(99).to_string());
+        let err = parse_compared_voters(&too_many).unwrap_err();
+        assert!(err.contains("Too many voters to compare"));
+        assert!(parse_compared_voters(&[]).is_err());
+    }
+}
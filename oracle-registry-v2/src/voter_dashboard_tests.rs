@@ -0,0 +1,116 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the voter dashboard activity sections
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, chain, query};
+    use crate::state::{
+        voter_activity, Query, QueryStatus, ResolutionResult, VoteCommit, VoterEarnings,
+        VoterHistoryItem, DASHBOARD_HISTORY_LIMIT,
+    };
+    use linera_sdk::linera_base_types::{Amount, Timestamp};
+
+    fn resolved(id: u64, voter_value: &str, result: &str, resolved_secs: u64) -> Query {
+        let mut q = query(&["Yes", "No"]);
+        q.id = id;
+        q.status = QueryStatus::Resolved;
+        q.result = Some(ResolutionResult::Outcome(result.to_string()));
+        q.result_display = Some(result.to_string());
+        q.resolved_at = Some(Timestamp::from(resolved_secs * 1_000_000));
+        add_vote(&mut q, 2, voter_value);
+        q
+    }
+
+    /// An active voter (`chain(2)`) with an open selection, an unrevealed
+    /// commit and two resolved votes
+    fn queries() -> Vec<Query> {
+        let mut selected = query(&["Yes", "No"]);
+        selected.id = 1;
+        selected.selected_voters = vec![chain(2), chain(3)];
+
+        let mut committed = query(&["Yes", "No"]);
+        committed.id = 2;
+        committed.selected_voters = vec![chain(2)];
+        committed.commits.insert(chain(2), VoteCommit {
+            voter: chain(2),
+            commit_hash: "abc".to_string(),
+            committed_at: Timestamp::from(500_000),
+            revealed: false,
+        });
+
+        vec![selected, committed, resolved(3, "Yes", "Yes", 10), resolved(4, "Yes", "No", 20)]
+    }
+
+    #[test]
+    fn test_every_section_populated_for_active_voter() {
+        let activity = voter_activity(&chain(2), &queries(), DASHBOARD_HISTORY_LIMIT);
+
+        assert_eq!(activity.selected_queries, vec![1, 2]);
+        assert_eq!(activity.pending_reveals, vec![2]);
+        assert_eq!(
+            activity.recent_history,
+            vec![
+                VoterHistoryItem {
+                    query_id: 4,
+                    value: "Yes".to_string(),
+                    correct: Some(false),
+                    resolved_at: Some(Timestamp::from(20_000_000)),
+                },
+                VoterHistoryItem {
+                    query_id: 3,
+                    value: "Yes".to_string(),
+                    correct: Some(true),
+                    resolved_at: Some(Timestamp::from(10_000_000)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_other_voter_sees_only_own_activity() {
+        let activity = voter_activity(&chain(3), &queries(), DASHBOARD_HISTORY_LIMIT);
+
+        assert_eq!(activity.selected_queries, vec![1]);
+        assert!(activity.pending_reveals.is_empty());
+        assert!(activity.recent_history.is_empty());
+    }
+
+    #[test]
+    fn test_revealed_commit_is_not_pending() {
+        let mut queries = queries();
+        queries[1].commits.get_mut(&chain(2)).unwrap().revealed = true;
+
+        let activity = voter_activity(&chain(2), &queries, DASHBOARD_HISTORY_LIMIT);
+
+        assert!(activity.pending_reveals.is_empty());
+    }
+
+    #[test]
+    fn test_abstention_has_no_correctness() {
+        let mut q = resolved(5, "Abstain", "Yes", 30);
+        q.allow_abstain = true;
+
+        let activity = voter_activity(&chain(2), &[q], DASHBOARD_HISTORY_LIMIT);
+
+        assert_eq!(activity.recent_history[0].correct, None);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let many: Vec<Query> = (0..30).map(|i| resolved(i, "Yes", "Yes", i + 1)).collect();
+
+        let activity = voter_activity(&chain(2), &many, DASHBOARD_HISTORY_LIMIT);
+
+        assert_eq!(activity.recent_history.len(), DASHBOARD_HISTORY_LIMIT);
+        assert_eq!(activity.recent_history[0].query_id, 29);
+    }
+
+    #[test]
+    fn test_earnings_start_at_zero() {
+        let earnings = VoterEarnings::default();
+        assert_eq!(earnings.total_rewarded, Amount::ZERO);
+        assert_eq!(earnings.total_slashed, Amount::ZERO);
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for distinct voter and address-prefix group power requirements
+
+#[cfg(test)]
+mod tests {
+    use crate::state::{check_group_power, validate_voter_diversity, ProtocolParameters, QueryStatus};
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::Operation;
+
+    /// Three registered voters and a Yes/No query allowing abstentions
+    fn diverse_query(min_distinct_voters: Option<usize>, max_group_power_bps: Option<u32>) -> (TestHarness, u64) {
+        // Each test chain ID is its own group only at the full 64-character prefix
+        let mut harness = TestHarness::with_parameters(ProtocolParameters {
+            voter_group_prefix_len: 64,
+            ..ProtocolParameters::default()
+        });
+        for id in 1..=3 {
+            assert!(harness.register(chain(id), 100).success);
+        }
+        let admin = harness.admin;
+        let response = harness.execute_as(admin, Operation::CreateQuery {
+            description: "Will it rain tomorrow?".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            strategy: oracle_registry_v2::state::DecisionStrategy::Majority,
+            min_votes: Some(3),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            duration_secs: Some(120),
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters,
+            max_group_power_bps,
+            min_voter_stake: None,
+            allow_abstain: true,
+            multi_winner: false,
+            category: None,
+            tags: Vec::new(),
+            reward_basis: None,
+            outcome_aliases: std::collections::BTreeMap::new(),
+            tie_break: oracle_registry_v2::state::TieBreak::OutcomeOrder,
+        });
+        assert!(response.success, "{}", response.message);
+        let query_id = response.data.and_then(|data| data.query_id).expect("query id");
+        (harness, query_id)
+    }
+
+    #[test]
+    fn test_too_few_distinct_voters_prevents_resolution() {
+        let (mut harness, query_id) = diverse_query(Some(3), None);
+        // Three votes meet min_votes, but only two chains are tallied
+        for (id, value) in [(1, "Yes"), (2, "Yes"), (3, "Abstain")] {
+            assert!(harness.submit_vote(chain(id), query_id, value).success);
+        }
+
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(!response.success);
+        assert!(response.message.contains("Only 2 distinct voter chain(s) voted, 3 required"), "{}", response.message);
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::NoConsensus);
+    }
+
+    #[test]
+    fn test_enough_distinct_voters_resolves() {
+        let (mut harness, query_id) = diverse_query(Some(3), None);
+        for (id, value) in [(1, "Yes"), (2, "Yes"), (3, "No")] {
+            assert!(harness.submit_vote(chain(id), query_id, value).success);
+        }
+
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(response.success, "{}", response.message);
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::Resolved);
+    }
+
+    #[test]
+    fn test_dominant_group_prevents_resolution() {
+        // Three equal voters each hold a third of the power
+        let (mut harness, query_id) = diverse_query(None, Some(3000));
+        for (id, value) in [(1, "Yes"), (2, "Yes"), (3, "No")] {
+            assert!(harness.submit_vote(chain(id), query_id, value).success);
+        }
+
+        harness.advance_past_deadline(query_id);
+        let response = harness.resolve(query_id);
+        assert!(!response.success);
+        assert!(response.message.contains("basis points of the voting power"), "{}", response.message);
+        assert_eq!(harness.query(query_id).unwrap().status, QueryStatus::NoConsensus);
+    }
+
+    #[test]
+    fn test_group_power_shares() {
+        let powers = [(chain(1), 100), (chain(2), 100), (chain(3), 200)];
+        // At a full-length prefix every chain is its own group
+        assert!(check_group_power(&powers, 64, 5000).is_ok());
+        assert!(check_group_power(&powers, 64, 4999).is_err());
+        // Test chain IDs share their leading zeros, so short prefixes merge them
+        assert!(check_group_power(&powers, 8, 9999).is_err());
+        assert!(check_group_power(&[], 8, 1).is_ok());
+    }
+
+    #[test]
+    fn test_validate_voter_diversity() {
+        assert!(validate_voter_diversity(None, None).is_ok());
+        assert!(validate_voter_diversity(Some(2), Some(5000)).is_ok());
+        assert!(validate_voter_diversity(Some(0), None).is_err());
+        assert!(validate_voter_diversity(None, Some(0)).is_err());
+        assert!(validate_voter_diversity(None, Some(10001)).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for a voter's per-query status
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{chain, query, vote};
+    use crate::state::{VoteCommit, VoterQueryStatus};
+    use linera_sdk::linera_base_types::Timestamp;
+
+    #[test]
+    fn test_flags_follow_commit_reveal_lifecycle() {
+        let mut query = query(&["Yes", "No"]);
+        query.selected_voters = vec![chain(1), chain(2)];
+        let voter = chain(1);
+
+        assert_eq!(query.voter_status(&voter), VoterQueryStatus {
+            selected: true,
+            ..VoterQueryStatus::default()
+        });
+
+        query.commits.insert(voter, VoteCommit {
+            voter,
+            commit_hash: "abc".to_string(),
+            committed_at: Timestamp::from(500_000),
+            revealed: false,
+        });
+        let status = query.voter_status(&voter);
+        assert!(status.selected && status.committed);
+        assert!(!status.revealed && !status.voted);
+        assert_eq!(status.vote_value, None);
+
+        query.commits.get_mut(&voter).unwrap().revealed = true;
+        let mut revealed = vote(1, "No");
+        revealed.salt = Some("salt".to_string());
+        revealed.confidence = Some(80);
+        query.votes.insert(voter, revealed);
+
+        assert_eq!(query.voter_status(&voter), VoterQueryStatus {
+            selected: true,
+            committed: true,
+            revealed: true,
+            voted: true,
+            vote_value: Some("No".to_string()),
+            confidence: Some(80),
+        });
+    }
+
+    #[test]
+    fn test_direct_vote_without_commit() {
+        let mut query = query(&["Yes", "No"]);
+        query.votes.insert(chain(3), vote(3, "Yes"));
+
+        let status = query.voter_status(&chain(3));
+        assert!(!status.selected && !status.committed && !status.revealed);
+        assert!(status.voted);
+        assert_eq!(status.vote_value.as_deref(), Some("Yes"));
+        assert_eq!(status.confidence, None);
+    }
+
+    #[test]
+    fn test_unselected_voter_has_no_flags() {
+        let mut query = query(&["Yes", "No"]);
+        query.selected_voters = vec![chain(1)];
+
+        assert_eq!(query.voter_status(&chain(9)), VoterQueryStatus::default());
+    }
+}
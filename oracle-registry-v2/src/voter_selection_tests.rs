@@ -0,0 +1,30 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for voter selection when creating queries
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::chain;
+    use crate::state::select_eligible_voters;
+
+    #[test]
+    fn test_empty_registry_fails_cleanly() {
+        let err = select_eligible_voters(&[], 0).unwrap_err();
+        assert_eq!(err, "No eligible voters: 0 registered, 0 active");
+    }
+
+    #[test]
+    fn test_inactive_voters_reported_with_count() {
+        let err = select_eligible_voters(&[], 3).unwrap_err();
+        assert!(err.contains("No eligible voters"));
+        assert!(err.contains("3 registered"));
+    }
+
+    #[test]
+    fn test_all_ranked_voters_selected_in_order() {
+        let ranked = vec![(chain(3), 300), (chain(1), 200), (chain(2), 100)];
+        let selected = select_eligible_voters(&ranked, 3).unwrap();
+        assert_eq!(selected, vec![chain(3), chain(1), chain(2)]);
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the per-query minimum voter stake
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{chain, TestHarness};
+    use linera_sdk::linera_base_types::Amount;
+    use oracle_registry_v2::{Operation, OperationResponse};
+
+    const HASH: &str = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    fn create_with_floor(harness: &mut TestHarness, min_voter_stake_tokens: u128) -> OperationResponse {
+        let admin = harness.admin;
+        harness.execute_as(admin, Operation::CreateQuery {
+            description: "High-value query".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            strategy: oracle_registry_v2::state::DecisionStrategy::Majority,
+            min_votes: Some(1),
+            reward_amount: Amount::from_tokens(10),
+            deadline: None,
+            duration_secs: Some(1200),
+            min_distinct_voted_outcomes: None,
+            min_votes_for_winner: None,
+            min_distinct_voters: None,
+            max_group_power_bps: None,
+            min_voter_stake: Some(Amount::from_tokens(min_voter_stake_tokens)),
+            allow_abstain: false,
+            multi_winner: false,
+            category: None,
+            tags: Vec::new(),
+            reward_basis: None,
+            outcome_aliases: std::collections::BTreeMap::new(),
+            tie_break: oracle_registry_v2::state::TieBreak::OutcomeOrder,
+        })
+    }
+
+    /// Harness with a 100-token voter and a 1000-token voter, both selected
+    /// for a query with a 500-token floor
+    fn setup() -> (TestHarness, u64) {
+        let mut harness = TestHarness::new();
+        assert!(harness.register(chain(1), 100).success);
+        assert!(harness.register(chain(2), 1000).success);
+
+        let created = create_with_floor(&mut harness, 500);
+        assert!(created.success, "{}", created.message);
+        let query_id = created.data.and_then(|data| data.query_id).expect("query id");
+
+        let query = harness.query(query_id).unwrap();
+        assert_eq!(query.min_voter_stake, Some(Amount::from_tokens(500)));
+        assert!(query.selected_voters.contains(&chain(1)));
+        (harness, query_id)
+    }
+
+    #[test]
+    fn test_selected_voter_below_floor_cannot_submit_vote() {
+        let (mut harness, query_id) = setup();
+
+        let response = harness.submit_vote(chain(1), query_id, "Yes");
+        assert!(!response.success);
+        assert!(response.message.contains("Insufficient stake to vote"));
+        assert!(harness.query(query_id).unwrap().votes.is_empty());
+        assert_eq!(harness.voter(&chain(1)).unwrap().locked_stake, Amount::ZERO);
+
+        assert!(harness.submit_vote(chain(2), query_id, "Yes").success);
+    }
+
+    #[test]
+    fn test_selected_voter_below_floor_cannot_commit_vote() {
+        let (mut harness, query_id) = setup();
+
+        let response = harness.commit_vote(chain(1), query_id, HASH);
+        assert!(!response.success);
+        assert!(response.message.contains("Insufficient stake to vote"));
+        assert!(harness.query(query_id).unwrap().commits.is_empty());
+
+        assert!(harness.commit_vote(chain(2), query_id, HASH).success);
+    }
+
+    #[test]
+    fn test_floor_below_registry_min_stake_is_rejected() {
+        let mut harness = TestHarness::new();
+        assert!(harness.register(chain(1), 1000).success);
+        let min_stake = harness.params().min_stake;
+        assert!(min_stake > Amount::from_tokens(1));
+
+        let response = create_with_floor(&mut harness, 1);
+        assert!(!response.success);
+        assert!(response.message.contains("below the registration minimum stake"));
+    }
+}
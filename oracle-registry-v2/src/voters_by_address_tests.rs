@@ -0,0 +1,47 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for looking up several voters by address at once
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{chain, voter_info};
+    use crate::state::{parse_voter_addresses, VoterInfo, MAX_VOTERS_BY_ADDRESS};
+    use linera_sdk::linera_base_types::ChainId;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_known_and_unknown_addresses_keep_order() {
+        let registered: BTreeMap<ChainId, VoterInfo> = [2, 4]
+            .into_iter()
+            .map(|id| (chain(id), voter_info(id, 100, 50)))
+            .collect();
+        let addresses: Vec<String> = [4, 3, 2].into_iter().map(|id| chain(id).to_string()).collect();
+
+        let chain_ids = parse_voter_addresses(&addresses).unwrap();
+        assert_eq!(chain_ids, vec![chain(4), chain(3), chain(2)]);
+
+        let found: Vec<Option<ChainId>> = chain_ids.iter()
+            .map(|id| registered.get(id).map(|info| info.chain_id))
+            .collect();
+        assert_eq!(found, vec![Some(chain(4)), None, Some(chain(2))]);
+    }
+
+    #[test]
+    fn test_rejects_empty_and_oversized_lists() {
+        assert!(parse_voter_addresses(&[]).is_err());
+
+        let addresses = vec![chain(1).to_string(); MAX_VOTERS_BY_ADDRESS + 1];
+        let err = parse_voter_addresses(&addresses).unwrap_err();
+        assert!(err.contains("Too many addresses"));
+        assert!(parse_voter_addresses(&addresses[..MAX_VOTERS_BY_ADDRESS]).is_ok());
+    }
+
+    #[test]
+    fn test_rejects_malformed_address() {
+        let addresses = vec![chain(1).to_string(), "not-a-chain".to_string()];
+
+        let err = parse_voter_addresses(&addresses).unwrap_err();
+        assert!(err.contains("'not-a-chain'"));
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright (c) Alethea Network
+// SPDX-License-Identifier: MIT
+
+//! Tests for the per-query minimum vote count of the winning outcome
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::test_fixtures::{add_vote, query};
+    use crate::state::ResolutionResult;
+
+    fn outcome(value: &str) -> ResolutionResult {
+        ResolutionResult::Outcome(value.to_string())
+    }
+
+    #[test]
+    fn test_sparse_plurality_winner_does_not_resolve() {
+        let mut q = query(&["Yes", "No"]);
+        q.min_votes_for_winner = Some(2);
+        add_vote(&mut q, 2, "Yes");
+
+        let result = ResolutionResult::from_majority(q.votes.values());
+        assert_eq!(result, outcome("Yes"));
+
+        let err = q.check_winner_votes(&result).unwrap_err();
+        assert!(err.contains("Winning outcome 'Yes' received 1 vote(s), 2 required"));
+    }
+
+    #[test]
+    fn test_plurality_winner_below_absolute_minimum() {
+        let mut q = query(&["A", "B", "C"]);
+        q.min_votes_for_winner = Some(3);
+        add_vote(&mut q, 2, "A");
+        add_vote(&mut q, 3, "A");
+        add_vote(&mut q, 4, "B");
+        add_vote(&mut q, 5, "C");
+
+        assert!(q.check_winner_votes(&outcome("A")).is_err());
+    }
+
+    #[test]
+    fn test_winner_meeting_minimum_resolves() {
+        let mut q = query(&["Yes", "No"]);
+        q.min_votes_for_winner = Some(2);
+        add_vote(&mut q, 2, "Yes");
+        add_vote(&mut q, 3, "Yes");
+        add_vote(&mut q, 4, "No");
+
+        assert!(q.check_winner_votes(&outcome("Yes")).is_ok());
+    }
+
+    #[test]
+    fn test_no_requirement_or_non_outcome_result_passes() {
+        let mut q = query(&["Yes", "No"]);
+        add_vote(&mut q, 2, "Yes");
+        assert!(q.check_winner_votes(&outcome("Yes")).is_ok());
+
+        q.min_votes_for_winner = Some(5);
+        assert!(q.check_winner_votes(&ResolutionResult::NoConsensus).is_ok());
+        assert!(q.check_winner_votes(&ResolutionResult::NumericMedian(1.0)).is_ok());
+    }
+}
@@ -16,6 +16,8 @@ async fn test_register_voter_integration() {
         stake,
         name,
         metadata_url,
+        referral: None,
+        encrypted_metadata: None,
     };
     
     // Serialize the operation
@@ -13,6 +13,7 @@ async fn test_register_voter_for_operation() {
         stake,
         name: Some("Alice".to_string()),
         metadata_url: None,
+        referral: None,
     };
     
     // Verify operation can be created
@@ -0,0 +1,13 @@
+// Integration test for configuring the token application
+// (the deregister/withdraw flow is covered by token_withdrawal_tests)
+
+use oracle_registry_v2::Operation;
+
+#[test]
+fn test_set_token_application_operation() {
+    let token_app_id = "0".repeat(64).parse().expect("valid application ID");
+    let operation = Operation::SetTokenApplication { token_app_id };
+
+    let serialized = serde_json::to_string(&operation).unwrap();
+    assert!(serialized.contains("SetTokenApplication"));
+}
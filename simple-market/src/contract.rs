@@ -466,6 +466,18 @@ impl SimpleMarketContract {
             panic!("Market already resolved");
         }
         
+        // Voters disagreed: cancel the market instead of paying out an outcome
+        if result == oracle_registry_v2::state::NO_CONSENSUS_OUTCOME {
+            market.status = MarketStatus::Cancelled;
+            market.query_id = Some(query_id);
+            market.resolved_at = Some(resolved_at);
+            self.state.markets.insert(&market_id, market)
+                .expect("Failed to update market");
+            
+            eprintln!("⚠️ Market {} cancelled: oracle reached no consensus", market_id);
+            return;
+        }
+        
         // Update market status
         market.status = MarketStatus::Resolved;
         market.query_id = Some(query_id);
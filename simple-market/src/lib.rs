@@ -69,6 +69,10 @@ pub enum Operation {
 }
 
 /// Cross-chain messages
+/// 
+/// BCS-encoded with the variant index as the tag: only append new variants,
+/// never reorder or change existing ones, or in-flight messages from an older
+/// version will be misread.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Message {
     /// Market -> Registry: Request query creation for expired market
@@ -184,3 +188,65 @@ pub enum ClaimStatus {
     Pending,   // Not yet claimed
     Claimed,   // Payout claimed
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(id: u8) -> ChainId {
+        format!("{:064x}", id).parse().expect("valid chain ID")
+    }
+
+    /// One message per variant, in declaration order
+    fn sample_messages() -> Vec<Message> {
+        vec![
+            Message::CreateQueryFromMarket {
+                market_id: 4,
+                question: "Will it rain?".to_string(),
+                outcomes: vec!["Yes".to_string(), "No".to_string()],
+                deadline: Timestamp::from(3_000_000),
+                callback_chain: chain(3),
+                callback_data: 4u64.to_le_bytes().to_vec(),
+            },
+            Message::QueryResolutionCallback {
+                query_id: 7,
+                resolved_outcome: "Yes".to_string(),
+                resolved_at: Timestamp::from(3_000_000),
+                callback_data: 4u64.to_le_bytes().to_vec(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_message_tags_pinned_and_round_trip() {
+        for (index, message) in sample_messages().into_iter().enumerate() {
+            let bytes = bcs::to_bytes(&message).unwrap();
+            assert_eq!(bytes[0] as usize, index);
+
+            let decoded: Message = bcs::from_bytes(&bytes).unwrap();
+            assert_eq!(bcs::to_bytes(&decoded).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_old_format_callback_still_deserializes() {
+        // Encoded by hand: tag, query_id, outcome, resolved_at, callback_data
+        let mut bytes = vec![1u8];
+        bytes.extend_from_slice(&7u64.to_le_bytes());
+        bytes.push(3);
+        bytes.extend_from_slice(b"Yes");
+        bytes.extend_from_slice(&3_000_000u64.to_le_bytes());
+        bytes.push(8);
+        bytes.extend_from_slice(&4u64.to_le_bytes());
+
+        match bcs::from_bytes::<Message>(&bytes).unwrap() {
+            Message::QueryResolutionCallback { query_id, resolved_outcome, resolved_at, callback_data } => {
+                assert_eq!(query_id, 7);
+                assert_eq!(resolved_outcome, "Yes");
+                assert_eq!(resolved_at, Timestamp::from(3_000_000));
+                assert_eq!(callback_data, 4u64.to_le_bytes().to_vec());
+            }
+            other => panic!("Expected QueryResolutionCallback, got {:?}", other),
+        }
+    }
+}